@@ -0,0 +1,144 @@
+//! Commands related to closing and reopening accounting periods
+use chrono::{Local, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::core::provided_ports::{
+    AccountingPeriodDTO, AccountingPeriodUsecase, ClosePeriodCommand, PeriodAuditEntryDTO,
+    ReopenPeriodCommand,
+};
+use crate::core::stocker::Stocker;
+
+/// Data of accounting period
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountingPeriodData {
+    id: String,
+    name: String,
+    period_start: i64,
+    period_end: i64,
+    closed: bool,
+    /// Closing and reopening, the oldest first
+    audit_trail: Vec<PeriodAuditEntryData>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodAuditEntryData {
+    /// `close` or `reopen`
+    operation: String,
+    performed_at: i64,
+    note: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloseAccountingPeriodData {
+    name: String,
+    /// Any time on the first day, the period starts at the beginning of that day
+    first_date: i64,
+    /// Any time on the last day, the period ends at the end of that day
+    last_date: i64,
+    #[serde(default)]
+    note: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReopenAccountingPeriodData {
+    id: String,
+    #[serde(default)]
+    note: Option<String>,
+}
+
+/// Converts an accounting period DTO into its command data
+fn accounting_period_data(period: AccountingPeriodDTO) -> AccountingPeriodData {
+    AccountingPeriodData {
+        id: period.id,
+        name: period.name,
+        period_start: period.period_start,
+        period_end: period.period_end,
+        closed: period.closed,
+        audit_trail: period
+            .audit_trail
+            .into_iter()
+            .map(audit_entry_data)
+            .collect(),
+    }
+}
+
+fn audit_entry_data(entry: PeriodAuditEntryDTO) -> PeriodAuditEntryData {
+    PeriodAuditEntryData {
+        operation: entry.operation,
+        performed_at: entry.performed_at,
+        note: entry.note,
+    }
+}
+
+/// Returns the timestamp of `time` on the local date of `timestamp`
+fn local_timestamp_on(timestamp: i64, time: NaiveTime) -> Result<i64, String> {
+    let date = Local
+        .timestamp_millis_opt(timestamp)
+        .single()
+        .ok_or("Invalid date".to_string())?
+        .date_naive();
+
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|datetime| datetime.timestamp_millis())
+        .ok_or("Invalid date".to_string())
+}
+
+#[tauri::command]
+pub fn list_all_accounting_periods(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<AccountingPeriodData>, String> {
+    let periods = app
+        .accounting_period_usecase()
+        .list()
+        .map_err(|e| e.to_string())?;
+
+    Ok(periods.into_iter().map(accounting_period_data).collect())
+}
+
+/// Command to close the accounting period from the first to the last date, both included
+#[tauri::command]
+pub fn close_accounting_period(
+    app: tauri::State<Stocker>,
+    command: CloseAccountingPeriodData,
+) -> Result<AccountingPeriodData, String> {
+    let period_start = local_timestamp_on(command.first_date, NaiveTime::MIN)?;
+    let period_end = local_timestamp_on(
+        command.last_date,
+        NaiveTime::from_hms_milli_opt(23, 59, 59, 999).unwrap(),
+    )?;
+
+    let period = app
+        .accounting_period_usecase()
+        .close(ClosePeriodCommand {
+            name: command.name,
+            period_start,
+            period_end,
+            closed_at: Local::now().timestamp_millis(),
+            note: command.note,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(accounting_period_data(period))
+}
+
+#[tauri::command]
+pub fn reopen_accounting_period(
+    app: tauri::State<Stocker>,
+    command: ReopenAccountingPeriodData,
+) -> Result<(), String> {
+    app.accounting_period_usecase()
+        .reopen(ReopenPeriodCommand {
+            period_id: command.id,
+            reopened_at: Local::now().timestamp_millis(),
+            note: command.note,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+//! Commands related to the audit log of changes
+use serde::{Deserialize, Serialize};
+
+use crate::core::provided_ports::{AuditEntryDTO, AuditUsecase, SearchAuditLogQuery};
+use crate::core::stocker::Stocker;
+
+/// Data of audit log entry
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntryData {
+    id: String,
+    /// `supplier`, `supply`, `journal` or `stocktaking`
+    entity: String,
+    entity_id: String,
+    /// `register`, `update`, `record`, `edit`, `delete`, `restore` or `purge`
+    operation: String,
+    logged_at: i64,
+    operator: Option<String>,
+    /// The entity before the change, none if it did not exist
+    before: Option<serde_json::Value>,
+    /// The entity after the change, none if it no longer exists
+    after: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogQuery {
+    #[serde(default)]
+    entity: Option<String>,
+    #[serde(default)]
+    entity_id: Option<String>,
+    #[serde(default)]
+    period_start: Option<i64>,
+    #[serde(default)]
+    period_end: Option<i64>,
+    #[serde(default)]
+    operator: Option<String>,
+}
+
+/// Converts an audit log entry DTO into its command data
+fn audit_entry_data(entry: AuditEntryDTO) -> Result<AuditEntryData, String> {
+    let snapshot = |json: Option<String>| {
+        json.map(|json| serde_json::from_str(&json))
+            .transpose()
+            .map_err(|e| format!("Failed to parse snapshot: {}", e))
+    };
+
+    Ok(AuditEntryData {
+        id: entry.id,
+        entity: entry.entity,
+        entity_id: entry.entity_id,
+        operation: entry.operation,
+        logged_at: entry.logged_at,
+        operator: entry.operator,
+        before: snapshot(entry.before)?,
+        after: snapshot(entry.after)?,
+    })
+}
+
+/// Command to search the audit log by entity, date range and operator, the latest first
+#[tauri::command]
+pub fn search_audit_log(
+    app: tauri::State<Stocker>,
+    query: AuditLogQuery,
+) -> Result<Vec<AuditEntryData>, String> {
+    let entries = app
+        .audit_usecase()
+        .search(SearchAuditLogQuery {
+            entity: query.entity,
+            entity_id: query.entity_id,
+            period_start: query.period_start,
+            period_end: query.period_end,
+            operator: query.operator,
+        })
+        .map_err(|e| e.to_string())?;
+
+    entries.into_iter().map(audit_entry_data).collect()
+}
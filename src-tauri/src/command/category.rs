@@ -0,0 +1,156 @@
+//! Commands related to supply categories
+use serde::{Deserialize, Serialize};
+
+use crate::command::{trashed_data, TrashedData};
+use crate::core::provided_ports::{
+    CategoryDTO, CategoryTotalDTO, CategoryUsecase, RegisterCategoryCommand, UpdateCategoryCommand,
+};
+use crate::core::stocker::Stocker;
+
+/// Data of category
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryData {
+    pub id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+}
+
+/// Total of the lines whose supplies are in a category or its descendants
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryTotalData {
+    /// none for supplies not categorized
+    pub category_id: Option<String>,
+    pub category_name: Option<String>,
+    pub parent_id: Option<String>,
+    pub total_price: f64,
+}
+
+/// Command to add a new category
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddCategoryCommand {
+    pub category_name: String,
+    /// top level category if not provided
+    #[serde(default)]
+    pub parent_id: Option<String>,
+}
+
+/// Command to update a category
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditCategoryCommand {
+    pub category_id: String,
+    pub category_name: String,
+    /// top level category if not provided
+    #[serde(default)]
+    pub parent_id: Option<String>,
+}
+
+/// Converts a category DTO into its data.
+fn category_data(category: CategoryDTO) -> CategoryData {
+    CategoryData {
+        id: category.id,
+        name: category.name,
+        parent_id: category.parent_id,
+    }
+}
+
+/// Converts a category total DTO into its data.
+pub(crate) fn category_total_data(total: CategoryTotalDTO) -> CategoryTotalData {
+    CategoryTotalData {
+        category_id: total.category_id,
+        category_name: total.category_name,
+        parent_id: total.parent_id,
+        total_price: total.total_price,
+    }
+}
+
+/// Command to list all categories, parents before their children
+#[tauri::command]
+pub fn list_all_categories(app: tauri::State<Stocker>) -> Result<Vec<CategoryData>, String> {
+    let categories = app.category_usecase().list().map_err(|e| e.to_string())?;
+
+    Ok(categories.into_iter().map(category_data).collect())
+}
+
+/// Command to register a new category
+#[tauri::command]
+pub fn register_category(
+    app: tauri::State<Stocker>,
+    command: AddCategoryCommand,
+) -> Result<CategoryData, String> {
+    let category = app
+        .category_usecase()
+        .register(RegisterCategoryCommand {
+            category_name: command.category_name,
+            parent_id: command.parent_id,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(category_data(category))
+}
+
+/// Command to rename or move a category
+#[tauri::command]
+pub fn update_category(
+    app: tauri::State<Stocker>,
+    command: EditCategoryCommand,
+) -> Result<(), String> {
+    app.category_usecase()
+        .update(UpdateCategoryCommand {
+            category_id: command.category_id,
+            category_name: command.category_name,
+            parent_id: command.parent_id,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a category
+#[tauri::command]
+pub fn delete_category(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.category_usecase()
+        .delete(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to list deleted categories in the trash
+#[tauri::command]
+pub fn list_deleted_categories(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<TrashedData<CategoryData>>, String> {
+    let categories = app
+        .category_usecase()
+        .list_deleted()
+        .map_err(|e| e.to_string())?;
+
+    Ok(categories
+        .into_iter()
+        .map(|trashed| trashed_data(trashed, category_data))
+        .collect())
+}
+
+/// Command to restore a deleted category from the trash
+#[tauri::command]
+pub fn restore_category(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.category_usecase()
+        .restore(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a category in the trash permanently
+#[tauri::command]
+pub fn purge_category(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.category_usecase()
+        .purge(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
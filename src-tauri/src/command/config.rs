@@ -4,7 +4,14 @@ use std::path::PathBuf;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_log::log::info;
 
-use crate::config::{Config, Theme, CONFIG_FILE_NAME};
+use crate::config::{
+    Config, PriceAlert, Rounding, RoundingMode, Theme, ValuationMethod, CONFIG_FILE_NAME,
+};
+use crate::core::domain::values::audit::OperatorName;
+use crate::core::domain::values::decimal::{self, RoundingPolicy};
+use crate::core::domain::values::price::PriceAlertRule;
+use crate::core::domain::values::valuation;
+use crate::core::stocker::Stocker;
 
 /// Initialize configuration file if it does not exist
 pub fn init_config(app: &AppHandle) -> Result<PathBuf, String> {
@@ -67,3 +74,199 @@ pub fn set_theme(app: AppHandle, theme: Theme) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Read configuration file
+pub fn read_config(app: &AppHandle) -> Result<Config, String> {
+    let config_path = init_config(app)?;
+
+    let config_data = fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read config file: {}", e))?;
+
+    serde_json::from_str(&config_data).map_err(|e| format!("Failed to parse config file: {}", e))
+}
+
+/// Write configuration file
+pub fn write_config(app: &AppHandle, config: &Config) -> Result<(), String> {
+    let config_path = init_config(app)?;
+
+    let config_data = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+
+    fs::write(&config_path, config_data).map_err(|e| format!("Failed to write config file: {}", e))
+}
+
+/// Convert rounding configuration into the core rounding policy
+pub fn rounding_policy(rounding: &Rounding) -> Result<RoundingPolicy, String> {
+    RoundingPolicy::new(
+        rounding_mode(rounding.mode),
+        rounding_mode(rounding.tax_mode),
+        rounding.digits,
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn rounding_mode(mode: RoundingMode) -> decimal::RoundingMode {
+    match mode {
+        RoundingMode::Down => decimal::RoundingMode::Down,
+        RoundingMode::Up => decimal::RoundingMode::Up,
+        RoundingMode::HalfUp => decimal::RoundingMode::HalfUp,
+        RoundingMode::HalfEven => decimal::RoundingMode::HalfEven,
+    }
+}
+
+/// Command to get the rounding of computed amounts
+#[tauri::command]
+pub fn get_rounding(app: AppHandle) -> Result<Rounding, String> {
+    let config = read_config(&app)?;
+
+    Ok(config.rounding)
+}
+
+/// Command to set the rounding of computed amounts
+#[tauri::command]
+pub fn set_rounding(
+    app: AppHandle,
+    state: tauri::State<Stocker>,
+    rounding: Rounding,
+) -> Result<(), String> {
+    let policy = rounding_policy(&rounding)?;
+
+    let mut config = read_config(&app)?;
+
+    config.rounding = rounding;
+
+    write_config(&app, &config)?;
+
+    state.set_rounding_policy(policy);
+
+    Ok(())
+}
+
+/// Convert valuation method configuration into the core valuation method
+pub fn valuation_method(method: ValuationMethod) -> valuation::ValuationMethod {
+    match method {
+        ValuationMethod::LastPurchasePrice => valuation::ValuationMethod::LastPurchasePrice,
+        ValuationMethod::MovingAverage => valuation::ValuationMethod::MovingAverage,
+        ValuationMethod::PeriodicAverage => valuation::ValuationMethod::PeriodicAverage,
+        ValuationMethod::Fifo => valuation::ValuationMethod::Fifo,
+    }
+}
+
+/// Command to get the method deriving stocktaking unit prices
+#[tauri::command]
+pub fn get_valuation_method(app: AppHandle) -> Result<ValuationMethod, String> {
+    let config = read_config(&app)?;
+
+    Ok(config.valuation_method)
+}
+
+/// Command to set the method deriving stocktaking unit prices
+#[tauri::command]
+pub fn set_valuation_method(
+    app: AppHandle,
+    state: tauri::State<Stocker>,
+    valuation_method: ValuationMethod,
+) -> Result<(), String> {
+    let mut config = read_config(&app)?;
+
+    config.valuation_method = valuation_method;
+
+    write_config(&app, &config)?;
+
+    state.set_valuation_method(self::valuation_method(valuation_method));
+
+    Ok(())
+}
+
+/// Convert price alert configuration into the core price alert rule
+pub fn price_alert_rule(price_alert: &PriceAlert) -> Result<PriceAlertRule, String> {
+    PriceAlertRule::new(
+        price_alert.threshold_percent,
+        price_alert.sample_size as usize,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Command to get the warning on purchase prices
+#[tauri::command]
+pub fn get_price_alert(app: AppHandle) -> Result<PriceAlert, String> {
+    let config = read_config(&app)?;
+
+    Ok(config.price_alert)
+}
+
+/// Command to set the warning on purchase prices
+#[tauri::command]
+pub fn set_price_alert(
+    app: AppHandle,
+    state: tauri::State<Stocker>,
+    price_alert: PriceAlert,
+) -> Result<(), String> {
+    let rule = price_alert_rule(&price_alert)?;
+
+    let mut config = read_config(&app)?;
+
+    config.price_alert = price_alert;
+
+    write_config(&app, &config)?;
+
+    state.set_price_alert_rule(rule);
+
+    Ok(())
+}
+
+/// Command to get the days deleted records are kept in the trash
+#[tauri::command]
+pub fn get_trash_retention_days(app: AppHandle) -> Result<u32, String> {
+    let config = read_config(&app)?;
+
+    Ok(config.trash_retention_days)
+}
+
+/// Command to set the days deleted records are kept in the trash, 0 keeping them forever
+#[tauri::command]
+pub fn set_trash_retention_days(app: AppHandle, retention_days: u32) -> Result<(), String> {
+    let mut config = read_config(&app)?;
+
+    config.trash_retention_days = retention_days;
+
+    write_config(&app, &config)?;
+
+    Ok(())
+}
+
+/// Converts a configured operator name, none if blank
+pub fn operator(name: Option<&str>) -> Result<Option<OperatorName>, String> {
+    name.filter(|name| !name.trim().is_empty())
+        .map(OperatorName::new)
+        .transpose()
+        .map_err(|e| e.to_string())
+}
+
+/// Command to get the name of the person operating the application
+#[tauri::command]
+pub fn get_operator_name(app: AppHandle) -> Result<Option<String>, String> {
+    let config = read_config(&app)?;
+
+    Ok(config.operator_name)
+}
+
+/// Command to set the name of the person operating the application, logged with the changes made
+#[tauri::command]
+pub fn set_operator_name(
+    app: AppHandle,
+    state: tauri::State<Stocker>,
+    operator_name: Option<String>,
+) -> Result<(), String> {
+    let operator = operator(operator_name.as_deref())?;
+
+    let mut config = read_config(&app)?;
+
+    config.operator_name = operator.as_ref().map(|operator| operator.to_string());
+
+    write_config(&app, &config)?;
+
+    state.set_operator(operator);
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+//! Commands related to consumptions
+use serde::{Deserialize, Serialize};
+
+use crate::command::{trashed_data, TrashedData};
+use crate::core::provided_ports::{
+    self, ConsumptionDTO, ConsumptionRecordDTO, ConsumptionUsecase, SearchConsumptionsQuery,
+};
+use crate::core::stocker::Stocker;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsumptionData {
+    id: String,
+    consumed_date: i64,
+    records: Vec<ConsumptionRecordData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsumptionRecordData {
+    supply_id: String,
+    supply_name: String,
+    unit_name: String,
+    quantity: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordConsumptionCommand {
+    consumed_date: i64,
+    records: Vec<ConsumptionRecordData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsumptionQuery {
+    period_start: Option<i64>,
+    period_end: Option<i64>,
+}
+
+fn consumption_data(consumption: ConsumptionDTO) -> ConsumptionData {
+    ConsumptionData {
+        id: consumption.id,
+        consumed_date: consumption.consumed_date,
+        records: consumption
+            .records
+            .into_iter()
+            .map(|record| ConsumptionRecordData {
+                supply_id: record.supply_id,
+                supply_name: record.supply_name,
+                unit_name: record.unit_name,
+                quantity: record.quantity,
+            })
+            .collect(),
+    }
+}
+
+/// Command to list all consumptions
+#[tauri::command]
+pub fn list_all_consumptions(app: tauri::State<Stocker>) -> Result<Vec<ConsumptionData>, String> {
+    let consumptions = app
+        .consumption_usecase()
+        .list()
+        .map_err(|e| e.to_string())?;
+
+    Ok(consumptions.into_iter().map(consumption_data).collect())
+}
+
+/// Command to search consumptions
+#[tauri::command]
+pub fn search_consumptions(
+    app: tauri::State<Stocker>,
+    query: ConsumptionQuery,
+) -> Result<Vec<ConsumptionData>, String> {
+    let consumptions = app
+        .consumption_usecase()
+        .search(SearchConsumptionsQuery {
+            period_start: query.period_start,
+            period_end: query.period_end,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(consumptions.into_iter().map(consumption_data).collect())
+}
+
+/// Command to record a new consumption
+#[tauri::command]
+pub fn record_consumption(
+    app: tauri::State<Stocker>,
+    command: RecordConsumptionCommand,
+) -> Result<ConsumptionData, String> {
+    let consumption = app
+        .consumption_usecase()
+        .record(provided_ports::RecordConsumptionCommand {
+            consumed_date: command.consumed_date,
+            records: command
+                .records
+                .into_iter()
+                .map(|record| ConsumptionRecordDTO {
+                    supply_id: record.supply_id,
+                    supply_name: record.supply_name,
+                    unit_name: record.unit_name,
+                    quantity: record.quantity,
+                })
+                .collect(),
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(consumption_data(consumption))
+}
+
+/// Command to delete a consumption by ID
+#[tauri::command]
+pub fn delete_consumption(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.consumption_usecase()
+        .delete(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to list deleted consumptions in the trash
+#[tauri::command]
+pub fn list_deleted_consumptions(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<TrashedData<ConsumptionData>>, String> {
+    let consumptions = app
+        .consumption_usecase()
+        .list_deleted()
+        .map_err(|e| e.to_string())?;
+
+    Ok(consumptions
+        .into_iter()
+        .map(|trashed| trashed_data(trashed, consumption_data))
+        .collect())
+}
+
+/// Command to restore a deleted consumption from the trash
+#[tauri::command]
+pub fn restore_consumption(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.consumption_usecase()
+        .restore(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a consumption in the trash permanently
+#[tauri::command]
+pub fn purge_consumption(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.consumption_usecase()
+        .purge(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
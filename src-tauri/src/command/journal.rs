@@ -1,7 +1,13 @@
 use chrono::{Local, TimeZone};
 use serde::{Deserialize, Serialize};
 
-use crate::core::provided_ports::{self, JournalRecordDTO, JournalUsecase, SearchJournalsQuery};
+use crate::command::{
+    category_total_data, line_diff_data, revision_data, stock_quantity_data, trashed_data,
+    CategoryTotalData, LineDiffData, RevisionData, StockQuantityData, TrashedData,
+};
+use crate::core::provided_ports::{
+    self, JournalDTO, JournalRecordDTO, JournalUsecase, PriceHistoryQuery, SearchJournalsQuery,
+};
 use crate::core::stocker::Stocker;
 
 #[derive(Debug, Clone, Serialize)]
@@ -10,7 +16,61 @@ pub struct JournalData {
     id: String,
     entry_date: i64,
     total_price: f64,
+    net_price: f64,
+    tax_price: f64,
+    gross_price: f64,
+    deductible_tax_price: f64,
+    non_deductible_tax_price: f64,
+    tax_summaries: Vec<TaxSummaryData>,
     records: Vec<JournalRecordData>,
+    warnings: Vec<PriceWarningData>,
+}
+
+/// Warning on a line priced far from the recent purchases
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceWarningData {
+    line: usize,
+    supply_id: String,
+    supply_name: String,
+    supplier_id: String,
+    unit_name: String,
+    unit_price: f64,
+    average_price: f64,
+    deviation_percent: f64,
+}
+
+/// Unit prices of a supply purchased from a supplier
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceHistoryData {
+    supply_id: String,
+    supply_name: String,
+    supplier_id: String,
+    supplier_name: String,
+    unit_name: String,
+    prices: Vec<PricePointData>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PricePointData {
+    journal_id: String,
+    entry_date: i64,
+    unit_price: f64,
+}
+
+/// Net, tax and gross amounts of a tax category
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxSummaryData {
+    tax_category: String,
+    tax_rate: i64,
+    net_price: f64,
+    tax_price: f64,
+    gross_price: f64,
+    deductible_tax_price: f64,
+    non_deductible_tax_price: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +84,23 @@ pub struct JournalRecordData {
     unit_price: f64,
     total_price: f64,
     quantity: f64,
+    /// `standard`, `reduced` or `exempt`, defaults to the tax category of the supply
+    #[serde(default)]
+    tax_category: Option<String>,
+    /// Whether the prices include consumption tax, defaults to true
+    #[serde(default)]
+    tax_included: Option<bool>,
+    /// Consumption tax allocated to the line, computed by the core
+    #[serde(default)]
+    tax_price: f64,
+    /// Whether the supplier was a qualified invoice issuer, defaults to the current registration
+    #[serde(default)]
+    invoice_registered: Option<bool>,
+    #[serde(default)]
+    lot_number: Option<String>,
+    /// Best-before or expiry date of the received goods
+    #[serde(default)]
+    expiry_date: Option<i64>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -49,6 +126,22 @@ pub struct JournalQuery {
     period_end: Option<i64>,
     supplier_name: Option<String>,
     supply_name: Option<String>,
+    /// lines of supplies in the category or its descendants
+    #[serde(default)]
+    category_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceHistoryQueryData {
+    #[serde(default)]
+    supply_id: Option<String>,
+    #[serde(default)]
+    supplier_id: Option<String>,
+    #[serde(default)]
+    period_start: Option<i64>,
+    #[serde(default)]
+    period_end: Option<i64>,
 }
 
 #[tauri::command]
@@ -57,25 +150,7 @@ pub fn list_all_journals(app: tauri::State<Stocker>) -> Result<Vec<JournalData>,
 
     let journals = jorunals
         .into_iter()
-        .map(|journal| JournalData {
-            id: journal.id,
-            entry_date: journal.entry_date,
-            total_price: journal.total_price,
-            records: journal
-                .records
-                .into_iter()
-                .map(|record| JournalRecordData {
-                    supply_id: record.supply_id,
-                    supply_name: record.supply_name,
-                    supplier_id: record.supplier_id,
-                    supplier_name: record.supplier_name,
-                    unit_name: record.unit_name,
-                    unit_price: record.unit_price,
-                    quantity: record.quantity,
-                    total_price: record.total_price,
-                })
-                .collect::<Vec<JournalRecordData>>(),
-        })
+        .map(journal_data)
         .collect::<Vec<JournalData>>();
 
     Ok(journals)
@@ -88,27 +163,7 @@ pub fn get_journal_by_id(
 ) -> Result<Option<JournalData>, String> {
     let journal = app.journal_usecase().get(&id).map_err(|e| e.to_string())?;
 
-    let journal = journal.and_then(|journal| {
-        Some(JournalData {
-            id: journal.id,
-            entry_date: journal.entry_date,
-            total_price: journal.total_price,
-            records: journal
-                .records
-                .into_iter()
-                .map(|record| JournalRecordData {
-                    supply_id: record.supply_id,
-                    supply_name: record.supply_name,
-                    supplier_id: record.supplier_id,
-                    supplier_name: record.supplier_name,
-                    unit_name: record.unit_name,
-                    unit_price: record.unit_price,
-                    quantity: record.quantity,
-                    total_price: record.total_price,
-                })
-                .collect::<Vec<JournalRecordData>>(),
-        })
-    });
+    let journal = journal.map(journal_data);
 
     Ok(journal)
 }
@@ -126,39 +181,12 @@ pub fn record_journal(
             records: command
                 .records
                 .into_iter()
-                .map(|record| JournalRecordDTO {
-                    supply_id: record.supply_id,
-                    supply_name: record.supply_name,
-                    supplier_id: record.supplier_id,
-                    supplier_name: record.supplier_name,
-                    unit_name: record.unit_name,
-                    unit_price: record.unit_price,
-                    quantity: record.quantity,
-                    total_price: record.total_price,
-                })
+                .map(record_dto)
                 .collect::<Vec<JournalRecordDTO>>(),
         })
         .map_err(|e| e.to_string())?;
 
-    let journal = JournalData {
-        id: journal.id,
-        entry_date: journal.entry_date,
-        total_price: journal.total_price,
-        records: journal
-            .records
-            .into_iter()
-            .map(|record| JournalRecordData {
-                supply_id: record.supply_id,
-                supply_name: record.supply_name,
-                supplier_id: record.supplier_id,
-                supplier_name: record.supplier_name,
-                unit_name: record.unit_name,
-                unit_price: record.unit_price,
-                quantity: record.quantity,
-                total_price: record.total_price,
-            })
-            .collect::<Vec<JournalRecordData>>(),
-    };
+    let journal = journal_data(journal);
 
     Ok(journal)
 }
@@ -191,30 +219,11 @@ pub fn get_journal_at(
             period_end: Some(end),
             supplier_name: None,
             supply_name: None,
+            category_id: None,
         })
         .map_err(|e| e.to_string())?;
 
-    let journal = journals.first().cloned().and_then(|journal| {
-        Some(JournalData {
-            id: journal.id,
-            entry_date: journal.entry_date,
-            total_price: journal.total_price,
-            records: journal
-                .records
-                .into_iter()
-                .map(|record| JournalRecordData {
-                    supply_id: record.supply_id,
-                    supply_name: record.supply_name,
-                    supplier_id: record.supplier_id,
-                    supplier_name: record.supplier_name,
-                    unit_name: record.unit_name,
-                    unit_price: record.unit_price,
-                    quantity: record.quantity,
-                    total_price: record.total_price,
-                })
-                .collect::<Vec<JournalRecordData>>(),
-        })
-    });
+    let journal = journals.first().cloned().map(journal_data);
 
     Ok(journal)
 }
@@ -231,16 +240,7 @@ pub fn update_journal(
             records: command
                 .records
                 .into_iter()
-                .map(|record| JournalRecordDTO {
-                    supply_id: record.supply_id,
-                    supply_name: record.supply_name,
-                    supplier_id: record.supplier_id,
-                    supplier_name: record.supplier_name,
-                    unit_name: record.unit_name,
-                    unit_price: record.unit_price,
-                    quantity: record.quantity,
-                    total_price: record.total_price,
-                })
+                .map(record_dto)
                 .collect::<Vec<JournalRecordDTO>>(),
         })
         .map_err(|e| e.to_string())?;
@@ -260,33 +260,95 @@ pub fn search_journals(
             period_end: query.period_end,
             supply_name: query.supply_name,
             supplier_name: query.supplier_name,
+            category_id: query.category_id,
         })
         .map_err(|e| e.to_string())?;
 
     let journals = journals
         .into_iter()
-        .map(|journal| JournalData {
-            id: journal.id,
-            entry_date: journal.entry_date,
-            total_price: journal.total_price,
-            records: journal
-                .records
+        .map(journal_data)
+        .collect::<Vec<JournalData>>();
+
+    Ok(journals)
+}
+
+/// Command to sum quantities of journal lines per supply in its stock unit
+#[tauri::command]
+pub fn aggregate_journal_quantities(
+    app: tauri::State<Stocker>,
+    query: JournalQuery,
+) -> Result<Vec<StockQuantityData>, String> {
+    let quantities = app
+        .journal_usecase()
+        .aggregate_quantities(SearchJournalsQuery {
+            period_start: query.period_start,
+            period_end: query.period_end,
+            supply_name: query.supply_name,
+            supplier_name: query.supplier_name,
+            category_id: query.category_id,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(quantities.into_iter().map(stock_quantity_data).collect())
+}
+
+/// Command to sum purchased values per category
+#[tauri::command]
+pub fn get_journal_category_totals(
+    app: tauri::State<Stocker>,
+    query: JournalQuery,
+) -> Result<Vec<CategoryTotalData>, String> {
+    let totals = app
+        .journal_usecase()
+        .totals_by_category(SearchJournalsQuery {
+            period_start: query.period_start,
+            period_end: query.period_end,
+            supply_name: query.supply_name,
+            supplier_name: query.supplier_name,
+            category_id: query.category_id,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(totals.into_iter().map(category_total_data).collect())
+}
+
+/// Command to list unit prices paid per supply and supplier
+#[tauri::command]
+pub fn get_price_history(
+    app: tauri::State<Stocker>,
+    query: PriceHistoryQueryData,
+) -> Result<Vec<PriceHistoryData>, String> {
+    let histories = app
+        .journal_usecase()
+        .price_history(PriceHistoryQuery {
+            supply_id: query.supply_id,
+            supplier_id: query.supplier_id,
+            period_start: query.period_start,
+            period_end: query.period_end,
+        })
+        .map_err(|e| e.to_string())?;
+
+    let histories = histories
+        .into_iter()
+        .map(|history| PriceHistoryData {
+            supply_id: history.supply_id,
+            supply_name: history.supply_name,
+            supplier_id: history.supplier_id,
+            supplier_name: history.supplier_name,
+            unit_name: history.unit_name,
+            prices: history
+                .prices
                 .into_iter()
-                .map(|record| JournalRecordData {
-                    supply_id: record.supply_id,
-                    supply_name: record.supply_name,
-                    supplier_id: record.supplier_id,
-                    supplier_name: record.supplier_name,
-                    unit_name: record.unit_name,
-                    unit_price: record.unit_price,
-                    quantity: record.quantity,
-                    total_price: record.total_price,
+                .map(|point| PricePointData {
+                    journal_id: point.journal_id,
+                    entry_date: point.entry_date,
+                    unit_price: point.unit_price,
                 })
-                .collect::<Vec<JournalRecordData>>(),
+                .collect(),
         })
-        .collect::<Vec<JournalData>>();
+        .collect::<Vec<PriceHistoryData>>();
 
-    Ok(journals)
+    Ok(histories)
 }
 
 #[tauri::command]
@@ -297,3 +359,161 @@ pub fn delete_journal(app: tauri::State<Stocker>, id: String) -> Result<(), Stri
 
     Ok(())
 }
+
+/// Command to list deleted journals in the trash
+#[tauri::command]
+pub fn list_deleted_journals(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<TrashedData<JournalData>>, String> {
+    let journals = app
+        .journal_usecase()
+        .list_deleted()
+        .map_err(|e| e.to_string())?;
+
+    Ok(journals
+        .into_iter()
+        .map(|trashed| trashed_data(trashed, journal_data))
+        .collect())
+}
+
+/// Command to restore a deleted journal from the trash
+#[tauri::command]
+pub fn restore_journal(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.journal_usecase()
+        .restore(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a journal in the trash permanently
+#[tauri::command]
+pub fn purge_journal(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.journal_usecase().purge(id).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to list the versions of a journal, the current one last
+#[tauri::command]
+pub fn list_journal_revisions(
+    app: tauri::State<Stocker>,
+    id: String,
+) -> Result<Vec<RevisionData<JournalData>>, String> {
+    let revisions = app
+        .journal_usecase()
+        .list_revisions(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(revisions
+        .into_iter()
+        .map(|revision| revision_data(revision, journal_data))
+        .collect())
+}
+
+/// Command to compare the lines of two versions of a journal
+#[tauri::command]
+pub fn diff_journal_revisions(
+    app: tauri::State<Stocker>,
+    id: String,
+    from: u32,
+    to: u32,
+) -> Result<Vec<LineDiffData>, String> {
+    let diffs = app
+        .journal_usecase()
+        .diff_revisions(id, from, to)
+        .map_err(|e| e.to_string())?;
+
+    Ok(diffs.into_iter().map(line_diff_data).collect())
+}
+
+/// Command to revert a journal to one of its versions
+#[tauri::command]
+pub fn revert_journal(app: tauri::State<Stocker>, id: String, revision: u32) -> Result<(), String> {
+    app.journal_usecase()
+        .revert(id, revision)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Converts a journal DTO into its command data
+pub(crate) fn journal_data(journal: JournalDTO) -> JournalData {
+    JournalData {
+        id: journal.id,
+        entry_date: journal.entry_date,
+        total_price: journal.total_price,
+        net_price: journal.net_price,
+        tax_price: journal.tax_price,
+        gross_price: journal.gross_price,
+        deductible_tax_price: journal.deductible_tax_price,
+        non_deductible_tax_price: journal.non_deductible_tax_price,
+        tax_summaries: journal
+            .tax_summaries
+            .into_iter()
+            .map(|summary| TaxSummaryData {
+                tax_category: summary.tax_category,
+                tax_rate: summary.tax_rate,
+                net_price: summary.net_price,
+                tax_price: summary.tax_price,
+                gross_price: summary.gross_price,
+                deductible_tax_price: summary.deductible_tax_price,
+                non_deductible_tax_price: summary.non_deductible_tax_price,
+            })
+            .collect::<Vec<TaxSummaryData>>(),
+        records: journal
+            .records
+            .into_iter()
+            .map(|record| JournalRecordData {
+                supply_id: record.supply_id,
+                supply_name: record.supply_name,
+                supplier_id: record.supplier_id,
+                supplier_name: record.supplier_name,
+                unit_name: record.unit_name,
+                unit_price: record.unit_price,
+                quantity: record.quantity,
+                total_price: record.total_price,
+                tax_category: record.tax_category,
+                tax_included: record.tax_included,
+                tax_price: record.tax_price,
+                invoice_registered: record.invoice_registered,
+                lot_number: record.lot_number,
+                expiry_date: record.expiry_date,
+            })
+            .collect::<Vec<JournalRecordData>>(),
+        warnings: journal
+            .warnings
+            .into_iter()
+            .map(|warning| PriceWarningData {
+                line: warning.line,
+                supply_id: warning.supply_id,
+                supply_name: warning.supply_name,
+                supplier_id: warning.supplier_id,
+                unit_name: warning.unit_name,
+                unit_price: warning.unit_price,
+                average_price: warning.average_price,
+                deviation_percent: warning.deviation_percent,
+            })
+            .collect::<Vec<PriceWarningData>>(),
+    }
+}
+
+/// Converts command data of a journal record into its DTO
+fn record_dto(record: JournalRecordData) -> JournalRecordDTO {
+    JournalRecordDTO {
+        supply_id: record.supply_id,
+        supply_name: record.supply_name,
+        supplier_id: record.supplier_id,
+        supplier_name: record.supplier_name,
+        unit_name: record.unit_name,
+        unit_price: record.unit_price,
+        quantity: record.quantity,
+        total_price: record.total_price,
+        tax_category: record.tax_category,
+        tax_included: record.tax_included,
+        tax_price: record.tax_price,
+        invoice_registered: record.invoice_registered,
+        lot_number: record.lot_number,
+        expiry_date: record.expiry_date,
+    }
+}
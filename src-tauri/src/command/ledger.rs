@@ -0,0 +1,174 @@
+//! Commands related to the theoretical stock ledger
+use serde::{Deserialize, Serialize};
+
+use crate::core::provided_ports::{
+    ExpiringLotsQuery, StockLedgerQuery, StockLedgerUsecase, StockVarianceQuery,
+};
+use crate::core::stocker::Stocker;
+
+/// Theoretical quantity of a supply in its stock unit
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StockLedgerData {
+    supply_id: String,
+    supply_name: String,
+    unit_name: String,
+    opening_stocktaking_id: Option<String>,
+    opening_quantity: f64,
+    purchased_quantity: f64,
+    /// received from other stores minus sent to them
+    transferred_quantity: f64,
+    consumed_quantity: f64,
+    quantity: f64,
+}
+
+/// Expected and counted stock of a stocktaking
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StockVarianceReportData {
+    stocktaking_id: String,
+    stocktaking_date: i64,
+    expected_value: f64,
+    counted_value: f64,
+    variance_value: f64,
+    supplies: Vec<SupplyVarianceData>,
+}
+
+/// Expected and counted stock of a supply
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplyVarianceData {
+    supply_id: String,
+    supply_name: String,
+    unit_name: String,
+    expected_quantity: f64,
+    counted_quantity: f64,
+    variance_quantity: f64,
+    unit_price: f64,
+    expected_value: f64,
+    counted_value: f64,
+    variance_value: f64,
+}
+
+/// Lot of a supply on hand expiring soon
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiringLotData {
+    supply_id: String,
+    supply_name: String,
+    lot_number: Option<String>,
+    expiry_date: i64,
+    journal_id: String,
+    received_date: i64,
+    /// Stocktaking the lot was last counted in, none if received since
+    stocktaking_id: Option<String>,
+    unit_name: String,
+    quantity: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StockLedgerTimeQuery {
+    at: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiringLotsTimeQuery {
+    at: i64,
+    /// Lots expiring within this many days, including those already expired
+    within_days: u32,
+}
+
+/// Command to get the theoretical quantity on hand per supply at a time
+#[tauri::command]
+pub fn get_stock_on_hand(
+    app: tauri::State<Stocker>,
+    query: StockLedgerTimeQuery,
+) -> Result<Vec<StockLedgerData>, String> {
+    let lines = app
+        .stock_ledger_usecase()
+        .on_hand(StockLedgerQuery { at: query.at })
+        .map_err(|e| e.to_string())?;
+
+    Ok(lines
+        .into_iter()
+        .map(|line| StockLedgerData {
+            supply_id: line.supply_id,
+            supply_name: line.supply_name,
+            unit_name: line.unit_name,
+            opening_stocktaking_id: line.opening_stocktaking_id,
+            opening_quantity: line.opening_quantity,
+            purchased_quantity: line.purchased_quantity,
+            transferred_quantity: line.transferred_quantity,
+            consumed_quantity: line.consumed_quantity,
+            quantity: line.quantity,
+        })
+        .collect())
+}
+
+/// Command to compare expected and counted stock of a stocktaking
+#[tauri::command]
+pub fn get_stock_variance(
+    app: tauri::State<Stocker>,
+    stocktaking_id: String,
+) -> Result<StockVarianceReportData, String> {
+    let report = app
+        .stock_ledger_usecase()
+        .variance(StockVarianceQuery { stocktaking_id })
+        .map_err(|e| e.to_string())?;
+
+    Ok(StockVarianceReportData {
+        stocktaking_id: report.stocktaking_id,
+        stocktaking_date: report.stocktaken_date,
+        expected_value: report.expected_value,
+        counted_value: report.counted_value,
+        variance_value: report.variance_value,
+        supplies: report
+            .supplies
+            .into_iter()
+            .map(|supply| SupplyVarianceData {
+                supply_id: supply.supply_id,
+                supply_name: supply.supply_name,
+                unit_name: supply.unit_name,
+                expected_quantity: supply.expected_quantity,
+                counted_quantity: supply.counted_quantity,
+                variance_quantity: supply.variance_quantity,
+                unit_price: supply.unit_price,
+                expected_value: supply.expected_value,
+                counted_value: supply.counted_value,
+                variance_value: supply.variance_value,
+            })
+            .collect(),
+    })
+}
+
+/// Command to list lots on hand expiring within days of a time
+#[tauri::command]
+pub fn get_expiring_lots(
+    app: tauri::State<Stocker>,
+    query: ExpiringLotsTimeQuery,
+) -> Result<Vec<ExpiringLotData>, String> {
+    let lots = app
+        .stock_ledger_usecase()
+        .expiring_lots(ExpiringLotsQuery {
+            at: query.at,
+            within_days: query.within_days,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(lots
+        .into_iter()
+        .map(|lot| ExpiringLotData {
+            supply_id: lot.supply_id,
+            supply_name: lot.supply_name,
+            lot_number: lot.lot_number,
+            expiry_date: lot.expiry_date,
+            journal_id: lot.journal_id,
+            received_date: lot.received_date,
+            stocktaking_id: lot.stocktaking_id,
+            unit_name: lot.unit_name,
+            quantity: lot.quantity,
+        })
+        .collect())
+}
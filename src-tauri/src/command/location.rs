@@ -0,0 +1,169 @@
+//! Commands related to storage locations
+use serde::{Deserialize, Serialize};
+
+use crate::command::{trashed_data, TrashedData};
+use crate::core::provided_ports::{
+    PlaceSupplyCommand, RegisterStorageLocationCommand, StorageLocationDTO, StorageLocationUsecase,
+    UpdateStorageLocationCommand,
+};
+use crate::core::stocker::Stocker;
+
+/// Data of storage location
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageLocationData {
+    pub id: String,
+    pub name: String,
+    pub sort_order: u32,
+}
+
+/// Command to add a new storage location
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddStorageLocationCommand {
+    pub location_name: String,
+    #[serde(default)]
+    pub sort_order: u32,
+}
+
+/// Command to update a storage location
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditStorageLocationCommand {
+    pub location_id: String,
+    pub location_name: String,
+    #[serde(default)]
+    pub sort_order: u32,
+}
+
+/// Command to set the default storage location of a supply
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceSupplyData {
+    pub supply_id: String,
+    /// clears the placement if not provided
+    #[serde(default)]
+    pub location_id: Option<String>,
+    /// order within the storage location
+    #[serde(default)]
+    pub sort_order: u32,
+}
+
+/// Converts a storage location DTO into its data.
+fn location_data(location: StorageLocationDTO) -> StorageLocationData {
+    StorageLocationData {
+        id: location.id,
+        name: location.name,
+        sort_order: location.sort_order,
+    }
+}
+
+/// Command to list all storage locations in counting order
+#[tauri::command]
+pub fn list_all_storage_locations(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<StorageLocationData>, String> {
+    let locations = app
+        .storage_location_usecase()
+        .list()
+        .map_err(|e| e.to_string())?;
+
+    Ok(locations.into_iter().map(location_data).collect())
+}
+
+/// Command to register a new storage location
+#[tauri::command]
+pub fn register_storage_location(
+    app: tauri::State<Stocker>,
+    command: AddStorageLocationCommand,
+) -> Result<StorageLocationData, String> {
+    let location = app
+        .storage_location_usecase()
+        .register(RegisterStorageLocationCommand {
+            location_name: command.location_name,
+            sort_order: command.sort_order,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(location_data(location))
+}
+
+/// Command to rename or reorder a storage location
+#[tauri::command]
+pub fn update_storage_location(
+    app: tauri::State<Stocker>,
+    command: EditStorageLocationCommand,
+) -> Result<(), String> {
+    app.storage_location_usecase()
+        .update(UpdateStorageLocationCommand {
+            location_id: command.location_id,
+            location_name: command.location_name,
+            sort_order: command.sort_order,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a storage location
+#[tauri::command]
+pub fn delete_storage_location(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.storage_location_usecase()
+        .delete(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to list deleted storage locations in the trash
+#[tauri::command]
+pub fn list_deleted_storage_locations(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<TrashedData<StorageLocationData>>, String> {
+    let locations = app
+        .storage_location_usecase()
+        .list_deleted()
+        .map_err(|e| e.to_string())?;
+
+    Ok(locations
+        .into_iter()
+        .map(|trashed| trashed_data(trashed, location_data))
+        .collect())
+}
+
+/// Command to restore a deleted storage location from the trash
+#[tauri::command]
+pub fn restore_storage_location(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.storage_location_usecase()
+        .restore(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a storage location in the trash permanently
+#[tauri::command]
+pub fn purge_storage_location(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.storage_location_usecase()
+        .purge(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to set the default storage location of a supply
+#[tauri::command]
+pub fn place_supply_in_location(
+    app: tauri::State<Stocker>,
+    command: PlaceSupplyData,
+) -> Result<(), String> {
+    app.storage_location_usecase()
+        .place_supply(PlaceSupplyCommand {
+            supply_id: command.supply_id,
+            location_id: command.location_id,
+            sort_order: command.sort_order,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -1,13 +1,45 @@
-//! This module contains Tauri commands related to suppliers, supplies, stocktaking, and journals.
+//! This module contains Tauri commands related to suppliers, supplies, units, categories, storage locations, stores, stocktaking, journals, accounting periods, purchase orders, transfers, supplier invoices and payments, consumptions, the stock ledger, reorder suggestions, reports, revisions, the trash and the audit log.
 
+mod accounting;
+mod audit;
+mod category;
 mod config;
+mod consumption;
 mod journal;
+mod ledger;
+mod location;
+mod payable;
+mod purchase_order;
+mod reorder;
+mod report;
+mod revision;
 mod stockatking;
+mod store;
 mod supplier;
 mod supply;
+mod transfer;
+mod trash;
+mod unit;
+mod waste;
 
+pub use accounting::*;
+pub use audit::*;
+pub use category::*;
 pub use config::*;
+pub use consumption::*;
 pub use journal::*;
+pub use ledger::*;
+pub use location::*;
+pub use payable::*;
+pub use purchase_order::*;
+pub use reorder::*;
+pub use report::*;
+pub use revision::*;
 pub use stockatking::*;
+pub use store::*;
 pub use supplier::*;
 pub use supply::*;
+pub use transfer::*;
+pub use trash::*;
+pub use unit::*;
+pub use waste::*;
@@ -0,0 +1,420 @@
+//! Commands related to supplier invoices, payments and balances
+use chrono::{Datelike, Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::command::{trashed_data, TrashedData};
+use crate::core::provided_ports::{
+    BillingTermsDTO, CancelPaymentCommand, PayableBalanceQuery, PayableUsecase,
+    RecordPaymentCommand, RecordSupplierInvoiceCommand, SearchSupplierInvoicesQuery,
+    SupplierBalanceDTO, SupplierInvoiceDTO, SupplierUsecase, UpdateSupplierInvoiceCommand,
+};
+use crate::core::stocker::Stocker;
+
+/// Data of supplier invoice
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplierInvoiceData {
+    id: String,
+    supplier_id: String,
+    supplier_name: String,
+    invoice_number: Option<String>,
+    period_start: i64,
+    period_end: i64,
+    due_date: i64,
+    amount: f64,
+    /// Journals of the supplier entered in the period
+    journal_ids: Vec<String>,
+    journal_amount: f64,
+    /// Billed amount less the journal amount
+    difference: f64,
+    paid_amount: f64,
+    balance: f64,
+    payments: Vec<PaymentData>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentData {
+    id: String,
+    paid_date: i64,
+    amount: f64,
+    payment_method: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordSupplierInvoiceData {
+    supplier_id: String,
+    #[serde(default)]
+    invoice_number: Option<String>,
+    /// Any time on the closing day, the period ends at the end of that day
+    closing_date: i64,
+    /// Day after the previous closing day of the billing terms if not given
+    #[serde(default)]
+    period_start: Option<i64>,
+    /// Payment day of the billing terms if not given
+    #[serde(default)]
+    due_date: Option<i64>,
+    amount: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSupplierInvoiceData {
+    id: String,
+    #[serde(default)]
+    invoice_number: Option<String>,
+    period_start: i64,
+    period_end: i64,
+    due_date: i64,
+    amount: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplierInvoiceQuery {
+    #[serde(default)]
+    supplier_id: Option<String>,
+    #[serde(default)]
+    period_start: Option<i64>,
+    #[serde(default)]
+    period_end: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordPaymentData {
+    invoice_id: String,
+    paid_date: i64,
+    amount: f64,
+    /// `bank_transfer`, `direct_debit`, `cash` or `other`, that of the billing terms if not given
+    #[serde(default)]
+    payment_method: Option<String>,
+}
+
+/// Outstanding balance of a supplier by days past the due dates
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplierBalanceData {
+    supplier_id: String,
+    supplier_name: String,
+    invoiced: f64,
+    paid: f64,
+    balance: f64,
+    current: f64,
+    overdue_1_30: f64,
+    overdue_31_60: f64,
+    overdue_61_90: f64,
+    overdue_over_90: f64,
+}
+
+/// Converts a supplier invoice DTO into its command data
+fn invoice_data(invoice: SupplierInvoiceDTO) -> SupplierInvoiceData {
+    SupplierInvoiceData {
+        id: invoice.id,
+        supplier_id: invoice.supplier_id,
+        supplier_name: invoice.supplier_name,
+        invoice_number: invoice.invoice_number,
+        period_start: invoice.period_start,
+        period_end: invoice.period_end,
+        due_date: invoice.due_date,
+        amount: invoice.amount,
+        journal_ids: invoice.journal_ids,
+        journal_amount: invoice.journal_amount,
+        difference: invoice.difference,
+        paid_amount: invoice.paid_amount,
+        balance: invoice.balance,
+        payments: invoice
+            .payments
+            .into_iter()
+            .map(|payment| PaymentData {
+                id: payment.id,
+                paid_date: payment.paid_date,
+                amount: payment.amount,
+                payment_method: payment.payment_method,
+            })
+            .collect(),
+    }
+}
+
+/// Converts a supplier balance DTO into its command data
+fn balance_data(balance: SupplierBalanceDTO) -> SupplierBalanceData {
+    SupplierBalanceData {
+        supplier_id: balance.supplier_id,
+        supplier_name: balance.supplier_name,
+        invoiced: balance.invoiced,
+        paid: balance.paid,
+        balance: balance.balance,
+        current: balance.current,
+        overdue_1_30: balance.overdue_1_30,
+        overdue_31_60: balance.overdue_31_60,
+        overdue_61_90: balance.overdue_61_90,
+        overdue_over_90: balance.overdue_over_90,
+    }
+}
+
+/// Returns the billing day of the month `months` after that of `date`,
+/// the last day if the month is shorter or the day is the end of the month (31)
+fn billing_date(date: NaiveDate, months: i32, day: u32) -> Result<NaiveDate, String> {
+    let month = date.year() * 12 + date.month0() as i32 + months;
+    let first = NaiveDate::from_ymd_opt(month.div_euclid(12), month.rem_euclid(12) as u32 + 1, 1)
+        .ok_or("Invalid billing date".to_string())?;
+    let last = first
+        .checked_add_months(chrono::Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .ok_or("Invalid billing date".to_string())?;
+
+    first
+        .with_day(day.min(last.day()))
+        .ok_or(format!("Invalid billing day: {}", day))
+}
+
+/// Returns the timestamp of the local date and time
+fn local_timestamp(
+    date: NaiveDate,
+    hour: u32,
+    min: u32,
+    sec: u32,
+    milli: u32,
+) -> Result<i64, String> {
+    date.and_hms_milli_opt(hour, min, sec, milli)
+        .and_then(|datetime| Local.from_local_datetime(&datetime).earliest())
+        .map(|datetime| datetime.timestamp_millis())
+        .ok_or("Invalid local time".to_string())
+}
+
+/// Derives the billing period and due date of a closing date,
+/// filling what is not given from the billing terms of the supplier
+fn billing_dates(
+    closing_date: i64,
+    period_start: Option<i64>,
+    due_date: Option<i64>,
+    terms: Option<&BillingTermsDTO>,
+) -> Result<(i64, i64, i64), String> {
+    let closing = Local
+        .timestamp_millis_opt(closing_date)
+        .single()
+        .ok_or("Invalid closing date".to_string())?
+        .date_naive();
+
+    let period_end = local_timestamp(closing, 23, 59, 59, 999)?;
+
+    let period_start = match (period_start, terms) {
+        (Some(period_start), _) => period_start,
+        (None, Some(terms)) => {
+            let previous = billing_date(closing, -1, terms.closing_day)?;
+            let start = previous
+                .succ_opt()
+                .ok_or("Invalid closing date".to_string())?;
+
+            local_timestamp(start, 0, 0, 0, 0)?
+        }
+        (None, None) => {
+            let start = closing
+                .with_day(1)
+                .ok_or("Invalid closing date".to_string())?;
+
+            local_timestamp(start, 0, 0, 0, 0)?
+        }
+    };
+
+    let due_date = match (due_date, terms) {
+        (Some(due_date), _) => due_date,
+        (None, Some(terms)) => local_timestamp(
+            billing_date(
+                closing,
+                terms.payment_months_later as i32,
+                terms.payment_day,
+            )?,
+            0,
+            0,
+            0,
+            0,
+        )?,
+        (None, None) => {
+            return Err("Due date is required as the supplier has no billing terms".to_string())
+        }
+    };
+
+    Ok((period_start, period_end, due_date))
+}
+
+#[tauri::command]
+pub fn list_all_supplier_invoices(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<SupplierInvoiceData>, String> {
+    let invoices = app.payable_usecase().list().map_err(|e| e.to_string())?;
+
+    Ok(invoices.into_iter().map(invoice_data).collect())
+}
+
+#[tauri::command]
+pub fn get_supplier_invoice_by_id(
+    app: tauri::State<Stocker>,
+    id: String,
+) -> Result<Option<SupplierInvoiceData>, String> {
+    let invoice = app.payable_usecase().get(&id).map_err(|e| e.to_string())?;
+
+    Ok(invoice.map(invoice_data))
+}
+
+#[tauri::command]
+pub fn search_supplier_invoices(
+    app: tauri::State<Stocker>,
+    query: SupplierInvoiceQuery,
+) -> Result<Vec<SupplierInvoiceData>, String> {
+    let invoices = app
+        .payable_usecase()
+        .search(SearchSupplierInvoicesQuery {
+            supplier_id: query.supplier_id,
+            period_start: query.period_start,
+            period_end: query.period_end,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(invoices.into_iter().map(invoice_data).collect())
+}
+
+/// Command to record an invoice of a supplier for the period closing on a date
+#[tauri::command]
+pub fn record_supplier_invoice(
+    app: tauri::State<Stocker>,
+    command: RecordSupplierInvoiceData,
+) -> Result<SupplierInvoiceData, String> {
+    let supplier = app
+        .supplier_usecase()
+        .get(&command.supplier_id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Supplier not found".to_string())?;
+
+    let (period_start, period_end, due_date) = billing_dates(
+        command.closing_date,
+        command.period_start,
+        command.due_date,
+        supplier.billing_terms.as_ref(),
+    )?;
+
+    let invoice = app
+        .payable_usecase()
+        .record(RecordSupplierInvoiceCommand {
+            supplier_id: command.supplier_id,
+            invoice_number: command.invoice_number,
+            period_start,
+            period_end,
+            due_date,
+            amount: command.amount,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(invoice_data(invoice))
+}
+
+#[tauri::command]
+pub fn update_supplier_invoice(
+    app: tauri::State<Stocker>,
+    command: UpdateSupplierInvoiceData,
+) -> Result<(), String> {
+    app.payable_usecase()
+        .update(UpdateSupplierInvoiceCommand {
+            invoice_id: command.id,
+            invoice_number: command.invoice_number,
+            period_start: command.period_start,
+            period_end: command.period_end,
+            due_date: command.due_date,
+            amount: command.amount,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_supplier_invoice(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.payable_usecase()
+        .delete(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to list deleted supplier invoices in the trash
+#[tauri::command]
+pub fn list_deleted_supplier_invoices(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<TrashedData<SupplierInvoiceData>>, String> {
+    let invoices = app
+        .payable_usecase()
+        .list_deleted()
+        .map_err(|e| e.to_string())?;
+
+    Ok(invoices
+        .into_iter()
+        .map(|trashed| trashed_data(trashed, invoice_data))
+        .collect())
+}
+
+/// Command to restore a deleted supplier invoice from the trash
+#[tauri::command]
+pub fn restore_supplier_invoice(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.payable_usecase()
+        .restore(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a supplier invoice in the trash permanently
+#[tauri::command]
+pub fn purge_supplier_invoice(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.payable_usecase().purge(id).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn record_supplier_payment(
+    app: tauri::State<Stocker>,
+    command: RecordPaymentData,
+) -> Result<SupplierInvoiceData, String> {
+    let invoice = app
+        .payable_usecase()
+        .record_payment(RecordPaymentCommand {
+            invoice_id: command.invoice_id,
+            paid_date: command.paid_date,
+            amount: command.amount,
+            payment_method: command.payment_method,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(invoice_data(invoice))
+}
+
+#[tauri::command]
+pub fn cancel_supplier_payment(
+    app: tauri::State<Stocker>,
+    invoice_id: String,
+    payment_id: String,
+) -> Result<(), String> {
+    app.payable_usecase()
+        .cancel_payment(CancelPaymentCommand {
+            invoice_id,
+            payment_id,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to get the outstanding balance and aging per supplier at a date
+#[tauri::command]
+pub fn get_payable_balances(
+    app: tauri::State<Stocker>,
+    at: i64,
+) -> Result<Vec<SupplierBalanceData>, String> {
+    let balances = app
+        .payable_usecase()
+        .balances(PayableBalanceQuery { at })
+        .map_err(|e| e.to_string())?;
+
+    Ok(balances.into_iter().map(balance_data).collect())
+}
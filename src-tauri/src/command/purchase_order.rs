@@ -0,0 +1,306 @@
+//! Commands related to purchase orders
+use serde::{Deserialize, Serialize};
+
+use crate::command::{journal_data, trashed_data, JournalData, TrashedData};
+use crate::core::provided_ports::{
+    DraftPurchaseOrderCommand, PurchaseOrderDTO, PurchaseOrderLineDTO, PurchaseOrderUsecase,
+    ReceivePurchaseOrderCommand, ReceivedLineDTO, RevisePurchaseOrderCommand,
+    SearchPurchaseOrdersQuery,
+};
+use crate::core::stocker::Stocker;
+
+/// Data of purchase order
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurchaseOrderData {
+    id: String,
+    supplier_id: String,
+    supplier_name: String,
+    ordered_date: i64,
+    /// `draft`, `sent`, `partially_received`, `received` or `cancelled`
+    status: String,
+    expected_total_price: f64,
+    lines: Vec<PurchaseOrderLineData>,
+    /// journals the deliveries were recorded in
+    journal_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurchaseOrderLineData {
+    supply_id: String,
+    /// Taken from the supply
+    #[serde(default)]
+    supply_name: String,
+    /// Purchase unit of the supply
+    #[serde(default)]
+    unit_name: String,
+    quantity: f64,
+    expected_unit_price: f64,
+    /// Quantity delivered so far, computed by the core
+    #[serde(default)]
+    received_quantity: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DraftPurchaseOrderData {
+    supplier_id: String,
+    ordered_date: i64,
+    lines: Vec<PurchaseOrderLineData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisePurchaseOrderData {
+    id: String,
+    ordered_date: i64,
+    lines: Vec<PurchaseOrderLineData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceivePurchaseOrderData {
+    id: String,
+    received_date: i64,
+    /// Appends the delivery to this journal instead of recording a new one
+    #[serde(default)]
+    journal_id: Option<String>,
+    lines: Vec<ReceivedLineData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceivedLineData {
+    supply_id: String,
+    quantity: f64,
+    /// Defaults to the expected unit price of the order
+    #[serde(default)]
+    unit_price: Option<f64>,
+    #[serde(default)]
+    lot_number: Option<String>,
+    /// Best-before or expiry date of the received goods
+    #[serde(default)]
+    expiry_date: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurchaseOrderQuery {
+    #[serde(default)]
+    period_start: Option<i64>,
+    #[serde(default)]
+    period_end: Option<i64>,
+    #[serde(default)]
+    supplier_id: Option<String>,
+    #[serde(default)]
+    status: Option<String>,
+}
+
+/// Converts a purchase order DTO into its command data
+fn purchase_order_data(order: PurchaseOrderDTO) -> PurchaseOrderData {
+    PurchaseOrderData {
+        id: order.id,
+        supplier_id: order.supplier_id,
+        supplier_name: order.supplier_name,
+        ordered_date: order.ordered_date,
+        status: order.status,
+        expected_total_price: order.expected_total_price,
+        lines: order
+            .lines
+            .into_iter()
+            .map(|line| PurchaseOrderLineData {
+                supply_id: line.supply_id,
+                supply_name: line.supply_name,
+                unit_name: line.unit_name,
+                quantity: line.quantity,
+                expected_unit_price: line.expected_unit_price,
+                received_quantity: line.received_quantity,
+            })
+            .collect(),
+        journal_ids: order.journal_ids,
+    }
+}
+
+/// Converts command data of a line into its DTO
+fn line_dto(line: PurchaseOrderLineData) -> PurchaseOrderLineDTO {
+    PurchaseOrderLineDTO {
+        supply_id: line.supply_id,
+        supply_name: line.supply_name,
+        unit_name: line.unit_name,
+        quantity: line.quantity,
+        expected_unit_price: line.expected_unit_price,
+        received_quantity: line.received_quantity,
+    }
+}
+
+#[tauri::command]
+pub fn list_all_purchase_orders(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<PurchaseOrderData>, String> {
+    let orders = app
+        .purchase_order_usecase()
+        .list()
+        .map_err(|e| e.to_string())?;
+
+    Ok(orders.into_iter().map(purchase_order_data).collect())
+}
+
+#[tauri::command]
+pub fn get_purchase_order_by_id(
+    app: tauri::State<Stocker>,
+    id: String,
+) -> Result<Option<PurchaseOrderData>, String> {
+    let order = app
+        .purchase_order_usecase()
+        .get(&id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(order.map(purchase_order_data))
+}
+
+#[tauri::command]
+pub fn search_purchase_orders(
+    app: tauri::State<Stocker>,
+    query: PurchaseOrderQuery,
+) -> Result<Vec<PurchaseOrderData>, String> {
+    let orders = app
+        .purchase_order_usecase()
+        .search(SearchPurchaseOrdersQuery {
+            period_start: query.period_start,
+            period_end: query.period_end,
+            supplier_id: query.supplier_id,
+            status: query.status,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(orders.into_iter().map(purchase_order_data).collect())
+}
+
+/// Command to draft a new purchase order
+#[tauri::command]
+pub fn draft_purchase_order(
+    app: tauri::State<Stocker>,
+    command: DraftPurchaseOrderData,
+) -> Result<PurchaseOrderData, String> {
+    let order = app
+        .purchase_order_usecase()
+        .draft(DraftPurchaseOrderCommand {
+            supplier_id: command.supplier_id,
+            ordered_date: command.ordered_date,
+            lines: command.lines.into_iter().map(line_dto).collect(),
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(purchase_order_data(order))
+}
+
+/// Command to revise the lines of a draft purchase order
+#[tauri::command]
+pub fn revise_purchase_order(
+    app: tauri::State<Stocker>,
+    command: RevisePurchaseOrderData,
+) -> Result<(), String> {
+    app.purchase_order_usecase()
+        .revise(RevisePurchaseOrderCommand {
+            purchase_order_id: command.id,
+            ordered_date: command.ordered_date,
+            lines: command.lines.into_iter().map(line_dto).collect(),
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to mark a draft purchase order as sent
+#[tauri::command]
+pub fn send_purchase_order(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.purchase_order_usecase()
+        .send(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn cancel_purchase_order(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.purchase_order_usecase()
+        .cancel(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to receive a delivery, returning the journal it was recorded in
+#[tauri::command]
+pub fn receive_purchase_order(
+    app: tauri::State<Stocker>,
+    command: ReceivePurchaseOrderData,
+) -> Result<JournalData, String> {
+    let journal = app
+        .purchase_order_usecase()
+        .receive(ReceivePurchaseOrderCommand {
+            purchase_order_id: command.id,
+            received_date: command.received_date,
+            journal_id: command.journal_id,
+            lines: command
+                .lines
+                .into_iter()
+                .map(|line| ReceivedLineDTO {
+                    supply_id: line.supply_id,
+                    quantity: line.quantity,
+                    unit_price: line.unit_price,
+                    lot_number: line.lot_number,
+                    expiry_date: line.expiry_date,
+                })
+                .collect(),
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(journal_data(journal))
+}
+
+#[tauri::command]
+pub fn delete_purchase_order(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.purchase_order_usecase()
+        .delete(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to list deleted purchase orders in the trash
+#[tauri::command]
+pub fn list_deleted_purchase_orders(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<TrashedData<PurchaseOrderData>>, String> {
+    let purchase_orders = app
+        .purchase_order_usecase()
+        .list_deleted()
+        .map_err(|e| e.to_string())?;
+
+    Ok(purchase_orders
+        .into_iter()
+        .map(|trashed| trashed_data(trashed, purchase_order_data))
+        .collect())
+}
+
+/// Command to restore a deleted purchase order from the trash
+#[tauri::command]
+pub fn restore_purchase_order(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.purchase_order_usecase()
+        .restore(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a purchase order in the trash permanently
+#[tauri::command]
+pub fn purge_purchase_order(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.purchase_order_usecase()
+        .purge(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+//! Commands related to reorder suggestions
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+
+use crate::core::provided_ports::{ReorderQuery, ReorderSuggestionDTO, ReorderUsecase};
+use crate::core::stocker::Stocker;
+
+/// Supplies to reorder from a supplier
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderSuggestionData {
+    supplier_id: String,
+    supplier_name: String,
+    lines: Vec<ReorderLineData>,
+}
+
+/// Quantities of a supply in its stock unit
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderLineData {
+    supply_id: String,
+    supply_name: String,
+    unit_name: String,
+    on_hand: f64,
+    par_level: f64,
+    reorder_point: f64,
+    suggested_quantity: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderQueryData {
+    /// estimates the stock on hand at this time
+    at: i64,
+}
+
+/// Converts a reorder suggestion DTO into its data
+fn reorder_suggestion_data(suggestion: ReorderSuggestionDTO) -> ReorderSuggestionData {
+    ReorderSuggestionData {
+        supplier_id: suggestion.supplier_id,
+        supplier_name: suggestion.supplier_name,
+        lines: suggestion
+            .lines
+            .into_iter()
+            .map(|line| ReorderLineData {
+                supply_id: line.supply_id,
+                supply_name: line.supply_name,
+                unit_name: line.unit_name,
+                on_hand: line.on_hand,
+                par_level: line.par_level,
+                reorder_point: line.reorder_point,
+                suggested_quantity: line.suggested_quantity,
+            })
+            .collect(),
+    }
+}
+
+/// Command to list supplies to order up to their par levels, grouped by supplier
+#[tauri::command]
+pub fn get_reorder_suggestions(
+    app: tauri::State<Stocker>,
+    query: ReorderQueryData,
+) -> Result<Vec<ReorderSuggestionData>, String> {
+    let suggestions = app
+        .reorder_usecase()
+        .suggest(ReorderQuery { at: query.at })
+        .map_err(|e| e.to_string())?;
+
+    Ok(suggestions
+        .into_iter()
+        .map(reorder_suggestion_data)
+        .collect())
+}
+
+/// Command to download reorder suggestions CSV
+#[tauri::command]
+pub fn download_reorder_csv(
+    app: AppHandle,
+    state: tauri::State<Stocker>,
+    query: ReorderQueryData,
+) -> Result<(), String> {
+    let suggestions = state
+        .reorder_usecase()
+        .suggest(ReorderQuery { at: query.at })
+        .map_err(|e| e.to_string())?;
+
+    let mut csv =
+        "\"仕入先\",\"仕入品名\",\"単位\",\"推定在庫\",\"定数\",\"発注点\",\"発注数量\"\n"
+            .to_string();
+
+    for suggestion in suggestions {
+        for line in suggestion.lines {
+            csv.push_str(&format!(
+                "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"\n",
+                suggestion.supplier_name,
+                line.supply_name,
+                line.unit_name,
+                line.on_hand,
+                line.par_level,
+                line.reorder_point,
+                line.suggested_quantity
+            ));
+        }
+    }
+
+    app.dialog()
+        .file()
+        .add_filter("csv", &["csv"])
+        .save_file(|file_path| {
+            if let Some(file_path) = file_path {
+                let path = file_path.as_path().ok_or("Failed to get path").unwrap();
+
+                std::fs::write(path, csv)
+                    .map_err(|e| e.to_string())
+                    .unwrap();
+            }
+        });
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+//! Commands related to reports
+use serde::{Deserialize, Serialize};
+
+use crate::core::provided_ports::{CostOfGoodsSoldQuery, ReportUsecase};
+use crate::core::stocker::Stocker;
+
+/// Cost of goods sold of a period
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostOfGoodsSoldData {
+    period_start: i64,
+    period_end: i64,
+    /// none when several stores are consolidated
+    opening_stocktaking_id: Option<String>,
+    /// none when several stores are consolidated
+    closing_stocktaking_id: Option<String>,
+    opening_inventory: f64,
+    purchases: f64,
+    /// transferred in from other stores minus transferred out to them
+    transfers: f64,
+    closing_inventory: f64,
+    cost_of_goods_sold: f64,
+    stores: Vec<StoreCostOfGoodsSoldData>,
+    suppliers: Vec<SupplierCostOfGoodsSoldData>,
+    supplies: Vec<SupplyCostOfGoodsSoldData>,
+}
+
+/// Cost of goods sold of a store
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreCostOfGoodsSoldData {
+    store_id: String,
+    store_name: String,
+    opening_stocktaking_id: Option<String>,
+    closing_stocktaking_id: String,
+    opening_inventory: f64,
+    purchases: f64,
+    transfers: f64,
+    closing_inventory: f64,
+    cost_of_goods_sold: f64,
+}
+
+/// Cost of goods sold of a supplier
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplierCostOfGoodsSoldData {
+    supplier_id: Option<String>,
+    supplier_name: Option<String>,
+    opening_inventory: f64,
+    purchases: f64,
+    transfers: f64,
+    closing_inventory: f64,
+    cost_of_goods_sold: f64,
+}
+
+/// Cost of goods sold of a supply
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplyCostOfGoodsSoldData {
+    supply_id: String,
+    supply_name: String,
+    supplier_id: Option<String>,
+    opening_inventory: f64,
+    purchases: f64,
+    transfers: f64,
+    closing_inventory: f64,
+    cost_of_goods_sold: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportPeriodQuery {
+    period_start: i64,
+    period_end: i64,
+    /// consolidates all stores if not provided
+    #[serde(default)]
+    store_id: Option<String>,
+}
+
+/// Command to get cost of goods sold of a period
+#[tauri::command]
+pub fn get_cost_of_goods_sold(
+    app: tauri::State<Stocker>,
+    query: ReportPeriodQuery,
+) -> Result<CostOfGoodsSoldData, String> {
+    let report = app
+        .report_usecase()
+        .cost_of_goods_sold(CostOfGoodsSoldQuery {
+            period_start: query.period_start,
+            period_end: query.period_end,
+            store_id: query.store_id,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(CostOfGoodsSoldData {
+        period_start: report.period_start,
+        period_end: report.period_end,
+        opening_stocktaking_id: report.opening_stocktaking_id,
+        closing_stocktaking_id: report.closing_stocktaking_id,
+        opening_inventory: report.opening_inventory,
+        purchases: report.purchases,
+        transfers: report.transfers,
+        closing_inventory: report.closing_inventory,
+        cost_of_goods_sold: report.cost_of_goods_sold,
+        stores: report
+            .stores
+            .into_iter()
+            .map(|store| StoreCostOfGoodsSoldData {
+                store_id: store.store_id,
+                store_name: store.store_name,
+                opening_stocktaking_id: store.opening_stocktaking_id,
+                closing_stocktaking_id: store.closing_stocktaking_id,
+                opening_inventory: store.opening_inventory,
+                purchases: store.purchases,
+                transfers: store.transfers,
+                closing_inventory: store.closing_inventory,
+                cost_of_goods_sold: store.cost_of_goods_sold,
+            })
+            .collect(),
+        suppliers: report
+            .suppliers
+            .into_iter()
+            .map(|supplier| SupplierCostOfGoodsSoldData {
+                supplier_id: supplier.supplier_id,
+                supplier_name: supplier.supplier_name,
+                opening_inventory: supplier.opening_inventory,
+                purchases: supplier.purchases,
+                transfers: supplier.transfers,
+                closing_inventory: supplier.closing_inventory,
+                cost_of_goods_sold: supplier.cost_of_goods_sold,
+            })
+            .collect(),
+        supplies: report
+            .supplies
+            .into_iter()
+            .map(|supply| SupplyCostOfGoodsSoldData {
+                supply_id: supply.supply_id,
+                supply_name: supply.supply_name,
+                supplier_id: supply.supplier_id,
+                opening_inventory: supply.opening_inventory,
+                purchases: supply.purchases,
+                transfers: supply.transfers,
+                closing_inventory: supply.closing_inventory,
+                cost_of_goods_sold: supply.cost_of_goods_sold,
+            })
+            .collect(),
+    })
+}
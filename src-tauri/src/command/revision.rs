@@ -0,0 +1,71 @@
+//! Data of revisions and the differences between them
+use serde::Serialize;
+
+use crate::core::provided_ports::{LineDiffDTO, RevisionDTO};
+
+/// Data of a version of a record
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisionData<T> {
+    /// numbered from 1, the current version last
+    pub number: u32,
+    /// timestamp the version was replaced by an edit, none for the current version
+    pub revised_at: Option<i64>,
+    pub item: T,
+}
+
+/// Data of a line added, removed or changed between two versions
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineDiffData {
+    /// one of added, removed or changed
+    pub change: String,
+    pub supply_id: String,
+    pub supply_name: String,
+    pub supplier_id: Option<String>,
+    pub supplier_name: Option<String>,
+    pub location_id: Option<String>,
+    pub lot_number: Option<String>,
+    pub unit_name: String,
+    pub quantity_before: Option<f64>,
+    pub quantity_after: Option<f64>,
+    pub quantity_delta: f64,
+    pub unit_price_before: Option<f64>,
+    pub unit_price_after: Option<f64>,
+    pub unit_price_delta: f64,
+    pub total_price_before: Option<f64>,
+    pub total_price_after: Option<f64>,
+    pub total_price_delta: f64,
+}
+
+/// Converts a version of a record with `data` converting the record itself.
+pub fn revision_data<T, D>(revision: RevisionDTO<T>, data: impl Fn(T) -> D) -> RevisionData<D> {
+    RevisionData {
+        number: revision.number,
+        revised_at: revision.revised_at,
+        item: data(revision.item),
+    }
+}
+
+/// Converts a difference between two versions into its command data
+pub fn line_diff_data(diff: LineDiffDTO) -> LineDiffData {
+    LineDiffData {
+        change: diff.change,
+        supply_id: diff.supply_id,
+        supply_name: diff.supply_name,
+        supplier_id: diff.supplier_id,
+        supplier_name: diff.supplier_name,
+        location_id: diff.location_id,
+        lot_number: diff.lot_number,
+        unit_name: diff.unit_name,
+        quantity_before: diff.quantity_before,
+        quantity_after: diff.quantity_after,
+        quantity_delta: diff.quantity_delta,
+        unit_price_before: diff.unit_price_before,
+        unit_price_after: diff.unit_price_after,
+        unit_price_delta: diff.unit_price_delta,
+        total_price_before: diff.total_price_before,
+        total_price_after: diff.total_price_after,
+        total_price_delta: diff.total_price_delta,
+    }
+}
@@ -3,6 +3,10 @@ use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
 use tauri_plugin_dialog::DialogExt;
 
+use crate::command::{
+    category_total_data, line_diff_data, revision_data, stock_quantity_data, trashed_data,
+    CategoryTotalData, LineDiffData, RevisionData, StockQuantityData, TrashedData,
+};
 use crate::core::provided_ports::{
     self, SearchStocktakingQuery, StocktakingRecordDTO, StocktakingUsecase,
 };
@@ -23,16 +27,51 @@ pub struct StocktakingRecordData {
     supply_id: String,
     supply_name: String,
     unit_name: String,
-    unit_price: f64,
+    /// derived from purchases if not provided
+    #[serde(default)]
+    unit_price: Option<f64>,
     quantity: f64,
-    total_price: f64,
+    /// computed from the unit price if not provided
+    #[serde(default)]
+    total_price: Option<f64>,
+    /// method the unit price was derived by, ignored on input
+    #[serde(default)]
+    valuation_method: Option<String>,
+    /// storage location counted in, defaults to the location of the supply
+    #[serde(default)]
+    location_id: Option<String>,
+    /// ignored on input
+    #[serde(default)]
+    location_name: Option<String>,
+    /// lot received in a journal the counted quantity belongs to
+    #[serde(default)]
+    lot_number: Option<String>,
+}
+
+/// Supplies to count in a storage location, in counting order
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CountSheetData {
+    /// none for supplies not placed
+    location_id: Option<String>,
+    location_name: Option<String>,
+    lines: Vec<CountSheetLineData>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CountSheetLineData {
+    supply_id: String,
+    supply_name: String,
+    unit_name: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RecordStocktakingCommand {
     stocktaking_date: i64,
-    total_price: f64,
+    #[serde(default)]
+    total_price: Option<f64>,
     records: Vec<StocktakingRecordData>,
 }
 
@@ -40,7 +79,8 @@ pub struct RecordStocktakingCommand {
 #[serde(rename_all = "camelCase")]
 pub struct UpdateStocktakingCommand {
     id: String,
-    total_price: f64,
+    #[serde(default)]
+    total_price: Option<f64>,
     records: Vec<StocktakingRecordData>,
 }
 
@@ -49,6 +89,9 @@ pub struct UpdateStocktakingCommand {
 pub struct StocktakingQuery {
     period_start: Option<i64>,
     period_end: Option<i64>,
+    /// lines of supplies in the category or its descendants
+    #[serde(default)]
+    category_id: Option<String>,
 }
 
 /// Command to list all stocktakings
@@ -75,6 +118,10 @@ pub fn list_all_stocktakings(app: tauri::State<Stocker>) -> Result<Vec<Stocktaki
                     unit_price: record.unit_price,
                     quantity: record.quantity,
                     total_price: record.total_price,
+                    valuation_method: record.valuation_method,
+                    location_id: record.location_id,
+                    location_name: record.location_name,
+                    lot_number: record.lot_number,
                 })
                 .collect::<Vec<StocktakingRecordData>>(),
         })
@@ -109,6 +156,10 @@ pub fn get_stocktaking_by_id(
                     unit_price: record.unit_price,
                     quantity: record.quantity,
                     total_price: record.total_price,
+                    valuation_method: record.valuation_method,
+                    location_id: record.location_id,
+                    location_name: record.location_name,
+                    lot_number: record.lot_number,
                 })
                 .collect::<Vec<StocktakingRecordData>>(),
         })
@@ -138,6 +189,10 @@ pub fn record_stocktaking(
                     unit_price: record.unit_price,
                     quantity: record.quantity,
                     total_price: record.total_price,
+                    valuation_method: None,
+                    location_id: record.location_id,
+                    location_name: None,
+                    lot_number: record.lot_number,
                 })
                 .collect::<Vec<StocktakingRecordDTO>>(),
         })
@@ -157,6 +212,10 @@ pub fn record_stocktaking(
                 unit_price: record.unit_price,
                 quantity: record.quantity,
                 total_price: record.total_price,
+                valuation_method: record.valuation_method,
+                location_id: record.location_id,
+                location_name: record.location_name,
+                lot_number: record.lot_number,
             })
             .collect::<Vec<StocktakingRecordData>>(),
     };
@@ -184,6 +243,10 @@ pub fn update_stocktaking(
                     unit_price: record.unit_price,
                     quantity: record.quantity,
                     total_price: record.total_price,
+                    valuation_method: None,
+                    location_id: record.location_id,
+                    location_name: None,
+                    lot_number: record.lot_number,
                 })
                 .collect::<Vec<StocktakingRecordDTO>>(),
         })
@@ -203,6 +266,7 @@ pub fn search_stocktakings(
         .search(SearchStocktakingQuery {
             period_start: query.period_start,
             period_end: query.period_end,
+            category_id: query.category_id,
         })
         .map_err(|e| e.to_string())?;
 
@@ -222,6 +286,10 @@ pub fn search_stocktakings(
                     unit_price: record.unit_price,
                     quantity: record.quantity,
                     total_price: record.total_price,
+                    valuation_method: record.valuation_method,
+                    location_id: record.location_id,
+                    location_name: record.location_name,
+                    lot_number: record.lot_number,
                 })
                 .collect::<Vec<StocktakingRecordData>>(),
         })
@@ -230,6 +298,72 @@ pub fn search_stocktakings(
     Ok(stocktakings)
 }
 
+/// Command to sum counted quantities per supply in its stock unit
+#[tauri::command]
+pub fn aggregate_stocktaking_quantities(
+    app: tauri::State<Stocker>,
+    query: StocktakingQuery,
+) -> Result<Vec<StockQuantityData>, String> {
+    let quantities = app
+        .stocktaking_usecase()
+        .aggregate_quantities(SearchStocktakingQuery {
+            period_start: query.period_start,
+            period_end: query.period_end,
+            category_id: query.category_id,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(quantities.into_iter().map(stock_quantity_data).collect())
+}
+
+/// Command to sum counted values per category
+#[tauri::command]
+pub fn get_stocktaking_category_totals(
+    app: tauri::State<Stocker>,
+    query: StocktakingQuery,
+) -> Result<Vec<CategoryTotalData>, String> {
+    let totals = app
+        .stocktaking_usecase()
+        .totals_by_category(SearchStocktakingQuery {
+            period_start: query.period_start,
+            period_end: query.period_end,
+            category_id: query.category_id,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(totals.into_iter().map(category_total_data).collect())
+}
+
+/// Command to list supplies to count per storage location
+#[tauri::command]
+pub fn get_stocktaking_count_sheet(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<CountSheetData>, String> {
+    let sheets = app
+        .stocktaking_usecase()
+        .count_sheet()
+        .map_err(|e| e.to_string())?;
+
+    let sheets = sheets
+        .into_iter()
+        .map(|sheet| CountSheetData {
+            location_id: sheet.location_id,
+            location_name: sheet.location_name,
+            lines: sheet
+                .lines
+                .into_iter()
+                .map(|line| CountSheetLineData {
+                    supply_id: line.supply_id,
+                    supply_name: line.supply_name,
+                    unit_name: line.unit_name,
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(sheets)
+}
+
 /// Command to get a stocktaking by date
 #[tauri::command]
 pub fn get_stocktaking_at(
@@ -257,6 +391,7 @@ pub fn get_stocktaking_at(
         .search(SearchStocktakingQuery {
             period_start: Some(start),
             period_end: Some(end),
+            category_id: None,
         })
         .map_err(|e| e.to_string())?;
 
@@ -275,6 +410,10 @@ pub fn get_stocktaking_at(
                     unit_price: record.unit_price,
                     quantity: record.quantity,
                     total_price: record.total_price,
+                    valuation_method: record.valuation_method,
+                    location_id: record.location_id,
+                    location_name: record.location_name,
+                    lot_number: record.lot_number,
                 })
                 .collect::<Vec<StocktakingRecordData>>(),
         })
@@ -293,6 +432,133 @@ pub fn delete_stocktaking(app: tauri::State<Stocker>, id: String) -> Result<(),
     Ok(())
 }
 
+/// Command to list deleted stocktakings in the trash
+#[tauri::command]
+pub fn list_deleted_stocktakings(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<TrashedData<StocktakingData>>, String> {
+    let stocktakings = app
+        .stocktaking_usecase()
+        .list_deleted()
+        .map_err(|e| e.to_string())?;
+
+    Ok(stocktakings
+        .into_iter()
+        .map(|trashed| {
+            trashed_data(trashed, |stocktaking| StocktakingData {
+                id: stocktaking.id,
+                stocktaking_date: stocktaking.stocktaken_date,
+                total_price: stocktaking.total_price,
+                records: stocktaking
+                    .records
+                    .into_iter()
+                    .map(|record| StocktakingRecordData {
+                        supply_id: record.supply_id,
+                        supply_name: record.supply_name,
+                        unit_name: record.unit_name,
+                        unit_price: record.unit_price,
+                        quantity: record.quantity,
+                        total_price: record.total_price,
+                        valuation_method: record.valuation_method,
+                        location_id: record.location_id,
+                        location_name: record.location_name,
+                        lot_number: record.lot_number,
+                    })
+                    .collect::<Vec<StocktakingRecordData>>(),
+            })
+        })
+        .collect())
+}
+
+/// Command to restore a deleted stocktaking from the trash
+#[tauri::command]
+pub fn restore_stocktaking(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.stocktaking_usecase()
+        .restore(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a stocktaking in the trash permanently
+#[tauri::command]
+pub fn purge_stocktaking(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.stocktaking_usecase()
+        .purge(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to list the versions of a stocktaking, the current one last
+#[tauri::command]
+pub fn list_stocktaking_revisions(
+    app: tauri::State<Stocker>,
+    id: String,
+) -> Result<Vec<RevisionData<StocktakingData>>, String> {
+    let revisions = app
+        .stocktaking_usecase()
+        .list_revisions(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(revisions
+        .into_iter()
+        .map(|revision| {
+            revision_data(revision, |stocktaking| StocktakingData {
+                id: stocktaking.id,
+                stocktaking_date: stocktaking.stocktaken_date,
+                total_price: stocktaking.total_price,
+                records: stocktaking
+                    .records
+                    .into_iter()
+                    .map(|record| StocktakingRecordData {
+                        supply_id: record.supply_id,
+                        supply_name: record.supply_name,
+                        unit_name: record.unit_name,
+                        unit_price: record.unit_price,
+                        quantity: record.quantity,
+                        total_price: record.total_price,
+                        valuation_method: record.valuation_method,
+                        location_id: record.location_id,
+                        location_name: record.location_name,
+                        lot_number: record.lot_number,
+                    })
+                    .collect::<Vec<StocktakingRecordData>>(),
+            })
+        })
+        .collect())
+}
+
+/// Command to compare the lines of two versions of a stocktaking
+#[tauri::command]
+pub fn diff_stocktaking_revisions(
+    app: tauri::State<Stocker>,
+    id: String,
+    from: u32,
+    to: u32,
+) -> Result<Vec<LineDiffData>, String> {
+    let diffs = app
+        .stocktaking_usecase()
+        .diff_revisions(id, from, to)
+        .map_err(|e| e.to_string())?;
+
+    Ok(diffs.into_iter().map(line_diff_data).collect())
+}
+
+/// Command to revert a stocktaking to one of its versions
+#[tauri::command]
+pub fn revert_stocktaking(
+    app: tauri::State<Stocker>,
+    id: String,
+    revision: u32,
+) -> Result<(), String> {
+    app.stocktaking_usecase()
+        .revert(id, revision)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
 /// Command to download stocktaking CSV
 #[tauri::command]
 pub fn download_stocktaking_csv(
@@ -306,14 +572,27 @@ pub fn download_stocktaking_csv(
         .map_err(|e| e.to_string())?
         .ok_or("Stocktaking not found".to_string())?;
 
-    let mut csv = "\"仕入品名\",\"単位\",\"単価\",\"数量\",\"金額\"\n".to_string();
+    let mut csv =
+        "\"保管場所\",\"仕入品名\",\"単位\",\"単価\",\"数量\",\"金額\",\"評価方法\"\n".to_string();
 
     for record in stocktaking.records {
-        let amount = (record.unit_price as f64 * record.quantity).round() as u32;
+        let valuation_method = match record.valuation_method.as_deref() {
+            Some("last_purchase_price") => "最終仕入原価法",
+            Some("moving_average") => "移動平均法",
+            Some("periodic_average") => "総平均法",
+            Some("fifo") => "先入先出法",
+            _ => "手入力",
+        };
 
         csv.push_str(&format!(
-            "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"\n",
-            record.supply_name, record.unit_name, record.unit_price, record.quantity, amount
+            "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"\n",
+            record.location_name.unwrap_or_default(),
+            record.supply_name,
+            record.unit_name,
+            record.unit_price.unwrap_or_default(),
+            record.quantity,
+            record.total_price.unwrap_or_default(),
+            valuation_method
         ));
     }
 
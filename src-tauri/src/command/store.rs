@@ -0,0 +1,157 @@
+//! Commands related to stores
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::command::{read_config, trashed_data, write_config, TrashedData};
+use crate::core::domain::values::store::StoreId;
+use crate::core::provided_ports::{
+    RegisterStoreCommand, StoreDTO, StoreUsecase, UpdateStoreCommand,
+};
+use crate::core::stocker::Stocker;
+
+/// Data of store
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreData {
+    pub id: String,
+    pub name: String,
+}
+
+/// Command to add a new store
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddStoreCommand {
+    pub store_name: String,
+}
+
+/// Command to rename a store
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditStoreCommand {
+    pub store_id: String,
+    pub store_name: String,
+}
+
+/// Converts a store DTO into its data.
+fn store_data(store: StoreDTO) -> StoreData {
+    StoreData {
+        id: store.id,
+        name: store.name,
+    }
+}
+
+/// Command to list all stores
+#[tauri::command]
+pub fn list_all_stores(app: tauri::State<Stocker>) -> Result<Vec<StoreData>, String> {
+    let stores = app.store_usecase().list().map_err(|e| e.to_string())?;
+
+    Ok(stores.into_iter().map(store_data).collect())
+}
+
+/// Command to register a new store
+#[tauri::command]
+pub fn register_store(
+    app: tauri::State<Stocker>,
+    command: AddStoreCommand,
+) -> Result<StoreData, String> {
+    let store = app
+        .store_usecase()
+        .register(RegisterStoreCommand {
+            store_name: command.store_name,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(store_data(store))
+}
+
+/// Command to rename a store
+#[tauri::command]
+pub fn update_store(app: tauri::State<Stocker>, command: EditStoreCommand) -> Result<(), String> {
+    app.store_usecase()
+        .update(UpdateStoreCommand {
+            store_id: command.store_id,
+            store_name: command.store_name,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a store without any data
+#[tauri::command]
+pub fn delete_store(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    if app.current_store().as_str() == id.trim() {
+        return Err(format!("the current store cannot be deleted."));
+    }
+
+    app.store_usecase().delete(id).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to list deleted stores in the trash
+#[tauri::command]
+pub fn list_deleted_stores(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<TrashedData<StoreData>>, String> {
+    let stores = app
+        .store_usecase()
+        .list_deleted()
+        .map_err(|e| e.to_string())?;
+
+    Ok(stores
+        .into_iter()
+        .map(|trashed| trashed_data(trashed, store_data))
+        .collect())
+}
+
+/// Command to restore a deleted store from the trash
+#[tauri::command]
+pub fn restore_store(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.store_usecase().restore(id).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a store in the trash permanently
+#[tauri::command]
+pub fn purge_store(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.store_usecase().purge(id).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to get the store data is read from and recorded in
+#[tauri::command]
+pub fn get_current_store(app: tauri::State<Stocker>) -> Result<Option<StoreData>, String> {
+    let store = app
+        .store_usecase()
+        .get(app.current_store().as_str())
+        .map_err(|e| e.to_string())?;
+
+    Ok(store.map(store_data))
+}
+
+/// Command to switch the store data is read from and recorded in
+#[tauri::command]
+pub fn switch_store(
+    app: AppHandle,
+    state: tauri::State<Stocker>,
+    id: String,
+) -> Result<StoreData, String> {
+    let store = state
+        .store_usecase()
+        .get(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or(format!("store does not exist."))?;
+
+    let mut config = read_config(&app)?;
+
+    config.current_store_id = Some(store.id.clone());
+
+    write_config(&app, &config)?;
+
+    state.set_current_store(StoreId::new(&store.id).map_err(|e| e.to_string())?);
+
+    Ok(store_data(store))
+}
@@ -11,14 +11,108 @@ use crate::core::stocker::Stocker;
 pub struct SupplierData {
     id: String,
     name: String,
+    invoice_registration_number: Option<String>,
+    invoice_registered: bool,
+    contact: SupplierContactData,
+    notes: Option<String>,
+    billing_terms: Option<BillingTermsData>,
     supplies: Vec<SupplyData>,
 }
 
+/// Contact details of supplier, empty strings read as not provided
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplierContactData {
+    #[serde(default)]
+    contact_person: Option<String>,
+    #[serde(default)]
+    phone_number: Option<String>,
+    #[serde(default)]
+    fax_number: Option<String>,
+    #[serde(default)]
+    email: Option<String>,
+    /// 7 digits, with or without a hyphen
+    #[serde(default)]
+    postal_code: Option<String>,
+    #[serde(default)]
+    address: Option<String>,
+}
+
+/// Closing and payment days of supplier
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BillingTermsData {
+    /// 1 to 28, or 31 for the end of the month
+    closing_day: u32,
+    /// 0 for the closing month, 1 for the next month and so on
+    payment_months_later: u32,
+    /// 1 to 28, or 31 for the end of the month
+    payment_day: u32,
+    /// `bank_transfer`, `direct_debit`, `cash` or `other`
+    payment_method: String,
+}
+
+/// Converts contact details into their command data
+fn contact_data(contact: SupplierContactDTO) -> SupplierContactData {
+    SupplierContactData {
+        contact_person: contact.contact_person,
+        phone_number: contact.phone_number,
+        fax_number: contact.fax_number,
+        email: contact.email,
+        postal_code: contact.postal_code,
+        address: contact.address,
+    }
+}
+
+/// Converts command data of contact details into their DTO
+fn contact_dto(contact: SupplierContactData) -> SupplierContactDTO {
+    SupplierContactDTO {
+        contact_person: contact.contact_person,
+        phone_number: contact.phone_number,
+        fax_number: contact.fax_number,
+        email: contact.email,
+        postal_code: contact.postal_code,
+        address: contact.address,
+    }
+}
+
+/// Converts billing terms into their command data
+fn billing_terms_data(terms: BillingTermsDTO) -> BillingTermsData {
+    BillingTermsData {
+        closing_day: terms.closing_day,
+        payment_months_later: terms.payment_months_later,
+        payment_day: terms.payment_day,
+        payment_method: terms.payment_method,
+    }
+}
+
+/// Converts command data of billing terms into their DTO
+fn billing_terms_dto(terms: BillingTermsData) -> BillingTermsDTO {
+    BillingTermsDTO {
+        closing_day: terms.closing_day,
+        payment_months_later: terms.payment_months_later,
+        payment_day: terms.payment_day,
+        payment_method: terms.payment_method,
+    }
+}
+
 /// Command to add a new supplier
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AddSupplierCommand {
     supplier_name: String,
+    /// `T` followed by 13 digits
+    #[serde(default)]
+    invoice_registration_number: Option<String>,
+    #[serde(default)]
+    invoice_registered: bool,
+    #[serde(default)]
+    contact: SupplierContactData,
+    #[serde(default)]
+    notes: Option<String>,
+    /// None if not agreed
+    #[serde(default)]
+    billing_terms: Option<BillingTermsData>,
 }
 
 /// Command to update a supplier
@@ -27,6 +121,19 @@ pub struct AddSupplierCommand {
 pub struct UpdateSupplierCommand {
     supplier_id: String,
     supplier_name: String,
+    /// `T` followed by 13 digits
+    #[serde(default)]
+    invoice_registration_number: Option<String>,
+    /// Keeps the current registration if not provided
+    #[serde(default)]
+    invoice_registered: Option<bool>,
+    #[serde(default)]
+    contact: SupplierContactData,
+    #[serde(default)]
+    notes: Option<String>,
+    /// None if not agreed
+    #[serde(default)]
+    billing_terms: Option<BillingTermsData>,
 }
 
 /// Query to search suppliers
@@ -35,6 +142,14 @@ pub struct UpdateSupplierCommand {
 pub struct SupplierQuery {
     supplier_name: Option<String>,
     supply_name: Option<String>,
+    #[serde(default)]
+    contact_person: Option<String>,
+    /// Matches phone and FAX numbers ignoring hyphens
+    #[serde(default)]
+    phone_number: Option<String>,
+    /// suppliers of supplies in the category or its descendants
+    #[serde(default)]
+    category_id: Option<String>,
 }
 
 /// Command to list all suppliers
@@ -51,12 +166,7 @@ pub fn list_all_suppliers(app: tauri::State<Stocker>) -> Result<Vec<SupplierData
                 .iter()
                 .filter_map(|supply| {
                     if supply.supplier_id.eq(&supplier.id) {
-                        Some(SupplyData {
-                            id: supply.id.clone(),
-                            name: supply.name.clone(),
-                            unit_name: supply.unit_name.clone(),
-                            supplier_id: supply.supplier_id.clone(),
-                        })
+                        Some(supply_data(supply.clone()))
                     } else {
                         None
                     }
@@ -66,6 +176,11 @@ pub fn list_all_suppliers(app: tauri::State<Stocker>) -> Result<Vec<SupplierData
             SupplierData {
                 id: supplier.id,
                 name: supplier.name,
+                invoice_registration_number: supplier.invoice_registration_number,
+                invoice_registered: supplier.invoice_registered,
+                contact: contact_data(supplier.contact),
+                notes: supplier.notes,
+                billing_terms: supplier.billing_terms.map(billing_terms_data),
                 supplies,
             }
         })
@@ -88,17 +203,17 @@ pub fn get_supplier_by_id(
             .get_of_supplier(supplier.id.to_string())
             .map_err(|e| e.to_string())?
             .into_iter()
-            .map(|supply| SupplyData {
-                id: supply.id,
-                name: supply.name,
-                unit_name: supply.unit_name,
-                supplier_id: supply.supplier_id,
-            })
+            .map(supply_data)
             .collect::<Vec<SupplyData>>();
 
         Some(SupplierData {
             id: supplier.id,
             name: supplier.name,
+            invoice_registration_number: supplier.invoice_registration_number,
+            invoice_registered: supplier.invoice_registered,
+            contact: contact_data(supplier.contact),
+            notes: supplier.notes,
+            billing_terms: supplier.billing_terms.map(billing_terms_data),
             supplies,
         })
     } else {
@@ -119,6 +234,9 @@ pub fn search_suppliers(
         .search(SearchSuppliersQuery {
             supplier_name: query.supplier_name.clone(),
             supply_name: query.supply_name.clone(),
+            contact_person: query.contact_person.clone(),
+            phone_number: query.phone_number.clone(),
+            category_id: query.category_id.clone(),
         })
         .map_err(|e| e.to_string())?;
 
@@ -130,17 +248,17 @@ pub fn search_suppliers(
                 .get_of_supplier(supplier.id.to_string())
                 .map_err(|e| e.to_string())?
                 .into_iter()
-                .map(|supply| SupplyData {
-                    id: supply.id,
-                    name: supply.name,
-                    unit_name: supply.unit_name,
-                    supplier_id: supply.supplier_id,
-                })
+                .map(supply_data)
                 .collect::<Vec<SupplyData>>();
 
             Ok(SupplierData {
                 id: supplier.id,
                 name: supplier.name,
+                invoice_registration_number: supplier.invoice_registration_number,
+                invoice_registered: supplier.invoice_registered,
+                contact: contact_data(supplier.contact),
+                notes: supplier.notes,
+                billing_terms: supplier.billing_terms.map(billing_terms_data),
                 supplies,
             })
         })
@@ -158,6 +276,11 @@ pub fn register_supplier(
     app.supplier_usecase()
         .register(RegisterSupplierCommand {
             supplier_name: command.supplier_name,
+            invoice_registration_number: command.invoice_registration_number,
+            invoice_registered: command.invoice_registered,
+            contact: contact_dto(command.contact),
+            notes: command.notes,
+            billing_terms: command.billing_terms.map(billing_terms_dto),
         })
         .map_err(|e| e.to_string())?;
 
@@ -174,6 +297,11 @@ pub fn update_supplier(
         .update(provided_ports::UpdateSupplierCommand {
             supplier_id: command.supplier_id,
             supplier_name: command.supplier_name,
+            invoice_registration_number: command.invoice_registration_number,
+            invoice_registered: command.invoice_registered,
+            contact: contact_dto(command.contact),
+            notes: command.notes,
+            billing_terms: command.billing_terms.map(billing_terms_dto),
         })
         .map_err(|e| e.to_string())?;
 
@@ -189,3 +317,59 @@ pub fn delete_supplier(app: tauri::State<Stocker>, id: String) -> Result<(), Str
 
     Ok(())
 }
+
+/// Command to list deleted suppliers in the trash
+#[tauri::command]
+pub fn list_deleted_suppliers(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<TrashedData<SupplierData>>, String> {
+    let suppliers = app
+        .supplier_usecase()
+        .list_deleted()
+        .map_err(|e| e.to_string())?;
+
+    let mut trashed = Vec::new();
+
+    for supplier in suppliers {
+        let supplies = app
+            .supply_usecase()
+            .get_of_supplier(supplier.item.id.to_string())
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .map(supply_data)
+            .collect::<Vec<SupplyData>>();
+
+        trashed.push(trashed_data(supplier, |supplier| SupplierData {
+            id: supplier.id,
+            name: supplier.name,
+            invoice_registration_number: supplier.invoice_registration_number,
+            invoice_registered: supplier.invoice_registered,
+            contact: contact_data(supplier.contact),
+            notes: supplier.notes,
+            billing_terms: supplier.billing_terms.map(billing_terms_data),
+            supplies: supplies.clone(),
+        }));
+    }
+
+    Ok(trashed)
+}
+
+/// Command to restore a deleted supplier from the trash
+#[tauri::command]
+pub fn restore_supplier(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.supplier_usecase()
+        .restore(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a supplier in the trash permanently
+#[tauri::command]
+pub fn purge_supplier(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.supplier_usecase()
+        .purge(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
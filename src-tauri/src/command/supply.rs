@@ -1,6 +1,7 @@
 //! Commands related to supplies
 use serde::{Deserialize, Serialize};
 
+use crate::command::{trashed_data, TrashedData};
 use crate::core::provided_ports::{self, *};
 use crate::core::stocker::Stocker;
 
@@ -11,7 +12,43 @@ pub struct SupplyData {
     pub id: String,
     pub name: String,
     pub unit_name: String,
+    /// preferred supplier
     pub supplier_id: String,
+    pub offers: Vec<SupplyOfferData>,
+    pub tax_category: String,
+    pub purchase_unit_id: String,
+    pub stock_unit_id: String,
+    pub conversions: Vec<UnitConversionData>,
+    pub category_id: Option<String>,
+    /// in the stock unit, none if the supply is not reordered
+    pub par_level: Option<f64>,
+    pub reorder_point: Option<f64>,
+    /// default storage location, none if the supply is not placed
+    pub location_id: Option<String>,
+    pub location_sort_order: Option<u32>,
+}
+
+/// Data of a supplier offering a supply
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplyOfferData {
+    pub supplier_id: String,
+    #[serde(default)]
+    pub item_code: Option<String>,
+    /// current price per purchase unit, not quoted if not provided
+    #[serde(default)]
+    pub unit_price: Option<f64>,
+    #[serde(default)]
+    pub preferred: bool,
+}
+
+/// Data of conversion of a unit into the stock unit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitConversionData {
+    pub unit_id: String,
+    /// number of stock units in one unit
+    pub factor: f64,
 }
 
 /// Command to add a new supply
@@ -21,6 +58,17 @@ pub struct AddSupplyCommand {
     pub supplier_id: String,
     pub supply_name: String,
     pub unit_name: String,
+    /// `standard`, `reduced` or `exempt`
+    #[serde(default)]
+    pub tax_category: Option<String>,
+    /// defaults to the purchase unit
+    #[serde(default)]
+    pub stock_unit_id: Option<String>,
+    #[serde(default)]
+    pub conversions: Vec<UnitConversionData>,
+    /// not categorized if not provided
+    #[serde(default)]
+    pub category_id: Option<String>,
 }
 
 /// Command to update a supply
@@ -30,6 +78,87 @@ pub struct UpdateSupplyCommand {
     pub supply_id: String,
     pub supply_name: String,
     pub unit_name: String,
+    /// `standard`, `reduced` or `exempt`
+    #[serde(default)]
+    pub tax_category: Option<String>,
+    /// keeps the current stock unit if not provided
+    #[serde(default)]
+    pub stock_unit_id: Option<String>,
+    /// keeps the current conversions if not provided
+    #[serde(default)]
+    pub conversions: Option<Vec<UnitConversionData>>,
+    /// keeps the current category if not provided, an empty id removes it
+    #[serde(default)]
+    pub category_id: Option<String>,
+}
+
+/// Command to set the par level and reorder point of a supply
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetReorderLevelsData {
+    pub supply_id: String,
+    /// in the stock unit, clears the levels if not provided
+    #[serde(default)]
+    pub par_level: Option<f64>,
+    /// defaults to the par level
+    #[serde(default)]
+    pub reorder_point: Option<f64>,
+}
+
+/// Command to set the suppliers offering a supply
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetSupplyOffersData {
+    pub supply_id: String,
+    /// the first is preferred unless another is
+    pub offers: Vec<SupplyOfferData>,
+}
+
+/// Converts a supply DTO into its data.
+pub(crate) fn supply_data(supply: SupplyDTO) -> SupplyData {
+    SupplyData {
+        id: supply.id,
+        name: supply.name,
+        unit_name: supply.unit_name,
+        supplier_id: supply.supplier_id,
+        offers: supply
+            .offers
+            .into_iter()
+            .map(|offer| SupplyOfferData {
+                supplier_id: offer.supplier_id,
+                item_code: offer.item_code,
+                unit_price: offer.unit_price,
+                preferred: offer.preferred,
+            })
+            .collect(),
+        tax_category: supply.tax_category,
+        purchase_unit_id: supply.purchase_unit_id,
+        stock_unit_id: supply.stock_unit_id,
+        conversions: supply
+            .conversions
+            .into_iter()
+            .map(|conversion| UnitConversionData {
+                unit_id: conversion.unit_id,
+                factor: conversion.factor,
+            })
+            .collect(),
+        category_id: supply.category_id,
+        par_level: supply.par_level,
+        reorder_point: supply.reorder_point,
+        location_id: supply.location_id,
+        location_sort_order: supply.location_sort_order,
+    }
+}
+
+/// Converts conversion data into DTOs.
+fn conversion_dtos(conversions: Vec<UnitConversionData>) -> Vec<UnitConversionDTO> {
+    conversions
+        .into_iter()
+        .map(|conversion| UnitConversionDTO {
+            unit_id: conversion.unit_id,
+            factor: conversion.factor,
+        })
+        .collect()
 }
 
 /// Command to list all supplies
@@ -37,15 +166,7 @@ pub struct UpdateSupplyCommand {
 pub fn list_all_supplies(app: tauri::State<Stocker>) -> Result<Vec<SupplyData>, String> {
     let supplies = app.supply_usecase().list().map_err(|e| e.to_string())?;
 
-    let supplies: Vec<SupplyData> = supplies
-        .into_iter()
-        .map(|supply| SupplyData {
-            id: supply.id,
-            name: supply.name,
-            unit_name: supply.unit_name,
-            supplier_id: supply.supplier_id,
-        })
-        .collect();
+    let supplies: Vec<SupplyData> = supplies.into_iter().map(supply_data).collect();
 
     Ok(supplies)
 }
@@ -58,14 +179,7 @@ pub fn get_supply_by_id(
 ) -> Result<Option<SupplyData>, String> {
     let supply = app.supply_usecase().get(&id).map_err(|e| e.to_string())?;
 
-    let supply = supply.and_then(|supply| {
-        Some(SupplyData {
-            id: supply.id,
-            name: supply.name,
-            unit_name: supply.unit_name,
-            supplier_id: supply.supplier_id,
-        })
-    });
+    let supply = supply.map(supply_data);
 
     Ok(supply)
 }
@@ -81,6 +195,10 @@ pub fn register_supply(
             supply_name: command.supply_name,
             unit_name: command.unit_name,
             supplier_id: command.supplier_id,
+            tax_category: command.tax_category,
+            stock_unit_id: command.stock_unit_id,
+            conversions: conversion_dtos(command.conversions),
+            category_id: command.category_id,
         })
         .map_err(|e| e.to_string())?;
 
@@ -110,6 +228,52 @@ pub fn update_supply(
             supply_name: command.supply_name,
             unit_name: command.unit_name,
             supplier_id: supply.supplier_id,
+            tax_category: command.tax_category,
+            stock_unit_id: command.stock_unit_id,
+            conversions: command.conversions.map(conversion_dtos),
+            category_id: command.category_id,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to set or clear the par level and reorder point of a supply
+#[tauri::command]
+pub fn set_supply_reorder_levels(
+    app: tauri::State<Stocker>,
+    command: SetReorderLevelsData,
+) -> Result<(), String> {
+    app.supply_usecase()
+        .set_reorder_levels(SetReorderLevelsCommand {
+            supply_id: command.supply_id,
+            par_level: command.par_level,
+            reorder_point: command.reorder_point,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to replace the suppliers offering a supply
+#[tauri::command]
+pub fn set_supply_offers(
+    app: tauri::State<Stocker>,
+    command: SetSupplyOffersData,
+) -> Result<(), String> {
+    app.supply_usecase()
+        .set_offers(SetSupplyOffersCommand {
+            supply_id: command.supply_id,
+            offers: command
+                .offers
+                .into_iter()
+                .map(|offer| SupplyOfferDTO {
+                    supplier_id: offer.supplier_id,
+                    item_code: offer.item_code,
+                    unit_price: offer.unit_price,
+                    preferred: offer.preferred,
+                })
+                .collect(),
         })
         .map_err(|e| e.to_string())?;
 
@@ -122,3 +286,37 @@ pub fn delete_supply(app: tauri::State<Stocker>, id: String) -> Result<(), Strin
 
     Ok(())
 }
+
+/// Command to list deleted supplies in the trash
+#[tauri::command]
+pub fn list_deleted_supplies(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<TrashedData<SupplyData>>, String> {
+    let supplies = app
+        .supply_usecase()
+        .list_deleted()
+        .map_err(|e| e.to_string())?;
+
+    Ok(supplies
+        .into_iter()
+        .map(|trashed| trashed_data(trashed, supply_data))
+        .collect())
+}
+
+/// Command to restore a deleted supply from the trash
+#[tauri::command]
+pub fn restore_supply(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.supply_usecase()
+        .restore(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a supply in the trash permanently
+#[tauri::command]
+pub fn purge_supply(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.supply_usecase().purge(id).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
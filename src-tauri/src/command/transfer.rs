@@ -0,0 +1,398 @@
+//! Commands related to transfers of stock between stores
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+
+use crate::command::{trashed_data, TrashedData};
+use crate::core::provided_ports::{
+    JournalUsecase, RecordTransferCommand, SearchJournalsQuery, SearchTransfersQuery, TransferDTO,
+    TransferLineDTO, TransferUsecase, UpdateTransferCommand,
+};
+use crate::core::stocker::Stocker;
+
+/// Data of transfer
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferData {
+    id: String,
+    transferred_date: i64,
+    source_store_id: String,
+    source_store_name: String,
+    destination_store_id: String,
+    destination_store_name: String,
+    total_price: f64,
+    lines: Vec<TransferLineData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferLineData {
+    /// Supply of the source store
+    supply_id: String,
+    /// Supply of the destination store, found by the supply name if empty
+    #[serde(default)]
+    destination_supply_id: String,
+    /// Taken from the supply
+    #[serde(default)]
+    supply_name: String,
+    /// Stock unit of the supply
+    #[serde(default)]
+    unit_name: String,
+    quantity: f64,
+    unit_price: f64,
+    /// Computed by the core
+    #[serde(default)]
+    total_price: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordTransferData {
+    transferred_date: i64,
+    source_store_id: String,
+    destination_store_id: String,
+    lines: Vec<TransferLineData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTransferData {
+    id: String,
+    transferred_date: i64,
+    source_store_id: String,
+    destination_store_id: String,
+    lines: Vec<TransferLineData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferQuery {
+    #[serde(default)]
+    period_start: Option<i64>,
+    #[serde(default)]
+    period_end: Option<i64>,
+}
+
+/// Line of the journal CSV, a purchase or a transfer
+struct JournalCsvRow {
+    date: i64,
+    /// `仕入`, `移動入庫` or `移動出庫`
+    kind: &'static str,
+    /// supplier of a purchase, other store of a transfer
+    counterpart: String,
+    supply_name: String,
+    unit_name: String,
+    unit_price: f64,
+    quantity: f64,
+    total_price: f64,
+}
+
+/// Converts a transfer DTO into its command data
+fn transfer_data(transfer: TransferDTO) -> TransferData {
+    TransferData {
+        id: transfer.id,
+        transferred_date: transfer.transferred_date,
+        source_store_id: transfer.source_store_id,
+        source_store_name: transfer.source_store_name,
+        destination_store_id: transfer.destination_store_id,
+        destination_store_name: transfer.destination_store_name,
+        total_price: transfer.total_price,
+        lines: transfer
+            .lines
+            .into_iter()
+            .map(|line| TransferLineData {
+                supply_id: line.supply_id,
+                destination_supply_id: line.destination_supply_id,
+                supply_name: line.supply_name,
+                unit_name: line.unit_name,
+                quantity: line.quantity,
+                unit_price: line.unit_price,
+                total_price: line.total_price,
+            })
+            .collect(),
+    }
+}
+
+/// Converts command data of a line into its DTO
+fn line_dto(line: TransferLineData) -> TransferLineDTO {
+    TransferLineDTO {
+        supply_id: line.supply_id,
+        destination_supply_id: line.destination_supply_id,
+        supply_name: line.supply_name,
+        unit_name: line.unit_name,
+        quantity: line.quantity,
+        unit_price: line.unit_price,
+        total_price: line.total_price,
+    }
+}
+
+/// Formats a timestamp as a local date for CSV
+fn csv_date(timestamp: i64) -> String {
+    Local
+        .timestamp_millis_opt(timestamp)
+        .single()
+        .map(|date| date.format("%Y/%m/%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Asks where to save the CSV and writes it
+fn save_csv(app: &AppHandle, csv: String) {
+    app.dialog()
+        .file()
+        .add_filter("csv", &["csv"])
+        .save_file(|file_path| {
+            if let Some(file_path) = file_path {
+                let path = file_path.as_path().ok_or("Failed to get path").unwrap();
+
+                std::fs::write(path, csv)
+                    .map_err(|e| e.to_string())
+                    .unwrap();
+            }
+        });
+}
+
+#[tauri::command]
+pub fn list_all_transfers(app: tauri::State<Stocker>) -> Result<Vec<TransferData>, String> {
+    let transfers = app.transfer_usecase().list().map_err(|e| e.to_string())?;
+
+    Ok(transfers.into_iter().map(transfer_data).collect())
+}
+
+#[tauri::command]
+pub fn get_transfer_by_id(
+    app: tauri::State<Stocker>,
+    id: String,
+) -> Result<Option<TransferData>, String> {
+    let transfer = app.transfer_usecase().get(&id).map_err(|e| e.to_string())?;
+
+    Ok(transfer.map(transfer_data))
+}
+
+#[tauri::command]
+pub fn search_transfers(
+    app: tauri::State<Stocker>,
+    query: TransferQuery,
+) -> Result<Vec<TransferData>, String> {
+    let transfers = app
+        .transfer_usecase()
+        .search(SearchTransfersQuery {
+            period_start: query.period_start,
+            period_end: query.period_end,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(transfers.into_iter().map(transfer_data).collect())
+}
+
+#[tauri::command]
+pub fn record_transfer(
+    app: tauri::State<Stocker>,
+    command: RecordTransferData,
+) -> Result<TransferData, String> {
+    let transfer = app
+        .transfer_usecase()
+        .record(RecordTransferCommand {
+            transferred_date: command.transferred_date,
+            source_store_id: command.source_store_id,
+            destination_store_id: command.destination_store_id,
+            lines: command.lines.into_iter().map(line_dto).collect(),
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(transfer_data(transfer))
+}
+
+#[tauri::command]
+pub fn update_transfer(
+    app: tauri::State<Stocker>,
+    command: UpdateTransferData,
+) -> Result<(), String> {
+    app.transfer_usecase()
+        .update(UpdateTransferCommand {
+            transfer_id: command.id,
+            transferred_date: command.transferred_date,
+            source_store_id: command.source_store_id,
+            destination_store_id: command.destination_store_id,
+            lines: command.lines.into_iter().map(line_dto).collect(),
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_transfer(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.transfer_usecase()
+        .delete(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to list deleted transfers in the trash
+#[tauri::command]
+pub fn list_deleted_transfers(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<TrashedData<TransferData>>, String> {
+    let transfers = app
+        .transfer_usecase()
+        .list_deleted()
+        .map_err(|e| e.to_string())?;
+
+    Ok(transfers
+        .into_iter()
+        .map(|trashed| trashed_data(trashed, transfer_data))
+        .collect())
+}
+
+/// Command to restore a deleted transfer from the trash
+#[tauri::command]
+pub fn restore_transfer(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.transfer_usecase()
+        .restore(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a transfer in the trash permanently
+#[tauri::command]
+pub fn purge_transfer(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.transfer_usecase()
+        .purge(id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to download the slip of a transfer as CSV
+#[tauri::command]
+pub fn download_transfer_slip(
+    app: AppHandle,
+    state: tauri::State<Stocker>,
+    id: String,
+) -> Result<(), String> {
+    let transfer = state
+        .transfer_usecase()
+        .get(&id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Transfer not found".to_string())?;
+
+    let mut csv = format!(
+        "\"移動伝票\",\"{}\"\n\"日付\",\"{}\"\n\"移動元\",\"{}\"\n\"移動先\",\"{}\"\n\n",
+        transfer.id,
+        csv_date(transfer.transferred_date),
+        transfer.source_store_name,
+        transfer.destination_store_name
+    );
+
+    csv.push_str("\"仕入品名\",\"単位\",\"単価\",\"数量\",\"金額\"\n");
+
+    for line in transfer.lines {
+        csv.push_str(&format!(
+            "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"\n",
+            line.supply_name, line.unit_name, line.unit_price, line.quantity, line.total_price
+        ));
+    }
+
+    csv.push_str(&format!(
+        "\"合計\",\"\",\"\",\"\",\"{}\"\n",
+        transfer.total_price
+    ));
+
+    save_csv(&app, csv);
+
+    Ok(())
+}
+
+/// Command to download journal lines of the store with its transfers as CSV
+#[tauri::command]
+pub fn download_journal_csv(
+    app: AppHandle,
+    state: tauri::State<Stocker>,
+    query: TransferQuery,
+) -> Result<(), String> {
+    let journals = state
+        .journal_usecase()
+        .search(SearchJournalsQuery {
+            period_start: query.period_start,
+            period_end: query.period_end,
+            supplier_name: None,
+            supply_name: None,
+            category_id: None,
+        })
+        .map_err(|e| e.to_string())?;
+
+    let transfers = state
+        .transfer_usecase()
+        .search(SearchTransfersQuery {
+            period_start: query.period_start,
+            period_end: query.period_end,
+        })
+        .map_err(|e| e.to_string())?;
+
+    let current_store = state.current_store();
+
+    let mut rows: Vec<JournalCsvRow> = Vec::new();
+
+    for journal in journals {
+        for record in journal.records {
+            rows.push(JournalCsvRow {
+                date: journal.entry_date,
+                kind: "仕入",
+                counterpart: record.supplier_name,
+                supply_name: record.supply_name,
+                unit_name: record.unit_name,
+                unit_price: record.unit_price,
+                quantity: record.quantity,
+                total_price: record.total_price,
+            });
+        }
+    }
+
+    for transfer in transfers {
+        let (kind, counterpart) = if transfer.destination_store_id == current_store.as_str() {
+            ("移動入庫", transfer.source_store_name)
+        } else {
+            ("移動出庫", transfer.destination_store_name)
+        };
+
+        for line in transfer.lines {
+            rows.push(JournalCsvRow {
+                date: transfer.transferred_date,
+                kind,
+                counterpart: counterpart.clone(),
+                supply_name: line.supply_name,
+                unit_name: line.unit_name,
+                unit_price: line.unit_price,
+                quantity: line.quantity,
+                total_price: line.total_price,
+            });
+        }
+    }
+
+    rows.sort_by_key(|row| row.date);
+
+    let mut csv =
+        "\"日付\",\"区分\",\"仕入先・店舗\",\"仕入品名\",\"単位\",\"単価\",\"数量\",\"金額\"\n"
+            .to_string();
+
+    for row in rows {
+        csv.push_str(&format!(
+            "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"\n",
+            csv_date(row.date),
+            row.kind,
+            row.counterpart,
+            row.supply_name,
+            row.unit_name,
+            row.unit_price,
+            row.quantity,
+            row.total_price
+        ));
+    }
+
+    save_csv(&app, csv);
+
+    Ok(())
+}
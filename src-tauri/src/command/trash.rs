@@ -0,0 +1,22 @@
+//! Data of records in the trash
+use serde::Serialize;
+
+use crate::core::provided_ports::TrashedDTO;
+
+/// Data of a record in the trash
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedData<T> {
+    #[serde(flatten)]
+    pub item: T,
+    /// timestamp the record was deleted at
+    pub deleted_at: i64,
+}
+
+/// Converts a record in the trash with `data` converting the record itself.
+pub fn trashed_data<T, D>(trashed: TrashedDTO<T>, data: impl Fn(T) -> D) -> TrashedData<D> {
+    TrashedData {
+        item: data(trashed.item),
+        deleted_at: trashed.deleted_at,
+    }
+}
@@ -0,0 +1,144 @@
+//! Commands related to units of measure
+use serde::{Deserialize, Serialize};
+
+use crate::command::{trashed_data, TrashedData};
+use crate::core::provided_ports::{
+    RegisterUnitCommand, StockQuantityDTO, UnitUsecase, UpdateUnitCommand,
+};
+use crate::core::stocker::Stocker;
+
+/// Data of unit
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitData {
+    pub id: String,
+    pub name: String,
+}
+
+/// Quantity of a supply in its stock unit
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StockQuantityData {
+    pub supply_id: String,
+    pub supply_name: String,
+    pub unit_name: String,
+    pub quantity: f64,
+}
+
+/// Command to add a new unit
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddUnitCommand {
+    pub unit_name: String,
+}
+
+/// Command to rename a unit
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameUnitCommand {
+    pub unit_id: String,
+    pub unit_name: String,
+}
+
+/// Converts a stock quantity DTO into its data.
+pub(crate) fn stock_quantity_data(quantity: StockQuantityDTO) -> StockQuantityData {
+    StockQuantityData {
+        supply_id: quantity.supply_id,
+        supply_name: quantity.supply_name,
+        unit_name: quantity.unit_name,
+        quantity: quantity.quantity,
+    }
+}
+
+/// Command to list all units
+#[tauri::command]
+pub fn list_all_units(app: tauri::State<Stocker>) -> Result<Vec<UnitData>, String> {
+    let units = app.unit_usecase().list().map_err(|e| e.to_string())?;
+
+    let units: Vec<UnitData> = units
+        .into_iter()
+        .map(|unit| UnitData {
+            id: unit.id,
+            name: unit.name,
+        })
+        .collect();
+
+    Ok(units)
+}
+
+/// Command to register a new unit
+#[tauri::command]
+pub fn register_unit(
+    app: tauri::State<Stocker>,
+    command: AddUnitCommand,
+) -> Result<UnitData, String> {
+    let unit = app
+        .unit_usecase()
+        .register(RegisterUnitCommand {
+            unit_name: command.unit_name,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(UnitData {
+        id: unit.id,
+        name: unit.name,
+    })
+}
+
+/// Command to rename a unit
+#[tauri::command]
+pub fn update_unit(app: tauri::State<Stocker>, command: RenameUnitCommand) -> Result<(), String> {
+    app.unit_usecase()
+        .update(UpdateUnitCommand {
+            unit_id: command.unit_id,
+            unit_name: command.unit_name,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a unit
+#[tauri::command]
+pub fn delete_unit(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.unit_usecase().delete(id).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to list deleted units in the trash
+#[tauri::command]
+pub fn list_deleted_units(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<TrashedData<UnitData>>, String> {
+    let units = app
+        .unit_usecase()
+        .list_deleted()
+        .map_err(|e| e.to_string())?;
+
+    Ok(units
+        .into_iter()
+        .map(|trashed| {
+            trashed_data(trashed, |unit| UnitData {
+                id: unit.id,
+                name: unit.name,
+            })
+        })
+        .collect())
+}
+
+/// Command to restore a deleted unit from the trash
+#[tauri::command]
+pub fn restore_unit(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.unit_usecase().restore(id).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a unit in the trash permanently
+#[tauri::command]
+pub fn purge_unit(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.unit_usecase().purge(id).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
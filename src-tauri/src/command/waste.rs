@@ -0,0 +1,250 @@
+//! Commands related to waste records
+use serde::{Deserialize, Serialize};
+
+use crate::command::{trashed_data, TrashedData};
+use crate::core::provided_ports::{
+    RecordWasteCommand, SearchWasteQuery, WasteDTO, WasteSummaryQuery, WasteUsecase,
+};
+use crate::core::stocker::Stocker;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WasteData {
+    id: String,
+    wasted_date: i64,
+    supply_id: String,
+    supply_name: String,
+    unit_name: String,
+    quantity: f64,
+    unit_price: f64,
+    total_price: f64,
+    /// `expired`, `damaged`, `over_prep` or `other`
+    reason: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordWasteData {
+    wasted_date: i64,
+    supply_id: String,
+    /// Defaults to the stock unit of the supply
+    #[serde(default)]
+    unit_name: Option<String>,
+    quantity: f64,
+    unit_price: f64,
+    reason: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WasteQuery {
+    #[serde(default)]
+    period_start: Option<i64>,
+    #[serde(default)]
+    period_end: Option<i64>,
+    #[serde(default)]
+    supply_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WasteSummaryPeriodQuery {
+    period_start: i64,
+    period_end: i64,
+}
+
+/// Waste of a period against the purchases and the counted inventory
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WasteSummaryData {
+    period_start: i64,
+    period_end: i64,
+    closing_stocktaking_id: Option<String>,
+    wasted: f64,
+    purchases: f64,
+    closing_inventory: f64,
+    /// Percentage of the purchases
+    loss_ratio: Option<f64>,
+    reasons: Vec<ReasonWasteData>,
+    supplies: Vec<SupplyWasteData>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReasonWasteData {
+    reason: String,
+    wasted: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplyWasteData {
+    supply_id: String,
+    supply_name: String,
+    quantities: Vec<WasteQuantityData>,
+    wasted: f64,
+    purchases: f64,
+    closing_inventory: f64,
+    loss_ratio: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WasteQuantityData {
+    unit_name: String,
+    quantity: f64,
+}
+
+fn waste_data(waste: WasteDTO) -> WasteData {
+    WasteData {
+        id: waste.id,
+        wasted_date: waste.wasted_date,
+        supply_id: waste.supply_id,
+        supply_name: waste.supply_name,
+        unit_name: waste.unit_name,
+        quantity: waste.quantity,
+        unit_price: waste.unit_price,
+        total_price: waste.total_price,
+        reason: waste.reason,
+    }
+}
+
+/// Command to list all waste records
+#[tauri::command]
+pub fn list_all_wastes(app: tauri::State<Stocker>) -> Result<Vec<WasteData>, String> {
+    let wastes = app.waste_usecase().list().map_err(|e| e.to_string())?;
+
+    Ok(wastes.into_iter().map(waste_data).collect())
+}
+
+/// Command to search waste records
+#[tauri::command]
+pub fn search_wastes(
+    app: tauri::State<Stocker>,
+    query: WasteQuery,
+) -> Result<Vec<WasteData>, String> {
+    let wastes = app
+        .waste_usecase()
+        .search(SearchWasteQuery {
+            period_start: query.period_start,
+            period_end: query.period_end,
+            supply_id: query.supply_id,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(wastes.into_iter().map(waste_data).collect())
+}
+
+/// Command to record stock thrown away
+#[tauri::command]
+pub fn record_waste(
+    app: tauri::State<Stocker>,
+    command: RecordWasteData,
+) -> Result<WasteData, String> {
+    let waste = app
+        .waste_usecase()
+        .record(RecordWasteCommand {
+            wasted_date: command.wasted_date,
+            supply_id: command.supply_id,
+            unit_name: command.unit_name,
+            quantity: command.quantity,
+            unit_price: command.unit_price,
+            reason: command.reason,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(waste_data(waste))
+}
+
+/// Command to delete a waste record by ID
+#[tauri::command]
+pub fn delete_waste(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.waste_usecase().delete(id).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to list deleted waste records in the trash
+#[tauri::command]
+pub fn list_deleted_wastes(
+    app: tauri::State<Stocker>,
+) -> Result<Vec<TrashedData<WasteData>>, String> {
+    let wastes = app
+        .waste_usecase()
+        .list_deleted()
+        .map_err(|e| e.to_string())?;
+
+    Ok(wastes
+        .into_iter()
+        .map(|trashed| trashed_data(trashed, waste_data))
+        .collect())
+}
+
+/// Command to restore a deleted waste record from the trash
+#[tauri::command]
+pub fn restore_waste(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.waste_usecase().restore(id).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to delete a waste record in the trash permanently
+#[tauri::command]
+pub fn purge_waste(app: tauri::State<Stocker>, id: String) -> Result<(), String> {
+    app.waste_usecase().purge(id).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Command to sum waste of a period with loss ratios
+#[tauri::command]
+pub fn get_waste_summary(
+    app: tauri::State<Stocker>,
+    query: WasteSummaryPeriodQuery,
+) -> Result<WasteSummaryData, String> {
+    let summary = app
+        .waste_usecase()
+        .summarize(WasteSummaryQuery {
+            period_start: query.period_start,
+            period_end: query.period_end,
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(WasteSummaryData {
+        period_start: summary.period_start,
+        period_end: summary.period_end,
+        closing_stocktaking_id: summary.closing_stocktaking_id,
+        wasted: summary.wasted,
+        purchases: summary.purchases,
+        closing_inventory: summary.closing_inventory,
+        loss_ratio: summary.loss_ratio,
+        reasons: summary
+            .reasons
+            .into_iter()
+            .map(|reason| ReasonWasteData {
+                reason: reason.reason,
+                wasted: reason.wasted,
+            })
+            .collect(),
+        supplies: summary
+            .supplies
+            .into_iter()
+            .map(|supply| SupplyWasteData {
+                supply_id: supply.supply_id,
+                supply_name: supply.supply_name,
+                quantities: supply
+                    .quantities
+                    .into_iter()
+                    .map(|quantity| WasteQuantityData {
+                        unit_name: quantity.unit_name,
+                        quantity: quantity.quantity,
+                    })
+                    .collect(),
+                wasted: supply.wasted,
+                purchases: supply.purchases,
+                closing_inventory: supply.closing_inventory,
+                loss_ratio: supply.loss_ratio,
+            })
+            .collect(),
+    })
+}
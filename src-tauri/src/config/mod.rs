@@ -10,18 +10,115 @@ pub enum Theme {
     Light,
 }
 
+/// Rounding mode of computed amounts
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RoundingMode {
+    Down,
+    Up,
+    HalfUp,
+    HalfEven,
+}
+
+/// Rounding of line totals computed from unit price and quantity
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rounding {
+    /// Rounding mode
+    pub mode: RoundingMode,
+    /// Rounding mode of consumption tax
+    #[serde(default = "default_tax_mode")]
+    pub tax_mode: RoundingMode,
+    /// Number of decimal digits kept
+    pub digits: u32,
+}
+
+impl Default for Rounding {
+    fn default() -> Self {
+        Rounding {
+            mode: RoundingMode::HalfUp,
+            tax_mode: default_tax_mode(),
+            digits: 0,
+        }
+    }
+}
+
+/// Consumption tax is rounded down by default
+fn default_tax_mode() -> RoundingMode {
+    RoundingMode::Down
+}
+
+/// Method deriving stocktaking unit prices from purchases
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ValuationMethod {
+    #[default]
+    LastPurchasePrice,
+    MovingAverage,
+    PeriodicAverage,
+    Fifo,
+}
+
+/// Warning on purchase prices deviating from recent purchases
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceAlert {
+    /// Deviation from the average in percent, 0 disables the warning
+    pub threshold_percent: u32,
+    /// Number of recent purchases averaged
+    pub sample_size: u32,
+}
+
+impl Default for PriceAlert {
+    fn default() -> Self {
+        PriceAlert {
+            threshold_percent: 20,
+            sample_size: 5,
+        }
+    }
+}
+
 /// Application configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Config {
     /// Application theme
     pub theme: Theme,
+    /// Rounding of computed amounts
+    #[serde(default)]
+    pub rounding: Rounding,
+    /// Method deriving stocktaking unit prices
+    #[serde(default)]
+    pub valuation_method: ValuationMethod,
+    /// Warning on purchase prices
+    #[serde(default)]
+    pub price_alert: PriceAlert,
+    /// Store data is read from and recorded in, the default store if not set
+    #[serde(default)]
+    pub current_store_id: Option<String>,
+    /// Days deleted records are kept in the trash, 0 keeps them forever
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    /// Name of the person operating the application, logged with the changes made
+    #[serde(default)]
+    pub operator_name: Option<String>,
+}
+
+/// Deleted records are kept for 30 days by default
+fn default_trash_retention_days() -> u32 {
+    30
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
             theme: Theme::Light,
+            rounding: Rounding::default(),
+            valuation_method: ValuationMethod::default(),
+            price_alert: PriceAlert::default(),
+            current_store_id: None,
+            trash_retention_days: default_trash_retention_days(),
+            operator_name: None,
         }
     }
 }
@@ -0,0 +1,138 @@
+use std::hash::Hash;
+
+use crate::core::domain::values::accounting::*;
+use crate::core::{Error, Result};
+
+/// Accounting period entity, a span of days whose records are locked once closed (締め)
+///
+/// Closing and reopening are kept in the audit trail of the period.
+#[derive(Debug, Clone, Eq)]
+pub struct AccountingPeriod {
+    id: AccountingPeriodId,
+    name: PeriodName,
+    range: PeriodRange,
+    closed: bool,
+    audit_trail: Vec<PeriodAuditEntry>,
+}
+
+impl AccountingPeriod {
+    /// Restore an AccountingPeriod entity.
+    pub fn restore(
+        id: AccountingPeriodId,
+        name: PeriodName,
+        range: PeriodRange,
+        closed: bool,
+        audit_trail: Vec<PeriodAuditEntry>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            range,
+            closed,
+            audit_trail,
+        }
+    }
+
+    /// Close a new accounting period.
+    pub fn close(
+        id: AccountingPeriodId,
+        name: PeriodName,
+        range: PeriodRange,
+        closed_at: i64,
+        note: Option<String>,
+    ) -> Self {
+        Self::restore(
+            id,
+            name,
+            range,
+            true,
+            vec![PeriodAuditEntry::new(
+                PeriodOperation::Close,
+                closed_at,
+                note,
+            )],
+        )
+    }
+
+    /// Returns accounting period ID.
+    pub fn id(&self) -> &AccountingPeriodId {
+        &self.id
+    }
+
+    /// Returns the name of the period.
+    pub fn name(&self) -> &PeriodName {
+        &self.name
+    }
+
+    /// Returns the dates the period spans.
+    pub fn range(&self) -> &PeriodRange {
+        &self.range
+    }
+
+    /// Returns whether the period is closed.
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Returns the closings and reopenings of the period, oldest first.
+    pub fn audit_trail(&self) -> &[PeriodAuditEntry] {
+        &self.audit_trail
+    }
+
+    /// Returns whether records dated `datetime` are locked by the period.
+    pub fn locks(&self, datetime: i64) -> bool {
+        self.closed && self.range.contains(datetime)
+    }
+
+    pub fn rename(&mut self, name: PeriodName) {
+        self.name = name;
+    }
+
+    /// Closes the period again after it was reopened.
+    pub fn reclose(&mut self, closed_at: i64, note: Option<String>) -> Result<()> {
+        if self.closed {
+            return Err(Error::DomainError(format!(
+                "accounting period is already closed."
+            )));
+        }
+
+        self.closed = true;
+        self.audit_trail.push(PeriodAuditEntry::new(
+            PeriodOperation::Close,
+            closed_at,
+            note,
+        ));
+
+        Ok(())
+    }
+
+    /// Reopens the period so its records can be changed again.
+    pub fn reopen(&mut self, reopened_at: i64, note: Option<String>) -> Result<()> {
+        if !self.closed {
+            return Err(Error::DomainError(format!(
+                "accounting period is not closed."
+            )));
+        }
+
+        self.closed = false;
+        self.audit_trail.push(PeriodAuditEntry::new(
+            PeriodOperation::Reopen,
+            reopened_at,
+            note,
+        ));
+
+        Ok(())
+    }
+}
+
+impl PartialEq for AccountingPeriod {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for AccountingPeriod {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
@@ -0,0 +1,51 @@
+use std::hash::Hash;
+
+use crate::core::domain::values::audit::*;
+
+/// Audit log entry entity, a change of an entity logged when it was made
+///
+/// Entries are only appended, never changed nor deleted.
+#[derive(Debug, Clone, Eq)]
+pub struct AuditEntry {
+    id: AuditEntryId,
+    logged_at: LoggedDateTime,
+    change: AuditChange,
+}
+
+impl AuditEntry {
+    /// Restore an AuditEntry entity.
+    pub fn restore(id: AuditEntryId, logged_at: LoggedDateTime, change: AuditChange) -> Self {
+        Self {
+            id,
+            logged_at,
+            change,
+        }
+    }
+
+    /// Returns audit log entry ID.
+    pub fn id(&self) -> &AuditEntryId {
+        &self.id
+    }
+
+    /// Returns when the change was logged.
+    pub fn logged_at(&self) -> LoggedDateTime {
+        self.logged_at
+    }
+
+    /// Returns the logged change.
+    pub fn change(&self) -> &AuditChange {
+        &self.change
+    }
+}
+
+impl PartialEq for AuditEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for AuditEntry {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
@@ -1 +1,4 @@
+pub mod accounting;
+pub mod audit;
+pub mod payable;
 pub mod stock;
@@ -0,0 +1,200 @@
+use std::hash::Hash;
+
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::payable::*;
+use crate::core::domain::values::stock::*;
+use crate::core::{Error, Result};
+
+/// Supplier invoice entity, the amount a supplier bills for a closing period (請求書)
+#[derive(Debug, Clone, Eq)]
+pub struct SupplierInvoice {
+    id: SupplierInvoiceId,
+    supplier_id: SupplierId,
+    invoice_number: Option<InvoiceNumber>,
+    period: BillingPeriod,
+    due_date: DueDate,
+    amount: InvoicedAmount,
+    payments: Vec<Payment>,
+}
+
+impl SupplierInvoice {
+    /// Restore a SupplierInvoice entity.
+    pub fn restore(
+        id: SupplierInvoiceId,
+        supplier_id: SupplierId,
+        invoice_number: Option<InvoiceNumber>,
+        period: BillingPeriod,
+        due_date: DueDate,
+        amount: InvoicedAmount,
+        payments: Vec<Payment>,
+    ) -> Self {
+        Self {
+            id,
+            supplier_id,
+            invoice_number,
+            period,
+            due_date,
+            amount,
+            payments,
+        }
+    }
+
+    /// Record a new supplier invoice.
+    pub fn new(
+        id: SupplierInvoiceId,
+        supplier_id: SupplierId,
+        invoice_number: Option<InvoiceNumber>,
+        period: BillingPeriod,
+        due_date: DueDate,
+        amount: InvoicedAmount,
+    ) -> Result<Self> {
+        let mut invoice =
+            Self::restore(id, supplier_id, None, period, due_date, amount, Vec::new());
+
+        invoice.revise(invoice_number, period, due_date, amount)?;
+
+        Ok(invoice)
+    }
+
+    /// Returns supplier invoice ID.
+    pub fn id(&self) -> &SupplierInvoiceId {
+        &self.id
+    }
+
+    /// Returns the ID of the supplier billing.
+    pub fn supplier_id(&self) -> &SupplierId {
+        &self.supplier_id
+    }
+
+    /// Returns the number printed on the invoice, none if not noted.
+    pub fn invoice_number(&self) -> Option<&InvoiceNumber> {
+        self.invoice_number.as_ref()
+    }
+
+    /// Returns the closing period billed.
+    pub fn period(&self) -> &BillingPeriod {
+        &self.period
+    }
+
+    /// Returns the date the invoice is to be paid by.
+    pub fn due_date(&self) -> &DueDate {
+        &self.due_date
+    }
+
+    /// Returns the amount billed.
+    pub fn amount(&self) -> &InvoicedAmount {
+        &self.amount
+    }
+
+    /// Returns the payments made against the invoice.
+    pub fn payments(&self) -> &[Payment] {
+        &self.payments
+    }
+
+    /// Replaces the number, period, due date and amount of the invoice.
+    ///
+    /// The amount must not fall below what has already been paid.
+    pub fn revise(
+        &mut self,
+        invoice_number: Option<InvoiceNumber>,
+        period: BillingPeriod,
+        due_date: DueDate,
+        amount: InvoicedAmount,
+    ) -> Result<()> {
+        if due_date.as_i64() < period.start() {
+            return Err(Error::DomainError(format!(
+                "due date must not be before the billing period."
+            )));
+        }
+
+        if amount.as_money() < self.paid_amount()? {
+            return Err(Error::DomainError(format!(
+                "invoiced amount must not be less than the amount paid."
+            )));
+        }
+
+        self.invoice_number = invoice_number;
+        self.period = period;
+        self.due_date = due_date;
+        self.amount = amount;
+
+        Ok(())
+    }
+
+    /// Returns the sum of the payments made.
+    pub fn paid_amount(&self) -> Result<Money> {
+        Money::checked_sum(
+            self.payments
+                .iter()
+                .map(|payment| payment.amount().as_money()),
+        )
+    }
+
+    /// Returns the amount still to be paid.
+    pub fn balance(&self) -> Result<Money> {
+        self.amount.as_money().checked_sub(self.paid_amount()?)
+    }
+
+    /// Returns the amount still to be paid at `at`, ignoring later payments.
+    pub fn balance_at(&self, at: &PaidDateTime) -> Result<Money> {
+        let paid = Money::checked_sum(
+            self.payments
+                .iter()
+                .filter(|payment| payment.paid_at() <= at)
+                .map(|payment| payment.amount().as_money()),
+        )?;
+
+        self.amount.as_money().checked_sub(paid)
+    }
+
+    /// Returns whether the invoice has been paid in full.
+    pub fn is_settled(&self) -> Result<bool> {
+        Ok(self.balance()? == Money::ZERO)
+    }
+
+    /// Records a payment, which must not exceed the outstanding balance.
+    pub fn pay(&mut self, payment: Payment) -> Result<()> {
+        if self.payments.iter().any(|p| p.id() == payment.id()) {
+            return Err(Error::DomainError(format!("payment already exists.")));
+        }
+
+        let balance = self.balance()?;
+
+        if payment.amount().as_money() > balance {
+            return Err(Error::DomainError(format!(
+                "payment of {} exceeds the outstanding balance of {}.",
+                payment.amount().as_f64(),
+                balance.as_f64()
+            )));
+        }
+
+        self.payments.push(payment);
+
+        Ok(())
+    }
+
+    /// Cancels a payment recorded by mistake.
+    pub fn cancel_payment(&mut self, payment_id: &PaymentId) -> Result<()> {
+        let index = self
+            .payments
+            .iter()
+            .position(|payment| payment.id() == payment_id)
+            .ok_or(Error::DomainError(format!("payment does not exist.")))?;
+
+        self.payments.remove(index);
+
+        Ok(())
+    }
+}
+
+impl PartialEq for SupplierInvoice {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for SupplierInvoice {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
@@ -1,27 +1,53 @@
 use std::hash::Hash;
 
+use crate::core::domain::values::category::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::location::*;
+use crate::core::domain::values::order::*;
+use crate::core::domain::values::reorder::*;
+use crate::core::domain::values::revision::*;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::tax::*;
+use crate::core::domain::values::transfer::*;
+use crate::core::domain::values::unit::*;
+use crate::core::domain::values::waste::*;
+use crate::core::{Error, Result};
 
 #[derive(Debug, Clone, Eq)]
 pub struct Supply {
     id: SupplyId,
     name: SupplyName,
     unit_name: UnitName,
-    supplier_id: SupplierId,
+    offers: Vec<SupplyOffer>,
+    tax_category: TaxCategory,
+    units: SupplyUnits,
+    category_id: Option<CategoryId>,
+    reorder_levels: Option<ReorderLevels>,
+    placement: Option<StoragePlacement>,
 }
 
 impl Supply {
+    /// Creates a supply offered by a single, preferred supplier.
     pub fn new(
         id: SupplyId,
         name: SupplyName,
         unit_name: UnitName,
         supplier_id: SupplierId,
+        tax_category: TaxCategory,
+        units: SupplyUnits,
+        category_id: Option<CategoryId>,
     ) -> Self {
         Self {
             id,
             name,
             unit_name,
-            supplier_id,
+            offers: vec![SupplyOffer::new(supplier_id).with_preferred(true)],
+            tax_category,
+            units,
+            category_id,
+            reorder_levels: None,
+            placement: None,
         }
     }
 
@@ -41,16 +67,133 @@ impl Supply {
         self.name = name;
     }
 
+    /// Returns the preferred supplier.
     pub fn supplier_id(&self) -> &SupplierId {
-        &self.supplier_id
+        self.offers
+            .iter()
+            .find(|offer| offer.is_preferred())
+            .unwrap_or(&self.offers[0])
+            .supplier_id()
+    }
+
+    /// Returns the suppliers offering the supply, the preferred one among them.
+    pub fn offers(&self) -> &[SupplyOffer] {
+        &self.offers
+    }
+
+    /// Returns the offer of the supplier, none if it does not offer the supply.
+    pub fn offer_of(&self, supplier_id: &SupplierId) -> Option<&SupplyOffer> {
+        self.offers
+            .iter()
+            .find(|offer| offer.supplier_id() == supplier_id)
+    }
+
+    pub fn is_offered_by(&self, supplier_id: &SupplierId) -> bool {
+        self.offer_of(supplier_id).is_some()
     }
 
     pub fn rename_unit(&mut self, name: UnitName) {
         self.unit_name = name;
     }
 
+    /// Makes the supplier the preferred one, adding its offer if it has none.
     pub fn change_supplier(&mut self, supplier_id: SupplierId) {
-        self.supplier_id = supplier_id
+        if !self.is_offered_by(&supplier_id) {
+            self.offers.push(SupplyOffer::new(supplier_id.clone()));
+        }
+
+        self.offers = self
+            .offers
+            .drain(..)
+            .map(|offer| {
+                let preferred = offer.supplier_id() == &supplier_id;
+                offer.with_preferred(preferred)
+            })
+            .collect();
+    }
+
+    /// Replaces the suppliers offering the supply.
+    ///
+    /// The first offer becomes the preferred one unless another is marked.
+    pub fn change_offers(&mut self, offers: Vec<SupplyOffer>) -> Result<()> {
+        if offers.is_empty() {
+            return Err(Error::DomainError(format!(
+                "supply must be offered by at least one supplier."
+            )));
+        }
+
+        for (index, offer) in offers.iter().enumerate() {
+            if offers[..index]
+                .iter()
+                .any(|other| other.supplier_id() == offer.supplier_id())
+            {
+                return Err(Error::DomainError(format!(
+                    "supplier {} is duplicated.",
+                    offer.supplier_id().as_str()
+                )));
+            }
+        }
+
+        let preferred = offers.iter().filter(|offer| offer.is_preferred()).count();
+
+        if preferred > 1 {
+            return Err(Error::DomainError(format!(
+                "only one supplier can be preferred."
+            )));
+        }
+
+        self.offers = offers;
+
+        if preferred == 0 {
+            let first = self.offers.remove(0);
+            self.offers.insert(0, first.with_preferred(true));
+        }
+
+        Ok(())
+    }
+
+    pub fn tax_category(&self) -> TaxCategory {
+        self.tax_category
+    }
+
+    pub fn change_tax_category(&mut self, tax_category: TaxCategory) {
+        self.tax_category = tax_category;
+    }
+
+    /// Returns the purchase unit, stock unit and conversions.
+    pub fn units(&self) -> &SupplyUnits {
+        &self.units
+    }
+
+    pub fn change_units(&mut self, units: SupplyUnits) {
+        self.units = units;
+    }
+
+    /// Returns the category, none if the supply is not categorized.
+    pub fn category_id(&self) -> Option<&CategoryId> {
+        self.category_id.as_ref()
+    }
+
+    pub fn change_category(&mut self, category_id: Option<CategoryId>) {
+        self.category_id = category_id;
+    }
+
+    /// Returns the par level and reorder point, none if the supply is not reordered.
+    pub fn reorder_levels(&self) -> Option<&ReorderLevels> {
+        self.reorder_levels.as_ref()
+    }
+
+    pub fn change_reorder_levels(&mut self, reorder_levels: Option<ReorderLevels>) {
+        self.reorder_levels = reorder_levels;
+    }
+
+    /// Returns the default storage location, none if the supply is not placed.
+    pub fn placement(&self) -> Option<&StoragePlacement> {
+        self.placement.as_ref()
+    }
+
+    pub fn change_placement(&mut self, placement: Option<StoragePlacement>) {
+        self.placement = placement;
     }
 }
 
@@ -66,15 +209,224 @@ impl Hash for Supply {
     }
 }
 
+/// Unit of measure entity
+#[derive(Debug, Clone, Eq)]
+pub struct Unit {
+    id: UnitId,
+    name: UnitName,
+}
+
+impl Unit {
+    pub fn restore(id: UnitId, name: UnitName) -> Self {
+        Self { id, name }
+    }
+
+    pub fn id(&self) -> &UnitId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &UnitName {
+        &self.name
+    }
+
+    pub fn rename(&mut self, name: UnitName) {
+        self.name = name;
+    }
+}
+
+impl PartialEq for Unit {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for Unit {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Supply category entity, e.g. 食材 > 野菜
+#[derive(Debug, Clone, Eq)]
+pub struct Category {
+    id: CategoryId,
+    name: CategoryName,
+    parent_id: Option<CategoryId>,
+}
+
+impl Category {
+    pub fn restore(id: CategoryId, name: CategoryName, parent_id: Option<CategoryId>) -> Self {
+        Self {
+            id,
+            name,
+            parent_id,
+        }
+    }
+
+    pub fn id(&self) -> &CategoryId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &CategoryName {
+        &self.name
+    }
+
+    pub fn rename(&mut self, name: CategoryName) {
+        self.name = name;
+    }
+
+    /// Returns the parent category, none for top level categories.
+    pub fn parent_id(&self) -> Option<&CategoryId> {
+        self.parent_id.as_ref()
+    }
+
+    /// Moves the category under `parent_id`.
+    ///
+    /// Cycles through other categories are checked against the whole hierarchy by the caller.
+    pub fn move_to(&mut self, parent_id: Option<CategoryId>) -> Result<()> {
+        if parent_id.as_ref() == Some(&self.id) {
+            return Err(Error::DomainError(format!(
+                "category cannot be its own parent."
+            )));
+        }
+
+        self.parent_id = parent_id;
+
+        Ok(())
+    }
+}
+
+impl PartialEq for Category {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for Category {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Storage location entity, e.g. 冷蔵庫・冷凍庫・倉庫
+#[derive(Debug, Clone, Eq)]
+pub struct StorageLocation {
+    id: StorageLocationId,
+    name: StorageLocationName,
+    sort_order: SortOrder,
+}
+
+impl StorageLocation {
+    pub fn restore(
+        id: StorageLocationId,
+        name: StorageLocationName,
+        sort_order: SortOrder,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            sort_order,
+        }
+    }
+
+    pub fn id(&self) -> &StorageLocationId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &StorageLocationName {
+        &self.name
+    }
+
+    pub fn rename(&mut self, name: StorageLocationName) {
+        self.name = name;
+    }
+
+    /// Returns the position of the location on count sheets.
+    pub fn sort_order(&self) -> SortOrder {
+        self.sort_order
+    }
+
+    pub fn reorder(&mut self, sort_order: SortOrder) {
+        self.sort_order = sort_order;
+    }
+}
+
+impl PartialEq for StorageLocation {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for StorageLocation {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Store entity, e.g. 本店・駅前店
+#[derive(Debug, Clone, Eq)]
+pub struct Store {
+    id: StoreId,
+    name: StoreName,
+}
+
+impl Store {
+    pub fn restore(id: StoreId, name: StoreName) -> Self {
+        Self { id, name }
+    }
+
+    pub fn id(&self) -> &StoreId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &StoreName {
+        &self.name
+    }
+
+    pub fn rename(&mut self, name: StoreName) {
+        self.name = name;
+    }
+}
+
+impl PartialEq for Store {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for Store {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
 #[derive(Debug, Clone, Eq)]
 pub struct Supplier {
     id: SupplierId,
     name: SupplierName,
+    invoice_registration_number: Option<InvoiceRegistrationNumber>,
+    invoice_registered: bool,
+    contact: SupplierContact,
+    notes: Option<SupplierNotes>,
+    billing_terms: Option<BillingTerms>,
 }
 
 impl Supplier {
-    pub fn restore(id: SupplierId, name: SupplierName) -> Self {
-        Self { id, name: name }
+    pub fn restore(
+        id: SupplierId,
+        name: SupplierName,
+        invoice_registration_number: Option<InvoiceRegistrationNumber>,
+        invoice_registered: bool,
+    ) -> Self {
+        Self {
+            id,
+            name: name,
+            invoice_registration_number,
+            invoice_registered,
+            contact: SupplierContact::default(),
+            notes: None,
+            billing_terms: None,
+        }
     }
 
     pub fn id(&self) -> &SupplierId {
@@ -88,6 +440,60 @@ impl Supplier {
     pub fn rename(&mut self, name: SupplierName) {
         self.name = name;
     }
+
+    pub fn invoice_registration_number(&self) -> Option<&InvoiceRegistrationNumber> {
+        self.invoice_registration_number.as_ref()
+    }
+
+    /// Returns whether the supplier is a qualified invoice issuer.
+    pub fn is_invoice_registered(&self) -> bool {
+        self.invoice_registered
+    }
+
+    /// Changes the qualified invoice issuer registration.
+    ///
+    /// A registered supplier must have a registration number.
+    pub fn change_invoice_registration(
+        &mut self,
+        invoice_registration_number: Option<InvoiceRegistrationNumber>,
+        invoice_registered: bool,
+    ) -> Result<()> {
+        if invoice_registered && invoice_registration_number.is_none() {
+            return Err(Error::DomainError(format!(
+                "registered supplier must have an invoice registration number."
+            )));
+        }
+
+        self.invoice_registration_number = invoice_registration_number;
+        self.invoice_registered = invoice_registered;
+
+        Ok(())
+    }
+
+    pub fn contact(&self) -> &SupplierContact {
+        &self.contact
+    }
+
+    pub fn change_contact(&mut self, contact: SupplierContact) {
+        self.contact = contact;
+    }
+
+    pub fn notes(&self) -> Option<&SupplierNotes> {
+        self.notes.as_ref()
+    }
+
+    pub fn change_notes(&mut self, notes: Option<SupplierNotes>) {
+        self.notes = notes;
+    }
+
+    /// Returns the closing and payment days, none if not agreed.
+    pub fn billing_terms(&self) -> Option<&BillingTerms> {
+        self.billing_terms.as_ref()
+    }
+
+    pub fn change_billing_terms(&mut self, billing_terms: Option<BillingTerms>) {
+        self.billing_terms = billing_terms;
+    }
 }
 
 impl PartialEq for Supplier {
@@ -127,6 +533,21 @@ impl Journal {
         }
     }
 
+    /// Record a new Journal, verifying the supplied totals.
+    pub fn record(
+        id: JournalId,
+        entry_datetime: EntryDateTime,
+        total_price: TotalPrice,
+        records: Vec<JournalRecord>,
+        policy: &RoundingPolicy,
+    ) -> Result<Self> {
+        let mut journal = Self::restore(id, entry_datetime, total_price.clone(), Vec::new());
+
+        journal.revise(total_price, records, policy)?;
+
+        Ok(journal)
+    }
+
     /// Returns the Journal ID.
     pub fn id(&self) -> &JournalId {
         &self.id
@@ -147,15 +568,173 @@ impl Journal {
         &self.records
     }
 
-    /// Changes the total price of the Journal.
-    pub fn change_total_price(&mut self, total_price: TotalPrice) {
-        self.total_price = total_price;
-    }
-
     /// Swaps the records of the Journal with the provided records.
     pub fn swap_records(&mut self, records: Vec<JournalRecord>) {
         self.records = records.into_iter().collect();
     }
+
+    /// Replaces the total price and records, verifying the supplied totals.
+    pub fn revise(
+        &mut self,
+        total_price: TotalPrice,
+        records: Vec<JournalRecord>,
+        policy: &RoundingPolicy,
+    ) -> Result<()> {
+        let lines = records
+            .iter()
+            .map(|record| {
+                Ok((
+                    record.supply_name().as_str(),
+                    record.total_price(),
+                    record.compute_total_price(policy)?,
+                ))
+            })
+            .collect::<Result<Vec<(&str, &TotalPrice, TotalPrice)>>>()?;
+
+        verify_totals(&lines, &total_price)?;
+
+        let mut records = records;
+
+        // Tax is computed once per category and inclusion, then allocated to the lines.
+        for category in TaxCategory::ALL {
+            for included in [true, false] {
+                let indices: Vec<usize> = records
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, record)| {
+                        record.tax_category() == category && record.is_tax_included() == included
+                    })
+                    .map(|(index, _)| index)
+                    .collect();
+
+                if indices.is_empty() {
+                    continue;
+                }
+
+                let weights: Vec<Money> = indices
+                    .iter()
+                    .map(|index| records[*index].total_price().as_money())
+                    .collect();
+
+                let tax = policy.tax(
+                    Money::checked_sum(weights.iter().copied())?,
+                    category.rate_percent(),
+                    included,
+                )?;
+
+                for (index, tax) in indices.iter().zip(policy.allocate(tax, &weights)) {
+                    records[*index].assign_tax_price(TotalPrice::from_money(tax)?);
+                }
+            }
+        }
+
+        self.total_price = total_price;
+        self.records = records;
+
+        Ok(())
+    }
+
+    /// Returns net, tax and gross amounts per tax category.
+    ///
+    /// The deductible tax is rounded by the tax rounding of the policy.
+    pub fn tax_summaries(&self, policy: &RoundingPolicy) -> Result<Vec<TaxSummary>> {
+        let mut summaries = Vec::new();
+
+        for category in TaxCategory::ALL {
+            let records: Vec<&JournalRecord> = self
+                .records
+                .iter()
+                .filter(|record| record.tax_category() == category)
+                .collect();
+
+            if records.is_empty() {
+                continue;
+            }
+
+            let net_price = Money::checked_sum(
+                records
+                    .iter()
+                    .map(|record| record.net_price())
+                    .collect::<Result<Vec<Money>>>()?,
+            )?;
+
+            let tax_price =
+                Money::checked_sum(records.iter().map(|record| record.tax_price().as_money()))?;
+
+            let mut deductible_tax_price = Money::ZERO;
+
+            for registered in [true, false] {
+                let ratio = DeductionRatio::of(registered, &self.entry_datetime);
+
+                let tax = Money::checked_sum(
+                    records
+                        .iter()
+                        .filter(|record| record.is_invoice_registered() == registered)
+                        .map(|record| record.tax_price().as_money()),
+                )?;
+
+                deductible_tax_price = deductible_tax_price
+                    .checked_add(policy.deductible_tax(tax, ratio.percent())?)?;
+            }
+
+            summaries.push(TaxSummary::new(
+                category,
+                net_price,
+                tax_price,
+                deductible_tax_price,
+            )?);
+        }
+
+        Ok(summaries)
+    }
+
+    /// Returns the total excluding consumption tax.
+    pub fn net_price(&self) -> Result<Money> {
+        Money::checked_sum(
+            self.records
+                .iter()
+                .map(|record| record.net_price())
+                .collect::<Result<Vec<Money>>>()?,
+        )
+    }
+
+    /// Returns the total consumption tax.
+    pub fn tax_price(&self) -> Result<Money> {
+        Money::checked_sum(
+            self.records
+                .iter()
+                .map(|record| record.tax_price().as_money()),
+        )
+    }
+
+    /// Returns the total including consumption tax.
+    pub fn gross_price(&self) -> Result<Money> {
+        Money::checked_sum(
+            self.records
+                .iter()
+                .map(|record| record.gross_price())
+                .collect::<Result<Vec<Money>>>()?,
+        )
+    }
+
+    /// Returns the records as lines compared between revisions.
+    pub fn revision_lines(&self) -> Vec<RevisionLine> {
+        self.records
+            .iter()
+            .map(|record| {
+                RevisionLine::new(
+                    record.supply_id().clone(),
+                    record.supply_name().clone(),
+                    record.unit_name().clone(),
+                    record.unit_price().as_money(),
+                    record.quantity().as_quantity(),
+                    record.total_price().as_money(),
+                )
+                .with_supplier(record.supplier_id().clone(), record.supplier_name().clone())
+                .with_lot(record.lot_number().cloned())
+            })
+            .collect()
+    }
 }
 
 impl PartialEq for Journal {
@@ -195,6 +774,22 @@ impl Stocktaking {
         }
     }
 
+    /// Record a new stocktaking, verifying the supplied totals.
+    pub fn record(
+        id: StocktakingId,
+        stocktaken_datetime: StocktakenDateTime,
+        total_price: TotalPrice,
+        records: Vec<StocktakingRecord>,
+        policy: &RoundingPolicy,
+    ) -> Result<Self> {
+        let mut stocktaking =
+            Self::restore(id, stocktaken_datetime, total_price.clone(), Vec::new());
+
+        stocktaking.revise(total_price, records, policy)?;
+
+        Ok(stocktaking)
+    }
+
     /// Returns stocktaking ID.
     pub fn id(&self) -> &StocktakingId {
         &self.id
@@ -215,15 +810,55 @@ impl Stocktaking {
         &self.records
     }
 
-    /// Changes the total price of the stocktaking.
-    pub fn change_total_price(&mut self, total_price: TotalPrice) {
-        self.total_price = total_price;
-    }
-
     /// Swaps the records of the stocktaking with the provided records.
     pub fn swap_records(&mut self, records: impl IntoIterator<Item = StocktakingRecord>) {
         self.records = records.into_iter().collect();
     }
+
+    /// Replaces the total price and records, verifying the supplied totals.
+    pub fn revise(
+        &mut self,
+        total_price: TotalPrice,
+        records: Vec<StocktakingRecord>,
+        policy: &RoundingPolicy,
+    ) -> Result<()> {
+        let lines = records
+            .iter()
+            .map(|record| {
+                Ok((
+                    record.supply_name().as_str(),
+                    record.total_price(),
+                    record.compute_total_price(policy)?,
+                ))
+            })
+            .collect::<Result<Vec<(&str, &TotalPrice, TotalPrice)>>>()?;
+
+        verify_totals(&lines, &total_price)?;
+
+        self.total_price = total_price;
+        self.records = records;
+
+        Ok(())
+    }
+
+    /// Returns the records as lines compared between revisions.
+    pub fn revision_lines(&self) -> Vec<RevisionLine> {
+        self.records
+            .iter()
+            .map(|record| {
+                RevisionLine::new(
+                    record.supply_id().clone(),
+                    record.supply_name().clone(),
+                    record.unit_name().clone(),
+                    record.unit_price().as_money(),
+                    record.quantity().as_quantity(),
+                    record.total_price().as_money(),
+                )
+                .with_location(record.location_id().cloned())
+                .with_lot(record.lot_number().cloned())
+            })
+            .collect()
+    }
 }
 
 impl PartialEq for Stocktaking {
@@ -237,3 +872,522 @@ impl Hash for Stocktaking {
         self.id.hash(state);
     }
 }
+
+/// Consumption entity, stock taken out for use between stocktakings
+#[derive(Debug, Clone, Eq)]
+pub struct Consumption {
+    id: ConsumptionId,
+    consumed_datetime: ConsumedDateTime,
+    records: Vec<ConsumptionRecord>,
+}
+
+impl Consumption {
+    /// Restore a Consumption entity.
+    pub fn restore(
+        id: ConsumptionId,
+        consumed_datetime: ConsumedDateTime,
+        records: Vec<ConsumptionRecord>,
+    ) -> Self {
+        Self {
+            id,
+            consumed_datetime,
+            records,
+        }
+    }
+
+    /// Record a new consumption.
+    pub fn record(
+        id: ConsumptionId,
+        consumed_datetime: ConsumedDateTime,
+        records: Vec<ConsumptionRecord>,
+    ) -> Result<Self> {
+        if records.is_empty() {
+            return Err(Error::DomainError(format!(
+                "consumption must have at least one record."
+            )));
+        }
+
+        Ok(Self::restore(id, consumed_datetime, records))
+    }
+
+    /// Returns consumption ID.
+    pub fn id(&self) -> &ConsumptionId {
+        &self.id
+    }
+
+    /// Returns the date and time when the stock was consumed.
+    pub fn consumed_at(&self) -> &ConsumedDateTime {
+        &self.consumed_datetime
+    }
+
+    /// Returns the records of the consumption.
+    pub fn records(&self) -> &[ConsumptionRecord] {
+        &self.records
+    }
+
+    /// Swaps the records of the consumption with the provided records.
+    pub fn swap_records(&mut self, records: impl IntoIterator<Item = ConsumptionRecord>) {
+        self.records = records.into_iter().collect();
+    }
+}
+
+impl PartialEq for Consumption {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for Consumption {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Purchase order entity, supplies ordered from a supplier
+#[derive(Debug, Clone, Eq)]
+pub struct PurchaseOrder {
+    id: PurchaseOrderId,
+    supplier_id: SupplierId,
+    supplier_name: SupplierName,
+    ordered_datetime: OrderedDateTime,
+    status: OrderStatus,
+    lines: Vec<PurchaseOrderLine>,
+    journal_ids: Vec<JournalId>,
+}
+
+impl PurchaseOrder {
+    /// Restore a PurchaseOrder entity.
+    pub fn restore(
+        id: PurchaseOrderId,
+        supplier_id: SupplierId,
+        supplier_name: SupplierName,
+        ordered_datetime: OrderedDateTime,
+        status: OrderStatus,
+        lines: Vec<PurchaseOrderLine>,
+        journal_ids: Vec<JournalId>,
+    ) -> Self {
+        Self {
+            id,
+            supplier_id,
+            supplier_name,
+            ordered_datetime,
+            status,
+            lines,
+            journal_ids,
+        }
+    }
+
+    /// Draft a new purchase order.
+    pub fn draft(
+        id: PurchaseOrderId,
+        supplier_id: SupplierId,
+        supplier_name: SupplierName,
+        ordered_datetime: OrderedDateTime,
+        lines: Vec<PurchaseOrderLine>,
+    ) -> Result<Self> {
+        let mut order = Self::restore(
+            id,
+            supplier_id,
+            supplier_name,
+            ordered_datetime.clone(),
+            OrderStatus::Draft,
+            Vec::new(),
+            Vec::new(),
+        );
+
+        order.revise(ordered_datetime, lines)?;
+
+        Ok(order)
+    }
+
+    /// Returns purchase order ID.
+    pub fn id(&self) -> &PurchaseOrderId {
+        &self.id
+    }
+
+    /// Returns the ID of the supplier ordered from.
+    pub fn supplier_id(&self) -> &SupplierId {
+        &self.supplier_id
+    }
+
+    /// Returns the name of the supplier ordered from.
+    pub fn supplier_name(&self) -> &SupplierName {
+        &self.supplier_name
+    }
+
+    /// Returns the date and time when the order was placed.
+    pub fn ordered_at(&self) -> &OrderedDateTime {
+        &self.ordered_datetime
+    }
+
+    /// Returns the status of the order.
+    pub fn status(&self) -> OrderStatus {
+        self.status
+    }
+
+    /// Returns the lines of the order.
+    pub fn lines(&self) -> &[PurchaseOrderLine] {
+        &self.lines
+    }
+
+    /// Returns the IDs of the journals the deliveries were recorded in.
+    pub fn journal_ids(&self) -> &[JournalId] {
+        &self.journal_ids
+    }
+
+    /// Replaces the order date and lines while the order is a draft.
+    pub fn revise(
+        &mut self,
+        ordered_datetime: OrderedDateTime,
+        lines: Vec<PurchaseOrderLine>,
+    ) -> Result<()> {
+        if self.status != OrderStatus::Draft {
+            return Err(Error::DomainError(format!(
+                "only draft orders can be revised."
+            )));
+        }
+
+        if lines.is_empty() {
+            return Err(Error::DomainError(format!(
+                "purchase order must have at least one line."
+            )));
+        }
+
+        for (index, line) in lines.iter().enumerate() {
+            if lines[..index]
+                .iter()
+                .any(|other| other.supply_id() == line.supply_id())
+            {
+                return Err(Error::DomainError(format!(
+                    "{} is ordered more than once.",
+                    line.supply_name().as_str()
+                )));
+            }
+        }
+
+        self.ordered_datetime = ordered_datetime;
+        self.lines = lines;
+
+        Ok(())
+    }
+
+    /// Marks the draft as sent to the supplier.
+    pub fn send(&mut self) -> Result<()> {
+        if self.status != OrderStatus::Draft {
+            return Err(Error::DomainError(format!(
+                "only draft orders can be sent."
+            )));
+        }
+
+        self.status = OrderStatus::Sent;
+
+        Ok(())
+    }
+
+    /// Cancels the order, deliveries already received are kept.
+    pub fn cancel(&mut self) -> Result<()> {
+        if matches!(self.status, OrderStatus::Received | OrderStatus::Cancelled) {
+            return Err(Error::DomainError(format!(
+                "{} orders cannot be cancelled.",
+                self.status.as_str()
+            )));
+        }
+
+        self.status = OrderStatus::Cancelled;
+
+        Ok(())
+    }
+
+    /// Adds delivered quantities to the lines and updates the status.
+    pub fn receive(&mut self, received: &[(SupplyId, Quantity)]) -> Result<()> {
+        if !self.status.is_open() {
+            return Err(Error::DomainError(format!(
+                "{} orders cannot be received.",
+                self.status.as_str()
+            )));
+        }
+
+        if received.is_empty() {
+            return Err(Error::DomainError(format!("nothing to receive.")));
+        }
+
+        let mut lines = self.lines.clone();
+
+        for (supply_id, quantity) in received {
+            let line = lines
+                .iter_mut()
+                .find(|line| line.supply_id() == supply_id)
+                .ok_or(Error::DomainError(format!(
+                    "supply {} is not ordered.",
+                    supply_id.as_str()
+                )))?;
+
+            line.receive(*quantity)?;
+        }
+
+        self.status = if lines.iter().all(|line| line.is_fulfilled()) {
+            OrderStatus::Received
+        } else {
+            OrderStatus::PartiallyReceived
+        };
+        self.lines = lines;
+
+        Ok(())
+    }
+
+    /// Links a journal the deliveries were recorded in.
+    pub fn link_journal(&mut self, journal_id: JournalId) {
+        if !self.journal_ids.contains(&journal_id) {
+            self.journal_ids.push(journal_id);
+        }
+    }
+}
+
+impl PartialEq for PurchaseOrder {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for PurchaseOrder {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Transfer entity, stock moved from one store to another
+#[derive(Debug, Clone, Eq)]
+pub struct Transfer {
+    id: TransferId,
+    transferred_datetime: TransferredDateTime,
+    source_store_id: StoreId,
+    destination_store_id: StoreId,
+    lines: Vec<TransferLine>,
+}
+
+impl Transfer {
+    /// Restore a Transfer entity.
+    pub fn restore(
+        id: TransferId,
+        transferred_datetime: TransferredDateTime,
+        source_store_id: StoreId,
+        destination_store_id: StoreId,
+        lines: Vec<TransferLine>,
+    ) -> Self {
+        Self {
+            id,
+            transferred_datetime,
+            source_store_id,
+            destination_store_id,
+            lines,
+        }
+    }
+
+    /// Create a new transfer.
+    pub fn new(
+        id: TransferId,
+        transferred_datetime: TransferredDateTime,
+        source_store_id: StoreId,
+        destination_store_id: StoreId,
+        lines: Vec<TransferLine>,
+    ) -> Result<Self> {
+        let mut transfer = Self::restore(
+            id,
+            transferred_datetime.clone(),
+            source_store_id.clone(),
+            destination_store_id.clone(),
+            Vec::new(),
+        );
+
+        transfer.revise(
+            transferred_datetime,
+            source_store_id,
+            destination_store_id,
+            lines,
+        )?;
+
+        Ok(transfer)
+    }
+
+    /// Returns transfer ID.
+    pub fn id(&self) -> &TransferId {
+        &self.id
+    }
+
+    /// Returns the date and time when the stock was moved.
+    pub fn transferred_at(&self) -> &TransferredDateTime {
+        &self.transferred_datetime
+    }
+
+    /// Returns the ID of the store the stock was sent from.
+    pub fn source_store_id(&self) -> &StoreId {
+        &self.source_store_id
+    }
+
+    /// Returns the ID of the store the stock was sent to.
+    pub fn destination_store_id(&self) -> &StoreId {
+        &self.destination_store_id
+    }
+
+    /// Returns the lines of the transfer.
+    pub fn lines(&self) -> &[TransferLine] {
+        &self.lines
+    }
+
+    /// Returns the sum of the line totals.
+    pub fn total_price(&self) -> Result<Money> {
+        Money::checked_sum(self.lines.iter().map(|line| line.total_price().as_money()))
+    }
+
+    /// Replaces the date, stores and lines of the transfer.
+    pub fn revise(
+        &mut self,
+        transferred_datetime: TransferredDateTime,
+        source_store_id: StoreId,
+        destination_store_id: StoreId,
+        lines: Vec<TransferLine>,
+    ) -> Result<()> {
+        if source_store_id == destination_store_id {
+            return Err(Error::DomainError(format!(
+                "source and destination stores must differ."
+            )));
+        }
+
+        if lines.is_empty() {
+            return Err(Error::DomainError(format!(
+                "transfer must have at least one line."
+            )));
+        }
+
+        for (index, line) in lines.iter().enumerate() {
+            if lines[..index]
+                .iter()
+                .any(|other| other.supply_id() == line.supply_id())
+            {
+                return Err(Error::DomainError(format!(
+                    "{} is transferred more than once.",
+                    line.supply_name().as_str()
+                )));
+            }
+        }
+
+        self.transferred_datetime = transferred_datetime;
+        self.source_store_id = source_store_id;
+        self.destination_store_id = destination_store_id;
+        self.lines = lines;
+
+        Ok(())
+    }
+
+    /// Returns how the transfer moves stock of the stores of `store`,
+    /// none if it does not concern them or moves stock between them.
+    pub fn direction(&self, store: &StoreContext) -> Option<TransferDirection> {
+        match (
+            store.includes(&self.source_store_id),
+            store.includes(&self.destination_store_id),
+        ) {
+            (true, false) => Some(TransferDirection::Outbound),
+            (false, true) => Some(TransferDirection::Inbound),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for Transfer {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for Transfer {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Waste record entity, stock thrown away instead of being used
+#[derive(Debug, Clone, Eq)]
+pub struct WasteRecord {
+    id: WasteId,
+    wasted_datetime: WastedDateTime,
+    wasted: WastedSupply,
+    reason: WasteReason,
+}
+
+impl WasteRecord {
+    /// Restore a WasteRecord entity.
+    pub fn restore(
+        id: WasteId,
+        wasted_datetime: WastedDateTime,
+        wasted: WastedSupply,
+        reason: WasteReason,
+    ) -> Self {
+        Self {
+            id,
+            wasted_datetime,
+            wasted,
+            reason,
+        }
+    }
+
+    /// Returns waste ID.
+    pub fn id(&self) -> &WasteId {
+        &self.id
+    }
+
+    /// Returns the date and time when the stock was thrown away.
+    pub fn wasted_at(&self) -> &WastedDateTime {
+        &self.wasted_datetime
+    }
+
+    /// Returns the supply thrown away and its value.
+    pub fn wasted(&self) -> &WastedSupply {
+        &self.wasted
+    }
+
+    pub fn reason(&self) -> WasteReason {
+        self.reason
+    }
+}
+
+impl PartialEq for WasteRecord {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Hash for WasteRecord {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Verifies that each line total and the header total match the computed values.
+///
+/// `lines` holds the supply name, the supplied total and the computed total of each line.
+fn verify_totals(
+    lines: &[(&str, &TotalPrice, TotalPrice)],
+    total_price: &TotalPrice,
+) -> Result<()> {
+    for (index, (supply_name, actual, expected)) in lines.iter().enumerate() {
+        if *actual != expected {
+            return Err(Error::LineTotalMismatch {
+                line: index + 1,
+                supply_name: supply_name.to_string(),
+                expected: expected.as_f64(),
+                actual: actual.as_f64(),
+            });
+        }
+    }
+
+    let expected = Money::checked_sum(lines.iter().map(|(_, actual, _)| actual.as_money()))?;
+
+    if total_price.as_money() != expected {
+        return Err(Error::TotalMismatch {
+            expected: expected.as_f64(),
+            actual: total_price.as_f64(),
+        });
+    }
+
+    Ok(())
+}
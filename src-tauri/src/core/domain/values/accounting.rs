@@ -0,0 +1,162 @@
+//! Accounting period value objects.
+use crate::core::{Error, Result};
+
+/// ID of accounting period.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccountingPeriodId {
+    value: String,
+}
+
+impl AccountingPeriodId {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for AccountingPeriodId {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Name of accounting period, e.g. `2024-04` for a month.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PeriodName {
+    value: String,
+}
+
+impl PeriodName {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for PeriodName {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Date and time range of accounting period, both ends included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodRange {
+    start: i64,
+    end: i64,
+}
+
+impl PeriodRange {
+    pub fn new(start: impl Into<i64>, end: impl Into<i64>) -> Result<Self> {
+        let start = start.into();
+        let end = end.into();
+
+        if end < start {
+            return Err(Error::DomainError(format!(
+                "accounting period must not end before it starts."
+            )));
+        }
+
+        Ok(Self { start, end })
+    }
+
+    pub fn start(&self) -> i64 {
+        self.start
+    }
+
+    pub fn end(&self) -> i64 {
+        self.end
+    }
+
+    /// Returns whether `datetime` falls in the range.
+    pub fn contains(&self, datetime: i64) -> bool {
+        self.start <= datetime && datetime <= self.end
+    }
+
+    /// Returns whether the range shares any date and time with `other`.
+    pub fn overlaps(&self, other: &PeriodRange) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+}
+
+/// Operation on accounting period kept in its audit trail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeriodOperation {
+    Close,
+    Reopen,
+}
+
+impl PeriodOperation {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        match value.as_ref().trim() {
+            "close" => Ok(PeriodOperation::Close),
+            "reopen" => Ok(PeriodOperation::Reopen),
+            other => Err(Error::DomainError(format!(
+                "unknown period operation: {}",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            PeriodOperation::Close => "close",
+            PeriodOperation::Reopen => "reopen",
+        }
+    }
+}
+
+/// Entry of the audit trail of accounting period, what was done when and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeriodAuditEntry {
+    operation: PeriodOperation,
+    performed_at: i64,
+    note: Option<String>,
+}
+
+impl PeriodAuditEntry {
+    pub fn new(
+        operation: PeriodOperation,
+        performed_at: impl Into<i64>,
+        note: Option<String>,
+    ) -> Self {
+        Self {
+            operation,
+            performed_at: performed_at.into(),
+            note: note
+                .map(|note| note.trim().to_string())
+                .filter(|note| !note.is_empty()),
+        }
+    }
+
+    pub fn operation(&self) -> PeriodOperation {
+        self.operation
+    }
+
+    pub fn performed_at(&self) -> i64 {
+        self.performed_at
+    }
+
+    /// Returns the reason given for the operation, none if not noted.
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+}
@@ -0,0 +1,31 @@
+use crate::core::domain::values::accounting::*;
+
+#[test]
+fn period_range_test() {
+    let april = PeriodRange::new(100, 199).unwrap();
+
+    assert!(april.contains(100));
+    assert!(april.contains(199));
+    assert!(!april.contains(200));
+
+    assert!(april.overlaps(&PeriodRange::new(199, 299).unwrap()));
+    assert!(april.overlaps(&PeriodRange::new(120, 130).unwrap()));
+    assert!(!april.overlaps(&PeriodRange::new(200, 299).unwrap()));
+
+    assert!(PeriodRange::new(200, 199).is_err());
+}
+
+#[test]
+fn period_audit_entry_test() {
+    let entry = PeriodAuditEntry::new(PeriodOperation::Reopen, 100, Some(" fix ".into()));
+
+    assert_eq!(entry.note(), Some("fix"));
+
+    let entry = PeriodAuditEntry::new(PeriodOperation::Close, 100, Some("  ".into()));
+
+    assert_eq!(entry.note(), None);
+    assert_eq!(
+        PeriodOperation::new(entry.operation().as_str()).unwrap(),
+        PeriodOperation::Close
+    );
+}
@@ -0,0 +1,238 @@
+//! Audit log value objects.
+use crate::core::{Error, Result};
+
+/// ID of audit log entry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AuditEntryId {
+    value: String,
+}
+
+impl AuditEntryId {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for AuditEntryId {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Kind of entity whose changes are logged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuditEntity {
+    Supplier,
+    Supply,
+    Journal,
+    Stocktaking,
+}
+
+impl AuditEntity {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        match value.as_ref().trim() {
+            "supplier" => Ok(AuditEntity::Supplier),
+            "supply" => Ok(AuditEntity::Supply),
+            "journal" => Ok(AuditEntity::Journal),
+            "stocktaking" => Ok(AuditEntity::Stocktaking),
+            other => Err(Error::DomainError(format!(
+                "unknown audit entity: {}",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            AuditEntity::Supplier => "supplier",
+            AuditEntity::Supply => "supply",
+            AuditEntity::Journal => "journal",
+            AuditEntity::Stocktaking => "stocktaking",
+        }
+    }
+}
+
+/// Usecase operation that changed an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuditOperation {
+    Register,
+    Update,
+    Record,
+    Edit,
+    Delete,
+    Restore,
+    Purge,
+}
+
+impl AuditOperation {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        match value.as_ref().trim() {
+            "register" => Ok(AuditOperation::Register),
+            "update" => Ok(AuditOperation::Update),
+            "record" => Ok(AuditOperation::Record),
+            "edit" => Ok(AuditOperation::Edit),
+            "delete" => Ok(AuditOperation::Delete),
+            "restore" => Ok(AuditOperation::Restore),
+            "purge" => Ok(AuditOperation::Purge),
+            other => Err(Error::DomainError(format!(
+                "unknown audit operation: {}",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            AuditOperation::Register => "register",
+            AuditOperation::Update => "update",
+            AuditOperation::Record => "record",
+            AuditOperation::Edit => "edit",
+            AuditOperation::Delete => "delete",
+            AuditOperation::Restore => "restore",
+            AuditOperation::Purge => "purge",
+        }
+    }
+}
+
+/// Name of the person operating the application.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OperatorName {
+    value: String,
+}
+
+impl OperatorName {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for OperatorName {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// JSON snapshot of an entity as shown to users.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditSnapshot {
+    value: String,
+}
+
+impl AuditSnapshot {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+/// Date and time a change was logged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LoggedDateTime {
+    value: i64,
+}
+
+impl LoggedDateTime {
+    pub fn new(value: i64) -> Self {
+        Self { value }
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        self.value
+    }
+}
+
+/// Change of an entity, with snapshots before and after it.
+///
+/// Nothing exists before a registration and after a deletion,
+/// so one of the snapshots may be missing but not both.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditChange {
+    entity: AuditEntity,
+    entity_id: String,
+    operation: AuditOperation,
+    operator: Option<OperatorName>,
+    before: Option<AuditSnapshot>,
+    after: Option<AuditSnapshot>,
+}
+
+impl AuditChange {
+    pub fn new(
+        entity: AuditEntity,
+        entity_id: impl AsRef<str>,
+        operation: AuditOperation,
+        operator: Option<OperatorName>,
+        before: Option<AuditSnapshot>,
+        after: Option<AuditSnapshot>,
+    ) -> Result<Self> {
+        let entity_id = entity_id.as_ref().trim().to_string();
+
+        if entity_id.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        if before.is_none() && after.is_none() {
+            return Err(Error::DomainError(format!(
+                "audit change needs a snapshot before or after it."
+            )));
+        }
+
+        Ok(Self {
+            entity,
+            entity_id,
+            operation,
+            operator,
+            before,
+            after,
+        })
+    }
+
+    pub fn entity(&self) -> AuditEntity {
+        self.entity
+    }
+
+    pub fn entity_id(&self) -> &str {
+        &self.entity_id
+    }
+
+    pub fn operation(&self) -> AuditOperation {
+        self.operation
+    }
+
+    /// Returns who made the change, none if no operator was set.
+    pub fn operator(&self) -> Option<&OperatorName> {
+        self.operator.as_ref()
+    }
+
+    pub fn before(&self) -> Option<&AuditSnapshot> {
+        self.before.as_ref()
+    }
+
+    pub fn after(&self) -> Option<&AuditSnapshot> {
+        self.after.as_ref()
+    }
+}
@@ -0,0 +1,53 @@
+use crate::core::domain::values::audit::*;
+
+#[test]
+fn audit_operation_test() {
+    for operation in [
+        AuditOperation::Register,
+        AuditOperation::Update,
+        AuditOperation::Record,
+        AuditOperation::Edit,
+        AuditOperation::Delete,
+        AuditOperation::Restore,
+        AuditOperation::Purge,
+    ] {
+        assert_eq!(AuditOperation::new(operation.as_str()).unwrap(), operation);
+    }
+
+    assert_eq!(AuditEntity::new(" journal ").unwrap(), AuditEntity::Journal);
+    assert!(AuditOperation::new("revise").is_err());
+    assert!(AuditEntity::new("unit").is_err());
+}
+
+#[test]
+fn audit_change_test() {
+    let change = AuditChange::new(
+        AuditEntity::Supplier,
+        " 1 ",
+        AuditOperation::Register,
+        Some(OperatorName::new("Sato").unwrap()),
+        None,
+        Some(AuditSnapshot::new(r#"{"id":"1"}"#)),
+    )
+    .unwrap();
+
+    assert_eq!(change.entity_id(), "1");
+    assert_eq!(
+        change.operator().map(|operator| operator.as_str()),
+        Some("Sato")
+    );
+    assert!(change.before().is_none());
+
+    // a change without snapshots tells nothing
+    let result = AuditChange::new(
+        AuditEntity::Supplier,
+        "1",
+        AuditOperation::Update,
+        None,
+        None,
+        None,
+    );
+
+    assert!(result.is_err());
+    assert!(OperatorName::new(" ").is_err());
+}
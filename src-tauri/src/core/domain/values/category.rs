@@ -0,0 +1,57 @@
+//! Supply category value objects.
+use crate::core::{Error, Result};
+
+/// ID of category.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CategoryId {
+    value: String,
+}
+
+impl CategoryId {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for CategoryId {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryName {
+    value: String,
+}
+
+impl CategoryName {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for CategoryName {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
@@ -0,0 +1,404 @@
+//! Exact decimal value objects backed by scaled `i64` integers.
+use crate::core::{Error, Result};
+
+/// Number of decimal digits kept by `Money`.
+pub const MONEY_DECIMAL_PRECISION: u32 = 2;
+
+/// Number of decimal digits kept by `Quantity`.
+pub const QUANTITY_DECIMAL_PRECISION: u32 = 3;
+
+/// Rounding mode applied when a value has more digits than can be kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RoundingMode {
+    /// Round towards zero (切り捨て).
+    Down,
+    /// Round away from zero (切り上げ).
+    Up,
+    /// Round half away from zero (四捨五入).
+    #[default]
+    HalfUp,
+    /// Round half to the nearest even digit.
+    HalfEven,
+}
+
+/// Rounding applied to amounts computed by the core.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RoundingPolicy {
+    mode: RoundingMode,
+    tax_mode: RoundingMode,
+    digits: u32,
+}
+
+impl RoundingPolicy {
+    /// Create `RoundingPolicy` keeping `digits` decimal digits of computed amounts.
+    ///
+    /// `mode` applies to line totals and `tax_mode` to consumption tax.
+    pub fn new(mode: RoundingMode, tax_mode: RoundingMode, digits: u32) -> Result<Self> {
+        if digits > MONEY_DECIMAL_PRECISION {
+            return Err(Error::DomainError(format!(
+                "digits must be at most {}.",
+                MONEY_DECIMAL_PRECISION
+            )));
+        }
+
+        Ok(Self {
+            mode,
+            tax_mode,
+            digits,
+        })
+    }
+
+    pub fn mode(&self) -> RoundingMode {
+        self.mode
+    }
+
+    pub fn tax_mode(&self) -> RoundingMode {
+        self.tax_mode
+    }
+
+    pub fn digits(&self) -> u32 {
+        self.digits
+    }
+
+    /// Returns `unit_price × quantity` rounded by this policy.
+    pub fn line_total(&self, unit_price: Money, quantity: Quantity) -> Result<Money> {
+        unit_price.checked_mul_rounded(quantity, self.digits, self.mode)
+    }
+
+    /// Returns the tax on `amount` at `rate_percent` rounded by this policy.
+    ///
+    /// `amount` is treated as a tax-inclusive price when `included` is true.
+    pub fn tax(&self, amount: Money, rate_percent: i64, included: bool) -> Result<Money> {
+        let denominator = if included { 100 + rate_percent } else { 100 };
+
+        amount.checked_mul_ratio(rate_percent, denominator, self.digits, self.tax_mode)
+    }
+
+    /// Returns the part of `tax` deductible at `ratio_percent` rounded by this policy.
+    pub fn deductible_tax(&self, tax: Money, ratio_percent: i64) -> Result<Money> {
+        tax.checked_mul_ratio(ratio_percent, 100, self.digits, self.tax_mode)
+    }
+
+    /// Splits `total` in proportion to `weights` without losing any unit.
+    ///
+    /// Shares are whole units of the kept digits, and the units left over
+    /// after flooring go to the largest remainders.
+    pub fn allocate(&self, total: Money, weights: &[Money]) -> Vec<Money> {
+        let mut shares = vec![Money::ZERO; weights.len()];
+
+        if weights.is_empty() {
+            return shares;
+        }
+
+        let unit = scale_factor(MONEY_DECIMAL_PRECISION - self.digits);
+        let units = total.minor as i128 / unit;
+        let weight_sum: i128 = weights.iter().map(|weight| weight.minor as i128).sum();
+
+        if weight_sum <= 0 {
+            shares[0] = total;
+
+            return shares;
+        }
+
+        let mut allocated: i128 = 0;
+        let mut remainders: Vec<(usize, i128)> = Vec::new();
+
+        for (index, weight) in weights.iter().enumerate() {
+            let share = units * weight.minor as i128 / weight_sum;
+
+            allocated += share;
+            remainders.push((index, (units * weight.minor as i128 % weight_sum).abs()));
+            shares[index] = Money::from_minor((share * unit) as i64);
+        }
+
+        remainders.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        let left = units - allocated;
+        let step = if left < 0 { -unit } else { unit };
+
+        for (index, _) in remainders.iter().take(left.unsigned_abs() as usize) {
+            shares[*index] = Money::from_minor(shares[*index].minor + step as i64);
+        }
+
+        // Amounts finer than the kept digits stay on the first share.
+        let fraction = (total.minor as i128 - units * unit) as i64;
+
+        shares[0] = Money::from_minor(shares[0].minor + fraction);
+
+        shares
+    }
+}
+
+/// Rounds line totals half up and tax down to whole yen.
+impl Default for RoundingPolicy {
+    fn default() -> Self {
+        Self {
+            mode: RoundingMode::HalfUp,
+            tax_mode: RoundingMode::Down,
+            digits: 0,
+        }
+    }
+}
+
+/// Monetary amount held as minor units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Money {
+    minor: i64,
+}
+
+impl Money {
+    /// Zero amount.
+    pub const ZERO: Money = Money { minor: 0 };
+
+    /// Create `Money` from minor units.
+    pub const fn from_minor(minor: i64) -> Self {
+        Self { minor }
+    }
+
+    /// Create `Money` from `f64`, rounding digits beyond the precision.
+    pub fn from_f64(value: f64, mode: RoundingMode) -> Result<Self> {
+        let minor = scale_f64(value, MONEY_DECIMAL_PRECISION, mode)?;
+
+        Ok(Self { minor })
+    }
+
+    /// Returns the amount in minor units.
+    pub fn as_minor(&self) -> i64 {
+        self.minor
+    }
+
+    /// Returns the amount as `f64`.
+    pub fn as_f64(&self) -> f64 {
+        self.minor as f64 / scale_factor(MONEY_DECIMAL_PRECISION) as f64
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.minor < 0
+    }
+
+    pub fn checked_add(&self, other: Money) -> Result<Money> {
+        self.minor
+            .checked_add(other.minor)
+            .map(Money::from_minor)
+            .ok_or(Error::DomainError(format!("money overflow.")))
+    }
+
+    pub fn checked_sub(&self, other: Money) -> Result<Money> {
+        self.minor
+            .checked_sub(other.minor)
+            .map(Money::from_minor)
+            .ok_or(Error::DomainError(format!("money overflow.")))
+    }
+
+    /// Multiplies the amount by a quantity, rounding the result to the precision.
+    pub fn checked_mul(&self, quantity: Quantity, mode: RoundingMode) -> Result<Money> {
+        self.checked_mul_rounded(quantity, MONEY_DECIMAL_PRECISION, mode)
+    }
+
+    /// Multiplies the amount by a quantity, rounding the result to `digits` decimal digits.
+    pub fn checked_mul_rounded(
+        &self,
+        quantity: Quantity,
+        digits: u32,
+        mode: RoundingMode,
+    ) -> Result<Money> {
+        let digits = digits.min(MONEY_DECIMAL_PRECISION);
+
+        let product = (self.minor as i128) * (quantity.raw as i128);
+
+        let divisor = scale_factor(QUANTITY_DECIMAL_PRECISION + MONEY_DECIMAL_PRECISION - digits);
+
+        let minor = divide(product, divisor, mode) * scale_factor(MONEY_DECIMAL_PRECISION - digits);
+
+        let minor =
+            i64::try_from(minor).map_err(|_| Error::DomainError(format!("money overflow.")))?;
+
+        Ok(Money::from_minor(minor))
+    }
+
+    /// Multiplies the amount by `numerator / denominator`, rounding the result to `digits` decimal digits.
+    pub fn checked_mul_ratio(
+        &self,
+        numerator: i64,
+        denominator: i64,
+        digits: u32,
+        mode: RoundingMode,
+    ) -> Result<Money> {
+        if denominator <= 0 {
+            return Err(Error::DomainError(format!("denominator must be positive.")));
+        }
+
+        let digits = digits.min(MONEY_DECIMAL_PRECISION);
+
+        let product = (self.minor as i128) * (numerator as i128);
+
+        let factor = scale_factor(MONEY_DECIMAL_PRECISION - digits);
+
+        let minor = divide(product, denominator as i128 * factor, mode) * factor;
+
+        let minor =
+            i64::try_from(minor).map_err(|_| Error::DomainError(format!("money overflow.")))?;
+
+        Ok(Money::from_minor(minor))
+    }
+
+    /// Divides the amount by a positive quantity, e.g. a unit price from a line total.
+    pub fn checked_div(&self, quantity: Quantity, mode: RoundingMode) -> Result<Money> {
+        if quantity.raw <= 0 {
+            return Err(Error::DomainError(format!("divisor must be positive.")));
+        }
+
+        let dividend = (self.minor as i128) * scale_factor(QUANTITY_DECIMAL_PRECISION);
+
+        let minor = divide(dividend, quantity.raw as i128, mode);
+
+        let minor =
+            i64::try_from(minor).map_err(|_| Error::DomainError(format!("money overflow.")))?;
+
+        Ok(Money::from_minor(minor))
+    }
+
+    /// Sums amounts, failing on overflow.
+    pub fn checked_sum(amounts: impl IntoIterator<Item = Money>) -> Result<Money> {
+        amounts
+            .into_iter()
+            .try_fold(Money::ZERO, |sum, amount| sum.checked_add(amount))
+    }
+}
+
+/// Quantity held as thousandths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Quantity {
+    raw: i64,
+}
+
+impl Quantity {
+    /// Zero quantity.
+    pub const ZERO: Quantity = Quantity { raw: 0 };
+
+    /// Create `Quantity` from its scaled integer representation.
+    pub const fn from_raw(raw: i64) -> Self {
+        Self { raw }
+    }
+
+    /// Create `Quantity` from `f64`, rounding digits beyond the precision.
+    pub fn from_f64(value: f64, mode: RoundingMode) -> Result<Self> {
+        let raw = scale_f64(value, QUANTITY_DECIMAL_PRECISION, mode)?;
+
+        Ok(Self { raw })
+    }
+
+    /// Returns the scaled integer representation.
+    pub fn as_raw(&self) -> i64 {
+        self.raw
+    }
+
+    /// Returns the quantity as `f64`.
+    pub fn as_f64(&self) -> f64 {
+        self.raw as f64 / scale_factor(QUANTITY_DECIMAL_PRECISION) as f64
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.raw < 0
+    }
+
+    pub fn checked_add(&self, other: Quantity) -> Result<Quantity> {
+        self.raw
+            .checked_add(other.raw)
+            .map(Quantity::from_raw)
+            .ok_or(Error::DomainError(format!("quantity overflow.")))
+    }
+
+    pub fn checked_sub(&self, other: Quantity) -> Result<Quantity> {
+        self.raw
+            .checked_sub(other.raw)
+            .map(Quantity::from_raw)
+            .ok_or(Error::DomainError(format!("quantity overflow.")))
+    }
+
+    /// Multiplies the quantity by another one, rounding the result to the precision.
+    pub fn checked_mul(&self, other: Quantity, mode: RoundingMode) -> Result<Quantity> {
+        let product = (self.raw as i128) * (other.raw as i128);
+
+        let raw = divide(product, scale_factor(QUANTITY_DECIMAL_PRECISION), mode);
+
+        let raw =
+            i64::try_from(raw).map_err(|_| Error::DomainError(format!("quantity overflow.")))?;
+
+        Ok(Quantity::from_raw(raw))
+    }
+}
+
+/// Returns `10^precision`.
+fn scale_factor(precision: u32) -> i128 {
+    10_i128.pow(precision)
+}
+
+/// Divides `value` by a positive `divisor` applying the rounding mode.
+fn divide(value: i128, divisor: i128, mode: RoundingMode) -> i128 {
+    let quotient = value / divisor;
+    let remainder = (value % divisor).abs();
+    let sign = if value < 0 { -1 } else { 1 };
+
+    let round_away = match mode {
+        RoundingMode::Down => false,
+        RoundingMode::Up => remainder != 0,
+        RoundingMode::HalfUp => remainder * 2 >= divisor,
+        RoundingMode::HalfEven => {
+            remainder * 2 > divisor || (remainder * 2 == divisor && quotient % 2 != 0)
+        }
+    };
+
+    if round_away {
+        quotient + sign
+    } else {
+        quotient
+    }
+}
+
+/// Converts `f64` to an integer scaled by `10^precision`.
+///
+/// The shortest decimal representation of the value is used so that
+/// e.g. `1.005` is treated as exactly `1.005` rather than its binary approximation.
+fn scale_f64(value: f64, precision: u32, mode: RoundingMode) -> Result<i64> {
+    if !value.is_finite() {
+        return Err(Error::DomainError(format!("{} is not a number.", value)));
+    }
+
+    let text = value.abs().to_string();
+
+    let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+
+    let precision = precision as usize;
+
+    let kept = format!(
+        "{}{:0<precision$}",
+        integer,
+        &fraction[..fraction.len().min(precision)]
+    );
+    let dropped = fraction.get(precision..).unwrap_or("");
+
+    let scaled = kept
+        .parse::<i128>()
+        .ok()
+        .filter(|scaled| *scaled <= i64::MAX as i128)
+        .ok_or(Error::DomainError(format!("{} is out of range.", value)))?;
+
+    let first_dropped = dropped.chars().next().unwrap_or('0');
+    let rest_nonzero = dropped.chars().skip(1).any(|c| c != '0');
+
+    let round_away = match mode {
+        RoundingMode::Down => false,
+        RoundingMode::Up => first_dropped != '0' || rest_nonzero,
+        RoundingMode::HalfUp => first_dropped >= '5',
+        RoundingMode::HalfEven => {
+            first_dropped > '5' || (first_dropped == '5' && (rest_nonzero || scaled % 2 != 0))
+        }
+    };
+
+    let scaled = if round_away { scaled + 1 } else { scaled };
+
+    let scaled = if value < 0.0 { -scaled } else { scaled };
+
+    i64::try_from(scaled).map_err(|_| Error::DomainError(format!("{} is out of range.", value)))
+}
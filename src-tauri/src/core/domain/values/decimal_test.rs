@@ -0,0 +1,160 @@
+use crate::core::domain::values::decimal::*;
+
+#[test]
+fn money_from_f64_test() {
+    assert_eq!(
+        Money::from_f64(1.005, RoundingMode::HalfUp).unwrap(),
+        Money::from_minor(101)
+    );
+    assert_eq!(
+        Money::from_f64(1.005, RoundingMode::HalfEven).unwrap(),
+        Money::from_minor(100)
+    );
+    assert_eq!(
+        Money::from_f64(1.015, RoundingMode::HalfEven).unwrap(),
+        Money::from_minor(102)
+    );
+    assert_eq!(
+        Money::from_f64(1.009, RoundingMode::Down).unwrap(),
+        Money::from_minor(100)
+    );
+    assert_eq!(
+        Money::from_f64(1.001, RoundingMode::Up).unwrap(),
+        Money::from_minor(101)
+    );
+    assert_eq!(
+        Money::from_f64(-1.005, RoundingMode::HalfUp).unwrap(),
+        Money::from_minor(-101)
+    );
+    assert_eq!(
+        Money::from_f64(12_345_678_901.0, RoundingMode::HalfUp).unwrap(),
+        Money::from_minor(1_234_567_890_100)
+    );
+
+    assert!(Money::from_f64(f64::NAN, RoundingMode::HalfUp).is_err());
+    assert!(Money::from_f64(1e300, RoundingMode::HalfUp).is_err());
+}
+
+#[test]
+fn money_arithmetic_test() {
+    let price = Money::from_f64(123.45, RoundingMode::HalfUp).unwrap();
+    let quantity = Quantity::from_f64(0.333, RoundingMode::HalfUp).unwrap();
+
+    // 123.45 * 0.333 = 41.10885
+    assert_eq!(
+        price.checked_mul(quantity, RoundingMode::HalfUp).unwrap(),
+        Money::from_minor(4111)
+    );
+    assert_eq!(
+        price.checked_mul(quantity, RoundingMode::Down).unwrap(),
+        Money::from_minor(4110)
+    );
+
+    let sum = Money::checked_sum([price, Money::from_minor(55)]).unwrap();
+
+    assert_eq!(sum, Money::from_minor(12400));
+    assert_eq!(sum.as_f64(), 124.0);
+
+    assert!(Money::from_minor(i64::MAX)
+        .checked_add(Money::from_minor(1))
+        .is_err());
+    assert!(Money::from_minor(i64::MAX)
+        .checked_mul(Quantity::from_raw(2000), RoundingMode::HalfUp)
+        .is_err());
+
+    // 1000 / 3 = 333.333...
+    assert_eq!(
+        Money::from_minor(100000)
+            .checked_div(Quantity::from_raw(3000), RoundingMode::HalfUp)
+            .unwrap(),
+        Money::from_minor(33333)
+    );
+    assert!(Money::from_minor(100)
+        .checked_div(Quantity::ZERO, RoundingMode::HalfUp)
+        .is_err());
+}
+
+#[test]
+fn quantity_test() {
+    let quantity = Quantity::from_f64(2.5, RoundingMode::HalfUp).unwrap();
+
+    assert_eq!(quantity.as_raw(), 2500);
+    assert_eq!(quantity.as_f64(), 2.5);
+
+    let quantity = quantity
+        .checked_sub(Quantity::from_f64(0.0005, RoundingMode::HalfUp).unwrap())
+        .unwrap();
+
+    assert_eq!(quantity.as_raw(), 2499);
+}
+
+#[test]
+fn rounding_policy_test() {
+    let unit_price = Money::from_f64(98.5, RoundingMode::HalfUp).unwrap();
+    let quantity = Quantity::from_f64(3.0, RoundingMode::HalfUp).unwrap();
+
+    // 98.5 * 3 = 295.5
+    let policy = RoundingPolicy::default();
+
+    assert_eq!(
+        policy.line_total(unit_price, quantity).unwrap(),
+        Money::from_minor(29600)
+    );
+
+    let policy = RoundingPolicy::new(RoundingMode::Down, RoundingMode::Down, 0).unwrap();
+
+    assert_eq!(
+        policy.line_total(unit_price, quantity).unwrap(),
+        Money::from_minor(29500)
+    );
+
+    let policy = RoundingPolicy::new(RoundingMode::HalfUp, RoundingMode::Down, 2).unwrap();
+
+    assert_eq!(
+        policy.line_total(unit_price, quantity).unwrap(),
+        Money::from_minor(29550)
+    );
+
+    assert!(RoundingPolicy::new(RoundingMode::HalfUp, RoundingMode::Down, 3).is_err());
+}
+
+#[test]
+fn tax_test() {
+    let policy = RoundingPolicy::default();
+
+    // 1080 includes 80 of tax at 8%
+    assert_eq!(
+        policy.tax(Money::from_minor(108000), 8, true).unwrap(),
+        Money::from_minor(8000)
+    );
+
+    // 999 * 10 / 110 = 90.81...
+    assert_eq!(
+        policy.tax(Money::from_minor(99900), 10, true).unwrap(),
+        Money::from_minor(9000)
+    );
+
+    // 999 * 10 / 100 = 99.9
+    assert_eq!(
+        policy.tax(Money::from_minor(99900), 10, false).unwrap(),
+        Money::from_minor(9900)
+    );
+
+    let shares = policy.allocate(
+        Money::from_minor(10000),
+        &[
+            Money::from_minor(100),
+            Money::from_minor(100),
+            Money::from_minor(100),
+        ],
+    );
+
+    assert_eq!(
+        shares,
+        vec![
+            Money::from_minor(3400),
+            Money::from_minor(3300),
+            Money::from_minor(3300),
+        ]
+    );
+}
@@ -0,0 +1,132 @@
+//! Domain event value objects, telling the rest of the application what changed.
+
+use crate::core::domain::values::stock::*;
+
+/// A change of suppliers, supplies, journals or stocktakings, published once it is saved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DomainEvent {
+    SupplierRegistered {
+        supplier_id: SupplierId,
+    },
+    SupplierUpdated {
+        supplier_id: SupplierId,
+    },
+    SupplierDeleted {
+        supplier_id: SupplierId,
+    },
+    SupplierRestored {
+        supplier_id: SupplierId,
+    },
+    SupplierPurged {
+        supplier_id: SupplierId,
+    },
+    SupplyRegistered {
+        supply_id: SupplyId,
+    },
+    SupplyUpdated {
+        supply_id: SupplyId,
+    },
+    /// published along with `SupplyUpdated` when the name changed
+    SupplyRenamed {
+        supply_id: SupplyId,
+        previous_name: SupplyName,
+        name: SupplyName,
+    },
+    SupplyDeleted {
+        supply_id: SupplyId,
+    },
+    SupplyRestored {
+        supply_id: SupplyId,
+    },
+    SupplyPurged {
+        supply_id: SupplyId,
+    },
+    JournalRecorded {
+        journal_id: JournalId,
+    },
+    /// published for edits and reverts to a revision
+    JournalEdited {
+        journal_id: JournalId,
+    },
+    JournalDeleted {
+        journal_id: JournalId,
+    },
+    JournalRestored {
+        journal_id: JournalId,
+    },
+    JournalPurged {
+        journal_id: JournalId,
+    },
+    StocktakingRecorded {
+        stocktaking_id: StocktakingId,
+    },
+    /// published for edits and reverts to a revision
+    StocktakingEdited {
+        stocktaking_id: StocktakingId,
+    },
+    StocktakingDeleted {
+        stocktaking_id: StocktakingId,
+    },
+    StocktakingRestored {
+        stocktaking_id: StocktakingId,
+    },
+    StocktakingPurged {
+        stocktaking_id: StocktakingId,
+    },
+}
+
+impl DomainEvent {
+    /// Returns the name of the event, such as `journal-recorded`.
+    pub fn name(&self) -> &str {
+        match self {
+            DomainEvent::SupplierRegistered { .. } => "supplier-registered",
+            DomainEvent::SupplierUpdated { .. } => "supplier-updated",
+            DomainEvent::SupplierDeleted { .. } => "supplier-deleted",
+            DomainEvent::SupplierRestored { .. } => "supplier-restored",
+            DomainEvent::SupplierPurged { .. } => "supplier-purged",
+            DomainEvent::SupplyRegistered { .. } => "supply-registered",
+            DomainEvent::SupplyUpdated { .. } => "supply-updated",
+            DomainEvent::SupplyRenamed { .. } => "supply-renamed",
+            DomainEvent::SupplyDeleted { .. } => "supply-deleted",
+            DomainEvent::SupplyRestored { .. } => "supply-restored",
+            DomainEvent::SupplyPurged { .. } => "supply-purged",
+            DomainEvent::JournalRecorded { .. } => "journal-recorded",
+            DomainEvent::JournalEdited { .. } => "journal-edited",
+            DomainEvent::JournalDeleted { .. } => "journal-deleted",
+            DomainEvent::JournalRestored { .. } => "journal-restored",
+            DomainEvent::JournalPurged { .. } => "journal-purged",
+            DomainEvent::StocktakingRecorded { .. } => "stocktaking-recorded",
+            DomainEvent::StocktakingEdited { .. } => "stocktaking-edited",
+            DomainEvent::StocktakingDeleted { .. } => "stocktaking-deleted",
+            DomainEvent::StocktakingRestored { .. } => "stocktaking-restored",
+            DomainEvent::StocktakingPurged { .. } => "stocktaking-purged",
+        }
+    }
+
+    /// Returns the id of the supplier, supply, journal or stocktaking that changed.
+    pub fn entity_id(&self) -> &str {
+        match self {
+            DomainEvent::SupplierRegistered { supplier_id }
+            | DomainEvent::SupplierUpdated { supplier_id }
+            | DomainEvent::SupplierDeleted { supplier_id }
+            | DomainEvent::SupplierRestored { supplier_id }
+            | DomainEvent::SupplierPurged { supplier_id } => supplier_id.as_str(),
+            DomainEvent::SupplyRegistered { supply_id }
+            | DomainEvent::SupplyUpdated { supply_id }
+            | DomainEvent::SupplyRenamed { supply_id, .. }
+            | DomainEvent::SupplyDeleted { supply_id }
+            | DomainEvent::SupplyRestored { supply_id }
+            | DomainEvent::SupplyPurged { supply_id } => supply_id.as_str(),
+            DomainEvent::JournalRecorded { journal_id }
+            | DomainEvent::JournalEdited { journal_id }
+            | DomainEvent::JournalDeleted { journal_id }
+            | DomainEvent::JournalRestored { journal_id }
+            | DomainEvent::JournalPurged { journal_id } => journal_id.as_str(),
+            DomainEvent::StocktakingRecorded { stocktaking_id }
+            | DomainEvent::StocktakingEdited { stocktaking_id }
+            | DomainEvent::StocktakingDeleted { stocktaking_id }
+            | DomainEvent::StocktakingRestored { stocktaking_id }
+            | DomainEvent::StocktakingPurged { stocktaking_id } => stocktaking_id.as_str(),
+        }
+    }
+}
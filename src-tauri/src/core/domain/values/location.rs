@@ -0,0 +1,101 @@
+//! Storage location value objects.
+use crate::core::{Error, Result};
+
+/// ID of storage location.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StorageLocationId {
+    value: String,
+}
+
+impl StorageLocationId {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for StorageLocationId {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Name of storage location, e.g. 冷蔵庫1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageLocationName {
+    value: String,
+}
+
+impl StorageLocationName {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for StorageLocationName {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Position on a count sheet, smaller first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SortOrder {
+    value: u32,
+}
+
+impl SortOrder {
+    pub fn new(value: impl Into<i64>) -> Result<Self> {
+        let value = u32::try_from(value.into())
+            .map_err(|_| Error::DomainError(format!("sort order must not be negative.")))?;
+
+        Ok(Self { value })
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        self.value
+    }
+}
+
+/// Location a supply is kept in by default and its position within the location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoragePlacement {
+    location_id: StorageLocationId,
+    sort_order: SortOrder,
+}
+
+impl StoragePlacement {
+    pub fn new(location_id: StorageLocationId, sort_order: SortOrder) -> Self {
+        Self {
+            location_id,
+            sort_order,
+        }
+    }
+
+    pub fn location_id(&self) -> &StorageLocationId {
+        &self.location_id
+    }
+
+    pub fn sort_order(&self) -> SortOrder {
+        self.sort_order
+    }
+}
@@ -0,0 +1,56 @@
+//! Lot value objects.
+use crate::core::{Error, Result};
+
+/// Milliseconds in a day.
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+
+/// Lot number printed on received goods, e.g. L2401-03
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LotNumber {
+    value: String,
+}
+
+impl LotNumber {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for LotNumber {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Best-before or expiry date of received goods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExpiryDate {
+    value: i64,
+}
+
+impl ExpiryDate {
+    pub fn new(value: impl Into<i64>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        self.value
+    }
+
+    /// Whether the goods expire within `days` days of `at`, including goods already expired.
+    pub fn is_within(&self, at: i64, days: u32) -> bool {
+        self.value <= at.saturating_add(i64::from(days).saturating_mul(DAY_MILLIS))
+    }
+}
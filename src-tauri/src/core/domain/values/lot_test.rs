@@ -0,0 +1,18 @@
+use crate::core::domain::values::lot::*;
+
+#[test]
+fn lot_number_test() {
+    assert_eq!(LotNumber::new(" L2401 ").unwrap().as_str(), "L2401");
+    assert!(LotNumber::new("  ").is_err());
+}
+
+#[test]
+fn expiry_date_test() {
+    let day = 24 * 60 * 60 * 1000;
+    let expiry = ExpiryDate::new(10 * day);
+
+    assert!(expiry.is_within(3 * day, 7));
+    assert!(!expiry.is_within(3 * day, 6));
+    // already expired
+    assert!(expiry.is_within(11 * day, 0));
+}
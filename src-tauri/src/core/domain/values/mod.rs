@@ -1 +1,46 @@
+pub mod accounting;
+pub mod audit;
+pub mod category;
+pub mod decimal;
+pub mod event;
+pub mod location;
+pub mod lot;
+pub mod order;
+pub mod payable;
+pub mod price;
+pub mod reorder;
+pub mod report;
+pub mod revision;
 pub mod stock;
+pub mod store;
+pub mod tax;
+pub mod transfer;
+pub mod trash;
+pub mod unit;
+pub mod valuation;
+pub mod waste;
+
+#[cfg(test)]
+mod accounting_test;
+#[cfg(test)]
+mod audit_test;
+#[cfg(test)]
+mod decimal_test;
+#[cfg(test)]
+mod lot_test;
+#[cfg(test)]
+mod price_test;
+#[cfg(test)]
+mod reorder_test;
+#[cfg(test)]
+mod revision_test;
+#[cfg(test)]
+mod stock_test;
+#[cfg(test)]
+mod tax_test;
+#[cfg(test)]
+mod trash_test;
+#[cfg(test)]
+mod valuation_test;
+#[cfg(test)]
+mod waste_test;
@@ -0,0 +1,192 @@
+//! Purchase order value objects.
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::*;
+use crate::core::{Error, Result};
+
+/// ID of purchase order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PurchaseOrderId {
+    value: String,
+}
+
+impl PurchaseOrderId {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for PurchaseOrderId {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct OrderedDateTime {
+    value: i64,
+}
+
+impl OrderedDateTime {
+    pub fn new(value: impl Into<i64>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        self.value
+    }
+}
+
+/// Lifecycle of a purchase order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OrderStatus {
+    /// Being prepared, lines can be revised.
+    #[default]
+    Draft,
+    /// Sent to the supplier, waiting for delivery.
+    Sent,
+    /// Some of the ordered quantities arrived.
+    PartiallyReceived,
+    /// All of the ordered quantities arrived.
+    Received,
+    /// Will not be delivered any further.
+    Cancelled,
+}
+
+impl OrderStatus {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        match value.as_ref().trim() {
+            "draft" => Ok(OrderStatus::Draft),
+            "sent" => Ok(OrderStatus::Sent),
+            "partially_received" => Ok(OrderStatus::PartiallyReceived),
+            "received" => Ok(OrderStatus::Received),
+            "cancelled" => Ok(OrderStatus::Cancelled),
+            value => Err(Error::DomainError(format!(
+                "unknown order status: {}",
+                value
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            OrderStatus::Draft => "draft",
+            OrderStatus::Sent => "sent",
+            OrderStatus::PartiallyReceived => "partially_received",
+            OrderStatus::Received => "received",
+            OrderStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// Whether deliveries can still be received.
+    pub fn is_open(&self) -> bool {
+        matches!(self, OrderStatus::Sent | OrderStatus::PartiallyReceived)
+    }
+}
+
+impl ToString for OrderStatus {
+    fn to_string(&self) -> String {
+        self.as_str().to_string()
+    }
+}
+
+/// Quantity of a supply ordered in its purchase unit and the quantity received so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PurchaseOrderLine {
+    supply_id: SupplyId,
+    supply_name: SupplyName,
+    unit_name: UnitName,
+    quantity: PurchaseQuantity,
+    expected_unit_price: PurchaseUnitPrice,
+    received_quantity: Quantity,
+}
+
+impl PurchaseOrderLine {
+    pub fn new(
+        supply_id: SupplyId,
+        supply_name: SupplyName,
+        unit_name: UnitName,
+        quantity: PurchaseQuantity,
+        expected_unit_price: PurchaseUnitPrice,
+    ) -> Self {
+        Self::restore(
+            supply_id,
+            supply_name,
+            unit_name,
+            quantity,
+            expected_unit_price,
+            Quantity::ZERO,
+        )
+    }
+
+    pub fn restore(
+        supply_id: SupplyId,
+        supply_name: SupplyName,
+        unit_name: UnitName,
+        quantity: PurchaseQuantity,
+        expected_unit_price: PurchaseUnitPrice,
+        received_quantity: Quantity,
+    ) -> Self {
+        Self {
+            supply_id,
+            supply_name,
+            unit_name,
+            quantity,
+            expected_unit_price,
+            received_quantity,
+        }
+    }
+
+    pub fn supply_id(&self) -> &SupplyId {
+        &self.supply_id
+    }
+
+    pub fn supply_name(&self) -> &SupplyName {
+        &self.supply_name
+    }
+
+    pub fn unit_name(&self) -> &UnitName {
+        &self.unit_name
+    }
+
+    pub fn quantity(&self) -> &PurchaseQuantity {
+        &self.quantity
+    }
+
+    pub fn expected_unit_price(&self) -> &PurchaseUnitPrice {
+        &self.expected_unit_price
+    }
+
+    pub fn received_quantity(&self) -> Quantity {
+        self.received_quantity
+    }
+
+    /// Whether the ordered quantity has arrived.
+    pub fn is_fulfilled(&self) -> bool {
+        self.received_quantity >= self.quantity.as_quantity()
+    }
+
+    /// Adds a delivered quantity, more than ordered is accepted.
+    pub fn receive(&mut self, quantity: Quantity) -> Result<()> {
+        if quantity.as_raw() <= 0 {
+            return Err(Error::DomainError(format!(
+                "received quantity must be positive."
+            )));
+        }
+
+        self.received_quantity = self.received_quantity.checked_add(quantity)?;
+
+        Ok(())
+    }
+}
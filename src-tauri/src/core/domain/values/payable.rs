@@ -0,0 +1,295 @@
+//! Accounts payable value objects.
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::*;
+use crate::core::{Error, Result};
+
+/// Milliseconds in a day.
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+
+/// ID of supplier invoice.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SupplierInvoiceId {
+    value: String,
+}
+
+impl SupplierInvoiceId {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for SupplierInvoiceId {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// ID of payment against a supplier invoice.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PaymentId {
+    value: String,
+}
+
+impl PaymentId {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for PaymentId {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Number the supplier printed on the invoice (請求書番号).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvoiceNumber {
+    value: String,
+}
+
+impl InvoiceNumber {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for InvoiceNumber {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Closing period an invoice bills, both ends included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BillingPeriod {
+    start: i64,
+    end: i64,
+}
+
+impl BillingPeriod {
+    pub fn new(start: impl Into<i64>, end: impl Into<i64>) -> Result<Self> {
+        let start = start.into();
+        let end = end.into();
+
+        if end < start {
+            return Err(Error::DomainError(format!(
+                "billing period must not end before it starts."
+            )));
+        }
+
+        Ok(Self { start, end })
+    }
+
+    pub fn start(&self) -> i64 {
+        self.start
+    }
+
+    pub fn end(&self) -> i64 {
+        self.end
+    }
+
+    /// Returns whether a purchase entered at `entry_datetime` falls in the period.
+    pub fn contains(&self, entry_datetime: &EntryDateTime) -> bool {
+        self.start <= entry_datetime.as_i64() && entry_datetime.as_i64() <= self.end
+    }
+}
+
+/// Date the invoice is to be paid by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DueDate {
+    value: i64,
+}
+
+impl DueDate {
+    pub fn new(value: impl Into<i64>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        self.value
+    }
+
+    /// Returns the whole days past the due date at `at`, zero if not yet overdue.
+    pub fn days_overdue(&self, at: i64) -> i64 {
+        ((at - self.value) / DAY_MILLIS).max(0)
+    }
+}
+
+/// Amount billed including consumption tax.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvoicedAmount {
+    value: Money,
+}
+
+impl InvoicedAmount {
+    pub fn new(value: impl Into<f64>) -> Result<Self> {
+        Self::from_money(Money::from_f64(value.into(), RoundingMode::HalfUp)?)
+    }
+
+    pub fn from_money(money: Money) -> Result<Self> {
+        if money.is_negative() {
+            return Err(Error::DomainError(format!(
+                "invoiced amount must not be negative."
+            )));
+        }
+
+        Ok(Self { value: money })
+    }
+
+    pub fn as_money(&self) -> Money {
+        self.value
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.value.as_f64()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PaidDateTime {
+    value: i64,
+}
+
+impl PaidDateTime {
+    pub fn new(value: impl Into<i64>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        self.value
+    }
+}
+
+/// Amount paid against an invoice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaymentAmount {
+    value: Money,
+}
+
+impl PaymentAmount {
+    pub fn new(value: impl Into<f64>) -> Result<Self> {
+        Self::from_money(Money::from_f64(value.into(), RoundingMode::HalfUp)?)
+    }
+
+    pub fn from_money(money: Money) -> Result<Self> {
+        if money <= Money::ZERO {
+            return Err(Error::DomainError(format!(
+                "payment amount must be positive."
+            )));
+        }
+
+        Ok(Self { value: money })
+    }
+
+    pub fn as_money(&self) -> Money {
+        self.value
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.value.as_f64()
+    }
+}
+
+/// Payment made against a supplier invoice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Payment {
+    id: PaymentId,
+    paid_datetime: PaidDateTime,
+    amount: PaymentAmount,
+    method: PaymentMethod,
+}
+
+impl Payment {
+    pub fn new(
+        id: PaymentId,
+        paid_datetime: PaidDateTime,
+        amount: PaymentAmount,
+        method: PaymentMethod,
+    ) -> Self {
+        Self {
+            id,
+            paid_datetime,
+            amount,
+            method,
+        }
+    }
+
+    pub fn id(&self) -> &PaymentId {
+        &self.id
+    }
+
+    pub fn paid_at(&self) -> &PaidDateTime {
+        &self.paid_datetime
+    }
+
+    pub fn amount(&self) -> &PaymentAmount {
+        &self.amount
+    }
+
+    pub fn method(&self) -> PaymentMethod {
+        self.method
+    }
+}
+
+/// Range of days past the due date an outstanding balance falls in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgingBucket {
+    /// Not yet overdue.
+    Current,
+    /// 1 to 30 days overdue.
+    Overdue1To30,
+    /// 31 to 60 days overdue.
+    Overdue31To60,
+    /// 61 to 90 days overdue.
+    Overdue61To90,
+    /// More than 90 days overdue.
+    OverdueOver90,
+}
+
+impl AgingBucket {
+    /// Returns the bucket of a balance due on `due_date` at `at`.
+    pub fn of(due_date: &DueDate, at: i64) -> Self {
+        match due_date.days_overdue(at) {
+            0 => AgingBucket::Current,
+            1..=30 => AgingBucket::Overdue1To30,
+            31..=60 => AgingBucket::Overdue31To60,
+            61..=90 => AgingBucket::Overdue61To90,
+            _ => AgingBucket::OverdueOver90,
+        }
+    }
+}
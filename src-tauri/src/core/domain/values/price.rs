@@ -0,0 +1,102 @@
+//! Purchase price value objects.
+use crate::core::domain::values::decimal::*;
+use crate::core::{Error, Result};
+
+/// Rule flagging unit prices deviating from the average of recent purchases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceAlertRule {
+    /// deviation flagged in percent, 0 disables the rule
+    threshold_percent: u32,
+    /// number of recent purchases averaged
+    sample_size: usize,
+}
+
+impl PriceAlertRule {
+    pub fn new(threshold_percent: u32, sample_size: usize) -> Result<Self> {
+        if sample_size == 0 {
+            return Err(Error::DomainError(format!("sample size must be positive.")));
+        }
+
+        Ok(Self {
+            threshold_percent,
+            sample_size,
+        })
+    }
+
+    pub fn threshold_percent(&self) -> u32 {
+        self.threshold_percent
+    }
+
+    pub fn sample_size(&self) -> usize {
+        self.sample_size
+    }
+
+    /// Checks `price` against the average of `recent` prices, latest first.
+    ///
+    /// Only the first `sample_size` prices are averaged.
+    /// Returns `None` if the deviation is within the threshold or there is nothing to compare with.
+    pub fn check(&self, recent: &[Money], price: Money) -> Result<Option<PriceDeviation>> {
+        let recent = &recent[..recent.len().min(self.sample_size)];
+
+        if self.threshold_percent == 0 || recent.is_empty() {
+            return Ok(None);
+        }
+
+        let average = Money::checked_sum(recent.iter().copied())?.checked_mul_ratio(
+            1,
+            recent.len() as i64,
+            MONEY_DECIMAL_PRECISION,
+            RoundingMode::HalfUp,
+        )?;
+
+        if average.as_minor() <= 0 {
+            return Ok(None);
+        }
+
+        // deviation in hundredths of a percent
+        let deviation = price.checked_sub(average)?.checked_mul_ratio(
+            10000,
+            average.as_minor(),
+            MONEY_DECIMAL_PRECISION,
+            RoundingMode::HalfUp,
+        )?;
+
+        if deviation.as_minor().abs() <= self.threshold_percent as i64 * 100 {
+            return Ok(None);
+        }
+
+        Ok(Some(PriceDeviation {
+            average,
+            basis_points: deviation.as_minor(),
+        }))
+    }
+}
+
+impl Default for PriceAlertRule {
+    /// Flags prices deviating more than 20% from the average of the latest 5 purchases.
+    fn default() -> Self {
+        Self {
+            threshold_percent: 20,
+            sample_size: 5,
+        }
+    }
+}
+
+/// Deviation of a unit price from the average of recent purchases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceDeviation {
+    average: Money,
+    /// deviation in hundredths of a percent
+    basis_points: i64,
+}
+
+impl PriceDeviation {
+    pub fn average(&self) -> Money {
+        self.average
+    }
+
+    /// Returns the deviation in percent, positive if the price rose.
+    pub fn percent(&self) -> f64 {
+        self.basis_points as f64 / 100.0
+    }
+}
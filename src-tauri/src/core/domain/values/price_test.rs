@@ -0,0 +1,43 @@
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::price::*;
+
+#[test]
+fn price_alert_rule_test() {
+    assert!(PriceAlertRule::new(20, 0).is_err());
+
+    let rule = PriceAlertRule::new(20, 3).unwrap();
+
+    let recent = [
+        Money::from_minor(12000),
+        Money::from_minor(10000),
+        Money::from_minor(11000),
+        // out of the sample
+        Money::from_minor(1000),
+    ];
+
+    // within 20% of the average 110
+    assert_eq!(rule.check(&recent, Money::from_minor(13200)).unwrap(), None);
+
+    let deviation = rule
+        .check(&recent, Money::from_minor(13300))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(deviation.average(), Money::from_minor(11000));
+    assert_eq!(deviation.percent(), 20.91);
+
+    let deviation = rule
+        .check(&recent, Money::from_minor(8000))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(deviation.percent(), -27.27);
+
+    // nothing to compare with
+    assert_eq!(rule.check(&[], Money::from_minor(13300)).unwrap(), None);
+
+    // disabled
+    let rule = PriceAlertRule::new(0, 3).unwrap();
+
+    assert_eq!(rule.check(&recent, Money::from_minor(50000)).unwrap(), None);
+}
@@ -0,0 +1,74 @@
+//! Reorder value objects.
+use crate::core::domain::values::decimal::*;
+use crate::core::{Error, Result};
+
+/// Par level and reorder point of a supply in its stock unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReorderLevels {
+    /// quantity to bring the stock back up to
+    par_level: Quantity,
+    /// stock at or below which the supply is reordered
+    reorder_point: Quantity,
+}
+
+impl ReorderLevels {
+    pub fn new(par_level: impl Into<f64>, reorder_point: impl Into<f64>) -> Result<Self> {
+        let par_level = Quantity::from_f64(par_level.into(), RoundingMode::HalfUp)?;
+        let reorder_point = Quantity::from_f64(reorder_point.into(), RoundingMode::HalfUp)?;
+
+        Self::from_quantities(par_level, reorder_point)
+    }
+
+    pub fn from_quantities(par_level: Quantity, reorder_point: Quantity) -> Result<Self> {
+        if par_level.as_raw() <= 0 {
+            return Err(Error::DomainError(format!("par level must be positive.")));
+        }
+
+        if reorder_point.is_negative() {
+            return Err(Error::DomainError(format!(
+                "reorder point must not be negative."
+            )));
+        }
+
+        if reorder_point > par_level {
+            return Err(Error::DomainError(format!(
+                "reorder point must not exceed the par level."
+            )));
+        }
+
+        Ok(Self {
+            par_level,
+            reorder_point,
+        })
+    }
+
+    pub fn par_level(&self) -> Quantity {
+        self.par_level
+    }
+
+    pub fn reorder_point(&self) -> Quantity {
+        self.reorder_point
+    }
+
+    /// Returns the quantity to order to reach the par level,
+    /// none while `on_hand` is above the reorder point.
+    pub fn suggest(&self, on_hand: Quantity) -> Result<Option<Quantity>> {
+        if on_hand > self.reorder_point {
+            return Ok(None);
+        }
+
+        let on_hand = if on_hand.is_negative() {
+            Quantity::ZERO
+        } else {
+            on_hand
+        };
+
+        let quantity = self.par_level.checked_sub(on_hand)?;
+
+        if quantity.as_raw() <= 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(quantity))
+    }
+}
@@ -0,0 +1,28 @@
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::reorder::*;
+
+#[test]
+fn reorder_levels_test() {
+    assert!(ReorderLevels::new(0, 0).is_err());
+    assert!(ReorderLevels::new(10, -1).is_err());
+    assert!(ReorderLevels::new(10, 11).is_err());
+
+    let levels = ReorderLevels::new(10, 3.5).unwrap();
+
+    let quantity = |value: f64| Quantity::from_f64(value, RoundingMode::HalfUp).unwrap();
+
+    // above the reorder point
+    assert_eq!(levels.suggest(quantity(3.6)).unwrap(), None);
+
+    assert_eq!(levels.suggest(quantity(3.5)).unwrap(), Some(quantity(6.5)));
+    assert_eq!(
+        levels.suggest(Quantity::ZERO).unwrap(),
+        Some(quantity(10.0))
+    );
+
+    // a shortfall in the theoretical stock is not ordered on top of the par level
+    assert_eq!(
+        levels.suggest(quantity(-2.0)).unwrap(),
+        Some(quantity(10.0))
+    );
+}
@@ -0,0 +1,68 @@
+//! Report value objects.
+use crate::core::domain::values::decimal::*;
+use crate::core::Result;
+
+/// Opening inventory, purchases, transfers and closing inventory of a period.
+///
+/// Cost of goods sold is `opening inventory + purchases + transfers - closing inventory`,
+/// transfers being negative when more was sent to other stores than received from them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CostOfGoodsSold {
+    opening_inventory: Money,
+    purchases: Money,
+    transfers: Money,
+    closing_inventory: Money,
+}
+
+impl CostOfGoodsSold {
+    pub fn new(
+        opening_inventory: Money,
+        purchases: Money,
+        transfers: Money,
+        closing_inventory: Money,
+    ) -> Self {
+        Self {
+            opening_inventory,
+            purchases,
+            transfers,
+            closing_inventory,
+        }
+    }
+
+    pub fn opening_inventory(&self) -> Money {
+        self.opening_inventory
+    }
+
+    pub fn purchases(&self) -> Money {
+        self.purchases
+    }
+
+    pub fn transfers(&self) -> Money {
+        self.transfers
+    }
+
+    pub fn closing_inventory(&self) -> Money {
+        self.closing_inventory
+    }
+
+    /// Returns the cost of goods sold, negative if the closing inventory exceeds the rest.
+    pub fn cost(&self) -> Result<Money> {
+        self.opening_inventory
+            .checked_add(self.purchases)?
+            .checked_add(self.transfers)?
+            .checked_sub(self.closing_inventory)
+    }
+
+    pub fn checked_add(&self, other: CostOfGoodsSold) -> Result<CostOfGoodsSold> {
+        Ok(Self {
+            opening_inventory: self
+                .opening_inventory
+                .checked_add(other.opening_inventory)?,
+            purchases: self.purchases.checked_add(other.purchases)?,
+            transfers: self.transfers.checked_add(other.transfers)?,
+            closing_inventory: self
+                .closing_inventory
+                .checked_add(other.closing_inventory)?,
+        })
+    }
+}
@@ -0,0 +1,326 @@
+//! Revision value objects, the prior versions kept when a journal or stocktaking is edited.
+
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::location::*;
+use crate::core::domain::values::lot::*;
+use crate::core::domain::values::stock::*;
+use crate::core::{Error, Result};
+
+/// Number of a revision, counting the versions of a record from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RevisionNumber {
+    value: u32,
+}
+
+impl RevisionNumber {
+    pub fn new(value: u32) -> Result<Self> {
+        if value == 0 {
+            return Err(Error::DomainError(format!(
+                "revision number must start from 1."
+            )));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        self.value
+    }
+}
+
+/// Date and time a version was revised, that is replaced by an edit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RevisedDateTime {
+    value: i64,
+}
+
+impl RevisedDateTime {
+    pub fn new(value: i64) -> Self {
+        Self { value }
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        self.value
+    }
+}
+
+/// A prior version of a record, kept immutable once revised.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revision<T> {
+    number: RevisionNumber,
+    revised_at: RevisedDateTime,
+    item: T,
+}
+
+impl<T> Revision<T> {
+    pub fn new(number: RevisionNumber, revised_at: RevisedDateTime, item: T) -> Self {
+        Self {
+            number,
+            revised_at,
+            item,
+        }
+    }
+
+    pub fn number(&self) -> RevisionNumber {
+        self.number
+    }
+
+    pub fn revised_at(&self) -> RevisedDateTime {
+        self.revised_at
+    }
+
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    pub fn into_item(self) -> T {
+        self.item
+    }
+}
+
+/// A line of a version compared between revisions.
+///
+/// Lines are matched by supply, supplier, location and lot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevisionLine {
+    supply_id: SupplyId,
+    supply_name: SupplyName,
+    supplier_id: Option<SupplierId>,
+    supplier_name: Option<SupplierName>,
+    location_id: Option<StorageLocationId>,
+    lot_number: Option<LotNumber>,
+    unit_name: UnitName,
+    unit_price: Money,
+    quantity: Quantity,
+    total_price: Money,
+}
+
+impl RevisionLine {
+    pub fn new(
+        supply_id: SupplyId,
+        supply_name: SupplyName,
+        unit_name: UnitName,
+        unit_price: Money,
+        quantity: Quantity,
+        total_price: Money,
+    ) -> Self {
+        Self {
+            supply_id,
+            supply_name,
+            supplier_id: None,
+            supplier_name: None,
+            location_id: None,
+            lot_number: None,
+            unit_name,
+            unit_price,
+            quantity,
+            total_price,
+        }
+    }
+
+    /// Sets the supplier the line was purchased from.
+    pub fn with_supplier(mut self, supplier_id: SupplierId, supplier_name: SupplierName) -> Self {
+        self.supplier_id = Some(supplier_id);
+        self.supplier_name = Some(supplier_name);
+        self
+    }
+
+    /// Sets the storage location the line was counted at.
+    pub fn with_location(mut self, location_id: Option<StorageLocationId>) -> Self {
+        self.location_id = location_id;
+        self
+    }
+
+    /// Sets the lot of the line.
+    pub fn with_lot(mut self, lot_number: Option<LotNumber>) -> Self {
+        self.lot_number = lot_number;
+        self
+    }
+
+    pub fn supply_id(&self) -> &SupplyId {
+        &self.supply_id
+    }
+
+    pub fn supply_name(&self) -> &SupplyName {
+        &self.supply_name
+    }
+
+    pub fn supplier_id(&self) -> Option<&SupplierId> {
+        self.supplier_id.as_ref()
+    }
+
+    pub fn supplier_name(&self) -> Option<&SupplierName> {
+        self.supplier_name.as_ref()
+    }
+
+    pub fn location_id(&self) -> Option<&StorageLocationId> {
+        self.location_id.as_ref()
+    }
+
+    pub fn lot_number(&self) -> Option<&LotNumber> {
+        self.lot_number.as_ref()
+    }
+
+    pub fn unit_name(&self) -> &UnitName {
+        &self.unit_name
+    }
+
+    pub fn unit_price(&self) -> Money {
+        self.unit_price
+    }
+
+    pub fn quantity(&self) -> Quantity {
+        self.quantity
+    }
+
+    pub fn total_price(&self) -> Money {
+        self.total_price
+    }
+
+    /// Returns whether both lines stand for the same line of a record.
+    fn matches(&self, other: &RevisionLine) -> bool {
+        self.supply_id == other.supply_id
+            && self.supplier_id == other.supplier_id
+            && self.location_id == other.location_id
+            && self.lot_number == other.lot_number
+    }
+
+    /// Returns whether the unit, price or quantity differ from `other`.
+    fn differs(&self, other: &RevisionLine) -> bool {
+        self.unit_name != other.unit_name
+            || self.unit_price != other.unit_price
+            || self.quantity != other.quantity
+            || self.total_price != other.total_price
+    }
+}
+
+/// How a line differs between two revisions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineChange {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl LineChange {
+    pub fn as_str(&self) -> &str {
+        match self {
+            LineChange::Added => "added",
+            LineChange::Removed => "removed",
+            LineChange::Changed => "changed",
+        }
+    }
+}
+
+/// A line added, removed or changed between two revisions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineDiff {
+    change: LineChange,
+    before: Option<RevisionLine>,
+    after: Option<RevisionLine>,
+}
+
+impl LineDiff {
+    pub fn change(&self) -> LineChange {
+        self.change
+    }
+
+    pub fn before(&self) -> Option<&RevisionLine> {
+        self.before.as_ref()
+    }
+
+    pub fn after(&self) -> Option<&RevisionLine> {
+        self.after.as_ref()
+    }
+
+    /// Returns the line as it is after the change, or before it when removed.
+    pub fn line(&self) -> &RevisionLine {
+        match (&self.after, &self.before) {
+            (Some(line), _) | (None, Some(line)) => line,
+            (None, None) => unreachable!("a line diff has at least one side"),
+        }
+    }
+
+    /// Returns the quantity after the change less the quantity before it.
+    pub fn quantity_delta(&self) -> Result<Quantity> {
+        let before = self
+            .before
+            .as_ref()
+            .map_or(Quantity::ZERO, |line| line.quantity);
+        let after = self
+            .after
+            .as_ref()
+            .map_or(Quantity::ZERO, |line| line.quantity);
+
+        after.checked_sub(before)
+    }
+
+    /// Returns the unit price after the change less the unit price before it.
+    pub fn unit_price_delta(&self) -> Result<Money> {
+        let before = self
+            .before
+            .as_ref()
+            .map_or(Money::ZERO, |line| line.unit_price);
+        let after = self
+            .after
+            .as_ref()
+            .map_or(Money::ZERO, |line| line.unit_price);
+
+        after.checked_sub(before)
+    }
+
+    /// Returns the total price after the change less the total price before it.
+    pub fn total_price_delta(&self) -> Result<Money> {
+        let before = self
+            .before
+            .as_ref()
+            .map_or(Money::ZERO, |line| line.total_price);
+        let after = self
+            .after
+            .as_ref()
+            .map_or(Money::ZERO, |line| line.total_price);
+
+        after.checked_sub(before)
+    }
+}
+
+/// Compares the lines of two revisions, leaving out lines that did not change.
+///
+/// Changed and added lines come in the order of `after`, followed by removed lines.
+/// Lines repeated in a revision are paired in the order they appear.
+pub fn diff_lines(before: &[RevisionLine], after: &[RevisionLine]) -> Vec<LineDiff> {
+    let mut unmatched: Vec<Option<&RevisionLine>> = before.iter().map(Some).collect();
+
+    let mut diffs = Vec::new();
+
+    for line in after {
+        let matched = unmatched
+            .iter_mut()
+            .find(|candidate| candidate.is_some_and(|candidate| candidate.matches(line)))
+            .and_then(Option::take);
+
+        match matched {
+            Some(previous) if previous.differs(line) => diffs.push(LineDiff {
+                change: LineChange::Changed,
+                before: Some(previous.clone()),
+                after: Some(line.clone()),
+            }),
+            Some(_) => {}
+            None => diffs.push(LineDiff {
+                change: LineChange::Added,
+                before: None,
+                after: Some(line.clone()),
+            }),
+        }
+    }
+
+    for line in unmatched.into_iter().flatten() {
+        diffs.push(LineDiff {
+            change: LineChange::Removed,
+            before: Some(line.clone()),
+            after: None,
+        });
+    }
+
+    diffs
+}
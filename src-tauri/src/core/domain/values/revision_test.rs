@@ -0,0 +1,69 @@
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::lot::*;
+use crate::core::domain::values::revision::*;
+use crate::core::domain::values::stock::*;
+use crate::core::Result;
+
+fn line(
+    supply_id: &str,
+    lot_number: Option<&str>,
+    quantity: i64,
+    unit_price: i64,
+) -> Result<RevisionLine> {
+    Ok(RevisionLine::new(
+        SupplyId::new(supply_id)?,
+        SupplyName::new(format!("supply {}", supply_id))?,
+        UnitName::new("kg")?,
+        Money::from_minor(unit_price),
+        Quantity::from_raw(quantity),
+        Money::from_minor(unit_price * quantity),
+    )
+    .with_supplier(SupplierId::new("1")?, SupplierName::new("supplier")?)
+    .with_lot(lot_number.map(LotNumber::new).transpose()?))
+}
+
+#[test]
+fn revision_number_test() -> Result<()> {
+    assert_eq!(RevisionNumber::new(3)?.as_u32(), 3);
+    assert!(RevisionNumber::new(0).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn diff_lines_test() -> Result<()> {
+    let before = vec![
+        line("1", None, 2, 100)?,
+        line("2", Some("A"), 1, 50)?,
+        line("3", None, 4, 10)?,
+    ];
+    let after = vec![
+        line("1", None, 2, 100)?,
+        line("2", Some("A"), 3, 60)?,
+        line("2", Some("B"), 1, 60)?,
+    ];
+
+    let diffs = diff_lines(&before, &after);
+
+    // the unchanged line is left out
+    assert_eq!(diffs.len(), 3);
+
+    assert_eq!(diffs[0].change(), LineChange::Changed);
+    assert_eq!(diffs[0].line().lot_number(), Some(&LotNumber::new("A")?));
+    assert_eq!(diffs[0].quantity_delta()?, Quantity::from_raw(2));
+    assert_eq!(diffs[0].unit_price_delta()?, Money::from_minor(10));
+    assert_eq!(diffs[0].total_price_delta()?, Money::from_minor(130));
+
+    // another lot of the same supply is a line of its own
+    assert_eq!(diffs[1].change(), LineChange::Added);
+    assert_eq!(diffs[1].quantity_delta()?, Quantity::from_raw(1));
+
+    assert_eq!(diffs[2].change(), LineChange::Removed);
+    assert_eq!(diffs[2].line().supply_id(), &SupplyId::new("3")?);
+    assert_eq!(diffs[2].quantity_delta()?, Quantity::from_raw(-4));
+    assert_eq!(diffs[2].total_price_delta()?, Money::from_minor(-40));
+
+    assert!(diff_lines(&after, &after).is_empty());
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::location::StorageLocationId;
+use crate::core::domain::values::lot::*;
+use crate::core::domain::values::tax::*;
+use crate::core::domain::values::valuation::ValuationMethod;
 use crate::core::{Error, Result};
 
-/// Number of digits for guaranteed price precision.
-pub const GUARANTEED_DECIMAL_PRECISION: u32 = 2;
-
 /// ID of supplier.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SupplierId {
@@ -58,6 +60,413 @@ impl ToString for SupplierName {
     }
 }
 
+/// Person in charge at the supplier (担当者).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContactPerson {
+    value: String,
+}
+
+impl ContactPerson {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for ContactPerson {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Phone or FAX number, e.g. 03-1234-5678
+///
+/// Kept as written. Digits are compared when searching.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PhoneNumber {
+    value: String,
+}
+
+impl PhoneNumber {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if !value
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '(' | ')' | ' '))
+        {
+            return Err(Error::DomainError(format!(
+                "phone number must consist of digits and hyphens: {}",
+                value
+            )));
+        }
+
+        let digits = value.chars().filter(|c| c.is_ascii_digit()).count();
+
+        if !(10..=15).contains(&digits) {
+            return Err(Error::DomainError(format!(
+                "phone number must have 10 to 15 digits: {}",
+                value
+            )));
+        }
+
+        Ok(Self { value })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the number without hyphens, parentheses and spaces.
+    pub fn digits(&self) -> String {
+        self.value.chars().filter(|c| c.is_ascii_digit()).collect()
+    }
+}
+
+impl ToString for PhoneNumber {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EmailAddress {
+    value: String,
+}
+
+impl EmailAddress {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        let valid = match value.split_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && !domain.contains('@')
+                    && domain.split('.').count() >= 2
+                    && domain.split('.').all(|label| !label.is_empty())
+                    && !value.chars().any(char::is_whitespace)
+            }
+            None => false,
+        };
+
+        if !valid {
+            return Err(Error::DomainError(format!(
+                "invalid email address: {}",
+                value
+            )));
+        }
+
+        Ok(Self { value })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for EmailAddress {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Japanese postal code, kept as 123-4567
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PostalCode {
+    value: String,
+}
+
+impl PostalCode {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim();
+
+        let digits: String = value.chars().filter(|c| *c != '-').collect();
+
+        if digits.len() != 7 || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(Error::DomainError(format!(
+                "postal code must be 7 digits: {}",
+                value
+            )));
+        }
+
+        Ok(Self {
+            value: format!("{}-{}", &digits[..3], &digits[3..]),
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for PostalCode {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Postal address after the postal code, e.g. 東京都千代田区丸の内1-1-1
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Address {
+    value: String,
+}
+
+impl Address {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for Address {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Contact details of a supplier, each of them optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SupplierContact {
+    person: Option<ContactPerson>,
+    phone_number: Option<PhoneNumber>,
+    fax_number: Option<PhoneNumber>,
+    email: Option<EmailAddress>,
+    postal_code: Option<PostalCode>,
+    address: Option<Address>,
+}
+
+impl SupplierContact {
+    pub fn with_person(mut self, person: Option<ContactPerson>) -> Self {
+        self.person = person;
+        self
+    }
+
+    pub fn with_phone_number(mut self, phone_number: Option<PhoneNumber>) -> Self {
+        self.phone_number = phone_number;
+        self
+    }
+
+    pub fn with_fax_number(mut self, fax_number: Option<PhoneNumber>) -> Self {
+        self.fax_number = fax_number;
+        self
+    }
+
+    pub fn with_email(mut self, email: Option<EmailAddress>) -> Self {
+        self.email = email;
+        self
+    }
+
+    pub fn with_address(
+        mut self,
+        postal_code: Option<PostalCode>,
+        address: Option<Address>,
+    ) -> Self {
+        self.postal_code = postal_code;
+        self.address = address;
+        self
+    }
+
+    pub fn person(&self) -> Option<&ContactPerson> {
+        self.person.as_ref()
+    }
+
+    pub fn phone_number(&self) -> Option<&PhoneNumber> {
+        self.phone_number.as_ref()
+    }
+
+    pub fn fax_number(&self) -> Option<&PhoneNumber> {
+        self.fax_number.as_ref()
+    }
+
+    pub fn email(&self) -> Option<&EmailAddress> {
+        self.email.as_ref()
+    }
+
+    pub fn postal_code(&self) -> Option<&PostalCode> {
+        self.postal_code.as_ref()
+    }
+
+    pub fn address(&self) -> Option<&Address> {
+        self.address.as_ref()
+    }
+}
+
+/// Free notes on a supplier, e.g. delivery days
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SupplierNotes {
+    value: String,
+}
+
+impl SupplierNotes {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for SupplierNotes {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Day of month invoices are closed (締め日) or paid (支払日) on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BillingDay {
+    /// 1 to 28.
+    Day(u32),
+    /// Last day of the month (末日).
+    EndOfMonth,
+}
+
+impl BillingDay {
+    /// Value meaning the end of the month.
+    pub const END_OF_MONTH: u32 = 31;
+
+    /// Creates a billing day from 1 to 28, or 31 for the end of the month.
+    pub fn new(value: u32) -> Result<Self> {
+        match value {
+            1..=28 => Ok(BillingDay::Day(value)),
+            Self::END_OF_MONTH => Ok(BillingDay::EndOfMonth),
+            value => Err(Error::DomainError(format!(
+                "billing day must be 1 to 28 or 31 for the end of the month: {}",
+                value
+            ))),
+        }
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            BillingDay::Day(day) => *day,
+            BillingDay::EndOfMonth => Self::END_OF_MONTH,
+        }
+    }
+}
+
+/// How a supplier is paid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PaymentMethod {
+    /// Bank transfer (振込).
+    BankTransfer,
+    /// Direct debit from our account (口座振替).
+    DirectDebit,
+    /// Cash (現金).
+    Cash,
+    /// Anything else (その他).
+    Other,
+}
+
+impl PaymentMethod {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        match value.as_ref().trim() {
+            "bank_transfer" => Ok(PaymentMethod::BankTransfer),
+            "direct_debit" => Ok(PaymentMethod::DirectDebit),
+            "cash" => Ok(PaymentMethod::Cash),
+            "other" => Ok(PaymentMethod::Other),
+            value => Err(Error::DomainError(format!(
+                "unknown payment method: {}",
+                value
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            PaymentMethod::BankTransfer => "bank_transfer",
+            PaymentMethod::DirectDebit => "direct_debit",
+            PaymentMethod::Cash => "cash",
+            PaymentMethod::Other => "other",
+        }
+    }
+}
+
+impl ToString for PaymentMethod {
+    fn to_string(&self) -> String {
+        self.as_str().to_string()
+    }
+}
+
+/// Billing terms agreed with a supplier, e.g. 月末締め翌月末払い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BillingTerms {
+    closing_day: BillingDay,
+    /// months after the closing month the payment is due in, 0 to 3
+    payment_months_later: u32,
+    payment_day: BillingDay,
+    payment_method: PaymentMethod,
+}
+
+impl BillingTerms {
+    pub fn new(
+        closing_day: BillingDay,
+        payment_months_later: u32,
+        payment_day: BillingDay,
+        payment_method: PaymentMethod,
+    ) -> Result<Self> {
+        if payment_months_later > 3 {
+            return Err(Error::DomainError(format!(
+                "payment must be due within 3 months after closing."
+            )));
+        }
+
+        if payment_months_later == 0 && payment_day.as_u32() <= closing_day.as_u32() {
+            return Err(Error::DomainError(format!(
+                "payment day in the closing month must be after the closing day."
+            )));
+        }
+
+        Ok(Self {
+            closing_day,
+            payment_months_later,
+            payment_day,
+            payment_method,
+        })
+    }
+
+    pub fn closing_day(&self) -> BillingDay {
+        self.closing_day
+    }
+
+    pub fn payment_months_later(&self) -> u32 {
+        self.payment_months_later
+    }
+
+    pub fn payment_day(&self) -> BillingDay {
+        self.payment_day
+    }
+
+    pub fn payment_method(&self) -> PaymentMethod {
+        self.payment_method
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SupplyId {
     value: String,
@@ -112,6 +521,87 @@ impl ToString for SupplyName {
     }
 }
 
+/// Code a supplier lists a supply under in its catalog.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ItemCode {
+    value: String,
+}
+
+impl ItemCode {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for ItemCode {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// A supplier offering a supply, with its item code and current price.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupplyOffer {
+    supplier_id: SupplierId,
+    item_code: Option<ItemCode>,
+    unit_price: Option<PurchaseUnitPrice>,
+    preferred: bool,
+}
+
+impl SupplyOffer {
+    pub fn new(supplier_id: SupplierId) -> Self {
+        Self {
+            supplier_id,
+            item_code: None,
+            unit_price: None,
+            preferred: false,
+        }
+    }
+
+    pub fn with_item_code(mut self, item_code: Option<ItemCode>) -> Self {
+        self.item_code = item_code;
+        self
+    }
+
+    pub fn with_unit_price(mut self, unit_price: Option<PurchaseUnitPrice>) -> Self {
+        self.unit_price = unit_price;
+        self
+    }
+
+    pub fn with_preferred(mut self, preferred: bool) -> Self {
+        self.preferred = preferred;
+        self
+    }
+
+    pub fn supplier_id(&self) -> &SupplierId {
+        &self.supplier_id
+    }
+
+    pub fn item_code(&self) -> Option<&ItemCode> {
+        self.item_code.as_ref()
+    }
+
+    /// Returns the current price per purchase unit, none if not quoted.
+    pub fn unit_price(&self) -> Option<&PurchaseUnitPrice> {
+        self.unit_price.as_ref()
+    }
+
+    /// Returns whether the supplier is the one the supply is usually bought from.
+    pub fn is_preferred(&self) -> bool {
+        self.preferred
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct JournalId {
     value: String,
@@ -149,9 +639,16 @@ pub struct JournalRecord {
     unit_price: PurchaseUnitPrice,
     quantity: PurchaseQuantity,
     total_price: TotalPrice,
+    tax_category: TaxCategory,
+    tax_included: bool,
+    tax_price: TotalPrice,
+    invoice_registered: bool,
+    lot_number: Option<LotNumber>,
+    expiry_date: Option<ExpiryDate>,
 }
 
 impl JournalRecord {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         supply_id: SupplyId,
         supply_name: SupplyName,
@@ -161,6 +658,10 @@ impl JournalRecord {
         unit_price: PurchaseUnitPrice,
         quantity: PurchaseQuantity,
         total_price: TotalPrice,
+        tax_category: TaxCategory,
+        tax_included: bool,
+        tax_price: TotalPrice,
+        invoice_registered: bool,
     ) -> Self {
         Self {
             supply_id,
@@ -171,9 +672,26 @@ impl JournalRecord {
             unit_price,
             quantity,
             total_price,
+            tax_category,
+            tax_included,
+            tax_price,
+            invoice_registered,
+            lot_number: None,
+            expiry_date: None,
         }
     }
 
+    /// Sets the lot number and expiry date of the received goods.
+    pub fn with_lot(
+        mut self,
+        lot_number: Option<LotNumber>,
+        expiry_date: Option<ExpiryDate>,
+    ) -> Self {
+        self.lot_number = lot_number;
+        self.expiry_date = expiry_date;
+        self
+    }
+
     pub fn supply_id(&self) -> &SupplyId {
         &self.supply_id
     }
@@ -205,6 +723,69 @@ impl JournalRecord {
     pub fn total_price(&self) -> &TotalPrice {
         &self.total_price
     }
+
+    pub fn tax_category(&self) -> TaxCategory {
+        self.tax_category
+    }
+
+    /// Returns whether the unit price and total price include consumption tax.
+    pub fn is_tax_included(&self) -> bool {
+        self.tax_included
+    }
+
+    /// Returns the consumption tax allocated to this line.
+    pub fn tax_price(&self) -> &TotalPrice {
+        &self.tax_price
+    }
+
+    /// Returns whether the supplier was a qualified invoice issuer at the time of purchase.
+    pub fn is_invoice_registered(&self) -> bool {
+        self.invoice_registered
+    }
+
+    /// Returns the lot number of the received goods, none if not recorded.
+    pub fn lot_number(&self) -> Option<&LotNumber> {
+        self.lot_number.as_ref()
+    }
+
+    /// Returns the best-before or expiry date of the received goods, none if not recorded.
+    pub fn expiry_date(&self) -> Option<ExpiryDate> {
+        self.expiry_date
+    }
+
+    /// Returns the line total excluding consumption tax.
+    pub fn net_price(&self) -> Result<Money> {
+        if self.tax_included {
+            self.total_price
+                .as_money()
+                .checked_sub(self.tax_price.as_money())
+        } else {
+            Ok(self.total_price.as_money())
+        }
+    }
+
+    /// Returns the line total including consumption tax.
+    pub fn gross_price(&self) -> Result<Money> {
+        if self.tax_included {
+            Ok(self.total_price.as_money())
+        } else {
+            self.total_price
+                .as_money()
+                .checked_add(self.tax_price.as_money())
+        }
+    }
+
+    /// Changes the consumption tax allocated to this line.
+    pub fn assign_tax_price(&mut self, tax_price: TotalPrice) {
+        self.tax_price = tax_price;
+    }
+
+    /// Computes `unit_price × quantity` with the rounding policy.
+    pub fn compute_total_price(&self, policy: &RoundingPolicy) -> Result<TotalPrice> {
+        TotalPrice::from_money(
+            policy.line_total(self.unit_price.as_money(), self.quantity.as_quantity())?,
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -253,35 +834,59 @@ impl ToString for UnitName {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PurchaseUnitPrice {
-    value: u32,
+    value: Money,
 }
 
 impl PurchaseUnitPrice {
     pub fn new(value: impl Into<f64>) -> Result<Self> {
-        let value = (value.into() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as u32;
+        Self::from_money(Money::from_f64(value.into(), RoundingMode::HalfUp)?)
+    }
 
-        Ok(Self { value })
+    pub fn from_money(money: Money) -> Result<Self> {
+        if money.is_negative() {
+            return Err(Error::DomainError(format!(
+                "unit price must not be negative."
+            )));
+        }
+
+        Ok(Self { value: money })
+    }
+
+    pub fn as_money(&self) -> Money {
+        self.value
     }
 
     pub fn as_f64(&self) -> f64 {
-        self.value as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64
+        self.value.as_f64()
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PurchaseQuantity {
-    value: u32,
+    value: Quantity,
 }
 
 impl PurchaseQuantity {
     pub fn new(value: impl Into<f64>) -> Result<Self> {
-        let value = (value.into() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as u32;
+        Self::from_quantity(Quantity::from_f64(value.into(), RoundingMode::HalfUp)?)
+    }
 
-        return Ok(Self { value });
+    pub fn from_quantity(quantity: Quantity) -> Result<Self> {
+        if quantity.is_negative() {
+            return Err(Error::DomainError(format!(
+                "quantity must not be negative."
+            )));
+        }
+
+        Ok(Self { value: quantity })
+    }
+
+    pub fn as_quantity(&self) -> Quantity {
+        self.value
     }
 
     pub fn as_f64(&self) -> f64 {
-        self.value as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64
+        self.value.as_f64()
     }
 }
 
@@ -331,35 +936,59 @@ impl StocktakenDateTime {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StocktakingQuantity {
-    value: u32,
+    value: Quantity,
 }
 
 impl StocktakingQuantity {
     pub fn new(value: impl Into<f64>) -> Result<Self> {
-        let value = (value.into() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as u32;
+        Self::from_quantity(Quantity::from_f64(value.into(), RoundingMode::HalfUp)?)
+    }
 
-        return Ok(Self { value });
+    pub fn from_quantity(quantity: Quantity) -> Result<Self> {
+        if quantity.is_negative() {
+            return Err(Error::DomainError(format!(
+                "quantity must not be negative."
+            )));
+        }
+
+        Ok(Self { value: quantity })
+    }
+
+    pub fn as_quantity(&self) -> Quantity {
+        self.value
     }
 
     pub fn as_f64(&self) -> f64 {
-        self.value as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64
+        self.value.as_f64()
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StocktakingUnitPrice {
-    value: u32,
+    value: Money,
 }
 
 impl StocktakingUnitPrice {
     pub fn new(value: impl Into<f64>) -> Result<Self> {
-        let value = (value.into() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as u32;
+        Self::from_money(Money::from_f64(value.into(), RoundingMode::HalfUp)?)
+    }
 
-        return Ok(Self { value });
+    pub fn from_money(money: Money) -> Result<Self> {
+        if money.is_negative() {
+            return Err(Error::DomainError(format!(
+                "unit price must not be negative."
+            )));
+        }
+
+        Ok(Self { value: money })
+    }
+
+    pub fn as_money(&self) -> Money {
+        self.value
     }
 
     pub fn as_f64(&self) -> f64 {
-        self.value as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64
+        self.value.as_f64()
     }
 }
 
@@ -371,9 +1000,15 @@ pub struct StocktakingRecord {
     unit_price: StocktakingUnitPrice,
     quantity: StocktakingQuantity,
     total_price: TotalPrice,
+    valuation_method: Option<ValuationMethod>,
+    location_id: Option<StorageLocationId>,
+    lot_number: Option<LotNumber>,
 }
 
 impl StocktakingRecord {
+    /// Create `StocktakingRecord`.
+    ///
+    /// `valuation_method` is the method the unit price was derived by, `None` if entered by hand.
     pub fn new(
         supply_id: SupplyId,
         supply_name: SupplyName,
@@ -381,6 +1016,7 @@ impl StocktakingRecord {
         unit_price: StocktakingUnitPrice,
         quantity: StocktakingQuantity,
         total_price: TotalPrice,
+        valuation_method: Option<ValuationMethod>,
     ) -> Self {
         Self {
             supply_id,
@@ -389,9 +1025,24 @@ impl StocktakingRecord {
             unit_price,
             quantity,
             total_price,
+            valuation_method,
+            location_id: None,
+            lot_number: None,
         }
     }
 
+    /// Sets the storage location the quantity was counted in.
+    pub fn with_location(mut self, location_id: Option<StorageLocationId>) -> Self {
+        self.location_id = location_id;
+        self
+    }
+
+    /// Sets the lot the counted quantity belongs to.
+    pub fn with_lot(mut self, lot_number: Option<LotNumber>) -> Self {
+        self.lot_number = lot_number;
+        self
+    }
+
     pub fn supply_id(&self) -> &SupplyId {
         &self.supply_id
     }
@@ -415,30 +1066,173 @@ impl StocktakingRecord {
     pub fn total_price(&self) -> &TotalPrice {
         &self.total_price
     }
+
+    pub fn valuation_method(&self) -> Option<ValuationMethod> {
+        self.valuation_method
+    }
+
+    /// Returns the storage location counted in, none if not recorded.
+    pub fn location_id(&self) -> Option<&StorageLocationId> {
+        self.location_id.as_ref()
+    }
+
+    /// Returns the lot counted, none if not recorded.
+    pub fn lot_number(&self) -> Option<&LotNumber> {
+        self.lot_number.as_ref()
+    }
+
+    /// Computes `unit_price × quantity` with the rounding policy.
+    pub fn compute_total_price(&self, policy: &RoundingPolicy) -> Result<TotalPrice> {
+        TotalPrice::from_money(
+            policy.line_total(self.unit_price.as_money(), self.quantity.as_quantity())?,
+        )
+    }
 }
 
 /// The total price value object.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TotalPrice {
-    value: u32,
+    value: Money,
 }
 
 impl TotalPrice {
     /// Create `TotalPrice`
     pub fn new(price: impl Into<f64>) -> Result<Self> {
-        let value = price.into();
+        Self::from_money(Money::from_f64(price.into(), RoundingMode::HalfUp)?)
+    }
 
-        if value < 0.0 {
-            panic!("The total price must be at least more than 0.");
+    /// Create `TotalPrice` from `Money`
+    pub fn from_money(money: Money) -> Result<Self> {
+        if money.is_negative() {
+            return Err(Error::DomainError(format!(
+                "total price must not be negative."
+            )));
         }
 
-        let value = (value * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as u32;
+        Ok(Self { value: money })
+    }
 
-        Ok(Self { value })
+    /// Returns total price value as `Money`.
+    pub fn as_money(&self) -> Money {
+        self.value
     }
 
     /// Returns total price value as `f64`.
     pub fn as_f64(&self) -> f64 {
-        self.value as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64
+        self.value.as_f64()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConsumptionId {
+    value: String,
+}
+
+impl ConsumptionId {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for ConsumptionId {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ConsumedDateTime {
+    value: i64,
+}
+
+impl ConsumedDateTime {
+    pub fn new(value: impl Into<i64>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        self.value
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumptionQuantity {
+    value: Quantity,
+}
+
+impl ConsumptionQuantity {
+    pub fn new(value: impl Into<f64>) -> Result<Self> {
+        Self::from_quantity(Quantity::from_f64(value.into(), RoundingMode::HalfUp)?)
+    }
+
+    pub fn from_quantity(quantity: Quantity) -> Result<Self> {
+        if quantity.is_negative() {
+            return Err(Error::DomainError(format!(
+                "quantity must not be negative."
+            )));
+        }
+
+        Ok(Self { value: quantity })
+    }
+
+    pub fn as_quantity(&self) -> Quantity {
+        self.value
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.value.as_f64()
+    }
+}
+
+/// Quantity of a supply taken out of stock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumptionRecord {
+    supply_id: SupplyId,
+    supply_name: SupplyName,
+    unit_name: UnitName,
+    quantity: ConsumptionQuantity,
+}
+
+impl ConsumptionRecord {
+    pub fn new(
+        supply_id: SupplyId,
+        supply_name: SupplyName,
+        unit_name: UnitName,
+        quantity: ConsumptionQuantity,
+    ) -> Self {
+        Self {
+            supply_id,
+            supply_name,
+            unit_name,
+            quantity,
+        }
+    }
+
+    pub fn supply_id(&self) -> &SupplyId {
+        &self.supply_id
+    }
+
+    pub fn supply_name(&self) -> &SupplyName {
+        &self.supply_name
+    }
+
+    pub fn unit_name(&self) -> &UnitName {
+        &self.unit_name
+    }
+
+    pub fn quantity(&self) -> &ConsumptionQuantity {
+        &self.quantity
     }
 }
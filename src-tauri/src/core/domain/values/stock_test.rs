@@ -0,0 +1,69 @@
+use crate::core::domain::values::stock::*;
+
+#[test]
+fn phone_number_test() {
+    let number = PhoneNumber::new(" 03-1234-5678 ").unwrap();
+
+    assert_eq!(number.as_str(), "03-1234-5678");
+    assert_eq!(number.digits(), "0312345678");
+
+    assert!(PhoneNumber::new("+81 (3) 1234-5678").is_ok());
+    assert!(PhoneNumber::new("1234-5678").is_err());
+    assert!(PhoneNumber::new("03-1234-567a").is_err());
+}
+
+#[test]
+fn email_address_test() {
+    assert!(EmailAddress::new("order@example.com").is_ok());
+    assert!(EmailAddress::new("order@example").is_err());
+    assert!(EmailAddress::new("@example.com").is_err());
+    assert!(EmailAddress::new("order@@example.com").is_err());
+    assert!(EmailAddress::new("or der@example.com").is_err());
+}
+
+#[test]
+fn postal_code_test() {
+    assert_eq!(PostalCode::new("1000005").unwrap().as_str(), "100-0005");
+    assert_eq!(PostalCode::new("100-0005").unwrap().as_str(), "100-0005");
+    assert!(PostalCode::new("100-005").is_err());
+}
+
+#[test]
+fn billing_terms_test() {
+    assert_eq!(BillingDay::new(31).unwrap(), BillingDay::EndOfMonth);
+    assert_eq!(BillingDay::new(20).unwrap().as_u32(), 20);
+    assert!(BillingDay::new(29).is_err());
+    assert!(BillingDay::new(0).is_err());
+
+    // 20日締め当月末払い
+    assert!(BillingTerms::new(
+        BillingDay::new(20).unwrap(),
+        0,
+        BillingDay::EndOfMonth,
+        PaymentMethod::BankTransfer,
+    )
+    .is_ok());
+
+    // paid before it is closed
+    assert!(BillingTerms::new(
+        BillingDay::EndOfMonth,
+        0,
+        BillingDay::new(10).unwrap(),
+        PaymentMethod::BankTransfer,
+    )
+    .is_err());
+
+    assert!(BillingTerms::new(
+        BillingDay::EndOfMonth,
+        4,
+        BillingDay::EndOfMonth,
+        PaymentMethod::Cash,
+    )
+    .is_err());
+
+    assert_eq!(
+        PaymentMethod::new("direct_debit").unwrap(),
+        PaymentMethod::DirectDebit
+    );
+    assert!(PaymentMethod::new("cheque").is_err());
+}
@@ -0,0 +1,117 @@
+//! Store value objects.
+use crate::core::{Error, Result};
+
+/// ID of store.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StoreId {
+    value: String,
+}
+
+impl StoreId {
+    /// Store existing data was migrated into.
+    pub const DEFAULT: &'static str = "1";
+
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Default for StoreId {
+    fn default() -> Self {
+        Self {
+            value: Self::DEFAULT.to_string(),
+        }
+    }
+}
+
+impl ToString for StoreId {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Name of store, e.g. 本店
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreName {
+    value: String,
+}
+
+impl StoreName {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for StoreName {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Stores the suppliers, supplies, journals and stocktakings are read from and written to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoreContext {
+    /// Data of one store.
+    Store(StoreId),
+    /// Data of all stores, for consolidated reports. Nothing can be recorded.
+    Consolidated,
+}
+
+impl StoreContext {
+    /// Returns the store new data is recorded in.
+    pub fn store_id(&self) -> Result<&StoreId> {
+        match self {
+            StoreContext::Store(store_id) => Ok(store_id),
+            StoreContext::Consolidated => Err(Error::DomainError(format!(
+                "a store must be selected to record data."
+            ))),
+        }
+    }
+
+    /// Returns the one store existing data is changed in.
+    pub fn owner(&self) -> Result<StoreContext> {
+        Ok(StoreContext::Store(self.store_id()?.clone()))
+    }
+
+    /// Returns the store data is read from, none for all stores.
+    pub fn scope(&self) -> Option<&StoreId> {
+        match self {
+            StoreContext::Store(store_id) => Some(store_id),
+            StoreContext::Consolidated => None,
+        }
+    }
+
+    /// Whether data of `store_id` is read.
+    pub fn includes(&self, store_id: &StoreId) -> bool {
+        match self {
+            StoreContext::Store(id) => id == store_id,
+            StoreContext::Consolidated => true,
+        }
+    }
+}
+
+impl Default for StoreContext {
+    fn default() -> Self {
+        StoreContext::Store(StoreId::default())
+    }
+}
@@ -0,0 +1,207 @@
+//! Consumption tax value objects.
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::EntryDateTime;
+use crate::core::{Error, Result};
+
+/// Consumption tax category of a supply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum TaxCategory {
+    /// Standard rate (10%).
+    #[default]
+    Standard,
+    /// Reduced rate for food and beverages (8%).
+    Reduced,
+    /// Not taxable.
+    Exempt,
+}
+
+impl TaxCategory {
+    /// All categories in display order.
+    pub const ALL: [TaxCategory; 3] = [
+        TaxCategory::Standard,
+        TaxCategory::Reduced,
+        TaxCategory::Exempt,
+    ];
+
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        match value.as_ref().trim() {
+            "standard" => Ok(TaxCategory::Standard),
+            "reduced" => Ok(TaxCategory::Reduced),
+            "exempt" => Ok(TaxCategory::Exempt),
+            value => Err(Error::DomainError(format!(
+                "unknown tax category: {}",
+                value
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            TaxCategory::Standard => "standard",
+            TaxCategory::Reduced => "reduced",
+            TaxCategory::Exempt => "exempt",
+        }
+    }
+
+    /// Returns the tax rate in percent.
+    pub fn rate_percent(&self) -> i64 {
+        match self {
+            TaxCategory::Standard => 10,
+            TaxCategory::Reduced => 8,
+            TaxCategory::Exempt => 0,
+        }
+    }
+}
+
+impl ToString for TaxCategory {
+    fn to_string(&self) -> String {
+        self.as_str().to_string()
+    }
+}
+
+/// Registration number of a qualified invoice issuer (適格請求書発行事業者登録番号).
+///
+/// `T` followed by 13 digits whose first digit is the check digit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InvoiceRegistrationNumber {
+    value: String,
+}
+
+impl InvoiceRegistrationNumber {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_uppercase();
+
+        let digits = value
+            .strip_prefix('T')
+            .filter(|digits| digits.len() == 13 && digits.chars().all(|c| c.is_ascii_digit()))
+            .ok_or(Error::DomainError(format!(
+                "invoice registration number must be T followed by 13 digits: {}",
+                value
+            )))?;
+
+        let digits: Vec<u32> = digits.chars().filter_map(|c| c.to_digit(10)).collect();
+
+        // Digits of the base number are weighted 1, 2, 1, 2... from the lowest one.
+        let sum: u32 = digits[1..]
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(index, digit)| if index % 2 == 0 { *digit } else { digit * 2 })
+            .sum();
+
+        if digits[0] != 9 - sum % 9 {
+            return Err(Error::DomainError(format!(
+                "invalid check digit of invoice registration number: {}",
+                value
+            )));
+        }
+
+        Ok(Self { value })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for InvoiceRegistrationNumber {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Start of the invoice system, 2023-10-01 00:00 JST.
+const INVOICE_SYSTEM_START: i64 = 1_696_086_000_000;
+
+/// End of the 80% transitional deduction, 2026-10-01 00:00 JST.
+const TRANSITIONAL_80_END: i64 = 1_790_780_400_000;
+
+/// End of the 50% transitional deduction, 2029-10-01 00:00 JST.
+const TRANSITIONAL_50_END: i64 = 1_885_474_800_000;
+
+/// Ratio of input tax that can be deducted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DeductionRatio {
+    percent: i64,
+}
+
+impl DeductionRatio {
+    /// Full deduction.
+    pub const FULL: DeductionRatio = DeductionRatio { percent: 100 };
+
+    /// Returns the ratio for a purchase at `entry_datetime`.
+    ///
+    /// Purchases from unregistered suppliers fall under the transitional measures.
+    pub fn of(invoice_registered: bool, entry_datetime: &EntryDateTime) -> Self {
+        let datetime = entry_datetime.as_i64();
+
+        let percent = if invoice_registered || datetime < INVOICE_SYSTEM_START {
+            100
+        } else if datetime < TRANSITIONAL_80_END {
+            80
+        } else if datetime < TRANSITIONAL_50_END {
+            50
+        } else {
+            0
+        };
+
+        Self { percent }
+    }
+
+    pub fn percent(&self) -> i64 {
+        self.percent
+    }
+}
+
+/// Net, tax and gross amounts of a tax category.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxSummary {
+    category: TaxCategory,
+    net_price: Money,
+    tax_price: Money,
+    gross_price: Money,
+    deductible_tax_price: Money,
+}
+
+impl TaxSummary {
+    pub fn new(
+        category: TaxCategory,
+        net_price: Money,
+        tax_price: Money,
+        deductible_tax_price: Money,
+    ) -> Result<Self> {
+        Ok(Self {
+            category,
+            net_price,
+            tax_price,
+            gross_price: net_price.checked_add(tax_price)?,
+            deductible_tax_price,
+        })
+    }
+
+    pub fn category(&self) -> TaxCategory {
+        self.category
+    }
+
+    pub fn net_price(&self) -> Money {
+        self.net_price
+    }
+
+    pub fn tax_price(&self) -> Money {
+        self.tax_price
+    }
+
+    pub fn gross_price(&self) -> Money {
+        self.gross_price
+    }
+
+    /// Returns the input tax that can be deducted.
+    pub fn deductible_tax_price(&self) -> Money {
+        self.deductible_tax_price
+    }
+
+    /// Returns the input tax that cannot be deducted.
+    pub fn non_deductible_tax_price(&self) -> Result<Money> {
+        self.tax_price.checked_sub(self.deductible_tax_price)
+    }
+}
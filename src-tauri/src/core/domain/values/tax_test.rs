@@ -0,0 +1,35 @@
+use crate::core::domain::values::stock::EntryDateTime;
+use crate::core::domain::values::tax::*;
+
+#[test]
+fn invoice_registration_number_test() {
+    let number = InvoiceRegistrationNumber::new(" t7000012050002 ").unwrap();
+
+    assert_eq!(number.as_str(), "T7000012050002");
+
+    assert!(InvoiceRegistrationNumber::new("T1000012050002").is_err());
+    assert!(InvoiceRegistrationNumber::new("7000012050002").is_err());
+    assert!(InvoiceRegistrationNumber::new("T700001205000").is_err());
+    assert!(InvoiceRegistrationNumber::new("T70000120500O2").is_err());
+}
+
+#[test]
+fn deduction_ratio_test() {
+    // 2023-09-30 12:00 JST
+    let before_invoice = EntryDateTime::new(1_696_042_800_000_i64);
+    // 2024-04-01 00:00 JST
+    let first_period = EntryDateTime::new(1_711_897_200_000_i64);
+    // 2026-10-18 00:00 JST
+    let second_period = EntryDateTime::new(1_792_249_200_000_i64);
+    // 2029-10-01 00:00 JST
+    let after_transition = EntryDateTime::new(1_885_474_800_000_i64);
+
+    assert_eq!(DeductionRatio::of(false, &before_invoice).percent(), 100);
+    assert_eq!(DeductionRatio::of(false, &first_period).percent(), 80);
+    assert_eq!(DeductionRatio::of(false, &second_period).percent(), 50);
+    assert_eq!(DeductionRatio::of(false, &after_transition).percent(), 0);
+    assert_eq!(
+        DeductionRatio::of(true, &after_transition),
+        DeductionRatio::FULL
+    );
+}
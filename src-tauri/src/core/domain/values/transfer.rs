@@ -0,0 +1,182 @@
+//! Inter-store transfer value objects.
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::*;
+use crate::core::{Error, Result};
+
+/// ID of transfer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TransferId {
+    value: String,
+}
+
+impl TransferId {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for TransferId {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TransferredDateTime {
+    value: i64,
+}
+
+impl TransferredDateTime {
+    pub fn new(value: impl Into<i64>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        self.value
+    }
+}
+
+/// Quantity moved in the stock unit of the supply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferQuantity {
+    value: Quantity,
+}
+
+impl TransferQuantity {
+    pub fn new(value: impl Into<f64>) -> Result<Self> {
+        Self::from_quantity(Quantity::from_f64(value.into(), RoundingMode::HalfUp)?)
+    }
+
+    pub fn from_quantity(quantity: Quantity) -> Result<Self> {
+        if quantity.as_raw() <= 0 {
+            return Err(Error::DomainError(format!(
+                "transferred quantity must be positive."
+            )));
+        }
+
+        Ok(Self { value: quantity })
+    }
+
+    pub fn as_quantity(&self) -> Quantity {
+        self.value
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.value.as_f64()
+    }
+}
+
+/// Price per stock unit the destination takes the supply over at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferUnitPrice {
+    value: Money,
+}
+
+impl TransferUnitPrice {
+    pub fn new(value: impl Into<f64>) -> Result<Self> {
+        Self::from_money(Money::from_f64(value.into(), RoundingMode::HalfUp)?)
+    }
+
+    pub fn from_money(money: Money) -> Result<Self> {
+        if money.is_negative() {
+            return Err(Error::DomainError(format!(
+                "transfer price must not be negative."
+            )));
+        }
+
+        Ok(Self { value: money })
+    }
+
+    pub fn as_money(&self) -> Money {
+        self.value
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.value.as_f64()
+    }
+}
+
+/// Supply moved from the source store to the destination store.
+///
+/// Stores keep their own supplies, so the line names the supply of each store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferLine {
+    supply_id: SupplyId,
+    destination_supply_id: SupplyId,
+    supply_name: SupplyName,
+    unit_name: UnitName,
+    quantity: TransferQuantity,
+    unit_price: TransferUnitPrice,
+    total_price: TotalPrice,
+}
+
+impl TransferLine {
+    pub fn new(
+        supply_id: SupplyId,
+        destination_supply_id: SupplyId,
+        supply_name: SupplyName,
+        unit_name: UnitName,
+        quantity: TransferQuantity,
+        unit_price: TransferUnitPrice,
+        total_price: TotalPrice,
+    ) -> Self {
+        Self {
+            supply_id,
+            destination_supply_id,
+            supply_name,
+            unit_name,
+            quantity,
+            unit_price,
+            total_price,
+        }
+    }
+
+    pub fn supply_id(&self) -> &SupplyId {
+        &self.supply_id
+    }
+
+    pub fn destination_supply_id(&self) -> &SupplyId {
+        &self.destination_supply_id
+    }
+
+    pub fn supply_name(&self) -> &SupplyName {
+        &self.supply_name
+    }
+
+    pub fn unit_name(&self) -> &UnitName {
+        &self.unit_name
+    }
+
+    pub fn quantity(&self) -> &TransferQuantity {
+        &self.quantity
+    }
+
+    pub fn unit_price(&self) -> &TransferUnitPrice {
+        &self.unit_price
+    }
+
+    pub fn total_price(&self) -> &TotalPrice {
+        &self.total_price
+    }
+}
+
+/// Way a transfer moves stock of the stores being read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferDirection {
+    /// Received from another store.
+    Inbound,
+    /// Sent to another store.
+    Outbound,
+}
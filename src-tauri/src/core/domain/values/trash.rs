@@ -0,0 +1,70 @@
+//! Trash bin value objects.
+
+/// Milliseconds in a day.
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+
+/// Date and time a record was moved to the trash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DeletedDateTime {
+    value: i64,
+}
+
+impl DeletedDateTime {
+    pub fn new(value: i64) -> Self {
+        Self { value }
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        self.value
+    }
+}
+
+/// A record in the trash, with the date and time it was deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trashed<T> {
+    item: T,
+    deleted_at: DeletedDateTime,
+}
+
+impl<T> Trashed<T> {
+    pub fn new(item: T, deleted_at: DeletedDateTime) -> Self {
+        Self { item, deleted_at }
+    }
+
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    pub fn deleted_at(&self) -> DeletedDateTime {
+        self.deleted_at
+    }
+
+    pub fn into_item(self) -> T {
+        self.item
+    }
+}
+
+/// Number of days records are kept in the trash before being purged, 0 keeping them forever.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPeriod {
+    days: u32,
+}
+
+impl RetentionPeriod {
+    pub fn new(days: u32) -> Self {
+        Self { days }
+    }
+
+    pub fn days(&self) -> u32 {
+        self.days
+    }
+
+    /// Returns whether a record deleted at `deleted_at` has outlived the period at `now`.
+    pub fn has_expired(&self, deleted_at: DeletedDateTime, now: i64) -> bool {
+        if self.days == 0 {
+            return false;
+        }
+
+        deleted_at.as_i64() <= now.saturating_sub(i64::from(self.days).saturating_mul(DAY_MILLIS))
+    }
+}
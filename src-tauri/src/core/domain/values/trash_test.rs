@@ -0,0 +1,17 @@
+use crate::core::domain::values::trash::*;
+
+const DAY: i64 = 24 * 60 * 60 * 1000;
+
+#[test]
+fn retention_period_test() {
+    let period = RetentionPeriod::new(30);
+
+    assert!(!period.has_expired(DeletedDateTime::new(0), 29 * DAY));
+    assert!(period.has_expired(DeletedDateTime::new(0), 30 * DAY));
+    assert!(period.has_expired(DeletedDateTime::new(DAY), 40 * DAY));
+
+    // records are kept forever without a period
+    let forever = RetentionPeriod::new(0);
+
+    assert!(!forever.has_expired(DeletedDateTime::new(0), i64::MAX));
+}
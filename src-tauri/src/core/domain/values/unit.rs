@@ -0,0 +1,180 @@
+//! Unit of measure value objects.
+use crate::core::domain::values::decimal::*;
+use crate::core::{Error, Result};
+
+/// ID of unit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnitId {
+    value: String,
+}
+
+impl UnitId {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for UnitId {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+/// Number of stock units in one unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConversionFactor {
+    value: Quantity,
+}
+
+impl ConversionFactor {
+    /// Factor of the stock unit itself.
+    pub const ONE: ConversionFactor = ConversionFactor {
+        value: Quantity::from_raw(1000),
+    };
+
+    pub fn new(value: impl Into<f64>) -> Result<Self> {
+        Self::from_quantity(Quantity::from_f64(value.into(), RoundingMode::HalfUp)?)
+    }
+
+    pub fn from_quantity(quantity: Quantity) -> Result<Self> {
+        if quantity.as_raw() <= 0 {
+            return Err(Error::DomainError(format!(
+                "conversion factor must be positive."
+            )));
+        }
+
+        Ok(Self { value: quantity })
+    }
+
+    pub fn as_quantity(&self) -> Quantity {
+        self.value
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.value.as_f64()
+    }
+}
+
+/// Conversion of a unit into the stock unit of a supply, e.g. 1 case = 12 bottles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitConversion {
+    unit_id: UnitId,
+    factor: ConversionFactor,
+}
+
+impl UnitConversion {
+    pub fn new(unit_id: UnitId, factor: ConversionFactor) -> Self {
+        Self { unit_id, factor }
+    }
+
+    pub fn unit_id(&self) -> &UnitId {
+        &self.unit_id
+    }
+
+    pub fn factor(&self) -> ConversionFactor {
+        self.factor
+    }
+}
+
+/// Purchase unit, stock unit and conversions of a supply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupplyUnits {
+    purchase_unit_id: UnitId,
+    stock_unit_id: UnitId,
+    conversions: Vec<UnitConversion>,
+}
+
+impl SupplyUnits {
+    /// Create `SupplyUnits`.
+    ///
+    /// The purchase unit must be the stock unit or have a conversion.
+    pub fn new(
+        purchase_unit_id: UnitId,
+        stock_unit_id: UnitId,
+        conversions: Vec<UnitConversion>,
+    ) -> Result<Self> {
+        let conversions: Vec<UnitConversion> = conversions
+            .into_iter()
+            .filter(|conversion| conversion.unit_id != stock_unit_id)
+            .collect();
+
+        for (index, conversion) in conversions.iter().enumerate() {
+            if conversions[..index]
+                .iter()
+                .any(|other| other.unit_id == conversion.unit_id)
+            {
+                return Err(Error::DomainError(format!(
+                    "conversion of unit {} is duplicated.",
+                    conversion.unit_id.as_str()
+                )));
+            }
+        }
+
+        let units = Self {
+            purchase_unit_id,
+            stock_unit_id,
+            conversions,
+        };
+
+        if units.factor_of(&units.purchase_unit_id).is_none() {
+            return Err(Error::DomainError(format!(
+                "purchase unit has no conversion into the stock unit."
+            )));
+        }
+
+        Ok(units)
+    }
+
+    /// Create `SupplyUnits` purchased and stocked in the same unit.
+    pub fn single(unit_id: UnitId) -> Self {
+        Self {
+            purchase_unit_id: unit_id.clone(),
+            stock_unit_id: unit_id,
+            conversions: Vec::new(),
+        }
+    }
+
+    pub fn purchase_unit_id(&self) -> &UnitId {
+        &self.purchase_unit_id
+    }
+
+    pub fn stock_unit_id(&self) -> &UnitId {
+        &self.stock_unit_id
+    }
+
+    pub fn conversions(&self) -> &[UnitConversion] {
+        &self.conversions
+    }
+
+    /// Returns the number of stock units in one `unit_id`.
+    pub fn factor_of(&self, unit_id: &UnitId) -> Option<ConversionFactor> {
+        if *unit_id == self.stock_unit_id {
+            return Some(ConversionFactor::ONE);
+        }
+
+        self.conversions
+            .iter()
+            .find(|conversion| conversion.unit_id == *unit_id)
+            .map(|conversion| conversion.factor)
+    }
+
+    /// Converts `quantity` in `unit_id` into the stock unit.
+    pub fn to_stock_quantity(&self, unit_id: &UnitId, quantity: Quantity) -> Result<Quantity> {
+        let factor = self.factor_of(unit_id).ok_or(Error::DomainError(format!(
+            "unit {} has no conversion into the stock unit.",
+            unit_id.as_str()
+        )))?;
+
+        quantity.checked_mul(factor.as_quantity(), RoundingMode::HalfUp)
+    }
+}
@@ -0,0 +1,207 @@
+//! Inventory valuation value objects.
+use crate::core::domain::values::decimal::*;
+use crate::core::{Error, Result};
+
+/// Method deriving stocktaking unit prices from the purchase history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ValuationMethod {
+    /// Price of the latest purchase (最終仕入原価法).
+    #[default]
+    LastPurchasePrice,
+    /// Average updated on every purchase, carried across stocktakings (移動平均法).
+    MovingAverage,
+    /// Average of the opening inventory and the purchases since then (総平均法).
+    PeriodicAverage,
+    /// Stock is valued at the latest purchases first (先入先出法).
+    Fifo,
+}
+
+impl ValuationMethod {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        match value.as_ref().trim() {
+            "last_purchase_price" => Ok(ValuationMethod::LastPurchasePrice),
+            "moving_average" => Ok(ValuationMethod::MovingAverage),
+            "periodic_average" => Ok(ValuationMethod::PeriodicAverage),
+            "fifo" => Ok(ValuationMethod::Fifo),
+            value => Err(Error::DomainError(format!(
+                "unknown valuation method: {}",
+                value
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            ValuationMethod::LastPurchasePrice => "last_purchase_price",
+            ValuationMethod::MovingAverage => "moving_average",
+            ValuationMethod::PeriodicAverage => "periodic_average",
+            ValuationMethod::Fifo => "fifo",
+        }
+    }
+
+    /// Derives the unit price of `quantity` in stock from the chronological `history`.
+    ///
+    /// Returns `None` if the history has no price to derive from.
+    pub fn unit_price(&self, history: &[StockEvent], quantity: Quantity) -> Result<Option<Money>> {
+        // the latest count is the opening inventory
+        let (opening, purchases) = match history
+            .iter()
+            .rposition(|event| matches!(event, StockEvent::Count { .. }))
+        {
+            Some(index) => (history[index].count(), &history[index + 1..]),
+            None => (None, history),
+        };
+
+        let purchases: Vec<(Quantity, Money)> = purchases
+            .iter()
+            .filter_map(|event| event.purchase())
+            .collect();
+
+        let last_price = match purchases.last() {
+            Some((quantity, amount)) => Some(amount.checked_div(*quantity, RoundingMode::HalfUp)?),
+            None => opening.map(|(_, unit_price)| unit_price),
+        };
+
+        match self {
+            ValuationMethod::LastPurchasePrice => Ok(last_price),
+            ValuationMethod::MovingAverage => {
+                let mut on_hand = Quantity::ZERO;
+                let mut average: Option<Money> = None;
+
+                for event in history {
+                    match *event {
+                        StockEvent::Count {
+                            quantity,
+                            unit_price,
+                        } => {
+                            on_hand = quantity;
+                            average = average.or(Some(unit_price));
+                        }
+                        StockEvent::Purchase { quantity, amount } => {
+                            let value = match average {
+                                Some(average) => {
+                                    average.checked_mul(on_hand, RoundingMode::HalfUp)?
+                                }
+                                None => Money::ZERO,
+                            };
+
+                            on_hand = on_hand.checked_add(quantity)?;
+
+                            if on_hand.as_raw() > 0 {
+                                average = Some(
+                                    value
+                                        .checked_add(amount)?
+                                        .checked_div(on_hand, RoundingMode::HalfUp)?,
+                                );
+                            }
+                        }
+                    }
+                }
+
+                Ok(average)
+            }
+            ValuationMethod::PeriodicAverage => {
+                let (mut total_quantity, mut total_value) = match opening {
+                    Some((quantity, unit_price)) => (
+                        quantity,
+                        unit_price.checked_mul(quantity, RoundingMode::HalfUp)?,
+                    ),
+                    None => (Quantity::ZERO, Money::ZERO),
+                };
+
+                for (quantity, amount) in &purchases {
+                    total_quantity = total_quantity.checked_add(*quantity)?;
+                    total_value = total_value.checked_add(*amount)?;
+                }
+
+                if total_quantity.as_raw() <= 0 {
+                    return Ok(last_price);
+                }
+
+                Ok(Some(
+                    total_value.checked_div(total_quantity, RoundingMode::HalfUp)?,
+                ))
+            }
+            ValuationMethod::Fifo => {
+                if quantity.as_raw() <= 0 {
+                    return Ok(last_price);
+                }
+
+                let mut remaining = quantity;
+                let mut value = Money::ZERO;
+
+                for (purchased, amount) in purchases.iter().rev() {
+                    let taken = remaining.min(*purchased);
+
+                    value = value.checked_add(amount.checked_mul_ratio(
+                        taken.as_raw(),
+                        purchased.as_raw(),
+                        MONEY_DECIMAL_PRECISION,
+                        RoundingMode::HalfUp,
+                    )?)?;
+
+                    remaining = remaining.checked_sub(taken)?;
+
+                    if remaining.as_raw() == 0 {
+                        break;
+                    }
+                }
+
+                // the rest is left from the opening inventory, or the oldest purchase
+                if remaining.as_raw() > 0 {
+                    let rest_price = match (opening, purchases.first()) {
+                        (Some((_, unit_price)), _) => unit_price,
+                        (None, Some((purchased, amount))) => {
+                            amount.checked_div(*purchased, RoundingMode::HalfUp)?
+                        }
+                        (None, None) => return Ok(None),
+                    };
+
+                    value = value
+                        .checked_add(rest_price.checked_mul(remaining, RoundingMode::HalfUp)?)?;
+                }
+
+                Ok(Some(value.checked_div(quantity, RoundingMode::HalfUp)?))
+            }
+        }
+    }
+}
+
+impl ToString for ValuationMethod {
+    fn to_string(&self) -> String {
+        self.as_str().to_string()
+    }
+}
+
+/// Event of the stock history of a supply, quantities in its stock unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StockEvent {
+    /// Purchase of `quantity` for `amount`.
+    Purchase { quantity: Quantity, amount: Money },
+    /// Stocktaking counting `quantity` valued at `unit_price`.
+    Count {
+        quantity: Quantity,
+        unit_price: Money,
+    },
+}
+
+impl StockEvent {
+    fn purchase(&self) -> Option<(Quantity, Money)> {
+        match *self {
+            StockEvent::Purchase { quantity, amount } if quantity.as_raw() > 0 => {
+                Some((quantity, amount))
+            }
+            _ => None,
+        }
+    }
+
+    fn count(&self) -> Option<(Quantity, Money)> {
+        match *self {
+            StockEvent::Count {
+                quantity,
+                unit_price,
+            } => Some((quantity, unit_price)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::valuation::*;
+
+fn quantity(value: f64) -> Quantity {
+    Quantity::from_f64(value, RoundingMode::HalfUp).unwrap()
+}
+
+fn money(value: f64) -> Money {
+    Money::from_f64(value, RoundingMode::HalfUp).unwrap()
+}
+
+#[test]
+fn valuation_method_test() {
+    assert_eq!(
+        ValuationMethod::new("periodic_average").unwrap(),
+        ValuationMethod::PeriodicAverage
+    );
+    assert!(ValuationMethod::new("lifo").is_err());
+
+    // 10 counted at 100, then 10 bought at 120 and 5 at 150
+    let history = [
+        StockEvent::Count {
+            quantity: quantity(10.0),
+            unit_price: money(100.0),
+        },
+        StockEvent::Purchase {
+            quantity: quantity(10.0),
+            amount: money(1200.0),
+        },
+        StockEvent::Purchase {
+            quantity: quantity(5.0),
+            amount: money(750.0),
+        },
+    ];
+
+    let unit_price = |method: ValuationMethod, closing: f64| {
+        method
+            .unit_price(&history, quantity(closing))
+            .unwrap()
+            .unwrap()
+    };
+
+    assert_eq!(
+        unit_price(ValuationMethod::LastPurchasePrice, 8.0),
+        money(150.0)
+    );
+    // (1000 + 1200 + 750) / 25
+    assert_eq!(
+        unit_price(ValuationMethod::PeriodicAverage, 8.0),
+        money(118.0)
+    );
+    assert_eq!(
+        unit_price(ValuationMethod::MovingAverage, 8.0),
+        money(118.0)
+    );
+    // 5 at 150 and 3 at 120
+    assert_eq!(unit_price(ValuationMethod::Fifo, 8.0), money(138.75));
+    // 5 at 150, 10 at 120 and 5 left from the opening inventory at 100
+    assert_eq!(unit_price(ValuationMethod::Fifo, 20.0), money(122.5));
+
+    // the moving average carries over a stocktaking priced by hand
+    let history = [
+        StockEvent::Count {
+            quantity: quantity(10.0),
+            unit_price: money(100.0),
+        },
+        StockEvent::Purchase {
+            quantity: quantity(10.0),
+            amount: money(1200.0),
+        },
+        StockEvent::Count {
+            quantity: quantity(5.0),
+            unit_price: money(90.0),
+        },
+        StockEvent::Purchase {
+            quantity: quantity(5.0),
+            amount: money(750.0),
+        },
+    ];
+
+    // (5 * 110 + 750) / 10
+    assert_eq!(
+        ValuationMethod::MovingAverage
+            .unit_price(&history, quantity(10.0))
+            .unwrap(),
+        Some(money(130.0))
+    );
+    // (5 * 90 + 750) / 10
+    assert_eq!(
+        ValuationMethod::PeriodicAverage
+            .unit_price(&history, quantity(10.0))
+            .unwrap(),
+        Some(money(120.0))
+    );
+
+    assert_eq!(
+        ValuationMethod::Fifo
+            .unit_price(&[], quantity(1.0))
+            .unwrap(),
+        None
+    );
+}
@@ -0,0 +1,288 @@
+//! Waste value objects.
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::*;
+use crate::core::{Error, Result};
+
+/// ID of waste record.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WasteId {
+    value: String,
+}
+
+impl WasteId {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        let value = value.as_ref().trim().to_string();
+
+        if value.is_empty() {
+            return Err(Error::DomainError(format!("empty string cannot be set")));
+        }
+
+        return Ok(Self { value });
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl ToString for WasteId {
+    fn to_string(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct WastedDateTime {
+    value: i64,
+}
+
+impl WastedDateTime {
+    pub fn new(value: impl Into<i64>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        self.value
+    }
+}
+
+/// Why stock was thrown away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WasteReason {
+    /// Past its use-by date (期限切れ).
+    Expired,
+    /// Dropped, spoiled or broken (破損).
+    Damaged,
+    /// Prepared but not served (仕込み過多).
+    OverPrep,
+    /// Anything else (その他).
+    Other,
+}
+
+impl WasteReason {
+    pub fn new(value: impl AsRef<str>) -> Result<Self> {
+        match value.as_ref().trim() {
+            "expired" => Ok(WasteReason::Expired),
+            "damaged" => Ok(WasteReason::Damaged),
+            "over_prep" => Ok(WasteReason::OverPrep),
+            "other" => Ok(WasteReason::Other),
+            value => Err(Error::DomainError(format!(
+                "unknown waste reason: {}",
+                value
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            WasteReason::Expired => "expired",
+            WasteReason::Damaged => "damaged",
+            WasteReason::OverPrep => "over_prep",
+            WasteReason::Other => "other",
+        }
+    }
+}
+
+impl ToString for WasteReason {
+    fn to_string(&self) -> String {
+        self.as_str().to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasteQuantity {
+    value: Quantity,
+}
+
+impl WasteQuantity {
+    pub fn new(value: impl Into<f64>) -> Result<Self> {
+        Self::from_quantity(Quantity::from_f64(value.into(), RoundingMode::HalfUp)?)
+    }
+
+    pub fn from_quantity(quantity: Quantity) -> Result<Self> {
+        if quantity.as_raw() <= 0 {
+            return Err(Error::DomainError(format!(
+                "wasted quantity must be positive."
+            )));
+        }
+
+        Ok(Self { value: quantity })
+    }
+
+    pub fn as_quantity(&self) -> Quantity {
+        self.value
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.value.as_f64()
+    }
+}
+
+/// Price a unit of wasted stock is valued at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasteUnitPrice {
+    value: Money,
+}
+
+impl WasteUnitPrice {
+    pub fn new(value: impl Into<f64>) -> Result<Self> {
+        Self::from_money(Money::from_f64(value.into(), RoundingMode::HalfUp)?)
+    }
+
+    pub fn from_money(money: Money) -> Result<Self> {
+        if money.is_negative() {
+            return Err(Error::DomainError(format!(
+                "unit price must not be negative."
+            )));
+        }
+
+        Ok(Self { value: money })
+    }
+
+    pub fn as_money(&self) -> Money {
+        self.value
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.value.as_f64()
+    }
+}
+
+/// Quantity of a supply thrown away and the value it is written off at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WastedSupply {
+    supply_id: SupplyId,
+    supply_name: SupplyName,
+    unit_name: UnitName,
+    quantity: WasteQuantity,
+    unit_price: WasteUnitPrice,
+    total_price: TotalPrice,
+}
+
+impl WastedSupply {
+    /// Values the wasted quantity at the unit price.
+    pub fn new(
+        supply_id: SupplyId,
+        supply_name: SupplyName,
+        unit_name: UnitName,
+        quantity: WasteQuantity,
+        unit_price: WasteUnitPrice,
+        policy: &RoundingPolicy,
+    ) -> Result<Self> {
+        let total_price = TotalPrice::from_money(
+            policy.line_total(unit_price.as_money(), quantity.as_quantity())?,
+        )?;
+
+        Ok(Self::restore(
+            supply_id,
+            supply_name,
+            unit_name,
+            quantity,
+            unit_price,
+            total_price,
+        ))
+    }
+
+    pub fn restore(
+        supply_id: SupplyId,
+        supply_name: SupplyName,
+        unit_name: UnitName,
+        quantity: WasteQuantity,
+        unit_price: WasteUnitPrice,
+        total_price: TotalPrice,
+    ) -> Self {
+        Self {
+            supply_id,
+            supply_name,
+            unit_name,
+            quantity,
+            unit_price,
+            total_price,
+        }
+    }
+
+    pub fn supply_id(&self) -> &SupplyId {
+        &self.supply_id
+    }
+
+    pub fn supply_name(&self) -> &SupplyName {
+        &self.supply_name
+    }
+
+    pub fn unit_name(&self) -> &UnitName {
+        &self.unit_name
+    }
+
+    pub fn quantity(&self) -> &WasteQuantity {
+        &self.quantity
+    }
+
+    pub fn unit_price(&self) -> &WasteUnitPrice {
+        &self.unit_price
+    }
+
+    pub fn total_price(&self) -> &TotalPrice {
+        &self.total_price
+    }
+}
+
+/// Value of wasted stock against the purchases and the counted inventory of a period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WasteLoss {
+    wasted: Money,
+    purchases: Money,
+    closing_inventory: Money,
+}
+
+impl WasteLoss {
+    pub fn new(wasted: Money, purchases: Money, closing_inventory: Money) -> Self {
+        Self {
+            wasted,
+            purchases,
+            closing_inventory,
+        }
+    }
+
+    pub fn wasted(&self) -> Money {
+        self.wasted
+    }
+
+    pub fn purchases(&self) -> Money {
+        self.purchases
+    }
+
+    pub fn closing_inventory(&self) -> Money {
+        self.closing_inventory
+    }
+
+    /// Returns the wasted value as a percentage of the purchases, rounded to two decimal digits.
+    ///
+    /// None when nothing was purchased.
+    pub fn loss_ratio(&self) -> Result<Option<f64>> {
+        if self.purchases.as_minor() <= 0 {
+            return Ok(None);
+        }
+
+        // percent in hundredths, e.g. 1234 for 12.34%
+        let hundredths = self.wasted.checked_mul_ratio(
+            10_000,
+            self.purchases.as_minor(),
+            MONEY_DECIMAL_PRECISION,
+            RoundingMode::HalfUp,
+        )?;
+
+        Ok(Some(hundredths.as_minor() as f64 / 100.0))
+    }
+
+    pub fn checked_add(&self, other: WasteLoss) -> Result<WasteLoss> {
+        Ok(Self {
+            wasted: self.wasted.checked_add(other.wasted)?,
+            purchases: self.purchases.checked_add(other.purchases)?,
+            closing_inventory: self
+                .closing_inventory
+                .checked_add(other.closing_inventory)?,
+        })
+    }
+}
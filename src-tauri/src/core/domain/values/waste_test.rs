@@ -0,0 +1,56 @@
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::waste::*;
+
+#[test]
+fn waste_reason_test() {
+    assert_eq!(
+        WasteReason::new("over_prep").unwrap(),
+        WasteReason::OverPrep
+    );
+    assert_eq!(WasteReason::Expired.as_str(), "expired");
+    assert!(WasteReason::new("stolen").is_err());
+}
+
+#[test]
+fn wasted_supply_test() {
+    assert!(WasteQuantity::new(0).is_err());
+    assert!(WasteUnitPrice::new(-1).is_err());
+
+    let policy = RoundingPolicy::default();
+
+    let wasted = WastedSupply::new(
+        SupplyId::new("1").unwrap(),
+        SupplyName::new("玉ねぎ").unwrap(),
+        UnitName::new("kg").unwrap(),
+        WasteQuantity::new(1.5).unwrap(),
+        WasteUnitPrice::new(333).unwrap(),
+        &policy,
+    )
+    .unwrap();
+
+    assert_eq!(wasted.total_price().as_f64(), 500.0);
+}
+
+#[test]
+fn waste_loss_test() {
+    let money = |value: f64| Money::from_f64(value, RoundingMode::HalfUp).unwrap();
+
+    assert_eq!(
+        WasteLoss::new(money(500.0), Money::ZERO, Money::ZERO)
+            .loss_ratio()
+            .unwrap(),
+        None
+    );
+
+    let loss = WasteLoss::new(money(500.0), money(3000.0), money(1200.0));
+
+    assert_eq!(loss.loss_ratio().unwrap(), Some(16.67));
+
+    let total = loss
+        .checked_add(WasteLoss::new(Money::ZERO, money(2000.0), Money::ZERO))
+        .unwrap();
+
+    assert_eq!(total.purchases(), money(5000.0));
+    assert_eq!(total.loss_ratio().unwrap(), Some(10.0));
+}
@@ -11,6 +11,20 @@ pub enum Error {
     #[error("Domain error: {0}")]
     DomainError(String),
 
+    #[error("Domain error: total price of line {line} ({supply_name}) is {actual}, but {expected} is expected")]
+    LineTotalMismatch {
+        line: usize,
+        supply_name: String,
+        expected: f64,
+        actual: f64,
+    },
+
+    #[error("Domain error: total price is {actual}, but {expected} is expected")]
+    TotalMismatch { expected: f64, actual: f64 },
+
+    #[error("Domain error: accounting period {period} is closed")]
+    PeriodClosed { period: String },
+
     #[error("Infrastructure error: {0}")]
     InfrastructureError(String),
 
@@ -1,18 +1,141 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct SupplyDTO {
     pub id: String,
     pub name: String,
     pub unit_name: String,
+    /// preferred supplier
     pub supplier_id: String,
+    pub offers: Vec<SupplyOfferDTO>,
+    pub tax_category: String,
+    pub purchase_unit_id: String,
+    pub stock_unit_id: String,
+    pub conversions: Vec<UnitConversionDTO>,
+    pub category_id: Option<String>,
+    /// in the stock unit, none if the supply is not reordered
+    pub par_level: Option<f64>,
+    pub reorder_point: Option<f64>,
+    /// default storage location, none if the supply is not placed
+    pub location_id: Option<String>,
+    /// order within the storage location
+    pub location_sort_order: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SupplyOfferDTO {
+    pub supplier_id: String,
+    pub item_code: Option<String>,
+    /// current price per purchase unit, none if not quoted
+    pub unit_price: Option<f64>,
+    pub preferred: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnitConversionDTO {
+    pub unit_id: String,
+    /// number of stock units in one unit
+    pub factor: f64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct SupplierDTO {
+pub struct UnitDTO {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryDTO {
     pub id: String,
     pub name: String,
+    /// none for top level categories
+    pub parent_id: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageLocationDTO {
+    pub id: String,
+    pub name: String,
+    pub sort_order: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreDTO {
+    pub id: String,
+    pub name: String,
+}
+
+/// Supplies to count in a storage location, in counting order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountSheetDTO {
+    /// none for supplies not placed
+    pub location_id: Option<String>,
+    pub location_name: Option<String>,
+    pub lines: Vec<CountSheetLineDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountSheetLineDTO {
+    pub supply_id: String,
+    pub supply_name: String,
+    /// stock unit of the supply
+    pub unit_name: String,
+}
+
+/// Total of the lines whose supplies are in a category or its descendants
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryTotalDTO {
+    /// none for supplies not categorized
+    pub category_id: Option<String>,
+    pub category_name: Option<String>,
+    pub parent_id: Option<String>,
+    pub total_price: f64,
+}
+
+/// Quantity of a supply in its stock unit
 #[derive(Debug, Clone, PartialEq)]
+pub struct StockQuantityDTO {
+    pub supply_id: String,
+    pub supply_name: String,
+    pub unit_name: String,
+    pub quantity: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SupplierDTO {
+    pub id: String,
+    pub name: String,
+    pub invoice_registration_number: Option<String>,
+    pub invoice_registered: bool,
+    pub contact: SupplierContactDTO,
+    pub notes: Option<String>,
+    pub billing_terms: Option<BillingTermsDTO>,
+}
+
+/// Contact details of a supplier, empty strings read as not provided
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SupplierContactDTO {
+    pub contact_person: Option<String>,
+    pub phone_number: Option<String>,
+    pub fax_number: Option<String>,
+    pub email: Option<String>,
+    pub postal_code: Option<String>,
+    pub address: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BillingTermsDTO {
+    /// 1 to 28, or 31 for the end of the month
+    pub closing_day: u32,
+    /// 0 for the closing month, 1 for the next month and so on
+    pub payment_months_later: u32,
+    /// 1 to 28, or 31 for the end of the month
+    pub payment_day: u32,
+    /// `bank_transfer`, `direct_debit`, `cash` or `other`
+    pub payment_method: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct StocktakingDTO {
     pub id: String,
     pub stocktaken_date: i64,
@@ -20,25 +143,69 @@ pub struct StocktakingDTO {
     pub records: Vec<StocktakingRecordDTO>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct StocktakingRecordDTO {
     pub supply_id: String,
     pub supply_name: String,
     pub unit_name: String,
-    pub unit_price: f64,
+    /// derived from purchases by the valuation method if not provided
+    pub unit_price: Option<f64>,
     pub quantity: f64,
-    pub total_price: f64,
+    /// computed from the unit price if not provided
+    pub total_price: Option<f64>,
+    /// method the unit price was derived by, none if entered by hand
+    pub valuation_method: Option<String>,
+    /// defaults to the storage location of the supply
+    pub location_id: Option<String>,
+    /// ignored on input
+    pub location_name: Option<String>,
+    /// lot received in a journal the counted quantity belongs to
+    pub lot_number: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct JournalDTO {
     pub id: String,
     pub entry_date: i64,
     pub total_price: f64,
+    pub net_price: f64,
+    pub tax_price: f64,
+    pub gross_price: f64,
+    pub deductible_tax_price: f64,
+    pub non_deductible_tax_price: f64,
+    pub tax_summaries: Vec<TaxSummaryDTO>,
     pub records: Vec<JournalRecordDTO>,
+    /// prices deviating from recent purchases, only when recorded
+    pub warnings: Vec<PriceWarningDTO>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Unit price of a journal line deviating from the average of recent purchases
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PriceWarningDTO {
+    /// 1-based line number
+    pub line: usize,
+    pub supply_id: String,
+    pub supply_name: String,
+    pub supplier_id: String,
+    pub unit_name: String,
+    pub unit_price: f64,
+    pub average_price: f64,
+    /// positive if the price rose
+    pub deviation_percent: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaxSummaryDTO {
+    pub tax_category: String,
+    pub tax_rate: i64,
+    pub net_price: f64,
+    pub tax_price: f64,
+    pub gross_price: f64,
+    pub deductible_tax_price: f64,
+    pub non_deductible_tax_price: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct JournalRecordDTO {
     pub supply_id: String,
     pub supply_name: String,
@@ -48,57 +215,177 @@ pub struct JournalRecordDTO {
     pub unit_price: f64,
     pub quantity: f64,
     pub total_price: f64,
+    /// defaults to the tax category of the supply
+    pub tax_category: Option<String>,
+    /// defaults to tax-inclusive
+    pub tax_included: Option<bool>,
+    pub tax_price: f64,
+    /// defaults to the current registration of the supplier
+    pub invoice_registered: Option<bool>,
+    pub lot_number: Option<String>,
+    /// best-before or expiry date of the received goods
+    pub expiry_date: Option<i64>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CreateSupplyCommand {
     pub supply_name: String,
+    /// name of the purchase unit, registered if it does not exist
     pub unit_name: String,
     pub supplier_id: String,
+    /// defaults to the standard rate
+    pub tax_category: Option<String>,
+    /// defaults to the purchase unit
+    pub stock_unit_id: Option<String>,
+    pub conversions: Vec<UnitConversionDTO>,
+    /// not categorized if not provided
+    pub category_id: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct UpdateSupplyCommand {
     pub supply_id: String,
     pub supply_name: String,
+    /// name of the purchase unit, registered if it does not exist
     pub unit_name: String,
     pub supplier_id: String,
+    /// keeps the current category if not provided
+    pub tax_category: Option<String>,
+    /// keeps the current stock unit if not provided
+    pub stock_unit_id: Option<String>,
+    /// keeps the current conversions if not provided
+    pub conversions: Option<Vec<UnitConversionDTO>>,
+    /// keeps the current category if not provided, an empty id removes it
+    pub category_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetReorderLevelsCommand {
+    pub supply_id: String,
+    /// in the stock unit, clears the levels if not provided
+    pub par_level: Option<f64>,
+    /// defaults to the par level
+    pub reorder_point: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetSupplyOffersCommand {
+    pub supply_id: String,
+    /// the first is preferred unless another is
+    pub offers: Vec<SupplyOfferDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterUnitCommand {
+    pub unit_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateUnitCommand {
+    pub unit_id: String,
+    pub unit_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterCategoryCommand {
+    pub category_name: String,
+    pub parent_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateCategoryCommand {
+    pub category_id: String,
+    pub category_name: String,
+    pub parent_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterStoreCommand {
+    pub store_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateStoreCommand {
+    pub store_id: String,
+    pub store_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterStorageLocationCommand {
+    pub location_name: String,
+    pub sort_order: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateStorageLocationCommand {
+    pub location_id: String,
+    pub location_name: String,
+    pub sort_order: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceSupplyCommand {
+    pub supply_id: String,
+    /// clears the placement if not provided
+    pub location_id: Option<String>,
+    /// order within the storage location
+    pub sort_order: u32,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SearchSuppliersQuery {
     pub supplier_name: Option<String>,
     pub supply_name: Option<String>,
+    pub contact_person: Option<String>,
+    /// matches phone and FAX numbers ignoring hyphens
+    pub phone_number: Option<String>,
+    /// suppliers of supplies in the category or its descendants
+    pub category_id: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RegisterSupplierCommand {
     pub supplier_name: String,
+    pub invoice_registration_number: Option<String>,
+    pub invoice_registered: bool,
+    pub contact: SupplierContactDTO,
+    pub notes: Option<String>,
+    pub billing_terms: Option<BillingTermsDTO>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UpdateSupplierCommand {
     pub supplier_id: String,
     pub supplier_name: String,
+    pub invoice_registration_number: Option<String>,
+    /// keeps the current registration if not provided
+    pub invoice_registered: Option<bool>,
+    pub contact: SupplierContactDTO,
+    pub notes: Option<String>,
+    pub billing_terms: Option<BillingTermsDTO>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SearchStocktakingQuery {
     pub period_start: Option<i64>,
     pub period_end: Option<i64>,
+    /// lines of supplies in the category or its descendants
+    pub category_id: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct RecordStocktakingCommand {
     pub stocktaken_date: i64,
-    pub total_price: f64,
+    /// computed from the records if not provided
+    pub total_price: Option<f64>,
     pub records: Vec<StocktakingRecordDTO>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct EditStocktakingCommand {
     pub stocktaking_id: String,
-    pub total_price: f64,
+    /// computed from the records if not provided
+    pub total_price: Option<f64>,
     pub records: Vec<StocktakingRecordDTO>,
 }
 
@@ -108,6 +395,34 @@ pub struct SearchJournalsQuery {
     pub period_end: Option<i64>,
     pub supplier_name: Option<String>,
     pub supply_name: Option<String>,
+    /// lines of supplies in the category or its descendants
+    pub category_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceHistoryQuery {
+    pub supply_id: Option<String>,
+    pub supplier_id: Option<String>,
+    pub period_start: Option<i64>,
+    pub period_end: Option<i64>,
+}
+
+/// Unit prices of a supply bought from a supplier in a unit, oldest first
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceHistoryDTO {
+    pub supply_id: String,
+    pub supply_name: String,
+    pub supplier_id: String,
+    pub supplier_name: String,
+    pub unit_name: String,
+    pub prices: Vec<PricePointDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricePointDTO {
+    pub journal_id: String,
+    pub entry_date: i64,
+    pub unit_price: f64,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -123,3 +438,610 @@ pub struct EditJournalCommand {
     pub total_price: f64,
     pub records: Vec<JournalRecordDTO>,
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurchaseOrderDTO {
+    pub id: String,
+    pub supplier_id: String,
+    pub supplier_name: String,
+    pub ordered_date: i64,
+    /// one of draft, sent, partially_received, received or cancelled
+    pub status: String,
+    /// sum of the ordered quantities at the expected prices
+    pub expected_total_price: f64,
+    pub lines: Vec<PurchaseOrderLineDTO>,
+    /// journals the deliveries were recorded in
+    pub journal_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurchaseOrderLineDTO {
+    pub supply_id: String,
+    /// taken from the supply, ignored on input
+    pub supply_name: String,
+    /// purchase unit of the supply, ignored on input
+    pub unit_name: String,
+    pub quantity: f64,
+    pub expected_unit_price: f64,
+    /// computed by the core, ignored on input
+    pub received_quantity: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchPurchaseOrdersQuery {
+    pub period_start: Option<i64>,
+    pub period_end: Option<i64>,
+    pub supplier_id: Option<String>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DraftPurchaseOrderCommand {
+    pub supplier_id: String,
+    pub ordered_date: i64,
+    pub lines: Vec<PurchaseOrderLineDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevisePurchaseOrderCommand {
+    pub purchase_order_id: String,
+    pub ordered_date: i64,
+    pub lines: Vec<PurchaseOrderLineDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceivePurchaseOrderCommand {
+    pub purchase_order_id: String,
+    /// entry date of the journal recorded for the delivery
+    pub received_date: i64,
+    /// appends to this journal instead of recording a new one
+    pub journal_id: Option<String>,
+    pub lines: Vec<ReceivedLineDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceivedLineDTO {
+    pub supply_id: String,
+    pub quantity: f64,
+    /// defaults to the expected unit price of the order
+    pub unit_price: Option<f64>,
+    pub lot_number: Option<String>,
+    pub expiry_date: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferDTO {
+    pub id: String,
+    pub transferred_date: i64,
+    pub source_store_id: String,
+    pub source_store_name: String,
+    pub destination_store_id: String,
+    pub destination_store_name: String,
+    pub total_price: f64,
+    pub lines: Vec<TransferLineDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferLineDTO {
+    /// supply of the source store
+    pub supply_id: String,
+    /// supply of the destination store, found by the supply name if empty on input
+    pub destination_supply_id: String,
+    /// taken from the supply, ignored on input
+    pub supply_name: String,
+    /// stock unit of the supply, ignored on input
+    pub unit_name: String,
+    pub quantity: f64,
+    pub unit_price: f64,
+    /// computed by the core, ignored on input
+    pub total_price: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchTransfersQuery {
+    pub period_start: Option<i64>,
+    pub period_end: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordTransferCommand {
+    pub transferred_date: i64,
+    pub source_store_id: String,
+    pub destination_store_id: String,
+    pub lines: Vec<TransferLineDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdateTransferCommand {
+    pub transfer_id: String,
+    pub transferred_date: i64,
+    pub source_store_id: String,
+    pub destination_store_id: String,
+    pub lines: Vec<TransferLineDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsumptionDTO {
+    pub id: String,
+    pub consumed_date: i64,
+    pub records: Vec<ConsumptionRecordDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsumptionRecordDTO {
+    pub supply_id: String,
+    pub supply_name: String,
+    pub unit_name: String,
+    pub quantity: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchConsumptionsQuery {
+    pub period_start: Option<i64>,
+    pub period_end: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordConsumptionCommand {
+    pub consumed_date: i64,
+    pub records: Vec<ConsumptionRecordDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StockLedgerQuery {
+    pub at: i64,
+}
+
+/// Theoretical quantity of a supply in its stock unit
+#[derive(Debug, Clone, PartialEq)]
+pub struct StockLedgerDTO {
+    pub supply_id: String,
+    pub supply_name: String,
+    pub unit_name: String,
+    /// latest stocktaking counting the supply, none if never counted
+    pub opening_stocktaking_id: Option<String>,
+    pub opening_quantity: f64,
+    pub purchased_quantity: f64,
+    /// received from other stores minus sent to them
+    pub transferred_quantity: f64,
+    pub consumed_quantity: f64,
+    pub quantity: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpiringLotsQuery {
+    /// estimates the lots on hand at this time
+    pub at: i64,
+    /// lots expiring within this many days, including those already expired
+    pub within_days: u32,
+}
+
+/// Lot of a supply on hand, quantity in the unit it was received or counted in
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiringLotDTO {
+    pub supply_id: String,
+    pub supply_name: String,
+    /// none for goods received with an expiry date only
+    pub lot_number: Option<String>,
+    pub expiry_date: i64,
+    /// journal the lot was received in
+    pub journal_id: String,
+    pub received_date: i64,
+    /// stocktaking the lot was last counted in, none if received since
+    pub stocktaking_id: Option<String>,
+    pub unit_name: String,
+    pub quantity: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StockVarianceQuery {
+    pub stocktaking_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StockVarianceReportDTO {
+    pub stocktaking_id: String,
+    pub stocktaken_date: i64,
+    pub expected_value: f64,
+    pub counted_value: f64,
+    pub variance_value: f64,
+    pub supplies: Vec<SupplyVarianceDTO>,
+}
+
+/// Expected and counted quantity of a supply in its stock unit
+#[derive(Debug, Clone, PartialEq)]
+pub struct SupplyVarianceDTO {
+    pub supply_id: String,
+    pub supply_name: String,
+    pub unit_name: String,
+    pub expected_quantity: f64,
+    pub counted_quantity: f64,
+    /// counted minus expected
+    pub variance_quantity: f64,
+    /// per stock unit, from the stocktaking or else the last purchase
+    pub unit_price: f64,
+    pub expected_value: f64,
+    pub counted_value: f64,
+    pub variance_value: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostOfGoodsSoldQuery {
+    pub period_start: i64,
+    pub period_end: i64,
+    /// consolidates all stores if not provided
+    pub store_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostOfGoodsSoldDTO {
+    pub period_start: i64,
+    pub period_end: i64,
+    /// latest stocktaking before the period, none for the first period or several stores
+    pub opening_stocktaking_id: Option<String>,
+    /// latest stocktaking in the period, none for several stores
+    pub closing_stocktaking_id: Option<String>,
+    pub opening_inventory: f64,
+    pub purchases: f64,
+    /// transferred in from other stores minus transferred out to them
+    pub transfers: f64,
+    pub closing_inventory: f64,
+    pub cost_of_goods_sold: f64,
+    pub suppliers: Vec<SupplierCostOfGoodsSoldDTO>,
+    pub supplies: Vec<SupplyCostOfGoodsSoldDTO>,
+    /// stores with a stocktaking in the period
+    pub stores: Vec<StoreCostOfGoodsSoldDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoreCostOfGoodsSoldDTO {
+    pub store_id: String,
+    pub store_name: String,
+    /// latest stocktaking of the store before the period
+    pub opening_stocktaking_id: Option<String>,
+    /// latest stocktaking of the store in the period
+    pub closing_stocktaking_id: String,
+    pub opening_inventory: f64,
+    pub purchases: f64,
+    pub transfers: f64,
+    pub closing_inventory: f64,
+    pub cost_of_goods_sold: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SupplierCostOfGoodsSoldDTO {
+    /// none for supplies whose supplier is unknown
+    pub supplier_id: Option<String>,
+    pub supplier_name: Option<String>,
+    pub opening_inventory: f64,
+    pub purchases: f64,
+    pub transfers: f64,
+    pub closing_inventory: f64,
+    pub cost_of_goods_sold: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SupplyCostOfGoodsSoldDTO {
+    pub supply_id: String,
+    pub supply_name: String,
+    pub supplier_id: Option<String>,
+    pub opening_inventory: f64,
+    pub purchases: f64,
+    pub transfers: f64,
+    pub closing_inventory: f64,
+    pub cost_of_goods_sold: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReorderQuery {
+    /// estimates the stock on hand at this time
+    pub at: i64,
+}
+
+/// Supplies to reorder from a supplier
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReorderSuggestionDTO {
+    pub supplier_id: String,
+    pub supplier_name: String,
+    pub lines: Vec<ReorderLineDTO>,
+}
+
+/// Quantities of a supply in its stock unit
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReorderLineDTO {
+    pub supply_id: String,
+    pub supply_name: String,
+    pub unit_name: String,
+    pub on_hand: f64,
+    pub par_level: f64,
+    pub reorder_point: f64,
+    pub suggested_quantity: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WasteDTO {
+    pub id: String,
+    pub wasted_date: i64,
+    pub supply_id: String,
+    pub supply_name: String,
+    pub unit_name: String,
+    pub quantity: f64,
+    pub unit_price: f64,
+    pub total_price: f64,
+    /// `expired`, `damaged`, `over_prep` or `other`
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchWasteQuery {
+    pub period_start: Option<i64>,
+    pub period_end: Option<i64>,
+    pub supply_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordWasteCommand {
+    pub wasted_date: i64,
+    pub supply_id: String,
+    /// defaults to the stock unit of the supply
+    pub unit_name: Option<String>,
+    pub quantity: f64,
+    pub unit_price: f64,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasteSummaryQuery {
+    pub period_start: i64,
+    pub period_end: i64,
+}
+
+/// Waste of a period against the journal and stocktaking totals
+#[derive(Debug, Clone, PartialEq)]
+pub struct WasteSummaryDTO {
+    pub period_start: i64,
+    pub period_end: i64,
+    /// latest stocktaking in the period, none if not counted yet
+    pub closing_stocktaking_id: Option<String>,
+    pub wasted: f64,
+    pub purchases: f64,
+    pub closing_inventory: f64,
+    /// wasted value as a percentage of the purchases, none if nothing was purchased
+    pub loss_ratio: Option<f64>,
+    pub reasons: Vec<ReasonWasteDTO>,
+    pub supplies: Vec<SupplyWasteDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReasonWasteDTO {
+    pub reason: String,
+    pub wasted: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SupplyWasteDTO {
+    pub supply_id: String,
+    pub supply_name: String,
+    /// wasted quantities per unit
+    pub quantities: Vec<WasteQuantityDTO>,
+    pub wasted: f64,
+    pub purchases: f64,
+    pub closing_inventory: f64,
+    pub loss_ratio: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WasteQuantityDTO {
+    pub unit_name: String,
+    pub quantity: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SupplierInvoiceDTO {
+    pub id: String,
+    pub supplier_id: String,
+    pub supplier_name: String,
+    pub invoice_number: Option<String>,
+    pub period_start: i64,
+    pub period_end: i64,
+    pub due_date: i64,
+    /// amount billed including tax
+    pub amount: f64,
+    /// journals of the supplier entered in the period
+    pub journal_ids: Vec<String>,
+    /// gross total of the journal lines of the supplier in the period
+    pub journal_amount: f64,
+    /// billed amount less the journal amount, zero when the invoice matches
+    pub difference: f64,
+    pub paid_amount: f64,
+    pub balance: f64,
+    pub payments: Vec<PaymentDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentDTO {
+    pub id: String,
+    pub paid_date: i64,
+    pub amount: f64,
+    pub payment_method: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchSupplierInvoicesQuery {
+    pub supplier_id: Option<String>,
+    /// invoices whose billing period overlaps the given period
+    pub period_start: Option<i64>,
+    pub period_end: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordSupplierInvoiceCommand {
+    pub supplier_id: String,
+    pub invoice_number: Option<String>,
+    pub period_start: i64,
+    pub period_end: i64,
+    pub due_date: i64,
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdateSupplierInvoiceCommand {
+    pub invoice_id: String,
+    pub invoice_number: Option<String>,
+    pub period_start: i64,
+    pub period_end: i64,
+    pub due_date: i64,
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordPaymentCommand {
+    pub invoice_id: String,
+    pub paid_date: i64,
+    pub amount: f64,
+    /// `bank_transfer`, `direct_debit`, `cash` or `other`,
+    /// the method of the billing terms of the supplier if none
+    pub payment_method: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CancelPaymentCommand {
+    pub invoice_id: String,
+    pub payment_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayableBalanceQuery {
+    /// balances at this time, later invoices and payments ignored
+    pub at: i64,
+}
+
+/// Outstanding balance of a supplier split by days past the due dates
+#[derive(Debug, Clone, PartialEq)]
+pub struct SupplierBalanceDTO {
+    pub supplier_id: String,
+    pub supplier_name: String,
+    pub invoiced: f64,
+    pub paid: f64,
+    pub balance: f64,
+    /// not yet due
+    pub current: f64,
+    pub overdue_1_30: f64,
+    pub overdue_31_60: f64,
+    pub overdue_61_90: f64,
+    pub overdue_over_90: f64,
+}
+
+/// A deleted record in the trash
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrashedDTO<T> {
+    pub item: T,
+    pub deleted_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PurgeExpiredCommand {
+    pub now: i64,
+    /// days records are kept in the trash, 0 keeps them forever
+    pub retention_days: u32,
+}
+
+/// A version of a journal or stocktaking
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevisionDTO<T> {
+    /// numbered from 1, the current version last
+    pub number: u32,
+    /// when the version was replaced by an edit, none for the current version
+    pub revised_at: Option<i64>,
+    pub item: T,
+}
+
+/// A line added, removed or changed between two versions
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineDiffDTO {
+    /// one of added, removed or changed
+    pub change: String,
+    pub supply_id: String,
+    pub supply_name: String,
+    pub supplier_id: Option<String>,
+    pub supplier_name: Option<String>,
+    pub location_id: Option<String>,
+    pub lot_number: Option<String>,
+    pub unit_name: String,
+    /// none when the line was added
+    pub quantity_before: Option<f64>,
+    /// none when the line was removed
+    pub quantity_after: Option<f64>,
+    pub quantity_delta: f64,
+    pub unit_price_before: Option<f64>,
+    pub unit_price_after: Option<f64>,
+    pub unit_price_delta: f64,
+    pub total_price_before: Option<f64>,
+    pub total_price_after: Option<f64>,
+    pub total_price_delta: f64,
+}
+
+/// Accounting period, whose journals and stocktakings are locked while closed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountingPeriodDTO {
+    pub id: String,
+    pub name: String,
+    pub period_start: i64,
+    pub period_end: i64,
+    pub closed: bool,
+    /// closings and reopenings, oldest first
+    pub audit_trail: Vec<PeriodAuditEntryDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeriodAuditEntryDTO {
+    /// `close` or `reopen`
+    pub operation: String,
+    pub performed_at: i64,
+    pub note: Option<String>,
+}
+
+/// Closes the period spanning `period_start` to `period_end`, both included.
+///
+/// A period reopened before is closed again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClosePeriodCommand {
+    pub name: String,
+    pub period_start: i64,
+    pub period_end: i64,
+    pub closed_at: i64,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReopenPeriodCommand {
+    pub period_id: String,
+    pub reopened_at: i64,
+    /// reason the closed records need changing
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntryDTO {
+    pub id: String,
+    /// `supplier`, `supply`, `journal` or `stocktaking`
+    pub entity: String,
+    pub entity_id: String,
+    /// `register`, `update`, `record`, `edit`, `delete`, `restore` or `purge`
+    pub operation: String,
+    pub logged_at: i64,
+    pub operator: Option<String>,
+    /// JSON of the entity before the change, none if registered or recorded
+    pub before: Option<String>,
+    /// JSON of the entity after the change, none if deleted or purged
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchAuditLogQuery {
+    pub entity: Option<String>,
+    pub entity_id: Option<String>,
+    pub period_start: Option<i64>,
+    pub period_end: Option<i64>,
+    pub operator: Option<String>,
+}
@@ -8,14 +8,106 @@ pub trait SupplyUsecase {
     fn list(&self) -> Result<Vec<SupplyDTO>>;
     /// Get a supply
     fn get(&self, supply_id: impl AsRef<str>) -> Result<Option<SupplyDTO>>;
-    /// List all supplies of a supplier
+    /// List all supplies offered by a supplier
     fn get_of_supplier(&self, supplier_id: String) -> Result<Vec<SupplyDTO>>;
     /// Register a new supply
     fn register(&self, command: CreateSupplyCommand) -> Result<SupplyDTO>;
     /// Update a supply
     fn update(&self, command: UpdateSupplyCommand) -> Result<()>;
+    /// Set or clear the par level and reorder point of a supply
+    fn set_reorder_levels(&self, command: SetReorderLevelsCommand) -> Result<()>;
+    /// Replace the suppliers offering a supply, with their item codes and prices
+    fn set_offers(&self, command: SetSupplyOffersCommand) -> Result<()>;
     /// Delete a supply
     fn delete(&self, supply_id: impl AsRef<str>) -> Result<()>;
+    /// List deleted supplies in the trash
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<SupplyDTO>>>;
+    /// Restore a deleted supply from the trash
+    fn restore(&self, supply_id: impl AsRef<str>) -> Result<()>;
+    /// Delete a supply in the trash permanently
+    fn purge(&self, supply_id: impl AsRef<str>) -> Result<()>;
+}
+
+/// Usecase trait for unit management
+pub trait UnitUsecase {
+    /// List all units
+    fn list(&self) -> Result<Vec<UnitDTO>>;
+    /// Get a unit
+    fn get(&self, unit_id: impl AsRef<str>) -> Result<Option<UnitDTO>>;
+    /// Register a new unit
+    fn register(&self, command: RegisterUnitCommand) -> Result<UnitDTO>;
+    /// Update a unit
+    fn update(&self, command: UpdateUnitCommand) -> Result<()>;
+    /// Delete a unit
+    fn delete(&self, unit_id: impl AsRef<str>) -> Result<()>;
+    /// List deleted units in the trash
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<UnitDTO>>>;
+    /// Restore a deleted unit from the trash
+    fn restore(&self, unit_id: impl AsRef<str>) -> Result<()>;
+    /// Delete a unit in the trash permanently
+    fn purge(&self, unit_id: impl AsRef<str>) -> Result<()>;
+}
+
+/// Usecase trait for supply category management
+pub trait CategoryUsecase {
+    /// List all categories, parents before their children
+    fn list(&self) -> Result<Vec<CategoryDTO>>;
+    /// Get a category
+    fn get(&self, category_id: impl AsRef<str>) -> Result<Option<CategoryDTO>>;
+    /// Register a new category
+    fn register(&self, command: RegisterCategoryCommand) -> Result<CategoryDTO>;
+    /// Update a category
+    fn update(&self, command: UpdateCategoryCommand) -> Result<()>;
+    /// Delete a category
+    fn delete(&self, category_id: impl AsRef<str>) -> Result<()>;
+    /// List deleted categories in the trash
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<CategoryDTO>>>;
+    /// Restore a deleted category from the trash
+    fn restore(&self, category_id: impl AsRef<str>) -> Result<()>;
+    /// Delete a category in the trash permanently
+    fn purge(&self, category_id: impl AsRef<str>) -> Result<()>;
+}
+
+/// Usecase trait for storage location management
+pub trait StorageLocationUsecase {
+    /// List all storage locations in counting order
+    fn list(&self) -> Result<Vec<StorageLocationDTO>>;
+    /// Get a storage location
+    fn get(&self, location_id: impl AsRef<str>) -> Result<Option<StorageLocationDTO>>;
+    /// Register a new storage location
+    fn register(&self, command: RegisterStorageLocationCommand) -> Result<StorageLocationDTO>;
+    /// Update a storage location
+    fn update(&self, command: UpdateStorageLocationCommand) -> Result<()>;
+    /// Delete a storage location
+    fn delete(&self, location_id: impl AsRef<str>) -> Result<()>;
+    /// List deleted storage locations in the trash
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<StorageLocationDTO>>>;
+    /// Restore a deleted storage location from the trash
+    fn restore(&self, location_id: impl AsRef<str>) -> Result<()>;
+    /// Delete a storage location in the trash permanently
+    fn purge(&self, location_id: impl AsRef<str>) -> Result<()>;
+    /// Set or clear the default storage location of a supply
+    fn place_supply(&self, command: PlaceSupplyCommand) -> Result<()>;
+}
+
+/// Usecase trait for store management
+pub trait StoreUsecase {
+    /// List all stores
+    fn list(&self) -> Result<Vec<StoreDTO>>;
+    /// Get a store
+    fn get(&self, store_id: impl AsRef<str>) -> Result<Option<StoreDTO>>;
+    /// Register a new store
+    fn register(&self, command: RegisterStoreCommand) -> Result<StoreDTO>;
+    /// Update a store
+    fn update(&self, command: UpdateStoreCommand) -> Result<()>;
+    /// Delete a store without any data
+    fn delete(&self, store_id: impl AsRef<str>) -> Result<()>;
+    /// List deleted stores in the trash
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<StoreDTO>>>;
+    /// Restore a deleted store from the trash
+    fn restore(&self, store_id: impl AsRef<str>) -> Result<()>;
+    /// Delete a store in the trash permanently
+    fn purge(&self, store_id: impl AsRef<str>) -> Result<()>;
 }
 
 /// Usecase trait for supplier management
@@ -32,6 +124,12 @@ pub trait SupplierUsecase {
     fn update(&self, command: UpdateSupplierCommand) -> Result<()>;
     /// Delete a supplier
     fn delete(&self, supplier_id: impl AsRef<str>) -> Result<()>;
+    /// List deleted suppliers in the trash
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<SupplierDTO>>>;
+    /// Restore a deleted supplier from the trash
+    fn restore(&self, supplier_id: impl AsRef<str>) -> Result<()>;
+    /// Delete a supplier in the trash permanently
+    fn purge(&self, supplier_id: impl AsRef<str>) -> Result<()>;
 }
 
 /// Usecase trait for stocktaking management
@@ -48,6 +146,32 @@ pub trait StocktakingUsecase {
     fn edit(&self, command: EditStocktakingCommand) -> Result<()>;
     /// Delete a stocktaking
     fn delete(&self, stocktaking_id: impl AsRef<str>) -> Result<()>;
+    /// List deleted stocktakings in the trash
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<StocktakingDTO>>>;
+    /// Restore a deleted stocktaking from the trash
+    fn restore(&self, stocktaking_id: impl AsRef<str>) -> Result<()>;
+    /// Delete a stocktaking in the trash permanently
+    fn purge(&self, stocktaking_id: impl AsRef<str>) -> Result<()>;
+    /// Sum counted quantities of the stocktakings per supply in its stock unit
+    fn aggregate_quantities(&self, query: SearchStocktakingQuery) -> Result<Vec<StockQuantityDTO>>;
+    /// Sum counted values of the stocktakings per category
+    fn totals_by_category(&self, query: SearchStocktakingQuery) -> Result<Vec<CategoryTotalDTO>>;
+    /// List supplies to count per storage location, in counting order
+    fn count_sheet(&self) -> Result<Vec<CountSheetDTO>>;
+    /// List the versions of a stocktaking, oldest first and the current one last
+    fn list_revisions(
+        &self,
+        stocktaking_id: impl AsRef<str>,
+    ) -> Result<Vec<RevisionDTO<StocktakingDTO>>>;
+    /// Compare the lines of two versions of a stocktaking
+    fn diff_revisions(
+        &self,
+        stocktaking_id: impl AsRef<str>,
+        from: u32,
+        to: u32,
+    ) -> Result<Vec<LineDiffDTO>>;
+    /// Revert a stocktaking to one of its versions as a new edit
+    fn revert(&self, stocktaking_id: impl AsRef<str>, revision: u32) -> Result<()>;
 }
 
 /// Usecase trait for journal management
@@ -64,4 +188,187 @@ pub trait JournalUsecase {
     fn edit(&self, command: EditJournalCommand) -> Result<()>;
     /// Delete a journal
     fn delete(&self, journal_id: impl AsRef<str>) -> Result<()>;
+    /// List deleted journals in the trash
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<JournalDTO>>>;
+    /// Restore a deleted journal from the trash
+    fn restore(&self, journal_id: impl AsRef<str>) -> Result<()>;
+    /// Delete a journal in the trash permanently
+    fn purge(&self, journal_id: impl AsRef<str>) -> Result<()>;
+    /// Sum purchased quantities of the journals per supply in its stock unit
+    fn aggregate_quantities(&self, query: SearchJournalsQuery) -> Result<Vec<StockQuantityDTO>>;
+    /// List unit prices over time per supply and supplier
+    fn price_history(&self, query: PriceHistoryQuery) -> Result<Vec<PriceHistoryDTO>>;
+    /// Sum purchased values of the journals per category
+    fn totals_by_category(&self, query: SearchJournalsQuery) -> Result<Vec<CategoryTotalDTO>>;
+    /// List the versions of a journal, oldest first and the current one last
+    fn list_revisions(&self, journal_id: impl AsRef<str>) -> Result<Vec<RevisionDTO<JournalDTO>>>;
+    /// Compare the lines of two versions of a journal
+    fn diff_revisions(
+        &self,
+        journal_id: impl AsRef<str>,
+        from: u32,
+        to: u32,
+    ) -> Result<Vec<LineDiffDTO>>;
+    /// Revert a journal to one of its versions as a new edit
+    fn revert(&self, journal_id: impl AsRef<str>, revision: u32) -> Result<()>;
+}
+
+/// Usecase trait for purchase order management
+pub trait PurchaseOrderUsecase {
+    /// List all purchase orders
+    fn list(&self) -> Result<Vec<PurchaseOrderDTO>>;
+    /// Get a purchase order
+    fn get(&self, purchase_order_id: impl AsRef<str>) -> Result<Option<PurchaseOrderDTO>>;
+    /// Search purchase orders
+    fn search(&self, query: SearchPurchaseOrdersQuery) -> Result<Vec<PurchaseOrderDTO>>;
+    /// Draft a new purchase order
+    fn draft(&self, command: DraftPurchaseOrderCommand) -> Result<PurchaseOrderDTO>;
+    /// Revise a draft purchase order
+    fn revise(&self, command: RevisePurchaseOrderCommand) -> Result<()>;
+    /// Mark a draft purchase order as sent
+    fn send(&self, purchase_order_id: impl AsRef<str>) -> Result<()>;
+    /// Cancel a purchase order
+    fn cancel(&self, purchase_order_id: impl AsRef<str>) -> Result<()>;
+    /// Receive delivered quantities, recording them in a journal
+    fn receive(&self, command: ReceivePurchaseOrderCommand) -> Result<JournalDTO>;
+    /// Delete a draft or cancelled purchase order
+    fn delete(&self, purchase_order_id: impl AsRef<str>) -> Result<()>;
+    /// List deleted purchase orders in the trash
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<PurchaseOrderDTO>>>;
+    /// Restore a deleted purchase order from the trash
+    fn restore(&self, purchase_order_id: impl AsRef<str>) -> Result<()>;
+    /// Delete a purchase order in the trash permanently
+    fn purge(&self, purchase_order_id: impl AsRef<str>) -> Result<()>;
+}
+
+/// Usecase trait for inter-store transfers
+pub trait TransferUsecase {
+    /// List all transfers from or to the store
+    fn list(&self) -> Result<Vec<TransferDTO>>;
+    /// Get a transfer
+    fn get(&self, transfer_id: impl AsRef<str>) -> Result<Option<TransferDTO>>;
+    /// Search transfers from or to the store
+    fn search(&self, query: SearchTransfersQuery) -> Result<Vec<TransferDTO>>;
+    /// Record stock moved from one store to another
+    fn record(&self, command: RecordTransferCommand) -> Result<TransferDTO>;
+    /// Update a transfer
+    fn update(&self, command: UpdateTransferCommand) -> Result<()>;
+    /// Delete a transfer
+    fn delete(&self, transfer_id: impl AsRef<str>) -> Result<()>;
+    /// List deleted transfers in the trash
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<TransferDTO>>>;
+    /// Restore a deleted transfer from the trash
+    fn restore(&self, transfer_id: impl AsRef<str>) -> Result<()>;
+    /// Delete a transfer in the trash permanently
+    fn purge(&self, transfer_id: impl AsRef<str>) -> Result<()>;
+}
+
+/// Usecase trait for consumption management
+pub trait ConsumptionUsecase {
+    /// List all consumptions
+    fn list(&self) -> Result<Vec<ConsumptionDTO>>;
+    /// Search consumptions
+    fn search(&self, query: SearchConsumptionsQuery) -> Result<Vec<ConsumptionDTO>>;
+    /// Record a new consumption
+    fn record(&self, command: RecordConsumptionCommand) -> Result<ConsumptionDTO>;
+    /// Delete a consumption
+    fn delete(&self, consumption_id: impl AsRef<str>) -> Result<()>;
+    /// List deleted consumptions in the trash
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<ConsumptionDTO>>>;
+    /// Restore a deleted consumption from the trash
+    fn restore(&self, consumption_id: impl AsRef<str>) -> Result<()>;
+    /// Delete a consumption in the trash permanently
+    fn purge(&self, consumption_id: impl AsRef<str>) -> Result<()>;
+}
+
+/// Usecase trait for the theoretical stock ledger
+pub trait StockLedgerUsecase {
+    /// Compute the theoretical quantity on hand per supply at a time
+    fn on_hand(&self, query: StockLedgerQuery) -> Result<Vec<StockLedgerDTO>>;
+    /// Compare the theoretical quantities with the counted quantities of a stocktaking
+    fn variance(&self, query: StockVarianceQuery) -> Result<StockVarianceReportDTO>;
+    /// List lots on hand expiring soon, earliest first
+    fn expiring_lots(&self, query: ExpiringLotsQuery) -> Result<Vec<ExpiringLotDTO>>;
+}
+
+/// Usecase trait for reorder suggestions
+pub trait ReorderUsecase {
+    /// Suggest supplies to order up to their par levels, grouped by supplier
+    fn suggest(&self, query: ReorderQuery) -> Result<Vec<ReorderSuggestionDTO>>;
+}
+
+/// Usecase trait for reports
+pub trait ReportUsecase {
+    /// Compute cost of goods sold of a period overall, per supplier and per supply
+    fn cost_of_goods_sold(&self, query: CostOfGoodsSoldQuery) -> Result<CostOfGoodsSoldDTO>;
+}
+
+/// Usecase trait for waste management
+pub trait WasteUsecase {
+    /// List all waste records
+    fn list(&self) -> Result<Vec<WasteDTO>>;
+    /// Search waste records
+    fn search(&self, query: SearchWasteQuery) -> Result<Vec<WasteDTO>>;
+    /// Record stock thrown away
+    fn record(&self, command: RecordWasteCommand) -> Result<WasteDTO>;
+    /// Delete a waste record
+    fn delete(&self, waste_id: impl AsRef<str>) -> Result<()>;
+    /// List deleted waste records in the trash
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<WasteDTO>>>;
+    /// Restore a deleted waste record from the trash
+    fn restore(&self, waste_id: impl AsRef<str>) -> Result<()>;
+    /// Delete a waste record in the trash permanently
+    fn purge(&self, waste_id: impl AsRef<str>) -> Result<()>;
+    /// Sum waste of a period overall, per reason and per supply with loss ratios
+    fn summarize(&self, query: WasteSummaryQuery) -> Result<WasteSummaryDTO>;
+}
+
+/// Usecase trait for accounts payable
+pub trait PayableUsecase {
+    /// List all supplier invoices of the store
+    fn list(&self) -> Result<Vec<SupplierInvoiceDTO>>;
+    /// Get a supplier invoice
+    fn get(&self, invoice_id: impl AsRef<str>) -> Result<Option<SupplierInvoiceDTO>>;
+    /// Search supplier invoices of the store
+    fn search(&self, query: SearchSupplierInvoicesQuery) -> Result<Vec<SupplierInvoiceDTO>>;
+    /// Record an invoice of a supplier for a closing period, matched against its journals
+    fn record(&self, command: RecordSupplierInvoiceCommand) -> Result<SupplierInvoiceDTO>;
+    /// Update a supplier invoice
+    fn update(&self, command: UpdateSupplierInvoiceCommand) -> Result<()>;
+    /// Delete a supplier invoice with its payments
+    fn delete(&self, invoice_id: impl AsRef<str>) -> Result<()>;
+    /// List deleted supplier invoices in the trash
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<SupplierInvoiceDTO>>>;
+    /// Restore a deleted supplier invoice from the trash
+    fn restore(&self, invoice_id: impl AsRef<str>) -> Result<()>;
+    /// Delete a supplier invoice in the trash permanently
+    fn purge(&self, invoice_id: impl AsRef<str>) -> Result<()>;
+    /// Record a payment against a supplier invoice
+    fn record_payment(&self, command: RecordPaymentCommand) -> Result<SupplierInvoiceDTO>;
+    /// Cancel a payment recorded by mistake
+    fn cancel_payment(&self, command: CancelPaymentCommand) -> Result<()>;
+    /// Compute the outstanding balance and aging per supplier
+    fn balances(&self, query: PayableBalanceQuery) -> Result<Vec<SupplierBalanceDTO>>;
+}
+
+/// Usecase trait for the trash bin
+pub trait TrashUsecase {
+    /// Purge records kept in the trash longer than the retention period, returning their number
+    fn purge_expired(&self, command: PurgeExpiredCommand) -> Result<usize>;
+}
+
+/// Usecase trait for closing accounting periods
+pub trait AccountingPeriodUsecase {
+    /// List all accounting periods of the store, the latest first
+    fn list(&self) -> Result<Vec<AccountingPeriodDTO>>;
+    /// Close an accounting period, locking its journals and stocktakings
+    fn close(&self, command: ClosePeriodCommand) -> Result<AccountingPeriodDTO>;
+    /// Reopen a closed accounting period
+    fn reopen(&self, command: ReopenPeriodCommand) -> Result<()>;
+}
+
+/// Usecase trait for the audit log
+pub trait AuditUsecase {
+    /// Search changes of suppliers, supplies, journals and stocktakings, the latest first
+    fn search(&self, query: SearchAuditLogQuery) -> Result<Vec<AuditEntryDTO>>;
 }
@@ -1,9 +1,16 @@
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::order::*;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::transfer::*;
+use crate::core::domain::values::waste::*;
 
 #[derive(Debug, Clone)]
 pub struct FindSupplierQuery {
     pub supplier_name: Option<SupplierName>,
     pub supply_name: Option<SupplyName>,
+    pub contact_person: Option<ContactPerson>,
+    /// digits of a phone or FAX number, partially matched
+    pub phone_digits: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -14,7 +21,46 @@ pub struct FindJournalsQuery {
     pub supply_name: Option<SupplyName>,
 }
 
+pub struct FindPurchaseOrdersQuery {
+    pub period_start: Option<OrderedDateTime>,
+    pub period_end: Option<OrderedDateTime>,
+    pub supplier_id: Option<SupplierId>,
+    pub status: Option<OrderStatus>,
+}
+
+pub struct FindTransfersQuery {
+    pub period_start: Option<TransferredDateTime>,
+    pub period_end: Option<TransferredDateTime>,
+}
+
+/// Invoices whose billing period overlaps the given period
+pub struct FindSupplierInvoicesQuery {
+    pub supplier_id: Option<SupplierId>,
+    pub period_start: Option<i64>,
+    pub period_end: Option<i64>,
+}
+
 pub struct FindStocktakingsQuery {
     pub period_start: Option<StocktakenDateTime>,
     pub period_end: Option<StocktakenDateTime>,
 }
+
+pub struct FindConsumptionsQuery {
+    pub period_start: Option<ConsumedDateTime>,
+    pub period_end: Option<ConsumedDateTime>,
+}
+
+pub struct FindWasteQuery {
+    pub period_start: Option<WastedDateTime>,
+    pub period_end: Option<WastedDateTime>,
+    pub supply_id: Option<SupplyId>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FindAuditEntriesQuery {
+    pub entity: Option<AuditEntity>,
+    pub entity_id: Option<String>,
+    pub period_start: Option<LoggedDateTime>,
+    pub period_end: Option<LoggedDateTime>,
+    pub operator: Option<OperatorName>,
+}
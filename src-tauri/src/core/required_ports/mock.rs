@@ -1,143 +1,247 @@
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::core::domain::entities::accounting::*;
+use crate::core::domain::entities::audit::*;
+use crate::core::domain::entities::payable::*;
 use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::accounting::*;
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::category::*;
+use crate::core::domain::values::event::*;
+use crate::core::domain::values::location::*;
+use crate::core::domain::values::order::*;
+use crate::core::domain::values::payable::*;
+use crate::core::domain::values::revision::*;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::transfer::*;
+use crate::core::domain::values::trash::*;
+use crate::core::domain::values::unit::*;
+use crate::core::domain::values::waste::*;
 use crate::core::required_ports::*;
 use crate::core::*;
 
+/// Returns the current time as the time records are deleted at.
+fn deleted_now() -> DeletedDateTime {
+    DeletedDateTime::new(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or_default(),
+    )
+}
+
+/// Returns the current time as the time changes are logged at.
+fn logged_now() -> LoggedDateTime {
+    LoggedDateTime::new(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or_default(),
+    )
+}
+
+/// Returns the current time as the time versions are revised at.
+fn revised_now() -> RevisedDateTime {
+    RevisedDateTime::new(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or_default(),
+    )
+}
+
 #[derive(Debug, Default)]
 pub struct Storage {
-    supplies: Vec<Supply>,
-    suppliers: Vec<Supplier>,
-    journals: Vec<Journal>,
-    stocktakings: Vec<Stocktaking>,
+    stores: Vec<Store>,
+    supplies: Vec<(StoreId, Supply)>,
+    units: Vec<Unit>,
+    categories: Vec<Category>,
+    locations: Vec<StorageLocation>,
+    suppliers: Vec<(StoreId, Supplier)>,
+    journals: Vec<(StoreId, Journal)>,
+    purchase_orders: Vec<(StoreId, PurchaseOrder)>,
+    transfers: Vec<Transfer>,
+    stocktakings: Vec<(StoreId, Stocktaking)>,
+    consumptions: Vec<(StoreId, Consumption)>,
+    wastes: Vec<(StoreId, WasteRecord)>,
+    invoices: Vec<(StoreId, SupplierInvoice)>,
+    payment_sequence: usize,
+    accounting_periods: Vec<(StoreId, AccountingPeriod)>,
+    audit_entries: Vec<(StoreId, AuditEntry)>,
+    journal_revisions: Vec<Revision<Journal>>,
+    stocktaking_revisions: Vec<Revision<Stocktaking>>,
+    events: Vec<DomainEvent>,
+    deleted_stores: Vec<(DeletedDateTime, Store)>,
+    deleted_supplies: Vec<(DeletedDateTime, StoreId, Supply)>,
+    deleted_units: Vec<(DeletedDateTime, Unit)>,
+    deleted_categories: Vec<(DeletedDateTime, Category)>,
+    deleted_locations: Vec<(DeletedDateTime, StorageLocation)>,
+    deleted_suppliers: Vec<(DeletedDateTime, StoreId, Supplier)>,
+    deleted_journals: Vec<(DeletedDateTime, StoreId, Journal)>,
+    deleted_stocktakings: Vec<(DeletedDateTime, StoreId, Stocktaking)>,
+    deleted_purchase_orders: Vec<(DeletedDateTime, StoreId, PurchaseOrder)>,
+    deleted_transfers: Vec<(DeletedDateTime, Transfer)>,
+    deleted_invoices: Vec<(DeletedDateTime, StoreId, SupplierInvoice)>,
+    deleted_consumptions: Vec<(DeletedDateTime, StoreId, Consumption)>,
+    deleted_wastes: Vec<(DeletedDateTime, StoreId, WasteRecord)>,
+}
+
+impl Storage {
+    /// Appends the change, if given, to the audit log of the store.
+    fn log_change(&mut self, store: &StoreContext, change: Option<AuditChange>) -> Result<()> {
+        if let Some(change) = change {
+            let id = AuditEntryId::new((self.audit_entries.len() + 1).to_string())?;
+
+            self.audit_entries.push((
+                store.store_id()?.clone(),
+                AuditEntry::restore(id, logged_now(), change),
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Default)]
-pub struct MockSupplyRepository {
+pub struct MockStoreRepository {
     storage: Arc<Mutex<Storage>>,
 }
 
-impl MockSupplyRepository {
+impl MockStoreRepository {
     pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
         Self { storage }
     }
 }
 
-impl ForSupplyPersistence for MockSupplyRepository {
-    fn next_id(&self) -> Result<SupplyId> {
-        let id = self.storage.lock().unwrap().supplies.len() + 1;
+impl ForStorePersistence for MockStoreRepository {
+    fn next_id(&self) -> Result<StoreId> {
+        let id = self.storage.lock().unwrap().stores.len() + 1;
 
-        let id = SupplyId::new(id.to_string())?;
+        let id = StoreId::new(id.to_string())?;
 
         Ok(id)
     }
 
-    fn has(&self, supply_ids: &[SupplyId]) -> Result<bool> {
-        let supplies = &self.storage.lock().unwrap().supplies;
-
-        for id in supply_ids {
-            if !supplies.iter().any(|s| s.id().eq(id)) {
-                return Ok(false);
-            }
-        }
+    fn list(&self) -> Result<Vec<Store>> {
+        let stores = self.storage.lock().unwrap().stores.clone();
 
-        Ok(true)
+        Ok(stores)
     }
 
-    fn list(&self) -> Result<Vec<Supply>> {
-        let supplies = &self.storage.lock().unwrap().supplies;
+    fn get(&self, id: StoreId) -> Result<Option<Store>> {
+        let store = self
+            .storage
+            .lock()
+            .unwrap()
+            .stores
+            .iter()
+            .find(|s| s.id().eq(&id))
+            .cloned();
 
-        Ok(supplies.iter().cloned().collect())
+        Ok(store)
     }
 
-    fn get(&self, id: SupplyId) -> Result<Option<Supply>> {
-        let supplies = &self.storage.lock().unwrap().supplies;
+    fn add(&self, store: Store) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
 
-        for supply in supplies {
-            if supply.id().eq(&id) {
-                return Ok(Some(supply.clone()));
-            }
+        if storage.stores.iter().any(|s| s.id().eq(store.id())) {
+            return Err(Error::InfrastructureError(format!("store already exists.")));
         }
 
-        Ok(None)
+        storage.stores.push(store);
+
+        Ok(())
     }
 
-    fn list_of_supplier(&self, supplier_id: SupplierId) -> Result<Vec<Supply>> {
-        let supplies = &self.storage.lock().unwrap().supplies;
+    fn save(&self, store: Store) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
 
-        let supplies: Vec<Supply> = supplies
-            .iter()
-            .filter(|supply| supply.supplier_id().eq(&supplier_id))
-            .cloned()
-            .collect();
+        if let Some(index) = storage.stores.iter().position(|s| s.id().eq(store.id())) {
+            storage.stores[index] = store;
+        }
 
-        Ok(supplies)
+        Ok(())
     }
 
-    fn add(&self, supply: Supply) -> Result<()> {
+    fn delete(&self, id: StoreId) -> Result<()> {
         let mut storage = self.storage.lock().unwrap();
 
-        storage.supplies.push(supply.clone());
+        if let Some(index) = storage.stores.iter().position(|s| s.id().eq(&id)) {
+            let item = storage.stores.remove(index);
+
+            storage.deleted_stores.push((deleted_now(), item));
+        }
 
         Ok(())
     }
 
-    fn save(&self, supply: Supply) -> Result<()> {
+    fn list_deleted(&self) -> Result<Vec<Trashed<Store>>> {
+        let deleted = &self.storage.lock().unwrap().deleted_stores;
+
+        Ok(deleted
+            .iter()
+            .map(|(deleted_at, item)| Trashed::new(item.clone(), *deleted_at))
+            .collect())
+    }
+
+    fn restore(&self, id: StoreId) -> Result<()> {
         let mut storage = self.storage.lock().unwrap();
 
         if let Some(index) = storage
-            .supplies
+            .deleted_stores
             .iter()
-            .position(|supply| supply.id().eq(supply.id()))
+            .position(|(_, item)| item.id().eq(&id))
         {
-            storage.supplies[index] = supply;
+            let (_, item) = storage.deleted_stores.remove(index);
+
+            storage.stores.push(item);
         }
 
         Ok(())
     }
 
-    fn delete(&self, id: SupplyId) -> Result<()> {
+    fn purge(&self, id: StoreId) -> Result<()> {
         let mut storage = self.storage.lock().unwrap();
 
-        if let Some(index) = storage
-            .supplies
-            .iter()
-            .position(|supply| supply.id().eq(&id))
-        {
-            storage.supplies.remove(index);
-        }
+        storage
+            .deleted_stores
+            .retain(|(_, item)| !item.id().eq(&id));
 
         Ok(())
     }
 }
 
 #[derive(Debug, Clone, Default)]
-pub struct MockSupplierRepository {
+pub struct MockSupplyRepository {
     storage: Arc<Mutex<Storage>>,
 }
 
-impl MockSupplierRepository {
+impl MockSupplyRepository {
     pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
         Self { storage }
     }
 }
 
-impl ForSupplierPersistence for MockSupplierRepository {
-    fn next_id(&self) -> Result<SupplierId> {
-        let id = self.storage.lock().unwrap().suppliers.len() + 1;
+impl ForSupplyPersistence for MockSupplyRepository {
+    fn next_id(&self) -> Result<SupplyId> {
+        let id = self.storage.lock().unwrap().supplies.len() + 1;
 
-        let id = SupplierId::new(id.to_string())?;
+        let id = SupplyId::new(id.to_string())?;
 
         Ok(id)
     }
 
-    fn has(&self, supplier_ids: &[SupplierId]) -> Result<bool> {
-        let suppliers = &self.storage.lock().unwrap().suppliers;
+    fn has(&self, store: &StoreContext, supply_ids: &[SupplyId]) -> Result<bool> {
+        let supplies = &self.storage.lock().unwrap().supplies;
 
-        for id in supplier_ids {
-            if !suppliers.iter().any(|s| s.id().eq(id)) {
+        for id in supply_ids {
+            if !supplies
+                .iter()
+                .any(|(store_id, s)| store.includes(store_id) && s.id().eq(id))
+            {
                 return Ok(false);
             }
         }
@@ -145,318 +249,2167 @@ impl ForSupplierPersistence for MockSupplierRepository {
         Ok(true)
     }
 
-    fn list(&self) -> Result<Vec<Supplier>> {
-        let suppliers = self.storage.lock().unwrap().suppliers.clone();
+    fn list(&self, store: &StoreContext) -> Result<Vec<Supply>> {
+        let supplies = &self.storage.lock().unwrap().supplies;
 
-        Ok(suppliers)
+        Ok(supplies
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, supply)| supply.clone())
+            .collect())
     }
 
-    fn get(&self, id: SupplierId) -> Result<Option<Supplier>> {
-        let supplier = self
-            .storage
-            .lock()
-            .unwrap()
-            .suppliers
+    fn get(&self, store: &StoreContext, id: SupplyId) -> Result<Option<Supply>> {
+        let supplies = &self.storage.lock().unwrap().supplies;
+
+        for (store_id, supply) in supplies {
+            if store.includes(store_id) && supply.id().eq(&id) {
+                return Ok(Some(supply.clone()));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn list_of_supplier(&self, supplier_id: SupplierId) -> Result<Vec<Supply>> {
+        let supplies = &self.storage.lock().unwrap().supplies;
+
+        let supplies: Vec<Supply> = supplies
             .iter()
-            .find(|s| s.id().eq(&id))
-            .cloned();
+            .map(|(_, supply)| supply)
+            .filter(|supply| supply.is_offered_by(&supplier_id))
+            .cloned()
+            .collect();
 
-        Ok(supplier)
+        Ok(supplies)
     }
 
-    fn find(&self, query: FindSupplierQuery) -> Result<Vec<Supplier>> {
-        let storage = self.storage.lock().unwrap();
+    fn add(&self, store: &StoreContext, supply: Supply, change: Option<AuditChange>) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
 
-        let supplier_name = query.supplier_name;
+        storage.log_change(store, change)?;
 
-        let supply_name = query.supply_name;
+        storage
+            .supplies
+            .push((store.store_id()?.clone(), supply.clone()));
 
-        let mut suppliers: Vec<&Supplier> = storage.suppliers.iter().collect();
+        Ok(())
+    }
 
-        if let Some(name) = supplier_name {
-            suppliers.retain(|s| s.name().as_str().contains(name.as_str()));
-        }
+    fn save(
+        &self,
+        store: &StoreContext,
+        supply: Supply,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
 
-        if let Some(name) = supply_name {
-            let supplies = storage
-                .supplies
-                .iter()
-                .filter(|supply| supply.name().as_str().contains(name.as_str()))
-                .collect::<Vec<&Supply>>();
+        storage.log_change(store, change)?;
 
-            suppliers.retain(|supplier| {
-                supplies
-                    .iter()
-                    .any(|supply| supply.supplier_id().eq(supplier.id()))
-            });
+        if let Some(index) = storage
+            .supplies
+            .iter()
+            .position(|(store_id, s)| store.includes(store_id) && s.id().eq(supply.id()))
+        {
+            storage.supplies[index].1 = supply;
         }
 
-        let suppliers: Vec<Supplier> = suppliers.into_iter().cloned().collect();
-
-        Ok(suppliers)
+        Ok(())
     }
 
-    fn add(&self, supplier: Supplier) -> Result<()> {
+    fn delete(
+        &self,
+        store: &StoreContext,
+        id: SupplyId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
         let mut storage = self.storage.lock().unwrap();
 
-        if storage.suppliers.iter().any(|s| s.id().eq(supplier.id())) {
-            return Err(Error::InfrastructureError(format!(
-                "supplier already exists."
-            )));
-        }
+        storage.log_change(store, change)?;
+
+        if let Some(index) = storage
+            .supplies
+            .iter()
+            .position(|(store_id, supply)| store.includes(store_id) && supply.id().eq(&id))
+        {
+            let (store_id, item) = storage.supplies.remove(index);
 
-        storage.suppliers.push(supplier.clone());
+            storage
+                .deleted_supplies
+                .push((deleted_now(), store_id, item));
+        }
 
         Ok(())
     }
 
-    fn save(&self, supplier: Supplier) -> Result<()> {
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Supply>>> {
+        let deleted = &self.storage.lock().unwrap().deleted_supplies;
+
+        Ok(deleted
+            .iter()
+            .filter(|(_, store_id, _)| store.includes(store_id))
+            .map(|(deleted_at, _, item)| Trashed::new(item.clone(), *deleted_at))
+            .collect())
+    }
+
+    fn restore(
+        &self,
+        store: &StoreContext,
+        id: SupplyId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
         let mut storage = self.storage.lock().unwrap();
 
+        storage.log_change(store, change)?;
+
         if let Some(index) = storage
-            .suppliers
+            .deleted_supplies
             .iter()
-            .position(|s| s.id().eq(supplier.id()))
+            .position(|(_, store_id, item)| store.includes(store_id) && item.id().eq(&id))
         {
-            storage.suppliers[index] = supplier.clone();
+            let (_, store_id, item) = storage.deleted_supplies.remove(index);
+
+            storage.supplies.push((store_id, item));
         }
 
         Ok(())
     }
 
-    fn delete(&self, id: SupplierId) -> Result<()> {
+    fn purge(&self, store: &StoreContext, id: SupplyId, change: Option<AuditChange>) -> Result<()> {
         let mut storage = self.storage.lock().unwrap();
 
-        if let Some(index) = storage.suppliers.iter().position(|s| s.id().eq(&id)) {
-            storage.suppliers.remove(index);
-        }
+        storage.log_change(store, change)?;
+
+        storage
+            .deleted_supplies
+            .retain(|(_, store_id, item)| !(store.includes(store_id) && item.id().eq(&id)));
 
         Ok(())
     }
 }
 
 #[derive(Debug, Clone, Default)]
-pub struct MockJournalRepository {
+pub struct MockUnitRepository {
     storage: Arc<Mutex<Storage>>,
 }
 
-impl MockJournalRepository {
+impl MockUnitRepository {
     pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
         Self { storage }
     }
 }
 
-impl ForJournalPersistence for MockJournalRepository {
-    fn next_id(&self) -> Result<JournalId> {
-        let id = self.storage.lock().unwrap().journals.len() + 1;
+impl ForUnitPersistence for MockUnitRepository {
+    fn next_id(&self) -> Result<UnitId> {
+        let id = self.storage.lock().unwrap().units.len() + 1;
 
-        let id = JournalId::new(id.to_string())?;
+        let id = UnitId::new(id.to_string())?;
 
         Ok(id)
     }
 
-    fn list(&self) -> Result<Vec<Journal>> {
-        let journals: Vec<Journal> = self
+    fn list(&self) -> Result<Vec<Unit>> {
+        let units = self.storage.lock().unwrap().units.clone();
+
+        Ok(units)
+    }
+
+    fn get(&self, id: UnitId) -> Result<Option<Unit>> {
+        let unit = self
             .storage
             .lock()
             .unwrap()
-            .journals
+            .units
             .iter()
-            .cloned()
-            .collect();
+            .find(|u| u.id().eq(&id))
+            .cloned();
 
-        Ok(journals)
+        Ok(unit)
     }
 
-    fn get(&self, id: JournalId) -> Result<Option<Journal>> {
-        let journal = self
+    fn get_by_name(&self, name: UnitName) -> Result<Option<Unit>> {
+        let unit = self
             .storage
             .lock()
             .unwrap()
-            .journals
+            .units
             .iter()
-            .find(|j| j.id().eq(&id))
+            .find(|u| u.name().eq(&name))
             .cloned();
 
-        Ok(journal)
+        Ok(unit)
     }
 
-    fn find(&self, query: FindJournalsQuery) -> Result<Vec<Journal>> {
-        let storage = self.storage.lock().unwrap();
-
-        let mut journals: Vec<&Journal> = storage.journals.iter().collect();
+    fn add(&self, unit: Unit) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
 
-        if let Some(date) = query.period_start {
-            journals.retain(|j| j.entry_datetime().ge(&date));
+        if storage.units.iter().any(|u| u.id().eq(unit.id())) {
+            return Err(Error::InfrastructureError(format!("unit already exists.")));
         }
 
-        if let Some(date) = query.period_end {
-            journals.retain(|j| j.entry_datetime().le(&date));
-        }
+        storage.units.push(unit);
 
-        if let Some(name) = query.supply_name {
-            journals.retain(|j| {
-                j.records()
-                    .iter()
-                    .any(|r| r.supply_name().as_str().contains(name.as_str()))
-            });
-        }
+        Ok(())
+    }
 
-        if let Some(name) = query.supplier_name {
-            journals.retain(|j| {
-                j.records()
-                    .iter()
-                    .any(|r| r.supplier_name().as_str().contains(name.as_str()))
-            });
-        }
+    fn save(&self, unit: Unit) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
 
-        let journals: Vec<Journal> = journals.into_iter().cloned().collect();
+        if let Some(index) = storage.units.iter().position(|u| u.id().eq(unit.id())) {
+            storage.units[index] = unit;
+        }
 
-        Ok(journals)
+        Ok(())
     }
 
-    fn add(&self, journal: Journal) -> Result<()> {
+    fn delete(&self, id: UnitId) -> Result<()> {
         let mut storage = self.storage.lock().unwrap();
 
-        if storage.journals.iter().any(|j| j.id().eq(journal.id())) {
-            return Err(Error::InfrastructureError(format!(
-                "journal already exists."
-            )));
-        }
+        if let Some(index) = storage.units.iter().position(|u| u.id().eq(&id)) {
+            let item = storage.units.remove(index);
 
-        storage.journals.push(journal.clone());
+            storage.deleted_units.push((deleted_now(), item));
+        }
 
         Ok(())
     }
 
-    fn save(&self, journal: Journal) -> Result<()> {
+    fn list_deleted(&self) -> Result<Vec<Trashed<Unit>>> {
+        let deleted = &self.storage.lock().unwrap().deleted_units;
+
+        Ok(deleted
+            .iter()
+            .map(|(deleted_at, item)| Trashed::new(item.clone(), *deleted_at))
+            .collect())
+    }
+
+    fn restore(&self, id: UnitId) -> Result<()> {
         let mut storage = self.storage.lock().unwrap();
 
         if let Some(index) = storage
-            .journals
+            .deleted_units
             .iter()
-            .position(|j| j.id().eq(journal.id()))
+            .position(|(_, item)| item.id().eq(&id))
         {
-            storage.journals[index] = journal.clone();
+            let (_, item) = storage.deleted_units.remove(index);
+
+            storage.units.push(item);
         }
 
         Ok(())
     }
 
-    fn delete(&self, id: JournalId) -> Result<()> {
+    fn purge(&self, id: UnitId) -> Result<()> {
         let mut storage = self.storage.lock().unwrap();
 
-        if let Some(index) = storage.journals.iter().position(|j| j.id().eq(&id)) {
-            storage.journals.remove(index);
-        }
+        storage.deleted_units.retain(|(_, item)| !item.id().eq(&id));
 
         Ok(())
     }
 }
 
 #[derive(Debug, Clone, Default)]
-pub struct MockStocktakingRepository {
+pub struct MockCategoryRepository {
     storage: Arc<Mutex<Storage>>,
 }
 
-impl MockStocktakingRepository {
+impl MockCategoryRepository {
     pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
         Self { storage }
     }
 }
 
-impl ForStocktakingPersistence for MockStocktakingRepository {
-    fn next_id(&self) -> Result<StocktakingId> {
-        let id = self.storage.lock().unwrap().stocktakings.len() + 1;
+impl ForCategoryPersistence for MockCategoryRepository {
+    fn next_id(&self) -> Result<CategoryId> {
+        let id = self.storage.lock().unwrap().categories.len() + 1;
 
-        let id = StocktakingId::new(id.to_string())?;
+        let id = CategoryId::new(id.to_string())?;
 
         Ok(id)
     }
 
-    fn list(&self) -> Result<Vec<Stocktaking>> {
-        let stocktakings: Vec<Stocktaking> = self
-            .storage
-            .lock()
-            .unwrap()
-            .stocktakings
-            .iter()
-            .cloned()
-            .collect();
-
-        Ok(stocktakings)
-    }
-
-    fn find(&self, query: FindStocktakingsQuery) -> Result<Vec<Stocktaking>> {
-        let storage = self.storage.lock().unwrap();
-
-        let mut stocktakings: Vec<&Stocktaking> = storage.stocktakings.iter().collect();
-
-        if let Some(date) = query.period_start {
-            stocktakings.retain(|s| s.stocktaken_at().ge(&date));
-        }
-
-        if let Some(date) = query.period_end {
-            stocktakings.retain(|s| s.stocktaken_at().le(&date));
-        }
-
-        let stocktakings: Vec<Stocktaking> = stocktakings.into_iter().cloned().collect();
+    fn list(&self) -> Result<Vec<Category>> {
+        let categories = self.storage.lock().unwrap().categories.clone();
 
-        Ok(stocktakings)
+        Ok(categories)
     }
 
-    fn get(&self, id: StocktakingId) -> Result<Option<Stocktaking>> {
-        let stocktaking = self
+    fn get(&self, id: CategoryId) -> Result<Option<Category>> {
+        let category = self
             .storage
             .lock()
             .unwrap()
-            .stocktakings
+            .categories
             .iter()
-            .find(|s| s.id().eq(&id))
+            .find(|c| c.id().eq(&id))
             .cloned();
 
-        Ok(stocktaking)
+        Ok(category)
     }
 
-    fn add(&self, stockatking: Stocktaking) -> Result<()> {
+    fn add(&self, category: Category) -> Result<()> {
         let mut storage = self.storage.lock().unwrap();
 
-        if storage
-            .stocktakings
-            .iter()
-            .any(|s| s.id().eq(stockatking.id()))
-        {
+        if storage.categories.iter().any(|c| c.id().eq(category.id())) {
             return Err(Error::InfrastructureError(format!(
-                "stocktaking already exists."
+                "category already exists."
             )));
         }
 
-        storage.stocktakings.push(stockatking.clone());
+        storage.categories.push(category);
 
         Ok(())
     }
 
-    fn save(&self, stocktaking: Stocktaking) -> Result<()> {
+    fn save(&self, category: Category) -> Result<()> {
         let mut storage = self.storage.lock().unwrap();
 
         if let Some(index) = storage
-            .stocktakings
+            .categories
             .iter()
-            .position(|s| s.id().eq(stocktaking.id()))
+            .position(|c| c.id().eq(category.id()))
         {
-            storage.stocktakings[index] = stocktaking.clone();
+            storage.categories[index] = category;
         }
 
         Ok(())
     }
 
-    fn delete(&self, id: StocktakingId) -> Result<()> {
+    fn delete(&self, id: CategoryId) -> Result<()> {
         let mut storage = self.storage.lock().unwrap();
 
-        if let Some(index) = storage.stocktakings.iter().position(|s| s.id().eq(&id)) {
-            storage.stocktakings.remove(index);
+        if let Some(index) = storage.categories.iter().position(|c| c.id().eq(&id)) {
+            let item = storage.categories.remove(index);
+
+            storage.deleted_categories.push((deleted_now(), item));
+        }
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<Trashed<Category>>> {
+        let deleted = &self.storage.lock().unwrap().deleted_categories;
+
+        Ok(deleted
+            .iter()
+            .map(|(deleted_at, item)| Trashed::new(item.clone(), *deleted_at))
+            .collect())
+    }
+
+    fn restore(&self, id: CategoryId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .deleted_categories
+            .iter()
+            .position(|(_, item)| item.id().eq(&id))
+        {
+            let (_, item) = storage.deleted_categories.remove(index);
+
+            storage.categories.push(item);
+        }
+
+        Ok(())
+    }
+
+    fn purge(&self, id: CategoryId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage
+            .deleted_categories
+            .retain(|(_, item)| !item.id().eq(&id));
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockStorageLocationRepository {
+    storage: Arc<Mutex<Storage>>,
+}
+
+impl MockStorageLocationRepository {
+    pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
+        Self { storage }
+    }
+}
+
+impl ForStorageLocationPersistence for MockStorageLocationRepository {
+    fn next_id(&self) -> Result<StorageLocationId> {
+        let id = self.storage.lock().unwrap().locations.len() + 1;
+
+        let id = StorageLocationId::new(id.to_string())?;
+
+        Ok(id)
+    }
+
+    fn list(&self) -> Result<Vec<StorageLocation>> {
+        let locations = self.storage.lock().unwrap().locations.clone();
+
+        Ok(locations)
+    }
+
+    fn get(&self, id: StorageLocationId) -> Result<Option<StorageLocation>> {
+        let location = self
+            .storage
+            .lock()
+            .unwrap()
+            .locations
+            .iter()
+            .find(|l| l.id().eq(&id))
+            .cloned();
+
+        Ok(location)
+    }
+
+    fn add(&self, location: StorageLocation) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if storage.locations.iter().any(|l| l.id().eq(location.id())) {
+            return Err(Error::InfrastructureError(format!(
+                "storage location already exists."
+            )));
+        }
+
+        storage.locations.push(location);
+
+        Ok(())
+    }
+
+    fn save(&self, location: StorageLocation) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .locations
+            .iter()
+            .position(|l| l.id().eq(location.id()))
+        {
+            storage.locations[index] = location;
+        }
+
+        Ok(())
+    }
+
+    fn delete(&self, id: StorageLocationId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage.locations.iter().position(|l| l.id().eq(&id)) {
+            let item = storage.locations.remove(index);
+
+            storage.deleted_locations.push((deleted_now(), item));
+        }
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<Trashed<StorageLocation>>> {
+        let deleted = &self.storage.lock().unwrap().deleted_locations;
+
+        Ok(deleted
+            .iter()
+            .map(|(deleted_at, item)| Trashed::new(item.clone(), *deleted_at))
+            .collect())
+    }
+
+    fn restore(&self, id: StorageLocationId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .deleted_locations
+            .iter()
+            .position(|(_, item)| item.id().eq(&id))
+        {
+            let (_, item) = storage.deleted_locations.remove(index);
+
+            storage.locations.push(item);
+        }
+
+        Ok(())
+    }
+
+    fn purge(&self, id: StorageLocationId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage
+            .deleted_locations
+            .retain(|(_, item)| !item.id().eq(&id));
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockSupplierRepository {
+    storage: Arc<Mutex<Storage>>,
+}
+
+impl MockSupplierRepository {
+    pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
+        Self { storage }
+    }
+}
+
+impl ForSupplierPersistence for MockSupplierRepository {
+    fn next_id(&self) -> Result<SupplierId> {
+        let id = self.storage.lock().unwrap().suppliers.len() + 1;
+
+        let id = SupplierId::new(id.to_string())?;
+
+        Ok(id)
+    }
+
+    fn has(&self, store: &StoreContext, supplier_ids: &[SupplierId]) -> Result<bool> {
+        let suppliers = &self.storage.lock().unwrap().suppliers;
+
+        for id in supplier_ids {
+            if !suppliers
+                .iter()
+                .any(|(store_id, s)| store.includes(store_id) && s.id().eq(id))
+            {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<Supplier>> {
+        let suppliers = &self.storage.lock().unwrap().suppliers;
+
+        Ok(suppliers
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, supplier)| supplier.clone())
+            .collect())
+    }
+
+    fn get(&self, store: &StoreContext, id: SupplierId) -> Result<Option<Supplier>> {
+        let supplier = self
+            .storage
+            .lock()
+            .unwrap()
+            .suppliers
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, supplier)| supplier)
+            .find(|s| s.id().eq(&id))
+            .cloned();
+
+        Ok(supplier)
+    }
+
+    fn find(&self, store: &StoreContext, query: FindSupplierQuery) -> Result<Vec<Supplier>> {
+        let storage = self.storage.lock().unwrap();
+
+        let supplier_name = query.supplier_name;
+
+        let supply_name = query.supply_name;
+
+        let mut suppliers: Vec<&Supplier> = storage
+            .suppliers
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, supplier)| supplier)
+            .collect();
+
+        if let Some(name) = supplier_name {
+            suppliers.retain(|s| s.name().as_str().contains(name.as_str()));
+        }
+
+        if let Some(person) = query.contact_person {
+            suppliers.retain(|s| {
+                s.contact()
+                    .person()
+                    .is_some_and(|p| p.as_str().contains(person.as_str()))
+            });
+        }
+
+        if let Some(digits) = query.phone_digits {
+            suppliers.retain(|s| {
+                [s.contact().phone_number(), s.contact().fax_number()]
+                    .into_iter()
+                    .flatten()
+                    .any(|number| number.digits().contains(&digits))
+            });
+        }
+
+        if let Some(name) = supply_name {
+            let supplies = storage
+                .supplies
+                .iter()
+                .map(|(_, supply)| supply)
+                .filter(|supply| supply.name().as_str().contains(name.as_str()))
+                .collect::<Vec<&Supply>>();
+
+            suppliers.retain(|supplier| {
+                supplies
+                    .iter()
+                    .any(|supply| supply.is_offered_by(supplier.id()))
+            });
+        }
+
+        let suppliers: Vec<Supplier> = suppliers.into_iter().cloned().collect();
+
+        Ok(suppliers)
+    }
+
+    fn add(
+        &self,
+        store: &StoreContext,
+        supplier: Supplier,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        if storage
+            .suppliers
+            .iter()
+            .any(|(_, s)| s.id().eq(supplier.id()))
+        {
+            return Err(Error::InfrastructureError(format!(
+                "supplier already exists."
+            )));
+        }
+
+        storage
+            .suppliers
+            .push((store.store_id()?.clone(), supplier.clone()));
+
+        Ok(())
+    }
+
+    fn save(
+        &self,
+        store: &StoreContext,
+        supplier: Supplier,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        if let Some(index) = storage
+            .suppliers
+            .iter()
+            .position(|(store_id, s)| store.includes(store_id) && s.id().eq(supplier.id()))
+        {
+            storage.suppliers[index].1 = supplier.clone();
+        }
+
+        Ok(())
+    }
+
+    fn delete(
+        &self,
+        store: &StoreContext,
+        id: SupplierId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        if let Some(index) = storage
+            .suppliers
+            .iter()
+            .position(|(store_id, s)| store.includes(store_id) && s.id().eq(&id))
+        {
+            let (store_id, item) = storage.suppliers.remove(index);
+
+            storage
+                .deleted_suppliers
+                .push((deleted_now(), store_id, item));
+        }
+
+        Ok(())
+    }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Supplier>>> {
+        let deleted = &self.storage.lock().unwrap().deleted_suppliers;
+
+        Ok(deleted
+            .iter()
+            .filter(|(_, store_id, _)| store.includes(store_id))
+            .map(|(deleted_at, _, item)| Trashed::new(item.clone(), *deleted_at))
+            .collect())
+    }
+
+    fn restore(
+        &self,
+        store: &StoreContext,
+        id: SupplierId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        if let Some(index) = storage
+            .deleted_suppliers
+            .iter()
+            .position(|(_, store_id, item)| store.includes(store_id) && item.id().eq(&id))
+        {
+            let (_, store_id, item) = storage.deleted_suppliers.remove(index);
+
+            storage.suppliers.push((store_id, item));
+        }
+
+        Ok(())
+    }
+
+    fn purge(
+        &self,
+        store: &StoreContext,
+        id: SupplierId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        storage
+            .deleted_suppliers
+            .retain(|(_, store_id, item)| !(store.includes(store_id) && item.id().eq(&id)));
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockJournalRepository {
+    storage: Arc<Mutex<Storage>>,
+}
+
+impl MockJournalRepository {
+    pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
+        Self { storage }
+    }
+}
+
+impl ForJournalPersistence for MockJournalRepository {
+    fn next_id(&self) -> Result<JournalId> {
+        let id = self.storage.lock().unwrap().journals.len() + 1;
+
+        let id = JournalId::new(id.to_string())?;
+
+        Ok(id)
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<Journal>> {
+        let journals: Vec<Journal> = self
+            .storage
+            .lock()
+            .unwrap()
+            .journals
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, journal)| journal.clone())
+            .collect();
+
+        Ok(journals)
+    }
+
+    fn get(&self, store: &StoreContext, id: JournalId) -> Result<Option<Journal>> {
+        let journal = self
+            .storage
+            .lock()
+            .unwrap()
+            .journals
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, journal)| journal)
+            .find(|j| j.id().eq(&id))
+            .cloned();
+
+        Ok(journal)
+    }
+
+    fn find(&self, store: &StoreContext, query: FindJournalsQuery) -> Result<Vec<Journal>> {
+        let storage = self.storage.lock().unwrap();
+
+        let mut journals: Vec<&Journal> = storage
+            .journals
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, journal)| journal)
+            .collect();
+
+        if let Some(date) = query.period_start {
+            journals.retain(|j| j.entry_datetime().ge(&date));
+        }
+
+        if let Some(date) = query.period_end {
+            journals.retain(|j| j.entry_datetime().le(&date));
+        }
+
+        if let Some(name) = query.supply_name {
+            journals.retain(|j| {
+                j.records()
+                    .iter()
+                    .any(|r| r.supply_name().as_str().contains(name.as_str()))
+            });
+        }
+
+        if let Some(name) = query.supplier_name {
+            journals.retain(|j| {
+                j.records()
+                    .iter()
+                    .any(|r| r.supplier_name().as_str().contains(name.as_str()))
+            });
+        }
+
+        let journals: Vec<Journal> = journals.into_iter().cloned().collect();
+
+        Ok(journals)
+    }
+
+    fn add(
+        &self,
+        store: &StoreContext,
+        journal: Journal,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        if storage
+            .journals
+            .iter()
+            .any(|(_, j)| j.id().eq(journal.id()))
+        {
+            return Err(Error::InfrastructureError(format!(
+                "journal already exists."
+            )));
+        }
+
+        storage
+            .journals
+            .push((store.store_id()?.clone(), journal.clone()));
+
+        Ok(())
+    }
+
+    fn save(
+        &self,
+        store: &StoreContext,
+        journal: Journal,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        if let Some(index) = storage
+            .journals
+            .iter()
+            .position(|(store_id, j)| store.includes(store_id) && j.id().eq(journal.id()))
+        {
+            storage.journals[index].1 = journal.clone();
+        }
+
+        Ok(())
+    }
+
+    fn delete(
+        &self,
+        store: &StoreContext,
+        id: JournalId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        if let Some(index) = storage
+            .journals
+            .iter()
+            .position(|(store_id, j)| store.includes(store_id) && j.id().eq(&id))
+        {
+            let (store_id, item) = storage.journals.remove(index);
+
+            storage
+                .deleted_journals
+                .push((deleted_now(), store_id, item));
+        }
+
+        Ok(())
+    }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Journal>>> {
+        let deleted = &self.storage.lock().unwrap().deleted_journals;
+
+        Ok(deleted
+            .iter()
+            .filter(|(_, store_id, _)| store.includes(store_id))
+            .map(|(deleted_at, _, item)| Trashed::new(item.clone(), *deleted_at))
+            .collect())
+    }
+
+    fn restore(
+        &self,
+        store: &StoreContext,
+        id: JournalId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        if let Some(index) = storage
+            .deleted_journals
+            .iter()
+            .position(|(_, store_id, item)| store.includes(store_id) && item.id().eq(&id))
+        {
+            let (_, store_id, item) = storage.deleted_journals.remove(index);
+
+            storage.journals.push((store_id, item));
+        }
+
+        Ok(())
+    }
+
+    fn purge(
+        &self,
+        store: &StoreContext,
+        id: JournalId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        let count = storage.deleted_journals.len();
+
+        storage
+            .deleted_journals
+            .retain(|(_, store_id, item)| !(store.includes(store_id) && item.id().eq(&id)));
+
+        if storage.deleted_journals.len() < count {
+            storage
+                .journal_revisions
+                .retain(|revision| !revision.item().id().eq(&id));
+        }
+
+        Ok(())
+    }
+
+    fn add_revision(&self, journal: Journal) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        let count = storage
+            .journal_revisions
+            .iter()
+            .filter(|revision| revision.item().id().eq(journal.id()))
+            .count();
+
+        let number = RevisionNumber::new(count as u32 + 1)?;
+
+        storage
+            .journal_revisions
+            .push(Revision::new(number, revised_now(), journal));
+
+        Ok(())
+    }
+
+    fn list_revisions(
+        &self,
+        store: &StoreContext,
+        id: JournalId,
+    ) -> Result<Vec<Revision<Journal>>> {
+        let storage = self.storage.lock().unwrap();
+
+        let of_store = storage
+            .journals
+            .iter()
+            .map(|(store_id, item)| (store_id, item))
+            .chain(
+                storage
+                    .deleted_journals
+                    .iter()
+                    .map(|(_, store_id, item)| (store_id, item)),
+            )
+            .any(|(store_id, item)| store.includes(store_id) && item.id().eq(&id));
+
+        if !of_store {
+            return Ok(Vec::new());
+        }
+
+        Ok(storage
+            .journal_revisions
+            .iter()
+            .filter(|revision| revision.item().id().eq(&id))
+            .cloned()
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockStocktakingRepository {
+    storage: Arc<Mutex<Storage>>,
+}
+
+impl MockStocktakingRepository {
+    pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
+        Self { storage }
+    }
+}
+
+impl ForStocktakingPersistence for MockStocktakingRepository {
+    fn next_id(&self) -> Result<StocktakingId> {
+        let id = self.storage.lock().unwrap().stocktakings.len() + 1;
+
+        let id = StocktakingId::new(id.to_string())?;
+
+        Ok(id)
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<Stocktaking>> {
+        let stocktakings: Vec<Stocktaking> = self
+            .storage
+            .lock()
+            .unwrap()
+            .stocktakings
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, stocktaking)| stocktaking.clone())
+            .collect();
+
+        Ok(stocktakings)
+    }
+
+    fn find(&self, store: &StoreContext, query: FindStocktakingsQuery) -> Result<Vec<Stocktaking>> {
+        let storage = self.storage.lock().unwrap();
+
+        let mut stocktakings: Vec<&Stocktaking> = storage
+            .stocktakings
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, stocktaking)| stocktaking)
+            .collect();
+
+        if let Some(date) = query.period_start {
+            stocktakings.retain(|s| s.stocktaken_at().ge(&date));
+        }
+
+        if let Some(date) = query.period_end {
+            stocktakings.retain(|s| s.stocktaken_at().le(&date));
+        }
+
+        let stocktakings: Vec<Stocktaking> = stocktakings.into_iter().cloned().collect();
+
+        Ok(stocktakings)
+    }
+
+    fn get(&self, store: &StoreContext, id: StocktakingId) -> Result<Option<Stocktaking>> {
+        let stocktaking = self
+            .storage
+            .lock()
+            .unwrap()
+            .stocktakings
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, stocktaking)| stocktaking)
+            .find(|s| s.id().eq(&id))
+            .cloned();
+
+        Ok(stocktaking)
+    }
+
+    fn add(
+        &self,
+        store: &StoreContext,
+        stockatking: Stocktaking,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        if storage
+            .stocktakings
+            .iter()
+            .any(|(_, s)| s.id().eq(stockatking.id()))
+        {
+            return Err(Error::InfrastructureError(format!(
+                "stocktaking already exists."
+            )));
+        }
+
+        storage
+            .stocktakings
+            .push((store.store_id()?.clone(), stockatking.clone()));
+
+        Ok(())
+    }
+
+    fn save(
+        &self,
+        store: &StoreContext,
+        stocktaking: Stocktaking,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        if let Some(index) = storage
+            .stocktakings
+            .iter()
+            .position(|(store_id, s)| store.includes(store_id) && s.id().eq(stocktaking.id()))
+        {
+            storage.stocktakings[index].1 = stocktaking.clone();
+        }
+
+        Ok(())
+    }
+
+    fn delete(
+        &self,
+        store: &StoreContext,
+        id: StocktakingId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        if let Some(index) = storage
+            .stocktakings
+            .iter()
+            .position(|(store_id, s)| store.includes(store_id) && s.id().eq(&id))
+        {
+            let (store_id, item) = storage.stocktakings.remove(index);
+
+            storage
+                .deleted_stocktakings
+                .push((deleted_now(), store_id, item));
+        }
+
+        Ok(())
+    }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Stocktaking>>> {
+        let deleted = &self.storage.lock().unwrap().deleted_stocktakings;
+
+        Ok(deleted
+            .iter()
+            .filter(|(_, store_id, _)| store.includes(store_id))
+            .map(|(deleted_at, _, item)| Trashed::new(item.clone(), *deleted_at))
+            .collect())
+    }
+
+    fn restore(
+        &self,
+        store: &StoreContext,
+        id: StocktakingId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        if let Some(index) = storage
+            .deleted_stocktakings
+            .iter()
+            .position(|(_, store_id, item)| store.includes(store_id) && item.id().eq(&id))
+        {
+            let (_, store_id, item) = storage.deleted_stocktakings.remove(index);
+
+            storage.stocktakings.push((store_id, item));
+        }
+
+        Ok(())
+    }
+
+    fn purge(
+        &self,
+        store: &StoreContext,
+        id: StocktakingId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.log_change(store, change)?;
+
+        let count = storage.deleted_stocktakings.len();
+
+        storage
+            .deleted_stocktakings
+            .retain(|(_, store_id, item)| !(store.includes(store_id) && item.id().eq(&id)));
+
+        if storage.deleted_stocktakings.len() < count {
+            storage
+                .stocktaking_revisions
+                .retain(|revision| !revision.item().id().eq(&id));
+        }
+
+        Ok(())
+    }
+
+    fn add_revision(&self, stocktaking: Stocktaking) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        let count = storage
+            .stocktaking_revisions
+            .iter()
+            .filter(|revision| revision.item().id().eq(stocktaking.id()))
+            .count();
+
+        let number = RevisionNumber::new(count as u32 + 1)?;
+
+        storage
+            .stocktaking_revisions
+            .push(Revision::new(number, revised_now(), stocktaking));
+
+        Ok(())
+    }
+
+    fn list_revisions(
+        &self,
+        store: &StoreContext,
+        id: StocktakingId,
+    ) -> Result<Vec<Revision<Stocktaking>>> {
+        let storage = self.storage.lock().unwrap();
+
+        let of_store = storage
+            .stocktakings
+            .iter()
+            .map(|(store_id, item)| (store_id, item))
+            .chain(
+                storage
+                    .deleted_stocktakings
+                    .iter()
+                    .map(|(_, store_id, item)| (store_id, item)),
+            )
+            .any(|(store_id, item)| store.includes(store_id) && item.id().eq(&id));
+
+        if !of_store {
+            return Ok(Vec::new());
+        }
+
+        Ok(storage
+            .stocktaking_revisions
+            .iter()
+            .filter(|revision| revision.item().id().eq(&id))
+            .cloned()
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockPurchaseOrderRepository {
+    storage: Arc<Mutex<Storage>>,
+}
+
+impl MockPurchaseOrderRepository {
+    pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
+        Self { storage }
+    }
+}
+
+impl ForPurchaseOrderPersistence for MockPurchaseOrderRepository {
+    fn next_id(&self) -> Result<PurchaseOrderId> {
+        let id = self.storage.lock().unwrap().purchase_orders.len() + 1;
+
+        let id = PurchaseOrderId::new(id.to_string())?;
+
+        Ok(id)
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<PurchaseOrder>> {
+        let orders: Vec<PurchaseOrder> = self
+            .storage
+            .lock()
+            .unwrap()
+            .purchase_orders
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, order)| order.clone())
+            .collect();
+
+        Ok(orders)
+    }
+
+    fn find(
+        &self,
+        store: &StoreContext,
+        query: FindPurchaseOrdersQuery,
+    ) -> Result<Vec<PurchaseOrder>> {
+        let storage = self.storage.lock().unwrap();
+
+        let mut orders: Vec<&PurchaseOrder> = storage
+            .purchase_orders
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, order)| order)
+            .collect();
+
+        if let Some(date) = query.period_start {
+            orders.retain(|o| o.ordered_at().ge(&date));
+        }
+
+        if let Some(date) = query.period_end {
+            orders.retain(|o| o.ordered_at().le(&date));
+        }
+
+        if let Some(supplier_id) = query.supplier_id {
+            orders.retain(|o| o.supplier_id().eq(&supplier_id));
+        }
+
+        if let Some(status) = query.status {
+            orders.retain(|o| o.status().eq(&status));
+        }
+
+        let orders: Vec<PurchaseOrder> = orders.into_iter().cloned().collect();
+
+        Ok(orders)
+    }
+
+    fn get(&self, store: &StoreContext, id: PurchaseOrderId) -> Result<Option<PurchaseOrder>> {
+        let order = self
+            .storage
+            .lock()
+            .unwrap()
+            .purchase_orders
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, order)| order)
+            .find(|o| o.id().eq(&id))
+            .cloned();
+
+        Ok(order)
+    }
+
+    fn add(&self, store: &StoreContext, order: PurchaseOrder) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if storage
+            .purchase_orders
+            .iter()
+            .any(|(_, o)| o.id().eq(order.id()))
+        {
+            return Err(Error::InfrastructureError(format!(
+                "purchase order already exists."
+            )));
+        }
+
+        storage
+            .purchase_orders
+            .push((store.store_id()?.clone(), order));
+
+        Ok(())
+    }
+
+    fn save(&self, store: &StoreContext, order: PurchaseOrder) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .purchase_orders
+            .iter()
+            .position(|(store_id, o)| store.includes(store_id) && o.id().eq(order.id()))
+        {
+            storage.purchase_orders[index].1 = order;
+        }
+
+        Ok(())
+    }
+
+    fn delete(&self, store: &StoreContext, id: PurchaseOrderId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .purchase_orders
+            .iter()
+            .position(|(store_id, o)| store.includes(store_id) && o.id().eq(&id))
+        {
+            let (store_id, item) = storage.purchase_orders.remove(index);
+
+            storage
+                .deleted_purchase_orders
+                .push((deleted_now(), store_id, item));
+        }
+
+        Ok(())
+    }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<PurchaseOrder>>> {
+        let deleted = &self.storage.lock().unwrap().deleted_purchase_orders;
+
+        Ok(deleted
+            .iter()
+            .filter(|(_, store_id, _)| store.includes(store_id))
+            .map(|(deleted_at, _, item)| Trashed::new(item.clone(), *deleted_at))
+            .collect())
+    }
+
+    fn restore(&self, store: &StoreContext, id: PurchaseOrderId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .deleted_purchase_orders
+            .iter()
+            .position(|(_, store_id, item)| store.includes(store_id) && item.id().eq(&id))
+        {
+            let (_, store_id, item) = storage.deleted_purchase_orders.remove(index);
+
+            storage.purchase_orders.push((store_id, item));
+        }
+
+        Ok(())
+    }
+
+    fn purge(&self, store: &StoreContext, id: PurchaseOrderId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage
+            .deleted_purchase_orders
+            .retain(|(_, store_id, item)| !(store.includes(store_id) && item.id().eq(&id)));
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockTransferRepository {
+    storage: Arc<Mutex<Storage>>,
+}
+
+impl MockTransferRepository {
+    pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
+        Self { storage }
+    }
+}
+
+/// Whether the transfer moves stock from or to the stores.
+fn involves(store: &StoreContext, transfer: &Transfer) -> bool {
+    store.includes(transfer.source_store_id()) || store.includes(transfer.destination_store_id())
+}
+
+impl ForTransferPersistence for MockTransferRepository {
+    fn next_id(&self) -> Result<TransferId> {
+        let id = self.storage.lock().unwrap().transfers.len() + 1;
+
+        let id = TransferId::new(id.to_string())?;
+
+        Ok(id)
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<Transfer>> {
+        let transfers: Vec<Transfer> = self
+            .storage
+            .lock()
+            .unwrap()
+            .transfers
+            .iter()
+            .filter(|t| involves(store, t))
+            .cloned()
+            .collect();
+
+        Ok(transfers)
+    }
+
+    fn find(&self, store: &StoreContext, query: FindTransfersQuery) -> Result<Vec<Transfer>> {
+        let mut transfers = self.list(store)?;
+
+        if let Some(date) = query.period_start {
+            transfers.retain(|t| t.transferred_at().ge(&date));
+        }
+
+        if let Some(date) = query.period_end {
+            transfers.retain(|t| t.transferred_at().le(&date));
+        }
+
+        Ok(transfers)
+    }
+
+    fn get(&self, store: &StoreContext, id: TransferId) -> Result<Option<Transfer>> {
+        let transfer = self.list(store)?.into_iter().find(|t| t.id().eq(&id));
+
+        Ok(transfer)
+    }
+
+    fn add(&self, transfer: Transfer) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if storage.transfers.iter().any(|t| t.id().eq(transfer.id())) {
+            return Err(Error::InfrastructureError(format!(
+                "transfer already exists."
+            )));
+        }
+
+        storage.transfers.push(transfer);
+
+        Ok(())
+    }
+
+    fn save(&self, store: &StoreContext, transfer: Transfer) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .transfers
+            .iter()
+            .position(|t| involves(store, t) && t.id().eq(transfer.id()))
+        {
+            storage.transfers[index] = transfer;
+        }
+
+        Ok(())
+    }
+
+    fn delete(&self, store: &StoreContext, id: TransferId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .transfers
+            .iter()
+            .position(|t| involves(store, t) && t.id().eq(&id))
+        {
+            let item = storage.transfers.remove(index);
+
+            storage.deleted_transfers.push((deleted_now(), item));
+        }
+
+        Ok(())
+    }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Transfer>>> {
+        let deleted = &self.storage.lock().unwrap().deleted_transfers;
+
+        Ok(deleted
+            .iter()
+            .filter(|(_, t)| involves(store, t))
+            .map(|(deleted_at, item)| Trashed::new(item.clone(), *deleted_at))
+            .collect())
+    }
+
+    fn restore(&self, store: &StoreContext, id: TransferId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .deleted_transfers
+            .iter()
+            .position(|(_, item)| involves(store, item) && item.id().eq(&id))
+        {
+            let (_, item) = storage.deleted_transfers.remove(index);
+
+            storage.transfers.push(item);
+        }
+
+        Ok(())
+    }
+
+    fn purge(&self, store: &StoreContext, id: TransferId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage
+            .deleted_transfers
+            .retain(|(_, item)| !(involves(store, item) && item.id().eq(&id)));
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockSupplierInvoiceRepository {
+    storage: Arc<Mutex<Storage>>,
+}
+
+impl MockSupplierInvoiceRepository {
+    pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
+        Self { storage }
+    }
+}
+
+impl ForSupplierInvoicePersistence for MockSupplierInvoiceRepository {
+    fn next_id(&self) -> Result<SupplierInvoiceId> {
+        let id = self.storage.lock().unwrap().invoices.len() + 1;
+
+        let id = SupplierInvoiceId::new(id.to_string())?;
+
+        Ok(id)
+    }
+
+    fn next_payment_id(&self) -> Result<PaymentId> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage.payment_sequence += 1;
+
+        let id = PaymentId::new(storage.payment_sequence.to_string())?;
+
+        Ok(id)
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<SupplierInvoice>> {
+        let invoices: Vec<SupplierInvoice> = self
+            .storage
+            .lock()
+            .unwrap()
+            .invoices
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, invoice)| invoice.clone())
+            .collect();
+
+        Ok(invoices)
+    }
+
+    fn find(
+        &self,
+        store: &StoreContext,
+        query: FindSupplierInvoicesQuery,
+    ) -> Result<Vec<SupplierInvoice>> {
+        let mut invoices = self.list(store)?;
+
+        if let Some(supplier_id) = query.supplier_id {
+            invoices.retain(|i| i.supplier_id().eq(&supplier_id));
+        }
+
+        if let Some(date) = query.period_start {
+            invoices.retain(|i| i.period().end() >= date);
+        }
+
+        if let Some(date) = query.period_end {
+            invoices.retain(|i| i.period().start() <= date);
+        }
+
+        Ok(invoices)
+    }
+
+    fn get(&self, store: &StoreContext, id: SupplierInvoiceId) -> Result<Option<SupplierInvoice>> {
+        let invoice = self
+            .storage
+            .lock()
+            .unwrap()
+            .invoices
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, invoice)| invoice)
+            .find(|i| i.id().eq(&id))
+            .cloned();
+
+        Ok(invoice)
+    }
+
+    fn add(&self, store: &StoreContext, invoice: SupplierInvoice) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if storage
+            .invoices
+            .iter()
+            .any(|(_, i)| i.id().eq(invoice.id()))
+        {
+            return Err(Error::InfrastructureError(format!(
+                "supplier invoice already exists."
+            )));
+        }
+
+        storage.invoices.push((store.store_id()?.clone(), invoice));
+
+        Ok(())
+    }
+
+    fn save(&self, store: &StoreContext, invoice: SupplierInvoice) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .invoices
+            .iter()
+            .position(|(store_id, i)| store.includes(store_id) && i.id().eq(invoice.id()))
+        {
+            storage.invoices[index].1 = invoice;
+        }
+
+        Ok(())
+    }
+
+    fn delete(&self, store: &StoreContext, id: SupplierInvoiceId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .invoices
+            .iter()
+            .position(|(store_id, i)| store.includes(store_id) && i.id().eq(&id))
+        {
+            let (store_id, item) = storage.invoices.remove(index);
+
+            storage
+                .deleted_invoices
+                .push((deleted_now(), store_id, item));
+        }
+
+        Ok(())
+    }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<SupplierInvoice>>> {
+        let deleted = &self.storage.lock().unwrap().deleted_invoices;
+
+        Ok(deleted
+            .iter()
+            .filter(|(_, store_id, _)| store.includes(store_id))
+            .map(|(deleted_at, _, item)| Trashed::new(item.clone(), *deleted_at))
+            .collect())
+    }
+
+    fn restore(&self, store: &StoreContext, id: SupplierInvoiceId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .deleted_invoices
+            .iter()
+            .position(|(_, store_id, item)| store.includes(store_id) && item.id().eq(&id))
+        {
+            let (_, store_id, item) = storage.deleted_invoices.remove(index);
+
+            storage.invoices.push((store_id, item));
+        }
+
+        Ok(())
+    }
+
+    fn purge(&self, store: &StoreContext, id: SupplierInvoiceId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage
+            .deleted_invoices
+            .retain(|(_, store_id, item)| !(store.includes(store_id) && item.id().eq(&id)));
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockConsumptionRepository {
+    storage: Arc<Mutex<Storage>>,
+}
+
+impl MockConsumptionRepository {
+    pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
+        Self { storage }
+    }
+}
+
+impl ForConsumptionPersistence for MockConsumptionRepository {
+    fn next_id(&self) -> Result<ConsumptionId> {
+        let id = self.storage.lock().unwrap().consumptions.len() + 1;
+
+        let id = ConsumptionId::new(id.to_string())?;
+
+        Ok(id)
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<Consumption>> {
+        let consumptions: Vec<Consumption> = self
+            .storage
+            .lock()
+            .unwrap()
+            .consumptions
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, consumption)| consumption.clone())
+            .collect();
+
+        Ok(consumptions)
+    }
+
+    fn find(&self, store: &StoreContext, query: FindConsumptionsQuery) -> Result<Vec<Consumption>> {
+        let storage = self.storage.lock().unwrap();
+
+        let mut consumptions: Vec<&Consumption> = storage
+            .consumptions
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, consumption)| consumption)
+            .collect();
+
+        if let Some(date) = query.period_start {
+            consumptions.retain(|c| c.consumed_at().ge(&date));
+        }
+
+        if let Some(date) = query.period_end {
+            consumptions.retain(|c| c.consumed_at().le(&date));
+        }
+
+        let consumptions: Vec<Consumption> = consumptions.into_iter().cloned().collect();
+
+        Ok(consumptions)
+    }
+
+    fn add(&self, store: &StoreContext, consumption: Consumption) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if storage
+            .consumptions
+            .iter()
+            .any(|(_, c)| c.id().eq(consumption.id()))
+        {
+            return Err(Error::InfrastructureError(format!(
+                "consumption already exists."
+            )));
+        }
+
+        storage
+            .consumptions
+            .push((store.store_id()?.clone(), consumption));
+
+        Ok(())
+    }
+
+    fn delete(&self, store: &StoreContext, id: ConsumptionId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .consumptions
+            .iter()
+            .position(|(store_id, c)| store.includes(store_id) && c.id().eq(&id))
+        {
+            let (store_id, item) = storage.consumptions.remove(index);
+
+            storage
+                .deleted_consumptions
+                .push((deleted_now(), store_id, item));
+        }
+
+        Ok(())
+    }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Consumption>>> {
+        let deleted = &self.storage.lock().unwrap().deleted_consumptions;
+
+        Ok(deleted
+            .iter()
+            .filter(|(_, store_id, _)| store.includes(store_id))
+            .map(|(deleted_at, _, item)| Trashed::new(item.clone(), *deleted_at))
+            .collect())
+    }
+
+    fn restore(&self, store: &StoreContext, id: ConsumptionId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .deleted_consumptions
+            .iter()
+            .position(|(_, store_id, item)| store.includes(store_id) && item.id().eq(&id))
+        {
+            let (_, store_id, item) = storage.deleted_consumptions.remove(index);
+
+            storage.consumptions.push((store_id, item));
+        }
+
+        Ok(())
+    }
+
+    fn purge(&self, store: &StoreContext, id: ConsumptionId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage
+            .deleted_consumptions
+            .retain(|(_, store_id, item)| !(store.includes(store_id) && item.id().eq(&id)));
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockWasteRepository {
+    storage: Arc<Mutex<Storage>>,
+}
+
+impl MockWasteRepository {
+    pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
+        Self { storage }
+    }
+}
+
+impl ForWastePersistence for MockWasteRepository {
+    fn next_id(&self) -> Result<WasteId> {
+        let id = self.storage.lock().unwrap().wastes.len() + 1;
+
+        let id = WasteId::new(id.to_string())?;
+
+        Ok(id)
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<WasteRecord>> {
+        let wastes: Vec<WasteRecord> = self
+            .storage
+            .lock()
+            .unwrap()
+            .wastes
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, waste)| waste.clone())
+            .collect();
+
+        Ok(wastes)
+    }
+
+    fn find(&self, store: &StoreContext, query: FindWasteQuery) -> Result<Vec<WasteRecord>> {
+        let storage = self.storage.lock().unwrap();
+
+        let mut wastes: Vec<&WasteRecord> = storage
+            .wastes
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, waste)| waste)
+            .collect();
+
+        if let Some(date) = query.period_start {
+            wastes.retain(|w| w.wasted_at().ge(&date));
+        }
+
+        if let Some(date) = query.period_end {
+            wastes.retain(|w| w.wasted_at().le(&date));
+        }
+
+        if let Some(supply_id) = query.supply_id {
+            wastes.retain(|w| w.wasted().supply_id().eq(&supply_id));
+        }
+
+        let wastes: Vec<WasteRecord> = wastes.into_iter().cloned().collect();
+
+        Ok(wastes)
+    }
+
+    fn add(&self, store: &StoreContext, waste: WasteRecord) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if storage.wastes.iter().any(|(_, w)| w.id().eq(waste.id())) {
+            return Err(Error::InfrastructureError(format!(
+                "waste record already exists."
+            )));
+        }
+
+        storage.wastes.push((store.store_id()?.clone(), waste));
+
+        Ok(())
+    }
+
+    fn delete(&self, store: &StoreContext, id: WasteId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .wastes
+            .iter()
+            .position(|(store_id, w)| store.includes(store_id) && w.id().eq(&id))
+        {
+            let (store_id, item) = storage.wastes.remove(index);
+
+            storage.deleted_wastes.push((deleted_now(), store_id, item));
+        }
+
+        Ok(())
+    }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<WasteRecord>>> {
+        let deleted = &self.storage.lock().unwrap().deleted_wastes;
+
+        Ok(deleted
+            .iter()
+            .filter(|(_, store_id, _)| store.includes(store_id))
+            .map(|(deleted_at, _, item)| Trashed::new(item.clone(), *deleted_at))
+            .collect())
+    }
+
+    fn restore(&self, store: &StoreContext, id: WasteId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .deleted_wastes
+            .iter()
+            .position(|(_, store_id, item)| store.includes(store_id) && item.id().eq(&id))
+        {
+            let (_, store_id, item) = storage.deleted_wastes.remove(index);
+
+            storage.wastes.push((store_id, item));
+        }
+
+        Ok(())
+    }
+
+    fn purge(&self, store: &StoreContext, id: WasteId) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        storage
+            .deleted_wastes
+            .retain(|(_, store_id, item)| !(store.includes(store_id) && item.id().eq(&id)));
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockAccountingPeriodRepository {
+    storage: Arc<Mutex<Storage>>,
+}
+
+impl MockAccountingPeriodRepository {
+    pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
+        Self { storage }
+    }
+}
+
+impl ForAccountingPeriodPersistence for MockAccountingPeriodRepository {
+    fn next_id(&self) -> Result<AccountingPeriodId> {
+        let id = self.storage.lock().unwrap().accounting_periods.len() + 1;
+
+        let id = AccountingPeriodId::new(id.to_string())?;
+
+        Ok(id)
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<AccountingPeriod>> {
+        let mut periods: Vec<AccountingPeriod> = self
+            .storage
+            .lock()
+            .unwrap()
+            .accounting_periods
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, period)| period.clone())
+            .collect();
+
+        periods.sort_by_key(|period| std::cmp::Reverse(period.range().start()));
+
+        Ok(periods)
+    }
+
+    fn get(
+        &self,
+        store: &StoreContext,
+        id: AccountingPeriodId,
+    ) -> Result<Option<AccountingPeriod>> {
+        let period = self
+            .storage
+            .lock()
+            .unwrap()
+            .accounting_periods
+            .iter()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, period)| period)
+            .find(|p| p.id().eq(&id))
+            .cloned();
+
+        Ok(period)
+    }
+
+    fn add(&self, store: &StoreContext, period: AccountingPeriod) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if storage
+            .accounting_periods
+            .iter()
+            .any(|(_, p)| p.id().eq(period.id()))
+        {
+            return Err(Error::InfrastructureError(format!(
+                "accounting period already exists."
+            )));
+        }
+
+        storage
+            .accounting_periods
+            .push((store.store_id()?.clone(), period));
+
+        Ok(())
+    }
+
+    fn save(&self, store: &StoreContext, period: AccountingPeriod) -> Result<()> {
+        let mut storage = self.storage.lock().unwrap();
+
+        if let Some(index) = storage
+            .accounting_periods
+            .iter()
+            .position(|(store_id, p)| store.includes(store_id) && p.id().eq(period.id()))
+        {
+            storage.accounting_periods[index].1 = period;
         }
 
         Ok(())
     }
 }
+
+pub struct MockAuditRepository {
+    storage: Arc<Mutex<Storage>>,
+}
+
+impl MockAuditRepository {
+    pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
+        Self { storage }
+    }
+}
+
+impl ForAuditPersistence for MockAuditRepository {
+    fn append(&self, store: &StoreContext, change: AuditChange) -> Result<()> {
+        self.storage.lock().unwrap().log_change(store, Some(change))
+    }
+
+    fn find(&self, store: &StoreContext, query: FindAuditEntriesQuery) -> Result<Vec<AuditEntry>> {
+        // appended last, listed first
+        let mut entries: Vec<AuditEntry> = self
+            .storage
+            .lock()
+            .unwrap()
+            .audit_entries
+            .iter()
+            .rev()
+            .filter(|(store_id, _)| store.includes(store_id))
+            .map(|(_, entry)| entry)
+            .filter(|entry| {
+                query
+                    .entity
+                    .map_or(true, |entity| entry.change().entity() == entity)
+            })
+            .filter(|entry| {
+                query
+                    .entity_id
+                    .as_ref()
+                    .map_or(true, |id| entry.change().entity_id() == id.trim())
+            })
+            .filter(|entry| {
+                query
+                    .period_start
+                    .map_or(true, |start| start <= entry.logged_at())
+            })
+            .filter(|entry| {
+                query
+                    .period_end
+                    .map_or(true, |end| entry.logged_at() <= end)
+            })
+            .filter(|entry| {
+                query
+                    .operator
+                    .as_ref()
+                    .map_or(true, |operator| entry.change().operator() == Some(operator))
+            })
+            .cloned()
+            .collect();
+
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.logged_at()));
+
+        Ok(entries)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockEventPublisher {
+    storage: Arc<Mutex<Storage>>,
+}
+
+impl MockEventPublisher {
+    pub fn new(storage: Arc<Mutex<Storage>>) -> Self {
+        Self { storage }
+    }
+
+    /// Returns the events published so far, oldest first.
+    pub fn published(&self) -> Vec<DomainEvent> {
+        self.storage.lock().unwrap().events.clone()
+    }
+}
+
+impl ForEventPublishing for MockEventPublisher {
+    fn publish(&self, event: DomainEvent) {
+        self.storage.lock().unwrap().events.push(event);
+    }
+}
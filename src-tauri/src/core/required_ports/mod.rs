@@ -1,8 +1,10 @@
 mod dto;
 mod persistence;
+mod publisher;
 
 #[cfg(test)]
 pub mod mock;
 
 pub use dto::*;
 pub use persistence::*;
+pub use publisher::*;
@@ -3,85 +3,458 @@
 //! This module defines the persistence interfaces (ports) that the core
 //! business logic depends on. Concrete adapters (e.g. SQLite, in-memory,
 //! or external services) implement these traits to provide storage and
-//! retrieval for domain entities such as supplies, suppliers, journals,
-//! and stocktakings.
+//! retrieval for domain entities such as stores, supplies, suppliers,
+//! journals, stocktakings and the records kept around them.
+use crate::core::domain::entities::accounting::*;
+use crate::core::domain::entities::audit::*;
+use crate::core::domain::entities::payable::*;
 use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::accounting::*;
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::category::*;
+use crate::core::domain::values::location::*;
+use crate::core::domain::values::order::*;
+use crate::core::domain::values::payable::*;
+use crate::core::domain::values::revision::*;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::transfer::*;
+use crate::core::domain::values::trash::*;
+use crate::core::domain::values::unit::*;
+use crate::core::domain::values::waste::*;
 use crate::core::required_ports::dto::*;
 use crate::core::Result;
 
+/// persistence implementations for stores
+pub trait ForStorePersistence {
+    /// get next store id
+    fn next_id(&self) -> Result<StoreId>;
+    /// get all stores
+    fn list(&self) -> Result<Vec<Store>>;
+    /// get a store
+    fn get(&self, id: StoreId) -> Result<Option<Store>>;
+    /// add a new store
+    fn add(&self, store: Store) -> Result<()>;
+    /// save changes of a store
+    fn save(&self, store: Store) -> Result<()>;
+    /// delete store
+    fn delete(&self, id: StoreId) -> Result<()>;
+    /// get all deleted stores with when they were deleted
+    fn list_deleted(&self) -> Result<Vec<Trashed<Store>>>;
+    /// restore a deleted store
+    fn restore(&self, id: StoreId) -> Result<()>;
+    /// delete a deleted store permanently
+    fn purge(&self, id: StoreId) -> Result<()>;
+}
+
 /// persistence implementations for supplies
+///
+/// The change given with a write is logged to the audit log in the same transaction.
 pub trait ForSupplyPersistence {
     /// get next supply id
     fn next_id(&self) -> Result<SupplyId>;
-    /// check if supplies exists
-    fn has(&self, supply_ids: &[SupplyId]) -> Result<bool>;
-    /// get all supplies
-    fn list(&self) -> Result<Vec<Supply>>;
-    /// get a supply
-    fn get(&self, id: SupplyId) -> Result<Option<Supply>>;
-    /// get all supplies of supplier
+    /// check if supplies exists in the stores
+    fn has(&self, store: &StoreContext, supply_ids: &[SupplyId]) -> Result<bool>;
+    /// get all supplies of the stores
+    fn list(&self, store: &StoreContext) -> Result<Vec<Supply>>;
+    /// get a supply of the stores
+    fn get(&self, store: &StoreContext, id: SupplyId) -> Result<Option<Supply>>;
+    /// get all supplies offered by supplier
     fn list_of_supplier(&self, supplier_id: SupplierId) -> Result<Vec<Supply>>;
-    /// add a new supply
-    fn add(&self, supply: Supply) -> Result<()>;
-    /// save changes of a supply
-    fn save(&self, supply: Supply) -> Result<()>;
-    /// delete supply
-    fn delete(&self, id: SupplyId) -> Result<()>;
+    /// add a new supply to the store
+    fn add(&self, store: &StoreContext, supply: Supply, change: Option<AuditChange>) -> Result<()>;
+    /// save changes of a supply of the stores
+    fn save(&self, store: &StoreContext, supply: Supply, change: Option<AuditChange>)
+        -> Result<()>;
+    /// delete supply of the stores
+    fn delete(&self, store: &StoreContext, id: SupplyId, change: Option<AuditChange>)
+        -> Result<()>;
+    /// get all deleted supplies of the stores with when they were deleted
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Supply>>>;
+    /// restore a deleted supply of the stores
+    fn restore(
+        &self,
+        store: &StoreContext,
+        id: SupplyId,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
+    /// delete a deleted supply of the stores permanently
+    fn purge(&self, store: &StoreContext, id: SupplyId, change: Option<AuditChange>) -> Result<()>;
+}
+
+/// persistence implementations for units
+pub trait ForUnitPersistence {
+    /// get next unit id
+    fn next_id(&self) -> Result<UnitId>;
+    /// get all units
+    fn list(&self) -> Result<Vec<Unit>>;
+    /// get a unit
+    fn get(&self, id: UnitId) -> Result<Option<Unit>>;
+    /// get a unit by name
+    fn get_by_name(&self, name: UnitName) -> Result<Option<Unit>>;
+    /// add a new unit
+    fn add(&self, unit: Unit) -> Result<()>;
+    /// save changes of a unit
+    fn save(&self, unit: Unit) -> Result<()>;
+    /// delete unit
+    fn delete(&self, id: UnitId) -> Result<()>;
+    /// get all deleted units with when they were deleted
+    fn list_deleted(&self) -> Result<Vec<Trashed<Unit>>>;
+    /// restore a deleted unit
+    fn restore(&self, id: UnitId) -> Result<()>;
+    /// delete a deleted unit permanently
+    fn purge(&self, id: UnitId) -> Result<()>;
+}
+
+/// persistence implementations for categories
+pub trait ForCategoryPersistence {
+    /// get next category id
+    fn next_id(&self) -> Result<CategoryId>;
+    /// get all categories
+    fn list(&self) -> Result<Vec<Category>>;
+    /// get a category
+    fn get(&self, id: CategoryId) -> Result<Option<Category>>;
+    /// add a new category
+    fn add(&self, category: Category) -> Result<()>;
+    /// save changes of a category
+    fn save(&self, category: Category) -> Result<()>;
+    /// delete category
+    fn delete(&self, id: CategoryId) -> Result<()>;
+    /// get all deleted categories with when they were deleted
+    fn list_deleted(&self) -> Result<Vec<Trashed<Category>>>;
+    /// restore a deleted category
+    fn restore(&self, id: CategoryId) -> Result<()>;
+    /// delete a deleted category permanently
+    fn purge(&self, id: CategoryId) -> Result<()>;
+}
+
+/// persistence implementations for storage locations
+pub trait ForStorageLocationPersistence {
+    /// get next storage location id
+    fn next_id(&self) -> Result<StorageLocationId>;
+    /// get all storage locations
+    fn list(&self) -> Result<Vec<StorageLocation>>;
+    /// get a storage location
+    fn get(&self, id: StorageLocationId) -> Result<Option<StorageLocation>>;
+    /// add a new storage location
+    fn add(&self, location: StorageLocation) -> Result<()>;
+    /// save changes of a storage location
+    fn save(&self, location: StorageLocation) -> Result<()>;
+    /// delete storage location
+    fn delete(&self, id: StorageLocationId) -> Result<()>;
+    /// get all deleted storage locations with when they were deleted
+    fn list_deleted(&self) -> Result<Vec<Trashed<StorageLocation>>>;
+    /// restore a deleted storage location
+    fn restore(&self, id: StorageLocationId) -> Result<()>;
+    /// delete a deleted storage location permanently
+    fn purge(&self, id: StorageLocationId) -> Result<()>;
 }
 
 /// persistence implementations for supplier
+///
+/// The change given with a write is logged to the audit log in the same transaction.
 pub trait ForSupplierPersistence {
     /// get next supplier id
     fn next_id(&self) -> Result<SupplierId>;
-    /// check if suppliers exists
-    fn has(&self, supplier_ids: &[SupplierId]) -> Result<bool>;
-    /// get all suppliers
-    fn list(&self) -> Result<Vec<Supplier>>;
-    /// get a supplier
-    fn get(&self, id: SupplierId) -> Result<Option<Supplier>>;
-    /// find suppliers
-    fn find(&self, query: FindSupplierQuery) -> Result<Vec<Supplier>>;
-    /// add a new supplier
-    fn add(&self, supplier: Supplier) -> Result<()>;
-    /// save changes of a supplier
-    fn save(&self, supplier: Supplier) -> Result<()>;
-    /// delete supplier
-    fn delete(&self, id: SupplierId) -> Result<()>;
+    /// check if suppliers exists in the stores
+    fn has(&self, store: &StoreContext, supplier_ids: &[SupplierId]) -> Result<bool>;
+    /// get all suppliers of the stores
+    fn list(&self, store: &StoreContext) -> Result<Vec<Supplier>>;
+    /// get a supplier of the stores
+    fn get(&self, store: &StoreContext, id: SupplierId) -> Result<Option<Supplier>>;
+    /// find suppliers of the stores
+    fn find(&self, store: &StoreContext, query: FindSupplierQuery) -> Result<Vec<Supplier>>;
+    /// add a new supplier to the store
+    fn add(
+        &self,
+        store: &StoreContext,
+        supplier: Supplier,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
+    /// save changes of a supplier of the stores
+    fn save(
+        &self,
+        store: &StoreContext,
+        supplier: Supplier,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
+    /// delete supplier of the stores
+    fn delete(
+        &self,
+        store: &StoreContext,
+        id: SupplierId,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
+    /// get all deleted suppliers of the stores with when they were deleted
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Supplier>>>;
+    /// restore a deleted supplier of the stores
+    fn restore(
+        &self,
+        store: &StoreContext,
+        id: SupplierId,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
+    /// delete a deleted supplier of the stores permanently
+    fn purge(
+        &self,
+        store: &StoreContext,
+        id: SupplierId,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
 }
 
 /// persistence implementations for journal
+///
+/// The change given with a write is logged to the audit log in the same transaction.
 pub trait ForJournalPersistence {
     /// get next journal id
     fn next_id(&self) -> Result<JournalId>;
-    /// get all journals
-    fn list(&self) -> Result<Vec<Journal>>;
-    /// get a journal
-    fn get(&self, id: JournalId) -> Result<Option<Journal>>;
-    /// find journals
-    fn find(&self, query: FindJournalsQuery) -> Result<Vec<Journal>>;
-    /// add a new journal
-    fn add(&self, journal: Journal) -> Result<()>;
-    /// save changes of a journal
-    fn save(&self, journal: Journal) -> Result<()>;
-    /// delete journal
-    fn delete(&self, id: JournalId) -> Result<()>;
+    /// get all journals of the stores
+    fn list(&self, store: &StoreContext) -> Result<Vec<Journal>>;
+    /// get a journal of the stores
+    fn get(&self, store: &StoreContext, id: JournalId) -> Result<Option<Journal>>;
+    /// find journals of the stores
+    fn find(&self, store: &StoreContext, query: FindJournalsQuery) -> Result<Vec<Journal>>;
+    /// add a new journal to the store
+    fn add(
+        &self,
+        store: &StoreContext,
+        journal: Journal,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
+    /// save changes of a journal of the stores
+    fn save(
+        &self,
+        store: &StoreContext,
+        journal: Journal,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
+    /// delete journal of the stores
+    fn delete(
+        &self,
+        store: &StoreContext,
+        id: JournalId,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
+    /// get all deleted journals of the stores with when they were deleted
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Journal>>>;
+    /// restore a deleted journal of the stores
+    fn restore(
+        &self,
+        store: &StoreContext,
+        id: JournalId,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
+    /// delete a deleted journal of the stores permanently with its revisions
+    fn purge(&self, store: &StoreContext, id: JournalId, change: Option<AuditChange>)
+        -> Result<()>;
+    /// keep a journal as its next revision, stamped with the current date and time
+    fn add_revision(&self, journal: Journal) -> Result<()>;
+    /// get the revisions of a journal of the stores, oldest first
+    fn list_revisions(&self, store: &StoreContext, id: JournalId)
+        -> Result<Vec<Revision<Journal>>>;
+}
+
+/// persistence implementations for purchase orders
+pub trait ForPurchaseOrderPersistence {
+    /// get next purchase order id
+    fn next_id(&self) -> Result<PurchaseOrderId>;
+    /// get all purchase orders of the stores
+    fn list(&self, store: &StoreContext) -> Result<Vec<PurchaseOrder>>;
+    /// find purchase orders of the stores
+    fn find(
+        &self,
+        store: &StoreContext,
+        query: FindPurchaseOrdersQuery,
+    ) -> Result<Vec<PurchaseOrder>>;
+    /// get a purchase order of the stores
+    fn get(&self, store: &StoreContext, id: PurchaseOrderId) -> Result<Option<PurchaseOrder>>;
+    /// add a new purchase order to the store
+    fn add(&self, store: &StoreContext, order: PurchaseOrder) -> Result<()>;
+    /// save changes of a purchase order of the stores
+    fn save(&self, store: &StoreContext, order: PurchaseOrder) -> Result<()>;
+    /// delete purchase order of the stores
+    fn delete(&self, store: &StoreContext, id: PurchaseOrderId) -> Result<()>;
+    /// get all deleted purchase orders of the stores with when they were deleted
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<PurchaseOrder>>>;
+    /// restore a deleted purchase order of the stores
+    fn restore(&self, store: &StoreContext, id: PurchaseOrderId) -> Result<()>;
+    /// delete a deleted purchase order of the stores permanently
+    fn purge(&self, store: &StoreContext, id: PurchaseOrderId) -> Result<()>;
+}
+
+/// persistence implementations for inter-store transfers
+pub trait ForTransferPersistence {
+    /// get next transfer id
+    fn next_id(&self) -> Result<TransferId>;
+    /// get all transfers from or to the stores
+    fn list(&self, store: &StoreContext) -> Result<Vec<Transfer>>;
+    /// find transfers from or to the stores
+    fn find(&self, store: &StoreContext, query: FindTransfersQuery) -> Result<Vec<Transfer>>;
+    /// get a transfer from or to the stores
+    fn get(&self, store: &StoreContext, id: TransferId) -> Result<Option<Transfer>>;
+    /// add a new transfer
+    fn add(&self, transfer: Transfer) -> Result<()>;
+    /// save changes of a transfer from or to the stores
+    fn save(&self, store: &StoreContext, transfer: Transfer) -> Result<()>;
+    /// delete transfer from or to the stores
+    fn delete(&self, store: &StoreContext, id: TransferId) -> Result<()>;
+    /// get all deleted transfers from or to the stores with when they were deleted
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Transfer>>>;
+    /// restore a deleted transfer from or to the stores
+    fn restore(&self, store: &StoreContext, id: TransferId) -> Result<()>;
+    /// delete a deleted transfer from or to the stores permanently
+    fn purge(&self, store: &StoreContext, id: TransferId) -> Result<()>;
+}
+
+/// persistence implementations for supplier invoices and their payments
+pub trait ForSupplierInvoicePersistence {
+    /// get next supplier invoice id
+    fn next_id(&self) -> Result<SupplierInvoiceId>;
+    /// get next payment id
+    fn next_payment_id(&self) -> Result<PaymentId>;
+    /// get all supplier invoices of the stores
+    fn list(&self, store: &StoreContext) -> Result<Vec<SupplierInvoice>>;
+    /// find supplier invoices of the stores
+    fn find(
+        &self,
+        store: &StoreContext,
+        query: FindSupplierInvoicesQuery,
+    ) -> Result<Vec<SupplierInvoice>>;
+    /// get a supplier invoice of the stores
+    fn get(&self, store: &StoreContext, id: SupplierInvoiceId) -> Result<Option<SupplierInvoice>>;
+    /// add a new supplier invoice to the store
+    fn add(&self, store: &StoreContext, invoice: SupplierInvoice) -> Result<()>;
+    /// save changes of a supplier invoice of the stores and its payments
+    fn save(&self, store: &StoreContext, invoice: SupplierInvoice) -> Result<()>;
+    /// delete supplier invoice of the stores
+    fn delete(&self, store: &StoreContext, id: SupplierInvoiceId) -> Result<()>;
+    /// get all deleted supplier invoices of the stores with when they were deleted
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<SupplierInvoice>>>;
+    /// restore a deleted supplier invoice of the stores
+    fn restore(&self, store: &StoreContext, id: SupplierInvoiceId) -> Result<()>;
+    /// delete a deleted supplier invoice of the stores permanently
+    fn purge(&self, store: &StoreContext, id: SupplierInvoiceId) -> Result<()>;
 }
 
 /// persistence implementations for stocktaking
+///
+/// The change given with a write is logged to the audit log in the same transaction.
 pub trait ForStocktakingPersistence {
     /// get next stocktaking id
     fn next_id(&self) -> Result<StocktakingId>;
-    /// get all stocktakings
-    fn list(&self) -> Result<Vec<Stocktaking>>;
-    /// find stocktakings
-    fn find(&self, query: FindStocktakingsQuery) -> Result<Vec<Stocktaking>>;
-    /// get a stocktaking
-    fn get(&self, id: StocktakingId) -> Result<Option<Stocktaking>>;
-    /// add a new stocktaking
-    fn add(&self, stocktaking: Stocktaking) -> Result<()>;
-    /// save changes of a stocktaking
-    fn save(&self, stocktaking: Stocktaking) -> Result<()>;
-    /// delete stocktaking
-    fn delete(&self, id: StocktakingId) -> Result<()>;
+    /// get all stocktakings of the stores
+    fn list(&self, store: &StoreContext) -> Result<Vec<Stocktaking>>;
+    /// find stocktakings of the stores
+    fn find(&self, store: &StoreContext, query: FindStocktakingsQuery) -> Result<Vec<Stocktaking>>;
+    /// get a stocktaking of the stores
+    fn get(&self, store: &StoreContext, id: StocktakingId) -> Result<Option<Stocktaking>>;
+    /// add a new stocktaking to the store
+    fn add(
+        &self,
+        store: &StoreContext,
+        stocktaking: Stocktaking,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
+    /// save changes of a stocktaking of the stores
+    fn save(
+        &self,
+        store: &StoreContext,
+        stocktaking: Stocktaking,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
+    /// delete stocktaking of the stores
+    fn delete(
+        &self,
+        store: &StoreContext,
+        id: StocktakingId,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
+    /// get all deleted stocktakings of the stores with when they were deleted
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Stocktaking>>>;
+    /// restore a deleted stocktaking of the stores
+    fn restore(
+        &self,
+        store: &StoreContext,
+        id: StocktakingId,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
+    /// delete a deleted stocktaking of the stores permanently with its revisions
+    fn purge(
+        &self,
+        store: &StoreContext,
+        id: StocktakingId,
+        change: Option<AuditChange>,
+    ) -> Result<()>;
+    /// keep a stocktaking as its next revision, stamped with the current date and time
+    fn add_revision(&self, stocktaking: Stocktaking) -> Result<()>;
+    /// get the revisions of a stocktaking of the stores, oldest first
+    fn list_revisions(
+        &self,
+        store: &StoreContext,
+        id: StocktakingId,
+    ) -> Result<Vec<Revision<Stocktaking>>>;
+}
+
+/// persistence implementations for consumption
+pub trait ForConsumptionPersistence {
+    /// get next consumption id
+    fn next_id(&self) -> Result<ConsumptionId>;
+    /// get all consumptions of the stores
+    fn list(&self, store: &StoreContext) -> Result<Vec<Consumption>>;
+    /// find consumptions of the stores
+    fn find(&self, store: &StoreContext, query: FindConsumptionsQuery) -> Result<Vec<Consumption>>;
+    /// add a new consumption to the store
+    fn add(&self, store: &StoreContext, consumption: Consumption) -> Result<()>;
+    /// delete consumption of the stores
+    fn delete(&self, store: &StoreContext, id: ConsumptionId) -> Result<()>;
+    /// get all deleted consumptions of the stores with when they were deleted
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Consumption>>>;
+    /// restore a deleted consumption of the stores
+    fn restore(&self, store: &StoreContext, id: ConsumptionId) -> Result<()>;
+    /// delete a deleted consumption of the stores permanently
+    fn purge(&self, store: &StoreContext, id: ConsumptionId) -> Result<()>;
+}
+
+/// persistence implementations for waste records
+pub trait ForWastePersistence {
+    /// get next waste id
+    fn next_id(&self) -> Result<WasteId>;
+    /// get all waste records of the stores
+    fn list(&self, store: &StoreContext) -> Result<Vec<WasteRecord>>;
+    /// find waste records of the stores
+    fn find(&self, store: &StoreContext, query: FindWasteQuery) -> Result<Vec<WasteRecord>>;
+    /// add a new waste record to the store
+    fn add(&self, store: &StoreContext, waste: WasteRecord) -> Result<()>;
+    /// delete waste record of the stores
+    fn delete(&self, store: &StoreContext, id: WasteId) -> Result<()>;
+    /// get all deleted waste records of the stores with when they were deleted
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<WasteRecord>>>;
+    /// restore a deleted waste record of the stores
+    fn restore(&self, store: &StoreContext, id: WasteId) -> Result<()>;
+    /// delete a deleted waste record of the stores permanently
+    fn purge(&self, store: &StoreContext, id: WasteId) -> Result<()>;
+}
+
+/// persistence implementations for accounting periods and their audit trails
+pub trait ForAccountingPeriodPersistence {
+    /// get next accounting period id
+    fn next_id(&self) -> Result<AccountingPeriodId>;
+    /// get all accounting periods of the stores, the latest first
+    fn list(&self, store: &StoreContext) -> Result<Vec<AccountingPeriod>>;
+    /// get an accounting period of the stores
+    fn get(&self, store: &StoreContext, id: AccountingPeriodId)
+        -> Result<Option<AccountingPeriod>>;
+    /// add a new accounting period to the store
+    fn add(&self, store: &StoreContext, period: AccountingPeriod) -> Result<()>;
+    /// save changes of an accounting period of the stores and its audit trail
+    fn save(&self, store: &StoreContext, period: AccountingPeriod) -> Result<()>;
+}
+
+/// persistence implementations for the audit log, which is append-only
+pub trait ForAuditPersistence {
+    /// append a change to the log of the store, stamped with the current date and time
+    fn append(&self, store: &StoreContext, change: AuditChange) -> Result<()>;
+    /// find log entries of the stores matching the query, the latest first
+    fn find(&self, store: &StoreContext, query: FindAuditEntriesQuery) -> Result<Vec<AuditEntry>>;
 }
@@ -0,0 +1,14 @@
+//! Required port trait publishing domain events.
+//!
+//! Core use-cases publish an event once a change is saved, so adapters can tell the rest of the
+//! application, such as the open views of the user interface, what changed.
+use crate::core::domain::values::event::*;
+
+/// publisher implementations for domain events
+pub trait ForEventPublishing {
+    /// publish an event of a saved change, on a best-effort basis
+    ///
+    /// The change is saved already, so a failure to publish is left to the adapter to report
+    /// and never fails the use-case.
+    fn publish(&self, event: DomainEvent);
+}
@@ -0,0 +1,26 @@
+//! This module provides implementations for accounting-related usecases.
+
+pub mod period;
+
+use crate::core::domain::values::store::*;
+use crate::core::required_ports::*;
+use crate::core::*;
+
+/// Fails with `Error::PeriodClosed` if records dated `datetime` fall in a closed period of the store.
+pub fn ensure_open(
+    period_repository: &dyn ForAccountingPeriodPersistence,
+    store: &StoreContext,
+    datetime: i64,
+) -> Result<()> {
+    let closed = period_repository
+        .list(store)?
+        .into_iter()
+        .find(|period| period.locks(datetime));
+
+    match closed {
+        Some(period) => Err(Error::PeriodClosed {
+            period: period.name().to_string(),
+        }),
+        None => Ok(()),
+    }
+}
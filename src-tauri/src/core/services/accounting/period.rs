@@ -0,0 +1,124 @@
+//! This module provides the implementation of the `AccountingPeriodUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::entities::accounting::*;
+use crate::core::domain::values::accounting::*;
+use crate::core::domain::values::store::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::*;
+
+/// Accounting period usecase
+///
+/// Periods of a store must not overlap, so a date falls in one period at most.
+pub struct AccountingPeriodService {
+    period_repository: Arc<dyn ForAccountingPeriodPersistence>,
+    store: StoreContext,
+}
+
+impl AccountingPeriodService {
+    pub fn new(
+        period_repository: Arc<dyn ForAccountingPeriodPersistence>,
+        store: StoreContext,
+    ) -> Self {
+        Self {
+            period_repository,
+            store,
+        }
+    }
+}
+
+/// Converts an accounting period into a DTO.
+fn period_dto(period: &AccountingPeriod) -> AccountingPeriodDTO {
+    AccountingPeriodDTO {
+        id: period.id().to_string(),
+        name: period.name().to_string(),
+        period_start: period.range().start(),
+        period_end: period.range().end(),
+        closed: period.is_closed(),
+        audit_trail: period
+            .audit_trail()
+            .iter()
+            .map(|entry| PeriodAuditEntryDTO {
+                operation: entry.operation().as_str().to_string(),
+                performed_at: entry.performed_at(),
+                note: entry.note().map(|note| note.to_string()),
+            })
+            .collect(),
+    }
+}
+
+/// Accounting period usecase implementation
+impl AccountingPeriodUsecase for AccountingPeriodService {
+    fn list(&self) -> Result<Vec<AccountingPeriodDTO>> {
+        let periods = self.period_repository.list(&self.store)?;
+
+        Ok(periods.iter().map(period_dto).collect())
+    }
+
+    fn close(&self, command: ClosePeriodCommand) -> Result<AccountingPeriodDTO> {
+        let name = PeriodName::new(command.name)?;
+        let range = PeriodRange::new(command.period_start, command.period_end)?;
+
+        // only periods of the store are closed
+        let store = self.store.owner()?;
+
+        let periods = self.period_repository.list(&store)?;
+
+        // a reopened period is closed again under the given name
+        if let Some(mut period) = periods
+            .iter()
+            .find(|period| period.range() == &range)
+            .cloned()
+        {
+            period.reclose(command.closed_at, command.note)?;
+            period.rename(name);
+
+            self.period_repository.save(&store, period.clone())?;
+
+            return Ok(period_dto(&period));
+        }
+
+        if let Some(period) = periods
+            .iter()
+            .find(|period| period.range().overlaps(&range))
+        {
+            return Err(Error::DomainError(format!(
+                "accounting period overlaps {}.",
+                period.name().as_str()
+            )));
+        }
+
+        let period = AccountingPeriod::close(
+            self.period_repository.next_id()?,
+            name,
+            range,
+            command.closed_at,
+            command.note,
+        );
+
+        self.period_repository.add(&store, period.clone())?;
+
+        Ok(period_dto(&period))
+    }
+
+    fn reopen(&self, command: ReopenPeriodCommand) -> Result<()> {
+        let period_id = AccountingPeriodId::new(command.period_id)?;
+
+        // only periods of the store are reopened
+        let store = self.store.owner()?;
+
+        let mut period =
+            self.period_repository
+                .get(&store, period_id)?
+                .ok_or(Error::DomainError(format!(
+                    "accounting period does not exist."
+                )))?;
+
+        period.reopen(command.reopened_at, command.note)?;
+
+        self.period_repository.save(&store, period)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,316 @@
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::price::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::tax::*;
+use crate::core::domain::values::unit::*;
+use crate::core::domain::values::valuation::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::{mock::*, *};
+use crate::core::services::accounting::period::*;
+use crate::core::services::stock::{journal::*, stocktaking::*};
+use crate::core::Error;
+use std::sync::{Arc, Mutex};
+
+fn storage_with_supply() -> Arc<Mutex<Storage>> {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    MockSupplierRepository::new(Arc::clone(&storage))
+        .add(
+            &StoreContext::default(),
+            Supplier::restore(
+                SupplierId::new("1").unwrap(),
+                SupplierName::new("SupplierA").unwrap(),
+                None,
+                false,
+            ),
+            None,
+        )
+        .unwrap();
+
+    MockUnitRepository::new(Arc::clone(&storage))
+        .add(Unit::restore(
+            UnitId::new("1").unwrap(),
+            UnitName::new("kg").unwrap(),
+        ))
+        .unwrap();
+
+    MockSupplyRepository::new(Arc::clone(&storage))
+        .add(
+            &StoreContext::default(),
+            Supply::new(
+                SupplyId::new("1").unwrap(),
+                SupplyName::new("Supply1").unwrap(),
+                UnitName::new("kg").unwrap(),
+                SupplierId::new("1").unwrap(),
+                TaxCategory::Standard,
+                SupplyUnits::single(UnitId::new("1").unwrap()),
+                None,
+            ),
+            None,
+        )
+        .unwrap();
+
+    storage
+}
+
+fn journal_record(quantity: f64) -> JournalRecordDTO {
+    JournalRecordDTO {
+        supply_id: "1".into(),
+        supply_name: "Supply1".into(),
+        supplier_id: "1".into(),
+        supplier_name: "SupplierA".into(),
+        unit_name: "kg".into(),
+        unit_price: 100.0,
+        quantity,
+        total_price: 100.0 * quantity,
+        tax_category: None,
+        tax_included: None,
+        tax_price: 0.0,
+        invoice_registered: None,
+        lot_number: None,
+        expiry_date: None,
+    }
+}
+
+fn stocktaking_record(quantity: f64) -> StocktakingRecordDTO {
+    StocktakingRecordDTO {
+        supply_id: "1".into(),
+        supply_name: "Supply1".into(),
+        unit_name: "kg".into(),
+        unit_price: Some(100.0),
+        quantity,
+        total_price: Some(100.0 * quantity),
+        valuation_method: None,
+        location_id: None,
+        location_name: None,
+        lot_number: None,
+    }
+}
+
+#[test]
+fn accounting_period_service_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    let service = AccountingPeriodService::new(
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        StoreContext::default(),
+    );
+
+    let close = |name: &str, period_start: i64, period_end: i64| ClosePeriodCommand {
+        name: name.into(),
+        period_start,
+        period_end,
+        closed_at: 5000,
+        note: None,
+    };
+
+    let april = service.close(close("2024-04", 1000, 1999)).unwrap();
+
+    assert!(april.closed);
+    assert_eq!(april.audit_trail.len(), 1);
+    assert_eq!(april.audit_trail[0].operation, "close");
+
+    let result = service.close(close("2024-04", 1000, 1999));
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    // periods must not overlap
+    let result = service.close(close("2024-04/05", 1500, 2999));
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    service.close(close("2024-05", 2000, 2999)).unwrap();
+
+    // another store cannot reopen the period
+    let branch_service = AccountingPeriodService::new(
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        StoreContext::Store(StoreId::new("2").unwrap()),
+    );
+
+    let result = branch_service.reopen(ReopenPeriodCommand {
+        period_id: april.id.clone(),
+        reopened_at: 6000,
+        note: None,
+    });
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    // nor is a period closed across stores
+    let consolidated_service = AccountingPeriodService::new(
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        StoreContext::Consolidated,
+    );
+
+    let result = consolidated_service.close(close("2024-06", 3000, 3999));
+
+    assert!(result.is_err());
+
+    service
+        .reopen(ReopenPeriodCommand {
+            period_id: april.id.clone(),
+            reopened_at: 6000,
+            note: Some("correction".into()),
+        })
+        .unwrap();
+
+    let result = service.reopen(ReopenPeriodCommand {
+        period_id: april.id.clone(),
+        reopened_at: 6000,
+        note: None,
+    });
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    // closing the same range again closes the reopened period under the new name
+    let closed = service
+        .close(close("2024-04 (corrected)", 1000, 1999))
+        .unwrap();
+
+    assert_eq!(closed.id, april.id);
+    assert_eq!(closed.name, "2024-04 (corrected)");
+
+    let periods = service.list().unwrap();
+
+    assert_eq!(periods.len(), 2);
+    assert_eq!(periods[0].name, "2024-05");
+    assert!(periods[1].closed);
+    assert_eq!(
+        periods[1]
+            .audit_trail
+            .iter()
+            .map(|entry| entry.operation.as_str())
+            .collect::<Vec<_>>(),
+        vec!["close", "reopen", "close"]
+    );
+    assert_eq!(periods[1].audit_trail[1].note, Some("correction".into()));
+}
+
+#[test]
+fn closed_period_locks_records_test() {
+    let storage = storage_with_supply();
+
+    let period_service = AccountingPeriodService::new(
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        StoreContext::default(),
+    );
+
+    let journal_service = JournalService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        PriceAlertRule::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    let stocktaking_service = StocktakingService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStorageLocationRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        ValuationMethod::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    let journal = journal_service
+        .record(RecordJournalCommand {
+            entry_date: 1500,
+            total_price: 100.0,
+            records: vec![journal_record(1.0)],
+        })
+        .unwrap();
+
+    let stocktaking = stocktaking_service
+        .record(RecordStocktakingCommand {
+            stocktaken_date: 1999,
+            total_price: None,
+            records: vec![stocktaking_record(1.0)],
+        })
+        .unwrap();
+
+    let period = period_service
+        .close(ClosePeriodCommand {
+            name: "2024-04".into(),
+            period_start: 1000,
+            period_end: 1999,
+            closed_at: 5000,
+            note: None,
+        })
+        .unwrap();
+
+    let is_locked = |result: crate::core::Result<_>| matches!(result, Err(Error::PeriodClosed { period }) if period == "2024-04");
+
+    assert!(is_locked(
+        journal_service
+            .record(RecordJournalCommand {
+                entry_date: 1000,
+                total_price: 100.0,
+                records: vec![journal_record(1.0)],
+            })
+            .map(|_| ())
+    ));
+    assert!(is_locked(journal_service.edit(EditJournalCommand {
+        journal_id: journal.id.clone(),
+        total_price: 200.0,
+        records: vec![journal_record(2.0)],
+    })));
+    assert!(is_locked(journal_service.delete(&journal.id)));
+
+    assert!(is_locked(
+        stocktaking_service
+            .record(RecordStocktakingCommand {
+                stocktaken_date: 1999,
+                total_price: None,
+                records: vec![stocktaking_record(1.0)],
+            })
+            .map(|_| ())
+    ));
+    assert!(is_locked(stocktaking_service.edit(
+        EditStocktakingCommand {
+            stocktaking_id: stocktaking.id.clone(),
+            total_price: None,
+            records: vec![stocktaking_record(2.0)],
+        }
+    )));
+    assert!(is_locked(stocktaking_service.delete(&stocktaking.id)));
+
+    // records out of the period are not locked
+    journal_service
+        .record(RecordJournalCommand {
+            entry_date: 2000,
+            total_price: 100.0,
+            records: vec![journal_record(1.0)],
+        })
+        .unwrap();
+
+    period_service
+        .reopen(ReopenPeriodCommand {
+            period_id: period.id,
+            reopened_at: 6000,
+            note: None,
+        })
+        .unwrap();
+
+    journal_service
+        .edit(EditJournalCommand {
+            journal_id: journal.id.clone(),
+            total_price: 200.0,
+            records: vec![journal_record(2.0)],
+        })
+        .unwrap();
+    journal_service.delete(&journal.id).unwrap();
+    stocktaking_service.delete(&stocktaking.id).unwrap();
+}
@@ -0,0 +1,61 @@
+//! This module provides the implementation of the `AuditUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::entities::audit::*;
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::store::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::*;
+
+/// Audit usecase
+pub struct AuditLogService {
+    audit_repository: Arc<dyn ForAuditPersistence>,
+    store: StoreContext,
+}
+
+impl AuditLogService {
+    pub fn new(audit_repository: Arc<dyn ForAuditPersistence>, store: StoreContext) -> Self {
+        Self {
+            audit_repository,
+            store,
+        }
+    }
+}
+
+/// Converts an audit log entry into a DTO.
+fn audit_entry_dto(entry: &AuditEntry) -> AuditEntryDTO {
+    let change = entry.change();
+
+    AuditEntryDTO {
+        id: entry.id().to_string(),
+        entity: change.entity().as_str().to_string(),
+        entity_id: change.entity_id().to_string(),
+        operation: change.operation().as_str().to_string(),
+        logged_at: entry.logged_at().as_i64(),
+        operator: change.operator().map(|operator| operator.to_string()),
+        before: change.before().map(|before| before.as_str().to_string()),
+        after: change.after().map(|after| after.as_str().to_string()),
+    }
+}
+
+/// Audit usecase implementation
+impl AuditUsecase for AuditLogService {
+    fn search(&self, query: SearchAuditLogQuery) -> Result<Vec<AuditEntryDTO>> {
+        let query = FindAuditEntriesQuery {
+            entity: query.entity.map(AuditEntity::new).transpose()?,
+            entity_id: query.entity_id.filter(|id| !id.trim().is_empty()),
+            period_start: query.period_start.map(LoggedDateTime::new),
+            period_end: query.period_end.map(LoggedDateTime::new),
+            operator: query
+                .operator
+                .filter(|operator| !operator.trim().is_empty())
+                .map(OperatorName::new)
+                .transpose()?,
+        };
+
+        let entries = self.audit_repository.find(&self.store, query)?;
+
+        Ok(entries.iter().map(audit_entry_dto).collect())
+    }
+}
@@ -0,0 +1,15 @@
+//! This module provides implementations for audit-related usecases.
+
+pub mod log;
+
+use serde::Serialize;
+
+use crate::core::domain::values::audit::*;
+use crate::core::*;
+
+/// Takes a JSON snapshot of an entity as shown to users, from its DTO.
+pub fn snapshot(dto: &impl Serialize) -> Result<AuditSnapshot> {
+    serde_json::to_string(dto)
+        .map(AuditSnapshot::new)
+        .map_err(|e| Error::DomainError(format!("failed to take snapshot: {}", e)))
+}
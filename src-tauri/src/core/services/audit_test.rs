@@ -0,0 +1,140 @@
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::store::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::mock::*;
+use crate::core::services::audit::log::*;
+use crate::core::services::stock::supplier::*;
+use std::sync::{Arc, Mutex};
+
+fn register_command(supplier_name: &str) -> RegisterSupplierCommand {
+    RegisterSupplierCommand {
+        supplier_name: supplier_name.into(),
+        invoice_registration_number: None,
+        invoice_registered: false,
+        contact: SupplierContactDTO::default(),
+        notes: None,
+        billing_terms: None,
+    }
+}
+
+fn query() -> SearchAuditLogQuery {
+    SearchAuditLogQuery {
+        entity: None,
+        entity_id: None,
+        period_start: None,
+        period_end: None,
+        operator: None,
+    }
+}
+
+#[test]
+fn audit_log_service_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    let supplier_service = |operator: &str| {
+        SupplierService::new(
+            Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+            Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+            Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+            Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+            Some(OperatorName::new(operator).unwrap()),
+            StoreContext::default(),
+        )
+    };
+
+    let audit_service = AuditLogService::new(
+        Arc::new(MockAuditRepository::new(Arc::clone(&storage))),
+        StoreContext::default(),
+    );
+
+    let supplier = supplier_service("Sato")
+        .register(register_command("SupplierA"))
+        .unwrap();
+
+    supplier_service("Suzuki")
+        .register(register_command("SupplierB"))
+        .unwrap();
+
+    supplier_service("Suzuki")
+        .update(UpdateSupplierCommand {
+            supplier_id: supplier.id.clone(),
+            supplier_name: "SupplierA2".into(),
+            invoice_registration_number: None,
+            invoice_registered: None,
+            contact: SupplierContactDTO::default(),
+            notes: None,
+            billing_terms: None,
+        })
+        .unwrap();
+
+    supplier_service("Sato").delete(&supplier.id).unwrap();
+
+    // failed changes are not logged
+    assert!(supplier_service("Sato").restore("100").is_err());
+
+    let entries = audit_service.search(query()).unwrap();
+
+    assert_eq!(
+        entries
+            .iter()
+            .map(|entry| entry.operation.as_str())
+            .collect::<Vec<_>>(),
+        vec!["delete", "update", "register", "register"]
+    );
+
+    let update = &entries[1];
+
+    assert_eq!(update.entity, "supplier");
+    assert_eq!(update.entity_id, supplier.id);
+    assert_eq!(update.operator, Some("Suzuki".into()));
+    assert!(update
+        .before
+        .as_ref()
+        .unwrap()
+        .contains(r#""name":"SupplierA""#));
+    assert!(update
+        .after
+        .as_ref()
+        .unwrap()
+        .contains(r#""name":"SupplierA2""#));
+
+    assert!(entries[0].after.is_none());
+    assert!(entries[3].before.is_none());
+
+    let entries = audit_service
+        .search(SearchAuditLogQuery {
+            entity: Some("supplier".into()),
+            entity_id: Some(supplier.id.clone()),
+            operator: Some("Sato".into()),
+            ..query()
+        })
+        .unwrap();
+
+    assert_eq!(entries.len(), 2);
+
+    let entries = audit_service
+        .search(SearchAuditLogQuery {
+            entity: Some("journal".into()),
+            ..query()
+        })
+        .unwrap();
+
+    assert!(entries.is_empty());
+
+    // nothing is logged after the end of the range
+    let entries = audit_service
+        .search(SearchAuditLogQuery {
+            period_end: Some(0),
+            ..query()
+        })
+        .unwrap();
+
+    assert!(entries.is_empty());
+
+    let result = audit_service.search(SearchAuditLogQuery {
+        entity: Some("unit".into()),
+        ..query()
+    });
+
+    assert!(result.is_err());
+}
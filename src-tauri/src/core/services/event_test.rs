@@ -0,0 +1,246 @@
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::event::*;
+use crate::core::domain::values::price::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::valuation::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::{mock::*, *};
+use crate::core::services::stock::{journal::*, stocktaking::*, supply::*};
+use std::sync::{Arc, Mutex};
+
+fn storage_with_supplier() -> Arc<Mutex<Storage>> {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    MockSupplierRepository::new(Arc::clone(&storage))
+        .add(
+            &StoreContext::default(),
+            Supplier::restore(
+                SupplierId::new("1").unwrap(),
+                SupplierName::new("SupplierA").unwrap(),
+                None,
+                false,
+            ),
+            None,
+        )
+        .unwrap();
+
+    storage
+}
+
+fn supply_service(storage: &Arc<Mutex<Storage>>) -> SupplyService {
+    SupplyService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(storage))),
+        None,
+        StoreContext::default(),
+    )
+}
+
+fn update_command(supply_name: &str) -> UpdateSupplyCommand {
+    UpdateSupplyCommand {
+        supply_id: "1".into(),
+        supply_name: supply_name.into(),
+        unit_name: "kg".into(),
+        supplier_id: "1".into(),
+        tax_category: None,
+        stock_unit_id: None,
+        conversions: None,
+        category_id: None,
+    }
+}
+
+#[test]
+fn supply_event_test() {
+    let storage = storage_with_supplier();
+    let service = supply_service(&storage);
+    let publisher = MockEventPublisher::new(Arc::clone(&storage));
+
+    let supply = service
+        .register(CreateSupplyCommand {
+            supplier_id: "1".into(),
+            supply_name: "SupplyA".into(),
+            unit_name: "kg".into(),
+            tax_category: None,
+            stock_unit_id: None,
+            conversions: vec![],
+            category_id: None,
+        })
+        .unwrap();
+
+    let supply_id = SupplyId::new(&supply.id).unwrap();
+
+    assert_eq!(
+        publisher.published(),
+        vec![DomainEvent::SupplyRegistered {
+            supply_id: supply_id.clone()
+        }]
+    );
+
+    // renaming publishes the previous name along with the update
+    service.update(update_command("SupplyB")).unwrap();
+    // an update keeping the name is not a rename
+    service.update(update_command("SupplyB")).unwrap();
+    service.delete(&supply.id).unwrap();
+
+    let published = publisher.published();
+
+    assert_eq!(
+        published[1..],
+        [
+            DomainEvent::SupplyUpdated {
+                supply_id: supply_id.clone()
+            },
+            DomainEvent::SupplyRenamed {
+                supply_id: supply_id.clone(),
+                previous_name: SupplyName::new("SupplyA").unwrap(),
+                name: SupplyName::new("SupplyB").unwrap(),
+            },
+            DomainEvent::SupplyUpdated {
+                supply_id: supply_id.clone()
+            },
+            DomainEvent::SupplyDeleted {
+                supply_id: supply_id.clone()
+            },
+        ]
+    );
+    assert_eq!(published[2].name(), "supply-renamed");
+    assert_eq!(published[2].entity_id(), supply.id);
+
+    // deleting a supply that does not exist changes nothing
+    service.delete("100").unwrap();
+
+    assert_eq!(publisher.published().len(), 5);
+}
+
+#[test]
+fn journal_and_stocktaking_event_test() {
+    let storage = storage_with_supplier();
+    let publisher = MockEventPublisher::new(Arc::clone(&storage));
+
+    let supply = supply_service(&storage)
+        .register(CreateSupplyCommand {
+            supplier_id: "1".into(),
+            supply_name: "SupplyA".into(),
+            unit_name: "kg".into(),
+            tax_category: None,
+            stock_unit_id: None,
+            conversions: vec![],
+            category_id: None,
+        })
+        .unwrap();
+
+    let journal_service = JournalService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        PriceAlertRule::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    let stocktaking_service = StocktakingService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStorageLocationRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        ValuationMethod::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    let journal_record = |quantity: f64| JournalRecordDTO {
+        supply_id: supply.id.clone(),
+        supply_name: supply.name.clone(),
+        supplier_id: "1".into(),
+        supplier_name: "SupplierA".into(),
+        unit_name: "kg".into(),
+        unit_price: 100.0,
+        quantity,
+        total_price: 100.0 * quantity,
+        tax_category: None,
+        tax_included: None,
+        tax_price: 0.0,
+        invoice_registered: None,
+        lot_number: None,
+        expiry_date: None,
+    };
+
+    let journal = journal_service
+        .record(RecordJournalCommand {
+            entry_date: 1000,
+            total_price: 100.0,
+            records: vec![journal_record(1.0)],
+        })
+        .unwrap();
+
+    journal_service
+        .edit(EditJournalCommand {
+            journal_id: journal.id.clone(),
+            total_price: 200.0,
+            records: vec![journal_record(2.0)],
+        })
+        .unwrap();
+
+    // reverting is published as an edit
+    journal_service.revert(&journal.id, 1).unwrap();
+
+    let stocktaking = stocktaking_service
+        .record(RecordStocktakingCommand {
+            stocktaken_date: 2000,
+            total_price: None,
+            records: vec![StocktakingRecordDTO {
+                supply_id: supply.id.clone(),
+                supply_name: supply.name.clone(),
+                unit_name: "kg".into(),
+                unit_price: Some(100.0),
+                quantity: 1.0,
+                total_price: Some(100.0),
+                valuation_method: None,
+                location_id: None,
+                location_name: None,
+                lot_number: None,
+            }],
+        })
+        .unwrap();
+
+    stocktaking_service.delete(&stocktaking.id).unwrap();
+
+    let journal_id = JournalId::new(&journal.id).unwrap();
+    let stocktaking_id = StocktakingId::new(&stocktaking.id).unwrap();
+
+    assert_eq!(
+        publisher.published()[1..],
+        [
+            DomainEvent::JournalRecorded {
+                journal_id: journal_id.clone()
+            },
+            DomainEvent::JournalEdited {
+                journal_id: journal_id.clone()
+            },
+            DomainEvent::JournalEdited {
+                journal_id: journal_id.clone()
+            },
+            DomainEvent::StocktakingRecorded {
+                stocktaking_id: stocktaking_id.clone()
+            },
+            DomainEvent::StocktakingDeleted {
+                stocktaking_id: stocktaking_id.clone()
+            },
+        ]
+    );
+}
@@ -1,3 +1,20 @@
+pub mod accounting;
+#[cfg(test)]
+mod accounting_test;
+pub mod audit;
+#[cfg(test)]
+mod audit_test;
+#[cfg(test)]
+mod event_test;
+pub mod payable;
+#[cfg(test)]
+mod payable_test;
+pub mod revision;
+#[cfg(test)]
+mod revision_test;
 pub mod stock;
 #[cfg(test)]
 mod stock_test;
+pub mod trash;
+#[cfg(test)]
+mod trash_test;
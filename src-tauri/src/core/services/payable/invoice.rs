@@ -0,0 +1,412 @@
+//! This module provides the implementation of the `PayableUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::entities::payable::*;
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::payable::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::services::trash::*;
+use crate::core::*;
+
+/// Payable usecase
+///
+/// Invoices are matched against the gross totals of the journal lines of the supplier
+/// entered in the billing period.
+pub struct PayableService {
+    invoice_repository: Arc<dyn ForSupplierInvoicePersistence>,
+    supplier_repository: Arc<dyn ForSupplierPersistence>,
+    journal_repository: Arc<dyn ForJournalPersistence>,
+    store: StoreContext,
+}
+
+impl PayableService {
+    pub fn new(
+        invoice_repository: Arc<dyn ForSupplierInvoicePersistence>,
+        supplier_repository: Arc<dyn ForSupplierPersistence>,
+        journal_repository: Arc<dyn ForJournalPersistence>,
+        store: StoreContext,
+    ) -> Self {
+        Self {
+            invoice_repository,
+            supplier_repository,
+            journal_repository,
+            store,
+        }
+    }
+
+    /// Returns the supplier invoice of the store, failing if it does not exist.
+    fn invoice_of(
+        &self,
+        store: &StoreContext,
+        invoice_id: impl AsRef<str>,
+    ) -> Result<SupplierInvoice> {
+        let invoice_id = SupplierInvoiceId::new(invoice_id.as_ref())?;
+
+        self.invoice_repository
+            .get(store, invoice_id)?
+            .ok_or(Error::DomainError(format!(
+                "supplier invoice does not exist."
+            )))
+    }
+
+    /// Returns the supplier, failing if it is not one of the store.
+    fn supplier_of(&self, supplier_id: impl AsRef<str>) -> Result<Supplier> {
+        let supplier_id = SupplierId::new(supplier_id.as_ref())?;
+
+        if !self
+            .supplier_repository
+            .has(&self.store, std::slice::from_ref(&supplier_id))?
+        {
+            return Err(Error::DomainError(format!("supplier does not exist.")));
+        }
+
+        self.supplier_repository
+            .get(&self.store, supplier_id)?
+            .ok_or(Error::DomainError(format!("supplier does not exist.")))
+    }
+
+    /// Converts invoices into their DTOs, matching each against the journals of its period.
+    fn invoice_dtos(&self, invoices: &[SupplierInvoice]) -> Result<Vec<SupplierInvoiceDTO>> {
+        if invoices.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let suppliers = self.supplier_repository.list(&self.store)?;
+
+        let journals = self.journal_repository.find(
+            &self.store,
+            FindJournalsQuery {
+                period_start: invoices
+                    .iter()
+                    .map(|invoice| invoice.period().start())
+                    .min()
+                    .map(EntryDateTime::new),
+                period_end: invoices
+                    .iter()
+                    .map(|invoice| invoice.period().end())
+                    .max()
+                    .map(EntryDateTime::new),
+                supplier_name: None,
+                supply_name: None,
+            },
+        )?;
+
+        invoices
+            .iter()
+            .map(|invoice| invoice_dto(invoice, &suppliers, &journals))
+            .collect()
+    }
+}
+
+/// Returns the journals of the supplier of the invoice entered in its period,
+/// with the gross total of their lines from the supplier.
+fn matched_journals(
+    invoice: &SupplierInvoice,
+    journals: &[Journal],
+) -> Result<(Vec<JournalId>, Money)> {
+    let mut journal_ids = Vec::new();
+    let mut amount = Money::ZERO;
+
+    for journal in journals
+        .iter()
+        .filter(|journal| invoice.period().contains(journal.entry_datetime()))
+    {
+        let records: Vec<&JournalRecord> = journal
+            .records()
+            .iter()
+            .filter(|record| record.supplier_id() == invoice.supplier_id())
+            .collect();
+
+        if records.is_empty() {
+            continue;
+        }
+
+        for record in records {
+            amount = amount.checked_add(record.gross_price()?)?;
+        }
+
+        journal_ids.push(journal.id().clone());
+    }
+
+    Ok((journal_ids, amount))
+}
+
+/// Converts an invoice into its DTO.
+fn invoice_dto(
+    invoice: &SupplierInvoice,
+    suppliers: &[Supplier],
+    journals: &[Journal],
+) -> Result<SupplierInvoiceDTO> {
+    let (journal_ids, journal_amount) = matched_journals(invoice, journals)?;
+
+    Ok(SupplierInvoiceDTO {
+        id: invoice.id().to_string(),
+        supplier_id: invoice.supplier_id().to_string(),
+        supplier_name: suppliers
+            .iter()
+            .find(|supplier| supplier.id() == invoice.supplier_id())
+            .map(|supplier| supplier.name().to_string())
+            .unwrap_or_default(),
+        invoice_number: invoice.invoice_number().map(|number| number.to_string()),
+        period_start: invoice.period().start(),
+        period_end: invoice.period().end(),
+        due_date: invoice.due_date().as_i64(),
+        amount: invoice.amount().as_f64(),
+        journal_ids: journal_ids.iter().map(|id| id.to_string()).collect(),
+        journal_amount: journal_amount.as_f64(),
+        difference: invoice
+            .amount()
+            .as_money()
+            .checked_sub(journal_amount)?
+            .as_f64(),
+        paid_amount: invoice.paid_amount()?.as_f64(),
+        balance: invoice.balance()?.as_f64(),
+        payments: invoice
+            .payments()
+            .iter()
+            .map(|payment| PaymentDTO {
+                id: payment.id().to_string(),
+                paid_date: payment.paid_at().as_i64(),
+                amount: payment.amount().as_f64(),
+                payment_method: payment.method().to_string(),
+            })
+            .collect(),
+    })
+}
+
+/// Builds the number of an invoice, none if empty.
+fn invoice_number_of(invoice_number: Option<&String>) -> Result<Option<InvoiceNumber>> {
+    invoice_number
+        .filter(|number| !number.trim().is_empty())
+        .map(InvoiceNumber::new)
+        .transpose()
+}
+
+/// Payable usecase implementation
+impl PayableUsecase for PayableService {
+    fn list(&self) -> Result<Vec<SupplierInvoiceDTO>> {
+        let invoices = self.invoice_repository.list(&self.store)?;
+
+        self.invoice_dtos(&invoices)
+    }
+
+    fn get(&self, invoice_id: impl AsRef<str>) -> Result<Option<SupplierInvoiceDTO>> {
+        let invoice_id = SupplierInvoiceId::new(invoice_id.as_ref())?;
+
+        let invoice = self.invoice_repository.get(&self.store, invoice_id)?;
+
+        match invoice {
+            Some(invoice) => Ok(self.invoice_dtos(&[invoice])?.into_iter().next()),
+            None => Ok(None),
+        }
+    }
+
+    fn search(&self, query: SearchSupplierInvoicesQuery) -> Result<Vec<SupplierInvoiceDTO>> {
+        let query = FindSupplierInvoicesQuery {
+            supplier_id: query.supplier_id.map(SupplierId::new).transpose()?,
+            period_start: query.period_start,
+            period_end: query.period_end,
+        };
+
+        let invoices = self.invoice_repository.find(&self.store, query)?;
+
+        self.invoice_dtos(&invoices)
+    }
+
+    fn record(&self, command: RecordSupplierInvoiceCommand) -> Result<SupplierInvoiceDTO> {
+        let supplier = self.supplier_of(&command.supplier_id)?;
+
+        let invoice = SupplierInvoice::new(
+            self.invoice_repository.next_id()?,
+            supplier.id().clone(),
+            invoice_number_of(command.invoice_number.as_ref())?,
+            BillingPeriod::new(command.period_start, command.period_end)?,
+            DueDate::new(command.due_date),
+            InvoicedAmount::new(command.amount)?,
+        )?;
+
+        self.invoice_repository.add(&self.store, invoice.clone())?;
+
+        Ok(self.invoice_dtos(&[invoice])?.into_iter().next().unwrap())
+    }
+
+    fn update(&self, command: UpdateSupplierInvoiceCommand) -> Result<()> {
+        let store = self.store.owner()?;
+
+        let mut invoice = self.invoice_of(&store, &command.invoice_id)?;
+
+        invoice.revise(
+            invoice_number_of(command.invoice_number.as_ref())?,
+            BillingPeriod::new(command.period_start, command.period_end)?,
+            DueDate::new(command.due_date),
+            InvoicedAmount::new(command.amount)?,
+        )?;
+
+        self.invoice_repository.save(&store, invoice)?;
+
+        Ok(())
+    }
+
+    fn delete(&self, invoice_id: impl AsRef<str>) -> Result<()> {
+        let store = self.store.owner()?;
+
+        let invoice = self.invoice_of(&store, invoice_id)?;
+
+        self.invoice_repository
+            .delete(&store, invoice.id().clone())?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<SupplierInvoiceDTO>>> {
+        let trashed = self.invoice_repository.list_deleted(&self.store)?;
+
+        let items: Vec<_> = trashed
+            .iter()
+            .map(|trashed| trashed.item().clone())
+            .collect();
+
+        let dtos = self.invoice_dtos(&items)?;
+
+        Ok(trashed_dtos(&trashed, dtos))
+    }
+
+    fn restore(&self, invoice_id: impl AsRef<str>) -> Result<()> {
+        let invoice_id = SupplierInvoiceId::new(invoice_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let invoice = trashed_item(
+            self.invoice_repository.list_deleted(&store)?,
+            |invoice| invoice.id() == &invoice_id,
+            "supplier invoice",
+        )?;
+
+        self.invoice_repository
+            .restore(&store, invoice.id().clone())?;
+
+        Ok(())
+    }
+
+    fn purge(&self, invoice_id: impl AsRef<str>) -> Result<()> {
+        let invoice_id = SupplierInvoiceId::new(invoice_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let invoice = trashed_item(
+            self.invoice_repository.list_deleted(&store)?,
+            |invoice| invoice.id() == &invoice_id,
+            "supplier invoice",
+        )?;
+
+        self.invoice_repository
+            .purge(&store, invoice.id().clone())?;
+
+        Ok(())
+    }
+
+    fn record_payment(&self, command: RecordPaymentCommand) -> Result<SupplierInvoiceDTO> {
+        let store = self.store.owner()?;
+
+        let mut invoice = self.invoice_of(&store, &command.invoice_id)?;
+
+        let method = match command.payment_method {
+            Some(method) => PaymentMethod::new(method)?,
+            None => self
+                .supplier_repository
+                .get(&self.store, invoice.supplier_id().clone())?
+                .and_then(|supplier| supplier.billing_terms().map(|terms| terms.payment_method()))
+                .unwrap_or(PaymentMethod::BankTransfer),
+        };
+
+        invoice.pay(Payment::new(
+            self.invoice_repository.next_payment_id()?,
+            PaidDateTime::new(command.paid_date),
+            PaymentAmount::new(command.amount)?,
+            method,
+        ))?;
+
+        self.invoice_repository.save(&store, invoice.clone())?;
+
+        Ok(self.invoice_dtos(&[invoice])?.into_iter().next().unwrap())
+    }
+
+    fn cancel_payment(&self, command: CancelPaymentCommand) -> Result<()> {
+        let store = self.store.owner()?;
+
+        let mut invoice = self.invoice_of(&store, &command.invoice_id)?;
+
+        invoice.cancel_payment(&PaymentId::new(&command.payment_id)?)?;
+
+        self.invoice_repository.save(&store, invoice)?;
+
+        Ok(())
+    }
+
+    fn balances(&self, query: PayableBalanceQuery) -> Result<Vec<SupplierBalanceDTO>> {
+        let suppliers = self.supplier_repository.list(&self.store)?;
+        let invoices = self.invoice_repository.find(
+            &self.store,
+            FindSupplierInvoicesQuery {
+                supplier_id: None,
+                period_start: None,
+                period_end: Some(query.at),
+            },
+        )?;
+
+        let at = PaidDateTime::new(query.at);
+
+        let mut balances: Vec<SupplierBalanceDTO> = Vec::new();
+
+        for supplier in &suppliers {
+            let mut invoiced = Money::ZERO;
+            let mut balance = Money::ZERO;
+            let mut buckets = [Money::ZERO; 5];
+
+            for invoice in invoices
+                .iter()
+                .filter(|invoice| invoice.supplier_id() == supplier.id())
+            {
+                let outstanding = invoice.balance_at(&at)?;
+
+                invoiced = invoiced.checked_add(invoice.amount().as_money())?;
+                balance = balance.checked_add(outstanding)?;
+
+                let bucket = match AgingBucket::of(invoice.due_date(), query.at) {
+                    AgingBucket::Current => 0,
+                    AgingBucket::Overdue1To30 => 1,
+                    AgingBucket::Overdue31To60 => 2,
+                    AgingBucket::Overdue61To90 => 3,
+                    AgingBucket::OverdueOver90 => 4,
+                };
+
+                buckets[bucket] = buckets[bucket].checked_add(outstanding)?;
+            }
+
+            if invoiced == Money::ZERO && balance == Money::ZERO {
+                continue;
+            }
+
+            balances.push(SupplierBalanceDTO {
+                supplier_id: supplier.id().to_string(),
+                supplier_name: supplier.name().to_string(),
+                invoiced: invoiced.as_f64(),
+                paid: invoiced.checked_sub(balance)?.as_f64(),
+                balance: balance.as_f64(),
+                current: buckets[0].as_f64(),
+                overdue_1_30: buckets[1].as_f64(),
+                overdue_31_60: buckets[2].as_f64(),
+                overdue_61_90: buckets[3].as_f64(),
+                overdue_over_90: buckets[4].as_f64(),
+            });
+        }
+
+        balances.sort_by(|a, b| a.supplier_name.cmp(&b.supplier_name));
+
+        Ok(balances)
+    }
+}
@@ -0,0 +1,3 @@
+//! This module provides implementations for payable-related usecases.
+
+pub mod invoice;
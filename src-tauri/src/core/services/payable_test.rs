@@ -0,0 +1,300 @@
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::tax::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::{mock::*, *};
+use crate::core::services::payable::invoice::*;
+use crate::core::Error;
+use std::sync::{Arc, Mutex};
+
+const DAY: i64 = 24 * 60 * 60 * 1000;
+
+/// Builds a journal of lines taxed inclusively, `(supplier_id, total_price)` each.
+fn journal(id: &str, entry_date: i64, lines: &[(&str, f64)]) -> Journal {
+    let records: Vec<JournalRecord> = lines
+        .iter()
+        .map(|(supplier_id, total_price)| {
+            JournalRecord::new(
+                SupplyId::new(format!("Supply{}", supplier_id)).unwrap(),
+                SupplyName::new(format!("Supply{}", supplier_id)).unwrap(),
+                SupplierId::new(*supplier_id).unwrap(),
+                SupplierName::new(format!("Supplier{}", supplier_id)).unwrap(),
+                UnitName::new("kg").unwrap(),
+                PurchaseUnitPrice::new(*total_price).unwrap(),
+                PurchaseQuantity::new(1.0).unwrap(),
+                TotalPrice::new(*total_price).unwrap(),
+                TaxCategory::Standard,
+                true,
+                TotalPrice::new(0.0).unwrap(),
+                true,
+            )
+        })
+        .collect();
+
+    let total: f64 = lines.iter().map(|(_, total_price)| total_price).sum();
+
+    Journal::restore(
+        JournalId::new(id).unwrap(),
+        EntryDateTime::new(entry_date),
+        TotalPrice::new(total).unwrap(),
+        records,
+    )
+}
+
+#[test]
+fn payable_service_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    let supplier_repository = MockSupplierRepository::new(Arc::clone(&storage));
+    let journal_repository = MockJournalRepository::new(Arc::clone(&storage));
+
+    for (id, name) in [("1", "SupplierA"), ("2", "SupplierB")] {
+        let mut supplier = Supplier::restore(
+            SupplierId::new(id).unwrap(),
+            SupplierName::new(name).unwrap(),
+            None,
+            false,
+        );
+
+        if id == "2" {
+            supplier.change_billing_terms(Some(
+                BillingTerms::new(
+                    BillingDay::EndOfMonth,
+                    1,
+                    BillingDay::EndOfMonth,
+                    PaymentMethod::DirectDebit,
+                )
+                .unwrap(),
+            ));
+        }
+
+        supplier_repository
+            .add(&StoreContext::default(), supplier, None)
+            .unwrap();
+    }
+
+    for journal in [
+        journal("1", 10 * DAY, &[("1", 3000.0), ("2", 500.0)]),
+        journal("2", 20 * DAY, &[("1", 2000.0)]),
+        // out of the billing period
+        journal("3", 40 * DAY, &[("1", 700.0)]),
+    ] {
+        journal_repository
+            .add(&StoreContext::default(), journal, None)
+            .unwrap();
+    }
+
+    let service = PayableService::new(
+        Arc::new(MockSupplierInvoiceRepository::new(Arc::clone(&storage))),
+        Arc::new(supplier_repository),
+        Arc::new(journal_repository),
+        StoreContext::default(),
+    );
+
+    // the invoice is matched against the journals of the supplier in the period
+    let invoice = service
+        .record(RecordSupplierInvoiceCommand {
+            supplier_id: "1".into(),
+            invoice_number: Some(" INV-001 ".into()),
+            period_start: 0,
+            period_end: 31 * DAY - 1,
+            due_date: 60 * DAY,
+            amount: 5100.0,
+        })
+        .unwrap();
+
+    assert_eq!(invoice.supplier_name, "SupplierA");
+    assert_eq!(invoice.invoice_number, Some("INV-001".into()));
+    assert_eq!(invoice.journal_ids, vec!["1".to_string(), "2".to_string()]);
+    assert_eq!(invoice.journal_amount, 5000.0);
+    assert_eq!(invoice.difference, 100.0);
+    assert_eq!(invoice.balance, 5100.0);
+
+    // suppliers of other stores cannot be billed from this one
+    let result = service.record(RecordSupplierInvoiceCommand {
+        supplier_id: "9".into(),
+        invoice_number: None,
+        period_start: 0,
+        period_end: 31 * DAY - 1,
+        due_date: 60 * DAY,
+        amount: 100.0,
+    });
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    let other = service
+        .record(RecordSupplierInvoiceCommand {
+            supplier_id: "2".into(),
+            invoice_number: None,
+            period_start: 0,
+            period_end: 31 * DAY - 1,
+            due_date: 30 * DAY,
+            amount: 500.0,
+        })
+        .unwrap();
+
+    assert_eq!(other.difference, 0.0);
+
+    // payments take the method of the billing terms unless given
+    let paid = service
+        .record_payment(RecordPaymentCommand {
+            invoice_id: other.id.clone(),
+            paid_date: 30 * DAY,
+            amount: 200.0,
+            payment_method: None,
+        })
+        .unwrap();
+
+    assert_eq!(paid.payments[0].payment_method, "direct_debit");
+    assert_eq!(paid.balance, 300.0);
+
+    let payment_id = paid.payments[0].id.clone();
+
+    let paid = service
+        .record_payment(RecordPaymentCommand {
+            invoice_id: invoice.id.clone(),
+            paid_date: 50 * DAY,
+            amount: 3000.0,
+            payment_method: None,
+        })
+        .unwrap();
+
+    assert_eq!(paid.payments[0].payment_method, "bank_transfer");
+    assert_eq!(paid.paid_amount, 3000.0);
+
+    // payments cannot exceed the outstanding balance
+    let result = service.record_payment(RecordPaymentCommand {
+        invoice_id: invoice.id.clone(),
+        paid_date: 55 * DAY,
+        amount: 2200.0,
+        payment_method: Some("cash".into()),
+    });
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    // nor can the invoice be cut below what has been paid
+    let result = service.update(UpdateSupplierInvoiceCommand {
+        invoice_id: invoice.id.clone(),
+        invoice_number: None,
+        period_start: 0,
+        period_end: 31 * DAY - 1,
+        due_date: 60 * DAY,
+        amount: 2000.0,
+    });
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    service
+        .update(UpdateSupplierInvoiceCommand {
+            invoice_id: invoice.id.clone(),
+            invoice_number: None,
+            period_start: 0,
+            period_end: 31 * DAY - 1,
+            due_date: 60 * DAY,
+            amount: 5000.0,
+        })
+        .unwrap();
+
+    let updated = service.get(&invoice.id).unwrap().unwrap();
+
+    assert_eq!(updated.invoice_number, None);
+    assert_eq!(updated.difference, 0.0);
+    assert_eq!(updated.balance, 2000.0);
+
+    // balances split by days past the due dates
+    let balances = service
+        .balances(PayableBalanceQuery { at: 75 * DAY })
+        .unwrap();
+
+    assert_eq!(
+        balances,
+        vec![
+            SupplierBalanceDTO {
+                supplier_id: "1".into(),
+                supplier_name: "SupplierA".into(),
+                invoiced: 5000.0,
+                paid: 3000.0,
+                balance: 2000.0,
+                current: 0.0,
+                overdue_1_30: 2000.0,
+                overdue_31_60: 0.0,
+                overdue_61_90: 0.0,
+                overdue_over_90: 0.0,
+            },
+            SupplierBalanceDTO {
+                supplier_id: "2".into(),
+                supplier_name: "SupplierB".into(),
+                invoiced: 500.0,
+                paid: 200.0,
+                balance: 300.0,
+                current: 0.0,
+                overdue_1_30: 0.0,
+                overdue_31_60: 300.0,
+                overdue_61_90: 0.0,
+                overdue_over_90: 0.0,
+            },
+        ]
+    );
+
+    // payments after the balance date are not counted
+    let balances = service
+        .balances(PayableBalanceQuery { at: 40 * DAY })
+        .unwrap();
+
+    assert_eq!(balances[0].balance, 5000.0);
+    assert_eq!(balances[0].current, 5000.0);
+    assert_eq!(balances[1].overdue_1_30, 300.0);
+
+    service
+        .cancel_payment(CancelPaymentCommand {
+            invoice_id: other.id.clone(),
+            payment_id,
+        })
+        .unwrap();
+
+    assert_eq!(service.get(&other.id).unwrap().unwrap().balance, 500.0);
+
+    let found = service
+        .search(SearchSupplierInvoicesQuery {
+            supplier_id: Some("2".into()),
+            period_start: None,
+            period_end: None,
+        })
+        .unwrap();
+
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, other.id);
+
+    // invoices of other stores are neither read nor changed from this one
+    let branch_service = PayableService::new(
+        Arc::new(MockSupplierInvoiceRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        StoreContext::Store(StoreId::new("2").unwrap()),
+    );
+
+    assert_eq!(branch_service.get(&other.id).unwrap(), None);
+
+    let result = branch_service.record_payment(RecordPaymentCommand {
+        invoice_id: other.id.clone(),
+        paid_date: 40 * DAY,
+        amount: 100.0,
+        payment_method: None,
+    });
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    let result = branch_service.delete(&other.id);
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    service.delete(&other.id).unwrap();
+
+    assert_eq!(service.list().unwrap().len(), 1);
+
+    let result = branch_service.purge(&other.id);
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+    assert_eq!(service.list_deleted().unwrap().len(), 1);
+}
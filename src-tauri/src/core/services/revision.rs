@@ -0,0 +1,91 @@
+//! This module provides helpers shared by the usecases keeping revisions of their records.
+
+use crate::core::domain::values::revision::*;
+use crate::core::provided_ports::*;
+use crate::core::*;
+
+/// Returns the version numbered `number`, the current version being numbered after the revisions.
+pub fn revision_item<'a, T>(
+    revisions: &'a [Revision<T>],
+    current: &'a T,
+    number: u32,
+) -> Result<&'a T> {
+    let number = RevisionNumber::new(number)?;
+
+    if number.as_u32() as usize == revisions.len() + 1 {
+        return Ok(current);
+    }
+
+    revisions
+        .iter()
+        .find(|revision| revision.number() == number)
+        .map(Revision::item)
+        .ok_or(Error::DomainError(format!("revision does not exist.")))
+}
+
+/// Returns the revision numbered `number` to revert to, failing for the current version.
+pub fn past_revision<T>(revisions: Vec<Revision<T>>, number: u32) -> Result<T> {
+    let number = RevisionNumber::new(number)?;
+
+    if number.as_u32() as usize == revisions.len() + 1 {
+        return Err(Error::DomainError(format!(
+            "revision is already the current version."
+        )));
+    }
+
+    revisions
+        .into_iter()
+        .find(|revision| revision.number() == number)
+        .map(Revision::into_item)
+        .ok_or(Error::DomainError(format!("revision does not exist.")))
+}
+
+/// Pairs DTOs of the revisions followed by the current version with their number and date and time.
+pub fn revision_dtos<T, D>(revisions: &[Revision<T>], dtos: Vec<D>) -> Vec<RevisionDTO<D>> {
+    dtos.into_iter()
+        .enumerate()
+        .map(|(index, item)| RevisionDTO {
+            number: index as u32 + 1,
+            revised_at: revisions
+                .get(index)
+                .map(|revision| revision.revised_at().as_i64()),
+            item,
+        })
+        .collect()
+}
+
+/// Converts the differences between two versions into DTOs.
+pub fn line_diff_dtos(diffs: &[LineDiff]) -> Result<Vec<LineDiffDTO>> {
+    diffs
+        .iter()
+        .map(|diff| {
+            let line = diff.line();
+
+            Ok(LineDiffDTO {
+                change: diff.change().as_str().to_string(),
+                supply_id: line.supply_id().to_string(),
+                supply_name: line.supply_name().to_string(),
+                supplier_id: line
+                    .supplier_id()
+                    .map(|supplier_id| supplier_id.to_string()),
+                supplier_name: line
+                    .supplier_name()
+                    .map(|supplier_name| supplier_name.to_string()),
+                location_id: line
+                    .location_id()
+                    .map(|location_id| location_id.to_string()),
+                lot_number: line.lot_number().map(|lot_number| lot_number.to_string()),
+                unit_name: line.unit_name().to_string(),
+                quantity_before: diff.before().map(|line| line.quantity().as_f64()),
+                quantity_after: diff.after().map(|line| line.quantity().as_f64()),
+                quantity_delta: diff.quantity_delta()?.as_f64(),
+                unit_price_before: diff.before().map(|line| line.unit_price().as_f64()),
+                unit_price_after: diff.after().map(|line| line.unit_price().as_f64()),
+                unit_price_delta: diff.unit_price_delta()?.as_f64(),
+                total_price_before: diff.before().map(|line| line.total_price().as_f64()),
+                total_price_after: diff.after().map(|line| line.total_price().as_f64()),
+                total_price_delta: diff.total_price_delta()?.as_f64(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,258 @@
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::price::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::tax::*;
+use crate::core::domain::values::unit::*;
+use crate::core::domain::values::valuation::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::{mock::*, *};
+use crate::core::services::stock::{journal::*, stocktaking::*};
+use crate::core::Error;
+use std::sync::{Arc, Mutex};
+
+fn storage_with_supplies() -> Arc<Mutex<Storage>> {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    MockSupplierRepository::new(Arc::clone(&storage))
+        .add(
+            &StoreContext::default(),
+            Supplier::restore(
+                SupplierId::new("1").unwrap(),
+                SupplierName::new("SupplierA").unwrap(),
+                None,
+                false,
+            ),
+            None,
+        )
+        .unwrap();
+
+    MockUnitRepository::new(Arc::clone(&storage))
+        .add(Unit::restore(
+            UnitId::new("1").unwrap(),
+            UnitName::new("kg").unwrap(),
+        ))
+        .unwrap();
+
+    for (id, name) in [("1", "Supply1"), ("2", "Supply2")] {
+        MockSupplyRepository::new(Arc::clone(&storage))
+            .add(
+                &StoreContext::default(),
+                Supply::new(
+                    SupplyId::new(id).unwrap(),
+                    SupplyName::new(name).unwrap(),
+                    UnitName::new("kg").unwrap(),
+                    SupplierId::new("1").unwrap(),
+                    TaxCategory::Standard,
+                    SupplyUnits::single(UnitId::new("1").unwrap()),
+                    None,
+                ),
+                None,
+            )
+            .unwrap();
+    }
+
+    storage
+}
+
+fn journal_record(supply_id: &str, unit_price: f64, quantity: f64) -> JournalRecordDTO {
+    JournalRecordDTO {
+        supply_id: supply_id.into(),
+        supply_name: format!("Supply{}", supply_id),
+        supplier_id: "1".into(),
+        supplier_name: "SupplierA".into(),
+        unit_name: "kg".into(),
+        unit_price,
+        quantity,
+        total_price: unit_price * quantity,
+        tax_category: None,
+        tax_included: None,
+        tax_price: 0.0,
+        invoice_registered: None,
+        lot_number: None,
+        expiry_date: None,
+    }
+}
+
+fn stocktaking_record(quantity: f64) -> StocktakingRecordDTO {
+    StocktakingRecordDTO {
+        supply_id: "1".into(),
+        supply_name: "Supply1".into(),
+        unit_name: "kg".into(),
+        unit_price: Some(100.0),
+        quantity,
+        total_price: Some(100.0 * quantity),
+        valuation_method: None,
+        location_id: None,
+        location_name: None,
+        lot_number: None,
+    }
+}
+
+#[test]
+fn journal_revision_test() {
+    let storage = storage_with_supplies();
+
+    let service = JournalService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        PriceAlertRule::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    let journal = service
+        .record(RecordJournalCommand {
+            entry_date: 1000,
+            total_price: 300.0,
+            records: vec![
+                journal_record("1", 100.0, 1.0),
+                journal_record("2", 50.0, 4.0),
+            ],
+        })
+        .unwrap();
+
+    // a recorded journal only has its current version
+    let revisions = service.list_revisions(&journal.id).unwrap();
+
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].number, 1);
+    assert_eq!(revisions[0].revised_at, None);
+
+    service
+        .edit(EditJournalCommand {
+            journal_id: journal.id.clone(),
+            total_price: 360.0,
+            records: vec![journal_record("1", 120.0, 3.0)],
+        })
+        .unwrap();
+
+    let revisions = service.list_revisions(&journal.id).unwrap();
+
+    assert_eq!(revisions.len(), 2);
+    assert!(revisions[0].revised_at.is_some());
+    assert_eq!(revisions[0].item.total_price, 300.0);
+    assert_eq!(revisions[1].item.total_price, 360.0);
+
+    let diffs = service.diff_revisions(&journal.id, 1, 2).unwrap();
+
+    assert_eq!(diffs.len(), 2);
+    assert_eq!(diffs[0].change, "changed");
+    assert_eq!(diffs[0].supply_id, "1");
+    assert_eq!(diffs[0].quantity_delta, 2.0);
+    assert_eq!(diffs[0].unit_price_delta, 20.0);
+    assert_eq!(diffs[0].total_price_delta, 260.0);
+    assert_eq!(diffs[1].change, "removed");
+    assert_eq!(diffs[1].supply_id, "2");
+    assert_eq!(diffs[1].quantity_after, None);
+    assert_eq!(diffs[1].quantity_delta, -4.0);
+
+    assert!(service
+        .diff_revisions(&journal.id, 2, 2)
+        .unwrap()
+        .is_empty());
+    assert!(matches!(
+        service.diff_revisions(&journal.id, 1, 3),
+        Err(Error::DomainError(_))
+    ));
+
+    // reverting is a new edit, keeping the version reverted from
+    assert!(matches!(
+        service.revert(&journal.id, 2),
+        Err(Error::DomainError(_))
+    ));
+
+    service.revert(&journal.id, 1).unwrap();
+
+    let revisions = service.list_revisions(&journal.id).unwrap();
+
+    assert_eq!(revisions.len(), 3);
+    assert_eq!(revisions[1].item.total_price, 360.0);
+    assert_eq!(revisions[2].item.total_price, 300.0);
+    assert_eq!(revisions[2].item.records.len(), 2);
+    assert!(service
+        .diff_revisions(&journal.id, 1, 3)
+        .unwrap()
+        .is_empty());
+
+    let diffs = service.diff_revisions(&journal.id, 2, 3).unwrap();
+
+    assert_eq!(
+        diffs
+            .iter()
+            .map(|diff| diff.change.as_str())
+            .collect::<Vec<_>>(),
+        vec!["changed", "added"]
+    );
+}
+
+#[test]
+fn stocktaking_revision_test() {
+    let storage = storage_with_supplies();
+
+    let service = StocktakingService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStorageLocationRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        ValuationMethod::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    let stocktaking = service
+        .record(RecordStocktakingCommand {
+            stocktaken_date: 1000,
+            total_price: None,
+            records: vec![stocktaking_record(2.0)],
+        })
+        .unwrap();
+
+    service
+        .edit(EditStocktakingCommand {
+            stocktaking_id: stocktaking.id.clone(),
+            total_price: None,
+            records: vec![stocktaking_record(5.0)],
+        })
+        .unwrap();
+
+    let diffs = service.diff_revisions(&stocktaking.id, 1, 2).unwrap();
+
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].change, "changed");
+    assert_eq!(diffs[0].quantity_before, Some(2.0));
+    assert_eq!(diffs[0].quantity_after, Some(5.0));
+    assert_eq!(diffs[0].total_price_delta, 300.0);
+
+    service.revert(&stocktaking.id, 1).unwrap();
+
+    let revisions = service.list_revisions(&stocktaking.id).unwrap();
+
+    assert_eq!(revisions.len(), 3);
+    assert_eq!(revisions[2].revised_at, None);
+    assert_eq!(revisions[2].item.total_price, 200.0);
+
+    // purging a stocktaking takes its revisions with it
+    service.delete(&stocktaking.id).unwrap();
+    service.purge(&stocktaking.id).unwrap();
+
+    assert!(MockStocktakingRepository::new(Arc::clone(&storage))
+        .list_revisions(
+            &StoreContext::default(),
+            StocktakingId::new(stocktaking.id).unwrap()
+        )
+        .unwrap()
+        .is_empty());
+}
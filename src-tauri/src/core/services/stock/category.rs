@@ -0,0 +1,338 @@
+//! This module provides the implementation of the `CategoryUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::category::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::services::trash::*;
+use crate::core::*;
+
+/// Category usecase
+pub struct CategoryService {
+    category_repository: Arc<dyn ForCategoryPersistence>,
+    supply_repository: Arc<dyn ForSupplyPersistence>,
+}
+
+impl CategoryService {
+    pub fn new(
+        category_repository: Arc<dyn ForCategoryPersistence>,
+        supply_repository: Arc<dyn ForSupplyPersistence>,
+    ) -> Self {
+        Self {
+            category_repository,
+            supply_repository,
+        }
+    }
+
+    /// Returns the parent category, failing if it does not exist.
+    fn parent_of(&self, parent_id: Option<String>) -> Result<Option<CategoryId>> {
+        let parent_id = parent_id.map(CategoryId::new).transpose()?;
+
+        if let Some(parent_id) = &parent_id {
+            if self.category_repository.get(parent_id.clone())?.is_none() {
+                return Err(Error::DomainError(format!(
+                    "parent category does not exist."
+                )));
+            }
+        }
+
+        Ok(parent_id)
+    }
+}
+
+/// Converts a category into its DTO.
+fn category_dto(category: &Category) -> CategoryDTO {
+    CategoryDTO {
+        id: category.id().to_string(),
+        name: category.name().to_string(),
+        parent_id: category.parent_id().map(|parent_id| parent_id.to_string()),
+    }
+}
+
+/// Category usecase implementation
+impl CategoryUsecase for CategoryService {
+    fn list(&self) -> Result<Vec<CategoryDTO>> {
+        let categories = self.category_repository.list()?;
+
+        let categories: Vec<CategoryDTO> = ordered_categories(&categories)
+            .into_iter()
+            .map(category_dto)
+            .collect();
+
+        Ok(categories)
+    }
+
+    fn get(&self, category_id: impl AsRef<str>) -> Result<Option<CategoryDTO>> {
+        let category_id = CategoryId::new(category_id.as_ref())?;
+
+        let category = self.category_repository.get(category_id)?;
+
+        Ok(category.as_ref().map(category_dto))
+    }
+
+    fn register(&self, command: RegisterCategoryCommand) -> Result<CategoryDTO> {
+        let parent_id = self.parent_of(command.parent_id)?;
+
+        let category = Category::restore(
+            self.category_repository.next_id()?,
+            CategoryName::new(command.category_name)?,
+            parent_id,
+        );
+
+        self.category_repository.add(category.clone())?;
+
+        Ok(category_dto(&category))
+    }
+
+    fn update(&self, command: UpdateCategoryCommand) -> Result<()> {
+        let category_id = CategoryId::new(command.category_id)?;
+
+        let mut category = self
+            .category_repository
+            .get(category_id)?
+            .ok_or(Error::DomainError(format!("category does not exist.")))?;
+
+        let parent_id = self.parent_of(command.parent_id)?;
+
+        if let Some(parent_id) = &parent_id {
+            let categories = self.category_repository.list()?;
+
+            if category_subtree(&categories, category.id()).contains(parent_id) {
+                return Err(Error::DomainError(format!(
+                    "category cannot be moved under its descendant."
+                )));
+            }
+        }
+
+        category.rename(CategoryName::new(command.category_name)?);
+        category.move_to(parent_id)?;
+
+        self.category_repository.save(category)?;
+
+        Ok(())
+    }
+
+    fn delete(&self, category_id: impl AsRef<str>) -> Result<()> {
+        let category_id = CategoryId::new(category_id.as_ref())?;
+
+        let has_children = self
+            .category_repository
+            .list()?
+            .iter()
+            .any(|category| category.parent_id() == Some(&category_id));
+
+        if has_children {
+            return Err(Error::DomainError(format!("category has subcategories.")));
+        }
+
+        // categories are shared by the stores
+        let in_use = self
+            .supply_repository
+            .list(&StoreContext::Consolidated)?
+            .iter()
+            .any(|supply| supply.category_id() == Some(&category_id));
+
+        if in_use {
+            return Err(Error::DomainError(format!("category is used by supplies.")));
+        }
+
+        self.category_repository.delete(category_id)?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<CategoryDTO>>> {
+        let trashed = self.category_repository.list_deleted()?;
+
+        let dtos = trashed
+            .iter()
+            .map(|trashed| category_dto(trashed.item()))
+            .collect();
+
+        Ok(trashed_dtos(&trashed, dtos))
+    }
+
+    fn restore(&self, category_id: impl AsRef<str>) -> Result<()> {
+        let category_id = CategoryId::new(category_id.as_ref())?;
+
+        let category = trashed_item(
+            self.category_repository.list_deleted()?,
+            |category| category.id() == &category_id,
+            "category",
+        )?;
+
+        if let Some(parent_id) = category.parent_id() {
+            if self.category_repository.get(parent_id.clone())?.is_none() {
+                return Err(Error::DomainError(format!(
+                    "parent category does not exist."
+                )));
+            }
+        }
+
+        self.category_repository.restore(category.id().clone())?;
+
+        Ok(())
+    }
+
+    fn purge(&self, category_id: impl AsRef<str>) -> Result<()> {
+        let category_id = CategoryId::new(category_id.as_ref())?;
+
+        let category = trashed_item(
+            self.category_repository.list_deleted()?,
+            |category| category.id() == &category_id,
+            "category",
+        )?;
+
+        self.category_repository.purge(category.id().clone())?;
+
+        Ok(())
+    }
+}
+
+/// Returns `categories` with parents before their children, siblings kept in order.
+fn ordered_categories(categories: &[Category]) -> Vec<&Category> {
+    fn visit<'a>(
+        categories: &'a [Category],
+        parent_id: Option<&CategoryId>,
+        ordered: &mut Vec<&'a Category>,
+    ) {
+        for category in categories
+            .iter()
+            .filter(|category| category.parent_id() == parent_id)
+        {
+            ordered.push(category);
+            visit(categories, Some(category.id()), ordered);
+        }
+    }
+
+    let mut ordered: Vec<&Category> = Vec::new();
+
+    visit(categories, None, &mut ordered);
+
+    ordered
+}
+
+/// Returns `category_id` and the ids of all its descendants.
+pub fn category_subtree(categories: &[Category], category_id: &CategoryId) -> Vec<CategoryId> {
+    let mut subtree = vec![category_id.clone()];
+    let mut index = 0;
+
+    while index < subtree.len() {
+        let children: Vec<CategoryId> = categories
+            .iter()
+            .filter(|category| category.parent_id() == Some(&subtree[index]))
+            .map(|category| category.id().clone())
+            .collect();
+
+        for child in children {
+            if !subtree.contains(&child) {
+                subtree.push(child);
+            }
+        }
+
+        index += 1;
+    }
+
+    subtree
+}
+
+/// Returns the ids of supplies in `category_id` or its descendants, none if no category is given.
+pub fn supplies_in_category(
+    category_repository: &dyn ForCategoryPersistence,
+    supply_repository: &dyn ForSupplyPersistence,
+    store: &StoreContext,
+    category_id: Option<String>,
+) -> Result<Option<Vec<SupplyId>>> {
+    let category_id = match category_id.filter(|id| !id.trim().is_empty()) {
+        Some(category_id) => CategoryId::new(category_id)?,
+        None => return Ok(None),
+    };
+
+    let categories = category_repository.list()?;
+
+    if !categories
+        .iter()
+        .any(|category| category.id() == &category_id)
+    {
+        return Err(Error::DomainError(format!("category does not exist.")));
+    }
+
+    let subtree = category_subtree(&categories, &category_id);
+
+    let supply_ids = supply_repository
+        .list(store)?
+        .iter()
+        .filter(|supply| {
+            supply
+                .category_id()
+                .is_some_and(|category_id| subtree.contains(category_id))
+        })
+        .map(|supply| supply.id().clone())
+        .collect();
+
+    Ok(Some(supply_ids))
+}
+
+/// Sums line totals per category, each category including its descendants.
+///
+/// Categories are listed parents first and only if they have lines,
+/// followed by the lines of supplies not categorized.
+pub fn category_totals(
+    categories: &[Category],
+    supplies: &[Supply],
+    lines: Vec<(SupplyId, Money)>,
+) -> Result<Vec<CategoryTotalDTO>> {
+    let mut own_totals: Vec<(Option<CategoryId>, Money)> = Vec::new();
+
+    for (supply_id, total) in lines {
+        let category_id = supplies
+            .iter()
+            .find(|supply| supply.id() == &supply_id)
+            .and_then(|supply| supply.category_id())
+            .filter(|category_id| categories.iter().any(|c| c.id() == *category_id))
+            .cloned();
+
+        match own_totals.iter_mut().find(|own| own.0 == category_id) {
+            Some(own) => own.1 = own.1.checked_add(total)?,
+            None => own_totals.push((category_id, total)),
+        }
+    }
+
+    let mut totals: Vec<CategoryTotalDTO> = Vec::new();
+
+    for category in ordered_categories(categories) {
+        let subtree = category_subtree(categories, category.id());
+
+        let in_subtree: Vec<Money> = own_totals
+            .iter()
+            .filter(|own| own.0.as_ref().is_some_and(|id| subtree.contains(id)))
+            .map(|own| own.1)
+            .collect();
+
+        if in_subtree.is_empty() {
+            continue;
+        }
+
+        totals.push(CategoryTotalDTO {
+            category_id: Some(category.id().to_string()),
+            category_name: Some(category.name().to_string()),
+            parent_id: category.parent_id().map(|parent_id| parent_id.to_string()),
+            total_price: Money::checked_sum(in_subtree)?.as_f64(),
+        });
+    }
+
+    if let Some(own) = own_totals.iter().find(|own| own.0.is_none()) {
+        totals.push(CategoryTotalDTO {
+            category_id: None,
+            category_name: None,
+            parent_id: None,
+            total_price: own.1.as_f64(),
+        });
+    }
+
+    Ok(totals)
+}
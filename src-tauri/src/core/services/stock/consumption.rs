@@ -0,0 +1,170 @@
+//! This module provides the implementation of the `ConsumptionUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::services::stock::unit::stock_quantity_of;
+use crate::core::services::trash::*;
+use crate::core::*;
+
+/// Consumption usecase
+pub struct ConsumptionService {
+    supply_respository: Arc<dyn ForSupplyPersistence>,
+    consumption_repository: Arc<dyn ForConsumptionPersistence>,
+    unit_repository: Arc<dyn ForUnitPersistence>,
+    store: StoreContext,
+}
+
+impl ConsumptionService {
+    pub fn new(
+        supply_respository: Arc<dyn ForSupplyPersistence>,
+        consumption_repository: Arc<dyn ForConsumptionPersistence>,
+        unit_repository: Arc<dyn ForUnitPersistence>,
+        store: StoreContext,
+    ) -> Self {
+        Self {
+            supply_respository,
+            consumption_repository,
+            unit_repository,
+            store,
+        }
+    }
+}
+
+/// Converts a consumption into its DTO.
+fn consumption_dto(consumption: &Consumption) -> ConsumptionDTO {
+    ConsumptionDTO {
+        id: consumption.id().to_string(),
+        consumed_date: consumption.consumed_at().as_i64(),
+        records: consumption
+            .records()
+            .iter()
+            .map(|record| ConsumptionRecordDTO {
+                supply_id: record.supply_id().to_string(),
+                supply_name: record.supply_name().to_string(),
+                unit_name: record.unit_name().to_string(),
+                quantity: record.quantity().as_f64(),
+            })
+            .collect(),
+    }
+}
+
+/// Consumption usecase implementation
+impl ConsumptionUsecase for ConsumptionService {
+    fn list(&self) -> Result<Vec<ConsumptionDTO>> {
+        let consumptions = self.consumption_repository.list(&self.store)?;
+
+        Ok(consumptions.iter().map(consumption_dto).collect())
+    }
+
+    fn search(&self, query: SearchConsumptionsQuery) -> Result<Vec<ConsumptionDTO>> {
+        let query = FindConsumptionsQuery {
+            period_start: query.period_start.map(ConsumedDateTime::new),
+            period_end: query.period_end.map(ConsumedDateTime::new),
+        };
+
+        let consumptions = self.consumption_repository.find(&self.store, query)?;
+
+        Ok(consumptions.iter().map(consumption_dto).collect())
+    }
+
+    fn record(&self, command: RecordConsumptionCommand) -> Result<ConsumptionDTO> {
+        let id = self.consumption_repository.next_id()?;
+
+        let supplies = self.supply_respository.list(&self.store)?;
+        let units = self.unit_repository.list()?;
+
+        let mut records: Vec<ConsumptionRecord> = Vec::new();
+
+        for record in &command.records {
+            let supply_id = SupplyId::new(&record.supply_id)?;
+            let unit_name = UnitName::new(&record.unit_name)?;
+            let quantity = ConsumptionQuantity::new(record.quantity)?;
+
+            if !supplies.iter().any(|supply| supply.id() == &supply_id) {
+                return Err(Error::DomainError(format!("supply does not exist.")));
+            }
+
+            // the unit must be convertible into the stock unit
+            stock_quantity_of(
+                &supplies,
+                &units,
+                &supply_id,
+                unit_name.clone(),
+                quantity.as_quantity(),
+            )?;
+
+            records.push(ConsumptionRecord::new(
+                supply_id,
+                SupplyName::new(&record.supply_name)?,
+                unit_name,
+                quantity,
+            ));
+        }
+
+        let consumption =
+            Consumption::record(id, ConsumedDateTime::new(command.consumed_date), records)?;
+
+        self.consumption_repository
+            .add(&self.store, consumption.clone())?;
+
+        Ok(consumption_dto(&consumption))
+    }
+
+    fn delete(&self, consumption_id: impl AsRef<str>) -> Result<()> {
+        let consumption_id = ConsumptionId::new(consumption_id.as_ref())?;
+
+        self.consumption_repository
+            .delete(&self.store, consumption_id)?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<ConsumptionDTO>>> {
+        let trashed = self.consumption_repository.list_deleted(&self.store)?;
+
+        let dtos = trashed
+            .iter()
+            .map(|trashed| consumption_dto(trashed.item()))
+            .collect();
+
+        Ok(trashed_dtos(&trashed, dtos))
+    }
+
+    fn restore(&self, consumption_id: impl AsRef<str>) -> Result<()> {
+        let consumption_id = ConsumptionId::new(consumption_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let consumption = trashed_item(
+            self.consumption_repository.list_deleted(&store)?,
+            |consumption| consumption.id() == &consumption_id,
+            "consumption",
+        )?;
+
+        self.consumption_repository
+            .restore(&store, consumption.id().clone())?;
+
+        Ok(())
+    }
+
+    fn purge(&self, consumption_id: impl AsRef<str>) -> Result<()> {
+        let consumption_id = ConsumptionId::new(consumption_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let consumption = trashed_item(
+            self.consumption_repository.list_deleted(&store)?,
+            |consumption| consumption.id() == &consumption_id,
+            "consumption",
+        )?;
+
+        self.consumption_repository
+            .purge(&store, consumption.id().clone())?;
+
+        Ok(())
+    }
+}
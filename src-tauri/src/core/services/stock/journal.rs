@@ -3,9 +3,23 @@ use std::collections::HashSet;
 use std::sync::Arc;
 
 use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::event::*;
+use crate::core::domain::values::lot::*;
+use crate::core::domain::values::price::*;
+use crate::core::domain::values::revision::*;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::tax::*;
 use crate::core::provided_ports::*;
 use crate::core::required_ports::*;
+use crate::core::services::accounting::ensure_open;
+use crate::core::services::audit::snapshot;
+use crate::core::services::revision::*;
+use crate::core::services::stock::category::{category_totals, supplies_in_category};
+use crate::core::services::stock::unit::aggregate_stock_quantities;
+use crate::core::services::trash::*;
 use crate::core::*;
 
 /// Journal usecase
@@ -13,142 +27,453 @@ pub struct JournalService {
     supply_respository: Arc<dyn ForSupplyPersistence>,
     supplier_repository: Arc<dyn ForSupplierPersistence>,
     journal_respository: Arc<dyn ForJournalPersistence>,
+    unit_repository: Arc<dyn ForUnitPersistence>,
+    category_repository: Arc<dyn ForCategoryPersistence>,
+    period_repository: Arc<dyn ForAccountingPeriodPersistence>,
+    event_publisher: Arc<dyn ForEventPublishing>,
+    rounding_policy: RoundingPolicy,
+    price_alert_rule: PriceAlertRule,
+    operator: Option<OperatorName>,
+    store: StoreContext,
 }
 
 impl JournalService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         supply_respository: Arc<dyn ForSupplyPersistence>,
         supplier_repository: Arc<dyn ForSupplierPersistence>,
         journal_respository: Arc<dyn ForJournalPersistence>,
+        unit_repository: Arc<dyn ForUnitPersistence>,
+        category_repository: Arc<dyn ForCategoryPersistence>,
+        period_repository: Arc<dyn ForAccountingPeriodPersistence>,
+        event_publisher: Arc<dyn ForEventPublishing>,
+        rounding_policy: RoundingPolicy,
+        price_alert_rule: PriceAlertRule,
+        operator: Option<OperatorName>,
+        store: StoreContext,
     ) -> Self {
         Self {
             supply_respository,
             supplier_repository,
             journal_respository,
+            unit_repository,
+            category_repository,
+            period_repository,
+            event_publisher,
+            rounding_policy,
+            price_alert_rule,
+            operator,
+            store,
         }
     }
-}
 
-/// Journal usecase implementation
-impl JournalUsecase for JournalService {
-    fn get(&self, journal_id: impl AsRef<str>) -> Result<Option<JournalDTO>> {
-        let journal_id = JournalId::new(journal_id.as_ref())?;
+    /// Returns the change of a journal to log in the audit log.
+    fn change_of(
+        &self,
+        journal_id: &JournalId,
+        operation: AuditOperation,
+        before: Option<&Journal>,
+        after: Option<&Journal>,
+    ) -> Result<AuditChange> {
+        AuditChange::new(
+            AuditEntity::Journal,
+            journal_id.as_str(),
+            operation,
+            self.operator.clone(),
+            before
+                .map(|journal| snapshot(&journal_dto(journal, &self.rounding_policy)?))
+                .transpose()?,
+            after
+                .map(|journal| snapshot(&journal_dto(journal, &self.rounding_policy)?))
+                .transpose()?,
+        )
+    }
 
-        let journal = self.journal_respository.get(journal_id)?;
+    /// Finds journals matching `query`.
+    ///
+    /// With a category, only the lines of supplies in it are kept
+    /// and journals without such lines are left out.
+    fn find(&self, query: SearchJournalsQuery) -> Result<Vec<Journal>> {
+        let supply_ids = supplies_in_category(
+            &*self.category_repository,
+            &*self.supply_respository,
+            &self.store,
+            query.category_id.clone(),
+        )?;
+
+        let mut journals = self
+            .journal_respository
+            .find(&self.store, find_query(query)?)?;
 
-        if journal.is_none() {
-            return Ok(None);
+        if let Some(supply_ids) = supply_ids {
+            for journal in journals.iter_mut() {
+                let records = journal
+                    .records()
+                    .iter()
+                    .filter(|record| supply_ids.contains(record.supply_id()))
+                    .cloned()
+                    .collect();
+
+                journal.swap_records(records);
+            }
+
+            journals.retain(|journal| !journal.records().is_empty());
         }
 
-        let journal = journal.unwrap();
+        Ok(journals)
+    }
+
+    /// Flags the lines of `journal` whose unit price deviates from recent purchases
+    /// of the same supply from the same supplier in the same unit.
+    fn price_warnings(&self, journal: &Journal) -> Result<Vec<PriceWarningDTO>> {
+        let mut journals = self.journal_respository.find(
+            &self.store,
+            FindJournalsQuery {
+                period_start: None,
+                period_end: Some(journal.entry_datetime().clone()),
+                supplier_name: None,
+                supply_name: None,
+            },
+        )?;
 
-        let journal = JournalDTO {
-            id: journal.id().to_string(),
-            entry_date: journal.entry_datetime().as_i64(),
-            total_price: journal.total_price().as_f64(),
-            records: journal
-                .records()
+        journals.retain(|other| other.id() != journal.id());
+
+        // latest first
+        journals.sort_by_key(|other| std::cmp::Reverse(other.entry_datetime().as_i64()));
+
+        let mut warnings: Vec<PriceWarningDTO> = Vec::new();
+
+        for (index, record) in journal.records().iter().enumerate() {
+            let recent: Vec<Money> = journals
                 .iter()
-                .map(|record| JournalRecordDTO {
-                    supplier_id: record.supplier_id().to_string(),
-                    supplier_name: record.supplier_name().to_string(),
+                .flat_map(|other| other.records())
+                .filter(|other| {
+                    other.supply_id() == record.supply_id()
+                        && other.supplier_id() == record.supplier_id()
+                        && other.unit_name() == record.unit_name()
+                })
+                .map(|other| other.unit_price().as_money())
+                .collect();
+
+            let deviation = self
+                .price_alert_rule
+                .check(&recent, record.unit_price().as_money())?;
+
+            if let Some(deviation) = deviation {
+                warnings.push(PriceWarningDTO {
+                    line: index + 1,
                     supply_id: record.supply_id().to_string(),
                     supply_name: record.supply_name().to_string(),
+                    supplier_id: record.supplier_id().to_string(),
                     unit_name: record.unit_name().to_string(),
                     unit_price: record.unit_price().as_f64(),
-                    quantity: record.quantity().as_f64(),
-                    total_price: record.total_price().as_f64(),
+                    average_price: deviation.average().as_f64(),
+                    deviation_percent: deviation.percent(),
+                });
+            }
+        }
+
+        Ok(warnings)
+    }
+
+    /// Builds journal records from DTOs.
+    ///
+    /// The tax category of a record defaults to the one of its supply,
+    /// and the invoice registration to the current one of its supplier.
+    /// Fails if the supplier of a record does not offer its supply.
+    fn records_of(&self, records: &[JournalRecordDTO]) -> Result<Vec<JournalRecord>> {
+        let mut journal_records: Vec<JournalRecord> = Vec::new();
+
+        for record in records {
+            let supply_id = SupplyId::new(&record.supply_id)?;
+            let supplier_id = SupplierId::new(&record.supplier_id)?;
+
+            let supply = self
+                .supply_respository
+                .get(&self.store, supply_id.clone())?;
+
+            if let Some(supply) = &supply {
+                if !supply.is_offered_by(&supplier_id) {
+                    return Err(Error::DomainError(format!(
+                        "{} is not offered by supplier {}.",
+                        supply.name().as_str(),
+                        supplier_id.as_str()
+                    )));
+                }
+            }
+
+            let tax_category = match &record.tax_category {
+                Some(tax_category) => TaxCategory::new(tax_category)?,
+                None => supply
+                    .map(|supply| supply.tax_category())
+                    .unwrap_or_default(),
+            };
+
+            let invoice_registered = match record.invoice_registered {
+                Some(invoice_registered) => invoice_registered,
+                None => self
+                    .supplier_repository
+                    .get(&self.store, supplier_id.clone())?
+                    .is_some_and(|supplier| supplier.is_invoice_registered()),
+            };
+
+            let lot_number = record
+                .lot_number
+                .as_deref()
+                .filter(|lot_number| !lot_number.trim().is_empty())
+                .map(LotNumber::new)
+                .transpose()?;
+
+            journal_records.push(
+                JournalRecord::new(
+                    supply_id,
+                    SupplyName::new(&record.supply_name)?,
+                    supplier_id,
+                    SupplierName::new(&record.supplier_name)?,
+                    UnitName::new(&record.unit_name)?,
+                    PurchaseUnitPrice::new(record.unit_price)?,
+                    PurchaseQuantity::new(record.quantity)?,
+                    TotalPrice::new(record.total_price)?,
+                    tax_category,
+                    record.tax_included.unwrap_or(true),
+                    TotalPrice::from_money(Money::ZERO)?,
+                    invoice_registered,
+                )
+                .with_lot(lot_number, record.expiry_date.map(ExpiryDate::new)),
+            );
+        }
+
+        Ok(journal_records)
+    }
+}
+
+/// Converts a journal into its DTO.
+fn journal_dto(journal: &Journal, policy: &RoundingPolicy) -> Result<JournalDTO> {
+    let tax_summaries = journal.tax_summaries(policy)?;
+
+    let deductible_tax_price = Money::checked_sum(
+        tax_summaries
+            .iter()
+            .map(|summary| summary.deductible_tax_price()),
+    )?;
+
+    let non_deductible_tax_price = journal.tax_price()?.checked_sub(deductible_tax_price)?;
+
+    Ok(JournalDTO {
+        id: journal.id().to_string(),
+        entry_date: journal.entry_datetime().as_i64(),
+        total_price: journal.total_price().as_f64(),
+        net_price: journal.net_price()?.as_f64(),
+        tax_price: journal.tax_price()?.as_f64(),
+        gross_price: journal.gross_price()?.as_f64(),
+        deductible_tax_price: deductible_tax_price.as_f64(),
+        non_deductible_tax_price: non_deductible_tax_price.as_f64(),
+        tax_summaries: tax_summaries
+            .iter()
+            .map(|summary| {
+                Ok(TaxSummaryDTO {
+                    tax_category: summary.category().to_string(),
+                    tax_rate: summary.category().rate_percent(),
+                    net_price: summary.net_price().as_f64(),
+                    tax_price: summary.tax_price().as_f64(),
+                    gross_price: summary.gross_price().as_f64(),
+                    deductible_tax_price: summary.deductible_tax_price().as_f64(),
+                    non_deductible_tax_price: summary.non_deductible_tax_price()?.as_f64(),
                 })
-                .collect(),
-        };
+            })
+            .collect::<Result<Vec<TaxSummaryDTO>>>()?,
+        records: journal
+            .records()
+            .iter()
+            .map(|record| JournalRecordDTO {
+                supplier_id: record.supplier_id().to_string(),
+                supplier_name: record.supplier_name().to_string(),
+                supply_id: record.supply_id().to_string(),
+                supply_name: record.supply_name().to_string(),
+                unit_name: record.unit_name().to_string(),
+                unit_price: record.unit_price().as_f64(),
+                quantity: record.quantity().as_f64(),
+                total_price: record.total_price().as_f64(),
+                tax_category: Some(record.tax_category().to_string()),
+                tax_included: Some(record.is_tax_included()),
+                tax_price: record.tax_price().as_f64(),
+                invoice_registered: Some(record.is_invoice_registered()),
+                lot_number: record.lot_number().map(|lot_number| lot_number.to_string()),
+                expiry_date: record.expiry_date().map(|expiry_date| expiry_date.as_i64()),
+            })
+            .collect(),
+        warnings: vec![],
+    })
+}
+
+/// Converts a search query into a persistence query.
+fn find_query(query: SearchJournalsQuery) -> Result<FindJournalsQuery> {
+    Ok(FindJournalsQuery {
+        period_start: query.period_start.map(EntryDateTime::new),
+        period_end: query.period_end.map(EntryDateTime::new),
+        supplier_name: query
+            .supplier_name
+            .and_then(|name| {
+                if name.trim().is_empty() {
+                    None
+                } else {
+                    Some(SupplierName::new(name.trim()))
+                }
+            })
+            .transpose()?,
+        supply_name: query
+            .supply_name
+            .and_then(|name| {
+                if name.trim().is_empty() {
+                    None
+                } else {
+                    Some(SupplyName::new(name.trim()))
+                }
+            })
+            .transpose()?,
+    })
+}
+
+/// Journal usecase implementation
+impl JournalUsecase for JournalService {
+    fn get(&self, journal_id: impl AsRef<str>) -> Result<Option<JournalDTO>> {
+        let journal_id = JournalId::new(journal_id.as_ref())?;
+
+        let journal = self.journal_respository.get(&self.store, journal_id)?;
+
+        if journal.is_none() {
+            return Ok(None);
+        }
+
+        let journal = journal_dto(&journal.unwrap(), &self.rounding_policy)?;
 
         Ok(Some(journal))
     }
 
     fn list(&self) -> Result<Vec<JournalDTO>> {
-        let journals = self.journal_respository.list()?;
+        let journals = self.journal_respository.list(&self.store)?;
 
-        let journals: Vec<JournalDTO> = journals
+        let journals = journals
             .iter()
-            .map(|journal| JournalDTO {
-                id: journal.id().to_string(),
-                entry_date: journal.entry_datetime().as_i64(),
-                total_price: journal.total_price().as_f64(),
-                records: journal
-                    .records()
-                    .iter()
-                    .map(|record| JournalRecordDTO {
-                        supplier_id: record.supplier_id().to_string(),
-                        supplier_name: record.supplier_name().to_string(),
-                        supply_id: record.supply_id().to_string(),
-                        supply_name: record.supply_name().to_string(),
-                        unit_name: record.unit_name().to_string(),
-                        unit_price: record.unit_price().as_f64(),
-                        quantity: record.quantity().as_f64(),
-                        total_price: record.total_price().as_f64(),
-                    })
-                    .collect(),
-            })
-            .collect();
+            .map(|journal| journal_dto(journal, &self.rounding_policy))
+            .collect::<Result<Vec<JournalDTO>>>()?;
 
         Ok(journals)
     }
 
     fn search(&self, query: SearchJournalsQuery) -> Result<Vec<JournalDTO>> {
-        let query = FindJournalsQuery {
-            period_start: query.period_start.map(|start| EntryDateTime::new(start)),
-            period_end: query.period_end.map(|end| EntryDateTime::new(end)),
-            supplier_name: query
-                .supplier_name
-                .and_then(|name| {
-                    if name.trim().is_empty() {
-                        None
-                    } else {
-                        Some(SupplierName::new(name.trim()))
-                    }
-                })
-                .transpose()?,
-            supply_name: query
-                .supply_name
-                .and_then(|name| {
-                    if name.trim().is_empty() {
-                        None
-                    } else {
-                        Some(SupplyName::new(name.trim()))
-                    }
-                })
-                .transpose()?,
-        };
+        let journals = self.find(query)?;
 
-        let journals = self.journal_respository.find(query)?;
+        let journals = journals
+            .iter()
+            .map(|journal| journal_dto(journal, &self.rounding_policy))
+            .collect::<Result<Vec<JournalDTO>>>()?;
 
-        let journals: Vec<JournalDTO> = journals
+        Ok(journals)
+    }
+
+    fn aggregate_quantities(&self, query: SearchJournalsQuery) -> Result<Vec<StockQuantityDTO>> {
+        let lines = self
+            .find(query)?
             .iter()
-            .map(|journal| JournalDTO {
-                id: journal.id().to_string(),
-                entry_date: journal.entry_datetime().as_i64(),
-                total_price: journal.total_price().as_f64(),
-                records: journal
-                    .records()
-                    .iter()
-                    .map(|record| JournalRecordDTO {
-                        supplier_id: record.supplier_id().to_string(),
-                        supplier_name: record.supplier_name().to_string(),
+            .flat_map(|journal| journal.records().to_vec())
+            .map(|record| {
+                (
+                    record.supply_id().clone(),
+                    record.supply_name().clone(),
+                    record.unit_name().clone(),
+                    record.quantity().as_quantity(),
+                )
+            })
+            .collect();
+
+        aggregate_stock_quantities(&*self.supply_respository, &*self.unit_repository, lines)
+    }
+
+    fn price_history(&self, query: PriceHistoryQuery) -> Result<Vec<PriceHistoryDTO>> {
+        let supply_id = query.supply_id.map(SupplyId::new).transpose()?;
+        let supplier_id = query.supplier_id.map(SupplierId::new).transpose()?;
+
+        let mut journals = self.journal_respository.find(
+            &self.store,
+            FindJournalsQuery {
+                period_start: query.period_start.map(EntryDateTime::new),
+                period_end: query.period_end.map(EntryDateTime::new),
+                supplier_name: None,
+                supply_name: None,
+            },
+        )?;
+
+        journals.sort_by_key(|journal| journal.entry_datetime().as_i64());
+
+        let mut histories: Vec<PriceHistoryDTO> = Vec::new();
+
+        for journal in &journals {
+            for record in journal.records() {
+                if supply_id
+                    .as_ref()
+                    .is_some_and(|id| id != record.supply_id())
+                    || supplier_id
+                        .as_ref()
+                        .is_some_and(|id| id != record.supplier_id())
+                {
+                    continue;
+                }
+
+                let point = PricePointDTO {
+                    journal_id: journal.id().to_string(),
+                    entry_date: journal.entry_datetime().as_i64(),
+                    unit_price: record.unit_price().as_f64(),
+                };
+
+                match histories.iter_mut().find(|history| {
+                    history.supply_id == record.supply_id().as_str()
+                        && history.supplier_id == record.supplier_id().as_str()
+                        && history.unit_name == record.unit_name().as_str()
+                }) {
+                    Some(history) => {
+                        // names recorded on the latest line
+                        history.supply_name = record.supply_name().to_string();
+                        history.supplier_name = record.supplier_name().to_string();
+                        history.prices.push(point);
+                    }
+                    None => histories.push(PriceHistoryDTO {
                         supply_id: record.supply_id().to_string(),
                         supply_name: record.supply_name().to_string(),
+                        supplier_id: record.supplier_id().to_string(),
+                        supplier_name: record.supplier_name().to_string(),
                         unit_name: record.unit_name().to_string(),
-                        unit_price: record.unit_price().as_f64(),
-                        quantity: record.quantity().as_f64(),
-                        total_price: record.total_price().as_f64(),
-                    })
-                    .collect(),
-            })
+                        prices: vec![point],
+                    }),
+                }
+            }
+        }
+
+        Ok(histories)
+    }
+
+    fn totals_by_category(&self, query: SearchJournalsQuery) -> Result<Vec<CategoryTotalDTO>> {
+        let lines = self
+            .find(query)?
+            .iter()
+            .flat_map(|journal| journal.records().to_vec())
+            .map(|record| (record.supply_id().clone(), record.total_price().as_money()))
             .collect();
 
-        Ok(journals)
+        category_totals(
+            &self.category_repository.list()?,
+            &self.supply_respository.list(&self.store)?,
+            lines,
+        )
     }
 
     fn record(&self, command: RecordJournalCommand) -> Result<JournalDTO> {
+        ensure_open(
+            self.period_repository.as_ref(),
+            &self.store,
+            command.entry_date,
+        )?;
+
         let id = self.journal_respository.next_id()?;
 
         let supply_ids: Vec<SupplyId> = command
@@ -159,7 +484,7 @@ impl JournalUsecase for JournalService {
             .into_iter()
             .collect::<Vec<SupplyId>>();
 
-        let supply_exists = self.supply_respository.has(&supply_ids)?;
+        let supply_exists = self.supply_respository.has(&self.store, &supply_ids)?;
 
         if !supply_exists {
             return Err(Error::DomainError(format!("supply does not exist.")));
@@ -173,55 +498,36 @@ impl JournalUsecase for JournalService {
             .into_iter()
             .collect::<Vec<SupplierId>>();
 
-        let supplier_exists = self.supplier_repository.has(&supplier_ids)?;
+        let supplier_exists = self.supplier_repository.has(&self.store, &supplier_ids)?;
 
         if !supplier_exists {
             return Err(Error::DomainError(format!("supplier does not exist.")));
         }
 
-        let mut records: Vec<JournalRecord> = Vec::new();
-
-        for record in &command.records {
-            records.push(JournalRecord::new(
-                SupplyId::new(&record.supply_id)?,
-                SupplyName::new(&record.supply_name)?,
-                SupplierId::new(&record.supplier_id)?,
-                SupplierName::new(&record.supplier_name)?,
-                UnitName::new(&record.unit_name)?,
-                PurchaseUnitPrice::new(record.unit_price)?,
-                PurchaseQuantity::new(record.quantity)?,
-                TotalPrice::new(record.total_price)?,
-            ));
-        }
+        let records = self.records_of(&command.records)?;
 
-        let journal = Journal::restore(
+        let journal = Journal::record(
             id,
             EntryDateTime::new(command.entry_date),
             TotalPrice::new(command.total_price)?,
             records,
-        );
+            &self.rounding_policy,
+        )?;
 
-        self.journal_respository.add(journal.clone())?;
+        let warnings = self.price_warnings(&journal)?;
 
-        let journal = JournalDTO {
-            id: journal.id().to_string(),
-            entry_date: journal.entry_datetime().as_i64(),
-            total_price: journal.total_price().as_f64(),
-            records: journal
-                .records()
-                .iter()
-                .map(|record| JournalRecordDTO {
-                    supplier_id: record.supplier_id().to_string(),
-                    supplier_name: record.supplier_name().to_string(),
-                    supply_id: record.supply_id().to_string(),
-                    supply_name: record.supply_name().to_string(),
-                    unit_name: record.unit_name().to_string(),
-                    unit_price: record.unit_price().as_f64(),
-                    quantity: record.quantity().as_f64(),
-                    total_price: record.total_price().as_f64(),
-                })
-                .collect(),
-        };
+        let change = self.change_of(journal.id(), AuditOperation::Record, None, Some(&journal))?;
+
+        self.journal_respository
+            .add(&self.store, journal.clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::JournalRecorded {
+            journal_id: journal.id().clone(),
+        });
+
+        let mut journal = journal_dto(&journal, &self.rounding_policy)?;
+
+        journal.warnings = warnings;
 
         Ok(journal)
     }
@@ -229,33 +535,42 @@ impl JournalUsecase for JournalService {
     fn edit(&self, command: EditJournalCommand) -> Result<()> {
         let journal_id = JournalId::new(command.journal_id)?;
 
+        let store = self.store.owner()?;
+
         let mut journal = self
             .journal_respository
-            .get(journal_id)?
+            .get(&store, journal_id)?
             .ok_or(Error::DomainError(format!("journal does not exist.")))?;
 
+        ensure_open(
+            self.period_repository.as_ref(),
+            &store,
+            journal.entry_datetime().as_i64(),
+        )?;
+
+        let before = journal.clone();
+
         let total_price = TotalPrice::new(command.total_price)?;
 
-        journal.change_total_price(total_price);
-
-        let mut records: Vec<JournalRecord> = Vec::new();
-
-        for record in &command.records {
-            records.push(JournalRecord::new(
-                SupplyId::new(&record.supply_id)?,
-                SupplyName::new(&record.supply_name)?,
-                SupplierId::new(&record.supplier_id)?,
-                SupplierName::new(&record.supplier_name)?,
-                UnitName::new(&record.unit_name)?,
-                PurchaseUnitPrice::new(record.unit_price)?,
-                PurchaseQuantity::new(record.quantity)?,
-                TotalPrice::new(record.total_price)?,
-            ));
-        }
+        let records = self.records_of(&command.records)?;
+
+        journal.revise(total_price, records, &self.rounding_policy)?;
 
-        journal.swap_records(records);
+        self.journal_respository.add_revision(before.clone())?;
 
-        self.journal_respository.save(journal)?;
+        let change = self.change_of(
+            journal.id(),
+            AuditOperation::Edit,
+            Some(&before),
+            Some(&journal),
+        )?;
+
+        self.journal_respository
+            .save(&store, journal.clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::JournalEdited {
+            journal_id: journal.id().clone(),
+        });
 
         Ok(())
     }
@@ -263,7 +578,187 @@ impl JournalUsecase for JournalService {
     fn delete(&self, journal_id: impl AsRef<str>) -> Result<()> {
         let journal_id = JournalId::new(journal_id.as_ref())?;
 
-        self.journal_respository.delete(journal_id)?;
+        let store = self.store.owner()?;
+
+        let journal = self.journal_respository.get(&store, journal_id.clone())?;
+
+        if let Some(journal) = &journal {
+            ensure_open(
+                self.period_repository.as_ref(),
+                &store,
+                journal.entry_datetime().as_i64(),
+            )?;
+        }
+
+        let change = journal
+            .as_ref()
+            .map(|journal| self.change_of(&journal_id, AuditOperation::Delete, Some(journal), None))
+            .transpose()?;
+
+        self.journal_respository
+            .delete(&store, journal_id.clone(), change)?;
+
+        if journal.is_some() {
+            self.event_publisher
+                .publish(DomainEvent::JournalDeleted { journal_id });
+        }
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<JournalDTO>>> {
+        let trashed = self.journal_respository.list_deleted(&self.store)?;
+
+        let dtos = trashed
+            .iter()
+            .map(|trashed| journal_dto(trashed.item(), &self.rounding_policy))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(trashed_dtos(&trashed, dtos))
+    }
+
+    fn restore(&self, journal_id: impl AsRef<str>) -> Result<()> {
+        let journal_id = JournalId::new(journal_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let journal = trashed_item(
+            self.journal_respository.list_deleted(&store)?,
+            |journal| journal.id() == &journal_id,
+            "journal",
+        )?;
+
+        ensure_open(
+            self.period_repository.as_ref(),
+            &store,
+            journal.entry_datetime().as_i64(),
+        )?;
+
+        let change = self.change_of(journal.id(), AuditOperation::Restore, None, Some(&journal))?;
+
+        self.journal_respository
+            .restore(&store, journal.id().clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::JournalRestored {
+            journal_id: journal.id().clone(),
+        });
+
+        Ok(())
+    }
+
+    fn purge(&self, journal_id: impl AsRef<str>) -> Result<()> {
+        let journal_id = JournalId::new(journal_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let journal = trashed_item(
+            self.journal_respository.list_deleted(&store)?,
+            |journal| journal.id() == &journal_id,
+            "journal",
+        )?;
+
+        let change = self.change_of(journal.id(), AuditOperation::Purge, Some(&journal), None)?;
+
+        self.journal_respository
+            .purge(&store, journal.id().clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::JournalPurged {
+            journal_id: journal.id().clone(),
+        });
+
+        Ok(())
+    }
+
+    fn list_revisions(&self, journal_id: impl AsRef<str>) -> Result<Vec<RevisionDTO<JournalDTO>>> {
+        let journal_id = JournalId::new(journal_id.as_ref())?;
+
+        let journal = self
+            .journal_respository
+            .get(&self.store, journal_id.clone())?
+            .ok_or(Error::DomainError(format!("journal does not exist.")))?;
+
+        let revisions = self
+            .journal_respository
+            .list_revisions(&self.store, journal_id)?;
+
+        let dtos = revisions
+            .iter()
+            .map(Revision::item)
+            .chain(std::iter::once(&journal))
+            .map(|journal| journal_dto(journal, &self.rounding_policy))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(revision_dtos(&revisions, dtos))
+    }
+
+    fn diff_revisions(
+        &self,
+        journal_id: impl AsRef<str>,
+        from: u32,
+        to: u32,
+    ) -> Result<Vec<LineDiffDTO>> {
+        let journal_id = JournalId::new(journal_id.as_ref())?;
+
+        let journal = self
+            .journal_respository
+            .get(&self.store, journal_id.clone())?
+            .ok_or(Error::DomainError(format!("journal does not exist.")))?;
+
+        let revisions = self
+            .journal_respository
+            .list_revisions(&self.store, journal_id)?;
+
+        let from = revision_item(&revisions, &journal, from)?;
+        let to = revision_item(&revisions, &journal, to)?;
+
+        line_diff_dtos(&diff_lines(&from.revision_lines(), &to.revision_lines()))
+    }
+
+    fn revert(&self, journal_id: impl AsRef<str>, revision: u32) -> Result<()> {
+        let journal_id = JournalId::new(journal_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let mut journal = self
+            .journal_respository
+            .get(&store, journal_id.clone())?
+            .ok_or(Error::DomainError(format!("journal does not exist.")))?;
+
+        ensure_open(
+            self.period_repository.as_ref(),
+            &store,
+            journal.entry_datetime().as_i64(),
+        )?;
+
+        let reverted = past_revision(
+            self.journal_respository
+                .list_revisions(&store, journal_id)?,
+            revision,
+        )?;
+
+        let before = journal.clone();
+
+        journal.revise(
+            reverted.total_price().clone(),
+            reverted.records().to_vec(),
+            &self.rounding_policy,
+        )?;
+
+        self.journal_respository.add_revision(before.clone())?;
+
+        let change = self.change_of(
+            journal.id(),
+            AuditOperation::Edit,
+            Some(&before),
+            Some(&journal),
+        )?;
+
+        self.journal_respository
+            .save(&store, journal.clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::JournalEdited {
+            journal_id: journal.id().clone(),
+        });
 
         Ok(())
     }
@@ -0,0 +1,578 @@
+//! This module provides the implementation of the `StockLedgerUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::transfer::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::services::stock::unit::stock_quantity_of;
+use crate::core::*;
+
+/// Stock ledger usecase
+pub struct StockLedgerService {
+    supply_respository: Arc<dyn ForSupplyPersistence>,
+    journal_respository: Arc<dyn ForJournalPersistence>,
+    stocktaking_respository: Arc<dyn ForStocktakingPersistence>,
+    consumption_repository: Arc<dyn ForConsumptionPersistence>,
+    unit_repository: Arc<dyn ForUnitPersistence>,
+    transfer_repository: Arc<dyn ForTransferPersistence>,
+    store: StoreContext,
+}
+
+/// Theoretical stock of a supply in its stock unit.
+struct LedgerLine {
+    supply_id: SupplyId,
+    supply_name: SupplyName,
+    unit_name: UnitName,
+    opening_quantity: Quantity,
+    purchased_quantity: Quantity,
+    /// received from other stores minus sent to them
+    transferred_quantity: Quantity,
+    consumed_quantity: Quantity,
+    /// price per stock unit of the latest purchase or transfer received
+    last_purchase_price: Option<Money>,
+}
+
+impl LedgerLine {
+    fn quantity(&self) -> Result<Quantity> {
+        self.opening_quantity
+            .checked_add(self.purchased_quantity)?
+            .checked_add(self.transferred_quantity)?
+            .checked_sub(self.consumed_quantity)
+    }
+}
+
+/// Opening stocktaking of a supply and the time it was counted at.
+type Opening = (SupplyId, StocktakingId, i64);
+
+/// Returns the line of `supply_id` in `unit_name` in `lines`, adding it if it does not exist.
+fn ledger_line<'a>(
+    lines: &'a mut Vec<LedgerLine>,
+    supply_id: &SupplyId,
+    supply_name: &SupplyName,
+    unit_name: &UnitName,
+) -> &'a mut LedgerLine {
+    match lines
+        .iter()
+        .position(|line| &line.supply_id == supply_id && &line.unit_name == unit_name)
+    {
+        Some(index) => &mut lines[index],
+        None => {
+            lines.push(LedgerLine {
+                supply_id: supply_id.clone(),
+                supply_name: supply_name.clone(),
+                unit_name: unit_name.clone(),
+                opening_quantity: Quantity::ZERO,
+                purchased_quantity: Quantity::ZERO,
+                transferred_quantity: Quantity::ZERO,
+                consumed_quantity: Quantity::ZERO,
+                last_purchase_price: None,
+            });
+
+            lines.last_mut().unwrap()
+        }
+    }
+}
+
+impl StockLedgerService {
+    pub fn new(
+        supply_respository: Arc<dyn ForSupplyPersistence>,
+        journal_respository: Arc<dyn ForJournalPersistence>,
+        stocktaking_respository: Arc<dyn ForStocktakingPersistence>,
+        consumption_repository: Arc<dyn ForConsumptionPersistence>,
+        unit_repository: Arc<dyn ForUnitPersistence>,
+        transfer_repository: Arc<dyn ForTransferPersistence>,
+        store: StoreContext,
+    ) -> Self {
+        Self {
+            supply_respository,
+            journal_respository,
+            stocktaking_respository,
+            consumption_repository,
+            unit_repository,
+            transfer_repository,
+            store,
+        }
+    }
+
+    /// Computes the theoretical stock at `at`.
+    ///
+    /// Each supply starts from the latest stocktaking counting it, other than `excluded`,
+    /// and adds the purchases and transfers received and subtracts the transfers sent
+    /// and the consumptions recorded after it.
+    /// Returns the lines and the opening stocktaking of each supply.
+    fn ledger(
+        &self,
+        at: i64,
+        excluded: Option<&StocktakingId>,
+    ) -> Result<(Vec<LedgerLine>, Vec<Opening>)> {
+        let supplies = self.supply_respository.list(&self.store)?;
+        let units = self.unit_repository.list()?;
+
+        let mut lines: Vec<LedgerLine> = Vec::new();
+        let mut openings: Vec<Opening> = Vec::new();
+
+        let mut stocktakings = self.stocktaking_respository.find(
+            &self.store,
+            FindStocktakingsQuery {
+                period_start: None,
+                period_end: Some(StocktakenDateTime::new(at)),
+            },
+        )?;
+
+        stocktakings.retain(|stocktaking| Some(stocktaking.id()) != excluded);
+        stocktakings.sort_by_key(|stocktaking| stocktaking.stocktaken_at().as_i64());
+
+        for stocktaking in &stocktakings {
+            let counted_at = stocktaking.stocktaken_at().as_i64();
+
+            // a later count replaces the opening quantity of the supplies it counts
+            for record in stocktaking.records() {
+                match openings
+                    .iter_mut()
+                    .find(|opening| &opening.0 == record.supply_id())
+                {
+                    Some(opening) if opening.1 == *stocktaking.id() => continue,
+                    Some(opening) => {
+                        opening.1 = stocktaking.id().clone();
+                        opening.2 = counted_at;
+                    }
+                    None => openings.push((
+                        record.supply_id().clone(),
+                        stocktaking.id().clone(),
+                        counted_at,
+                    )),
+                }
+
+                for line in lines
+                    .iter_mut()
+                    .filter(|line| &line.supply_id == record.supply_id())
+                {
+                    line.opening_quantity = Quantity::ZERO;
+                }
+            }
+
+            for record in stocktaking.records() {
+                let (unit_name, quantity) = stock_quantity_of(
+                    &supplies,
+                    &units,
+                    record.supply_id(),
+                    record.unit_name().clone(),
+                    record.quantity().as_quantity(),
+                )?;
+
+                let line = ledger_line(
+                    &mut lines,
+                    record.supply_id(),
+                    record.supply_name(),
+                    &unit_name,
+                );
+
+                line.opening_quantity = line.opening_quantity.checked_add(quantity)?;
+            }
+        }
+
+        // movements at the time of the opening stocktaking are counted by it
+        let opened_at = |supply_id: &SupplyId| -> Option<i64> {
+            openings
+                .iter()
+                .find(|opening| &opening.0 == supply_id)
+                .map(|opening| opening.2)
+        };
+
+        let mut journals = self.journal_respository.find(
+            &self.store,
+            FindJournalsQuery {
+                period_start: None,
+                period_end: Some(EntryDateTime::new(at)),
+                supplier_name: None,
+                supply_name: None,
+            },
+        )?;
+
+        journals.sort_by_key(|journal| journal.entry_datetime().as_i64());
+
+        for journal in &journals {
+            let entered_at = journal.entry_datetime().as_i64();
+
+            for record in journal.records() {
+                let (unit_name, quantity) = stock_quantity_of(
+                    &supplies,
+                    &units,
+                    record.supply_id(),
+                    record.unit_name().clone(),
+                    record.quantity().as_quantity(),
+                )?;
+
+                let after_opening =
+                    opened_at(record.supply_id()).is_none_or(|opened_at| opened_at < entered_at);
+
+                let line = ledger_line(
+                    &mut lines,
+                    record.supply_id(),
+                    record.supply_name(),
+                    &unit_name,
+                );
+
+                if quantity.as_raw() > 0 {
+                    line.last_purchase_price = Some(
+                        record
+                            .total_price()
+                            .as_money()
+                            .checked_div(quantity, RoundingMode::HalfUp)?,
+                    );
+                }
+
+                if after_opening {
+                    line.purchased_quantity = line.purchased_quantity.checked_add(quantity)?;
+                }
+            }
+        }
+
+        let mut transfers = self.transfer_repository.find(
+            &self.store,
+            FindTransfersQuery {
+                period_start: None,
+                period_end: Some(TransferredDateTime::new(at)),
+            },
+        )?;
+
+        transfers.sort_by_key(|transfer| transfer.transferred_at().as_i64());
+
+        for transfer in &transfers {
+            let transferred_at = transfer.transferred_at().as_i64();
+
+            for line in transfer.lines() {
+                // a transfer between the stores read moves stock of both supplies
+                let mut moves: Vec<(&SupplyId, TransferDirection)> = Vec::new();
+
+                if self.store.includes(transfer.source_store_id()) {
+                    moves.push((line.supply_id(), TransferDirection::Outbound));
+                }
+
+                if self.store.includes(transfer.destination_store_id()) {
+                    moves.push((line.destination_supply_id(), TransferDirection::Inbound));
+                }
+
+                for (supply_id, direction) in moves {
+                    let (unit_name, quantity) = stock_quantity_of(
+                        &supplies,
+                        &units,
+                        supply_id,
+                        line.unit_name().clone(),
+                        line.quantity().as_quantity(),
+                    )?;
+
+                    let after_opening =
+                        opened_at(supply_id).is_none_or(|opened_at| opened_at < transferred_at);
+
+                    let ledger_line =
+                        ledger_line(&mut lines, supply_id, line.supply_name(), &unit_name);
+
+                    if direction == TransferDirection::Inbound {
+                        ledger_line.last_purchase_price = Some(
+                            line.total_price()
+                                .as_money()
+                                .checked_div(quantity, RoundingMode::HalfUp)?,
+                        );
+                    }
+
+                    if !after_opening {
+                        continue;
+                    }
+
+                    ledger_line.transferred_quantity = match direction {
+                        TransferDirection::Inbound => {
+                            ledger_line.transferred_quantity.checked_add(quantity)?
+                        }
+                        TransferDirection::Outbound => {
+                            ledger_line.transferred_quantity.checked_sub(quantity)?
+                        }
+                    };
+                }
+            }
+        }
+
+        let consumptions = self.consumption_repository.find(
+            &self.store,
+            FindConsumptionsQuery {
+                period_start: None,
+                period_end: Some(ConsumedDateTime::new(at)),
+            },
+        )?;
+
+        for consumption in &consumptions {
+            let consumed_at = consumption.consumed_at().as_i64();
+
+            for record in consumption.records() {
+                let after_opening =
+                    opened_at(record.supply_id()).is_none_or(|opened_at| opened_at < consumed_at);
+
+                if !after_opening {
+                    continue;
+                }
+
+                let (unit_name, quantity) = stock_quantity_of(
+                    &supplies,
+                    &units,
+                    record.supply_id(),
+                    record.unit_name().clone(),
+                    record.quantity().as_quantity(),
+                )?;
+
+                let line = ledger_line(
+                    &mut lines,
+                    record.supply_id(),
+                    record.supply_name(),
+                    &unit_name,
+                );
+
+                line.consumed_quantity = line.consumed_quantity.checked_add(quantity)?;
+            }
+        }
+
+        Ok((lines, openings))
+    }
+}
+
+/// Stock ledger usecase implementation
+impl StockLedgerUsecase for StockLedgerService {
+    fn on_hand(&self, query: StockLedgerQuery) -> Result<Vec<StockLedgerDTO>> {
+        let (lines, openings) = self.ledger(query.at, None)?;
+
+        lines
+            .iter()
+            .map(|line| {
+                Ok(StockLedgerDTO {
+                    supply_id: line.supply_id.to_string(),
+                    supply_name: line.supply_name.to_string(),
+                    unit_name: line.unit_name.to_string(),
+                    opening_stocktaking_id: openings
+                        .iter()
+                        .find(|opening| opening.0 == line.supply_id)
+                        .map(|opening| opening.1.to_string()),
+                    opening_quantity: line.opening_quantity.as_f64(),
+                    purchased_quantity: line.purchased_quantity.as_f64(),
+                    transferred_quantity: line.transferred_quantity.as_f64(),
+                    consumed_quantity: line.consumed_quantity.as_f64(),
+                    quantity: line.quantity()?.as_f64(),
+                })
+            })
+            .collect()
+    }
+
+    fn variance(&self, query: StockVarianceQuery) -> Result<StockVarianceReportDTO> {
+        let stocktaking_id = StocktakingId::new(query.stocktaking_id)?;
+
+        let stocktaking = self
+            .stocktaking_respository
+            .get(&self.store, stocktaking_id)?
+            .ok_or(Error::DomainError(format!("stocktaking does not exist.")))?;
+
+        let stocktaken_at = stocktaking.stocktaken_at().as_i64();
+
+        // expected from the stocktakings before this one
+        let (mut lines, _) = self.ledger(stocktaken_at, Some(stocktaking.id()))?;
+
+        let supplies = self.supply_respository.list(&self.store)?;
+        let units = self.unit_repository.list()?;
+
+        // (index of ledger line, counted quantity, counted value)
+        let mut counts: Vec<(usize, Quantity, Money)> = Vec::new();
+
+        for record in stocktaking.records() {
+            let (unit_name, quantity) = stock_quantity_of(
+                &supplies,
+                &units,
+                record.supply_id(),
+                record.unit_name().clone(),
+                record.quantity().as_quantity(),
+            )?;
+
+            ledger_line(
+                &mut lines,
+                record.supply_id(),
+                record.supply_name(),
+                &unit_name,
+            );
+
+            let index = lines
+                .iter()
+                .position(|line| {
+                    &line.supply_id == record.supply_id() && line.unit_name == unit_name
+                })
+                .unwrap();
+
+            match counts.iter_mut().find(|count| count.0 == index) {
+                Some(count) => {
+                    count.1 = count.1.checked_add(quantity)?;
+                    count.2 = count.2.checked_add(record.total_price().as_money())?;
+                }
+                None => counts.push((index, quantity, record.total_price().as_money())),
+            }
+        }
+
+        let mut supply_variances: Vec<SupplyVarianceDTO> = Vec::new();
+        let (mut expected_total, mut counted_total) = (Money::ZERO, Money::ZERO);
+
+        for (index, line) in lines.iter().enumerate() {
+            let (counted_quantity, counted_value) = counts
+                .iter()
+                .find(|count| count.0 == index)
+                .map(|count| (count.1, count.2))
+                .unwrap_or((Quantity::ZERO, Money::ZERO));
+
+            let unit_price = if counted_quantity.as_raw() > 0 {
+                counted_value.checked_div(counted_quantity, RoundingMode::HalfUp)?
+            } else {
+                line.last_purchase_price.unwrap_or(Money::ZERO)
+            };
+
+            let expected_quantity = line.quantity()?;
+            let expected_value = unit_price.checked_mul(expected_quantity, RoundingMode::HalfUp)?;
+
+            expected_total = expected_total.checked_add(expected_value)?;
+            counted_total = counted_total.checked_add(counted_value)?;
+
+            supply_variances.push(SupplyVarianceDTO {
+                supply_id: line.supply_id.to_string(),
+                supply_name: line.supply_name.to_string(),
+                unit_name: line.unit_name.to_string(),
+                expected_quantity: expected_quantity.as_f64(),
+                counted_quantity: counted_quantity.as_f64(),
+                variance_quantity: counted_quantity.checked_sub(expected_quantity)?.as_f64(),
+                unit_price: unit_price.as_f64(),
+                expected_value: expected_value.as_f64(),
+                counted_value: counted_value.as_f64(),
+                variance_value: counted_value.checked_sub(expected_value)?.as_f64(),
+            });
+        }
+
+        Ok(StockVarianceReportDTO {
+            stocktaking_id: stocktaking.id().to_string(),
+            stocktaken_date: stocktaken_at,
+            expected_value: expected_total.as_f64(),
+            counted_value: counted_total.as_f64(),
+            variance_value: counted_total.checked_sub(expected_total)?.as_f64(),
+            supplies: supply_variances,
+        })
+    }
+
+    fn expiring_lots(&self, query: ExpiringLotsQuery) -> Result<Vec<ExpiringLotDTO>> {
+        let (lines, openings) = self.ledger(query.at, None)?;
+
+        let mut journals = self.journal_respository.find(
+            &self.store,
+            FindJournalsQuery {
+                period_start: None,
+                period_end: Some(EntryDateTime::new(query.at)),
+                supplier_name: None,
+                supply_name: None,
+            },
+        )?;
+
+        journals.sort_by_key(|journal| journal.entry_datetime().as_i64());
+
+        let stocktakings = self.stocktaking_respository.find(
+            &self.store,
+            FindStocktakingsQuery {
+                period_start: None,
+                period_end: Some(StocktakenDateTime::new(query.at)),
+            },
+        )?;
+
+        // supplies used up are left out
+        let in_stock = |supply_id: &SupplyId| -> Result<bool> {
+            for line in lines.iter().filter(|line| &line.supply_id == supply_id) {
+                if line.quantity()?.as_raw() > 0 {
+                    return Ok(true);
+                }
+            }
+
+            Ok(false)
+        };
+
+        let mut lots: Vec<ExpiringLotDTO> = Vec::new();
+
+        for (journal, record) in journals.iter().flat_map(|journal| {
+            journal
+                .records()
+                .iter()
+                .map(move |record| (journal, record))
+        }) {
+            let Some(expiry_date) = record.expiry_date() else {
+                continue;
+            };
+
+            if !expiry_date.is_within(query.at, query.within_days) || !in_stock(record.supply_id())?
+            {
+                continue;
+            }
+
+            let entered_at = journal.entry_datetime().as_i64();
+
+            let opening = openings
+                .iter()
+                .find(|opening| &opening.0 == record.supply_id());
+
+            let lot = ExpiringLotDTO {
+                supply_id: record.supply_id().to_string(),
+                supply_name: record.supply_name().to_string(),
+                lot_number: record.lot_number().map(|lot_number| lot_number.to_string()),
+                expiry_date: expiry_date.as_i64(),
+                journal_id: journal.id().to_string(),
+                received_date: entered_at,
+                stocktaking_id: None,
+                unit_name: record.unit_name().to_string(),
+                quantity: record.quantity().as_f64(),
+            };
+
+            match opening {
+                // received since the opening stocktaking
+                None => lots.push(lot),
+                Some(opening) if opening.2 < entered_at => lots.push(lot),
+                // received before, on hand as far as the opening stocktaking counted the lot
+                Some(opening) => {
+                    let Some(lot_number) = record.lot_number() else {
+                        continue;
+                    };
+
+                    let counted = stocktakings
+                        .iter()
+                        .filter(|stocktaking| stocktaking.id() == &opening.1)
+                        .flat_map(|stocktaking| stocktaking.records())
+                        .filter(|counted| {
+                            counted.supply_id() == record.supply_id()
+                                && counted.lot_number() == Some(lot_number)
+                        });
+
+                    // a lot received more than once is counted once, with its latest receipt
+                    lots.retain(|other| {
+                        other.supply_id != lot.supply_id
+                            || other.lot_number != lot.lot_number
+                            || other.stocktaking_id.is_none()
+                    });
+
+                    for counted in counted {
+                        lots.push(ExpiringLotDTO {
+                            stocktaking_id: Some(opening.1.to_string()),
+                            unit_name: counted.unit_name().to_string(),
+                            quantity: counted.quantity().as_f64(),
+                            ..lot.clone()
+                        });
+                    }
+                }
+            }
+        }
+
+        lots.sort_by(|a, b| {
+            a.expiry_date
+                .cmp(&b.expiry_date)
+                .then_with(|| a.supply_name.cmp(&b.supply_name))
+        });
+
+        Ok(lots)
+    }
+}
@@ -0,0 +1,255 @@
+//! This module provides the implementation of the `StorageLocationUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::event::*;
+use crate::core::domain::values::location::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::services::audit::snapshot;
+use crate::core::services::stock::supply::supply_dto;
+use crate::core::services::trash::*;
+use crate::core::*;
+
+/// Storage location usecase
+pub struct StorageLocationService {
+    location_repository: Arc<dyn ForStorageLocationPersistence>,
+    supply_repository: Arc<dyn ForSupplyPersistence>,
+    event_publisher: Arc<dyn ForEventPublishing>,
+    operator: Option<OperatorName>,
+    store: StoreContext,
+}
+
+impl StorageLocationService {
+    pub fn new(
+        location_repository: Arc<dyn ForStorageLocationPersistence>,
+        supply_repository: Arc<dyn ForSupplyPersistence>,
+        event_publisher: Arc<dyn ForEventPublishing>,
+        operator: Option<OperatorName>,
+        store: StoreContext,
+    ) -> Self {
+        Self {
+            location_repository,
+            supply_repository,
+            event_publisher,
+            operator,
+            store,
+        }
+    }
+
+    /// Returns the change of the placement of a supply to log in the audit log.
+    fn supply_change_of(&self, before: &Supply, after: &Supply) -> Result<AuditChange> {
+        AuditChange::new(
+            AuditEntity::Supply,
+            after.id().as_str(),
+            AuditOperation::Update,
+            self.operator.clone(),
+            Some(snapshot(&supply_dto(before))?),
+            Some(snapshot(&supply_dto(after))?),
+        )
+    }
+}
+
+/// Converts a storage location into its DTO.
+fn location_dto(location: &StorageLocation) -> StorageLocationDTO {
+    StorageLocationDTO {
+        id: location.id().to_string(),
+        name: location.name().to_string(),
+        sort_order: location.sort_order().as_u32(),
+    }
+}
+
+/// Storage location usecase implementation
+impl StorageLocationUsecase for StorageLocationService {
+    fn list(&self) -> Result<Vec<StorageLocationDTO>> {
+        let locations = self.location_repository.list()?;
+
+        let locations: Vec<StorageLocationDTO> = ordered_locations(&locations)
+            .into_iter()
+            .map(location_dto)
+            .collect();
+
+        Ok(locations)
+    }
+
+    fn get(&self, location_id: impl AsRef<str>) -> Result<Option<StorageLocationDTO>> {
+        let location_id = StorageLocationId::new(location_id.as_ref())?;
+
+        let location = self.location_repository.get(location_id)?;
+
+        Ok(location.as_ref().map(location_dto))
+    }
+
+    fn register(&self, command: RegisterStorageLocationCommand) -> Result<StorageLocationDTO> {
+        let location = StorageLocation::restore(
+            self.location_repository.next_id()?,
+            StorageLocationName::new(command.location_name)?,
+            SortOrder::new(command.sort_order)?,
+        );
+
+        self.location_repository.add(location.clone())?;
+
+        Ok(location_dto(&location))
+    }
+
+    fn update(&self, command: UpdateStorageLocationCommand) -> Result<()> {
+        let location_id = StorageLocationId::new(command.location_id)?;
+
+        let mut location = self
+            .location_repository
+            .get(location_id)?
+            .ok_or(Error::DomainError(format!(
+                "storage location does not exist."
+            )))?;
+
+        location.rename(StorageLocationName::new(command.location_name)?);
+        location.reorder(SortOrder::new(command.sort_order)?);
+
+        self.location_repository.save(location)?;
+
+        Ok(())
+    }
+
+    fn delete(&self, location_id: impl AsRef<str>) -> Result<()> {
+        let location_id = StorageLocationId::new(location_id.as_ref())?;
+
+        // storage locations are shared by the stores
+        let in_use = self
+            .supply_repository
+            .list(&StoreContext::Consolidated)?
+            .iter()
+            .any(|supply| {
+                supply
+                    .placement()
+                    .is_some_and(|placement| placement.location_id() == &location_id)
+            });
+
+        if in_use {
+            return Err(Error::DomainError(format!(
+                "storage location is used by supplies."
+            )));
+        }
+
+        self.location_repository.delete(location_id)?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<StorageLocationDTO>>> {
+        let trashed = self.location_repository.list_deleted()?;
+
+        let dtos = trashed
+            .iter()
+            .map(|trashed| location_dto(trashed.item()))
+            .collect();
+
+        Ok(trashed_dtos(&trashed, dtos))
+    }
+
+    fn restore(&self, location_id: impl AsRef<str>) -> Result<()> {
+        let location_id = StorageLocationId::new(location_id.as_ref())?;
+
+        let location = trashed_item(
+            self.location_repository.list_deleted()?,
+            |location| location.id() == &location_id,
+            "storage location",
+        )?;
+
+        self.location_repository.restore(location.id().clone())?;
+
+        Ok(())
+    }
+
+    fn purge(&self, location_id: impl AsRef<str>) -> Result<()> {
+        let location_id = StorageLocationId::new(location_id.as_ref())?;
+
+        let location = trashed_item(
+            self.location_repository.list_deleted()?,
+            |location| location.id() == &location_id,
+            "storage location",
+        )?;
+
+        self.location_repository.purge(location.id().clone())?;
+
+        Ok(())
+    }
+
+    fn place_supply(&self, command: PlaceSupplyCommand) -> Result<()> {
+        let supply_id = SupplyId::new(command.supply_id)?;
+
+        let mut supply = self
+            .supply_repository
+            .get(&self.store, supply_id)?
+            .ok_or(Error::DomainError(format!("supply does not exist.")))?;
+
+        let placement = match command.location_id.filter(|id| !id.trim().is_empty()) {
+            Some(location_id) => {
+                let location = self
+                    .location_repository
+                    .get(StorageLocationId::new(location_id)?)?
+                    .ok_or(Error::DomainError(format!(
+                        "storage location does not exist."
+                    )))?;
+
+                Some(StoragePlacement::new(
+                    location.id().clone(),
+                    SortOrder::new(command.sort_order)?,
+                ))
+            }
+            None => None,
+        };
+
+        let before = supply.clone();
+
+        supply.change_placement(placement);
+
+        let change = self.supply_change_of(&before, &supply)?;
+
+        self.supply_repository
+            .save(&self.store, supply.clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::SupplyUpdated {
+            supply_id: supply.id().clone(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Returns `locations` in counting order, locations of the same order kept as they are.
+pub fn ordered_locations(locations: &[StorageLocation]) -> Vec<&StorageLocation> {
+    let mut ordered: Vec<&StorageLocation> = locations.iter().collect();
+
+    ordered.sort_by_key(|location| location.sort_order());
+
+    ordered
+}
+
+/// Returns the position of a line of `supply` counted in `location_id` on count sheets.
+///
+/// `locations` must be in counting order. Lines without a registered location come last,
+/// and supplies not placed in the location of the line come last within it.
+pub fn counting_position(
+    locations: &[&StorageLocation],
+    supply: Option<&Supply>,
+    location_id: Option<&StorageLocationId>,
+) -> (usize, u32) {
+    let location_index = location_id
+        .and_then(|location_id| {
+            locations
+                .iter()
+                .position(|location| location.id() == location_id)
+        })
+        .unwrap_or(locations.len());
+
+    let sort_order = supply
+        .and_then(|supply| supply.placement())
+        .filter(|placement| Some(placement.location_id()) == location_id)
+        .map(|placement| placement.sort_order().as_u32())
+        .unwrap_or(u32::MAX);
+
+    (location_index, sort_order)
+}
@@ -1,6 +1,17 @@
 //! This module provides implementations for stock-related usecases.
 
+pub mod category;
+pub mod consumption;
 pub mod journal;
+pub mod ledger;
+pub mod location;
+pub mod order;
+pub mod reorder;
+pub mod report;
 pub mod stocktaking;
+pub mod store;
 pub mod supplier;
 pub mod supply;
+pub mod transfer;
+pub mod unit;
+pub mod waste;
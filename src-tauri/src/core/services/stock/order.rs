@@ -0,0 +1,365 @@
+//! This module provides the implementation of the `PurchaseOrderUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::order::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::services::trash::*;
+use crate::core::*;
+
+/// Purchase order usecase
+///
+/// Deliveries are recorded through the journal usecase,
+/// so received lines are checked and taxed like any other journal.
+pub struct PurchaseOrderService<J: JournalUsecase> {
+    purchase_order_repository: Arc<dyn ForPurchaseOrderPersistence>,
+    supplier_repository: Arc<dyn ForSupplierPersistence>,
+    supply_respository: Arc<dyn ForSupplyPersistence>,
+    journals: J,
+    rounding_policy: RoundingPolicy,
+    store: StoreContext,
+}
+
+impl<J: JournalUsecase> PurchaseOrderService<J> {
+    pub fn new(
+        purchase_order_repository: Arc<dyn ForPurchaseOrderPersistence>,
+        supplier_repository: Arc<dyn ForSupplierPersistence>,
+        supply_respository: Arc<dyn ForSupplyPersistence>,
+        journals: J,
+        rounding_policy: RoundingPolicy,
+        store: StoreContext,
+    ) -> Self {
+        Self {
+            purchase_order_repository,
+            supplier_repository,
+            supply_respository,
+            journals,
+            rounding_policy,
+            store,
+        }
+    }
+
+    /// Returns the purchase order, failing if it does not exist.
+    fn order_of(&self, purchase_order_id: impl AsRef<str>) -> Result<PurchaseOrder> {
+        let purchase_order_id = PurchaseOrderId::new(purchase_order_id.as_ref())?;
+
+        self.purchase_order_repository
+            .get(&self.store, purchase_order_id)?
+            .ok_or(Error::DomainError(format!(
+                "purchase order does not exist."
+            )))
+    }
+
+    /// Builds order lines, names and units taken from the supplies.
+    fn lines_of(&self, lines: &[PurchaseOrderLineDTO]) -> Result<Vec<PurchaseOrderLine>> {
+        lines
+            .iter()
+            .map(|line| {
+                let supply = self
+                    .supply_respository
+                    .get(&self.store, SupplyId::new(&line.supply_id)?)?
+                    .ok_or(Error::DomainError(format!("supply does not exist.")))?;
+
+                Ok(PurchaseOrderLine::new(
+                    supply.id().clone(),
+                    supply.name().clone(),
+                    supply.unit_name().clone(),
+                    PurchaseQuantity::new(line.quantity)?,
+                    PurchaseUnitPrice::new(line.expected_unit_price)?,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Converts a purchase order into its DTO.
+fn purchase_order_dto(order: &PurchaseOrder, policy: &RoundingPolicy) -> Result<PurchaseOrderDTO> {
+    let expected_totals = order
+        .lines()
+        .iter()
+        .map(|line| {
+            policy.line_total(
+                line.expected_unit_price().as_money(),
+                line.quantity().as_quantity(),
+            )
+        })
+        .collect::<Result<Vec<Money>>>()?;
+
+    Ok(PurchaseOrderDTO {
+        id: order.id().to_string(),
+        supplier_id: order.supplier_id().to_string(),
+        supplier_name: order.supplier_name().to_string(),
+        ordered_date: order.ordered_at().as_i64(),
+        status: order.status().to_string(),
+        expected_total_price: Money::checked_sum(expected_totals)?.as_f64(),
+        lines: order
+            .lines()
+            .iter()
+            .map(|line| PurchaseOrderLineDTO {
+                supply_id: line.supply_id().to_string(),
+                supply_name: line.supply_name().to_string(),
+                unit_name: line.unit_name().to_string(),
+                quantity: line.quantity().as_f64(),
+                expected_unit_price: line.expected_unit_price().as_f64(),
+                received_quantity: line.received_quantity().as_f64(),
+            })
+            .collect(),
+        journal_ids: order
+            .journal_ids()
+            .iter()
+            .map(|journal_id| journal_id.to_string())
+            .collect(),
+    })
+}
+
+/// Purchase order usecase implementation
+impl<J: JournalUsecase> PurchaseOrderUsecase for PurchaseOrderService<J> {
+    fn list(&self) -> Result<Vec<PurchaseOrderDTO>> {
+        let orders = self.purchase_order_repository.list(&self.store)?;
+
+        orders
+            .iter()
+            .map(|order| purchase_order_dto(order, &self.rounding_policy))
+            .collect()
+    }
+
+    fn get(&self, purchase_order_id: impl AsRef<str>) -> Result<Option<PurchaseOrderDTO>> {
+        let purchase_order_id = PurchaseOrderId::new(purchase_order_id.as_ref())?;
+
+        let order = self
+            .purchase_order_repository
+            .get(&self.store, purchase_order_id)?;
+
+        order
+            .map(|order| purchase_order_dto(&order, &self.rounding_policy))
+            .transpose()
+    }
+
+    fn search(&self, query: SearchPurchaseOrdersQuery) -> Result<Vec<PurchaseOrderDTO>> {
+        let query = FindPurchaseOrdersQuery {
+            period_start: query.period_start.map(OrderedDateTime::new),
+            period_end: query.period_end.map(OrderedDateTime::new),
+            supplier_id: query
+                .supplier_id
+                .filter(|id| !id.trim().is_empty())
+                .map(SupplierId::new)
+                .transpose()?,
+            status: query
+                .status
+                .filter(|status| !status.trim().is_empty())
+                .map(OrderStatus::new)
+                .transpose()?,
+        };
+
+        let orders = self.purchase_order_repository.find(&self.store, query)?;
+
+        orders
+            .iter()
+            .map(|order| purchase_order_dto(order, &self.rounding_policy))
+            .collect()
+    }
+
+    fn draft(&self, command: DraftPurchaseOrderCommand) -> Result<PurchaseOrderDTO> {
+        let supplier = self
+            .supplier_repository
+            .get(&self.store, SupplierId::new(command.supplier_id)?)?
+            .ok_or(Error::DomainError(format!("supplier does not exist.")))?;
+
+        let order = PurchaseOrder::draft(
+            self.purchase_order_repository.next_id()?,
+            supplier.id().clone(),
+            supplier.name().clone(),
+            OrderedDateTime::new(command.ordered_date),
+            self.lines_of(&command.lines)?,
+        )?;
+
+        self.purchase_order_repository
+            .add(&self.store, order.clone())?;
+
+        purchase_order_dto(&order, &self.rounding_policy)
+    }
+
+    fn revise(&self, command: RevisePurchaseOrderCommand) -> Result<()> {
+        let mut order = self.order_of(&command.purchase_order_id)?;
+
+        order.revise(
+            OrderedDateTime::new(command.ordered_date),
+            self.lines_of(&command.lines)?,
+        )?;
+
+        self.purchase_order_repository.save(&self.store, order)?;
+
+        Ok(())
+    }
+
+    fn send(&self, purchase_order_id: impl AsRef<str>) -> Result<()> {
+        let mut order = self.order_of(purchase_order_id)?;
+
+        order.send()?;
+
+        self.purchase_order_repository.save(&self.store, order)?;
+
+        Ok(())
+    }
+
+    fn cancel(&self, purchase_order_id: impl AsRef<str>) -> Result<()> {
+        let mut order = self.order_of(purchase_order_id)?;
+
+        order.cancel()?;
+
+        self.purchase_order_repository.save(&self.store, order)?;
+
+        Ok(())
+    }
+
+    fn receive(&self, command: ReceivePurchaseOrderCommand) -> Result<JournalDTO> {
+        let mut order = self.order_of(&command.purchase_order_id)?;
+
+        let mut received: Vec<(SupplyId, Quantity)> = Vec::new();
+        let mut records: Vec<JournalRecordDTO> = Vec::new();
+        let mut totals: Vec<Money> = Vec::new();
+
+        for line in command.lines.iter().filter(|line| line.quantity != 0.0) {
+            let supply_id = SupplyId::new(&line.supply_id)?;
+
+            let ordered = order
+                .lines()
+                .iter()
+                .find(|ordered| ordered.supply_id() == &supply_id)
+                .ok_or(Error::DomainError(format!(
+                    "supply {} is not ordered.",
+                    supply_id.as_str()
+                )))?;
+
+            let quantity = PurchaseQuantity::new(line.quantity)?;
+            let unit_price = match line.unit_price {
+                Some(unit_price) => PurchaseUnitPrice::new(unit_price)?,
+                None => ordered.expected_unit_price().clone(),
+            };
+            let total = self
+                .rounding_policy
+                .line_total(unit_price.as_money(), quantity.as_quantity())?;
+
+            records.push(JournalRecordDTO {
+                supply_id: supply_id.to_string(),
+                supply_name: ordered.supply_name().to_string(),
+                supplier_id: order.supplier_id().to_string(),
+                supplier_name: order.supplier_name().to_string(),
+                unit_name: ordered.unit_name().to_string(),
+                unit_price: unit_price.as_f64(),
+                quantity: quantity.as_f64(),
+                total_price: total.as_f64(),
+                tax_category: None,
+                tax_included: None,
+                tax_price: 0.0,
+                invoice_registered: None,
+                lot_number: line.lot_number.clone(),
+                expiry_date: line.expiry_date,
+            });
+            totals.push(total);
+            received.push((supply_id, quantity.as_quantity()));
+        }
+
+        order.receive(&received)?;
+
+        let total = Money::checked_sum(totals)?;
+
+        let journal = match command.journal_id.filter(|id| !id.trim().is_empty()) {
+            Some(journal_id) => {
+                let journal = self
+                    .journals
+                    .get(&journal_id)?
+                    .ok_or(Error::DomainError(format!("journal does not exist.")))?;
+
+                let total_price = TotalPrice::new(journal.total_price)?
+                    .as_money()
+                    .checked_add(total)?;
+
+                self.journals.edit(EditJournalCommand {
+                    journal_id: journal.id.clone(),
+                    total_price: total_price.as_f64(),
+                    records: journal.records.into_iter().chain(records).collect(),
+                })?;
+
+                self.journals
+                    .get(&journal_id)?
+                    .ok_or(Error::DomainError(format!("journal does not exist.")))?
+            }
+            None => self.journals.record(RecordJournalCommand {
+                entry_date: command.received_date,
+                total_price: total.as_f64(),
+                records,
+            })?,
+        };
+
+        order.link_journal(JournalId::new(&journal.id)?);
+
+        self.purchase_order_repository.save(&self.store, order)?;
+
+        Ok(journal)
+    }
+
+    fn delete(&self, purchase_order_id: impl AsRef<str>) -> Result<()> {
+        let order = self.order_of(purchase_order_id)?;
+
+        if !matches!(order.status(), OrderStatus::Draft | OrderStatus::Cancelled) {
+            return Err(Error::DomainError(format!(
+                "only draft or cancelled orders can be deleted."
+            )));
+        }
+
+        self.purchase_order_repository
+            .delete(&self.store, order.id().clone())?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<PurchaseOrderDTO>>> {
+        let trashed = self.purchase_order_repository.list_deleted(&self.store)?;
+
+        let dtos = trashed
+            .iter()
+            .map(|trashed| purchase_order_dto(trashed.item(), &self.rounding_policy))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(trashed_dtos(&trashed, dtos))
+    }
+
+    fn restore(&self, purchase_order_id: impl AsRef<str>) -> Result<()> {
+        let purchase_order_id = PurchaseOrderId::new(purchase_order_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let order = trashed_item(
+            self.purchase_order_repository.list_deleted(&store)?,
+            |order| order.id() == &purchase_order_id,
+            "purchase order",
+        )?;
+
+        self.purchase_order_repository
+            .restore(&store, order.id().clone())?;
+
+        Ok(())
+    }
+
+    fn purge(&self, purchase_order_id: impl AsRef<str>) -> Result<()> {
+        let purchase_order_id = PurchaseOrderId::new(purchase_order_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let order = trashed_item(
+            self.purchase_order_repository.list_deleted(&store)?,
+            |order| order.id() == &purchase_order_id,
+            "purchase order",
+        )?;
+
+        self.purchase_order_repository
+            .purge(&store, order.id().clone())?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+//! This module provides the implementation of the `ReorderUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::store::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::*;
+
+/// Reorder usecase
+///
+/// Stock on hand is estimated by the stock ledger,
+/// from the latest stocktaking and the purchases and consumptions recorded since.
+pub struct ReorderService<L: StockLedgerUsecase> {
+    supply_respository: Arc<dyn ForSupplyPersistence>,
+    supplier_repository: Arc<dyn ForSupplierPersistence>,
+    unit_repository: Arc<dyn ForUnitPersistence>,
+    ledger: L,
+    store: StoreContext,
+}
+
+impl<L: StockLedgerUsecase> ReorderService<L> {
+    pub fn new(
+        supply_respository: Arc<dyn ForSupplyPersistence>,
+        supplier_repository: Arc<dyn ForSupplierPersistence>,
+        unit_repository: Arc<dyn ForUnitPersistence>,
+        ledger: L,
+        store: StoreContext,
+    ) -> Self {
+        Self {
+            supply_respository,
+            supplier_repository,
+            unit_repository,
+            ledger,
+            store,
+        }
+    }
+}
+
+/// Reorder usecase implementation
+impl<L: StockLedgerUsecase> ReorderUsecase for ReorderService<L> {
+    fn suggest(&self, query: ReorderQuery) -> Result<Vec<ReorderSuggestionDTO>> {
+        let supplies = self.supply_respository.list(&self.store)?;
+        let suppliers = self.supplier_repository.list(&self.store)?;
+        let units = self.unit_repository.list()?;
+
+        let ledger = self.ledger.on_hand(StockLedgerQuery { at: query.at })?;
+
+        let mut suggestions: Vec<ReorderSuggestionDTO> = Vec::new();
+
+        for supply in &supplies {
+            let levels = match supply.reorder_levels() {
+                Some(levels) => levels,
+                None => continue,
+            };
+
+            let stock_unit = units
+                .iter()
+                .find(|unit| unit.id() == supply.units().stock_unit_id())
+                .ok_or(Error::DomainError(format!(
+                    "stock unit of {} is not registered.",
+                    supply.name().as_str()
+                )))?;
+
+            let on_hand = ledger
+                .iter()
+                .find(|line| {
+                    line.supply_id == supply.id().as_str()
+                        && line.unit_name == stock_unit.name().as_str()
+                })
+                .map(|line| Quantity::from_f64(line.quantity, RoundingMode::HalfUp))
+                .transpose()?
+                .unwrap_or(Quantity::ZERO);
+
+            let suggested_quantity = match levels.suggest(on_hand)? {
+                Some(quantity) => quantity,
+                None => continue,
+            };
+
+            let line = ReorderLineDTO {
+                supply_id: supply.id().to_string(),
+                supply_name: supply.name().to_string(),
+                unit_name: stock_unit.name().to_string(),
+                on_hand: on_hand.as_f64(),
+                par_level: levels.par_level().as_f64(),
+                reorder_point: levels.reorder_point().as_f64(),
+                suggested_quantity: suggested_quantity.as_f64(),
+            };
+
+            match suggestions
+                .iter_mut()
+                .find(|suggestion| suggestion.supplier_id == supply.supplier_id().as_str())
+            {
+                Some(suggestion) => suggestion.lines.push(line),
+                None => suggestions.push(ReorderSuggestionDTO {
+                    supplier_id: supply.supplier_id().to_string(),
+                    supplier_name: suppliers
+                        .iter()
+                        .find(|supplier| supplier.id() == supply.supplier_id())
+                        .map(|supplier| supplier.name().to_string())
+                        .unwrap_or_default(),
+                    lines: vec![line],
+                }),
+            }
+        }
+
+        Ok(suggestions)
+    }
+}
@@ -0,0 +1,459 @@
+//! This module provides the implementation of the `ReportUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::report::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::transfer::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::*;
+
+/// Report usecase
+///
+/// Reports consolidate the stores unless a store is given,
+/// each store valued by its own stocktakings.
+/// Transfers move cost from the supply of the source store to that of the destination,
+/// so they cancel out once both stores are consolidated.
+pub struct ReportService {
+    store_repository: Arc<dyn ForStorePersistence>,
+    supplier_repository: Arc<dyn ForSupplierPersistence>,
+    journal_respository: Arc<dyn ForJournalPersistence>,
+    stocktaking_respository: Arc<dyn ForStocktakingPersistence>,
+    transfer_repository: Arc<dyn ForTransferPersistence>,
+}
+
+impl ReportService {
+    pub fn new(
+        store_repository: Arc<dyn ForStorePersistence>,
+        supplier_repository: Arc<dyn ForSupplierPersistence>,
+        journal_respository: Arc<dyn ForJournalPersistence>,
+        stocktaking_respository: Arc<dyn ForStocktakingPersistence>,
+        transfer_repository: Arc<dyn ForTransferPersistence>,
+    ) -> Self {
+        Self {
+            store_repository,
+            supplier_repository,
+            journal_respository,
+            stocktaking_respository,
+            transfer_repository,
+        }
+    }
+
+    /// Returns the latest stocktaking of the store between `start` and `end`.
+    fn latest_stocktaking(
+        &self,
+        store: &StoreContext,
+        start: Option<StocktakenDateTime>,
+        end: StocktakenDateTime,
+    ) -> Result<Option<Stocktaking>> {
+        let stocktakings = self.stocktaking_respository.find(
+            store,
+            FindStocktakingsQuery {
+                period_start: start,
+                period_end: Some(end),
+            },
+        )?;
+
+        Ok(stocktakings
+            .into_iter()
+            .max_by_key(|stocktaking| stocktaking.stocktaken_at().as_i64()))
+    }
+
+    /// Computes the cost of goods sold of the supplies of a store in the period,
+    /// none if the store has no stocktaking in the period.
+    fn store_costs(
+        &self,
+        store: &Store,
+        query: &CostOfGoodsSoldQuery,
+    ) -> Result<Option<StoreCosts>> {
+        let context = StoreContext::Store(store.id().clone());
+
+        // opening inventory is the latest stocktaking before the period
+        let opening = self.latest_stocktaking(
+            &context,
+            None,
+            StocktakenDateTime::new(query.period_start.saturating_sub(1)),
+        )?;
+
+        let closing = match self.latest_stocktaking(
+            &context,
+            Some(StocktakenDateTime::new(query.period_start)),
+            StocktakenDateTime::new(query.period_end),
+        )? {
+            Some(closing) => closing,
+            None => return Ok(None),
+        };
+
+        // purchases and transfers are those after the opening stocktaking up to the closing one
+        let opened_at = opening
+            .as_ref()
+            .map(|opening| opening.stocktaken_at().as_i64() + 1);
+        let closed_at = closing.stocktaken_at().as_i64();
+
+        let mut journals = self.journal_respository.find(
+            &context,
+            FindJournalsQuery {
+                period_start: opened_at.map(EntryDateTime::new),
+                period_end: Some(EntryDateTime::new(closed_at)),
+                supplier_name: None,
+                supply_name: None,
+            },
+        )?;
+
+        journals.sort_by_key(|journal| journal.entry_datetime().as_i64());
+
+        let mut costs: Vec<SupplyCost> = Vec::new();
+        let mut purchases: Vec<SupplierCost> = Vec::new();
+
+        if let Some(opening) = &opening {
+            for record in opening.records() {
+                let cost = supply_cost(&mut costs, record.supply_id(), record.supply_name());
+
+                cost.cost = cost.cost.checked_add(CostOfGoodsSold::new(
+                    record.total_price().as_money(),
+                    Money::ZERO,
+                    Money::ZERO,
+                    Money::ZERO,
+                ))?;
+            }
+        }
+
+        for record in journals.iter().flat_map(|journal| journal.records()) {
+            let cost = supply_cost(&mut costs, record.supply_id(), record.supply_name());
+
+            cost.supply_name = record.supply_name().clone();
+            cost.supplier_id = Some(record.supplier_id().clone());
+            cost.supplier_name = Some(record.supplier_name().clone());
+            cost.cost = cost.cost.checked_add(CostOfGoodsSold::new(
+                Money::ZERO,
+                record.total_price().as_money(),
+                Money::ZERO,
+                Money::ZERO,
+            ))?;
+
+            let purchase = supplier_cost(
+                &mut purchases,
+                Some(record.supplier_id()),
+                Some(record.supplier_name()),
+            );
+
+            purchase.cost = purchase.cost.checked_add(CostOfGoodsSold::new(
+                Money::ZERO,
+                record.total_price().as_money(),
+                Money::ZERO,
+                Money::ZERO,
+            ))?;
+        }
+
+        let transfers = self.transfer_repository.find(
+            &context,
+            FindTransfersQuery {
+                period_start: opened_at.map(TransferredDateTime::new),
+                period_end: Some(TransferredDateTime::new(closed_at)),
+            },
+        )?;
+
+        for transfer in &transfers {
+            let direction = match transfer.direction(&context) {
+                Some(direction) => direction,
+                None => continue,
+            };
+
+            for line in transfer.lines() {
+                let (supply_id, transferred) = match direction {
+                    TransferDirection::Inbound => {
+                        (line.destination_supply_id(), line.total_price().as_money())
+                    }
+                    TransferDirection::Outbound => (
+                        line.supply_id(),
+                        Money::ZERO.checked_sub(line.total_price().as_money())?,
+                    ),
+                };
+
+                let cost = supply_cost(&mut costs, supply_id, line.supply_name());
+
+                cost.cost = cost.cost.checked_add(CostOfGoodsSold::new(
+                    Money::ZERO,
+                    Money::ZERO,
+                    transferred,
+                    Money::ZERO,
+                ))?;
+            }
+        }
+
+        for record in closing.records() {
+            let cost = supply_cost(&mut costs, record.supply_id(), record.supply_name());
+
+            cost.supply_name = record.supply_name().clone();
+            cost.cost = cost.cost.checked_add(CostOfGoodsSold::new(
+                Money::ZERO,
+                Money::ZERO,
+                Money::ZERO,
+                record.total_price().as_money(),
+            ))?;
+        }
+
+        Ok(Some(StoreCosts {
+            store_id: store.id().clone(),
+            store_name: store.name().clone(),
+            opening_stocktaking_id: opening.map(|opening| opening.id().clone()),
+            closing_stocktaking_id: closing.id().clone(),
+            costs,
+            purchases,
+        }))
+    }
+}
+
+/// Cost of goods sold of the supplies of a store.
+struct StoreCosts {
+    store_id: StoreId,
+    store_name: StoreName,
+    opening_stocktaking_id: Option<StocktakingId>,
+    closing_stocktaking_id: StocktakingId,
+    costs: Vec<SupplyCost>,
+    /// purchases by the supplier of each journal line
+    purchases: Vec<SupplierCost>,
+}
+
+/// Cost of goods sold of a supply.
+struct SupplyCost {
+    supply_id: SupplyId,
+    supply_name: SupplyName,
+    /// supplier the supply was last bought from
+    supplier_id: Option<SupplierId>,
+    /// supplier name recorded on journal lines
+    supplier_name: Option<SupplierName>,
+    cost: CostOfGoodsSold,
+}
+
+/// Cost of goods sold of a supplier.
+struct SupplierCost {
+    supplier_id: Option<SupplierId>,
+    /// supplier name recorded on journal lines
+    supplier_name: Option<SupplierName>,
+    cost: CostOfGoodsSold,
+}
+
+/// Returns the cost of `supply_id` in `costs`, adding it if it does not exist.
+fn supply_cost<'a>(
+    costs: &'a mut Vec<SupplyCost>,
+    supply_id: &SupplyId,
+    supply_name: &SupplyName,
+) -> &'a mut SupplyCost {
+    match costs.iter().position(|cost| &cost.supply_id == supply_id) {
+        Some(index) => &mut costs[index],
+        None => {
+            costs.push(SupplyCost {
+                supply_id: supply_id.clone(),
+                supply_name: supply_name.clone(),
+                supplier_id: None,
+                supplier_name: None,
+                cost: CostOfGoodsSold::default(),
+            });
+
+            costs.last_mut().unwrap()
+        }
+    }
+}
+
+/// Returns the cost of `supplier_id` in `costs`, adding it if it does not exist.
+fn supplier_cost<'a>(
+    costs: &'a mut Vec<SupplierCost>,
+    supplier_id: Option<&SupplierId>,
+    supplier_name: Option<&SupplierName>,
+) -> &'a mut SupplierCost {
+    match costs
+        .iter()
+        .position(|cost| cost.supplier_id.as_ref() == supplier_id)
+    {
+        Some(index) => &mut costs[index],
+        None => {
+            costs.push(SupplierCost {
+                supplier_id: supplier_id.cloned(),
+                supplier_name: supplier_name.cloned(),
+                cost: CostOfGoodsSold::default(),
+            });
+
+            costs.last_mut().unwrap()
+        }
+    }
+}
+
+/// Report usecase implementation
+impl ReportUsecase for ReportService {
+    fn cost_of_goods_sold(&self, query: CostOfGoodsSoldQuery) -> Result<CostOfGoodsSoldDTO> {
+        if query.period_end < query.period_start {
+            return Err(Error::DomainError(format!(
+                "period end must not be before period start."
+            )));
+        }
+
+        let stores = match query.store_id.as_ref().filter(|id| !id.trim().is_empty()) {
+            Some(store_id) => vec![self
+                .store_repository
+                .get(StoreId::new(store_id)?)?
+                .ok_or(Error::DomainError(format!("store does not exist.")))?],
+            None => self.store_repository.list()?,
+        };
+
+        let mut store_costs: Vec<StoreCosts> = Vec::new();
+
+        // stores without a stocktaking in the period are left out of consolidation
+        for store in &stores {
+            if let Some(costs) = self.store_costs(store, &query)? {
+                store_costs.push(costs);
+            }
+        }
+
+        if store_costs.is_empty() {
+            return Err(Error::DomainError(format!(
+                "stocktaking in the period does not exist."
+            )));
+        }
+
+        let mut costs: Vec<SupplyCost> = Vec::new();
+
+        for store_cost in &store_costs {
+            for own in &store_cost.costs {
+                let cost = supply_cost(&mut costs, &own.supply_id, &own.supply_name);
+
+                if own.supplier_id.is_some() {
+                    cost.supplier_id = own.supplier_id.clone();
+                    cost.supplier_name = own.supplier_name.clone();
+                }
+                cost.cost = cost.cost.checked_add(own.cost)?;
+            }
+        }
+
+        let mut supplier_costs: Vec<SupplierCost> = Vec::new();
+
+        // inventories and transfers of a supply go to the supplier it was last bought from
+        for cost in &costs {
+            let supplier_cost = supplier_cost(
+                &mut supplier_costs,
+                cost.supplier_id.as_ref(),
+                cost.supplier_name.as_ref(),
+            );
+
+            supplier_cost.cost = supplier_cost.cost.checked_add(CostOfGoodsSold::new(
+                cost.cost.opening_inventory(),
+                Money::ZERO,
+                cost.cost.transfers(),
+                cost.cost.closing_inventory(),
+            ))?;
+        }
+
+        // purchases go to the supplier of each journal line
+        for purchase in store_costs
+            .iter()
+            .flat_map(|store_cost| &store_cost.purchases)
+        {
+            let supplier_cost = supplier_cost(
+                &mut supplier_costs,
+                purchase.supplier_id.as_ref(),
+                purchase.supplier_name.as_ref(),
+            );
+
+            supplier_cost.cost = supplier_cost.cost.checked_add(purchase.cost)?;
+        }
+
+        let total = costs
+            .iter()
+            .try_fold(CostOfGoodsSold::default(), |total, cost| {
+                total.checked_add(cost.cost)
+            })?;
+
+        // stocktakings are only given for a single store
+        let (opening_stocktaking_id, closing_stocktaking_id) = match store_costs.as_slice() {
+            [store_cost] => (
+                store_cost
+                    .opening_stocktaking_id
+                    .as_ref()
+                    .map(|id| id.to_string()),
+                Some(store_cost.closing_stocktaking_id.to_string()),
+            ),
+            _ => (None, None),
+        };
+
+        let stores = store_costs
+            .iter()
+            .map(|store_cost| {
+                let total = store_cost
+                    .costs
+                    .iter()
+                    .try_fold(CostOfGoodsSold::default(), |total, cost| {
+                        total.checked_add(cost.cost)
+                    })?;
+
+                Ok(StoreCostOfGoodsSoldDTO {
+                    store_id: store_cost.store_id.to_string(),
+                    store_name: store_cost.store_name.to_string(),
+                    opening_stocktaking_id: store_cost
+                        .opening_stocktaking_id
+                        .as_ref()
+                        .map(|id| id.to_string()),
+                    closing_stocktaking_id: store_cost.closing_stocktaking_id.to_string(),
+                    opening_inventory: total.opening_inventory().as_f64(),
+                    purchases: total.purchases().as_f64(),
+                    transfers: total.transfers().as_f64(),
+                    closing_inventory: total.closing_inventory().as_f64(),
+                    cost_of_goods_sold: total.cost()?.as_f64(),
+                })
+            })
+            .collect::<Result<Vec<StoreCostOfGoodsSoldDTO>>>()?;
+
+        Ok(CostOfGoodsSoldDTO {
+            period_start: query.period_start,
+            period_end: query.period_end,
+            opening_stocktaking_id,
+            closing_stocktaking_id,
+            opening_inventory: total.opening_inventory().as_f64(),
+            purchases: total.purchases().as_f64(),
+            transfers: total.transfers().as_f64(),
+            closing_inventory: total.closing_inventory().as_f64(),
+            cost_of_goods_sold: total.cost()?.as_f64(),
+            suppliers: supplier_costs
+                .into_iter()
+                .map(|cost| {
+                    let supplier_name = match &cost.supplier_id {
+                        Some(supplier_id) => self
+                            .supplier_repository
+                            .get(&StoreContext::Consolidated, supplier_id.clone())?
+                            .map(|supplier| supplier.name().to_string())
+                            .or(cost.supplier_name.as_ref().map(|name| name.to_string())),
+                        None => None,
+                    };
+
+                    Ok(SupplierCostOfGoodsSoldDTO {
+                        supplier_id: cost.supplier_id.map(|id| id.to_string()),
+                        supplier_name,
+                        opening_inventory: cost.cost.opening_inventory().as_f64(),
+                        purchases: cost.cost.purchases().as_f64(),
+                        transfers: cost.cost.transfers().as_f64(),
+                        closing_inventory: cost.cost.closing_inventory().as_f64(),
+                        cost_of_goods_sold: cost.cost.cost()?.as_f64(),
+                    })
+                })
+                .collect::<Result<Vec<SupplierCostOfGoodsSoldDTO>>>()?,
+            supplies: costs
+                .into_iter()
+                .map(|cost| {
+                    Ok(SupplyCostOfGoodsSoldDTO {
+                        supply_id: cost.supply_id.to_string(),
+                        supply_name: cost.supply_name.to_string(),
+                        supplier_id: cost.supplier_id.map(|id| id.to_string()),
+                        opening_inventory: cost.cost.opening_inventory().as_f64(),
+                        purchases: cost.cost.purchases().as_f64(),
+                        transfers: cost.cost.transfers().as_f64(),
+                        closing_inventory: cost.cost.closing_inventory().as_f64(),
+                        cost_of_goods_sold: cost.cost.cost()?.as_f64(),
+                    })
+                })
+                .collect::<Result<Vec<SupplyCostOfGoodsSoldDTO>>>()?,
+            stores,
+        })
+    }
+}
@@ -2,27 +2,433 @@
 use std::sync::Arc;
 
 use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::event::*;
+use crate::core::domain::values::location::*;
+use crate::core::domain::values::lot::*;
+use crate::core::domain::values::revision::*;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::unit::*;
+use crate::core::domain::values::valuation::*;
 use crate::core::provided_ports::*;
 use crate::core::required_ports::*;
+use crate::core::services::accounting::ensure_open;
+use crate::core::services::audit::snapshot;
+use crate::core::services::revision::*;
+use crate::core::services::stock::category::{category_totals, supplies_in_category};
+use crate::core::services::stock::location::{counting_position, ordered_locations};
+use crate::core::services::stock::unit::aggregate_stock_quantities;
+use crate::core::services::trash::*;
 use crate::core::*;
 
 /// Stocktaking usecase
 pub struct StocktakingService {
     supply_respository: Arc<dyn ForSupplyPersistence>,
     stocktaking_respository: Arc<dyn ForStocktakingPersistence>,
+    journal_respository: Arc<dyn ForJournalPersistence>,
+    unit_repository: Arc<dyn ForUnitPersistence>,
+    category_repository: Arc<dyn ForCategoryPersistence>,
+    location_repository: Arc<dyn ForStorageLocationPersistence>,
+    period_repository: Arc<dyn ForAccountingPeriodPersistence>,
+    event_publisher: Arc<dyn ForEventPublishing>,
+    rounding_policy: RoundingPolicy,
+    valuation_method: ValuationMethod,
+    operator: Option<OperatorName>,
+    store: StoreContext,
 }
 
 impl StocktakingService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         supply_respository: Arc<dyn ForSupplyPersistence>,
         stocktaking_respository: Arc<dyn ForStocktakingPersistence>,
+        journal_respository: Arc<dyn ForJournalPersistence>,
+        unit_repository: Arc<dyn ForUnitPersistence>,
+        category_repository: Arc<dyn ForCategoryPersistence>,
+        location_repository: Arc<dyn ForStorageLocationPersistence>,
+        period_repository: Arc<dyn ForAccountingPeriodPersistence>,
+        event_publisher: Arc<dyn ForEventPublishing>,
+        rounding_policy: RoundingPolicy,
+        valuation_method: ValuationMethod,
+        operator: Option<OperatorName>,
+        store: StoreContext,
     ) -> Self {
         Self {
             supply_respository,
             stocktaking_respository,
+            journal_respository,
+            unit_repository,
+            category_repository,
+            location_repository,
+            period_repository,
+            event_publisher,
+            rounding_policy,
+            valuation_method,
+            operator,
+            store,
         }
     }
+
+    /// Returns the change of a stocktaking to log in the audit log.
+    fn change_of(
+        &self,
+        stocktaking_id: &StocktakingId,
+        operation: AuditOperation,
+        before: Option<&Stocktaking>,
+        after: Option<&Stocktaking>,
+    ) -> Result<AuditChange> {
+        let snapshot_of = |stocktaking: &Stocktaking| {
+            self.dtos_of(std::slice::from_ref(stocktaking))?
+                .first()
+                .map(snapshot)
+                .transpose()
+        };
+
+        AuditChange::new(
+            AuditEntity::Stocktaking,
+            stocktaking_id.as_str(),
+            operation,
+            self.operator.clone(),
+            before.map(snapshot_of).transpose()?.flatten(),
+            after.map(snapshot_of).transpose()?.flatten(),
+        )
+    }
+
+    /// Converts stocktakings into DTOs, records in counting order.
+    fn dtos_of(&self, stocktakings: &[Stocktaking]) -> Result<Vec<StocktakingDTO>> {
+        let locations = self.location_repository.list()?;
+        let locations = ordered_locations(&locations);
+        let supplies = self.supply_respository.list(&self.store)?;
+
+        Ok(stocktakings
+            .iter()
+            .map(|stocktaking| stocktaking_dto(stocktaking, &locations, &supplies))
+            .collect())
+    }
+
+    /// Returns the storage location a line of `supply_id` was counted in.
+    ///
+    /// Defaults to the location the supply is placed in, failing if the location does not exist.
+    fn location_of(
+        &self,
+        location_id: Option<&String>,
+        supply_id: &SupplyId,
+    ) -> Result<Option<StorageLocationId>> {
+        let location_id = match location_id.filter(|id| !id.trim().is_empty()) {
+            Some(location_id) => StorageLocationId::new(location_id)?,
+            None => {
+                return Ok(self
+                    .supply_respository
+                    .get(&self.store, supply_id.clone())?
+                    .and_then(|supply| supply.placement().cloned())
+                    .map(|placement| placement.location_id().clone()))
+            }
+        };
+
+        if self.location_repository.get(location_id.clone())?.is_none() {
+            return Err(Error::DomainError(format!(
+                "storage location does not exist."
+            )));
+        }
+
+        Ok(Some(location_id))
+    }
+
+    /// Finds stocktakings matching `query`.
+    ///
+    /// With a category, only the lines of supplies in it are kept
+    /// and stocktakings without such lines are left out.
+    fn find(&self, query: SearchStocktakingQuery) -> Result<Vec<Stocktaking>> {
+        let supply_ids = supplies_in_category(
+            &*self.category_repository,
+            &*self.supply_respository,
+            &self.store,
+            query.category_id,
+        )?;
+
+        let mut stocktakings = self.stocktaking_respository.find(
+            &self.store,
+            FindStocktakingsQuery {
+                period_start: query.period_start.map(StocktakenDateTime::new),
+                period_end: query.period_end.map(StocktakenDateTime::new),
+            },
+        )?;
+
+        if let Some(supply_ids) = supply_ids {
+            for stocktaking in stocktakings.iter_mut() {
+                let records: Vec<StocktakingRecord> = stocktaking
+                    .records()
+                    .iter()
+                    .filter(|record| supply_ids.contains(record.supply_id()))
+                    .cloned()
+                    .collect();
+
+                stocktaking.swap_records(records);
+            }
+
+            stocktakings.retain(|stocktaking| !stocktaking.records().is_empty());
+        }
+
+        Ok(stocktakings)
+    }
+
+    /// Builds stocktaking records from DTOs.
+    ///
+    /// Unit prices not provided are derived from the history before `stocktaken_at`,
+    /// ignoring the stocktaking `stocktaking_id` itself.
+    fn records_of(
+        &self,
+        records: &[StocktakingRecordDTO],
+        stocktaken_at: &StocktakenDateTime,
+        stocktaking_id: &StocktakingId,
+    ) -> Result<Vec<StocktakingRecord>> {
+        let mut stocktaking_records: Vec<StocktakingRecord> = Vec::new();
+
+        for record in records {
+            let supply_id = SupplyId::new(&record.supply_id)?;
+            let supply_name = SupplyName::new(&record.supply_name)?;
+            let unit_name = UnitName::new(&record.unit_name)?;
+            let quantity = StocktakingQuantity::new(record.quantity)?;
+
+            let (unit_price, valuation_method) = match record.unit_price {
+                Some(unit_price) => (StocktakingUnitPrice::new(unit_price)?, None),
+                None => (
+                    self.valuate(
+                        &supply_id,
+                        &unit_name,
+                        quantity.as_quantity(),
+                        stocktaken_at,
+                        stocktaking_id,
+                    )?,
+                    Some(self.valuation_method),
+                ),
+            };
+
+            let total_price = match record.total_price {
+                Some(total_price) => TotalPrice::new(total_price)?,
+                None => TotalPrice::from_money(
+                    self.rounding_policy
+                        .line_total(unit_price.as_money(), quantity.as_quantity())?,
+                )?,
+            };
+
+            let location_id = self.location_of(record.location_id.as_ref(), &supply_id)?;
+
+            let lot_number = record
+                .lot_number
+                .as_deref()
+                .filter(|lot_number| !lot_number.trim().is_empty())
+                .map(LotNumber::new)
+                .transpose()?;
+
+            stocktaking_records.push(
+                StocktakingRecord::new(
+                    supply_id,
+                    supply_name,
+                    unit_name,
+                    unit_price,
+                    quantity,
+                    total_price,
+                    valuation_method,
+                )
+                .with_location(location_id)
+                .with_lot(lot_number),
+            );
+        }
+
+        Ok(stocktaking_records)
+    }
+
+    /// Derives the unit price of `quantity` in `unit_name` from the purchases and
+    /// stocktakings of the supply.
+    fn valuate(
+        &self,
+        supply_id: &SupplyId,
+        unit_name: &UnitName,
+        quantity: Quantity,
+        stocktaken_at: &StocktakenDateTime,
+        stocktaking_id: &StocktakingId,
+    ) -> Result<StocktakingUnitPrice> {
+        let supply = self
+            .supply_respository
+            .get(&self.store, supply_id.clone())?;
+        let units = self.unit_repository.list()?;
+
+        // number of stock units in one `name`, none for lines which cannot be converted
+        let factor_of = |name: &UnitName| -> Result<Option<Quantity>> {
+            let supply = match &supply {
+                Some(supply) => supply,
+                // deleted supplies are valued in the unit of the line
+                None if name == unit_name => return Ok(Some(ConversionFactor::ONE.as_quantity())),
+                None => return Ok(None),
+            };
+
+            let factor = units
+                .iter()
+                .find(|unit| unit.name() == name)
+                .and_then(|unit| supply.units().factor_of(unit.id()))
+                .ok_or(Error::DomainError(format!(
+                    "{} has no conversion from {} into its stock unit.",
+                    supply.name().as_str(),
+                    name.as_str()
+                )))?;
+
+            Ok(Some(factor.as_quantity()))
+        };
+
+        // (time, order at the same time, event)
+        let mut history: Vec<(i64, u8, StockEvent)> = Vec::new();
+
+        let journals = self.journal_respository.find(
+            &self.store,
+            FindJournalsQuery {
+                period_start: None,
+                period_end: Some(EntryDateTime::new(stocktaken_at.as_i64())),
+                supplier_name: None,
+                supply_name: None,
+            },
+        )?;
+
+        for journal in &journals {
+            for record in journal.records() {
+                if record.supply_id() != supply_id {
+                    continue;
+                }
+
+                if let Some(factor) = factor_of(record.unit_name())? {
+                    history.push((
+                        journal.entry_datetime().as_i64(),
+                        0,
+                        StockEvent::Purchase {
+                            quantity: record
+                                .quantity()
+                                .as_quantity()
+                                .checked_mul(factor, RoundingMode::HalfUp)?,
+                            amount: record.total_price().as_money(),
+                        },
+                    ));
+                }
+            }
+        }
+
+        let stocktakings = self.stocktaking_respository.find(
+            &self.store,
+            FindStocktakingsQuery {
+                period_start: None,
+                period_end: Some(stocktaken_at.clone()),
+            },
+        )?;
+
+        // purchases at the same time are counted by the stocktaking
+        for stocktaking in stocktakings.iter().filter(|stocktaking| {
+            stocktaking.id() != stocktaking_id
+                && stocktaking.stocktaken_at().as_i64() < stocktaken_at.as_i64()
+        }) {
+            for record in stocktaking.records() {
+                if record.supply_id() != supply_id {
+                    continue;
+                }
+
+                if let Some(factor) = factor_of(record.unit_name())? {
+                    history.push((
+                        stocktaking.stocktaken_at().as_i64(),
+                        1,
+                        StockEvent::Count {
+                            quantity: record
+                                .quantity()
+                                .as_quantity()
+                                .checked_mul(factor, RoundingMode::HalfUp)?,
+                            unit_price: record
+                                .unit_price()
+                                .as_money()
+                                .checked_div(factor, RoundingMode::HalfUp)?,
+                        },
+                    ));
+                }
+            }
+        }
+
+        history.sort_by_key(|(at, order, _)| (*at, *order));
+
+        let history: Vec<StockEvent> = history.into_iter().map(|(_, _, event)| event).collect();
+
+        let factor = factor_of(unit_name)?.ok_or(Error::DomainError(format!(
+            "{} has no conversion from {} into its stock unit.",
+            supply_id.as_str(),
+            unit_name.as_str()
+        )))?;
+
+        let unit_price = self
+            .valuation_method
+            .unit_price(
+                &history,
+                quantity.checked_mul(factor, RoundingMode::HalfUp)?,
+            )?
+            .ok_or(Error::DomainError(format!(
+                "unit price of {} cannot be derived from purchases.",
+                unit_name.as_str()
+            )))?;
+
+        StocktakingUnitPrice::from_money(unit_price.checked_mul(factor, RoundingMode::HalfUp)?)
+    }
+}
+
+/// Converts a stocktaking into its DTO.
+///
+/// Records are ordered by their storage location, then by the position of the supply in it.
+fn stocktaking_dto(
+    stocktaking: &Stocktaking,
+    locations: &[&StorageLocation],
+    supplies: &[Supply],
+) -> StocktakingDTO {
+    let mut records: Vec<&StocktakingRecord> = stocktaking.records().iter().collect();
+
+    records.sort_by_key(|record| {
+        counting_position(
+            locations,
+            supplies
+                .iter()
+                .find(|supply| supply.id() == record.supply_id()),
+            record.location_id(),
+        )
+    });
+
+    StocktakingDTO {
+        id: stocktaking.id().to_string(),
+        stocktaken_date: stocktaking.stocktaken_at().as_i64(),
+        total_price: stocktaking.total_price().as_f64(),
+        records: records
+            .into_iter()
+            .map(|record| StocktakingRecordDTO {
+                supply_id: record.supply_id().to_string(),
+                supply_name: record.supply_name().to_string(),
+                unit_name: record.unit_name().to_string(),
+                unit_price: Some(record.unit_price().as_f64()),
+                quantity: record.quantity().as_f64(),
+                total_price: Some(record.total_price().as_f64()),
+                valuation_method: record.valuation_method().map(|method| method.to_string()),
+                location_id: record
+                    .location_id()
+                    .map(|location_id| location_id.to_string()),
+                location_name: record.location_id().and_then(|location_id| {
+                    locations
+                        .iter()
+                        .find(|location| location.id() == location_id)
+                        .map(|location| location.name().to_string())
+                }),
+                lot_number: record.lot_number().map(|lot_number| lot_number.to_string()),
+            })
+            .collect(),
+    }
+}
+
+/// Returns the total price of stocktaking records.
+fn total_of(records: &[StocktakingRecord]) -> Result<TotalPrice> {
+    TotalPrice::from_money(Money::checked_sum(
+        records.iter().map(|record| record.total_price().as_money()),
+    )?)
 }
 
 /// Stocktaking usecase implementation
@@ -30,7 +436,9 @@ impl StocktakingUsecase for StocktakingService {
     fn get(&self, stocktaking_id: impl AsRef<str>) -> Result<Option<StocktakingDTO>> {
         let stocktaking_id = StocktakingId::new(stocktaking_id.as_ref())?;
 
-        let stocktaking = self.stocktaking_respository.get(stocktaking_id)?;
+        let stocktaking = self
+            .stocktaking_respository
+            .get(&self.store, stocktaking_id)?;
 
         if stocktaking.is_none() {
             return Ok(None);
@@ -38,86 +446,101 @@ impl StocktakingUsecase for StocktakingService {
 
         let stocktaking = stocktaking.unwrap();
 
-        let stocktaking = StocktakingDTO {
-            id: stocktaking.id().to_string(),
-            stocktaken_date: stocktaking.stocktaken_at().as_i64(),
-            total_price: stocktaking.total_price().as_f64(),
-            records: stocktaking
-                .records()
-                .iter()
-                .map(|record| StocktakingRecordDTO {
-                    supply_id: record.supply_id().to_string(),
-                    supply_name: record.supply_name().to_string(),
-                    quantity: record.quantity().as_f64(),
-                    unit_name: record.unit_name().to_string(),
-                    unit_price: record.unit_price().as_f64(),
-                    total_price: record.total_price().as_f64(),
-                })
-                .collect(),
-        };
-
-        Ok(Some(stocktaking))
+        Ok(self.dtos_of(&[stocktaking])?.pop())
     }
 
     fn list(&self) -> Result<Vec<StocktakingDTO>> {
-        let stocktakings = self.stocktaking_respository.list()?;
+        let stocktakings = self.stocktaking_respository.list(&self.store)?;
+
+        self.dtos_of(&stocktakings)
+    }
+
+    fn search(&self, query: SearchStocktakingQuery) -> Result<Vec<StocktakingDTO>> {
+        let stocktakings = self.find(query)?;
 
-        let stocktakings: Vec<StocktakingDTO> = stocktakings
+        self.dtos_of(&stocktakings)
+    }
+
+    fn aggregate_quantities(&self, query: SearchStocktakingQuery) -> Result<Vec<StockQuantityDTO>> {
+        let lines = self
+            .find(query)?
             .iter()
-            .map(|stocktaking| StocktakingDTO {
-                id: stocktaking.id().to_string(),
-                stocktaken_date: stocktaking.stocktaken_at().as_i64(),
-                total_price: stocktaking.total_price().as_f64(),
-                records: stocktaking
-                    .records()
-                    .iter()
-                    .map(|record| StocktakingRecordDTO {
-                        quantity: record.quantity().as_f64(),
-                        supply_id: record.supply_id().to_string(),
-                        supply_name: record.supply_name().to_string(),
-                        unit_name: record.unit_name().to_string(),
-                        unit_price: record.unit_price().as_f64(),
-                        total_price: record.total_price().as_f64(),
-                    })
-                    .collect(),
+            .flat_map(|stocktaking| stocktaking.records().to_vec())
+            .map(|record| {
+                (
+                    record.supply_id().clone(),
+                    record.supply_name().clone(),
+                    record.unit_name().clone(),
+                    record.quantity().as_quantity(),
+                )
             })
             .collect();
 
-        Ok(stocktakings)
+        aggregate_stock_quantities(&*self.supply_respository, &*self.unit_repository, lines)
     }
 
-    fn search(&self, query: SearchStocktakingQuery) -> Result<Vec<StocktakingDTO>> {
-        let query = FindStocktakingsQuery {
-            period_start: query
-                .period_start
-                .map(|start| StocktakenDateTime::new(start)),
-            period_end: query.period_end.map(|end| StocktakenDateTime::new(end)),
-        };
+    fn totals_by_category(&self, query: SearchStocktakingQuery) -> Result<Vec<CategoryTotalDTO>> {
+        let lines = self
+            .find(query)?
+            .iter()
+            .flat_map(|stocktaking| stocktaking.records().to_vec())
+            .map(|record| (record.supply_id().clone(), record.total_price().as_money()))
+            .collect();
+
+        category_totals(
+            &self.category_repository.list()?,
+            &self.supply_respository.list(&self.store)?,
+            lines,
+        )
+    }
 
-        let stocktakings = self.stocktaking_respository.find(query)?;
+    fn count_sheet(&self) -> Result<Vec<CountSheetDTO>> {
+        let locations = self.location_repository.list()?;
+        let locations = ordered_locations(&locations);
+        let units = self.unit_repository.list()?;
 
-        let stocktakings: Vec<StocktakingDTO> = stocktakings
-            .iter()
-            .map(|stocktaking| StocktakingDTO {
-                id: stocktaking.id().to_string(),
-                stocktaken_date: stocktaking.stocktaken_at().as_i64(),
-                total_price: stocktaking.total_price().as_f64(),
-                records: stocktaking
-                    .records()
+        let mut supplies = self.supply_respository.list(&self.store)?;
+
+        supplies.sort_by_key(|supply| {
+            counting_position(
+                &locations,
+                Some(supply),
+                supply.placement().map(|placement| placement.location_id()),
+            )
+        });
+
+        let mut sheets: Vec<CountSheetDTO> = Vec::new();
+
+        for supply in &supplies {
+            let location = supply.placement().and_then(|placement| {
+                locations
                     .iter()
-                    .map(|record| StocktakingRecordDTO {
-                        quantity: record.quantity().as_f64(),
-                        supply_id: record.supply_id().to_string(),
-                        supply_name: record.supply_name().to_string(),
-                        unit_name: record.unit_name().to_string(),
-                        unit_price: record.unit_price().as_f64(),
-                        total_price: record.total_price().as_f64(),
-                    })
-                    .collect(),
-            })
-            .collect();
+                    .find(|location| location.id() == placement.location_id())
+            });
 
-        Ok(stocktakings)
+            let line = CountSheetLineDTO {
+                supply_id: supply.id().to_string(),
+                supply_name: supply.name().to_string(),
+                unit_name: units
+                    .iter()
+                    .find(|unit| unit.id() == supply.units().stock_unit_id())
+                    .map(|unit| unit.name().to_string())
+                    .unwrap_or(supply.unit_name().to_string()),
+            };
+
+            let location_id = location.map(|location| location.id().to_string());
+
+            match sheets.last_mut() {
+                Some(sheet) if sheet.location_id == location_id => sheet.lines.push(line),
+                _ => sheets.push(CountSheetDTO {
+                    location_id,
+                    location_name: location.map(|location| location.name().to_string()),
+                    lines: vec![line],
+                }),
+            }
+        }
+
+        Ok(sheets)
     }
 
     fn record(&self, command: RecordStocktakingCommand) -> Result<StocktakingDTO> {
@@ -129,81 +552,100 @@ impl StocktakingUsecase for StocktakingService {
             .map(|record| SupplyId::new(&record.supply_id))
             .collect::<Result<Vec<SupplyId>>>()?;
 
-        if !self.supply_respository.has(&supply_ids)? {
+        if !self.supply_respository.has(&self.store, &supply_ids)? {
             return Err(Error::DomainError(format!("supply does not exist.")));
         }
 
-        let mut records: Vec<StocktakingRecord> = Vec::new();
-
-        for record in &command.records {
-            records.push(StocktakingRecord::new(
-                SupplyId::new(&record.supply_id)?,
-                SupplyName::new(&record.supply_name)?,
-                UnitName::new(&record.unit_name)?,
-                StocktakingUnitPrice::new(record.unit_price)?,
-                StocktakingQuantity::new(record.quantity)?,
-                TotalPrice::new(record.total_price)?,
-            ));
-        }
+        ensure_open(
+            self.period_repository.as_ref(),
+            &self.store,
+            command.stocktaken_date,
+        )?;
 
-        let stocktaking = Stocktaking::restore(
-            id,
-            StocktakenDateTime::new(command.stocktaken_date),
-            TotalPrice::new(command.total_price)?,
-            records,
-        );
+        let stocktaken_at = StocktakenDateTime::new(command.stocktaken_date);
 
-        self.stocktaking_respository.add(stocktaking.clone())?;
+        let records = self.records_of(&command.records, &stocktaken_at, &id)?;
 
-        let stocktaking = StocktakingDTO {
-            id: stocktaking.id().to_string(),
-            stocktaken_date: stocktaking.stocktaken_at().as_i64(),
-            total_price: stocktaking.total_price().as_f64(),
-            records: stocktaking
-                .records()
-                .iter()
-                .map(|record| StocktakingRecordDTO {
-                    quantity: record.quantity().as_f64(),
-                    supply_id: record.supply_id().to_string(),
-                    supply_name: record.supply_name().to_string(),
-                    unit_name: record.unit_name().to_string(),
-                    unit_price: record.unit_price().as_f64(),
-                    total_price: record.total_price().as_f64(),
-                })
-                .collect(),
+        let total_price = match command.total_price {
+            Some(total_price) => TotalPrice::new(total_price)?,
+            None => total_of(&records)?,
         };
 
-        Ok(stocktaking)
+        let stocktaking = Stocktaking::record(
+            id,
+            stocktaken_at,
+            total_price,
+            records,
+            &self.rounding_policy,
+        )?;
+
+        let change = self.change_of(
+            stocktaking.id(),
+            AuditOperation::Record,
+            None,
+            Some(&stocktaking),
+        )?;
+
+        self.stocktaking_respository
+            .add(&self.store, stocktaking.clone(), Some(change))?;
+
+        self.event_publisher
+            .publish(DomainEvent::StocktakingRecorded {
+                stocktaking_id: stocktaking.id().clone(),
+            });
+
+        self.dtos_of(&[stocktaking])?
+            .pop()
+            .ok_or(Error::DomainError(format!("stocktaking does not exist.")))
     }
 
     fn edit(&self, command: EditStocktakingCommand) -> Result<()> {
         let stocktaking_id = StocktakingId::new(command.stocktaking_id)?;
 
+        let store = self.store.owner()?;
+
         let mut stocktaking = self
             .stocktaking_respository
-            .get(stocktaking_id)?
+            .get(&store, stocktaking_id)?
             .ok_or(Error::DomainError(format!("stocktaking does not exist.")))?;
 
-        let total_price = TotalPrice::new(command.total_price)?;
+        ensure_open(
+            self.period_repository.as_ref(),
+            &store,
+            stocktaking.stocktaken_at().as_i64(),
+        )?;
 
-        stocktaking.change_total_price(total_price);
+        let before = stocktaking.clone();
 
-        let mut records: Vec<StocktakingRecord> = Vec::new();
+        let records = self.records_of(
+            &command.records,
+            stocktaking.stocktaken_at(),
+            stocktaking.id(),
+        )?;
 
-        for record in &command.records {
-            records.push(StocktakingRecord::new(
-                SupplyId::new(&record.supply_id)?,
-                SupplyName::new(&record.supply_name)?,
-                UnitName::new(&record.unit_name)?,
-                StocktakingUnitPrice::new(record.unit_price)?,
-                StocktakingQuantity::new(record.quantity)?,
-                TotalPrice::new(record.total_price)?,
-            ));
-        }
+        let total_price = match command.total_price {
+            Some(total_price) => TotalPrice::new(total_price)?,
+            None => total_of(&records)?,
+        };
+
+        stocktaking.revise(total_price, records, &self.rounding_policy)?;
+
+        self.stocktaking_respository.add_revision(before.clone())?;
 
-        stocktaking.swap_records(records);
+        let change = self.change_of(
+            stocktaking.id(),
+            AuditOperation::Edit,
+            Some(&before),
+            Some(&stocktaking),
+        )?;
 
-        self.stocktaking_respository.save(stocktaking)?;
+        self.stocktaking_respository
+            .save(&store, stocktaking.clone(), Some(change))?;
+
+        self.event_publisher
+            .publish(DomainEvent::StocktakingEdited {
+                stocktaking_id: stocktaking.id().clone(),
+            });
 
         Ok(())
     }
@@ -211,7 +653,215 @@ impl StocktakingUsecase for StocktakingService {
     fn delete(&self, stocktaking_id: impl AsRef<str>) -> Result<()> {
         let stocktaking_id = StocktakingId::new(stocktaking_id.as_ref())?;
 
-        self.stocktaking_respository.delete(stocktaking_id)?;
+        let store = self.store.owner()?;
+
+        let stocktaking = self
+            .stocktaking_respository
+            .get(&store, stocktaking_id.clone())?;
+
+        if let Some(stocktaking) = &stocktaking {
+            ensure_open(
+                self.period_repository.as_ref(),
+                &store,
+                stocktaking.stocktaken_at().as_i64(),
+            )?;
+        }
+
+        let change = stocktaking
+            .as_ref()
+            .map(|stocktaking| {
+                self.change_of(
+                    &stocktaking_id,
+                    AuditOperation::Delete,
+                    Some(stocktaking),
+                    None,
+                )
+            })
+            .transpose()?;
+
+        self.stocktaking_respository
+            .delete(&store, stocktaking_id.clone(), change)?;
+
+        if stocktaking.is_some() {
+            self.event_publisher
+                .publish(DomainEvent::StocktakingDeleted { stocktaking_id });
+        }
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<StocktakingDTO>>> {
+        let trashed = self.stocktaking_respository.list_deleted(&self.store)?;
+
+        let items: Vec<_> = trashed
+            .iter()
+            .map(|trashed| trashed.item().clone())
+            .collect();
+
+        let dtos = self.dtos_of(&items)?;
+
+        Ok(trashed_dtos(&trashed, dtos))
+    }
+
+    fn restore(&self, stocktaking_id: impl AsRef<str>) -> Result<()> {
+        let stocktaking_id = StocktakingId::new(stocktaking_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let stocktaking = trashed_item(
+            self.stocktaking_respository.list_deleted(&store)?,
+            |stocktaking| stocktaking.id() == &stocktaking_id,
+            "stocktaking",
+        )?;
+
+        ensure_open(
+            self.period_repository.as_ref(),
+            &store,
+            stocktaking.stocktaken_at().as_i64(),
+        )?;
+
+        let change = self.change_of(
+            stocktaking.id(),
+            AuditOperation::Restore,
+            None,
+            Some(&stocktaking),
+        )?;
+
+        self.stocktaking_respository
+            .restore(&store, stocktaking.id().clone(), Some(change))?;
+
+        self.event_publisher
+            .publish(DomainEvent::StocktakingRestored {
+                stocktaking_id: stocktaking.id().clone(),
+            });
+
+        Ok(())
+    }
+
+    fn purge(&self, stocktaking_id: impl AsRef<str>) -> Result<()> {
+        let stocktaking_id = StocktakingId::new(stocktaking_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let stocktaking = trashed_item(
+            self.stocktaking_respository.list_deleted(&store)?,
+            |stocktaking| stocktaking.id() == &stocktaking_id,
+            "stocktaking",
+        )?;
+
+        let change = self.change_of(
+            stocktaking.id(),
+            AuditOperation::Purge,
+            Some(&stocktaking),
+            None,
+        )?;
+
+        self.stocktaking_respository
+            .purge(&store, stocktaking.id().clone(), Some(change))?;
+
+        self.event_publisher
+            .publish(DomainEvent::StocktakingPurged {
+                stocktaking_id: stocktaking.id().clone(),
+            });
+
+        Ok(())
+    }
+
+    fn list_revisions(
+        &self,
+        stocktaking_id: impl AsRef<str>,
+    ) -> Result<Vec<RevisionDTO<StocktakingDTO>>> {
+        let stocktaking_id = StocktakingId::new(stocktaking_id.as_ref())?;
+
+        let stocktaking = self
+            .stocktaking_respository
+            .get(&self.store, stocktaking_id.clone())?
+            .ok_or(Error::DomainError(format!("stocktaking does not exist.")))?;
+
+        let revisions = self
+            .stocktaking_respository
+            .list_revisions(&self.store, stocktaking_id)?;
+
+        let stocktakings: Vec<Stocktaking> = revisions
+            .iter()
+            .map(|revision| revision.item().clone())
+            .chain(std::iter::once(stocktaking))
+            .collect();
+
+        let dtos = self.dtos_of(&stocktakings)?;
+
+        Ok(revision_dtos(&revisions, dtos))
+    }
+
+    fn diff_revisions(
+        &self,
+        stocktaking_id: impl AsRef<str>,
+        from: u32,
+        to: u32,
+    ) -> Result<Vec<LineDiffDTO>> {
+        let stocktaking_id = StocktakingId::new(stocktaking_id.as_ref())?;
+
+        let stocktaking = self
+            .stocktaking_respository
+            .get(&self.store, stocktaking_id.clone())?
+            .ok_or(Error::DomainError(format!("stocktaking does not exist.")))?;
+
+        let revisions = self
+            .stocktaking_respository
+            .list_revisions(&self.store, stocktaking_id)?;
+
+        let from = revision_item(&revisions, &stocktaking, from)?;
+        let to = revision_item(&revisions, &stocktaking, to)?;
+
+        line_diff_dtos(&diff_lines(&from.revision_lines(), &to.revision_lines()))
+    }
+
+    fn revert(&self, stocktaking_id: impl AsRef<str>, revision: u32) -> Result<()> {
+        let stocktaking_id = StocktakingId::new(stocktaking_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let mut stocktaking = self
+            .stocktaking_respository
+            .get(&store, stocktaking_id.clone())?
+            .ok_or(Error::DomainError(format!("stocktaking does not exist.")))?;
+
+        ensure_open(
+            self.period_repository.as_ref(),
+            &store,
+            stocktaking.stocktaken_at().as_i64(),
+        )?;
+
+        let reverted = past_revision(
+            self.stocktaking_respository
+                .list_revisions(&store, stocktaking_id)?,
+            revision,
+        )?;
+
+        let before = stocktaking.clone();
+
+        stocktaking.revise(
+            reverted.total_price().clone(),
+            reverted.records().to_vec(),
+            &self.rounding_policy,
+        )?;
+
+        self.stocktaking_respository.add_revision(before.clone())?;
+
+        let change = self.change_of(
+            stocktaking.id(),
+            AuditOperation::Edit,
+            Some(&before),
+            Some(&stocktaking),
+        )?;
+
+        self.stocktaking_respository
+            .save(&store, stocktaking.clone(), Some(change))?;
+
+        self.event_publisher
+            .publish(DomainEvent::StocktakingEdited {
+                stocktaking_id: stocktaking.id().clone(),
+            });
 
         Ok(())
     }
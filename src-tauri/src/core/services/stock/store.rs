@@ -0,0 +1,171 @@
+//! This module provides the implementation of the `StoreUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::services::trash::*;
+use crate::core::*;
+
+/// Store usecase
+pub struct StoreService {
+    store_repository: Arc<dyn ForStorePersistence>,
+    supplier_repository: Arc<dyn ForSupplierPersistence>,
+    supply_repository: Arc<dyn ForSupplyPersistence>,
+    journal_repository: Arc<dyn ForJournalPersistence>,
+    stocktaking_repository: Arc<dyn ForStocktakingPersistence>,
+    transfer_repository: Arc<dyn ForTransferPersistence>,
+    purchase_order_repository: Arc<dyn ForPurchaseOrderPersistence>,
+    consumption_repository: Arc<dyn ForConsumptionPersistence>,
+    waste_repository: Arc<dyn ForWastePersistence>,
+}
+
+impl StoreService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        store_repository: Arc<dyn ForStorePersistence>,
+        supplier_repository: Arc<dyn ForSupplierPersistence>,
+        supply_repository: Arc<dyn ForSupplyPersistence>,
+        journal_repository: Arc<dyn ForJournalPersistence>,
+        stocktaking_repository: Arc<dyn ForStocktakingPersistence>,
+        transfer_repository: Arc<dyn ForTransferPersistence>,
+        purchase_order_repository: Arc<dyn ForPurchaseOrderPersistence>,
+        consumption_repository: Arc<dyn ForConsumptionPersistence>,
+        waste_repository: Arc<dyn ForWastePersistence>,
+    ) -> Self {
+        Self {
+            store_repository,
+            supplier_repository,
+            supply_repository,
+            journal_repository,
+            stocktaking_repository,
+            transfer_repository,
+            purchase_order_repository,
+            consumption_repository,
+            waste_repository,
+        }
+    }
+
+    /// Whether anything has been recorded in the store.
+    fn has_data(&self, store_id: &StoreId) -> Result<bool> {
+        let store = StoreContext::Store(store_id.clone());
+
+        Ok(!self.supplier_repository.list(&store)?.is_empty()
+            || !self.supply_repository.list(&store)?.is_empty()
+            || !self.journal_repository.list(&store)?.is_empty()
+            || !self.stocktaking_repository.list(&store)?.is_empty()
+            || !self.transfer_repository.list(&store)?.is_empty()
+            || !self.purchase_order_repository.list(&store)?.is_empty()
+            || !self.consumption_repository.list(&store)?.is_empty()
+            || !self.waste_repository.list(&store)?.is_empty())
+    }
+}
+
+/// Converts a store into its DTO.
+fn store_dto(store: &Store) -> StoreDTO {
+    StoreDTO {
+        id: store.id().to_string(),
+        name: store.name().to_string(),
+    }
+}
+
+/// Store usecase implementation
+impl StoreUsecase for StoreService {
+    fn list(&self) -> Result<Vec<StoreDTO>> {
+        let stores = self.store_repository.list()?;
+
+        Ok(stores.iter().map(store_dto).collect())
+    }
+
+    fn get(&self, store_id: impl AsRef<str>) -> Result<Option<StoreDTO>> {
+        let store_id = StoreId::new(store_id.as_ref())?;
+
+        let store = self.store_repository.get(store_id)?;
+
+        Ok(store.as_ref().map(store_dto))
+    }
+
+    fn register(&self, command: RegisterStoreCommand) -> Result<StoreDTO> {
+        let store = Store::restore(
+            self.store_repository.next_id()?,
+            StoreName::new(command.store_name)?,
+        );
+
+        self.store_repository.add(store.clone())?;
+
+        Ok(store_dto(&store))
+    }
+
+    fn update(&self, command: UpdateStoreCommand) -> Result<()> {
+        let store_id = StoreId::new(command.store_id)?;
+
+        let mut store = self
+            .store_repository
+            .get(store_id)?
+            .ok_or(Error::DomainError(format!("store does not exist.")))?;
+
+        store.rename(StoreName::new(command.store_name)?);
+
+        self.store_repository.save(store)?;
+
+        Ok(())
+    }
+
+    fn delete(&self, store_id: impl AsRef<str>) -> Result<()> {
+        let store_id = StoreId::new(store_id.as_ref())?;
+
+        if store_id == StoreId::default() {
+            return Err(Error::DomainError(format!(
+                "the default store cannot be deleted."
+            )));
+        }
+
+        if self.has_data(&store_id)? {
+            return Err(Error::DomainError(format!("store has recorded data.")));
+        }
+
+        self.store_repository.delete(store_id)?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<StoreDTO>>> {
+        let trashed = self.store_repository.list_deleted()?;
+
+        let dtos = trashed
+            .iter()
+            .map(|trashed| store_dto(trashed.item()))
+            .collect();
+
+        Ok(trashed_dtos(&trashed, dtos))
+    }
+
+    fn restore(&self, store_id: impl AsRef<str>) -> Result<()> {
+        let store_id = StoreId::new(store_id.as_ref())?;
+
+        let store = trashed_item(
+            self.store_repository.list_deleted()?,
+            |store| store.id() == &store_id,
+            "store",
+        )?;
+
+        self.store_repository.restore(store.id().clone())?;
+
+        Ok(())
+    }
+
+    fn purge(&self, store_id: impl AsRef<str>) -> Result<()> {
+        let store_id = StoreId::new(store_id.as_ref())?;
+
+        let store = trashed_item(
+            self.store_repository.list_deleted()?,
+            |store| store.id() == &store_id,
+            "store",
+        )?;
+
+        self.store_repository.purge(store.id().clone())?;
+
+        Ok(())
+    }
+}
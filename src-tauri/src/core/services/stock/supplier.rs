@@ -2,22 +2,157 @@
 use std::sync::Arc;
 
 use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::event::*;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::tax::*;
 use crate::core::provided_ports::*;
 use crate::core::required_ports::*;
+use crate::core::services::audit::snapshot;
+use crate::core::services::stock::category::supplies_in_category;
+use crate::core::services::trash::*;
 use crate::core::*;
 
 /// Supplier usecase
 pub struct SupplierService {
     supplier_repository: Arc<dyn ForSupplierPersistence>,
+    supply_repository: Arc<dyn ForSupplyPersistence>,
+    category_repository: Arc<dyn ForCategoryPersistence>,
+    event_publisher: Arc<dyn ForEventPublishing>,
+    operator: Option<OperatorName>,
+    store: StoreContext,
 }
 
 impl SupplierService {
-    pub fn new(supplier_repository: Arc<dyn ForSupplierPersistence>) -> Self {
+    pub fn new(
+        supplier_repository: Arc<dyn ForSupplierPersistence>,
+        supply_repository: Arc<dyn ForSupplyPersistence>,
+        category_repository: Arc<dyn ForCategoryPersistence>,
+        event_publisher: Arc<dyn ForEventPublishing>,
+        operator: Option<OperatorName>,
+        store: StoreContext,
+    ) -> Self {
         Self {
             supplier_repository,
+            supply_repository,
+            category_repository,
+            event_publisher,
+            operator,
+            store,
         }
     }
+
+    /// Returns the change of a supplier to log in the audit log.
+    fn change_of(
+        &self,
+        supplier_id: &SupplierId,
+        operation: AuditOperation,
+        before: Option<&Supplier>,
+        after: Option<&Supplier>,
+    ) -> Result<AuditChange> {
+        AuditChange::new(
+            AuditEntity::Supplier,
+            supplier_id.as_str(),
+            operation,
+            self.operator.clone(),
+            before
+                .map(|supplier| snapshot(&supplier_dto(supplier)))
+                .transpose()?,
+            after
+                .map(|supplier| snapshot(&supplier_dto(supplier)))
+                .transpose()?,
+        )
+    }
+}
+
+/// Converts a supplier into its DTO.
+fn supplier_dto(supplier: &Supplier) -> SupplierDTO {
+    SupplierDTO {
+        id: supplier.id().to_string(),
+        name: supplier.name().to_string(),
+        invoice_registration_number: supplier
+            .invoice_registration_number()
+            .map(|number| number.to_string()),
+        invoice_registered: supplier.is_invoice_registered(),
+        contact: SupplierContactDTO {
+            contact_person: supplier.contact().person().map(|person| person.to_string()),
+            phone_number: supplier
+                .contact()
+                .phone_number()
+                .map(|number| number.to_string()),
+            fax_number: supplier
+                .contact()
+                .fax_number()
+                .map(|number| number.to_string()),
+            email: supplier.contact().email().map(|email| email.to_string()),
+            postal_code: supplier
+                .contact()
+                .postal_code()
+                .map(|code| code.to_string()),
+            address: supplier
+                .contact()
+                .address()
+                .map(|address| address.to_string()),
+        },
+        notes: supplier.notes().map(|notes| notes.to_string()),
+        billing_terms: supplier.billing_terms().map(|terms| BillingTermsDTO {
+            closing_day: terms.closing_day().as_u32(),
+            payment_months_later: terms.payment_months_later(),
+            payment_day: terms.payment_day().as_u32(),
+            payment_method: terms.payment_method().to_string(),
+        }),
+    }
+}
+
+/// Returns the value unless it is empty.
+fn provided(value: &Option<String>) -> Option<&str> {
+    value.as_deref().filter(|value| !value.trim().is_empty())
+}
+
+/// Builds contact details from a DTO, leaving out empty fields.
+fn contact_of(contact: &SupplierContactDTO) -> Result<SupplierContact> {
+    Ok(SupplierContact::default()
+        .with_person(
+            provided(&contact.contact_person)
+                .map(ContactPerson::new)
+                .transpose()?,
+        )
+        .with_phone_number(
+            provided(&contact.phone_number)
+                .map(PhoneNumber::new)
+                .transpose()?,
+        )
+        .with_fax_number(
+            provided(&contact.fax_number)
+                .map(PhoneNumber::new)
+                .transpose()?,
+        )
+        .with_email(
+            provided(&contact.email)
+                .map(EmailAddress::new)
+                .transpose()?,
+        )
+        .with_address(
+            provided(&contact.postal_code)
+                .map(PostalCode::new)
+                .transpose()?,
+            provided(&contact.address).map(Address::new).transpose()?,
+        ))
+}
+
+/// Builds billing terms from a DTO.
+fn billing_terms_of(billing_terms: Option<&BillingTermsDTO>) -> Result<Option<BillingTerms>> {
+    billing_terms
+        .map(|terms| {
+            BillingTerms::new(
+                BillingDay::new(terms.closing_day)?,
+                terms.payment_months_later,
+                BillingDay::new(terms.payment_day)?,
+                PaymentMethod::new(&terms.payment_method)?,
+            )
+        })
+        .transpose()
 }
 
 /// Supplier usecase implementation
@@ -25,7 +160,7 @@ impl SupplierUsecase for SupplierService {
     fn get(&self, supplier_id: impl AsRef<str>) -> Result<Option<SupplierDTO>> {
         let supplier_id = SupplierId::new(supplier_id.as_ref())?;
 
-        let supplier = self.supplier_repository.get(supplier_id)?;
+        let supplier = self.supplier_repository.get(&self.store, supplier_id)?;
 
         if supplier.is_none() {
             return Ok(None);
@@ -33,29 +168,27 @@ impl SupplierUsecase for SupplierService {
 
         let supplier = supplier.unwrap();
 
-        let supplier = SupplierDTO {
-            id: supplier.id().to_string(),
-            name: supplier.name().to_string(),
-        };
+        let supplier = supplier_dto(&supplier);
 
         Ok(Some(supplier))
     }
 
     fn list(&self) -> Result<Vec<SupplierDTO>> {
-        let suppliers = self.supplier_repository.list()?;
+        let suppliers = self.supplier_repository.list(&self.store)?;
 
-        let suppliers: Vec<SupplierDTO> = suppliers
-            .iter()
-            .map(|supplier| SupplierDTO {
-                id: supplier.id().to_string(),
-                name: supplier.name().to_string(),
-            })
-            .collect();
+        let suppliers: Vec<SupplierDTO> = suppliers.iter().map(supplier_dto).collect();
 
         Ok(suppliers)
     }
 
     fn search(&self, query: SearchSuppliersQuery) -> Result<Vec<SupplierDTO>> {
+        let supply_ids = supplies_in_category(
+            &*self.category_repository,
+            &*self.supply_repository,
+            &self.store,
+            query.category_id,
+        )?;
+
         let query = required_ports::FindSupplierQuery {
             supplier_name: query
                 .supplier_name
@@ -77,17 +210,26 @@ impl SupplierUsecase for SupplierService {
                     }
                 })
                 .transpose()?,
+            contact_person: provided(&query.contact_person)
+                .map(ContactPerson::new)
+                .transpose()?,
+            phone_digits: provided(&query.phone_number)
+                .map(|number| number.chars().filter(|c| c.is_ascii_digit()).collect()),
         };
 
-        let suppliers = self.supplier_repository.find(query)?;
+        let mut suppliers = self.supplier_repository.find(&self.store, query)?;
 
-        let suppliers: Vec<SupplierDTO> = suppliers
-            .iter()
-            .map(|supplier| SupplierDTO {
-                id: supplier.id().to_string(),
-                name: supplier.name().to_string(),
-            })
-            .collect();
+        if let Some(supply_ids) = supply_ids {
+            let supplies = self.supply_repository.list(&self.store)?;
+
+            suppliers.retain(|supplier| {
+                supplies.iter().any(|supply| {
+                    supply.is_offered_by(supplier.id()) && supply_ids.contains(supply.id())
+                })
+            });
+        }
+
+        let suppliers: Vec<SupplierDTO> = suppliers.iter().map(supplier_dto).collect();
 
         Ok(suppliers)
     }
@@ -95,14 +237,41 @@ impl SupplierUsecase for SupplierService {
     fn register(&self, command: RegisterSupplierCommand) -> Result<SupplierDTO> {
         let id = self.supplier_repository.next_id()?;
 
-        let supplier = Supplier::restore(id, SupplierName::new(command.supplier_name)?);
+        let mut supplier =
+            Supplier::restore(id, SupplierName::new(command.supplier_name)?, None, false);
 
-        self.supplier_repository.add(supplier.clone())?;
+        supplier.change_invoice_registration(
+            command
+                .invoice_registration_number
+                .map(InvoiceRegistrationNumber::new)
+                .transpose()?,
+            command.invoice_registered,
+        )?;
 
-        let supplier = SupplierDTO {
-            id: supplier.id().to_string(),
-            name: supplier.name().to_string(),
-        };
+        supplier.change_contact(contact_of(&command.contact)?);
+        supplier.change_notes(
+            provided(&command.notes)
+                .map(SupplierNotes::new)
+                .transpose()?,
+        );
+        supplier.change_billing_terms(billing_terms_of(command.billing_terms.as_ref())?);
+
+        let change = self.change_of(
+            supplier.id(),
+            AuditOperation::Register,
+            None,
+            Some(&supplier),
+        )?;
+
+        self.supplier_repository
+            .add(&self.store, supplier.clone(), Some(change))?;
+
+        self.event_publisher
+            .publish(DomainEvent::SupplierRegistered {
+                supplier_id: supplier.id().clone(),
+            });
+
+        let supplier = supplier_dto(&supplier);
 
         Ok(supplier)
     }
@@ -112,12 +281,44 @@ impl SupplierUsecase for SupplierService {
 
         let mut supplier = self
             .supplier_repository
-            .get(supplier_id)?
+            .get(&self.store, supplier_id)?
             .ok_or(Error::DomainError(format!("suppler does not exist.")))?;
 
+        let before = supplier.clone();
+
         supplier.rename(SupplierName::new(command.supplier_name)?);
 
-        self.supplier_repository.save(supplier)?;
+        if let Some(invoice_registered) = command.invoice_registered {
+            supplier.change_invoice_registration(
+                command
+                    .invoice_registration_number
+                    .map(InvoiceRegistrationNumber::new)
+                    .transpose()?,
+                invoice_registered,
+            )?;
+        }
+
+        supplier.change_contact(contact_of(&command.contact)?);
+        supplier.change_notes(
+            provided(&command.notes)
+                .map(SupplierNotes::new)
+                .transpose()?,
+        );
+        supplier.change_billing_terms(billing_terms_of(command.billing_terms.as_ref())?);
+
+        let change = self.change_of(
+            supplier.id(),
+            AuditOperation::Update,
+            Some(&before),
+            Some(&supplier),
+        )?;
+
+        self.supplier_repository
+            .save(&self.store, supplier.clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::SupplierUpdated {
+            supplier_id: supplier.id().clone(),
+        });
 
         Ok(())
     }
@@ -125,7 +326,86 @@ impl SupplierUsecase for SupplierService {
     fn delete(&self, supplier_id: impl AsRef<str>) -> Result<()> {
         let supplier_id = SupplierId::new(supplier_id.as_ref())?;
 
-        self.supplier_repository.delete(supplier_id)?;
+        let supplier = self
+            .supplier_repository
+            .get(&self.store, supplier_id.clone())?;
+
+        let change = supplier
+            .as_ref()
+            .map(|supplier| {
+                self.change_of(&supplier_id, AuditOperation::Delete, Some(supplier), None)
+            })
+            .transpose()?;
+
+        self.supplier_repository
+            .delete(&self.store, supplier_id.clone(), change)?;
+
+        if supplier.is_some() {
+            self.event_publisher
+                .publish(DomainEvent::SupplierDeleted { supplier_id });
+        }
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<SupplierDTO>>> {
+        let trashed = self.supplier_repository.list_deleted(&self.store)?;
+
+        let dtos = trashed
+            .iter()
+            .map(|trashed| supplier_dto(trashed.item()))
+            .collect();
+
+        Ok(trashed_dtos(&trashed, dtos))
+    }
+
+    fn restore(&self, supplier_id: impl AsRef<str>) -> Result<()> {
+        let supplier_id = SupplierId::new(supplier_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let supplier = trashed_item(
+            self.supplier_repository.list_deleted(&store)?,
+            |supplier| supplier.id() == &supplier_id,
+            "supplier",
+        )?;
+
+        let change = self.change_of(
+            supplier.id(),
+            AuditOperation::Restore,
+            None,
+            Some(&supplier),
+        )?;
+
+        self.supplier_repository
+            .restore(&store, supplier.id().clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::SupplierRestored {
+            supplier_id: supplier.id().clone(),
+        });
+
+        Ok(())
+    }
+
+    fn purge(&self, supplier_id: impl AsRef<str>) -> Result<()> {
+        let supplier_id = SupplierId::new(supplier_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let supplier = trashed_item(
+            self.supplier_repository.list_deleted(&store)?,
+            |supplier| supplier.id() == &supplier_id,
+            "supplier",
+        )?;
+
+        let change = self.change_of(supplier.id(), AuditOperation::Purge, Some(&supplier), None)?;
+
+        self.supplier_repository
+            .purge(&store, supplier.id().clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::SupplierPurged {
+            supplier_id: supplier.id().clone(),
+        });
 
         Ok(())
     }
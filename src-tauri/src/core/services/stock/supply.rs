@@ -2,27 +2,183 @@
 use std::sync::Arc;
 
 use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::category::*;
+use crate::core::domain::values::event::*;
+use crate::core::domain::values::reorder::*;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::tax::*;
+use crate::core::domain::values::unit::*;
 use crate::core::provided_ports::*;
 use crate::core::required_ports::*;
+use crate::core::services::audit::snapshot;
+use crate::core::services::stock::unit::unit_named;
+use crate::core::services::trash::*;
 use crate::core::*;
 
 /// Supply usecase
 pub struct SupplyService {
     supply_repository: Arc<dyn ForSupplyPersistence>,
     supplier_repository: Arc<dyn ForSupplierPersistence>,
+    unit_repository: Arc<dyn ForUnitPersistence>,
+    category_repository: Arc<dyn ForCategoryPersistence>,
+    event_publisher: Arc<dyn ForEventPublishing>,
+    operator: Option<OperatorName>,
+    store: StoreContext,
 }
 
 impl SupplyService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         supply_repository: Arc<dyn ForSupplyPersistence>,
         supplier_repository: Arc<dyn ForSupplierPersistence>,
+        unit_repository: Arc<dyn ForUnitPersistence>,
+        category_repository: Arc<dyn ForCategoryPersistence>,
+        event_publisher: Arc<dyn ForEventPublishing>,
+        operator: Option<OperatorName>,
+        store: StoreContext,
     ) -> Self {
         Self {
             supply_repository,
             supplier_repository,
+            unit_repository,
+            category_repository,
+            event_publisher,
+            operator,
+            store,
         }
     }
+
+    /// Returns the change of a supply to log in the audit log.
+    fn change_of(
+        &self,
+        supply_id: &SupplyId,
+        operation: AuditOperation,
+        before: Option<&Supply>,
+        after: Option<&Supply>,
+    ) -> Result<AuditChange> {
+        AuditChange::new(
+            AuditEntity::Supply,
+            supply_id.as_str(),
+            operation,
+            self.operator.clone(),
+            before
+                .map(|supply| snapshot(&supply_dto(supply)))
+                .transpose()?,
+            after
+                .map(|supply| snapshot(&supply_dto(supply)))
+                .transpose()?,
+        )
+    }
+
+    /// Returns the supplier of `supplier_id`, failing if it is not a supplier of the store.
+    fn supplier_of(&self, supplier_id: String) -> Result<Supplier> {
+        let supplier_id = SupplierId::new(supplier_id)?;
+
+        if !self
+            .supplier_repository
+            .has(&self.store, std::slice::from_ref(&supplier_id))?
+        {
+            return Err(Error::DomainError(format!("supplier does not exist.")));
+        }
+
+        self.supplier_repository
+            .get(&self.store, supplier_id)?
+            .ok_or(Error::DomainError(format!("supplier does not exist.")))
+    }
+
+    /// Returns the category of `category_id`, none for an empty id, failing if it does not exist.
+    fn category_of(&self, category_id: String) -> Result<Option<CategoryId>> {
+        if category_id.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let category_id = CategoryId::new(category_id)?;
+
+        if self.category_repository.get(category_id.clone())?.is_none() {
+            return Err(Error::DomainError(format!("category does not exist.")));
+        }
+
+        Ok(Some(category_id))
+    }
+
+    /// Builds supply units from the purchase unit, the stock unit and conversion DTOs.
+    fn units_of(
+        &self,
+        purchase_unit_id: UnitId,
+        stock_unit_id: UnitId,
+        conversions: &[UnitConversionDTO],
+    ) -> Result<SupplyUnits> {
+        let conversions = conversions
+            .iter()
+            .map(|conversion| {
+                Ok(UnitConversion::new(
+                    UnitId::new(&conversion.unit_id)?,
+                    ConversionFactor::new(conversion.factor)?,
+                ))
+            })
+            .collect::<Result<Vec<UnitConversion>>>()?;
+
+        for unit_id in conversions
+            .iter()
+            .map(|conversion| conversion.unit_id())
+            .chain([&stock_unit_id])
+        {
+            if self.unit_repository.get(unit_id.clone())?.is_none() {
+                return Err(Error::DomainError(format!("unit does not exist.")));
+            }
+        }
+
+        SupplyUnits::new(purchase_unit_id, stock_unit_id, conversions)
+    }
+}
+
+/// Converts a supply into its DTO.
+pub fn supply_dto(supply: &Supply) -> SupplyDTO {
+    SupplyDTO {
+        id: supply.id().to_string(),
+        name: supply.name().to_string(),
+        unit_name: supply.unit_name().to_string(),
+        supplier_id: supply.supplier_id().to_string(),
+        offers: supply
+            .offers()
+            .iter()
+            .map(|offer| SupplyOfferDTO {
+                supplier_id: offer.supplier_id().to_string(),
+                item_code: offer.item_code().map(|code| code.to_string()),
+                unit_price: offer.unit_price().map(|price| price.as_f64()),
+                preferred: offer.is_preferred(),
+            })
+            .collect(),
+        tax_category: supply.tax_category().to_string(),
+        purchase_unit_id: supply.units().purchase_unit_id().to_string(),
+        stock_unit_id: supply.units().stock_unit_id().to_string(),
+        conversions: supply
+            .units()
+            .conversions()
+            .iter()
+            .map(|conversion| UnitConversionDTO {
+                unit_id: conversion.unit_id().to_string(),
+                factor: conversion.factor().as_f64(),
+            })
+            .collect(),
+        category_id: supply
+            .category_id()
+            .map(|category_id| category_id.to_string()),
+        par_level: supply
+            .reorder_levels()
+            .map(|levels| levels.par_level().as_f64()),
+        reorder_point: supply
+            .reorder_levels()
+            .map(|levels| levels.reorder_point().as_f64()),
+        location_id: supply
+            .placement()
+            .map(|placement| placement.location_id().to_string()),
+        location_sort_order: supply
+            .placement()
+            .map(|placement| placement.sort_order().as_u32()),
+    }
 }
 
 /// Supply usecase implementation
@@ -30,7 +186,7 @@ impl SupplyUsecase for SupplyService {
     fn get(&self, supply_id: impl AsRef<str>) -> Result<Option<SupplyDTO>> {
         let supply_id = SupplyId::new(supply_id.as_ref())?;
 
-        let supply = self.supply_repository.get(supply_id)?;
+        let supply = self.supply_repository.get(&self.store, supply_id)?;
 
         if supply.is_none() {
             return Ok(None);
@@ -38,12 +194,7 @@ impl SupplyUsecase for SupplyService {
 
         let supply = supply.unwrap();
 
-        Ok(Some(SupplyDTO {
-            id: supply.id().to_string(),
-            name: supply.name().to_string(),
-            unit_name: supply.unit_name().to_string(),
-            supplier_id: supply.supplier_id().to_string(),
-        }))
+        Ok(Some(supply_dto(&supply)))
     }
 
     fn get_of_supplier(&self, supplier_id: String) -> Result<Vec<SupplyDTO>> {
@@ -51,31 +202,15 @@ impl SupplyUsecase for SupplyService {
 
         let supplies = self.supply_repository.list_of_supplier(supplier_id)?;
 
-        let supplies: Vec<SupplyDTO> = supplies
-            .iter()
-            .map(|supply| SupplyDTO {
-                id: supply.id().to_string(),
-                name: supply.name().to_string(),
-                unit_name: supply.unit_name().to_string(),
-                supplier_id: supply.supplier_id().to_string(),
-            })
-            .collect();
+        let supplies: Vec<SupplyDTO> = supplies.iter().map(supply_dto).collect();
 
         Ok(supplies)
     }
 
     fn list(&self) -> Result<Vec<SupplyDTO>> {
-        let supplies = self.supply_repository.list()?;
+        let supplies = self.supply_repository.list(&self.store)?;
 
-        let supplies: Vec<SupplyDTO> = supplies
-            .iter()
-            .map(|supply| SupplyDTO {
-                id: supply.id().to_string(),
-                name: supply.name().to_string(),
-                unit_name: supply.unit_name().to_string(),
-                supplier_id: supply.supplier_id().to_string(),
-            })
-            .collect();
+        let supplies: Vec<SupplyDTO> = supplies.iter().map(supply_dto).collect();
 
         Ok(supplies)
     }
@@ -83,28 +218,50 @@ impl SupplyUsecase for SupplyService {
     fn register(&self, command: CreateSupplyCommand) -> Result<SupplyDTO> {
         let id = self.supply_repository.next_id()?;
 
-        let supplier_id = SupplierId::new(command.supplier_id)?;
+        let supplier = self.supplier_of(command.supplier_id)?;
 
-        let supplier = self
-            .supplier_repository
-            .get(supplier_id)?
-            .ok_or(Error::DomainError(format!("supplier does not exist.")))?;
+        let purchase_unit = unit_named(&*self.unit_repository, UnitName::new(command.unit_name)?)?;
+
+        let stock_unit_id = match command.stock_unit_id {
+            Some(stock_unit_id) => UnitId::new(stock_unit_id)?,
+            None => purchase_unit.id().clone(),
+        };
+
+        let units = self.units_of(
+            purchase_unit.id().clone(),
+            stock_unit_id,
+            &command.conversions,
+        )?;
+
+        let category_id = match command.category_id {
+            Some(category_id) => self.category_of(category_id)?,
+            None => None,
+        };
 
         let supply = Supply::new(
             id,
             SupplyName::new(command.supply_name)?,
-            UnitName::new(command.unit_name)?,
+            purchase_unit.name().clone(),
             supplier.id().clone(),
+            command
+                .tax_category
+                .map(TaxCategory::new)
+                .transpose()?
+                .unwrap_or_default(),
+            units,
+            category_id,
         );
 
-        self.supply_repository.add(supply.clone())?;
+        let change = self.change_of(supply.id(), AuditOperation::Register, None, Some(&supply))?;
+
+        self.supply_repository
+            .add(&self.store, supply.clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::SupplyRegistered {
+            supply_id: supply.id().clone(),
+        });
 
-        Ok(SupplyDTO {
-            id: supply.id().to_string(),
-            name: supply.name().to_string(),
-            unit_name: supply.unit_name().to_string(),
-            supplier_id: supply.supplier_id().to_string(),
-        })
+        Ok(supply_dto(&supply))
     }
 
     fn update(&self, commad: UpdateSupplyCommand) -> Result<()> {
@@ -112,21 +269,159 @@ impl SupplyUsecase for SupplyService {
 
         let mut supply = self
             .supply_repository
-            .get(supply_id)?
+            .get(&self.store, supply_id)?
             .ok_or(Error::DomainError(format!("supply does not exist.")))?;
 
-        let supplier_id = SupplierId::new(commad.supplier_id)?;
+        let before = supply.clone();
 
-        let supplier = self
-            .supplier_repository
-            .get(supplier_id)?
-            .ok_or(Error::DomainError(format!("supplier does not exist.")))?;
+        let supplier = self.supplier_of(commad.supplier_id)?;
+
+        let purchase_unit = unit_named(&*self.unit_repository, UnitName::new(commad.unit_name)?)?;
+
+        let stock_unit_id = match commad.stock_unit_id {
+            Some(stock_unit_id) => UnitId::new(stock_unit_id)?,
+            None => supply.units().stock_unit_id().clone(),
+        };
+
+        let conversions = match commad.conversions {
+            Some(conversions) => conversions,
+            None => supply
+                .units()
+                .conversions()
+                .iter()
+                .map(|conversion| UnitConversionDTO {
+                    unit_id: conversion.unit_id().to_string(),
+                    factor: conversion.factor().as_f64(),
+                })
+                .collect(),
+        };
+
+        let units = self.units_of(purchase_unit.id().clone(), stock_unit_id, &conversions)?;
 
         supply.rename(SupplyName::new(commad.supply_name)?);
-        supply.rename_unit(UnitName::new(commad.unit_name)?);
+        supply.rename_unit(purchase_unit.name().clone());
         supply.change_supplier(supplier.id().clone());
+        supply.change_units(units);
+
+        if let Some(tax_category) = commad.tax_category {
+            supply.change_tax_category(TaxCategory::new(tax_category)?);
+        }
+
+        if let Some(category_id) = commad.category_id {
+            supply.change_category(self.category_of(category_id)?);
+        }
+
+        let change = self.change_of(
+            supply.id(),
+            AuditOperation::Update,
+            Some(&before),
+            Some(&supply),
+        )?;
+
+        self.supply_repository
+            .save(&self.store, supply.clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::SupplyUpdated {
+            supply_id: supply.id().clone(),
+        });
 
-        self.supply_repository.save(supply)?;
+        if before.name() != supply.name() {
+            self.event_publisher.publish(DomainEvent::SupplyRenamed {
+                supply_id: supply.id().clone(),
+                previous_name: before.name().clone(),
+                name: supply.name().clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn set_reorder_levels(&self, command: SetReorderLevelsCommand) -> Result<()> {
+        let supply_id = SupplyId::new(command.supply_id)?;
+
+        let mut supply = self
+            .supply_repository
+            .get(&self.store, supply_id)?
+            .ok_or(Error::DomainError(format!("supply does not exist.")))?;
+
+        let before = supply.clone();
+
+        let reorder_levels = match (command.par_level, command.reorder_point) {
+            (Some(par_level), reorder_point) => Some(ReorderLevels::new(
+                par_level,
+                reorder_point.unwrap_or(par_level),
+            )?),
+            (None, Some(_)) => {
+                return Err(Error::DomainError(format!(
+                    "reorder point requires a par level."
+                )))
+            }
+            (None, None) => None,
+        };
+
+        supply.change_reorder_levels(reorder_levels);
+
+        let change = self.change_of(
+            supply.id(),
+            AuditOperation::Update,
+            Some(&before),
+            Some(&supply),
+        )?;
+
+        self.supply_repository
+            .save(&self.store, supply.clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::SupplyUpdated {
+            supply_id: supply.id().clone(),
+        });
+
+        Ok(())
+    }
+
+    fn set_offers(&self, command: SetSupplyOffersCommand) -> Result<()> {
+        let supply_id = SupplyId::new(command.supply_id)?;
+
+        let mut supply = self
+            .supply_repository
+            .get(&self.store, supply_id)?
+            .ok_or(Error::DomainError(format!("supply does not exist.")))?;
+
+        let before = supply.clone();
+
+        let offers = command
+            .offers
+            .into_iter()
+            .map(|offer| {
+                let supplier = self.supplier_of(offer.supplier_id)?;
+
+                Ok(SupplyOffer::new(supplier.id().clone())
+                    .with_item_code(
+                        offer
+                            .item_code
+                            .filter(|code| !code.trim().is_empty())
+                            .map(ItemCode::new)
+                            .transpose()?,
+                    )
+                    .with_unit_price(offer.unit_price.map(PurchaseUnitPrice::new).transpose()?)
+                    .with_preferred(offer.preferred))
+            })
+            .collect::<Result<Vec<SupplyOffer>>>()?;
+
+        supply.change_offers(offers)?;
+
+        let change = self.change_of(
+            supply.id(),
+            AuditOperation::Update,
+            Some(&before),
+            Some(&supply),
+        )?;
+
+        self.supply_repository
+            .save(&self.store, supply.clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::SupplyUpdated {
+            supply_id: supply.id().clone(),
+        });
 
         Ok(())
     }
@@ -134,7 +429,115 @@ impl SupplyUsecase for SupplyService {
     fn delete(&self, supply_id: impl AsRef<str>) -> Result<()> {
         let supply_id = SupplyId::new(supply_id.as_ref())?;
 
-        self.supply_repository.delete(supply_id)?;
+        let supply = self.supply_repository.get(&self.store, supply_id.clone())?;
+
+        let change = supply
+            .as_ref()
+            .map(|supply| self.change_of(&supply_id, AuditOperation::Delete, Some(supply), None))
+            .transpose()?;
+
+        self.supply_repository
+            .delete(&self.store, supply_id.clone(), change)?;
+
+        if supply.is_some() {
+            self.event_publisher
+                .publish(DomainEvent::SupplyDeleted { supply_id });
+        }
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<SupplyDTO>>> {
+        let trashed = self.supply_repository.list_deleted(&self.store)?;
+
+        let dtos = trashed
+            .iter()
+            .map(|trashed| supply_dto(trashed.item()))
+            .collect();
+
+        Ok(trashed_dtos(&trashed, dtos))
+    }
+
+    fn restore(&self, supply_id: impl AsRef<str>) -> Result<()> {
+        let supply_id = SupplyId::new(supply_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let supply = trashed_item(
+            self.supply_repository.list_deleted(&store)?,
+            |supply| supply.id() == &supply_id,
+            "supply",
+        )?;
+
+        // the suppliers, units and category of the supply may have been deleted since
+        let supplier_ids: Vec<SupplierId> = supply
+            .offers()
+            .iter()
+            .map(|offer| offer.supplier_id().clone())
+            .collect();
+
+        if !self.supplier_repository.has(&store, &supplier_ids)? {
+            return Err(Error::DomainError(format!(
+                "supplier of the supply does not exist."
+            )));
+        }
+
+        for unit_id in supply
+            .units()
+            .conversions()
+            .iter()
+            .map(|conversion| conversion.unit_id())
+            .chain([
+                supply.units().purchase_unit_id(),
+                supply.units().stock_unit_id(),
+            ])
+        {
+            if self.unit_repository.get(unit_id.clone())?.is_none() {
+                return Err(Error::DomainError(format!(
+                    "unit of the supply does not exist."
+                )));
+            }
+        }
+
+        if let Some(category_id) = supply.category_id() {
+            if self.category_repository.get(category_id.clone())?.is_none() {
+                return Err(Error::DomainError(format!(
+                    "category of the supply does not exist."
+                )));
+            }
+        }
+
+        let change = self.change_of(supply.id(), AuditOperation::Restore, None, Some(&supply))?;
+
+        self.supply_repository
+            .restore(&store, supply.id().clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::SupplyRestored {
+            supply_id: supply.id().clone(),
+        });
+
+        Ok(())
+    }
+
+    fn purge(&self, supply_id: impl AsRef<str>) -> Result<()> {
+        let supply_id = SupplyId::new(supply_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let supply = trashed_item(
+            self.supply_repository.list_deleted(&store)?,
+            |supply| supply.id() == &supply_id,
+            "supply",
+        )?;
+
+        let change = self.change_of(supply.id(), AuditOperation::Purge, Some(&supply), None)?;
+
+        self.supply_repository
+            .purge(&store, supply.id().clone(), Some(change))?;
+
+        self.event_publisher.publish(DomainEvent::SupplyPurged {
+            supply_id: supply.id().clone(),
+        });
 
         Ok(())
     }
@@ -0,0 +1,320 @@
+//! This module provides the implementation of the `TransferUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::transfer::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::services::stock::unit::stock_quantity_of;
+use crate::core::services::trash::*;
+use crate::core::*;
+
+/// Transfer usecase
+///
+/// Lines are moved in the stock unit of the supply of the source store.
+pub struct TransferService {
+    transfer_repository: Arc<dyn ForTransferPersistence>,
+    store_repository: Arc<dyn ForStorePersistence>,
+    supply_respository: Arc<dyn ForSupplyPersistence>,
+    unit_repository: Arc<dyn ForUnitPersistence>,
+    rounding_policy: RoundingPolicy,
+    store: StoreContext,
+}
+
+impl TransferService {
+    pub fn new(
+        transfer_repository: Arc<dyn ForTransferPersistence>,
+        store_repository: Arc<dyn ForStorePersistence>,
+        supply_respository: Arc<dyn ForSupplyPersistence>,
+        unit_repository: Arc<dyn ForUnitPersistence>,
+        rounding_policy: RoundingPolicy,
+        store: StoreContext,
+    ) -> Self {
+        Self {
+            transfer_repository,
+            store_repository,
+            supply_respository,
+            unit_repository,
+            rounding_policy,
+            store,
+        }
+    }
+
+    /// Returns the transfer from or to the store, failing if it does not exist.
+    fn transfer_of(&self, store: &StoreContext, transfer_id: impl AsRef<str>) -> Result<Transfer> {
+        let transfer_id = TransferId::new(transfer_id.as_ref())?;
+
+        self.transfer_repository
+            .get(store, transfer_id)?
+            .ok_or(Error::DomainError(format!("transfer does not exist.")))
+    }
+
+    /// Returns the store, failing if it does not exist.
+    fn store_of(&self, store_id: impl AsRef<str>) -> Result<Store> {
+        self.store_repository
+            .get(StoreId::new(store_id)?)?
+            .ok_or(Error::DomainError(format!("store does not exist.")))
+    }
+
+    /// Builds transfer lines, names and units taken from the supplies of the source store.
+    ///
+    /// Supplies of the destination store are found by name unless given.
+    fn lines_of(
+        &self,
+        source: &Store,
+        destination: &Store,
+        lines: &[TransferLineDTO],
+    ) -> Result<Vec<TransferLine>> {
+        let sources = self
+            .supply_respository
+            .list(&StoreContext::Store(source.id().clone()))?;
+        let destinations = self
+            .supply_respository
+            .list(&StoreContext::Store(destination.id().clone()))?;
+        let units = self.unit_repository.list()?;
+
+        lines
+            .iter()
+            .map(|line| {
+                let supply_id = SupplyId::new(&line.supply_id)?;
+
+                let supply = sources
+                    .iter()
+                    .find(|supply| supply.id() == &supply_id)
+                    .ok_or(Error::DomainError(format!(
+                        "supply does not exist in {}.",
+                        source.name().as_str()
+                    )))?;
+
+                let destination_supply = match line.destination_supply_id.trim() {
+                    "" => destinations
+                        .iter()
+                        .find(|other| other.name() == supply.name()),
+                    destination_supply_id => {
+                        let destination_supply_id = SupplyId::new(destination_supply_id)?;
+
+                        destinations
+                            .iter()
+                            .find(|other| other.id() == &destination_supply_id)
+                    }
+                }
+                .ok_or(Error::DomainError(format!(
+                    "{} does not exist in {}.",
+                    supply.name().as_str(),
+                    destination.name().as_str()
+                )))?;
+
+                let unit_name = units
+                    .iter()
+                    .find(|unit| unit.id() == supply.units().stock_unit_id())
+                    .map(|unit| unit.name().clone())
+                    .ok_or(Error::DomainError(format!(
+                        "stock unit of {} is not registered.",
+                        supply.name().as_str()
+                    )))?;
+
+                // the destination must be able to count the supply in the same unit
+                stock_quantity_of(
+                    &destinations,
+                    &units,
+                    destination_supply.id(),
+                    unit_name.clone(),
+                    Quantity::ZERO,
+                )?;
+
+                let quantity = TransferQuantity::new(line.quantity)?;
+                let unit_price = TransferUnitPrice::new(line.unit_price)?;
+                let total_price = TotalPrice::from_money(
+                    self.rounding_policy
+                        .line_total(unit_price.as_money(), quantity.as_quantity())?,
+                )?;
+
+                Ok(TransferLine::new(
+                    supply.id().clone(),
+                    destination_supply.id().clone(),
+                    supply.name().clone(),
+                    unit_name,
+                    quantity,
+                    unit_price,
+                    total_price,
+                ))
+            })
+            .collect()
+    }
+
+    /// Converts transfers into their DTOs.
+    fn transfer_dtos(&self, transfers: &[Transfer]) -> Result<Vec<TransferDTO>> {
+        let stores = self.store_repository.list()?;
+
+        transfers
+            .iter()
+            .map(|transfer| transfer_dto(transfer, &stores))
+            .collect()
+    }
+}
+
+/// Converts a transfer into its DTO.
+fn transfer_dto(transfer: &Transfer, stores: &[Store]) -> Result<TransferDTO> {
+    let store_name = |store_id: &StoreId| {
+        stores
+            .iter()
+            .find(|store| store.id() == store_id)
+            .map(|store| store.name().to_string())
+            .unwrap_or_default()
+    };
+
+    Ok(TransferDTO {
+        id: transfer.id().to_string(),
+        transferred_date: transfer.transferred_at().as_i64(),
+        source_store_id: transfer.source_store_id().to_string(),
+        source_store_name: store_name(transfer.source_store_id()),
+        destination_store_id: transfer.destination_store_id().to_string(),
+        destination_store_name: store_name(transfer.destination_store_id()),
+        total_price: transfer.total_price()?.as_f64(),
+        lines: transfer
+            .lines()
+            .iter()
+            .map(|line| TransferLineDTO {
+                supply_id: line.supply_id().to_string(),
+                destination_supply_id: line.destination_supply_id().to_string(),
+                supply_name: line.supply_name().to_string(),
+                unit_name: line.unit_name().to_string(),
+                quantity: line.quantity().as_f64(),
+                unit_price: line.unit_price().as_f64(),
+                total_price: line.total_price().as_f64(),
+            })
+            .collect(),
+    })
+}
+
+/// Transfer usecase implementation
+impl TransferUsecase for TransferService {
+    fn list(&self) -> Result<Vec<TransferDTO>> {
+        let transfers = self.transfer_repository.list(&self.store)?;
+
+        self.transfer_dtos(&transfers)
+    }
+
+    fn get(&self, transfer_id: impl AsRef<str>) -> Result<Option<TransferDTO>> {
+        let transfer_id = TransferId::new(transfer_id.as_ref())?;
+
+        let transfer = self.transfer_repository.get(&self.store, transfer_id)?;
+
+        match transfer {
+            Some(transfer) => Ok(Some(transfer_dto(
+                &transfer,
+                &self.store_repository.list()?,
+            )?)),
+            None => Ok(None),
+        }
+    }
+
+    fn search(&self, query: SearchTransfersQuery) -> Result<Vec<TransferDTO>> {
+        let query = FindTransfersQuery {
+            period_start: query.period_start.map(TransferredDateTime::new),
+            period_end: query.period_end.map(TransferredDateTime::new),
+        };
+
+        let transfers = self.transfer_repository.find(&self.store, query)?;
+
+        self.transfer_dtos(&transfers)
+    }
+
+    fn record(&self, command: RecordTransferCommand) -> Result<TransferDTO> {
+        let source = self.store_of(&command.source_store_id)?;
+        let destination = self.store_of(&command.destination_store_id)?;
+
+        let transfer = Transfer::new(
+            self.transfer_repository.next_id()?,
+            TransferredDateTime::new(command.transferred_date),
+            source.id().clone(),
+            destination.id().clone(),
+            self.lines_of(&source, &destination, &command.lines)?,
+        )?;
+
+        self.transfer_repository.add(transfer.clone())?;
+
+        transfer_dto(&transfer, &[source, destination])
+    }
+
+    fn update(&self, command: UpdateTransferCommand) -> Result<()> {
+        let store = self.store.owner()?;
+
+        let mut transfer = self.transfer_of(&store, &command.transfer_id)?;
+
+        let source = self.store_of(&command.source_store_id)?;
+        let destination = self.store_of(&command.destination_store_id)?;
+
+        transfer.revise(
+            TransferredDateTime::new(command.transferred_date),
+            source.id().clone(),
+            destination.id().clone(),
+            self.lines_of(&source, &destination, &command.lines)?,
+        )?;
+
+        self.transfer_repository.save(&store, transfer)?;
+
+        Ok(())
+    }
+
+    fn delete(&self, transfer_id: impl AsRef<str>) -> Result<()> {
+        let store = self.store.owner()?;
+
+        let transfer = self.transfer_of(&store, transfer_id)?;
+
+        self.transfer_repository
+            .delete(&store, transfer.id().clone())?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<TransferDTO>>> {
+        let trashed = self.transfer_repository.list_deleted(&self.store)?;
+
+        let items: Vec<_> = trashed
+            .iter()
+            .map(|trashed| trashed.item().clone())
+            .collect();
+
+        let dtos = self.transfer_dtos(&items)?;
+
+        Ok(trashed_dtos(&trashed, dtos))
+    }
+
+    fn restore(&self, transfer_id: impl AsRef<str>) -> Result<()> {
+        let transfer_id = TransferId::new(transfer_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let transfer = trashed_item(
+            self.transfer_repository.list_deleted(&store)?,
+            |transfer| transfer.id() == &transfer_id,
+            "transfer",
+        )?;
+
+        self.transfer_repository
+            .restore(&store, transfer.id().clone())?;
+
+        Ok(())
+    }
+
+    fn purge(&self, transfer_id: impl AsRef<str>) -> Result<()> {
+        let transfer_id = TransferId::new(transfer_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let transfer = trashed_item(
+            self.transfer_repository.list_deleted(&store)?,
+            |transfer| transfer.id() == &transfer_id,
+            "transfer",
+        )?;
+
+        self.transfer_repository
+            .purge(&store, transfer.id().clone())?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+//! This module provides the implementation of the `UnitUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::unit::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::services::trash::*;
+use crate::core::*;
+
+/// Unit usecase
+pub struct UnitService {
+    unit_repository: Arc<dyn ForUnitPersistence>,
+    supply_repository: Arc<dyn ForSupplyPersistence>,
+}
+
+impl UnitService {
+    pub fn new(
+        unit_repository: Arc<dyn ForUnitPersistence>,
+        supply_repository: Arc<dyn ForSupplyPersistence>,
+    ) -> Self {
+        Self {
+            unit_repository,
+            supply_repository,
+        }
+    }
+}
+
+/// Unit usecase implementation
+impl UnitUsecase for UnitService {
+    fn list(&self) -> Result<Vec<UnitDTO>> {
+        let units = self.unit_repository.list()?;
+
+        let units: Vec<UnitDTO> = units
+            .iter()
+            .map(|unit| UnitDTO {
+                id: unit.id().to_string(),
+                name: unit.name().to_string(),
+            })
+            .collect();
+
+        Ok(units)
+    }
+
+    fn get(&self, unit_id: impl AsRef<str>) -> Result<Option<UnitDTO>> {
+        let unit_id = UnitId::new(unit_id.as_ref())?;
+
+        let unit = self.unit_repository.get(unit_id)?;
+
+        Ok(unit.map(|unit| UnitDTO {
+            id: unit.id().to_string(),
+            name: unit.name().to_string(),
+        }))
+    }
+
+    fn register(&self, command: RegisterUnitCommand) -> Result<UnitDTO> {
+        let unit = register_unit(&*self.unit_repository, UnitName::new(command.unit_name)?)?;
+
+        Ok(UnitDTO {
+            id: unit.id().to_string(),
+            name: unit.name().to_string(),
+        })
+    }
+
+    fn update(&self, command: UpdateUnitCommand) -> Result<()> {
+        let unit_id = UnitId::new(command.unit_id)?;
+
+        let mut unit = self
+            .unit_repository
+            .get(unit_id)?
+            .ok_or(Error::DomainError(format!("unit does not exist.")))?;
+
+        let name = UnitName::new(command.unit_name)?;
+
+        if let Some(other) = self.unit_repository.get_by_name(name.clone())? {
+            if other.id() != unit.id() {
+                return Err(Error::DomainError(format!(
+                    "unit {} already exists.",
+                    name.as_str()
+                )));
+            }
+        }
+
+        unit.rename(name);
+
+        self.unit_repository.save(unit)?;
+
+        Ok(())
+    }
+
+    fn delete(&self, unit_id: impl AsRef<str>) -> Result<()> {
+        let unit_id = UnitId::new(unit_id.as_ref())?;
+
+        // units are shared by the stores
+        let in_use = self
+            .supply_repository
+            .list(&StoreContext::Consolidated)?
+            .iter()
+            .any(|supply| {
+                supply.units().stock_unit_id() == &unit_id
+                    || supply.units().factor_of(&unit_id).is_some()
+            });
+
+        if in_use {
+            return Err(Error::DomainError(format!("unit is used by supplies.")));
+        }
+
+        self.unit_repository.delete(unit_id)?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<UnitDTO>>> {
+        let trashed = self.unit_repository.list_deleted()?;
+
+        let dtos = trashed
+            .iter()
+            .map(|trashed| UnitDTO {
+                id: trashed.item().id().to_string(),
+                name: trashed.item().name().to_string(),
+            })
+            .collect();
+
+        Ok(trashed_dtos(&trashed, dtos))
+    }
+
+    fn restore(&self, unit_id: impl AsRef<str>) -> Result<()> {
+        let unit_id = UnitId::new(unit_id.as_ref())?;
+
+        let unit = trashed_item(
+            self.unit_repository.list_deleted()?,
+            |unit| unit.id() == &unit_id,
+            "unit",
+        )?;
+
+        self.unit_repository.restore(unit.id().clone())?;
+
+        Ok(())
+    }
+
+    fn purge(&self, unit_id: impl AsRef<str>) -> Result<()> {
+        let unit_id = UnitId::new(unit_id.as_ref())?;
+
+        let unit = trashed_item(
+            self.unit_repository.list_deleted()?,
+            |unit| unit.id() == &unit_id,
+            "unit",
+        )?;
+
+        self.unit_repository.purge(unit.id().clone())?;
+
+        Ok(())
+    }
+}
+
+/// Registers a unit named `name`, failing if it already exists.
+fn register_unit(unit_repository: &dyn ForUnitPersistence, name: UnitName) -> Result<Unit> {
+    if unit_repository.get_by_name(name.clone())?.is_some() {
+        return Err(Error::DomainError(format!(
+            "unit {} already exists.",
+            name.as_str()
+        )));
+    }
+
+    let unit = Unit::restore(unit_repository.next_id()?, name);
+
+    unit_repository.add(unit.clone())?;
+
+    Ok(unit)
+}
+
+/// Returns the unit named `name`, registering it if it does not exist.
+pub fn unit_named(unit_repository: &dyn ForUnitPersistence, name: UnitName) -> Result<Unit> {
+    match unit_repository.get_by_name(name.clone())? {
+        Some(unit) => Ok(unit),
+        None => register_unit(unit_repository, name),
+    }
+}
+
+/// Converts a quantity of a supply in `unit_name` into the stock unit of the supply.
+///
+/// Quantities of deleted supplies are kept in their own unit.
+pub fn stock_quantity_of(
+    supplies: &[Supply],
+    units: &[Unit],
+    supply_id: &SupplyId,
+    unit_name: UnitName,
+    quantity: Quantity,
+) -> Result<(UnitName, Quantity)> {
+    let supply = match supplies.iter().find(|supply| supply.id() == supply_id) {
+        Some(supply) => supply,
+        None => return Ok((unit_name, quantity)),
+    };
+
+    let unit = units
+        .iter()
+        .find(|unit| unit.name() == &unit_name)
+        .ok_or(Error::DomainError(format!(
+            "unit {} is not registered.",
+            unit_name.as_str()
+        )))?;
+
+    let stock_unit = units
+        .iter()
+        .find(|unit| unit.id() == supply.units().stock_unit_id())
+        .ok_or(Error::DomainError(format!(
+            "stock unit of {} is not registered.",
+            supply.name().as_str()
+        )))?;
+
+    if supply.units().factor_of(unit.id()).is_none() {
+        return Err(Error::DomainError(format!(
+            "{} has no conversion from {} into {}.",
+            supply.name().as_str(),
+            unit.name().as_str(),
+            stock_unit.name().as_str()
+        )));
+    }
+
+    let quantity = supply.units().to_stock_quantity(unit.id(), quantity)?;
+
+    Ok((stock_unit.name().clone(), quantity))
+}
+
+/// Sums quantities of lines per supply, converted into the stock unit of the supply.
+///
+/// Each line is `(supply id, supply name, unit name, quantity)`.
+/// Lines of deleted supplies are summed in their own unit.
+pub fn aggregate_stock_quantities(
+    supply_repository: &dyn ForSupplyPersistence,
+    unit_repository: &dyn ForUnitPersistence,
+    lines: Vec<(SupplyId, SupplyName, UnitName, Quantity)>,
+) -> Result<Vec<StockQuantityDTO>> {
+    // lines are of the supplies they name, whichever store they belong to
+    let supplies = supply_repository.list(&StoreContext::Consolidated)?;
+    let units = unit_repository.list()?;
+
+    let mut totals: Vec<(SupplyId, SupplyName, UnitName, Quantity)> = Vec::new();
+
+    for (supply_id, supply_name, unit_name, quantity) in lines {
+        let (unit_name, quantity) =
+            stock_quantity_of(&supplies, &units, &supply_id, unit_name, quantity)?;
+
+        match totals
+            .iter_mut()
+            .find(|total| total.0 == supply_id && total.2 == unit_name)
+        {
+            Some(total) => total.3 = total.3.checked_add(quantity)?,
+            None => totals.push((supply_id, supply_name, unit_name, quantity)),
+        }
+    }
+
+    let totals = totals
+        .into_iter()
+        .map(
+            |(supply_id, supply_name, unit_name, quantity)| StockQuantityDTO {
+                supply_id: supply_id.to_string(),
+                supply_name: supply_name.to_string(),
+                unit_name: unit_name.to_string(),
+                quantity: quantity.as_f64(),
+            },
+        )
+        .collect();
+
+    Ok(totals)
+}
@@ -0,0 +1,378 @@
+//! This module provides the implementation of the `WasteUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::waste::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::services::stock::unit::stock_quantity_of;
+use crate::core::services::trash::*;
+use crate::core::*;
+
+/// Waste usecase
+///
+/// Loss ratios compare the wasted value with the purchases journaled in the period.
+pub struct WasteService {
+    waste_repository: Arc<dyn ForWastePersistence>,
+    supply_respository: Arc<dyn ForSupplyPersistence>,
+    unit_repository: Arc<dyn ForUnitPersistence>,
+    journal_respository: Arc<dyn ForJournalPersistence>,
+    stocktaking_respository: Arc<dyn ForStocktakingPersistence>,
+    rounding_policy: RoundingPolicy,
+    store: StoreContext,
+}
+
+impl WasteService {
+    pub fn new(
+        waste_repository: Arc<dyn ForWastePersistence>,
+        supply_respository: Arc<dyn ForSupplyPersistence>,
+        unit_repository: Arc<dyn ForUnitPersistence>,
+        journal_respository: Arc<dyn ForJournalPersistence>,
+        stocktaking_respository: Arc<dyn ForStocktakingPersistence>,
+        rounding_policy: RoundingPolicy,
+        store: StoreContext,
+    ) -> Self {
+        Self {
+            waste_repository,
+            supply_respository,
+            unit_repository,
+            journal_respository,
+            stocktaking_respository,
+            rounding_policy,
+            store,
+        }
+    }
+}
+
+/// Converts a waste record into its DTO.
+fn waste_dto(waste: &WasteRecord) -> WasteDTO {
+    let wasted = waste.wasted();
+
+    WasteDTO {
+        id: waste.id().to_string(),
+        wasted_date: waste.wasted_at().as_i64(),
+        supply_id: wasted.supply_id().to_string(),
+        supply_name: wasted.supply_name().to_string(),
+        unit_name: wasted.unit_name().to_string(),
+        quantity: wasted.quantity().as_f64(),
+        unit_price: wasted.unit_price().as_f64(),
+        total_price: wasted.total_price().as_f64(),
+        reason: waste.reason().to_string(),
+    }
+}
+
+/// Waste, purchases and inventory of a supply in a period.
+struct SupplyLoss {
+    supply_id: SupplyId,
+    supply_name: SupplyName,
+    quantities: Vec<(UnitName, Quantity)>,
+    loss: WasteLoss,
+}
+
+/// Returns the loss of `supply_id` in `losses`, adding it if it does not exist.
+fn supply_loss<'a>(
+    losses: &'a mut Vec<SupplyLoss>,
+    supply_id: &SupplyId,
+    supply_name: &SupplyName,
+) -> &'a mut SupplyLoss {
+    match losses.iter().position(|loss| &loss.supply_id == supply_id) {
+        Some(index) => &mut losses[index],
+        None => {
+            losses.push(SupplyLoss {
+                supply_id: supply_id.clone(),
+                supply_name: supply_name.clone(),
+                quantities: Vec::new(),
+                loss: WasteLoss::default(),
+            });
+
+            losses.last_mut().unwrap()
+        }
+    }
+}
+
+/// Waste usecase implementation
+impl WasteUsecase for WasteService {
+    fn list(&self) -> Result<Vec<WasteDTO>> {
+        let wastes = self.waste_repository.list(&self.store)?;
+
+        Ok(wastes.iter().map(waste_dto).collect())
+    }
+
+    fn search(&self, query: SearchWasteQuery) -> Result<Vec<WasteDTO>> {
+        let query = FindWasteQuery {
+            period_start: query.period_start.map(WastedDateTime::new),
+            period_end: query.period_end.map(WastedDateTime::new),
+            supply_id: query
+                .supply_id
+                .filter(|id| !id.trim().is_empty())
+                .map(SupplyId::new)
+                .transpose()?,
+        };
+
+        let wastes = self.waste_repository.find(&self.store, query)?;
+
+        Ok(wastes.iter().map(waste_dto).collect())
+    }
+
+    fn record(&self, command: RecordWasteCommand) -> Result<WasteDTO> {
+        let supply_id = SupplyId::new(&command.supply_id)?;
+
+        let supplies = self.supply_respository.list(&self.store)?;
+        let units = self.unit_repository.list()?;
+
+        let supply = supplies
+            .iter()
+            .find(|supply| supply.id() == &supply_id)
+            .ok_or(Error::DomainError(format!("supply does not exist.")))?;
+
+        let quantity = WasteQuantity::new(command.quantity)?;
+
+        let unit_name = match command.unit_name.filter(|name| !name.trim().is_empty()) {
+            Some(unit_name) => UnitName::new(unit_name)?,
+            None => units
+                .iter()
+                .find(|unit| unit.id() == supply.units().stock_unit_id())
+                .map(|unit| unit.name().clone())
+                .ok_or(Error::DomainError(format!(
+                    "stock unit of {} is not registered.",
+                    supply.name().as_str()
+                )))?,
+        };
+
+        // the unit must be convertible into the stock unit
+        stock_quantity_of(
+            &supplies,
+            &units,
+            &supply_id,
+            unit_name.clone(),
+            quantity.as_quantity(),
+        )?;
+
+        let wasted = WastedSupply::new(
+            supply_id,
+            supply.name().clone(),
+            unit_name,
+            quantity,
+            WasteUnitPrice::new(command.unit_price)?,
+            &self.rounding_policy,
+        )?;
+
+        let waste = WasteRecord::restore(
+            self.waste_repository.next_id()?,
+            WastedDateTime::new(command.wasted_date),
+            wasted,
+            WasteReason::new(command.reason)?,
+        );
+
+        self.waste_repository.add(&self.store, waste.clone())?;
+
+        Ok(waste_dto(&waste))
+    }
+
+    fn delete(&self, waste_id: impl AsRef<str>) -> Result<()> {
+        let waste_id = WasteId::new(waste_id.as_ref())?;
+
+        self.waste_repository.delete(&self.store, waste_id)?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<TrashedDTO<WasteDTO>>> {
+        let trashed = self.waste_repository.list_deleted(&self.store)?;
+
+        let dtos = trashed
+            .iter()
+            .map(|trashed| waste_dto(trashed.item()))
+            .collect();
+
+        Ok(trashed_dtos(&trashed, dtos))
+    }
+
+    fn restore(&self, waste_id: impl AsRef<str>) -> Result<()> {
+        let waste_id = WasteId::new(waste_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let waste = trashed_item(
+            self.waste_repository.list_deleted(&store)?,
+            |waste| waste.id() == &waste_id,
+            "waste record",
+        )?;
+
+        self.waste_repository.restore(&store, waste.id().clone())?;
+
+        Ok(())
+    }
+
+    fn purge(&self, waste_id: impl AsRef<str>) -> Result<()> {
+        let waste_id = WasteId::new(waste_id.as_ref())?;
+
+        let store = self.store.owner()?;
+
+        let waste = trashed_item(
+            self.waste_repository.list_deleted(&store)?,
+            |waste| waste.id() == &waste_id,
+            "waste record",
+        )?;
+
+        self.waste_repository.purge(&store, waste.id().clone())?;
+
+        Ok(())
+    }
+
+    fn summarize(&self, query: WasteSummaryQuery) -> Result<WasteSummaryDTO> {
+        if query.period_end < query.period_start {
+            return Err(Error::DomainError(format!(
+                "period end must not be before period start."
+            )));
+        }
+
+        let supply_ids: Vec<SupplyId> = self
+            .supply_respository
+            .list(&self.store)?
+            .iter()
+            .map(|supply| supply.id().clone())
+            .collect();
+
+        // waste of supplies in the stores only
+        let mut wastes = self.waste_repository.find(
+            &self.store,
+            FindWasteQuery {
+                period_start: Some(WastedDateTime::new(query.period_start)),
+                period_end: Some(WastedDateTime::new(query.period_end)),
+                supply_id: None,
+            },
+        )?;
+
+        wastes.retain(|waste| supply_ids.contains(waste.wasted().supply_id()));
+
+        let journals = self.journal_respository.find(
+            &self.store,
+            FindJournalsQuery {
+                period_start: Some(EntryDateTime::new(query.period_start)),
+                period_end: Some(EntryDateTime::new(query.period_end)),
+                supplier_name: None,
+                supply_name: None,
+            },
+        )?;
+
+        let closing = self
+            .stocktaking_respository
+            .find(
+                &self.store,
+                FindStocktakingsQuery {
+                    period_start: Some(StocktakenDateTime::new(query.period_start)),
+                    period_end: Some(StocktakenDateTime::new(query.period_end)),
+                },
+            )?
+            .into_iter()
+            .max_by_key(|stocktaking| stocktaking.stocktaken_at().as_i64());
+
+        let mut losses: Vec<SupplyLoss> = Vec::new();
+        let mut reasons: Vec<(WasteReason, Money)> = Vec::new();
+
+        for waste in &wastes {
+            let wasted = waste.wasted();
+            let loss = supply_loss(&mut losses, wasted.supply_id(), wasted.supply_name());
+
+            match loss
+                .quantities
+                .iter_mut()
+                .find(|(unit_name, _)| unit_name == wasted.unit_name())
+            {
+                Some((_, quantity)) => {
+                    *quantity = quantity.checked_add(wasted.quantity().as_quantity())?
+                }
+                None => loss
+                    .quantities
+                    .push((wasted.unit_name().clone(), wasted.quantity().as_quantity())),
+            }
+
+            loss.loss = loss.loss.checked_add(WasteLoss::new(
+                wasted.total_price().as_money(),
+                Money::ZERO,
+                Money::ZERO,
+            ))?;
+
+            match reasons
+                .iter_mut()
+                .find(|(reason, _)| reason == &waste.reason())
+            {
+                Some((_, total)) => *total = total.checked_add(wasted.total_price().as_money())?,
+                None => reasons.push((waste.reason(), wasted.total_price().as_money())),
+            }
+        }
+
+        for record in journals.iter().flat_map(|journal| journal.records()) {
+            let loss = supply_loss(&mut losses, record.supply_id(), record.supply_name());
+
+            loss.loss = loss.loss.checked_add(WasteLoss::new(
+                Money::ZERO,
+                record.total_price().as_money(),
+                Money::ZERO,
+            ))?;
+        }
+
+        if let Some(closing) = &closing {
+            for record in closing.records() {
+                let loss = supply_loss(&mut losses, record.supply_id(), record.supply_name());
+
+                loss.loss = loss.loss.checked_add(WasteLoss::new(
+                    Money::ZERO,
+                    Money::ZERO,
+                    record.total_price().as_money(),
+                ))?;
+            }
+        }
+
+        let total = losses
+            .iter()
+            .try_fold(WasteLoss::default(), |total, loss| {
+                total.checked_add(loss.loss)
+            })?;
+
+        let supplies = losses
+            .iter()
+            .filter(|loss| !loss.quantities.is_empty())
+            .map(|loss| {
+                Ok(SupplyWasteDTO {
+                    supply_id: loss.supply_id.to_string(),
+                    supply_name: loss.supply_name.to_string(),
+                    quantities: loss
+                        .quantities
+                        .iter()
+                        .map(|(unit_name, quantity)| WasteQuantityDTO {
+                            unit_name: unit_name.to_string(),
+                            quantity: quantity.as_f64(),
+                        })
+                        .collect(),
+                    wasted: loss.loss.wasted().as_f64(),
+                    purchases: loss.loss.purchases().as_f64(),
+                    closing_inventory: loss.loss.closing_inventory().as_f64(),
+                    loss_ratio: loss.loss.loss_ratio()?,
+                })
+            })
+            .collect::<Result<Vec<SupplyWasteDTO>>>()?;
+
+        Ok(WasteSummaryDTO {
+            period_start: query.period_start,
+            period_end: query.period_end,
+            closing_stocktaking_id: closing.map(|closing| closing.id().to_string()),
+            wasted: total.wasted().as_f64(),
+            purchases: total.purchases().as_f64(),
+            closing_inventory: total.closing_inventory().as_f64(),
+            loss_ratio: total.loss_ratio()?,
+            reasons: reasons
+                .into_iter()
+                .map(|(reason, wasted)| ReasonWasteDTO {
+                    reason: reason.to_string(),
+                    wasted: wasted.as_f64(),
+                })
+                .collect(),
+            supplies,
+        })
+    }
+}
@@ -1,8 +1,21 @@
 use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::category::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::event::*;
+use crate::core::domain::values::price::*;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::tax::*;
+use crate::core::domain::values::unit::*;
+use crate::core::domain::values::valuation::*;
 use crate::core::provided_ports::*;
 use crate::core::required_ports::{mock::*, *};
-use crate::core::services::stock::{journal::*, stocktaking::*, supplier::*, supply::*};
+use crate::core::services::stock::{
+    category::*, consumption::*, journal::*, ledger::*, location::*, order::*, reorder::*,
+    report::*, stocktaking::*, store::*, supplier::*, supply::*, transfer::*, unit::*, waste::*,
+};
+use crate::core::Error;
 use std::sync::{Arc, Mutex};
 
 #[test]
@@ -12,21 +25,36 @@ fn supply_service_test() {
     let supplier_repository = MockSupplierRepository::new(Arc::clone(&storage));
 
     supplier_repository
-        .add(Supplier::restore(
-            SupplierId::new("1").unwrap(),
-            SupplierName::new("SupplierA").unwrap(),
-        ))
+        .add(
+            &StoreContext::default(),
+            Supplier::restore(
+                SupplierId::new("1").unwrap(),
+                SupplierName::new("SupplierA").unwrap(),
+                None,
+                false,
+            ),
+            None,
+        )
         .unwrap();
 
     let service = SupplyService::new(
         Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
         Arc::new(supplier_repository),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        None,
+        StoreContext::default(),
     );
 
     let create_command = CreateSupplyCommand {
         supplier_id: "1".into(),
         supply_name: "SupplyA".into(),
         unit_name: "g".into(),
+        tax_category: Some("reduced".into()),
+        stock_unit_id: None,
+        conversions: vec![],
+        category_id: None,
     };
 
     let supply = service.register(create_command).unwrap();
@@ -38,6 +66,21 @@ fn supply_service_test() {
             name: "SupplyA".into(),
             unit_name: "g".into(),
             supplier_id: "1".into(),
+            offers: vec![SupplyOfferDTO {
+                supplier_id: "1".into(),
+                item_code: None,
+                unit_price: None,
+                preferred: true,
+            }],
+            tax_category: "reduced".into(),
+            purchase_unit_id: "1".into(),
+            stock_unit_id: "1".into(),
+            conversions: vec![],
+            category_id: None,
+            par_level: None,
+            reorder_point: None,
+            location_id: None,
+            location_sort_order: None,
         }
     );
 
@@ -50,6 +93,21 @@ fn supply_service_test() {
             name: "SupplyA".into(),
             unit_name: "g".into(),
             supplier_id: "1".into(),
+            offers: vec![SupplyOfferDTO {
+                supplier_id: "1".into(),
+                item_code: None,
+                unit_price: None,
+                preferred: true,
+            }],
+            tax_category: "reduced".into(),
+            purchase_unit_id: "1".into(),
+            stock_unit_id: "1".into(),
+            conversions: vec![],
+            category_id: None,
+            par_level: None,
+            reorder_point: None,
+            location_id: None,
+            location_sort_order: None,
         }]
     );
 
@@ -62,14 +120,50 @@ fn supply_service_test() {
             name: "SupplyA".into(),
             unit_name: "g".into(),
             supplier_id: "1".into(),
+            offers: vec![SupplyOfferDTO {
+                supplier_id: "1".into(),
+                item_code: None,
+                unit_price: None,
+                preferred: true,
+            }],
+            tax_category: "reduced".into(),
+            purchase_unit_id: "1".into(),
+            stock_unit_id: "1".into(),
+            conversions: vec![],
+            category_id: None,
+            par_level: None,
+            reorder_point: None,
+            location_id: None,
+            location_sort_order: None,
         })
     );
 
+    // kg cannot be stocked in g without a conversion
+    let result = service.update(UpdateSupplyCommand {
+        supply_id: "1".into(),
+        supply_name: "SupplyB".into(),
+        unit_name: "kg".into(),
+        supplier_id: "1".into(),
+        tax_category: None,
+        stock_unit_id: None,
+        conversions: None,
+        category_id: None,
+    });
+
+    assert!(result.is_err());
+
     let result = service.update(UpdateSupplyCommand {
         supply_id: "1".into(),
         supply_name: "SupplyB".into(),
         unit_name: "kg".into(),
         supplier_id: "1".into(),
+        tax_category: None,
+        stock_unit_id: None,
+        conversions: Some(vec![UnitConversionDTO {
+            unit_id: "2".into(),
+            factor: 1000.0,
+        }]),
+        category_id: None,
     });
 
     assert!(result.is_ok());
@@ -83,6 +177,24 @@ fn supply_service_test() {
             name: "SupplyB".into(),
             unit_name: "kg".into(),
             supplier_id: "1".into(),
+            offers: vec![SupplyOfferDTO {
+                supplier_id: "1".into(),
+                item_code: None,
+                unit_price: None,
+                preferred: true,
+            }],
+            tax_category: "reduced".into(),
+            purchase_unit_id: "2".into(),
+            stock_unit_id: "1".into(),
+            conversions: vec![UnitConversionDTO {
+                unit_id: "2".into(),
+                factor: 1000.0,
+            }],
+            category_id: None,
+            par_level: None,
+            reorder_point: None,
+            location_id: None,
+            location_sort_order: None,
         })
     );
 
@@ -91,6 +203,10 @@ fn supply_service_test() {
         supply_name: "SupplyB".into(),
         unit_name: "kg".into(),
         supplier_id: "1".into(),
+        tax_category: None,
+        stock_unit_id: None,
+        conversions: None,
+        category_id: None,
     });
 
     assert!(result.is_err());
@@ -100,10 +216,103 @@ fn supply_service_test() {
         supply_name: "SupplyB".into(),
         unit_name: "kg".into(),
         supplier_id: "2".into(),
+        tax_category: None,
+        stock_unit_id: None,
+        conversions: None,
+        category_id: None,
     });
 
     assert!(result.is_err());
 
+    MockSupplierRepository::new(Arc::clone(&storage))
+        .add(
+            &StoreContext::default(),
+            Supplier::restore(
+                SupplierId::new("2").unwrap(),
+                SupplierName::new("SupplierB").unwrap(),
+                None,
+                false,
+            ),
+            None,
+        )
+        .unwrap();
+
+    // the first supplier is preferred unless another is
+    service
+        .set_offers(SetSupplyOffersCommand {
+            supply_id: "1".into(),
+            offers: vec![
+                SupplyOfferDTO {
+                    supplier_id: "1".into(),
+                    item_code: Some(" ON-01 ".into()),
+                    unit_price: Some(120.0),
+                    preferred: false,
+                },
+                SupplyOfferDTO {
+                    supplier_id: "2".into(),
+                    item_code: None,
+                    unit_price: Some(110.0),
+                    preferred: false,
+                },
+            ],
+        })
+        .unwrap();
+
+    let supply = service.get("1").unwrap().unwrap();
+
+    assert_eq!(supply.supplier_id, "1");
+    assert_eq!(supply.offers[0].item_code, Some("ON-01".into()));
+    assert_eq!(supply.offers[1].unit_price, Some(110.0));
+    assert!(!supply.offers[1].preferred);
+
+    assert_eq!(service.get_of_supplier("2".into()).unwrap(), vec![supply]);
+
+    // updating the supplier makes it the preferred one, keeping the others
+    service
+        .update(UpdateSupplyCommand {
+            supply_id: "1".into(),
+            supply_name: "SupplyB".into(),
+            unit_name: "kg".into(),
+            supplier_id: "2".into(),
+            tax_category: None,
+            stock_unit_id: None,
+            conversions: None,
+            category_id: None,
+        })
+        .unwrap();
+
+    let supply = service.get("1").unwrap().unwrap();
+
+    assert_eq!(supply.supplier_id, "2");
+    assert_eq!(supply.offers.len(), 2);
+
+    let result = service.set_offers(SetSupplyOffersCommand {
+        supply_id: "1".into(),
+        offers: vec![
+            SupplyOfferDTO {
+                supplier_id: "1".into(),
+                item_code: None,
+                unit_price: None,
+                preferred: true,
+            },
+            SupplyOfferDTO {
+                supplier_id: "2".into(),
+                item_code: None,
+                unit_price: None,
+                preferred: true,
+            },
+        ],
+    });
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    let result = service.set_offers(SetSupplyOffersCommand {
+        supply_id: "1".into(),
+        offers: vec![],
+    });
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
     service.delete("1").unwrap();
 
     let supplies = service.list().unwrap();
@@ -117,14 +326,54 @@ fn supplier_service_test() {
 
     let supplier_repository = Arc::new(MockSupplierRepository::new(Arc::clone(&storage)));
 
-    let service = SupplierService::new(supplier_repository);
+    let service = SupplierService::new(
+        supplier_repository,
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        None,
+        StoreContext::default(),
+    );
 
     service
         .register(RegisterSupplierCommand {
             supplier_name: "SupplierA".into(),
+            invoice_registration_number: Some("T7000012050002".into()),
+            invoice_registered: true,
+            contact: SupplierContactDTO {
+                contact_person: Some("山田".into()),
+                phone_number: Some("03-1234-5678".into()),
+                fax_number: Some("".into()),
+                email: Some("order@example.com".into()),
+                postal_code: Some("1000005".into()),
+                address: Some("東京都千代田区丸の内1-1-1".into()),
+            },
+            notes: Some("火・金配送".into()),
+            billing_terms: Some(BillingTermsDTO {
+                closing_day: 31,
+                payment_months_later: 1,
+                payment_day: 31,
+                payment_method: "bank_transfer".into(),
+            }),
         })
         .unwrap();
 
+    let contact = SupplierContactDTO {
+        contact_person: Some("山田".into()),
+        phone_number: Some("03-1234-5678".into()),
+        fax_number: None,
+        email: Some("order@example.com".into()),
+        postal_code: Some("100-0005".into()),
+        address: Some("東京都千代田区丸の内1-1-1".into()),
+    };
+
+    let billing_terms = BillingTermsDTO {
+        closing_day: 31,
+        payment_months_later: 1,
+        payment_day: 31,
+        payment_method: "bank_transfer".into(),
+    };
+
     let suppliers = service.list().unwrap();
 
     assert_eq!(
@@ -132,9 +381,32 @@ fn supplier_service_test() {
         vec![SupplierDTO {
             id: "1".into(),
             name: "SupplierA".into(),
+            invoice_registration_number: Some("T7000012050002".into()),
+            invoice_registered: true,
+            contact: contact.clone(),
+            notes: Some("火・金配送".into()),
+            billing_terms: Some(billing_terms.clone()),
         }]
     );
 
+    let search = |contact_person: Option<&str>, phone_number: Option<&str>| {
+        service
+            .search(SearchSuppliersQuery {
+                supplier_name: None,
+                supply_name: None,
+                contact_person: contact_person.map(|person| person.into()),
+                phone_number: phone_number.map(|number| number.into()),
+                category_id: None,
+            })
+            .unwrap()
+            .len()
+    };
+
+    assert_eq!(search(Some("山田"), None), 1);
+    assert_eq!(search(None, Some("0312345678")), 1);
+    assert_eq!(search(None, Some("1234-5678")), 1);
+    assert_eq!(search(None, Some("06")), 0);
+
     let supplier = service.get("1").unwrap();
 
     assert_eq!(
@@ -142,6 +414,11 @@ fn supplier_service_test() {
         Some(SupplierDTO {
             id: "1".into(),
             name: "SupplierA".into(),
+            invoice_registration_number: Some("T7000012050002".into()),
+            invoice_registered: true,
+            contact: contact.clone(),
+            notes: Some("火・金配送".into()),
+            billing_terms: Some(billing_terms.clone()),
         })
     );
 
@@ -149,6 +426,11 @@ fn supplier_service_test() {
         .update(UpdateSupplierCommand {
             supplier_id: "1".into(),
             supplier_name: "SupplierB".into(),
+            invoice_registration_number: None,
+            invoice_registered: None,
+            contact: contact.clone(),
+            notes: None,
+            billing_terms: None,
         })
         .unwrap();
 
@@ -159,9 +441,53 @@ fn supplier_service_test() {
         Some(SupplierDTO {
             id: "1".into(),
             name: "SupplierB".into(),
+            invoice_registration_number: Some("T7000012050002".into()),
+            invoice_registered: true,
+            contact,
+            notes: None,
+            billing_terms: None,
         })
     );
 
+    let result = service.update(UpdateSupplierCommand {
+        supplier_id: "1".into(),
+        supplier_name: "SupplierB".into(),
+        invoice_registration_number: Some("T7000012050003".into()),
+        invoice_registered: Some(true),
+        contact: SupplierContactDTO::default(),
+        notes: None,
+        billing_terms: None,
+    });
+
+    assert!(result.is_err());
+
+    let result = service.update(UpdateSupplierCommand {
+        supplier_id: "1".into(),
+        supplier_name: "SupplierB".into(),
+        invoice_registration_number: None,
+        invoice_registered: Some(true),
+        contact: SupplierContactDTO::default(),
+        notes: None,
+        billing_terms: None,
+    });
+
+    assert!(result.is_err());
+
+    let result = service.update(UpdateSupplierCommand {
+        supplier_id: "1".into(),
+        supplier_name: "SupplierB".into(),
+        invoice_registration_number: None,
+        invoice_registered: None,
+        contact: SupplierContactDTO {
+            phone_number: Some("1234".into()),
+            ..SupplierContactDTO::default()
+        },
+        notes: None,
+        billing_terms: None,
+    });
+
+    assert!(result.is_err());
+
     service.delete("1").unwrap();
 
     let suppliers = service.list().unwrap();
@@ -169,6 +495,173 @@ fn supplier_service_test() {
     assert!(suppliers.is_empty());
 }
 
+#[test]
+fn unit_service_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    let supplier_repository = MockSupplierRepository::new(Arc::clone(&storage));
+
+    supplier_repository
+        .add(
+            &StoreContext::default(),
+            Supplier::restore(
+                SupplierId::new("1").unwrap(),
+                SupplierName::new("SupplierA").unwrap(),
+                None,
+                false,
+            ),
+            None,
+        )
+        .unwrap();
+
+    let unit_service = UnitService::new(
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+    );
+
+    let bottle = unit_service
+        .register(RegisterUnitCommand {
+            unit_name: "bottle".into(),
+        })
+        .unwrap();
+
+    let result = unit_service.register(RegisterUnitCommand {
+        unit_name: "bottle".into(),
+    });
+
+    assert!(result.is_err());
+
+    let supply_service = SupplyService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(supplier_repository),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        None,
+        StoreContext::default(),
+    );
+
+    // case is registered as the purchase unit, 1 case = 12 bottles
+    let result = supply_service.register(CreateSupplyCommand {
+        supplier_id: "1".into(),
+        supply_name: "Beer".into(),
+        unit_name: "case".into(),
+        tax_category: None,
+        stock_unit_id: Some(bottle.id.clone()),
+        conversions: vec![UnitConversionDTO {
+            unit_id: "2".into(),
+            factor: 12.0,
+        }],
+        category_id: None,
+    });
+
+    assert!(result.is_ok());
+
+    let units = unit_service.list().unwrap();
+
+    assert_eq!(
+        units,
+        vec![
+            UnitDTO {
+                id: "1".into(),
+                name: "bottle".into(),
+            },
+            UnitDTO {
+                id: "2".into(),
+                name: "case".into(),
+            },
+        ]
+    );
+
+    let result = unit_service.delete(&bottle.id);
+
+    assert!(result.is_err());
+
+    let journal_service = JournalService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        PriceAlertRule::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    let record = |unit_name: &str, unit_price: f64, quantity: f64| JournalRecordDTO {
+        supply_id: "1".into(),
+        supply_name: "Beer".into(),
+        supplier_id: "1".into(),
+        supplier_name: "SupplierA".into(),
+        unit_name: unit_name.into(),
+        unit_price,
+        quantity,
+        total_price: unit_price * quantity,
+        tax_category: None,
+        tax_included: None,
+        tax_price: 0.0,
+        invoice_registered: None,
+        lot_number: None,
+        expiry_date: None,
+    };
+
+    journal_service
+        .record(RecordJournalCommand {
+            entry_date: 100000,
+            total_price: 4200.0,
+            records: vec![record("case", 2400.0, 1.5), record("bottle", 200.0, 3.0)],
+        })
+        .unwrap();
+
+    let quantities = journal_service
+        .aggregate_quantities(SearchJournalsQuery {
+            period_start: None,
+            period_end: None,
+            supplier_name: None,
+            supply_name: None,
+            category_id: None,
+        })
+        .unwrap();
+
+    assert_eq!(
+        quantities,
+        vec![StockQuantityDTO {
+            supply_id: "1".into(),
+            supply_name: "Beer".into(),
+            unit_name: "bottle".into(),
+            quantity: 21.0,
+        }]
+    );
+
+    // a unit without conversion cannot be aggregated
+    unit_service
+        .register(RegisterUnitCommand {
+            unit_name: "pack".into(),
+        })
+        .unwrap();
+
+    journal_service
+        .record(RecordJournalCommand {
+            entry_date: 200000,
+            total_price: 1000.0,
+            records: vec![record("pack", 1000.0, 1.0)],
+        })
+        .unwrap();
+
+    let result = journal_service.aggregate_quantities(SearchJournalsQuery {
+        period_start: None,
+        period_end: None,
+        supplier_name: None,
+        supply_name: None,
+        category_id: None,
+    });
+
+    assert!(result.is_err());
+}
+
 #[test]
 fn journal_service_test() {
     let storage = Arc::new(Mutex::new(Storage::default()));
@@ -178,34 +671,62 @@ fn journal_service_test() {
     let journal_repository = MockJournalRepository::new(Arc::clone(&storage));
 
     supplier_respository
-        .add(Supplier::restore(
-            SupplierId::new("1").unwrap(),
-            SupplierName::new("SupplierA").unwrap(),
-        ))
+        .add(
+            &StoreContext::default(),
+            Supplier::restore(
+                SupplierId::new("1").unwrap(),
+                SupplierName::new("SupplierA").unwrap(),
+                None,
+                false,
+            ),
+            None,
+        )
         .unwrap();
 
     supply_respository
-        .add(Supply::new(
-            SupplyId::new("1").unwrap(),
-            SupplyName::new("SupplyA").unwrap(),
-            UnitName::new("g").unwrap(),
-            SupplierId::new("1").unwrap(),
-        ))
+        .add(
+            &StoreContext::default(),
+            Supply::new(
+                SupplyId::new("1").unwrap(),
+                SupplyName::new("SupplyA").unwrap(),
+                UnitName::new("g").unwrap(),
+                SupplierId::new("1").unwrap(),
+                TaxCategory::Reduced,
+                SupplyUnits::single(UnitId::new("1").unwrap()),
+                None,
+            ),
+            None,
+        )
         .unwrap();
 
     supply_respository
-        .add(Supply::new(
-            SupplyId::new("2").unwrap(),
-            SupplyName::new("SupplyB").unwrap(),
-            UnitName::new("g").unwrap(),
-            SupplierId::new("1").unwrap(),
-        ))
+        .add(
+            &StoreContext::default(),
+            Supply::new(
+                SupplyId::new("2").unwrap(),
+                SupplyName::new("SupplyB").unwrap(),
+                UnitName::new("g").unwrap(),
+                SupplierId::new("1").unwrap(),
+                TaxCategory::Standard,
+                SupplyUnits::single(UnitId::new("1").unwrap()),
+                None,
+            ),
+            None,
+        )
         .unwrap();
 
     let service = JournalService::new(
         Arc::new(supply_respository),
         Arc::new(supplier_respository),
         Arc::new(journal_repository),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        PriceAlertRule::default(),
+        None,
+        StoreContext::default(),
     );
 
     service
@@ -222,6 +743,12 @@ fn journal_service_test() {
                     unit_price: 100.0,
                     quantity: 10.0,
                     total_price: 1000.0,
+                    tax_category: None,
+                    tax_included: None,
+                    tax_price: 0.0,
+                    invoice_registered: None,
+                    lot_number: None,
+                    expiry_date: None,
                 },
                 JournalRecordDTO {
                     supply_id: "2".into(),
@@ -232,6 +759,12 @@ fn journal_service_test() {
                     unit_price: 120.0,
                     quantity: 5.0,
                     total_price: 600.0,
+                    tax_category: None,
+                    tax_included: None,
+                    tax_price: 0.0,
+                    invoice_registered: None,
+                    lot_number: None,
+                    expiry_date: None,
                 },
             ],
         })
@@ -245,6 +778,31 @@ fn journal_service_test() {
             id: "1".into(),
             entry_date: 100000,
             total_price: 1600.0,
+            net_price: 1472.0,
+            tax_price: 128.0,
+            gross_price: 1600.0,
+            deductible_tax_price: 128.0,
+            non_deductible_tax_price: 0.0,
+            tax_summaries: vec![
+                TaxSummaryDTO {
+                    tax_category: "standard".into(),
+                    tax_rate: 10,
+                    net_price: 546.0,
+                    tax_price: 54.0,
+                    gross_price: 600.0,
+                    deductible_tax_price: 54.0,
+                    non_deductible_tax_price: 0.0,
+                },
+                TaxSummaryDTO {
+                    tax_category: "reduced".into(),
+                    tax_rate: 8,
+                    net_price: 926.0,
+                    tax_price: 74.0,
+                    gross_price: 1000.0,
+                    deductible_tax_price: 74.0,
+                    non_deductible_tax_price: 0.0,
+                },
+            ],
             records: vec![
                 JournalRecordDTO {
                     supply_id: "1".into(),
@@ -255,6 +813,12 @@ fn journal_service_test() {
                     unit_price: 100.0,
                     quantity: 10.0,
                     total_price: 1000.0,
+                    tax_category: Some("reduced".into()),
+                    tax_included: Some(true),
+                    tax_price: 74.0,
+                    invoice_registered: Some(false),
+                    lot_number: None,
+                    expiry_date: None,
                 },
                 JournalRecordDTO {
                     supply_id: "2".into(),
@@ -265,8 +829,15 @@ fn journal_service_test() {
                     unit_price: 120.0,
                     quantity: 5.0,
                     total_price: 600.0,
+                    tax_category: Some("standard".into()),
+                    tax_included: Some(true),
+                    tax_price: 54.0,
+                    invoice_registered: Some(false),
+                    lot_number: None,
+                    expiry_date: None,
                 },
             ],
+            warnings: vec![],
         }]
     );
 
@@ -278,6 +849,31 @@ fn journal_service_test() {
             id: "1".into(),
             entry_date: 100000,
             total_price: 1600.0,
+            net_price: 1472.0,
+            tax_price: 128.0,
+            gross_price: 1600.0,
+            deductible_tax_price: 128.0,
+            non_deductible_tax_price: 0.0,
+            tax_summaries: vec![
+                TaxSummaryDTO {
+                    tax_category: "standard".into(),
+                    tax_rate: 10,
+                    net_price: 546.0,
+                    tax_price: 54.0,
+                    gross_price: 600.0,
+                    deductible_tax_price: 54.0,
+                    non_deductible_tax_price: 0.0,
+                },
+                TaxSummaryDTO {
+                    tax_category: "reduced".into(),
+                    tax_rate: 8,
+                    net_price: 926.0,
+                    tax_price: 74.0,
+                    gross_price: 1000.0,
+                    deductible_tax_price: 74.0,
+                    non_deductible_tax_price: 0.0,
+                },
+            ],
             records: vec![
                 JournalRecordDTO {
                     supply_id: "1".into(),
@@ -288,6 +884,12 @@ fn journal_service_test() {
                     unit_price: 100.0,
                     quantity: 10.0,
                     total_price: 1000.0,
+                    tax_category: Some("reduced".into()),
+                    tax_included: Some(true),
+                    tax_price: 74.0,
+                    invoice_registered: Some(false),
+                    lot_number: None,
+                    expiry_date: None,
                 },
                 JournalRecordDTO {
                     supply_id: "2".into(),
@@ -298,11 +900,55 @@ fn journal_service_test() {
                     unit_price: 120.0,
                     quantity: 5.0,
                     total_price: 600.0,
+                    tax_category: Some("standard".into()),
+                    tax_included: Some(true),
+                    tax_price: 54.0,
+                    invoice_registered: Some(false),
+                    lot_number: None,
+                    expiry_date: None,
                 },
             ],
+            warnings: vec![],
         })
     );
 
+    MockSupplierRepository::new(Arc::clone(&storage))
+        .add(
+            &StoreContext::default(),
+            Supplier::restore(
+                SupplierId::new("2").unwrap(),
+                SupplierName::new("SupplierB").unwrap(),
+                None,
+                false,
+            ),
+            None,
+        )
+        .unwrap();
+
+    // lines must come from a supplier offering the supply
+    let result = service.edit(EditJournalCommand {
+        journal_id: "1".into(),
+        total_price: 1000.0,
+        records: vec![JournalRecordDTO {
+            supply_id: "1".into(),
+            supplier_id: "2".into(),
+            supply_name: "SupplyA".into(),
+            supplier_name: "SupplierB".into(),
+            unit_name: "g".into(),
+            unit_price: 100.0,
+            quantity: 10.0,
+            total_price: 1000.0,
+            tax_category: None,
+            tax_included: None,
+            tax_price: 0.0,
+            invoice_registered: None,
+            lot_number: None,
+            expiry_date: None,
+        }],
+    });
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
     service
         .edit(EditJournalCommand {
             journal_id: "1".into(),
@@ -316,6 +962,12 @@ fn journal_service_test() {
                 unit_price: 200.0,
                 quantity: 10.0,
                 total_price: 2000.0,
+                tax_category: None,
+                tax_included: None,
+                tax_price: 0.0,
+                invoice_registered: None,
+                lot_number: None,
+                expiry_date: None,
             }],
         })
         .unwrap();
@@ -328,6 +980,20 @@ fn journal_service_test() {
             id: "1".into(),
             entry_date: 100000,
             total_price: 2000.0,
+            net_price: 1819.0,
+            tax_price: 181.0,
+            gross_price: 2000.0,
+            deductible_tax_price: 181.0,
+            non_deductible_tax_price: 0.0,
+            tax_summaries: vec![TaxSummaryDTO {
+                tax_category: "standard".into(),
+                tax_rate: 10,
+                net_price: 1819.0,
+                tax_price: 181.0,
+                gross_price: 2000.0,
+                deductible_tax_price: 181.0,
+                non_deductible_tax_price: 0.0,
+            },],
             records: vec![JournalRecordDTO {
                 supply_id: "2".into(),
                 supplier_id: "1".into(),
@@ -337,7 +1003,14 @@ fn journal_service_test() {
                 unit_price: 200.0,
                 quantity: 10.0,
                 total_price: 2000.0,
+                tax_category: Some("standard".into()),
+                tax_included: Some(true),
+                tax_price: 181.0,
+                invoice_registered: Some(false),
+                lot_number: None,
+                expiry_date: None,
             },],
+            warnings: vec![],
         })
     );
 
@@ -347,6 +1020,7 @@ fn journal_service_test() {
             period_end: Some(200000),
             supply_name: Some("B".into()),
             supplier_name: Some("C".into()),
+            category_id: None,
         })
         .unwrap();
 
@@ -356,6 +1030,20 @@ fn journal_service_test() {
             id: "1".into(),
             entry_date: 100000,
             total_price: 2000.0,
+            net_price: 1819.0,
+            tax_price: 181.0,
+            gross_price: 2000.0,
+            deductible_tax_price: 181.0,
+            non_deductible_tax_price: 0.0,
+            tax_summaries: vec![TaxSummaryDTO {
+                tax_category: "standard".into(),
+                tax_rate: 10,
+                net_price: 1819.0,
+                tax_price: 181.0,
+                gross_price: 2000.0,
+                deductible_tax_price: 181.0,
+                non_deductible_tax_price: 0.0,
+            },],
             records: vec![JournalRecordDTO {
                 supply_id: "2".into(),
                 supplier_id: "1".into(),
@@ -365,15 +1053,160 @@ fn journal_service_test() {
                 unit_price: 200.0,
                 quantity: 10.0,
                 total_price: 2000.0,
+                tax_category: Some("standard".into()),
+                tax_included: Some(true),
+                tax_price: 181.0,
+                invoice_registered: Some(false),
+                lot_number: None,
+                expiry_date: None,
             },],
+            warnings: vec![],
         }]
     );
 
+    let result = service.record(RecordJournalCommand {
+        entry_date: 300000,
+        total_price: 1605.0,
+        records: vec![
+            JournalRecordDTO {
+                supply_id: "1".into(),
+                supplier_id: "1".into(),
+                supply_name: "SupplyA".into(),
+                supplier_name: "SupplierA".into(),
+                unit_name: "g".into(),
+                unit_price: 100.0,
+                quantity: 10.0,
+                total_price: 1000.0,
+                tax_category: None,
+                tax_included: None,
+                tax_price: 0.0,
+                invoice_registered: None,
+                lot_number: None,
+                expiry_date: None,
+            },
+            JournalRecordDTO {
+                supply_id: "2".into(),
+                supplier_id: "1".into(),
+                supply_name: "SupplyB".into(),
+                supplier_name: "SupplierA".into(),
+                unit_name: "g".into(),
+                unit_price: 120.5,
+                quantity: 5.0,
+                total_price: 600.0,
+                tax_category: None,
+                tax_included: None,
+                tax_price: 0.0,
+                invoice_registered: None,
+                lot_number: None,
+                expiry_date: None,
+            },
+        ],
+    });
+
+    assert!(matches!(
+        result,
+        Err(Error::LineTotalMismatch { line: 2, expected, actual, .. }) if expected == 603.0 && actual == 600.0
+    ));
+
+    // 2026-10-18 00:00 JST, half of the tax from unregistered suppliers is deductible
+    let journal = service
+        .record(RecordJournalCommand {
+            entry_date: 1_792_249_200_000,
+            total_price: 1100.0,
+            records: vec![JournalRecordDTO {
+                supply_id: "2".into(),
+                supplier_id: "1".into(),
+                supply_name: "SupplyB".into(),
+                supplier_name: "SupplierA".into(),
+                unit_name: "g".into(),
+                unit_price: 110.0,
+                quantity: 10.0,
+                total_price: 1100.0,
+                tax_category: None,
+                tax_included: None,
+                tax_price: 0.0,
+                invoice_registered: None,
+                lot_number: None,
+                expiry_date: None,
+            }],
+        })
+        .unwrap();
+
+    assert_eq!(journal.tax_price, 100.0);
+    assert_eq!(journal.deductible_tax_price, 50.0);
+    assert_eq!(journal.non_deductible_tax_price, 50.0);
+
+    service.delete(journal.id).unwrap();
     service.delete("1").unwrap();
 
     let journals = service.list().unwrap();
 
     assert!(journals.is_empty());
+
+    // prices deviating from the recent purchases are warned
+    let mut warnings = vec![];
+
+    for (entry_date, unit_price) in [(400000, 100.0), (500000, 100.0), (600000, 130.0)] {
+        let journal = service
+            .record(RecordJournalCommand {
+                entry_date,
+                total_price: unit_price * 10.0,
+                records: vec![JournalRecordDTO {
+                    supply_id: "1".into(),
+                    supplier_id: "1".into(),
+                    supply_name: "SupplyA".into(),
+                    supplier_name: "SupplierA".into(),
+                    unit_name: "g".into(),
+                    unit_price,
+                    quantity: 10.0,
+                    total_price: unit_price * 10.0,
+                    tax_category: None,
+                    tax_included: None,
+                    tax_price: 0.0,
+                    invoice_registered: None,
+                    lot_number: None,
+                    expiry_date: None,
+                }],
+            })
+            .unwrap();
+
+        warnings.push(journal.warnings);
+    }
+
+    assert!(warnings[0].is_empty());
+    assert!(warnings[1].is_empty());
+    assert_eq!(
+        warnings[2],
+        vec![PriceWarningDTO {
+            line: 1,
+            supply_id: "1".into(),
+            supply_name: "SupplyA".into(),
+            supplier_id: "1".into(),
+            unit_name: "g".into(),
+            unit_price: 130.0,
+            average_price: 100.0,
+            deviation_percent: 30.0,
+        }]
+    );
+
+    let histories = service
+        .price_history(PriceHistoryQuery {
+            supply_id: Some("1".into()),
+            supplier_id: None,
+            period_start: Some(500000),
+            period_end: None,
+        })
+        .unwrap();
+
+    assert_eq!(histories.len(), 1);
+    assert_eq!(
+        histories[0]
+            .prices
+            .iter()
+            .map(|point| (point.entry_date, point.unit_price))
+            .collect::<Vec<_>>(),
+        vec![(500000, 100.0), (600000, 130.0)]
+    );
 }
 
 #[test]
@@ -385,55 +1218,93 @@ fn stocktaking_service_test() {
     let stocktaking_repository = MockStocktakingRepository::new(Arc::clone(&storage));
 
     supplier_respository
-        .add(Supplier::restore(
-            SupplierId::new("1").unwrap(),
-            SupplierName::new("SupplierA").unwrap(),
-        ))
+        .add(
+            &StoreContext::default(),
+            Supplier::restore(
+                SupplierId::new("1").unwrap(),
+                SupplierName::new("SupplierA").unwrap(),
+                None,
+                false,
+            ),
+            None,
+        )
         .unwrap();
 
     supply_respository
-        .add(Supply::new(
-            SupplyId::new("1").unwrap(),
-            SupplyName::new("SupplyA").unwrap(),
-            UnitName::new("g").unwrap(),
-            SupplierId::new("1").unwrap(),
-        ))
+        .add(
+            &StoreContext::default(),
+            Supply::new(
+                SupplyId::new("1").unwrap(),
+                SupplyName::new("SupplyA").unwrap(),
+                UnitName::new("g").unwrap(),
+                SupplierId::new("1").unwrap(),
+                TaxCategory::Standard,
+                SupplyUnits::single(UnitId::new("1").unwrap()),
+                None,
+            ),
+            None,
+        )
         .unwrap();
 
     supply_respository
-        .add(Supply::new(
-            SupplyId::new("2").unwrap(),
-            SupplyName::new("SupplyB").unwrap(),
-            UnitName::new("g").unwrap(),
-            SupplierId::new("1").unwrap(),
-        ))
+        .add(
+            &StoreContext::default(),
+            Supply::new(
+                SupplyId::new("2").unwrap(),
+                SupplyName::new("SupplyB").unwrap(),
+                UnitName::new("g").unwrap(),
+                SupplierId::new("1").unwrap(),
+                TaxCategory::Standard,
+                SupplyUnits::single(UnitId::new("1").unwrap()),
+                None,
+            ),
+            None,
+        )
         .unwrap();
 
     let service = StocktakingService::new(
         Arc::new(supply_respository),
         Arc::new(stocktaking_repository),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStorageLocationRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        ValuationMethod::default(),
+        None,
+        StoreContext::default(),
     );
 
     service
         .record(RecordStocktakingCommand {
             stocktaken_date: 100000,
-            total_price: 3250.0,
+            total_price: Some(3250.0),
             records: vec![
                 StocktakingRecordDTO {
                     supply_id: "1".into(),
                     supply_name: "SupplyA".into(),
                     unit_name: "g".into(),
-                    unit_price: 100.0,
+                    unit_price: Some(100.0),
                     quantity: 10.0,
-                    total_price: 1000.0,
+                    total_price: Some(1000.0),
+                    valuation_method: None,
+                    location_id: None,
+                    location_name: None,
+                    lot_number: None,
                 },
                 StocktakingRecordDTO {
                     supply_id: "2".into(),
                     supply_name: "SupplyB".into(),
                     unit_name: "g".into(),
-                    unit_price: 150.0,
+                    unit_price: Some(150.0),
                     quantity: 15.0,
-                    total_price: 2250.0,
+                    total_price: Some(2250.0),
+                    valuation_method: None,
+                    location_id: None,
+                    location_name: None,
+                    lot_number: None,
                 },
             ],
         })
@@ -452,17 +1323,25 @@ fn stocktaking_service_test() {
                     supply_id: "1".into(),
                     supply_name: "SupplyA".into(),
                     unit_name: "g".into(),
-                    unit_price: 100.0,
+                    unit_price: Some(100.0),
                     quantity: 10.0,
-                    total_price: 1000.0,
+                    total_price: Some(1000.0),
+                    valuation_method: None,
+                    location_id: None,
+                    location_name: None,
+                    lot_number: None,
                 },
                 StocktakingRecordDTO {
                     supply_id: "2".into(),
                     supply_name: "SupplyB".into(),
                     unit_name: "g".into(),
-                    unit_price: 150.0,
+                    unit_price: Some(150.0),
                     quantity: 15.0,
-                    total_price: 2250.0,
+                    total_price: Some(2250.0),
+                    valuation_method: None,
+                    location_id: None,
+                    location_name: None,
+                    lot_number: None,
                 },
             ],
         }],
@@ -481,17 +1360,25 @@ fn stocktaking_service_test() {
                     supply_id: "1".into(),
                     supply_name: "SupplyA".into(),
                     unit_name: "g".into(),
-                    unit_price: 100.0,
+                    unit_price: Some(100.0),
                     quantity: 10.0,
-                    total_price: 1000.0,
+                    total_price: Some(1000.0),
+                    valuation_method: None,
+                    location_id: None,
+                    location_name: None,
+                    lot_number: None,
                 },
                 StocktakingRecordDTO {
                     supply_id: "2".into(),
                     supply_name: "SupplyB".into(),
                     unit_name: "g".into(),
-                    unit_price: 150.0,
+                    unit_price: Some(150.0),
                     quantity: 15.0,
-                    total_price: 2250.0,
+                    total_price: Some(2250.0),
+                    valuation_method: None,
+                    location_id: None,
+                    location_name: None,
+                    lot_number: None,
                 },
             ],
         })
@@ -500,14 +1387,18 @@ fn stocktaking_service_test() {
     service
         .edit(EditStocktakingCommand {
             stocktaking_id: "1".into(),
-            total_price: 750.0,
+            total_price: Some(750.0),
             records: vec![StocktakingRecordDTO {
                 supply_id: "1".into(),
                 supply_name: "SupplyA".into(),
                 unit_name: "kg".into(),
-                unit_price: 150.0,
+                unit_price: Some(150.0),
                 quantity: 5.0,
-                total_price: 750.0,
+                total_price: Some(750.0),
+                valuation_method: None,
+                location_id: None,
+                location_name: None,
+                lot_number: None,
             }],
         })
         .unwrap();
@@ -524,9 +1415,13 @@ fn stocktaking_service_test() {
                 supply_id: "1".into(),
                 supply_name: "SupplyA".into(),
                 unit_name: "kg".into(),
-                unit_price: 150.0,
+                unit_price: Some(150.0),
                 quantity: 5.0,
-                total_price: 750.0,
+                total_price: Some(750.0),
+                valuation_method: None,
+                location_id: None,
+                location_name: None,
+                lot_number: None,
             },],
         })
     );
@@ -535,6 +1430,7 @@ fn stocktaking_service_test() {
         .search(SearchStocktakingQuery {
             period_start: Some(100000),
             period_end: Some(250000),
+            category_id: None,
         })
         .unwrap();
 
@@ -548,16 +1444,2316 @@ fn stocktaking_service_test() {
                 supply_id: "1".into(),
                 supply_name: "SupplyA".into(),
                 unit_name: "kg".into(),
-                unit_price: 150.0,
+                unit_price: Some(150.0),
                 quantity: 5.0,
-                total_price: 750.0,
+                total_price: Some(750.0),
+                valuation_method: None,
+                location_id: None,
+                location_name: None,
+                lot_number: None,
             },],
         }]
     );
 
+    let result = service.edit(EditStocktakingCommand {
+        stocktaking_id: "1".into(),
+        total_price: Some(700.0),
+        records: vec![StocktakingRecordDTO {
+            supply_id: "1".into(),
+            supply_name: "SupplyA".into(),
+            unit_name: "kg".into(),
+            unit_price: Some(150.0),
+            quantity: 5.0,
+            total_price: Some(750.0),
+            valuation_method: None,
+            location_id: None,
+            location_name: None,
+            lot_number: None,
+        }],
+    });
+
+    assert!(matches!(
+        result,
+        Err(Error::TotalMismatch { expected, actual }) if expected == 750.0 && actual == 700.0
+    ));
+
     service.delete("1").unwrap();
 
     let stocktakings = service.list().unwrap();
 
     assert!(stocktakings.is_empty());
+
+    // unit prices not entered are derived from the purchases
+    MockUnitRepository::new(Arc::clone(&storage))
+        .add(Unit::restore(
+            UnitId::new("1").unwrap(),
+            UnitName::new("g").unwrap(),
+        ))
+        .unwrap();
+
+    let journal_service = JournalService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        PriceAlertRule::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    for (entry_date, total_price) in [(200000, 1000.0), (300000, 1400.0)] {
+        journal_service
+            .record(RecordJournalCommand {
+                entry_date,
+                total_price,
+                records: vec![JournalRecordDTO {
+                    supply_id: "1".into(),
+                    supply_name: "SupplyA".into(),
+                    supplier_id: "1".into(),
+                    supplier_name: "SupplierA".into(),
+                    unit_name: "g".into(),
+                    unit_price: total_price / 10.0,
+                    quantity: 10.0,
+                    total_price,
+                    tax_category: None,
+                    tax_included: None,
+                    tax_price: 0.0,
+                    invoice_registered: None,
+                    lot_number: None,
+                    expiry_date: None,
+                }],
+            })
+            .unwrap();
+    }
+
+    let derived_record = |supply_id: &str| StocktakingRecordDTO {
+        supply_id: supply_id.into(),
+        supply_name: format!("Supply{}", if supply_id == "1" { "A" } else { "B" }),
+        unit_name: "g".into(),
+        unit_price: None,
+        quantity: 15.0,
+        total_price: None,
+        valuation_method: None,
+        location_id: None,
+        location_name: None,
+        lot_number: None,
+    };
+
+    let stocktaking = service
+        .record(RecordStocktakingCommand {
+            stocktaken_date: 400000,
+            total_price: None,
+            records: vec![derived_record("1")],
+        })
+        .unwrap();
+
+    assert_eq!(stocktaking.total_price, 2100.0);
+    assert_eq!(stocktaking.records[0].unit_price, Some(140.0));
+    assert_eq!(
+        stocktaking.records[0].valuation_method,
+        Some("last_purchase_price".into())
+    );
+
+    let fifo_service = StocktakingService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStorageLocationRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        ValuationMethod::Fifo,
+        None,
+        StoreContext::default(),
+    );
+
+    // 10g at 140 and 5g at 100
+    fifo_service
+        .edit(EditStocktakingCommand {
+            stocktaking_id: stocktaking.id.clone(),
+            total_price: None,
+            records: vec![derived_record("1")],
+        })
+        .unwrap();
+
+    let stocktaking = fifo_service.get(&stocktaking.id).unwrap().unwrap();
+
+    assert_eq!(stocktaking.records[0].unit_price, Some(126.67));
+    assert_eq!(stocktaking.records[0].total_price, Some(1900.0));
+    assert_eq!(stocktaking.records[0].valuation_method, Some("fifo".into()));
+
+    // supplies never purchased cannot be valued
+    let result = service.record(RecordStocktakingCommand {
+        stocktaken_date: 400000,
+        total_price: None,
+        records: vec![derived_record("2")],
+    });
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn report_service_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    let supplier_respository = MockSupplierRepository::new(Arc::clone(&storage));
+    let supply_respository = MockSupplyRepository::new(Arc::clone(&storage));
+
+    for (id, name) in [("1", "SupplierA"), ("2", "SupplierB")] {
+        supplier_respository
+            .add(
+                &StoreContext::default(),
+                Supplier::restore(
+                    SupplierId::new(id).unwrap(),
+                    SupplierName::new(name).unwrap(),
+                    None,
+                    false,
+                ),
+                None,
+            )
+            .unwrap();
+
+        supply_respository
+            .add(
+                &StoreContext::default(),
+                Supply::new(
+                    SupplyId::new(id).unwrap(),
+                    SupplyName::new(format!("Supply{}", id)).unwrap(),
+                    UnitName::new("g").unwrap(),
+                    SupplierId::new(id).unwrap(),
+                    TaxCategory::Standard,
+                    SupplyUnits::single(UnitId::new("1").unwrap()),
+                    None,
+                ),
+                None,
+            )
+            .unwrap();
+    }
+
+    let stocktaking_service = StocktakingService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStorageLocationRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        ValuationMethod::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    let journal_service = JournalService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        PriceAlertRule::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    let store_repository = MockStoreRepository::new(Arc::clone(&storage));
+
+    store_repository
+        .add(Store::restore(
+            StoreId::default(),
+            StoreName::new("本店").unwrap(),
+        ))
+        .unwrap();
+
+    let service = ReportService::new(
+        Arc::new(store_repository),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockTransferRepository::new(Arc::clone(&storage))),
+    );
+
+    let stocktaking_record = |id: &str, total_price: f64| StocktakingRecordDTO {
+        supply_id: id.into(),
+        supply_name: format!("Supply{}", id),
+        unit_name: "g".into(),
+        unit_price: Some(total_price),
+        quantity: 1.0,
+        total_price: Some(total_price),
+        valuation_method: None,
+        location_id: None,
+        location_name: None,
+        lot_number: None,
+    };
+
+    let journal_record = |id: &str, supplier_name: &str, total_price: f64| JournalRecordDTO {
+        supply_id: id.into(),
+        supply_name: format!("Supply{}", id),
+        supplier_id: id.into(),
+        supplier_name: supplier_name.into(),
+        unit_name: "g".into(),
+        unit_price: total_price,
+        quantity: 1.0,
+        total_price,
+        tax_category: None,
+        tax_included: None,
+        tax_price: 0.0,
+        invoice_registered: None,
+        lot_number: None,
+        expiry_date: None,
+    };
+
+    // opening stocktaking before the period
+    stocktaking_service
+        .record(RecordStocktakingCommand {
+            stocktaken_date: 1000,
+            total_price: Some(1500.0),
+            records: vec![
+                stocktaking_record("1", 1000.0),
+                stocktaking_record("2", 500.0),
+            ],
+        })
+        .unwrap();
+
+    // purchases after the opening stocktaking are counted even before the period
+    journal_service
+        .record(RecordJournalCommand {
+            entry_date: 1500,
+            total_price: 200.0,
+            records: vec![journal_record("1", "SupplierA", 200.0)],
+        })
+        .unwrap();
+
+    journal_service
+        .record(RecordJournalCommand {
+            entry_date: 2500,
+            total_price: 5000.0,
+            records: vec![
+                journal_record("1", "SupplierA", 3000.0),
+                journal_record("2", "SupplierB", 2000.0),
+            ],
+        })
+        .unwrap();
+
+    // purchases after the closing stocktaking are not counted
+    journal_service
+        .record(RecordJournalCommand {
+            entry_date: 4500,
+            total_price: 100.0,
+            records: vec![journal_record("1", "SupplierA", 100.0)],
+        })
+        .unwrap();
+
+    // closing stocktaking in the period
+    stocktaking_service
+        .record(RecordStocktakingCommand {
+            stocktaken_date: 3000,
+            total_price: Some(2200.0),
+            records: vec![
+                stocktaking_record("1", 1500.0),
+                stocktaking_record("2", 700.0),
+            ],
+        })
+        .unwrap();
+
+    let report = service
+        .cost_of_goods_sold(CostOfGoodsSoldQuery {
+            period_start: 2000,
+            period_end: 4000,
+            store_id: None,
+        })
+        .unwrap();
+
+    assert_eq!(
+        report,
+        CostOfGoodsSoldDTO {
+            period_start: 2000,
+            period_end: 4000,
+            opening_stocktaking_id: Some("1".into()),
+            closing_stocktaking_id: Some("2".into()),
+            opening_inventory: 1500.0,
+            purchases: 5200.0,
+            transfers: 0.0,
+            closing_inventory: 2200.0,
+            cost_of_goods_sold: 4500.0,
+            suppliers: vec![
+                SupplierCostOfGoodsSoldDTO {
+                    supplier_id: Some("1".into()),
+                    supplier_name: Some("SupplierA".into()),
+                    opening_inventory: 1000.0,
+                    purchases: 3200.0,
+                    transfers: 0.0,
+                    closing_inventory: 1500.0,
+                    cost_of_goods_sold: 2700.0,
+                },
+                SupplierCostOfGoodsSoldDTO {
+                    supplier_id: Some("2".into()),
+                    supplier_name: Some("SupplierB".into()),
+                    opening_inventory: 500.0,
+                    purchases: 2000.0,
+                    transfers: 0.0,
+                    closing_inventory: 700.0,
+                    cost_of_goods_sold: 1800.0,
+                },
+            ],
+            supplies: vec![
+                SupplyCostOfGoodsSoldDTO {
+                    supply_id: "1".into(),
+                    supply_name: "Supply1".into(),
+                    supplier_id: Some("1".into()),
+                    opening_inventory: 1000.0,
+                    purchases: 3200.0,
+                    transfers: 0.0,
+                    closing_inventory: 1500.0,
+                    cost_of_goods_sold: 2700.0,
+                },
+                SupplyCostOfGoodsSoldDTO {
+                    supply_id: "2".into(),
+                    supply_name: "Supply2".into(),
+                    supplier_id: Some("2".into()),
+                    opening_inventory: 500.0,
+                    purchases: 2000.0,
+                    transfers: 0.0,
+                    closing_inventory: 700.0,
+                    cost_of_goods_sold: 1800.0,
+                },
+            ],
+            stores: vec![StoreCostOfGoodsSoldDTO {
+                store_id: "1".into(),
+                store_name: "本店".into(),
+                opening_stocktaking_id: Some("1".into()),
+                closing_stocktaking_id: "2".into(),
+                opening_inventory: 1500.0,
+                purchases: 5200.0,
+                transfers: 0.0,
+                closing_inventory: 2200.0,
+                cost_of_goods_sold: 4500.0,
+            }],
+        }
+    );
+
+    // the period has no closing stocktaking
+    let result = service.cost_of_goods_sold(CostOfGoodsSoldQuery {
+        period_start: 4000,
+        period_end: 5000,
+        store_id: None,
+    });
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn report_supplier_cost_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    let supplier_respository = MockSupplierRepository::new(Arc::clone(&storage));
+
+    for (id, name) in [("1", "SupplierA"), ("2", "SupplierB")] {
+        supplier_respository
+            .add(
+                &StoreContext::default(),
+                Supplier::restore(
+                    SupplierId::new(id).unwrap(),
+                    SupplierName::new(name).unwrap(),
+                    None,
+                    false,
+                ),
+                None,
+            )
+            .unwrap();
+    }
+
+    // both suppliers offer the supply, SupplierB is preferred
+    let mut supply = Supply::new(
+        SupplyId::new("1").unwrap(),
+        SupplyName::new("Supply1").unwrap(),
+        UnitName::new("g").unwrap(),
+        SupplierId::new("1").unwrap(),
+        TaxCategory::Standard,
+        SupplyUnits::single(UnitId::new("1").unwrap()),
+        None,
+    );
+    supply.change_supplier(SupplierId::new("2").unwrap());
+
+    MockSupplyRepository::new(Arc::clone(&storage))
+        .add(&StoreContext::default(), supply, None)
+        .unwrap();
+
+    MockStoreRepository::new(Arc::clone(&storage))
+        .add(Store::restore(
+            StoreId::default(),
+            StoreName::new("本店").unwrap(),
+        ))
+        .unwrap();
+
+    let stocktaking_service = StocktakingService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStorageLocationRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        ValuationMethod::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    let journal_service = JournalService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        PriceAlertRule::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    let service = ReportService::new(
+        Arc::new(MockStoreRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockTransferRepository::new(Arc::clone(&storage))),
+    );
+
+    let stocktaking = |stocktaken_date: i64, total_price: f64| RecordStocktakingCommand {
+        stocktaken_date,
+        total_price: Some(total_price),
+        records: vec![StocktakingRecordDTO {
+            supply_id: "1".into(),
+            supply_name: "Supply1".into(),
+            unit_name: "g".into(),
+            unit_price: Some(total_price),
+            quantity: 1.0,
+            total_price: Some(total_price),
+            valuation_method: None,
+            location_id: None,
+            location_name: None,
+            lot_number: None,
+        }],
+    };
+
+    let journal = |entry_date: i64, supplier_id: &str, supplier_name: &str, total_price: f64| {
+        RecordJournalCommand {
+            entry_date,
+            total_price,
+            records: vec![JournalRecordDTO {
+                supply_id: "1".into(),
+                supply_name: "Supply1".into(),
+                supplier_id: supplier_id.into(),
+                supplier_name: supplier_name.into(),
+                unit_name: "g".into(),
+                unit_price: total_price,
+                quantity: 1.0,
+                total_price,
+                tax_category: None,
+                tax_included: None,
+                tax_price: 0.0,
+                invoice_registered: None,
+                lot_number: None,
+                expiry_date: None,
+            }],
+        }
+    };
+
+    stocktaking_service
+        .record(stocktaking(1000, 1000.0))
+        .unwrap();
+    journal_service
+        .record(journal(2000, "2", "SupplierB", 3000.0))
+        .unwrap();
+    journal_service
+        .record(journal(2500, "1", "SupplierA", 500.0))
+        .unwrap();
+    stocktaking_service
+        .record(stocktaking(3000, 1200.0))
+        .unwrap();
+
+    let report = service
+        .cost_of_goods_sold(CostOfGoodsSoldQuery {
+            period_start: 2000,
+            period_end: 4000,
+            store_id: None,
+        })
+        .unwrap();
+
+    // purchases go to the supplier of each line, inventories to the one last bought from
+    assert_eq!(
+        report.suppliers,
+        vec![
+            SupplierCostOfGoodsSoldDTO {
+                supplier_id: Some("1".into()),
+                supplier_name: Some("SupplierA".into()),
+                opening_inventory: 1000.0,
+                purchases: 500.0,
+                transfers: 0.0,
+                closing_inventory: 1200.0,
+                cost_of_goods_sold: 300.0,
+            },
+            SupplierCostOfGoodsSoldDTO {
+                supplier_id: Some("2".into()),
+                supplier_name: Some("SupplierB".into()),
+                opening_inventory: 0.0,
+                purchases: 3000.0,
+                transfers: 0.0,
+                closing_inventory: 0.0,
+                cost_of_goods_sold: 3000.0,
+            },
+        ]
+    );
+    assert_eq!(report.cost_of_goods_sold, 3300.0);
+}
+
+#[test]
+fn stock_ledger_service_test() {
+    const DAY: i64 = 24 * 60 * 60 * 1000;
+
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    MockSupplierRepository::new(Arc::clone(&storage))
+        .add(
+            &StoreContext::default(),
+            Supplier::restore(
+                SupplierId::new("1").unwrap(),
+                SupplierName::new("SupplierA").unwrap(),
+                None,
+                false,
+            ),
+            None,
+        )
+        .unwrap();
+
+    MockUnitRepository::new(Arc::clone(&storage))
+        .add(Unit::restore(
+            UnitId::new("1").unwrap(),
+            UnitName::new("g").unwrap(),
+        ))
+        .unwrap();
+
+    MockSupplyRepository::new(Arc::clone(&storage))
+        .add(
+            &StoreContext::default(),
+            Supply::new(
+                SupplyId::new("1").unwrap(),
+                SupplyName::new("SupplyA").unwrap(),
+                UnitName::new("g").unwrap(),
+                SupplierId::new("1").unwrap(),
+                TaxCategory::Standard,
+                SupplyUnits::single(UnitId::new("1").unwrap()),
+                None,
+            ),
+            None,
+        )
+        .unwrap();
+
+    let stocktaking_service = StocktakingService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStorageLocationRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        ValuationMethod::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    let journal_service = JournalService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        PriceAlertRule::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    let consumption_service = ConsumptionService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockConsumptionRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        StoreContext::default(),
+    );
+
+    let service = StockLedgerService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockConsumptionRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockTransferRepository::new(Arc::clone(&storage))),
+        StoreContext::default(),
+    );
+
+    let stocktaking_record = |quantity: f64, unit_price: f64| StocktakingRecordDTO {
+        supply_id: "1".into(),
+        supply_name: "SupplyA".into(),
+        unit_name: "g".into(),
+        unit_price: Some(unit_price),
+        quantity,
+        total_price: None,
+        valuation_method: None,
+        location_id: None,
+        location_name: None,
+        lot_number: None,
+    };
+
+    stocktaking_service
+        .record(RecordStocktakingCommand {
+            stocktaken_date: 1000,
+            total_price: None,
+            records: vec![stocktaking_record(10.0, 100.0)],
+        })
+        .unwrap();
+
+    journal_service
+        .record(RecordJournalCommand {
+            entry_date: 2000,
+            total_price: 2400.0,
+            records: vec![JournalRecordDTO {
+                supply_id: "1".into(),
+                supply_name: "SupplyA".into(),
+                supplier_id: "1".into(),
+                supplier_name: "SupplierA".into(),
+                unit_name: "g".into(),
+                unit_price: 120.0,
+                quantity: 20.0,
+                total_price: 2400.0,
+                tax_category: None,
+                tax_included: None,
+                tax_price: 0.0,
+                invoice_registered: None,
+                lot_number: Some("L1".into()),
+                expiry_date: Some(2000 + 3 * DAY),
+            }],
+        })
+        .unwrap();
+
+    consumption_service
+        .record(RecordConsumptionCommand {
+            consumed_date: 2500,
+            records: vec![ConsumptionRecordDTO {
+                supply_id: "1".into(),
+                supply_name: "SupplyA".into(),
+                unit_name: "g".into(),
+                quantity: 15.0,
+            }],
+        })
+        .unwrap();
+
+    let result = consumption_service.record(RecordConsumptionCommand {
+        consumed_date: 2500,
+        records: vec![ConsumptionRecordDTO {
+            supply_id: "1".into(),
+            supply_name: "SupplyA".into(),
+            unit_name: "kg".into(),
+            quantity: 1.0,
+        }],
+    });
+
+    assert!(result.is_err());
+
+    let on_hand = service.on_hand(StockLedgerQuery { at: 3000 }).unwrap();
+
+    assert_eq!(
+        on_hand,
+        vec![StockLedgerDTO {
+            supply_id: "1".into(),
+            supply_name: "SupplyA".into(),
+            unit_name: "g".into(),
+            opening_stocktaking_id: Some("1".into()),
+            opening_quantity: 10.0,
+            purchased_quantity: 20.0,
+            transferred_quantity: 0.0,
+            consumed_quantity: 15.0,
+            quantity: 15.0,
+        }]
+    );
+
+    let lots = service
+        .expiring_lots(ExpiringLotsQuery {
+            at: 3000,
+            within_days: 7,
+        })
+        .unwrap();
+
+    assert_eq!(
+        lots,
+        vec![ExpiringLotDTO {
+            supply_id: "1".into(),
+            supply_name: "SupplyA".into(),
+            lot_number: Some("L1".into()),
+            expiry_date: 2000 + 3 * DAY,
+            journal_id: "1".into(),
+            received_date: 2000,
+            stocktaking_id: None,
+            unit_name: "g".into(),
+            quantity: 20.0,
+        }]
+    );
+
+    let lots = service
+        .expiring_lots(ExpiringLotsQuery {
+            at: 3000,
+            within_days: 1,
+        })
+        .unwrap();
+
+    assert!(lots.is_empty());
+
+    stocktaking_service
+        .record(RecordStocktakingCommand {
+            stocktaken_date: 4000,
+            total_price: None,
+            records: vec![StocktakingRecordDTO {
+                lot_number: Some("L1".into()),
+                ..stocktaking_record(12.0, 120.0)
+            }],
+        })
+        .unwrap();
+
+    let report = service
+        .variance(StockVarianceQuery {
+            stocktaking_id: "2".into(),
+        })
+        .unwrap();
+
+    assert_eq!(
+        report,
+        StockVarianceReportDTO {
+            stocktaking_id: "2".into(),
+            stocktaken_date: 4000,
+            expected_value: 1800.0,
+            counted_value: 1440.0,
+            variance_value: -360.0,
+            supplies: vec![SupplyVarianceDTO {
+                supply_id: "1".into(),
+                supply_name: "SupplyA".into(),
+                unit_name: "g".into(),
+                expected_quantity: 15.0,
+                counted_quantity: 12.0,
+                variance_quantity: -3.0,
+                unit_price: 120.0,
+                expected_value: 1800.0,
+                counted_value: 1440.0,
+                variance_value: -360.0,
+            }],
+        }
+    );
+
+    // the ledger starts over from the latest stocktaking
+    let on_hand = service.on_hand(StockLedgerQuery { at: 5000 }).unwrap();
+
+    assert_eq!(on_hand[0].opening_stocktaking_id, Some("2".into()));
+    assert_eq!(on_hand[0].quantity, 12.0);
+
+    // lots received before the latest stocktaking are on hand as counted
+    let lots = service
+        .expiring_lots(ExpiringLotsQuery {
+            at: 5000,
+            within_days: 7,
+        })
+        .unwrap();
+
+    assert_eq!(lots.len(), 1);
+    assert_eq!(lots[0].stocktaking_id, Some("2".into()));
+    assert_eq!(lots[0].quantity, 12.0);
+}
+
+#[test]
+fn category_service_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    let service = CategoryService::new(
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+    );
+
+    let food = service
+        .register(RegisterCategoryCommand {
+            category_name: "食材".into(),
+            parent_id: None,
+        })
+        .unwrap();
+
+    let drinks = service
+        .register(RegisterCategoryCommand {
+            category_name: "飲料".into(),
+            parent_id: None,
+        })
+        .unwrap();
+
+    let vegetables = service
+        .register(RegisterCategoryCommand {
+            category_name: "野菜".into(),
+            parent_id: Some(food.id.clone()),
+        })
+        .unwrap();
+
+    // parents are listed before their children
+    assert_eq!(
+        service.list().unwrap(),
+        vec![food.clone(), vegetables.clone(), drinks.clone()]
+    );
+
+    let result = service.update(UpdateCategoryCommand {
+        category_id: food.id.clone(),
+        category_name: "食材".into(),
+        parent_id: Some(vegetables.id.clone()),
+    });
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    MockSupplierRepository::new(Arc::clone(&storage))
+        .add(
+            &StoreContext::default(),
+            Supplier::restore(
+                SupplierId::new("1").unwrap(),
+                SupplierName::new("SupplierA").unwrap(),
+                None,
+                false,
+            ),
+            None,
+        )
+        .unwrap();
+
+    MockUnitRepository::new(Arc::clone(&storage))
+        .add(Unit::restore(
+            UnitId::new("1").unwrap(),
+            UnitName::new("g").unwrap(),
+        ))
+        .unwrap();
+
+    for (supply_id, category_id) in [("1", &vegetables.id), ("2", &drinks.id)] {
+        MockSupplyRepository::new(Arc::clone(&storage))
+            .add(
+                &StoreContext::default(),
+                Supply::new(
+                    SupplyId::new(supply_id).unwrap(),
+                    SupplyName::new(format!("Supply{}", supply_id)).unwrap(),
+                    UnitName::new("g").unwrap(),
+                    SupplierId::new("1").unwrap(),
+                    TaxCategory::Standard,
+                    SupplyUnits::single(UnitId::new("1").unwrap()),
+                    Some(CategoryId::new(category_id).unwrap()),
+                ),
+                None,
+            )
+            .unwrap();
+    }
+
+    let result = service.delete(&vegetables.id);
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    let journal_service = JournalService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        PriceAlertRule::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    let journal_record = |supply_id: &str, total_price: f64| JournalRecordDTO {
+        supply_id: supply_id.into(),
+        supply_name: format!("Supply{}", supply_id),
+        supplier_id: "1".into(),
+        supplier_name: "SupplierA".into(),
+        unit_name: "g".into(),
+        unit_price: total_price / 10.0,
+        quantity: 10.0,
+        total_price,
+        tax_category: None,
+        tax_included: None,
+        tax_price: 0.0,
+        invoice_registered: None,
+        lot_number: None,
+        expiry_date: None,
+    };
+
+    journal_service
+        .record(RecordJournalCommand {
+            entry_date: 100000,
+            total_price: 1500.0,
+            records: vec![journal_record("1", 1000.0), journal_record("2", 500.0)],
+        })
+        .unwrap();
+
+    let query = |category_id: Option<&String>| SearchJournalsQuery {
+        period_start: None,
+        period_end: None,
+        supplier_name: None,
+        supply_name: None,
+        category_id: category_id.cloned(),
+    };
+
+    // lines of supplies in descendants are included
+    let journals = journal_service.search(query(Some(&food.id))).unwrap();
+
+    assert_eq!(journals.len(), 1);
+    assert_eq!(journals[0].records.len(), 1);
+    assert_eq!(journals[0].records[0].supply_id, "1");
+
+    let totals = journal_service.totals_by_category(query(None)).unwrap();
+
+    assert_eq!(
+        totals
+            .iter()
+            .map(|total| (total.category_name.clone().unwrap(), total.total_price))
+            .collect::<Vec<_>>(),
+        vec![
+            ("食材".to_string(), 1000.0),
+            ("野菜".to_string(), 1000.0),
+            ("飲料".to_string(), 500.0),
+        ]
+    );
+
+    let supplier_service = SupplierService::new(
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        None,
+        StoreContext::default(),
+    );
+
+    let suppliers = supplier_service
+        .search(SearchSuppliersQuery {
+            supplier_name: None,
+            supply_name: None,
+            contact_person: None,
+            phone_number: None,
+            category_id: Some(drinks.id.clone()),
+        })
+        .unwrap();
+
+    assert_eq!(suppliers.len(), 1);
+}
+
+#[test]
+fn storage_location_service_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    let service = StorageLocationService::new(
+        Arc::new(MockStorageLocationRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        None,
+        StoreContext::default(),
+    );
+
+    let freezer = service
+        .register(RegisterStorageLocationCommand {
+            location_name: "冷凍庫".into(),
+            sort_order: 2,
+        })
+        .unwrap();
+
+    let fridge = service
+        .register(RegisterStorageLocationCommand {
+            location_name: "冷蔵庫".into(),
+            sort_order: 1,
+        })
+        .unwrap();
+
+    assert_eq!(
+        service.list().unwrap(),
+        vec![fridge.clone(), freezer.clone()]
+    );
+
+    MockUnitRepository::new(Arc::clone(&storage))
+        .add(Unit::restore(
+            UnitId::new("1").unwrap(),
+            UnitName::new("g").unwrap(),
+        ))
+        .unwrap();
+
+    for supply_id in ["1", "2", "3", "4"] {
+        MockSupplyRepository::new(Arc::clone(&storage))
+            .add(
+                &StoreContext::default(),
+                Supply::new(
+                    SupplyId::new(supply_id).unwrap(),
+                    SupplyName::new(format!("Supply{}", supply_id)).unwrap(),
+                    UnitName::new("g").unwrap(),
+                    SupplierId::new("1").unwrap(),
+                    TaxCategory::Standard,
+                    SupplyUnits::single(UnitId::new("1").unwrap()),
+                    None,
+                ),
+                None,
+            )
+            .unwrap();
+    }
+
+    for (supply_id, location_id, sort_order) in [
+        ("1", &freezer.id, 1),
+        ("2", &fridge.id, 2),
+        ("3", &fridge.id, 1),
+    ] {
+        service
+            .place_supply(PlaceSupplyCommand {
+                supply_id: supply_id.into(),
+                location_id: Some(location_id.clone()),
+                sort_order,
+            })
+            .unwrap();
+    }
+
+    // placements are logged as updates of the supplies
+    let entries = MockAuditRepository::new(Arc::clone(&storage))
+        .find(
+            &StoreContext::default(),
+            FindAuditEntriesQuery {
+                entity: Some(AuditEntity::Supply),
+                entity_id: Some("3".into()),
+                period_start: None,
+                period_end: None,
+                operator: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].change().operation(), AuditOperation::Update);
+    assert!(entries[0]
+        .change()
+        .after()
+        .unwrap()
+        .as_str()
+        .contains(&format!(r#""location_id":"{}""#, fridge.id)));
+
+    // and published as updates of the supplies
+    assert_eq!(
+        MockEventPublisher::new(Arc::clone(&storage)).published(),
+        ["1", "2", "3"]
+            .into_iter()
+            .map(|supply_id| DomainEvent::SupplyUpdated {
+                supply_id: SupplyId::new(supply_id).unwrap()
+            })
+            .collect::<Vec<_>>()
+    );
+
+    let result = service.place_supply(PlaceSupplyCommand {
+        supply_id: "4".into(),
+        location_id: Some("99".into()),
+        sort_order: 0,
+    });
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    let result = service.delete(&fridge.id);
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    let stocktaking_service = StocktakingService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStorageLocationRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        ValuationMethod::default(),
+        None,
+        StoreContext::default(),
+    );
+
+    // count sheets follow the locations, unplaced supplies last
+    let sheets = stocktaking_service.count_sheet().unwrap();
+
+    assert_eq!(
+        sheets
+            .iter()
+            .map(|sheet| (
+                sheet.location_name.clone(),
+                sheet
+                    .lines
+                    .iter()
+                    .map(|line| line.supply_id.clone())
+                    .collect::<Vec<String>>()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (Some("冷蔵庫".to_string()), vec!["3".into(), "2".into()]),
+            (Some("冷凍庫".to_string()), vec!["1".into()]),
+            (None, vec!["4".into()]),
+        ]
+    );
+
+    let record = |supply_id: &str, location_id: Option<String>| StocktakingRecordDTO {
+        supply_id: supply_id.into(),
+        supply_name: format!("Supply{}", supply_id),
+        unit_name: "g".into(),
+        unit_price: Some(100.0),
+        quantity: 1.0,
+        total_price: None,
+        valuation_method: None,
+        location_id,
+        location_name: None,
+        lot_number: None,
+    };
+
+    // the same supply can be counted in several locations
+    let stocktaking = stocktaking_service
+        .record(RecordStocktakingCommand {
+            stocktaken_date: 100000,
+            total_price: None,
+            records: vec![
+                record("2", Some(freezer.id.clone())),
+                record("1", None),
+                record("2", None),
+            ],
+        })
+        .unwrap();
+
+    assert_eq!(
+        stocktaking
+            .records
+            .iter()
+            .map(|record| (record.supply_id.as_str(), record.location_name.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("2", Some("冷蔵庫")),
+            ("1", Some("冷凍庫")),
+            ("2", Some("冷凍庫")),
+        ]
+    );
+    assert_eq!(stocktaking.total_price, 300.0);
+}
+
+#[test]
+fn store_service_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    let service = StoreService::new(
+        Arc::new(MockStoreRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockTransferRepository::new(Arc::clone(&storage))),
+        Arc::new(MockPurchaseOrderRepository::new(Arc::clone(&storage))),
+        Arc::new(MockConsumptionRepository::new(Arc::clone(&storage))),
+        Arc::new(MockWasteRepository::new(Arc::clone(&storage))),
+    );
+
+    let main = service
+        .register(RegisterStoreCommand {
+            store_name: "本店".into(),
+        })
+        .unwrap();
+
+    let branch = service
+        .register(RegisterStoreCommand {
+            store_name: "駅前店".into(),
+        })
+        .unwrap();
+
+    assert_eq!(main.id, StoreId::DEFAULT);
+
+    let supplier_service = |store: StoreContext| {
+        SupplierService::new(
+            Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+            Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+            Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+            Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+            None,
+            store,
+        )
+    };
+
+    let register = |store: StoreContext, supplier_name: &str| {
+        supplier_service(store).register(RegisterSupplierCommand {
+            supplier_name: supplier_name.into(),
+            invoice_registration_number: None,
+            invoice_registered: false,
+            contact: SupplierContactDTO::default(),
+            notes: None,
+            billing_terms: None,
+        })
+    };
+
+    let branch_store = StoreContext::Store(StoreId::new(&branch.id).unwrap());
+
+    let supplier_a = register(StoreContext::default(), "SupplierA").unwrap();
+    let supplier_b = register(branch_store.clone(), "SupplierB").unwrap();
+
+    // each store only reads its own suppliers
+    assert_eq!(
+        supplier_service(StoreContext::default()).list().unwrap(),
+        vec![supplier_a.clone()]
+    );
+    assert_eq!(
+        supplier_service(branch_store).list().unwrap(),
+        vec![supplier_b.clone()]
+    );
+    assert_eq!(
+        supplier_service(StoreContext::Consolidated).list().unwrap(),
+        vec![supplier_a.clone(), supplier_b]
+    );
+
+    // nothing is recorded without a store
+    assert!(register(StoreContext::Consolidated, "SupplierC").is_err());
+
+    // nor is a deleted supplier restored or purged
+    supplier_service(StoreContext::default())
+        .delete(&supplier_a.id)
+        .unwrap();
+
+    assert!(supplier_service(StoreContext::Consolidated)
+        .restore(&supplier_a.id)
+        .is_err());
+    assert!(supplier_service(StoreContext::Consolidated)
+        .purge(&supplier_a.id)
+        .is_err());
+
+    supplier_service(StoreContext::default())
+        .purge(&supplier_a.id)
+        .unwrap();
+
+    // the default store and stores with data are kept
+    assert!(service.delete(&main.id).is_err());
+    assert!(service.delete(&branch.id).is_err());
+
+    let kitchen = service
+        .register(RegisterStoreCommand {
+            store_name: "セントラルキッチン".into(),
+        })
+        .unwrap();
+
+    MockConsumptionRepository::new(Arc::clone(&storage))
+        .add(
+            &StoreContext::Store(StoreId::new(&kitchen.id).unwrap()),
+            Consumption::restore(
+                ConsumptionId::new("1").unwrap(),
+                ConsumedDateTime::new(100000),
+                vec![],
+            ),
+        )
+        .unwrap();
+
+    assert!(service.delete(&kitchen.id).is_err());
+
+    let warehouse = service
+        .register(RegisterStoreCommand {
+            store_name: "倉庫".into(),
+        })
+        .unwrap();
+
+    service.delete(&warehouse.id).unwrap();
+
+    assert_eq!(service.list().unwrap(), vec![main, branch, kitchen]);
+}
+
+#[test]
+fn journal_store_scope_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    MockSupplierRepository::new(Arc::clone(&storage))
+        .add(
+            &StoreContext::default(),
+            Supplier::restore(
+                SupplierId::new("1").unwrap(),
+                SupplierName::new("SupplierA").unwrap(),
+                None,
+                false,
+            ),
+            None,
+        )
+        .unwrap();
+
+    MockSupplyRepository::new(Arc::clone(&storage))
+        .add(
+            &StoreContext::default(),
+            Supply::new(
+                SupplyId::new("1").unwrap(),
+                SupplyName::new("SupplyA").unwrap(),
+                UnitName::new("g").unwrap(),
+                SupplierId::new("1").unwrap(),
+                TaxCategory::Reduced,
+                SupplyUnits::single(UnitId::new("1").unwrap()),
+                None,
+            ),
+            None,
+        )
+        .unwrap();
+
+    let journal_service = |store: StoreContext| {
+        JournalService::new(
+            Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+            Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+            Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+            Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+            Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+            Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+            Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+            RoundingPolicy::default(),
+            PriceAlertRule::default(),
+            None,
+            store,
+        )
+    };
+
+    let record = JournalRecordDTO {
+        supply_id: "1".into(),
+        supplier_id: "1".into(),
+        supply_name: "SupplyA".into(),
+        supplier_name: "SupplierA".into(),
+        unit_name: "g".into(),
+        unit_price: 100.0,
+        quantity: 10.0,
+        total_price: 1000.0,
+        tax_category: None,
+        tax_included: None,
+        tax_price: 0.0,
+        invoice_registered: None,
+        lot_number: None,
+        expiry_date: None,
+    };
+
+    let journal = journal_service(StoreContext::default())
+        .record(RecordJournalCommand {
+            entry_date: 100000,
+            total_price: 1000.0,
+            records: vec![record.clone()],
+        })
+        .unwrap();
+
+    let branch_service = journal_service(StoreContext::Store(StoreId::new("2").unwrap()));
+
+    // another store neither reads nor changes the journal
+    assert_eq!(branch_service.get(&journal.id).unwrap(), None);
+    assert!(branch_service
+        .edit(EditJournalCommand {
+            journal_id: journal.id.clone(),
+            total_price: 2000.0,
+            records: vec![JournalRecordDTO {
+                quantity: 20.0,
+                total_price: 2000.0,
+                ..record
+            }],
+        })
+        .is_err());
+    assert!(branch_service.revert(&journal.id, 1).is_err());
+
+    branch_service.delete(&journal.id).unwrap();
+
+    assert_eq!(
+        journal_service(StoreContext::default())
+            .get(&journal.id)
+            .unwrap(),
+        Some(journal)
+    );
+}
+
+#[test]
+fn purchase_order_service_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    MockSupplierRepository::new(Arc::clone(&storage))
+        .add(
+            &StoreContext::default(),
+            Supplier::restore(
+                SupplierId::new("1").unwrap(),
+                SupplierName::new("SupplierA").unwrap(),
+                None,
+                false,
+            ),
+            None,
+        )
+        .unwrap();
+
+    MockUnitRepository::new(Arc::clone(&storage))
+        .add(Unit::restore(
+            UnitId::new("1").unwrap(),
+            UnitName::new("kg").unwrap(),
+        ))
+        .unwrap();
+
+    for supply_id in ["1", "2"] {
+        MockSupplyRepository::new(Arc::clone(&storage))
+            .add(
+                &StoreContext::default(),
+                Supply::new(
+                    SupplyId::new(supply_id).unwrap(),
+                    SupplyName::new(format!("Supply{}", supply_id)).unwrap(),
+                    UnitName::new("kg").unwrap(),
+                    SupplierId::new("1").unwrap(),
+                    TaxCategory::Standard,
+                    SupplyUnits::single(UnitId::new("1").unwrap()),
+                    None,
+                ),
+                None,
+            )
+            .unwrap();
+    }
+
+    let journal_service = || {
+        JournalService::new(
+            Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+            Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+            Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+            Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+            Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+            Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+            Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+            RoundingPolicy::default(),
+            PriceAlertRule::default(),
+            None,
+            StoreContext::default(),
+        )
+    };
+
+    let service = PurchaseOrderService::new(
+        Arc::new(MockPurchaseOrderRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        journal_service(),
+        RoundingPolicy::default(),
+        StoreContext::default(),
+    );
+
+    let line = |supply_id: &str, quantity: f64, expected_unit_price: f64| PurchaseOrderLineDTO {
+        supply_id: supply_id.into(),
+        supply_name: String::new(),
+        unit_name: String::new(),
+        quantity,
+        expected_unit_price,
+        received_quantity: 0.0,
+    };
+
+    let order = service
+        .draft(DraftPurchaseOrderCommand {
+            supplier_id: "1".into(),
+            ordered_date: 1000,
+            lines: vec![line("1", 10.0, 100.0), line("2", 5.0, 200.0)],
+        })
+        .unwrap();
+
+    assert_eq!(order.status, "draft");
+    assert_eq!(order.supplier_name, "SupplierA");
+    assert_eq!(order.lines[0].supply_name, "Supply1");
+    assert_eq!(order.expected_total_price, 2000.0);
+
+    let receive =
+        |journal_id: Option<String>, lines: Vec<ReceivedLineDTO>| ReceivePurchaseOrderCommand {
+            purchase_order_id: order.id.clone(),
+            received_date: 2000,
+            journal_id,
+            lines,
+        };
+
+    let received = |supply_id: &str, quantity: f64, unit_price: Option<f64>| ReceivedLineDTO {
+        supply_id: supply_id.into(),
+        quantity,
+        unit_price,
+        lot_number: None,
+        expiry_date: None,
+    };
+
+    // drafts are not expected to be delivered
+    let result = service.receive(receive(None, vec![received("1", 10.0, None)]));
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    service.send(&order.id).unwrap();
+
+    let result = service.revise(RevisePurchaseOrderCommand {
+        purchase_order_id: order.id.clone(),
+        ordered_date: 1000,
+        lines: vec![line("1", 20.0, 100.0)],
+    });
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    let journal = service
+        .receive(receive(None, vec![received("1", 10.0, None)]))
+        .unwrap();
+
+    assert_eq!(journal.entry_date, 2000);
+    assert_eq!(journal.total_price, 1000.0);
+
+    let order = service.get(&order.id).unwrap().unwrap();
+
+    assert_eq!(order.status, "partially_received");
+    assert_eq!(order.lines[0].received_quantity, 10.0);
+    assert_eq!(order.journal_ids, vec![journal.id.clone()]);
+
+    // the rest arrives at another price and is appended to the same journal
+    let appended = service
+        .receive(receive(
+            Some(journal.id.clone()),
+            vec![received("2", 5.0, Some(210.0))],
+        ))
+        .unwrap();
+
+    assert_eq!(appended.id, journal.id);
+    assert_eq!(appended.total_price, 2050.0);
+    assert_eq!(appended.records.len(), 2);
+    assert_eq!(appended.records[1].unit_price, 210.0);
+    assert_eq!(journal_service().list().unwrap().len(), 1);
+
+    let order = service.get(&order.id).unwrap().unwrap();
+
+    assert_eq!(order.status, "received");
+    assert_eq!(order.journal_ids, vec![journal.id.clone()]);
+
+    let result = service.cancel(&order.id);
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    let result = service.delete(&order.id);
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    let orders = service
+        .search(SearchPurchaseOrdersQuery {
+            period_start: None,
+            period_end: None,
+            supplier_id: Some("1".into()),
+            status: Some("received".into()),
+        })
+        .unwrap();
+
+    assert_eq!(orders, vec![order]);
+}
+
+#[test]
+fn reorder_service_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    for (supplier_id, supplier_name) in [("1", "SupplierA"), ("2", "SupplierB")] {
+        MockSupplierRepository::new(Arc::clone(&storage))
+            .add(
+                &StoreContext::default(),
+                Supplier::restore(
+                    SupplierId::new(supplier_id).unwrap(),
+                    SupplierName::new(supplier_name).unwrap(),
+                    None,
+                    false,
+                ),
+                None,
+            )
+            .unwrap();
+    }
+
+    MockUnitRepository::new(Arc::clone(&storage))
+        .add(Unit::restore(
+            UnitId::new("1").unwrap(),
+            UnitName::new("kg").unwrap(),
+        ))
+        .unwrap();
+
+    for (supply_id, supplier_id) in [("1", "1"), ("2", "1"), ("3", "2"), ("4", "2")] {
+        MockSupplyRepository::new(Arc::clone(&storage))
+            .add(
+                &StoreContext::default(),
+                Supply::new(
+                    SupplyId::new(supply_id).unwrap(),
+                    SupplyName::new(format!("Supply{}", supply_id)).unwrap(),
+                    UnitName::new("kg").unwrap(),
+                    SupplierId::new(supplier_id).unwrap(),
+                    TaxCategory::Standard,
+                    SupplyUnits::single(UnitId::new("1").unwrap()),
+                    None,
+                ),
+                None,
+            )
+            .unwrap();
+    }
+
+    let supply_service = SupplyService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        None,
+        StoreContext::default(),
+    );
+
+    let levels = |supply_id: &str, par_level: Option<f64>, reorder_point: Option<f64>| {
+        SetReorderLevelsCommand {
+            supply_id: supply_id.into(),
+            par_level,
+            reorder_point,
+        }
+    };
+
+    let result = supply_service.set_reorder_levels(levels("1", None, Some(4.0)));
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    supply_service
+        .set_reorder_levels(levels("1", Some(10.0), Some(4.0)))
+        .unwrap();
+    supply_service
+        .set_reorder_levels(levels("2", Some(5.0), Some(2.0)))
+        .unwrap();
+    // the reorder point defaults to the par level
+    supply_service
+        .set_reorder_levels(levels("3", Some(6.0), None))
+        .unwrap();
+
+    let supply = supply_service.get("3").unwrap().unwrap();
+
+    assert_eq!(supply.par_level, Some(6.0));
+    assert_eq!(supply.reorder_point, Some(6.0));
+
+    let stocktaking_record = |supply_id: &str, quantity: f64| StocktakingRecordDTO {
+        supply_id: supply_id.into(),
+        supply_name: format!("Supply{}", supply_id),
+        unit_name: "kg".into(),
+        unit_price: Some(100.0),
+        quantity,
+        total_price: Some(100.0 * quantity),
+        valuation_method: None,
+        location_id: None,
+        location_name: None,
+        lot_number: None,
+    };
+
+    StocktakingService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStorageLocationRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        ValuationMethod::default(),
+        None,
+        StoreContext::default(),
+    )
+    .record(RecordStocktakingCommand {
+        stocktaken_date: 1000,
+        total_price: Some(600.0),
+        records: vec![stocktaking_record("1", 2.0), stocktaking_record("2", 4.0)],
+    })
+    .unwrap();
+
+    JournalService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        PriceAlertRule::default(),
+        None,
+        StoreContext::default(),
+    )
+    .record(RecordJournalCommand {
+        entry_date: 2000,
+        total_price: 100.0,
+        records: vec![JournalRecordDTO {
+            supply_id: "1".into(),
+            supply_name: "Supply1".into(),
+            supplier_id: "1".into(),
+            supplier_name: "SupplierA".into(),
+            unit_name: "kg".into(),
+            unit_price: 100.0,
+            quantity: 1.0,
+            total_price: 100.0,
+            tax_category: None,
+            tax_included: None,
+            tax_price: 0.0,
+            invoice_registered: None,
+            lot_number: None,
+            expiry_date: None,
+        }],
+    })
+    .unwrap();
+
+    let service = ReorderService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        StockLedgerService::new(
+            Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+            Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+            Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+            Arc::new(MockConsumptionRepository::new(Arc::clone(&storage))),
+            Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+            Arc::new(MockTransferRepository::new(Arc::clone(&storage))),
+            StoreContext::default(),
+        ),
+        StoreContext::default(),
+    );
+
+    let suggestions = service.suggest(ReorderQuery { at: 3000 }).unwrap();
+
+    // Supply2 is above its reorder point and Supply4 has no par level
+    assert_eq!(
+        suggestions,
+        vec![
+            ReorderSuggestionDTO {
+                supplier_id: "1".into(),
+                supplier_name: "SupplierA".into(),
+                lines: vec![ReorderLineDTO {
+                    supply_id: "1".into(),
+                    supply_name: "Supply1".into(),
+                    unit_name: "kg".into(),
+                    on_hand: 3.0,
+                    par_level: 10.0,
+                    reorder_point: 4.0,
+                    suggested_quantity: 7.0,
+                }],
+            },
+            ReorderSuggestionDTO {
+                supplier_id: "2".into(),
+                supplier_name: "SupplierB".into(),
+                lines: vec![ReorderLineDTO {
+                    supply_id: "3".into(),
+                    supply_name: "Supply3".into(),
+                    unit_name: "kg".into(),
+                    on_hand: 0.0,
+                    par_level: 6.0,
+                    reorder_point: 6.0,
+                    suggested_quantity: 6.0,
+                }],
+            },
+        ]
+    );
+}
+
+#[test]
+fn waste_service_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    MockSupplierRepository::new(Arc::clone(&storage))
+        .add(
+            &StoreContext::default(),
+            Supplier::restore(
+                SupplierId::new("1").unwrap(),
+                SupplierName::new("SupplierA").unwrap(),
+                None,
+                false,
+            ),
+            None,
+        )
+        .unwrap();
+
+    MockUnitRepository::new(Arc::clone(&storage))
+        .add(Unit::restore(
+            UnitId::new("1").unwrap(),
+            UnitName::new("kg").unwrap(),
+        ))
+        .unwrap();
+
+    for supply_id in ["1", "2"] {
+        MockSupplyRepository::new(Arc::clone(&storage))
+            .add(
+                &StoreContext::default(),
+                Supply::new(
+                    SupplyId::new(supply_id).unwrap(),
+                    SupplyName::new(format!("Supply{}", supply_id)).unwrap(),
+                    UnitName::new("kg").unwrap(),
+                    SupplierId::new("1").unwrap(),
+                    TaxCategory::Standard,
+                    SupplyUnits::single(UnitId::new("1").unwrap()),
+                    None,
+                ),
+                None,
+            )
+            .unwrap();
+    }
+
+    let journal_record = |supply_id: &str, unit_price: f64, quantity: f64| JournalRecordDTO {
+        supply_id: supply_id.into(),
+        supply_name: format!("Supply{}", supply_id),
+        supplier_id: "1".into(),
+        supplier_name: "SupplierA".into(),
+        unit_name: "kg".into(),
+        unit_price,
+        quantity,
+        total_price: unit_price * quantity,
+        tax_category: None,
+        tax_included: None,
+        tax_price: 0.0,
+        invoice_registered: None,
+        lot_number: None,
+        expiry_date: None,
+    };
+
+    JournalService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        PriceAlertRule::default(),
+        None,
+        StoreContext::default(),
+    )
+    .record(RecordJournalCommand {
+        entry_date: 2000,
+        total_price: 2000.0,
+        records: vec![
+            journal_record("1", 100.0, 10.0),
+            journal_record("2", 200.0, 5.0),
+        ],
+    })
+    .unwrap();
+
+    StocktakingService::new(
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStorageLocationRepository::new(Arc::clone(&storage))),
+        Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+        Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        ValuationMethod::default(),
+        None,
+        StoreContext::default(),
+    )
+    .record(RecordStocktakingCommand {
+        stocktaken_date: 3000,
+        total_price: Some(200.0),
+        records: vec![StocktakingRecordDTO {
+            supply_id: "1".into(),
+            supply_name: "Supply1".into(),
+            unit_name: "kg".into(),
+            unit_price: Some(100.0),
+            quantity: 2.0,
+            total_price: Some(200.0),
+            valuation_method: None,
+            location_id: None,
+            location_name: None,
+            lot_number: None,
+        }],
+    })
+    .unwrap();
+
+    let service = WasteService::new(
+        Arc::new(MockWasteRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        RoundingPolicy::default(),
+        StoreContext::default(),
+    );
+
+    let waste = |wasted_date: i64,
+                 supply_id: &str,
+                 unit_name: Option<&str>,
+                 quantity: f64,
+                 unit_price: f64,
+                 reason: &str| RecordWasteCommand {
+        wasted_date,
+        supply_id: supply_id.into(),
+        unit_name: unit_name.map(|name| name.into()),
+        quantity,
+        unit_price,
+        reason: reason.into(),
+    };
+
+    let result = service.record(waste(2500, "1", None, 1.0, 100.0, "stolen"));
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    let result = service.record(waste(2500, "1", Some("g"), 1.0, 100.0, "expired"));
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    let result = service.record(waste(2500, "1", None, 0.0, 100.0, "expired"));
+
+    assert!(matches!(result, Err(Error::DomainError(_))));
+
+    // the unit defaults to the stock unit
+    let expired = service
+        .record(waste(2500, "1", None, 1.5, 100.0, "expired"))
+        .unwrap();
+
+    assert_eq!(expired.unit_name, "kg");
+    assert_eq!(expired.total_price, 150.0);
+
+    service
+        .record(waste(2600, "1", Some("kg"), 0.5, 100.0, "damaged"))
+        .unwrap();
+    service
+        .record(waste(2700, "2", None, 0.5, 200.0, "over_prep"))
+        .unwrap();
+    // outside of the summarized period
+    service
+        .record(waste(5000, "2", None, 1.0, 200.0, "expired"))
+        .unwrap();
+
+    let wastes = service
+        .search(SearchWasteQuery {
+            period_start: None,
+            period_end: None,
+            supply_id: Some("2".into()),
+        })
+        .unwrap();
+
+    assert_eq!(wastes.len(), 2);
+
+    let summary = service
+        .summarize(WasteSummaryQuery {
+            period_start: 0,
+            period_end: 4000,
+        })
+        .unwrap();
+
+    assert_eq!(summary.closing_stocktaking_id, Some("1".into()));
+    assert_eq!(summary.wasted, 300.0);
+    assert_eq!(summary.purchases, 2000.0);
+    assert_eq!(summary.closing_inventory, 200.0);
+    assert_eq!(summary.loss_ratio, Some(15.0));
+    assert_eq!(
+        summary.reasons,
+        vec![
+            ReasonWasteDTO {
+                reason: "expired".into(),
+                wasted: 150.0,
+            },
+            ReasonWasteDTO {
+                reason: "damaged".into(),
+                wasted: 50.0,
+            },
+            ReasonWasteDTO {
+                reason: "over_prep".into(),
+                wasted: 100.0,
+            },
+        ]
+    );
+    assert_eq!(
+        summary.supplies,
+        vec![
+            SupplyWasteDTO {
+                supply_id: "1".into(),
+                supply_name: "Supply1".into(),
+                quantities: vec![WasteQuantityDTO {
+                    unit_name: "kg".into(),
+                    quantity: 2.0,
+                }],
+                wasted: 200.0,
+                purchases: 1000.0,
+                closing_inventory: 200.0,
+                loss_ratio: Some(20.0),
+            },
+            SupplyWasteDTO {
+                supply_id: "2".into(),
+                supply_name: "Supply2".into(),
+                quantities: vec![WasteQuantityDTO {
+                    unit_name: "kg".into(),
+                    quantity: 0.5,
+                }],
+                wasted: 100.0,
+                purchases: 1000.0,
+                closing_inventory: 0.0,
+                loss_ratio: Some(10.0),
+            },
+        ]
+    );
+
+    service.delete(&expired.id).unwrap();
+
+    assert_eq!(service.list().unwrap().len(), 3);
+}
+
+#[test]
+fn transfer_service_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    let main = StoreContext::Store(StoreId::new("1").unwrap());
+    let branch = StoreContext::Store(StoreId::new("2").unwrap());
+
+    let store_repository = MockStoreRepository::new(Arc::clone(&storage));
+
+    for (id, name) in [("1", "本店"), ("2", "駅前店")] {
+        store_repository
+            .add(Store::restore(
+                StoreId::new(id).unwrap(),
+                StoreName::new(name).unwrap(),
+            ))
+            .unwrap();
+    }
+
+    MockUnitRepository::new(Arc::clone(&storage))
+        .add(Unit::restore(
+            UnitId::new("1").unwrap(),
+            UnitName::new("g").unwrap(),
+        ))
+        .unwrap();
+
+    // each store keeps its own supply of the same name
+    for (id, store) in [("1", &main), ("2", &branch)] {
+        MockSupplierRepository::new(Arc::clone(&storage))
+            .add(
+                store,
+                Supplier::restore(
+                    SupplierId::new(id).unwrap(),
+                    SupplierName::new("SupplierA").unwrap(),
+                    None,
+                    false,
+                ),
+                None,
+            )
+            .unwrap();
+
+        MockSupplyRepository::new(Arc::clone(&storage))
+            .add(
+                store,
+                Supply::new(
+                    SupplyId::new(id).unwrap(),
+                    SupplyName::new("SupplyA").unwrap(),
+                    UnitName::new("g").unwrap(),
+                    SupplierId::new(id).unwrap(),
+                    TaxCategory::Standard,
+                    SupplyUnits::single(UnitId::new("1").unwrap()),
+                    None,
+                ),
+                None,
+            )
+            .unwrap();
+    }
+
+    let transfer_service = |store: &StoreContext| {
+        TransferService::new(
+            Arc::new(MockTransferRepository::new(Arc::clone(&storage))),
+            Arc::new(MockStoreRepository::new(Arc::clone(&storage))),
+            Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+            Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+            RoundingPolicy::default(),
+            store.clone(),
+        )
+    };
+
+    let stocktaking_service = |store: &StoreContext| {
+        StocktakingService::new(
+            Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+            Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+            Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+            Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+            Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+            Arc::new(MockStorageLocationRepository::new(Arc::clone(&storage))),
+            Arc::new(MockAccountingPeriodRepository::new(Arc::clone(&storage))),
+            Arc::new(MockEventPublisher::new(Arc::clone(&storage))),
+            RoundingPolicy::default(),
+            ValuationMethod::default(),
+            None,
+            store.clone(),
+        )
+    };
+
+    let ledger_service = |store: &StoreContext| {
+        StockLedgerService::new(
+            Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+            Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+            Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+            Arc::new(MockConsumptionRepository::new(Arc::clone(&storage))),
+            Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+            Arc::new(MockTransferRepository::new(Arc::clone(&storage))),
+            store.clone(),
+        )
+    };
+
+    let stocktaking_record = |id: &str, quantity: f64| StocktakingRecordDTO {
+        supply_id: id.into(),
+        supply_name: "SupplyA".into(),
+        unit_name: "g".into(),
+        unit_price: Some(100.0),
+        quantity,
+        total_price: Some(quantity * 100.0),
+        valuation_method: None,
+        location_id: None,
+        location_name: None,
+        lot_number: None,
+    };
+
+    let line = |supply_id: &str, quantity: f64| TransferLineDTO {
+        supply_id: supply_id.into(),
+        destination_supply_id: "".into(),
+        supply_name: "".into(),
+        unit_name: "".into(),
+        quantity,
+        unit_price: 100.0,
+        total_price: 0.0,
+    };
+
+    stocktaking_service(&main)
+        .record(RecordStocktakingCommand {
+            stocktaken_date: 1000,
+            total_price: Some(1000.0),
+            records: vec![stocktaking_record("1", 10.0)],
+        })
+        .unwrap();
+
+    // the supply of the destination is found by name
+    let transfer = transfer_service(&main)
+        .record(RecordTransferCommand {
+            transferred_date: 2000,
+            source_store_id: "1".into(),
+            destination_store_id: "2".into(),
+            lines: vec![line("1", 4.0)],
+        })
+        .unwrap();
+
+    assert_eq!(
+        transfer,
+        TransferDTO {
+            id: "1".into(),
+            transferred_date: 2000,
+            source_store_id: "1".into(),
+            source_store_name: "本店".into(),
+            destination_store_id: "2".into(),
+            destination_store_name: "駅前店".into(),
+            total_price: 400.0,
+            lines: vec![TransferLineDTO {
+                supply_id: "1".into(),
+                destination_supply_id: "2".into(),
+                supply_name: "SupplyA".into(),
+                unit_name: "g".into(),
+                quantity: 4.0,
+                unit_price: 100.0,
+                total_price: 400.0,
+            }],
+        }
+    );
+
+    assert_eq!(transfer_service(&branch).list().unwrap(), vec![transfer]);
+
+    // stores must differ and supplies must belong to the source store
+    assert!(transfer_service(&main)
+        .record(RecordTransferCommand {
+            transferred_date: 2000,
+            source_store_id: "1".into(),
+            destination_store_id: "1".into(),
+            lines: vec![line("1", 1.0)],
+        })
+        .is_err());
+
+    assert!(transfer_service(&main)
+        .record(RecordTransferCommand {
+            transferred_date: 2000,
+            source_store_id: "1".into(),
+            destination_store_id: "2".into(),
+            lines: vec![line("2", 1.0)],
+        })
+        .is_err());
+
+    // stock leaves the source and arrives at the destination
+    let on_hand = ledger_service(&main)
+        .on_hand(StockLedgerQuery { at: 3000 })
+        .unwrap();
+
+    assert_eq!(on_hand[0].transferred_quantity, -4.0);
+    assert_eq!(on_hand[0].quantity, 6.0);
+
+    let on_hand = ledger_service(&branch)
+        .on_hand(StockLedgerQuery { at: 3000 })
+        .unwrap();
+
+    assert_eq!(on_hand[0].supply_id, "2");
+    assert_eq!(on_hand[0].transferred_quantity, 4.0);
+    assert_eq!(on_hand[0].quantity, 4.0);
+
+    stocktaking_service(&main)
+        .record(RecordStocktakingCommand {
+            stocktaken_date: 3000,
+            total_price: Some(600.0),
+            records: vec![stocktaking_record("1", 6.0)],
+        })
+        .unwrap();
+
+    stocktaking_service(&branch)
+        .record(RecordStocktakingCommand {
+            stocktaken_date: 3000,
+            total_price: Some(300.0),
+            records: vec![stocktaking_record("2", 3.0)],
+        })
+        .unwrap();
+
+    // cost moves with the stock and cancels out when consolidated
+    let report = ReportService::new(
+        Arc::new(MockStoreRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockTransferRepository::new(Arc::clone(&storage))),
+    )
+    .cost_of_goods_sold(CostOfGoodsSoldQuery {
+        period_start: 1500,
+        period_end: 4000,
+        store_id: None,
+    })
+    .unwrap();
+
+    assert_eq!(report.transfers, 0.0);
+    assert_eq!(report.cost_of_goods_sold, 100.0);
+
+    let stores: Vec<(f64, f64)> = report
+        .stores
+        .iter()
+        .map(|store| (store.transfers, store.cost_of_goods_sold))
+        .collect();
+
+    assert_eq!(stores, vec![(-400.0, 0.0), (400.0, 100.0)]);
+
+    // transfers between other stores are neither read nor changed
+    let outlet = StoreContext::Store(StoreId::new("3").unwrap());
+
+    assert_eq!(transfer_service(&outlet).get("1").unwrap(), None);
+    assert!(matches!(
+        transfer_service(&outlet).delete("1"),
+        Err(Error::DomainError(_))
+    ));
+
+    transfer_service(&main).delete("1").unwrap();
+
+    assert!(transfer_service(&main).list().unwrap().is_empty());
+
+    assert!(matches!(
+        transfer_service(&outlet).purge("1"),
+        Err(Error::DomainError(_))
+    ));
+
+    transfer_service(&branch).restore("1").unwrap();
+
+    assert_eq!(transfer_service(&main).list().unwrap().len(), 1);
 }
@@ -0,0 +1,28 @@
+//! This module provides implementations for trash-related usecases.
+
+pub mod retention;
+
+use crate::core::domain::values::trash::*;
+use crate::core::provided_ports::*;
+use crate::core::*;
+
+/// Returns the record in the trash `is` holds for, failing if it is not in the trash.
+pub fn trashed_item<T>(trashed: Vec<Trashed<T>>, is: impl Fn(&T) -> bool, name: &str) -> Result<T> {
+    trashed
+        .into_iter()
+        .find(|trashed| is(trashed.item()))
+        .map(Trashed::into_item)
+        .ok_or(Error::DomainError(format!("{} is not in the trash.", name)))
+}
+
+/// Pairs DTOs of records in the trash with the date and time the records were deleted.
+pub fn trashed_dtos<T, D>(trashed: &[Trashed<T>], dtos: Vec<D>) -> Vec<TrashedDTO<D>> {
+    trashed
+        .iter()
+        .zip(dtos)
+        .map(|(trashed, item)| TrashedDTO {
+            item,
+            deleted_at: trashed.deleted_at().as_i64(),
+        })
+        .collect()
+}
@@ -0,0 +1,180 @@
+//! This module provides the implementation of the `TrashUsecase`.
+use std::sync::Arc;
+
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::trash::*;
+use crate::core::provided_ports::*;
+use crate::core::required_ports::*;
+use crate::core::*;
+
+/// Trash usecase
+///
+/// Records of every store are purged, records before the ones they refer to.
+pub struct RetentionService {
+    store_repository: Arc<dyn ForStorePersistence>,
+    supply_repository: Arc<dyn ForSupplyPersistence>,
+    unit_repository: Arc<dyn ForUnitPersistence>,
+    category_repository: Arc<dyn ForCategoryPersistence>,
+    location_repository: Arc<dyn ForStorageLocationPersistence>,
+    supplier_repository: Arc<dyn ForSupplierPersistence>,
+    journal_repository: Arc<dyn ForJournalPersistence>,
+    purchase_order_repository: Arc<dyn ForPurchaseOrderPersistence>,
+    transfer_repository: Arc<dyn ForTransferPersistence>,
+    invoice_repository: Arc<dyn ForSupplierInvoicePersistence>,
+    stocktaking_repository: Arc<dyn ForStocktakingPersistence>,
+    consumption_repository: Arc<dyn ForConsumptionPersistence>,
+    waste_repository: Arc<dyn ForWastePersistence>,
+}
+
+impl RetentionService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        store_repository: Arc<dyn ForStorePersistence>,
+        supply_repository: Arc<dyn ForSupplyPersistence>,
+        unit_repository: Arc<dyn ForUnitPersistence>,
+        category_repository: Arc<dyn ForCategoryPersistence>,
+        location_repository: Arc<dyn ForStorageLocationPersistence>,
+        supplier_repository: Arc<dyn ForSupplierPersistence>,
+        journal_repository: Arc<dyn ForJournalPersistence>,
+        purchase_order_repository: Arc<dyn ForPurchaseOrderPersistence>,
+        transfer_repository: Arc<dyn ForTransferPersistence>,
+        invoice_repository: Arc<dyn ForSupplierInvoicePersistence>,
+        stocktaking_repository: Arc<dyn ForStocktakingPersistence>,
+        consumption_repository: Arc<dyn ForConsumptionPersistence>,
+        waste_repository: Arc<dyn ForWastePersistence>,
+    ) -> Self {
+        Self {
+            store_repository,
+            supply_repository,
+            unit_repository,
+            category_repository,
+            location_repository,
+            supplier_repository,
+            journal_repository,
+            purchase_order_repository,
+            transfer_repository,
+            invoice_repository,
+            stocktaking_repository,
+            consumption_repository,
+            waste_repository,
+        }
+    }
+}
+
+/// Purges the records in the trash that have expired, returning their number.
+///
+/// Records still referred to by others stay in the trash.
+fn purge_expired<T>(
+    trashed: Vec<Trashed<T>>,
+    expired: &dyn Fn(DeletedDateTime) -> bool,
+    purge: impl Fn(&T) -> Result<()>,
+) -> Result<usize> {
+    let mut purged = 0;
+
+    for trashed in trashed {
+        if !expired(trashed.deleted_at()) {
+            continue;
+        }
+
+        match purge(trashed.item()) {
+            Ok(()) => purged += 1,
+            Err(Error::DomainError(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(purged)
+}
+
+/// Trash usecase implementation
+impl TrashUsecase for RetentionService {
+    fn purge_expired(&self, command: PurgeExpiredCommand) -> Result<usize> {
+        let period = RetentionPeriod::new(command.retention_days);
+        let expired = |deleted_at: DeletedDateTime| period.has_expired(deleted_at, command.now);
+        let all = StoreContext::Consolidated;
+
+        let purged = [
+            purge_expired(
+                self.journal_repository.list_deleted(&all)?,
+                &expired,
+                |journal| {
+                    self.journal_repository
+                        .purge(&all, journal.id().clone(), None)
+                },
+            )?,
+            purge_expired(
+                self.stocktaking_repository.list_deleted(&all)?,
+                &expired,
+                |stocktaking| {
+                    self.stocktaking_repository
+                        .purge(&all, stocktaking.id().clone(), None)
+                },
+            )?,
+            purge_expired(
+                self.consumption_repository.list_deleted(&all)?,
+                &expired,
+                |consumption| {
+                    self.consumption_repository
+                        .purge(&all, consumption.id().clone())
+                },
+            )?,
+            purge_expired(
+                self.waste_repository.list_deleted(&all)?,
+                &expired,
+                |waste| self.waste_repository.purge(&all, waste.id().clone()),
+            )?,
+            purge_expired(
+                self.transfer_repository.list_deleted(&all)?,
+                &expired,
+                |transfer| self.transfer_repository.purge(&all, transfer.id().clone()),
+            )?,
+            purge_expired(
+                self.purchase_order_repository.list_deleted(&all)?,
+                &expired,
+                |order| {
+                    self.purchase_order_repository
+                        .purge(&all, order.id().clone())
+                },
+            )?,
+            purge_expired(
+                self.invoice_repository.list_deleted(&all)?,
+                &expired,
+                |invoice| self.invoice_repository.purge(&all, invoice.id().clone()),
+            )?,
+            purge_expired(
+                self.supply_repository.list_deleted(&all)?,
+                &expired,
+                |supply| {
+                    self.supply_repository
+                        .purge(&all, supply.id().clone(), None)
+                },
+            )?,
+            purge_expired(
+                self.supplier_repository.list_deleted(&all)?,
+                &expired,
+                |supplier| {
+                    self.supplier_repository
+                        .purge(&all, supplier.id().clone(), None)
+                },
+            )?,
+            purge_expired(
+                self.category_repository.list_deleted()?,
+                &expired,
+                |category| self.category_repository.purge(category.id().clone()),
+            )?,
+            purge_expired(self.unit_repository.list_deleted()?, &expired, |unit| {
+                self.unit_repository.purge(unit.id().clone())
+            })?,
+            purge_expired(
+                self.location_repository.list_deleted()?,
+                &expired,
+                |location| self.location_repository.purge(location.id().clone()),
+            )?,
+            purge_expired(self.store_repository.list_deleted()?, &expired, |store| {
+                self.store_repository.purge(store.id().clone())
+            })?,
+        ];
+
+        Ok(purged.iter().sum())
+    }
+}
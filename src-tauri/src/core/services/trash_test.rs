@@ -0,0 +1,138 @@
+use crate::core::provided_ports::*;
+use crate::core::required_ports::mock::*;
+use crate::core::services::stock::category::*;
+use crate::core::services::trash::retention::*;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DAY: i64 = 24 * 60 * 60 * 1000;
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap()
+}
+
+#[test]
+fn trash_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    let category_service = CategoryService::new(
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+    );
+
+    let food = category_service
+        .register(RegisterCategoryCommand {
+            category_name: "Food".into(),
+            parent_id: None,
+        })
+        .unwrap();
+
+    let meat = category_service
+        .register(RegisterCategoryCommand {
+            category_name: "Meat".into(),
+            parent_id: Some(food.id.clone()),
+        })
+        .unwrap();
+
+    category_service.delete(&meat.id).unwrap();
+    category_service.delete(&food.id).unwrap();
+
+    assert!(category_service.list().unwrap().is_empty());
+
+    let trashed = category_service.list_deleted().unwrap();
+
+    assert_eq!(trashed.len(), 2);
+    assert!(trashed.iter().any(|trashed| trashed.item == meat));
+
+    // the parent has to be restored first
+    let result = category_service.restore(&meat.id);
+
+    assert!(result.is_err());
+
+    category_service.restore(&food.id).unwrap();
+    category_service.restore(&meat.id).unwrap();
+
+    assert_eq!(category_service.list().unwrap().len(), 2);
+    assert!(category_service.list_deleted().unwrap().is_empty());
+
+    // only records in the trash can be restored or purged
+    assert!(category_service.restore(&meat.id).is_err());
+    assert!(category_service.purge(&meat.id).is_err());
+
+    category_service.delete(&meat.id).unwrap();
+    category_service.purge(&meat.id).unwrap();
+
+    assert!(category_service.list_deleted().unwrap().is_empty());
+    assert!(category_service.restore(&meat.id).is_err());
+}
+
+#[test]
+fn retention_service_test() {
+    let storage = Arc::new(Mutex::new(Storage::default()));
+
+    let category_service = CategoryService::new(
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+    );
+
+    let retention_service = RetentionService::new(
+        Arc::new(MockStoreRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplyRepository::new(Arc::clone(&storage))),
+        Arc::new(MockUnitRepository::new(Arc::clone(&storage))),
+        Arc::new(MockCategoryRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStorageLocationRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierRepository::new(Arc::clone(&storage))),
+        Arc::new(MockJournalRepository::new(Arc::clone(&storage))),
+        Arc::new(MockPurchaseOrderRepository::new(Arc::clone(&storage))),
+        Arc::new(MockTransferRepository::new(Arc::clone(&storage))),
+        Arc::new(MockSupplierInvoiceRepository::new(Arc::clone(&storage))),
+        Arc::new(MockStocktakingRepository::new(Arc::clone(&storage))),
+        Arc::new(MockConsumptionRepository::new(Arc::clone(&storage))),
+        Arc::new(MockWasteRepository::new(Arc::clone(&storage))),
+    );
+
+    for name in ["Food", "Drink"] {
+        let category = category_service
+            .register(RegisterCategoryCommand {
+                category_name: name.into(),
+                parent_id: None,
+            })
+            .unwrap();
+
+        category_service.delete(&category.id).unwrap();
+    }
+
+    // 0 days keeps records forever
+    let purged = retention_service
+        .purge_expired(PurgeExpiredCommand {
+            now: now() + 365 * DAY,
+            retention_days: 0,
+        })
+        .unwrap();
+
+    assert_eq!(purged, 0);
+
+    // not expired yet
+    let purged = retention_service
+        .purge_expired(PurgeExpiredCommand {
+            now: now() + 29 * DAY,
+            retention_days: 30,
+        })
+        .unwrap();
+
+    assert_eq!(purged, 0);
+    assert_eq!(category_service.list_deleted().unwrap().len(), 2);
+
+    let purged = retention_service
+        .purge_expired(PurgeExpiredCommand {
+            now: now() + 31 * DAY,
+            retention_days: 30,
+        })
+        .unwrap();
+
+    assert_eq!(purged, 2);
+    assert!(category_service.list_deleted().unwrap().is_empty());
+}
@@ -1,36 +1,134 @@
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
+use crate::core::domain::values::audit::OperatorName;
+use crate::core::domain::values::decimal::RoundingPolicy;
+use crate::core::domain::values::price::PriceAlertRule;
+use crate::core::domain::values::store::{StoreContext, StoreId};
+use crate::core::domain::values::valuation::ValuationMethod;
 use crate::core::provided_ports::*;
 use crate::core::required_ports::*;
-use crate::core::services::stock::{journal::*, stocktaking::*, supplier::*, supply::*};
+use crate::core::services::accounting::period::*;
+use crate::core::services::audit::log::*;
+use crate::core::services::payable::invoice::*;
+use crate::core::services::stock::{
+    category::*, consumption::*, journal::*, ledger::*, location::*, order::*, reorder::*,
+    report::*, stocktaking::*, store::*, supplier::*, supply::*, transfer::*, unit::*, waste::*,
+};
+use crate::core::services::trash::retention::*;
 
 pub struct Stocker {
     supply_respository: Arc<dyn ForSupplyPersistence + Send + Sync + 'static>,
     supplier_repository: Arc<dyn ForSupplierPersistence + Send + Sync + 'static>,
     jorunal_repository: Arc<dyn ForJournalPersistence + Send + Sync + 'static>,
     stocktaking_repository: Arc<dyn ForStocktakingPersistence + Send + Sync + 'static>,
+    unit_repository: Arc<dyn ForUnitPersistence + Send + Sync + 'static>,
+    consumption_repository: Arc<dyn ForConsumptionPersistence + Send + Sync + 'static>,
+    category_repository: Arc<dyn ForCategoryPersistence + Send + Sync + 'static>,
+    purchase_order_repository: Arc<dyn ForPurchaseOrderPersistence + Send + Sync + 'static>,
+    waste_repository: Arc<dyn ForWastePersistence + Send + Sync + 'static>,
+    location_repository: Arc<dyn ForStorageLocationPersistence + Send + Sync + 'static>,
+    store_repository: Arc<dyn ForStorePersistence + Send + Sync + 'static>,
+    transfer_repository: Arc<dyn ForTransferPersistence + Send + Sync + 'static>,
+    invoice_repository: Arc<dyn ForSupplierInvoicePersistence + Send + Sync + 'static>,
+    accounting_period_repository: Arc<dyn ForAccountingPeriodPersistence + Send + Sync + 'static>,
+    audit_repository: Arc<dyn ForAuditPersistence + Send + Sync + 'static>,
+    event_publisher: Arc<dyn ForEventPublishing + Send + Sync + 'static>,
+    rounding_policy: RwLock<RoundingPolicy>,
+    valuation_method: RwLock<ValuationMethod>,
+    price_alert_rule: RwLock<PriceAlertRule>,
+    current_store: RwLock<StoreId>,
+    operator: RwLock<Option<OperatorName>>,
 }
 
-pub struct Ports<SupplyRepository, SupplierRepository, JournalRepository, StocktakingRepository>
-where
+pub struct Ports<
+    SupplyRepository,
+    SupplierRepository,
+    JournalRepository,
+    StocktakingRepository,
+    UnitRepository,
+    ConsumptionRepository,
+    CategoryRepository,
+    PurchaseOrderRepository,
+    WasteRepository,
+    StorageLocationRepository,
+    StoreRepository,
+    TransferRepository,
+    SupplierInvoiceRepository,
+    AccountingPeriodRepository,
+    AuditRepository,
+    EventPublisher,
+> where
     SupplyRepository: ForSupplyPersistence,
     SupplierRepository: ForSupplierPersistence,
     JournalRepository: ForJournalPersistence,
     StocktakingRepository: ForStocktakingPersistence,
+    UnitRepository: ForUnitPersistence,
+    ConsumptionRepository: ForConsumptionPersistence,
+    CategoryRepository: ForCategoryPersistence,
+    PurchaseOrderRepository: ForPurchaseOrderPersistence,
+    WasteRepository: ForWastePersistence,
+    StorageLocationRepository: ForStorageLocationPersistence,
+    StoreRepository: ForStorePersistence,
+    TransferRepository: ForTransferPersistence,
+    SupplierInvoiceRepository: ForSupplierInvoicePersistence,
+    AccountingPeriodRepository: ForAccountingPeriodPersistence,
+    AuditRepository: ForAuditPersistence,
+    EventPublisher: ForEventPublishing,
 {
     pub for_supply_persistence: SupplyRepository,
     pub for_supplier_persistence: SupplierRepository,
     pub for_journal_persistence: JournalRepository,
     pub for_stocktaking_persistence: StocktakingRepository,
+    pub for_unit_persistence: UnitRepository,
+    pub for_consumption_persistence: ConsumptionRepository,
+    pub for_category_persistence: CategoryRepository,
+    pub for_purchase_order_persistence: PurchaseOrderRepository,
+    pub for_waste_persistence: WasteRepository,
+    pub for_storage_location_persistence: StorageLocationRepository,
+    pub for_store_persistence: StoreRepository,
+    pub for_transfer_persistence: TransferRepository,
+    pub for_supplier_invoice_persistence: SupplierInvoiceRepository,
+    pub for_accounting_period_persistence: AccountingPeriodRepository,
+    pub for_audit_persistence: AuditRepository,
+    pub for_event_publishing: EventPublisher,
 }
 
 impl Stocker {
-    pub fn plug<SupplyRepository, SupplierRepository, JournalRepository, StocktakingRepository>(
+    pub fn plug<
+        SupplyRepository,
+        SupplierRepository,
+        JournalRepository,
+        StocktakingRepository,
+        UnitRepository,
+        ConsumptionRepository,
+        CategoryRepository,
+        PurchaseOrderRepository,
+        WasteRepository,
+        StorageLocationRepository,
+        StoreRepository,
+        TransferRepository,
+        SupplierInvoiceRepository,
+        AccountingPeriodRepository,
+        AuditRepository,
+        EventPublisher,
+    >(
         ports: Ports<
             SupplyRepository,
             SupplierRepository,
             JournalRepository,
             StocktakingRepository,
+            UnitRepository,
+            ConsumptionRepository,
+            CategoryRepository,
+            PurchaseOrderRepository,
+            WasteRepository,
+            StorageLocationRepository,
+            StoreRepository,
+            TransferRepository,
+            SupplierInvoiceRepository,
+            AccountingPeriodRepository,
+            AuditRepository,
+            EventPublisher,
         >,
     ) -> Self
     where
@@ -38,39 +136,399 @@ impl Stocker {
         SupplierRepository: ForSupplierPersistence + Send + Sync + 'static,
         JournalRepository: ForJournalPersistence + Send + Sync + 'static,
         StocktakingRepository: ForStocktakingPersistence + Send + Sync + 'static,
+        UnitRepository: ForUnitPersistence + Send + Sync + 'static,
+        ConsumptionRepository: ForConsumptionPersistence + Send + Sync + 'static,
+        CategoryRepository: ForCategoryPersistence + Send + Sync + 'static,
+        PurchaseOrderRepository: ForPurchaseOrderPersistence + Send + Sync + 'static,
+        WasteRepository: ForWastePersistence + Send + Sync + 'static,
+        StorageLocationRepository: ForStorageLocationPersistence + Send + Sync + 'static,
+        StoreRepository: ForStorePersistence + Send + Sync + 'static,
+        TransferRepository: ForTransferPersistence + Send + Sync + 'static,
+        SupplierInvoiceRepository: ForSupplierInvoicePersistence + Send + Sync + 'static,
+        AccountingPeriodRepository: ForAccountingPeriodPersistence + Send + Sync + 'static,
+        AuditRepository: ForAuditPersistence + Send + Sync + 'static,
+        EventPublisher: ForEventPublishing + Send + Sync + 'static,
     {
         Self {
             supply_respository: Arc::new(ports.for_supply_persistence),
             supplier_repository: Arc::new(ports.for_supplier_persistence),
             jorunal_repository: Arc::new(ports.for_journal_persistence),
             stocktaking_repository: Arc::new(ports.for_stocktaking_persistence),
+            unit_repository: Arc::new(ports.for_unit_persistence),
+            consumption_repository: Arc::new(ports.for_consumption_persistence),
+            category_repository: Arc::new(ports.for_category_persistence),
+            purchase_order_repository: Arc::new(ports.for_purchase_order_persistence),
+            waste_repository: Arc::new(ports.for_waste_persistence),
+            location_repository: Arc::new(ports.for_storage_location_persistence),
+            store_repository: Arc::new(ports.for_store_persistence),
+            transfer_repository: Arc::new(ports.for_transfer_persistence),
+            invoice_repository: Arc::new(ports.for_supplier_invoice_persistence),
+            accounting_period_repository: Arc::new(ports.for_accounting_period_persistence),
+            audit_repository: Arc::new(ports.for_audit_persistence),
+            event_publisher: Arc::new(ports.for_event_publishing),
+            rounding_policy: RwLock::new(RoundingPolicy::default()),
+            valuation_method: RwLock::new(ValuationMethod::default()),
+            price_alert_rule: RwLock::new(PriceAlertRule::default()),
+            current_store: RwLock::new(StoreId::default()),
+            operator: RwLock::new(None),
         }
     }
 
+    /// Changes the rounding policy used to compute totals.
+    pub fn set_rounding_policy(&self, policy: RoundingPolicy) {
+        *self.rounding_policy.write().unwrap() = policy;
+    }
+
+    /// Returns the rounding policy used to compute totals.
+    pub fn rounding_policy(&self) -> RoundingPolicy {
+        *self.rounding_policy.read().unwrap()
+    }
+
+    /// Changes the method deriving stocktaking unit prices.
+    pub fn set_valuation_method(&self, method: ValuationMethod) {
+        *self.valuation_method.write().unwrap() = method;
+    }
+
+    /// Returns the method deriving stocktaking unit prices.
+    pub fn valuation_method(&self) -> ValuationMethod {
+        *self.valuation_method.read().unwrap()
+    }
+
+    /// Changes the rule flagging unusual purchase prices.
+    pub fn set_price_alert_rule(&self, rule: PriceAlertRule) {
+        *self.price_alert_rule.write().unwrap() = rule;
+    }
+
+    /// Returns the rule flagging unusual purchase prices.
+    pub fn price_alert_rule(&self) -> PriceAlertRule {
+        *self.price_alert_rule.read().unwrap()
+    }
+
+    /// Changes the store data is read from and recorded in.
+    pub fn set_current_store(&self, store_id: StoreId) {
+        *self.current_store.write().unwrap() = store_id;
+    }
+
+    /// Returns the store data is read from and recorded in.
+    pub fn current_store(&self) -> StoreId {
+        self.current_store.read().unwrap().clone()
+    }
+
+    /// Changes the person changes are logged on behalf of, none if unknown.
+    pub fn set_operator(&self, operator: Option<OperatorName>) {
+        *self.operator.write().unwrap() = operator;
+    }
+
+    /// Returns the person changes are logged on behalf of.
+    pub fn operator(&self) -> Option<OperatorName> {
+        self.operator.read().unwrap().clone()
+    }
+
+    fn store_context(&self) -> StoreContext {
+        StoreContext::Store(self.current_store())
+    }
+
     pub fn supply_usecase(&self) -> impl SupplyUsecase {
         let supply_repository = Arc::clone(&self.supply_respository);
         let supplier_repository = Arc::clone(&self.supplier_repository);
-        SupplyService::new(supply_repository, supplier_repository)
+        let unit_repository = Arc::clone(&self.unit_repository);
+        let category_repository = Arc::clone(&self.category_repository);
+        let event_publisher = Arc::clone(&self.event_publisher);
+
+        SupplyService::new(
+            supply_repository,
+            supplier_repository,
+            unit_repository,
+            category_repository,
+            event_publisher,
+            self.operator(),
+            self.store_context(),
+        )
+    }
+
+    pub fn unit_usecase(&self) -> impl UnitUsecase {
+        let unit_repository = Arc::clone(&self.unit_repository);
+        let supply_repository = Arc::clone(&self.supply_respository);
+
+        UnitService::new(unit_repository, supply_repository)
+    }
+
+    pub fn category_usecase(&self) -> impl CategoryUsecase {
+        let category_repository = Arc::clone(&self.category_repository);
+        let supply_repository = Arc::clone(&self.supply_respository);
+
+        CategoryService::new(category_repository, supply_repository)
+    }
+
+    pub fn storage_location_usecase(&self) -> impl StorageLocationUsecase {
+        let location_repository = Arc::clone(&self.location_repository);
+        let supply_repository = Arc::clone(&self.supply_respository);
+        let event_publisher = Arc::clone(&self.event_publisher);
+
+        StorageLocationService::new(
+            location_repository,
+            supply_repository,
+            event_publisher,
+            self.operator(),
+            self.store_context(),
+        )
     }
 
     pub fn supplier_usecase(&self) -> impl SupplierUsecase {
         let supplier_repository = Arc::clone(&self.supplier_repository);
+        let supply_repository = Arc::clone(&self.supply_respository);
+        let category_repository = Arc::clone(&self.category_repository);
+        let event_publisher = Arc::clone(&self.event_publisher);
 
-        SupplierService::new(supplier_repository)
+        SupplierService::new(
+            supplier_repository,
+            supply_repository,
+            category_repository,
+            event_publisher,
+            self.operator(),
+            self.store_context(),
+        )
     }
 
     pub fn journal_usecase(&self) -> impl JournalUsecase {
         let supply_repository = Arc::clone(&self.supply_respository);
         let supplier_repository = Arc::clone(&self.supplier_repository);
         let journal_repository = Arc::clone(&self.jorunal_repository);
+        let unit_repository = Arc::clone(&self.unit_repository);
+        let category_repository = Arc::clone(&self.category_repository);
+        let period_repository = Arc::clone(&self.accounting_period_repository);
+        let event_publisher = Arc::clone(&self.event_publisher);
+
+        JournalService::new(
+            supply_repository,
+            supplier_repository,
+            journal_repository,
+            unit_repository,
+            category_repository,
+            period_repository,
+            event_publisher,
+            self.rounding_policy(),
+            self.price_alert_rule(),
+            self.operator(),
+            self.store_context(),
+        )
+    }
+
+    pub fn purchase_order_usecase(&self) -> impl PurchaseOrderUsecase {
+        let purchase_order_repository = Arc::clone(&self.purchase_order_repository);
+        let supplier_repository = Arc::clone(&self.supplier_repository);
+        let supply_repository = Arc::clone(&self.supply_respository);
 
-        JournalService::new(supply_repository, supplier_repository, journal_repository)
+        PurchaseOrderService::new(
+            purchase_order_repository,
+            supplier_repository,
+            supply_repository,
+            self.journal_usecase(),
+            self.rounding_policy(),
+            self.store_context(),
+        )
     }
 
     pub fn stocktaking_usecase(&self) -> impl StocktakingUsecase {
         let supply_repository = Arc::clone(&self.supply_respository);
         let stocktaking_repository = Arc::clone(&self.stocktaking_repository);
+        let journal_repository = Arc::clone(&self.jorunal_repository);
+        let unit_repository = Arc::clone(&self.unit_repository);
+        let category_repository = Arc::clone(&self.category_repository);
+        let location_repository = Arc::clone(&self.location_repository);
+        let period_repository = Arc::clone(&self.accounting_period_repository);
+        let event_publisher = Arc::clone(&self.event_publisher);
+
+        StocktakingService::new(
+            supply_repository,
+            stocktaking_repository,
+            journal_repository,
+            unit_repository,
+            category_repository,
+            location_repository,
+            period_repository,
+            event_publisher,
+            self.rounding_policy(),
+            self.valuation_method(),
+            self.operator(),
+            self.store_context(),
+        )
+    }
+
+    pub fn consumption_usecase(&self) -> impl ConsumptionUsecase {
+        let supply_repository = Arc::clone(&self.supply_respository);
+        let consumption_repository = Arc::clone(&self.consumption_repository);
+        let unit_repository = Arc::clone(&self.unit_repository);
+
+        ConsumptionService::new(
+            supply_repository,
+            consumption_repository,
+            unit_repository,
+            self.store_context(),
+        )
+    }
+
+    pub fn stock_ledger_usecase(&self) -> impl StockLedgerUsecase {
+        let supply_repository = Arc::clone(&self.supply_respository);
+        let journal_repository = Arc::clone(&self.jorunal_repository);
+        let stocktaking_repository = Arc::clone(&self.stocktaking_repository);
+        let consumption_repository = Arc::clone(&self.consumption_repository);
+        let unit_repository = Arc::clone(&self.unit_repository);
+        let transfer_repository = Arc::clone(&self.transfer_repository);
+
+        StockLedgerService::new(
+            supply_repository,
+            journal_repository,
+            stocktaking_repository,
+            consumption_repository,
+            unit_repository,
+            transfer_repository,
+            self.store_context(),
+        )
+    }
+
+    pub fn reorder_usecase(&self) -> impl ReorderUsecase {
+        let supply_repository = Arc::clone(&self.supply_respository);
+        let supplier_repository = Arc::clone(&self.supplier_repository);
+        let unit_repository = Arc::clone(&self.unit_repository);
+
+        ReorderService::new(
+            supply_repository,
+            supplier_repository,
+            unit_repository,
+            self.stock_ledger_usecase(),
+            self.store_context(),
+        )
+    }
+
+    pub fn waste_usecase(&self) -> impl WasteUsecase {
+        let waste_repository = Arc::clone(&self.waste_repository);
+        let supply_repository = Arc::clone(&self.supply_respository);
+        let unit_repository = Arc::clone(&self.unit_repository);
+        let journal_repository = Arc::clone(&self.jorunal_repository);
+        let stocktaking_repository = Arc::clone(&self.stocktaking_repository);
+
+        WasteService::new(
+            waste_repository,
+            supply_repository,
+            unit_repository,
+            journal_repository,
+            stocktaking_repository,
+            self.rounding_policy(),
+            self.store_context(),
+        )
+    }
+
+    pub fn report_usecase(&self) -> impl ReportUsecase {
+        let store_repository = Arc::clone(&self.store_repository);
+        let supplier_repository = Arc::clone(&self.supplier_repository);
+        let journal_repository = Arc::clone(&self.jorunal_repository);
+        let stocktaking_repository = Arc::clone(&self.stocktaking_repository);
+        let transfer_repository = Arc::clone(&self.transfer_repository);
+
+        ReportService::new(
+            store_repository,
+            supplier_repository,
+            journal_repository,
+            stocktaking_repository,
+            transfer_repository,
+        )
+    }
+
+    pub fn store_usecase(&self) -> impl StoreUsecase {
+        let store_repository = Arc::clone(&self.store_repository);
+        let supplier_repository = Arc::clone(&self.supplier_repository);
+        let supply_repository = Arc::clone(&self.supply_respository);
+        let journal_repository = Arc::clone(&self.jorunal_repository);
+        let stocktaking_repository = Arc::clone(&self.stocktaking_repository);
+        let transfer_repository = Arc::clone(&self.transfer_repository);
+        let purchase_order_repository = Arc::clone(&self.purchase_order_repository);
+        let consumption_repository = Arc::clone(&self.consumption_repository);
+        let waste_repository = Arc::clone(&self.waste_repository);
+
+        StoreService::new(
+            store_repository,
+            supplier_repository,
+            supply_repository,
+            journal_repository,
+            stocktaking_repository,
+            transfer_repository,
+            purchase_order_repository,
+            consumption_repository,
+            waste_repository,
+        )
+    }
+
+    pub fn transfer_usecase(&self) -> impl TransferUsecase {
+        let transfer_repository = Arc::clone(&self.transfer_repository);
+        let store_repository = Arc::clone(&self.store_repository);
+        let supply_repository = Arc::clone(&self.supply_respository);
+        let unit_repository = Arc::clone(&self.unit_repository);
+
+        TransferService::new(
+            transfer_repository,
+            store_repository,
+            supply_repository,
+            unit_repository,
+            self.rounding_policy(),
+            self.store_context(),
+        )
+    }
+
+    pub fn payable_usecase(&self) -> impl PayableUsecase {
+        let invoice_repository = Arc::clone(&self.invoice_repository);
+        let supplier_repository = Arc::clone(&self.supplier_repository);
+        let journal_repository = Arc::clone(&self.jorunal_repository);
+
+        PayableService::new(
+            invoice_repository,
+            supplier_repository,
+            journal_repository,
+            self.store_context(),
+        )
+    }
+
+    pub fn accounting_period_usecase(&self) -> impl AccountingPeriodUsecase {
+        let period_repository = Arc::clone(&self.accounting_period_repository);
+
+        AccountingPeriodService::new(period_repository, self.store_context())
+    }
+
+    pub fn audit_usecase(&self) -> impl AuditUsecase {
+        let audit_repository = Arc::clone(&self.audit_repository);
+
+        AuditLogService::new(audit_repository, self.store_context())
+    }
+
+    pub fn trash_usecase(&self) -> impl TrashUsecase {
+        let store_repository = Arc::clone(&self.store_repository);
+        let supply_repository = Arc::clone(&self.supply_respository);
+        let unit_repository = Arc::clone(&self.unit_repository);
+        let category_repository = Arc::clone(&self.category_repository);
+        let location_repository = Arc::clone(&self.location_repository);
+        let supplier_repository = Arc::clone(&self.supplier_repository);
+        let journal_repository = Arc::clone(&self.jorunal_repository);
+        let purchase_order_repository = Arc::clone(&self.purchase_order_repository);
+        let transfer_repository = Arc::clone(&self.transfer_repository);
+        let invoice_repository = Arc::clone(&self.invoice_repository);
+        let stocktaking_repository = Arc::clone(&self.stocktaking_repository);
+        let consumption_repository = Arc::clone(&self.consumption_repository);
+        let waste_repository = Arc::clone(&self.waste_repository);
 
-        StocktakingService::new(supply_repository, stocktaking_repository)
+        RetentionService::new(
+            store_repository,
+            supply_repository,
+            unit_repository,
+            category_repository,
+            location_repository,
+            supplier_repository,
+            journal_repository,
+            purchase_order_repository,
+            transfer_repository,
+            invoice_repository,
+            stocktaking_repository,
+            consumption_repository,
+            waste_repository,
+        )
     }
 }
@@ -0,0 +1,2 @@
+//! Adapters publishing domain events to the rest of the application
+pub mod window;
@@ -0,0 +1,55 @@
+//! This module provides the Tauri-based implementation of the `ForEventPublishing` trait.
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_log::log::warn;
+
+use crate::core::domain::values::event::*;
+use crate::core::required_ports::*;
+
+/// Data of a domain event, emitted to every open window under the event name
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DomainEventData {
+    /// id of the supplier, supply, journal or stocktaking that changed
+    pub id: String,
+    /// name before the change, for renames only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_name: Option<String>,
+    /// name after the change, for renames only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Tauri implementation of `ForEventPublishing`, forwarding events as window events
+pub struct WindowEventPublisher {
+    app: AppHandle,
+}
+
+impl WindowEventPublisher {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+impl ForEventPublishing for WindowEventPublisher {
+    fn publish(&self, event: DomainEvent) {
+        let (previous_name, name) = match &event {
+            DomainEvent::SupplyRenamed {
+                previous_name,
+                name,
+                ..
+            } => (Some(previous_name.to_string()), Some(name.to_string())),
+            _ => (None, None),
+        };
+
+        let data = DomainEventData {
+            id: event.entity_id().to_string(),
+            previous_name,
+            name,
+        };
+
+        if let Err(e) = self.app.emit(event.name(), data) {
+            warn!("failed to emit event {}: {}", event.name(), e);
+        }
+    }
+}
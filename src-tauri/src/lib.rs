@@ -1,6 +1,7 @@
 mod command;
 mod config;
 mod core;
+mod event;
 mod persistence;
 
 use std::{env, fs};
@@ -11,7 +12,10 @@ use tauri_plugin_log::log::Level;
 use tauri_plugin_log::{log, Target, TargetKind};
 
 use crate::command::*;
+use crate::core::domain::values::store::StoreId;
+use crate::core::provided_ports::{PurgeExpiredCommand, TrashUsecase};
 use crate::core::stocker::{Ports, Stocker};
+use crate::event::window::*;
 use crate::persistence::sqlite::*;
 
 const DB_NAME: &str = "stocker.db";
@@ -44,7 +48,7 @@ pub fn run() {
             // Migrate database
             migrate(db_path.to_string_lossy())?;
 
-            // Plug Stocker with SQLite implementations
+            // Plug Stocker with SQLite implementations and window events
             let stocker = Stocker::plug(Ports {
                 for_supply_persistence: SqliteSupplyRepository::new(db_path.to_string_lossy()),
                 for_supplier_persistence: SqliteSupplierRepository::new(db_path.to_string_lossy()),
@@ -52,8 +56,49 @@ pub fn run() {
                 for_stocktaking_persistence: SqliteStocktakingRepository::new(
                     db_path.to_string_lossy(),
                 ),
+                for_unit_persistence: SqliteUnitRepository::new(db_path.to_string_lossy()),
+                for_consumption_persistence: SqliteConsumptionRepository::new(
+                    db_path.to_string_lossy(),
+                ),
+                for_category_persistence: SqliteCategoryRepository::new(db_path.to_string_lossy()),
+                for_purchase_order_persistence: SqlitePurchaseOrderRepository::new(
+                    db_path.to_string_lossy(),
+                ),
+                for_waste_persistence: SqliteWasteRepository::new(db_path.to_string_lossy()),
+                for_storage_location_persistence: SqliteStorageLocationRepository::new(
+                    db_path.to_string_lossy(),
+                ),
+                for_store_persistence: SqliteStoreRepository::new(db_path.to_string_lossy()),
+                for_transfer_persistence: SqliteTransferRepository::new(db_path.to_string_lossy()),
+                for_supplier_invoice_persistence: SqliteSupplierInvoiceRepository::new(
+                    db_path.to_string_lossy(),
+                ),
+                for_accounting_period_persistence: SqliteAccountingPeriodRepository::new(
+                    db_path.to_string_lossy(),
+                ),
+                for_audit_persistence: SqliteAuditRepository::new(db_path.to_string_lossy()),
+                for_event_publishing: WindowEventPublisher::new(app.handle().clone()),
             });
 
+            // Apply configured rounding of computed amounts, valuation method, price alert, store and operator
+            let config = read_config(app.handle())?;
+
+            stocker.set_rounding_policy(rounding_policy(&config.rounding)?);
+            stocker.set_valuation_method(valuation_method(config.valuation_method));
+            stocker.set_price_alert_rule(price_alert_rule(&config.price_alert)?);
+
+            if let Some(store_id) = &config.current_store_id {
+                stocker.set_current_store(StoreId::new(store_id)?);
+            }
+
+            stocker.set_operator(operator(config.operator_name.as_deref())?);
+
+            // Purge deleted records kept in the trash beyond the retention period
+            stocker.trash_usecase().purge_expired(PurgeExpiredCommand {
+                now: Local::now().timestamp_millis(),
+                retention_days: config.trash_retention_days,
+            })?;
+
             // Register application core to state manager
             app.manage(stocker);
 
@@ -85,7 +130,47 @@ pub fn run() {
             get_supply_by_id,
             register_supply,
             update_supply,
+            set_supply_reorder_levels,
+            set_supply_offers,
             delete_supply,
+            list_deleted_supplies,
+            restore_supply,
+            purge_supply,
+            // unit commands
+            list_all_units,
+            register_unit,
+            update_unit,
+            delete_unit,
+            list_deleted_units,
+            restore_unit,
+            purge_unit,
+            // category commands
+            list_all_categories,
+            register_category,
+            update_category,
+            delete_category,
+            list_deleted_categories,
+            restore_category,
+            purge_category,
+            // storage location commands
+            list_all_storage_locations,
+            register_storage_location,
+            update_storage_location,
+            delete_storage_location,
+            list_deleted_storage_locations,
+            restore_storage_location,
+            purge_storage_location,
+            place_supply_in_location,
+            // store commands
+            list_all_stores,
+            register_store,
+            update_store,
+            delete_store,
+            list_deleted_stores,
+            restore_store,
+            purge_store,
+            get_current_store,
+            switch_store,
             // supplier commands
             list_all_suppliers,
             get_supplier_by_id,
@@ -93,6 +178,9 @@ pub fn run() {
             update_supplier,
             search_suppliers,
             delete_supplier,
+            list_deleted_suppliers,
+            restore_supplier,
+            purge_supplier,
             // journal commands
             list_all_journals,
             get_journal_by_id,
@@ -100,7 +188,60 @@ pub fn run() {
             record_journal,
             update_journal,
             search_journals,
+            aggregate_journal_quantities,
+            get_price_history,
+            get_journal_category_totals,
             delete_journal,
+            list_deleted_journals,
+            restore_journal,
+            purge_journal,
+            list_journal_revisions,
+            diff_journal_revisions,
+            revert_journal,
+            // purchase order commands
+            list_all_purchase_orders,
+            get_purchase_order_by_id,
+            search_purchase_orders,
+            draft_purchase_order,
+            revise_purchase_order,
+            send_purchase_order,
+            cancel_purchase_order,
+            receive_purchase_order,
+            delete_purchase_order,
+            list_deleted_purchase_orders,
+            restore_purchase_order,
+            purge_purchase_order,
+            // transfer commands
+            list_all_transfers,
+            get_transfer_by_id,
+            search_transfers,
+            record_transfer,
+            update_transfer,
+            delete_transfer,
+            list_deleted_transfers,
+            restore_transfer,
+            purge_transfer,
+            download_transfer_slip,
+            download_journal_csv,
+            // payable commands
+            list_all_supplier_invoices,
+            get_supplier_invoice_by_id,
+            search_supplier_invoices,
+            record_supplier_invoice,
+            update_supplier_invoice,
+            delete_supplier_invoice,
+            list_deleted_supplier_invoices,
+            restore_supplier_invoice,
+            purge_supplier_invoice,
+            record_supplier_payment,
+            cancel_supplier_payment,
+            get_payable_balances,
+            // accounting period commands
+            list_all_accounting_periods,
+            close_accounting_period,
+            reopen_accounting_period,
+            // audit log commands
+            search_audit_log,
             // stocktaking commands
             list_all_stocktakings,
             get_stocktaking_by_id,
@@ -108,11 +249,56 @@ pub fn run() {
             record_stocktaking,
             update_stocktaking,
             search_stocktakings,
+            aggregate_stocktaking_quantities,
+            get_stocktaking_category_totals,
             delete_stocktaking,
+            list_deleted_stocktakings,
+            restore_stocktaking,
+            purge_stocktaking,
+            list_stocktaking_revisions,
+            diff_stocktaking_revisions,
+            revert_stocktaking,
             download_stocktaking_csv,
+            get_stocktaking_count_sheet,
+            // consumption commands
+            list_all_consumptions,
+            search_consumptions,
+            record_consumption,
+            delete_consumption,
+            list_deleted_consumptions,
+            restore_consumption,
+            purge_consumption,
+            // waste commands
+            list_all_wastes,
+            search_wastes,
+            record_waste,
+            delete_waste,
+            list_deleted_wastes,
+            restore_waste,
+            purge_waste,
+            get_waste_summary,
+            // stock ledger commands
+            get_stock_on_hand,
+            get_stock_variance,
+            get_expiring_lots,
+            // reorder commands
+            get_reorder_suggestions,
+            download_reorder_csv,
+            // report commands
+            get_cost_of_goods_sold,
             // config commands
             get_current_theme,
             set_theme,
+            get_rounding,
+            set_rounding,
+            get_valuation_method,
+            set_valuation_method,
+            get_price_alert,
+            set_price_alert,
+            get_trash_retention_days,
+            set_trash_retention_days,
+            get_operator_name,
+            set_operator_name,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
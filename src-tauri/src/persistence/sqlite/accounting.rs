@@ -0,0 +1,333 @@
+//! This module provides the SQLite-based implementation of the `ForAccountingPeriodPersistence` trait.
+use std::path::Path;
+
+use rusqlite::named_params;
+use rusqlite::Connection;
+use rusqlite::Row;
+use rusqlite::Transaction;
+
+use crate::core::domain::entities::accounting::AccountingPeriod;
+use crate::core::domain::values::accounting::*;
+use crate::core::domain::values::store::*;
+use crate::core::required_ports::*;
+use crate::core::Error;
+use crate::core::Result;
+
+/// SQLite implementation of `ForAccountingPeriodPersistence`
+pub struct SqliteAccountingPeriodRepository {
+    db_path: String,
+}
+
+impl SqliteAccountingPeriodRepository {
+    pub fn new(db_path: impl AsRef<str>) -> Self {
+        Self {
+            db_path: Path::new(db_path.as_ref()).to_string_lossy().to_string(),
+        }
+    }
+
+    /// Reads accounting periods of the stores, only `id` if given.
+    fn select(
+        &self,
+        id: Option<&AccountingPeriodId>,
+        store: &StoreContext,
+    ) -> Result<Vec<AccountingPeriod>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    accounting_periods.id,
+                    accounting_periods.name,
+                    accounting_periods.period_start,
+                    accounting_periods.period_end,
+                    accounting_periods.closed,
+                    accounting_period_audit_entries.operation,
+                    accounting_period_audit_entries.performed_at,
+                    accounting_period_audit_entries.note
+                FROM accounting_periods
+                LEFT JOIN accounting_period_audit_entries
+                    ON accounting_period_audit_entries.accounting_period_id = accounting_periods.id
+                WHERE
+                    (:id IS NULL OR accounting_periods.id = :id)
+                    AND
+                    (:store_id IS NULL OR accounting_periods.store_id = :store_id)
+                ORDER BY
+                    accounting_periods.period_start DESC,
+                    accounting_periods.id,
+                    accounting_period_audit_entries.id
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let rows = statement
+            .query_map(
+                named_params! {
+                    ":id": id.map(|id| id.as_str()),
+                    ":store_id": store.scope().map(|id| id.as_str()),
+                },
+                period_row,
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|row| {
+                row.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<(AccountingPeriod, Option<PeriodAuditEntry>)>>>()?;
+
+        Ok(group_periods(rows))
+    }
+}
+
+/// Reads an accounting period and one of its audit entries, if any, from a joined row.
+fn period_row(row: &Row) -> rusqlite::Result<(AccountingPeriod, Option<PeriodAuditEntry>)> {
+    let period = AccountingPeriod::restore(
+        AccountingPeriodId::new(row.get::<_, i64>(0)?.to_string())?,
+        PeriodName::new(row.get::<_, String>(1)?)?,
+        PeriodRange::new(row.get::<_, i64>(2)?, row.get::<_, i64>(3)?)?,
+        row.get::<_, bool>(4)?,
+        vec![],
+    );
+
+    let entry = match row.get::<_, Option<String>>(5)? {
+        Some(operation) => Some(PeriodAuditEntry::new(
+            PeriodOperation::new(operation)?,
+            row.get::<_, i64>(6)?,
+            row.get::<_, Option<String>>(7)?,
+        )),
+        None => None,
+    };
+
+    Ok((period, entry))
+}
+
+/// Groups joined rows into accounting periods, keeping the order of the rows.
+fn group_periods(rows: Vec<(AccountingPeriod, Option<PeriodAuditEntry>)>) -> Vec<AccountingPeriod> {
+    let mut periods: Vec<(AccountingPeriod, Vec<PeriodAuditEntry>)> = Vec::new();
+
+    for (period, entry) in rows {
+        let index = match periods.iter().position(|p| p.0 == period) {
+            Some(index) => index,
+            None => {
+                periods.push((period, vec![]));
+                periods.len() - 1
+            }
+        };
+
+        if let Some(entry) = entry {
+            periods[index].1.push(entry);
+        }
+    }
+
+    periods
+        .into_iter()
+        .map(|(period, audit_trail)| {
+            AccountingPeriod::restore(
+                period.id().clone(),
+                period.name().clone(),
+                *period.range(),
+                period.is_closed(),
+                audit_trail,
+            )
+        })
+        .collect()
+}
+
+/// Inserts the audit trail of an accounting period.
+fn insert_audit_trail(tran: &Transaction, period: &AccountingPeriod) -> Result<()> {
+    let mut statement = tran
+        .prepare(
+            r"
+            INSERT INTO accounting_period_audit_entries (
+                operation,
+                performed_at,
+                note,
+                accounting_period_id
+            ) VALUES (
+                :operation,
+                :performed_at,
+                :note,
+                :accounting_period_id
+            )
+            ",
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to prepare statement: {}", e)))?;
+
+    for entry in period.audit_trail() {
+        statement
+            .execute(named_params! {
+                ":operation": entry.operation().as_str(),
+                ":performed_at": entry.performed_at(),
+                ":note": entry.note(),
+                ":accounting_period_id": period.id().as_str(),
+            })
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to execute statement: {}", e))
+            })?;
+    }
+
+    Ok(())
+}
+
+/// Implementation of `ForAccountingPeriodPersistence` for `SqliteAccountingPeriodRepository`
+impl ForAccountingPeriodPersistence for SqliteAccountingPeriodRepository {
+    fn next_id(&self) -> Result<AccountingPeriodId> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let next_id = conn
+            .query_row(
+                r"
+                UPDATE accounting_periods_id_sequence
+                SET value = value + 1
+                WHERE name = 'accounting_period_id'
+                RETURNING value
+                ",
+                [],
+                |r| r.get::<_, i64>(0),
+            )
+            .map_err(|e| Error::InfrastructureError(format!("fail to query: {}", e)))?;
+
+        AccountingPeriodId::new(next_id.to_string())
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<AccountingPeriod>> {
+        self.select(None, store)
+    }
+
+    fn get(
+        &self,
+        store: &StoreContext,
+        id: AccountingPeriodId,
+    ) -> Result<Option<AccountingPeriod>> {
+        let periods = self.select(Some(&id), store)?;
+
+        Ok(periods.into_iter().next())
+    }
+
+    fn add(&self, store: &StoreContext, period: AccountingPeriod) -> Result<()> {
+        let store_id = store.store_id()?;
+
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let tran = conn.transaction().map_err(|e| {
+            Error::InfrastructureError(format!("failed to start transaction: {}", e))
+        })?;
+
+        let result: Result<()> = (|| {
+            tran.execute(
+                r"
+                INSERT INTO accounting_periods (
+                    id,
+                    name,
+                    period_start,
+                    period_end,
+                    closed,
+                    store_id
+                ) VALUES (
+                    :id,
+                    :name,
+                    :period_start,
+                    :period_end,
+                    :closed,
+                    :store_id
+                )
+                ",
+                named_params! {
+                    ":id": period.id().as_str(),
+                    ":name": period.name().as_str(),
+                    ":period_start": period.range().start(),
+                    ":period_end": period.range().end(),
+                    ":closed": period.is_closed(),
+                    ":store_id": store_id.as_str(),
+                },
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            insert_audit_trail(&tran, &period)
+        })();
+
+        if result.is_err() {
+            tran.rollback()
+                .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+            return result;
+        }
+
+        tran.commit()
+            .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn save(&self, store: &StoreContext, period: AccountingPeriod) -> Result<()> {
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let tran = conn.transaction().map_err(|e| {
+            Error::InfrastructureError(format!("failed to start transaction: {}", e))
+        })?;
+
+        let result: Result<()> = (|| {
+            let updated = tran
+                .execute(
+                    r"
+                    UPDATE accounting_periods
+                    SET
+                        name = :name,
+                        period_start = :period_start,
+                        period_end = :period_end,
+                        closed = :closed
+                    WHERE
+                        id = :id
+                        AND
+                        (:store_id IS NULL OR store_id = :store_id)
+                    ",
+                    named_params! {
+                        ":id": period.id().as_str(),
+                        ":store_id": store.scope().map(|id| id.as_str()),
+                        ":name": period.name().as_str(),
+                        ":period_start": period.range().start(),
+                        ":period_end": period.range().end(),
+                        ":closed": period.is_closed(),
+                    },
+                )
+                .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            // a period of another store is left as it is
+            if updated == 0 {
+                return Ok(());
+            }
+
+            tran.execute(
+                r"
+                DELETE FROM accounting_period_audit_entries
+                WHERE accounting_period_id = :id
+                ",
+                named_params! {
+                    ":id": period.id().as_str(),
+                },
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            insert_audit_trail(&tran, &period)
+        })();
+
+        if result.is_err() {
+            tran.rollback()
+                .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+            return result;
+        }
+
+        tran.commit()
+            .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+        Ok(())
+    }
+}
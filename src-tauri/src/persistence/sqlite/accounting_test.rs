@@ -0,0 +1,118 @@
+use std::fs::{self};
+use std::path::Path;
+
+use rusqlite::Connection;
+use scopeguard::defer;
+
+use crate::core::domain::entities::accounting::AccountingPeriod;
+use crate::core::domain::values::accounting::*;
+use crate::core::domain::values::store::*;
+use crate::core::required_ports::*;
+use crate::persistence::sqlite::{migrate, SqliteAccountingPeriodRepository};
+
+#[test]
+fn accounting_period_repository_test() {
+    let tmp_path = Path::new("tmp/accounting_period_repository_test.db");
+
+    defer! {
+        fs::remove_file(tmp_path).unwrap();
+    }
+
+    if let Some(parent) = tmp_path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+
+    migrate(tmp_path.to_string_lossy()).unwrap();
+
+    let conn = Connection::open(tmp_path).unwrap();
+
+    conn.execute("INSERT INTO stores (id, name) VALUES (2, '駅前店');", [])
+        .unwrap();
+
+    let repository = SqliteAccountingPeriodRepository::new(tmp_path.to_string_lossy());
+
+    let main = StoreContext::Store(StoreId::new("1").unwrap());
+    let branch = StoreContext::Store(StoreId::new("2").unwrap());
+
+    let next_id = repository.next_id().unwrap();
+
+    assert_eq!(next_id, AccountingPeriodId::new("1").unwrap());
+
+    let mut april = AccountingPeriod::close(
+        next_id,
+        PeriodName::new("2024-04").unwrap(),
+        PeriodRange::new(1000, 1999).unwrap(),
+        5000,
+        Some("monthly closing".into()),
+    );
+
+    repository.add(&main, april.clone()).unwrap();
+
+    let may = AccountingPeriod::close(
+        repository.next_id().unwrap(),
+        PeriodName::new("2024-05").unwrap(),
+        PeriodRange::new(2000, 2999).unwrap(),
+        6000,
+        None,
+    );
+
+    repository.add(&main, may.clone()).unwrap();
+
+    let branch_april = AccountingPeriod::close(
+        repository.next_id().unwrap(),
+        PeriodName::new("2024-04").unwrap(),
+        PeriodRange::new(1000, 1999).unwrap(),
+        5000,
+        None,
+    );
+
+    repository.add(&branch, branch_april.clone()).unwrap();
+
+    // the latest first
+    let periods = repository.list(&main).unwrap();
+
+    assert_eq!(periods.len(), 2);
+    assert_eq!(periods[0], may);
+    assert_eq!(periods[1], april);
+    assert_eq!(periods[1].audit_trail(), april.audit_trail());
+
+    assert_eq!(repository.list(&branch).unwrap(), vec![branch_april]);
+    assert_eq!(
+        repository.list(&StoreContext::Consolidated).unwrap().len(),
+        3
+    );
+
+    april.reopen(7000, Some("correction".into())).unwrap();
+
+    repository
+        .save(&StoreContext::default(), april.clone())
+        .unwrap();
+
+    let saved = repository
+        .get(&StoreContext::default(), april.id().clone())
+        .unwrap()
+        .unwrap();
+
+    assert!(!saved.is_closed());
+    assert_eq!(saved.audit_trail().len(), 2);
+    assert_eq!(saved.audit_trail()[1].operation(), PeriodOperation::Reopen);
+    assert_eq!(saved.audit_trail()[1].note(), Some("correction"));
+    assert_eq!(saved.audit_trail(), april.audit_trail());
+
+    assert!(repository
+        .get(
+            &StoreContext::default(),
+            AccountingPeriodId::new("100").unwrap()
+        )
+        .unwrap()
+        .is_none());
+
+    // periods of another store are not read
+    assert!(repository
+        .get(
+            &StoreContext::Store(StoreId::new("2").unwrap()),
+            april.id().clone()
+        )
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,171 @@
+//! This module provides the SQLite-based implementation of the `ForAuditPersistence` trait.
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::named_params;
+use rusqlite::Connection;
+use rusqlite::Row;
+
+use crate::core::domain::entities::audit::AuditEntry;
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::store::*;
+use crate::core::required_ports::*;
+use crate::core::Error;
+use crate::core::Result;
+
+/// SQLite implementation of `ForAuditPersistence`
+pub struct SqliteAuditRepository {
+    db_path: String,
+}
+
+impl SqliteAuditRepository {
+    pub fn new(db_path: impl AsRef<str>) -> Self {
+        Self {
+            db_path: Path::new(db_path.as_ref()).to_string_lossy().to_string(),
+        }
+    }
+}
+
+/// Reads an audit log entry from a row.
+fn audit_entry_row(row: &Row) -> rusqlite::Result<AuditEntry> {
+    let change = AuditChange::new(
+        AuditEntity::new(row.get::<_, String>(1)?)?,
+        row.get::<_, String>(2)?,
+        AuditOperation::new(row.get::<_, String>(3)?)?,
+        row.get::<_, Option<String>>(4)?
+            .map(OperatorName::new)
+            .transpose()?,
+        row.get::<_, Option<String>>(6)?.map(AuditSnapshot::new),
+        row.get::<_, Option<String>>(7)?.map(AuditSnapshot::new),
+    )?;
+
+    Ok(AuditEntry::restore(
+        AuditEntryId::new(row.get::<_, i64>(0)?.to_string())?,
+        LoggedDateTime::new(row.get::<_, i64>(5)?),
+        change,
+    ))
+}
+
+/// Appends the change, if given, to the log of the store.
+///
+/// Repositories pass the connection of the change they save, so both are saved in one transaction.
+pub(super) fn insert_change(
+    conn: &Connection,
+    store: &StoreContext,
+    change: Option<&AuditChange>,
+) -> Result<()> {
+    let change = match change {
+        Some(change) => change,
+        None => return Ok(()),
+    };
+
+    let store_id = store.store_id()?;
+
+    conn.execute(
+        r"
+        INSERT INTO audit_log (
+            entity,
+            entity_id,
+            operation,
+            operator,
+            logged_at,
+            before_snapshot,
+            after_snapshot,
+            store_id
+        ) VALUES (
+            :entity,
+            :entity_id,
+            :operation,
+            :operator,
+            :logged_at,
+            :before_snapshot,
+            :after_snapshot,
+            :store_id
+        )
+        ",
+        named_params! {
+            ":entity": change.entity().as_str(),
+            ":entity_id": change.entity_id(),
+            ":operation": change.operation().as_str(),
+            ":operator": change.operator().map(|operator| operator.as_str()),
+            ":logged_at": Utc::now().timestamp_millis(),
+            ":before_snapshot": change.before().map(|before| before.as_str()),
+            ":after_snapshot": change.after().map(|after| after.as_str()),
+            ":store_id": store_id.as_str(),
+        },
+    )
+    .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+    Ok(())
+}
+
+/// Implementation of `ForAuditPersistence` for `SqliteAuditRepository`
+impl ForAuditPersistence for SqliteAuditRepository {
+    fn append(&self, store: &StoreContext, change: AuditChange) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        insert_change(&conn, store, Some(&change))
+    }
+
+    fn find(&self, store: &StoreContext, query: FindAuditEntriesQuery) -> Result<Vec<AuditEntry>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    id,
+                    entity,
+                    entity_id,
+                    operation,
+                    operator,
+                    logged_at,
+                    before_snapshot,
+                    after_snapshot
+                FROM audit_log
+                WHERE
+                    (:store_id IS NULL OR store_id = :store_id)
+                    AND
+                    (:entity IS NULL OR entity = :entity)
+                    AND
+                    (:entity_id IS NULL OR entity_id = :entity_id)
+                    AND
+                    (:start IS NULL OR :start <= logged_at)
+                    AND
+                    (:end IS NULL OR logged_at <= :end)
+                    AND
+                    (:operator IS NULL OR operator = :operator)
+                ORDER BY
+                    logged_at DESC,
+                    id DESC
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let entries = statement
+            .query_map(
+                named_params! {
+                    ":store_id": store.scope().map(|id| id.as_str()),
+                    ":entity": query.entity.map(|entity| entity.as_str().to_string()),
+                    ":entity_id": query.entity_id.as_ref().map(|id| id.trim()),
+                    ":start": query.period_start.map(|start| start.as_i64()),
+                    ":end": query.period_end.map(|end| end.as_i64()),
+                    ":operator": query.operator.as_ref().map(|operator| operator.as_str()),
+                },
+                audit_entry_row,
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|row| {
+                row.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<AuditEntry>>>()?;
+
+        Ok(entries)
+    }
+}
@@ -0,0 +1,211 @@
+use std::fs::{self};
+use std::path::Path;
+
+use rusqlite::Connection;
+use scopeguard::defer;
+
+use crate::core::domain::entities::stock::Supplier;
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::required_ports::*;
+use crate::persistence::sqlite::{migrate, SqliteAuditRepository, SqliteSupplierRepository};
+
+#[test]
+fn audit_repository_test() {
+    let tmp_path = Path::new("tmp/audit_repository_test.db");
+
+    defer! {
+        fs::remove_file(tmp_path).unwrap();
+    }
+
+    if let Some(parent) = tmp_path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+
+    migrate(tmp_path.to_string_lossy()).unwrap();
+
+    let conn = Connection::open(tmp_path).unwrap();
+
+    conn.execute("INSERT INTO stores (id, name) VALUES (2, '駅前店');", [])
+        .unwrap();
+
+    let repository = SqliteAuditRepository::new(tmp_path.to_string_lossy());
+
+    let main = StoreContext::Store(StoreId::new("1").unwrap());
+    let branch = StoreContext::Store(StoreId::new("2").unwrap());
+
+    let sato = OperatorName::new("Sato").unwrap();
+
+    let register = AuditChange::new(
+        AuditEntity::Supplier,
+        "1",
+        AuditOperation::Register,
+        Some(sato.clone()),
+        None,
+        Some(AuditSnapshot::new(r#"{"id":"1","name":"SupplierA"}"#)),
+    )
+    .unwrap();
+
+    let update = AuditChange::new(
+        AuditEntity::Supplier,
+        "1",
+        AuditOperation::Update,
+        None,
+        Some(AuditSnapshot::new(r#"{"id":"1","name":"SupplierA"}"#)),
+        Some(AuditSnapshot::new(r#"{"id":"1","name":"SupplierB"}"#)),
+    )
+    .unwrap();
+
+    let record = AuditChange::new(
+        AuditEntity::Journal,
+        "1",
+        AuditOperation::Record,
+        Some(sato.clone()),
+        None,
+        Some(AuditSnapshot::new(r#"{"id":"1"}"#)),
+    )
+    .unwrap();
+
+    repository.append(&main, register.clone()).unwrap();
+    repository.append(&main, update.clone()).unwrap();
+    repository.append(&branch, record.clone()).unwrap();
+
+    let all = FindAuditEntriesQuery {
+        entity: None,
+        entity_id: None,
+        period_start: None,
+        period_end: None,
+        operator: None,
+    };
+
+    // the latest first
+    let entries = repository.find(&main, all.clone()).unwrap();
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].change(), &update);
+    assert_eq!(entries[1].change(), &register);
+    assert!(entries[1].logged_at() <= entries[0].logged_at());
+
+    let entries = repository
+        .find(
+            &StoreContext::Consolidated,
+            FindAuditEntriesQuery {
+                operator: Some(sato),
+                ..all.clone()
+            },
+        )
+        .unwrap();
+
+    assert_eq!(entries.len(), 2);
+
+    let entries = repository
+        .find(
+            &StoreContext::Consolidated,
+            FindAuditEntriesQuery {
+                entity: Some(AuditEntity::Journal),
+                entity_id: Some("1".into()),
+                ..all.clone()
+            },
+        )
+        .unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].change(), &record);
+
+    let entries = repository
+        .find(
+            &StoreContext::Consolidated,
+            FindAuditEntriesQuery {
+                period_end: Some(LoggedDateTime::new(0)),
+                ..all
+            },
+        )
+        .unwrap();
+
+    assert!(entries.is_empty());
+
+    // entries are only appended
+    assert!(conn
+        .execute("UPDATE audit_log SET operator = 'Suzuki'", [])
+        .is_err());
+    assert!(conn.execute("DELETE FROM audit_log", []).is_err());
+}
+
+#[test]
+fn audit_in_transaction_test() {
+    let tmp_path = Path::new("tmp/audit_in_transaction_test.db");
+
+    defer! {
+        fs::remove_file(tmp_path).unwrap();
+    }
+
+    if let Some(parent) = tmp_path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+
+    migrate(tmp_path.to_string_lossy()).unwrap();
+
+    let repository = SqliteAuditRepository::new(tmp_path.to_string_lossy());
+    let supplier_repository = SqliteSupplierRepository::new(tmp_path.to_string_lossy());
+
+    let main = StoreContext::default();
+
+    let register = |supplier_id: &str| {
+        AuditChange::new(
+            AuditEntity::Supplier,
+            supplier_id,
+            AuditOperation::Register,
+            None,
+            None,
+            Some(AuditSnapshot::new(format!(r#"{{"id":"{}"}}"#, supplier_id))),
+        )
+        .unwrap()
+    };
+
+    let supplier = |supplier_id: &str| {
+        Supplier::restore(
+            SupplierId::new(supplier_id).unwrap(),
+            SupplierName::new("SupplierA").unwrap(),
+            None,
+            false,
+        )
+    };
+
+    // the change is logged with the supplier
+    supplier_repository
+        .add(&main, supplier("1"), Some(register("1")))
+        .unwrap();
+
+    let entries = repository
+        .find(
+            &main,
+            FindAuditEntriesQuery {
+                entity: None,
+                entity_id: None,
+                period_start: None,
+                period_end: None,
+                operator: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].change(), &register("1"));
+
+    // and the supplier is not saved when the change cannot be logged
+    Connection::open(tmp_path)
+        .unwrap()
+        .execute("DROP TABLE audit_log", [])
+        .unwrap();
+
+    assert!(supplier_repository
+        .add(&main, supplier("2"), Some(register("2")))
+        .is_err());
+
+    let supplier = supplier_repository
+        .get(&main, SupplierId::new("2").unwrap())
+        .unwrap();
+
+    assert!(supplier.is_none());
+}
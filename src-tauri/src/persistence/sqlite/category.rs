@@ -0,0 +1,244 @@
+//! This module provides the SQLite-based implementation of the `ForCategoryPersistence` trait.
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::named_params;
+use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+use rusqlite::Row;
+
+use crate::core::domain::entities::stock::Category;
+use crate::core::domain::values::category::*;
+use crate::core::domain::values::trash::*;
+use crate::core::required_ports::*;
+use crate::core::Error;
+use crate::core::Result;
+
+use super::trash;
+
+/// SQLite implementation of `ForCategoryPersistence`
+pub struct SqliteCategoryRepository {
+    db_path: String,
+}
+
+impl SqliteCategoryRepository {
+    pub fn new(db_path: impl AsRef<str>) -> Self {
+        Self {
+            db_path: Path::new(db_path.as_ref()).to_string_lossy().to_string(),
+        }
+    }
+}
+
+/// Reads a category from a row of id, name and parent id.
+fn category_row(row: &Row) -> rusqlite::Result<Category> {
+    Ok(Category::restore(
+        CategoryId::new(row.get::<_, i64>(0)?.to_string())?,
+        CategoryName::new(row.get::<_, String>(1)?)?,
+        row.get::<_, Option<i64>>(2)?
+            .map(|parent_id| CategoryId::new(parent_id.to_string()))
+            .transpose()?,
+    ))
+}
+
+/// Implementation of `ForCategoryPersistence` for `SqliteCategoryRepository`
+impl ForCategoryPersistence for SqliteCategoryRepository {
+    fn next_id(&self) -> Result<CategoryId> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let next_id = conn
+            .query_row(
+                r"
+                UPDATE categories_id_sequence
+                SET value = value + 1
+                WHERE name = 'category_id'
+                RETURNING value
+                ",
+                [],
+                |r| r.get::<_, i64>(0),
+            )
+            .map_err(|e| Error::InfrastructureError(format!("fail to query: {}", e)))?;
+
+        let next_id = CategoryId::new(next_id.to_string())?;
+
+        Ok(next_id)
+    }
+
+    fn list(&self) -> Result<Vec<Category>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    id,
+                    name,
+                    parent_id
+                FROM categories
+                WHERE
+                    deleted_at IS NULL
+                ORDER BY id
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let categories = statement
+            .query_map([], category_row)
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|category| {
+                category.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<Category>>>()?;
+
+        Ok(categories)
+    }
+
+    fn get(&self, id: CategoryId) -> Result<Option<Category>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let category = conn
+            .query_row(
+                r"
+                SELECT
+                    id,
+                    name,
+                    parent_id
+                FROM categories
+                WHERE
+                    id = :id
+                    AND
+                    deleted_at IS NULL
+                ",
+                named_params! {
+                    ":id": id.as_str(),
+                },
+                category_row,
+            )
+            .optional()
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
+
+        Ok(category)
+    }
+
+    fn add(&self, category: Category) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.execute(
+            r"
+            INSERT INTO categories (
+                id,
+                name,
+                parent_id
+            ) VALUES (
+                :id,
+                :name,
+                :parent_id
+            )
+            ",
+            named_params! {
+                ":id": category.id().as_str(),
+                ":name": category.name().as_str(),
+                ":parent_id": category.parent_id().map(|parent_id| parent_id.as_str()),
+            },
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to insert new category: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn save(&self, category: Category) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.execute(
+            r"
+            UPDATE categories
+            SET
+                name = :name,
+                parent_id = :parent_id
+            WHERE id = :id
+            ",
+            named_params! {
+                ":id": category.id().as_str(),
+                ":name": category.name().as_str(),
+                ":parent_id": category.parent_id().map(|parent_id| parent_id.as_str()),
+            },
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn delete(&self, id: CategoryId) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.execute(
+            r"
+            UPDATE categories
+            SET deleted_at = :deleted_at
+            WHERE id = :id
+            ",
+            named_params! {
+                ":id": id.as_str(),
+                ":deleted_at": Utc::now().timestamp_millis(),
+            },
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<Trashed<Category>>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    id,
+                    name,
+                    parent_id
+                FROM categories
+                WHERE
+                    deleted_at IS NOT NULL
+                ORDER BY id
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let categories = statement
+            .query_map([], category_row)
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|category| {
+                category.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<Category>>>()?;
+
+        let deleted_ats = trash::deleted_ats(&conn, "categories")?;
+
+        Ok(trash::trashed(categories, &deleted_ats, |category| {
+            category.id().as_str()
+        }))
+    }
+
+    fn restore(&self, id: CategoryId) -> Result<()> {
+        trash::restore(&self.db_path, "categories", id.as_str(), None, None)
+    }
+
+    fn purge(&self, id: CategoryId) -> Result<()> {
+        trash::purge(&self.db_path, "categories", id.as_str(), None, &[], None)
+    }
+}
@@ -0,0 +1,93 @@
+use std::fs::{self};
+use std::path::Path;
+
+use scopeguard::defer;
+
+use crate::core::domain::entities::stock::Category;
+use crate::core::domain::values::category::*;
+use crate::core::required_ports::*;
+use crate::persistence::sqlite::{migrate, SqliteCategoryRepository};
+
+#[test]
+fn category_repository_test() {
+    let tmp_path = Path::new("tmp/category_repository_test.db");
+
+    defer! {
+        fs::remove_file(tmp_path).unwrap();
+    }
+
+    if let Some(parent) = tmp_path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+
+    migrate(tmp_path.to_string_lossy()).unwrap();
+
+    let repository = SqliteCategoryRepository::new(tmp_path.to_string_lossy());
+
+    let food = Category::restore(
+        repository.next_id().unwrap(),
+        CategoryName::new("食材").unwrap(),
+        None,
+    );
+
+    let vegetables = Category::restore(
+        repository.next_id().unwrap(),
+        CategoryName::new("野菜").unwrap(),
+        Some(food.id().clone()),
+    );
+
+    assert_eq!(vegetables.id(), &CategoryId::new("2").unwrap());
+
+    repository.add(food.clone()).unwrap();
+    repository.add(vegetables.clone()).unwrap();
+
+    let category = repository.get(CategoryId::new("2").unwrap()).unwrap();
+
+    assert!(category.is_some_and(|category| {
+        assert_eq!(category.name(), &CategoryName::new("野菜").unwrap());
+        assert_eq!(category.parent_id(), Some(&CategoryId::new("1").unwrap()));
+        true
+    }));
+
+    let mut vegetables = vegetables;
+
+    vegetables.rename(CategoryName::new("青果").unwrap());
+    vegetables.move_to(None).unwrap();
+
+    repository.save(vegetables).unwrap();
+
+    let category = repository.get(CategoryId::new("2").unwrap()).unwrap();
+
+    assert!(category.is_some_and(|category| {
+        assert_eq!(category.name(), &CategoryName::new("青果").unwrap());
+        assert_eq!(category.parent_id(), None);
+        true
+    }));
+
+    repository.delete(CategoryId::new("2").unwrap()).unwrap();
+
+    let categories = repository.list().unwrap();
+
+    assert_eq!(categories, vec![food.clone()]);
+
+    let trashed = repository.list_deleted().unwrap();
+
+    assert_eq!(trashed.len(), 1);
+    assert_eq!(trashed[0].item().id(), &CategoryId::new("2").unwrap());
+
+    repository.restore(CategoryId::new("2").unwrap()).unwrap();
+
+    assert_eq!(repository.list().unwrap().len(), 2);
+    assert!(repository.list_deleted().unwrap().is_empty());
+
+    // only records in the trash are purged
+    repository.purge(CategoryId::new("2").unwrap()).unwrap();
+
+    assert_eq!(repository.list().unwrap().len(), 2);
+
+    repository.delete(CategoryId::new("2").unwrap()).unwrap();
+    repository.purge(CategoryId::new("2").unwrap()).unwrap();
+
+    assert_eq!(repository.list().unwrap(), vec![food]);
+    assert!(repository.list_deleted().unwrap().is_empty());
+}
@@ -0,0 +1,315 @@
+//! This module provides the SQLite-based implementation of the `ForConsumptionPersistence` trait.
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::named_params;
+use rusqlite::Connection;
+use rusqlite::Row;
+
+use crate::core::domain::entities::stock::Consumption;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::trash::*;
+use crate::core::required_ports::*;
+use crate::core::Error;
+use crate::core::Result;
+
+use super::trash;
+
+/// SQLite implementation of `ForConsumptionPersistence`
+pub struct SqliteConsumptionRepository {
+    db_path: String,
+}
+
+impl SqliteConsumptionRepository {
+    pub fn new(db_path: impl AsRef<str>) -> Self {
+        Self {
+            db_path: Path::new(db_path.as_ref()).to_string_lossy().to_string(),
+        }
+    }
+
+    /// Reads consumptions of the stores matching the query, those in the trash if `trashed`.
+    fn select(
+        &self,
+        store: &StoreContext,
+        trashed: bool,
+        query: FindConsumptionsQuery,
+    ) -> Result<Vec<Consumption>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    consumptions.id,
+                    consumptions.recorded_at,
+                    consumption_records.supply_id,
+                    consumption_records.supply_name,
+                    consumption_records.unit_name,
+                    consumption_records.quantity
+                FROM consumptions
+                INNER JOIN consumption_records
+                    ON consumption_records.consumption_id = consumptions.id
+                WHERE
+                    (:start IS NULL OR :start <= consumptions.recorded_at)
+                    AND
+                    (:end IS NULL OR consumptions.recorded_at <= :end)
+                    AND
+                    (:store_id IS NULL OR consumptions.store_id = :store_id)
+                    AND
+                    (consumptions.deleted_at IS NOT NULL) = :trashed
+                ORDER BY consumptions.recorded_at DESC, consumptions.id, consumption_records.rowid
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let rows = statement
+            .query_map(
+                named_params! {
+                    ":trashed": trashed,
+                    ":start": query.period_start.as_ref().map(|start| start.as_i64()),
+                    ":end": query.period_end.as_ref().map(|end| end.as_i64()),
+                    ":store_id": store.scope().map(|id| id.as_str()),
+                },
+                consumption_row,
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|row| {
+                row.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<(Consumption, ConsumptionRecord)>>>()?;
+
+        Ok(group_consumptions(rows))
+    }
+}
+
+/// Reads a consumption and one of its records from a joined row.
+fn consumption_row(row: &Row) -> rusqlite::Result<(Consumption, ConsumptionRecord)> {
+    let consumption = Consumption::restore(
+        ConsumptionId::new(row.get::<_, i64>(0)?.to_string())?,
+        ConsumedDateTime::new(row.get::<_, i64>(1)?),
+        vec![],
+    );
+
+    let record = ConsumptionRecord::new(
+        SupplyId::new(row.get::<_, i64>(2)?.to_string())?,
+        SupplyName::new(row.get::<_, String>(3)?)?,
+        UnitName::new(row.get::<_, String>(4)?)?,
+        ConsumptionQuantity::from_quantity(Quantity::from_raw(row.get::<_, i64>(5)?))?,
+    );
+
+    Ok((consumption, record))
+}
+
+/// Groups joined rows into consumptions, keeping the order of the rows.
+fn group_consumptions(rows: Vec<(Consumption, ConsumptionRecord)>) -> Vec<Consumption> {
+    let mut consumptions: Vec<(Consumption, Vec<ConsumptionRecord>)> = Vec::new();
+
+    for (consumption, record) in rows {
+        match consumptions.iter_mut().find(|c| c.0 == consumption) {
+            Some(c) => c.1.push(record),
+            None => consumptions.push((consumption, vec![record])),
+        }
+    }
+
+    consumptions
+        .into_iter()
+        .map(|(mut consumption, records)| {
+            consumption.swap_records(records);
+            consumption
+        })
+        .collect()
+}
+
+/// Implementation of `ForConsumptionPersistence` for `SqliteConsumptionRepository`
+impl ForConsumptionPersistence for SqliteConsumptionRepository {
+    fn next_id(&self) -> Result<ConsumptionId> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let next_id = conn
+            .query_row(
+                r"
+                UPDATE consumptions_id_sequence
+                SET value = value + 1
+                WHERE name = 'consumption_id'
+                RETURNING value
+                ",
+                [],
+                |r| r.get::<_, i64>(0),
+            )
+            .map_err(|e| Error::InfrastructureError(format!("fail to query: {}", e)))?;
+
+        let next_id = ConsumptionId::new(next_id.to_string())?;
+
+        Ok(next_id)
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<Consumption>> {
+        self.find(
+            store,
+            FindConsumptionsQuery {
+                period_start: None,
+                period_end: None,
+            },
+        )
+    }
+
+    fn find(&self, store: &StoreContext, query: FindConsumptionsQuery) -> Result<Vec<Consumption>> {
+        self.select(store, false, query)
+    }
+
+    fn add(&self, store: &StoreContext, consumption: Consumption) -> Result<()> {
+        let store_id = store.store_id()?;
+
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let tran = conn.transaction().map_err(|e| {
+            Error::InfrastructureError(format!("failed to start transaction: {}", e))
+        })?;
+
+        let result: Result<()> = (|| {
+            tran.execute(
+                r"
+                INSERT INTO consumptions (
+                    id,
+                    recorded_at,
+                    store_id
+                ) VALUES (
+                    :id,
+                    :recorded_at,
+                    :store_id
+                )
+                ",
+                named_params! {
+                    ":id": consumption.id().as_str(),
+                    ":recorded_at": consumption.consumed_at().as_i64(),
+                    ":store_id": store_id.as_str(),
+                },
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            let mut statement = tran
+                .prepare(
+                    r"
+                INSERT INTO consumption_records (
+                    supply_id,
+                    supply_name,
+                    unit_name,
+                    quantity,
+                    consumption_id
+                ) VALUES (
+                    :supply_id,
+                    :supply_name,
+                    :unit_name,
+                    :quantity,
+                    :consumption_id
+                )
+                ",
+                )
+                .map_err(|e| {
+                    Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+                })?;
+
+            for record in consumption.records() {
+                statement
+                    .execute(named_params! {
+                        ":supply_id": record.supply_id().as_str(),
+                        ":supply_name": record.supply_name().as_str(),
+                        ":unit_name": record.unit_name().as_str(),
+                        ":quantity": record.quantity().as_quantity().as_raw(),
+                        ":consumption_id": consumption.id().as_str(),
+                    })
+                    .map_err(|e| {
+                        Error::InfrastructureError(format!("failed to execute statement: {}", e))
+                    })?;
+            }
+
+            Ok(())
+        })();
+
+        if result.is_err() {
+            tran.rollback()
+                .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+            return result;
+        }
+
+        tran.commit()
+            .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn delete(&self, store: &StoreContext, id: ConsumptionId) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.execute(
+            r"
+            UPDATE consumptions
+            SET deleted_at = :deleted_at
+            WHERE
+                id = :id
+                AND
+                (:store_id IS NULL OR store_id = :store_id)
+            ",
+            named_params! {
+                ":id": id.as_str(),
+                ":deleted_at": Utc::now().timestamp_millis(),
+                ":store_id": store.scope().map(|id| id.as_str()),
+            },
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Consumption>>> {
+        let consumptions = self.select(
+            store,
+            true,
+            FindConsumptionsQuery {
+                period_start: None,
+                period_end: None,
+            },
+        )?;
+
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let deleted_ats = trash::deleted_ats(&conn, "consumptions")?;
+
+        Ok(trash::trashed(consumptions, &deleted_ats, |consumption| {
+            consumption.id().as_str()
+        }))
+    }
+
+    fn restore(&self, store: &StoreContext, id: ConsumptionId) -> Result<()> {
+        trash::restore(
+            &self.db_path,
+            "consumptions",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            None,
+        )
+    }
+
+    fn purge(&self, store: &StoreContext, id: ConsumptionId) -> Result<()> {
+        trash::purge(
+            &self.db_path,
+            "consumptions",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            &[("consumption_records", "consumption_id")],
+            None,
+        )
+    }
+}
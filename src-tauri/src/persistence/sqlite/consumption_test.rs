@@ -0,0 +1,120 @@
+use std::fs::{self};
+use std::path::Path;
+
+use rusqlite::Connection;
+use scopeguard::defer;
+
+use crate::core::domain::entities::stock::Consumption;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::{StoreContext, StoreId};
+use crate::core::required_ports::*;
+use crate::persistence::sqlite::{migrate, SqliteConsumptionRepository};
+
+#[test]
+fn consumption_repository_test() {
+    let tmp_path = Path::new("tmp/consumption_repository_test.db");
+
+    defer! {
+        fs::remove_file(tmp_path).unwrap();
+    }
+
+    if let Some(parent) = tmp_path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+
+    migrate(tmp_path.to_string_lossy()).unwrap();
+
+    let conn = Connection::open(tmp_path).unwrap();
+
+    conn.execute(
+        "INSERT INTO suppliers (id, name) VALUES (1, 'SupplierA');",
+        [],
+    )
+    .unwrap();
+
+    conn.execute(
+        "INSERT INTO supplies (id, name, unit_name, supplier_id) VALUES (1, 'SupplyA', 'g', 1), (2, 'SupplyB', 'kg', 1);",
+        [],
+    )
+    .unwrap();
+
+    let repository = SqliteConsumptionRepository::new(tmp_path.to_string_lossy());
+
+    let next_id = repository.next_id().unwrap();
+
+    assert_eq!(next_id, ConsumptionId::new("1").unwrap());
+
+    let records = vec![
+        ConsumptionRecord::new(
+            SupplyId::new("1").unwrap(),
+            SupplyName::new("SupplyA").unwrap(),
+            UnitName::new("g").unwrap(),
+            ConsumptionQuantity::new(2.5).unwrap(),
+        ),
+        ConsumptionRecord::new(
+            SupplyId::new("2").unwrap(),
+            SupplyName::new("SupplyB").unwrap(),
+            UnitName::new("kg").unwrap(),
+            ConsumptionQuantity::new(1_u32).unwrap(),
+        ),
+    ];
+
+    repository
+        .add(
+            &StoreContext::default(),
+            Consumption::restore(
+                ConsumptionId::new("1").unwrap(),
+                ConsumedDateTime::new(100000),
+                records.clone(),
+            ),
+        )
+        .unwrap();
+
+    let consumptions = repository
+        .find(
+            &StoreContext::default(),
+            FindConsumptionsQuery {
+                period_start: Some(ConsumedDateTime::new(50000)),
+                period_end: Some(ConsumedDateTime::new(150000)),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(consumptions.len(), 1);
+    assert_eq!(
+        consumptions[0].consumed_at(),
+        &ConsumedDateTime::new(100000)
+    );
+    assert_eq!(consumptions[0].records(), &records[..]);
+
+    let consumptions = repository
+        .find(
+            &StoreContext::default(),
+            FindConsumptionsQuery {
+                period_start: Some(ConsumedDateTime::new(150000)),
+                period_end: None,
+            },
+        )
+        .unwrap();
+
+    assert!(consumptions.is_empty());
+
+    // consumptions of another store are neither read nor deleted
+    let branch = StoreContext::Store(StoreId::new("2").unwrap());
+
+    assert!(repository.list(&branch).unwrap().is_empty());
+
+    repository
+        .delete(&branch, ConsumptionId::new("1").unwrap())
+        .unwrap();
+
+    assert_eq!(repository.list(&StoreContext::default()).unwrap().len(), 1);
+
+    repository
+        .delete(&StoreContext::default(), ConsumptionId::new("1").unwrap())
+        .unwrap();
+
+    let consumptions = repository.list(&StoreContext::default()).unwrap();
+
+    assert!(consumptions.is_empty());
+}
@@ -7,11 +7,21 @@ use rusqlite::Connection;
 use rusqlite::OptionalExtension;
 
 use crate::core::domain::entities::stock::Journal;
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::lot::*;
+use crate::core::domain::values::revision::*;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::tax::*;
+use crate::core::domain::values::trash::*;
 use crate::core::required_ports::*;
 use crate::core::Error;
 use crate::core::Result;
 
+use super::audit;
+use super::trash;
+
 /// SQLite implementation of `ForJournalPersistence`
 pub struct SqliteJournalRepository {
     db_path: String,
@@ -49,10 +59,15 @@ impl ForJournalPersistence for SqliteJournalRepository {
         Ok(next_id)
     }
 
-    fn list(&self) -> Result<Vec<crate::core::domain::entities::stock::Journal>> {
+    fn list(
+        &self,
+        store: &StoreContext,
+    ) -> Result<Vec<crate::core::domain::entities::stock::Journal>> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
+        let store_id = store.scope().map(|id| id.as_str());
+
         let mut statement = conn
             .prepare(
                 r"
@@ -67,11 +82,19 @@ impl ForJournalPersistence for SqliteJournalRepository {
                     journal_records.unit_name,
                     journal_records.unit_price,
                     journal_records.quantity,
-                    journal_records.total_price
+                    journal_records.total_price,
+                    journal_records.tax_category,
+                    journal_records.tax_included,
+                    journal_records.tax_price,
+                    journal_records.invoice_registered,
+                    journal_records.lot_number,
+                    journal_records.expiry_date
                 FROM journals
                 INNER JOIN journal_records
                     ON journal_records.journal_id = journals.id
                 WHERE
+                    (:store_id IS NULL OR journals.store_id = :store_id)
+                    AND
                     journals.deleted_at IS NULL
                 ORDER BY journals.recorded_at ASC
                 ",
@@ -81,13 +104,11 @@ impl ForJournalPersistence for SqliteJournalRepository {
             })?;
 
         let mut jorunals = statement
-            .query_map([], |row| {
+            .query_map(named_params! { ":store_id": store_id }, |row| {
                 let journal = Journal::restore(
                     JournalId::new(row.get::<_, i64>(0)?.to_string())?,
                     EntryDateTime::new(row.get::<_, i64>(1)?),
-                    TotalPrice::new(
-                        row.get::<_, i64>(2)? as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                    )?,
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(2)?))?,
                     Vec::new(),
                 );
 
@@ -102,7 +123,7 @@ impl ForJournalPersistence for SqliteJournalRepository {
             .collect::<Result<Vec<Journal>>>()?;
 
         let jorunal_records = statement
-            .query_map([], |row| {
+            .query_map(named_params! { ":store_id": store_id }, |row| {
                 let journal_id = JournalId::new(row.get::<_, i64>(0)?.to_string())
                     .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
 
@@ -112,15 +133,19 @@ impl ForJournalPersistence for SqliteJournalRepository {
                     SupplierId::new(row.get::<_, i64>(5)?.to_string())?,
                     SupplierName::new(row.get::<_, String>(6)?)?,
                     UnitName::new(row.get::<_, String>(7)?)?,
-                    PurchaseUnitPrice::new(
-                        row.get::<_, i64>(8)? as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                    )?,
-                    PurchaseQuantity::new(
-                        row.get::<_, i64>(9)? as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                    )?,
-                    TotalPrice::new(
-                        row.get::<_, i64>(10)? as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                    )?,
+                    PurchaseUnitPrice::from_money(Money::from_minor(row.get::<_, i64>(8)?))?,
+                    PurchaseQuantity::from_quantity(Quantity::from_raw(row.get::<_, i64>(9)?))?,
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(10)?))?,
+                    TaxCategory::new(row.get::<_, String>(11)?)?,
+                    row.get::<_, bool>(12)?,
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(13)?))?,
+                    row.get::<_, bool>(14)?,
+                )
+                .with_lot(
+                    row.get::<_, Option<String>>(15)?
+                        .map(LotNumber::new)
+                        .transpose()?,
+                    row.get::<_, Option<i64>>(16)?.map(ExpiryDate::new),
                 );
 
                 Ok((journal_id, journal_record))
@@ -154,7 +179,7 @@ impl ForJournalPersistence for SqliteJournalRepository {
         Ok(jorunals)
     }
 
-    fn get(&self, id: JournalId) -> Result<Option<Journal>> {
+    fn get(&self, store: &StoreContext, id: JournalId) -> Result<Option<Journal>> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -172,13 +197,21 @@ impl ForJournalPersistence for SqliteJournalRepository {
                     journal_records.unit_name,
                     journal_records.unit_price,
                     journal_records.quantity,
-                    journal_records.total_price
+                    journal_records.total_price,
+                    journal_records.tax_category,
+                    journal_records.tax_included,
+                    journal_records.tax_price,
+                    journal_records.invoice_registered,
+                    journal_records.lot_number,
+                    journal_records.expiry_date
                 FROM journals
                 INNER JOIN journal_records
                     ON journal_records.journal_id = journals.id
                 WHERE
                     journals.id = :id
                     AND
+                    (:store_id IS NULL OR journals.store_id = :store_id)
+                    AND
                     deleted_at IS NULL
                 ",
             )
@@ -190,15 +223,13 @@ impl ForJournalPersistence for SqliteJournalRepository {
             .query_row(
                 named_params! {
                     ":id": id.as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
                 },
                 |row| {
                     let journal = Journal::restore(
                         JournalId::new(row.get::<_, i64>(0)?.to_string())?,
                         EntryDateTime::new(row.get::<_, i64>(1)?),
-                        TotalPrice::new(
-                            row.get::<_, i64>(2)? as f64
-                                / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                        )?,
+                        TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(2)?))?,
                         Vec::new(),
                     );
 
@@ -212,6 +243,7 @@ impl ForJournalPersistence for SqliteJournalRepository {
             .query_map(
                 named_params! {
                     ":id": id.as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
                 },
                 |row| {
                     let journal_record = JournalRecord::new(
@@ -220,18 +252,19 @@ impl ForJournalPersistence for SqliteJournalRepository {
                         SupplierId::new(row.get::<_, i64>(5)?.to_string())?,
                         SupplierName::new(row.get::<_, String>(6)?)?,
                         UnitName::new(row.get::<_, String>(7)?)?,
-                        PurchaseUnitPrice::new(
-                            row.get::<_, i64>(8)? as f64
-                                / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                        )?,
-                        PurchaseQuantity::new(
-                            row.get::<_, i64>(9)? as f64
-                                / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                        )?,
-                        TotalPrice::new(
-                            row.get::<_, i64>(10)? as f64
-                                / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                        )?,
+                        PurchaseUnitPrice::from_money(Money::from_minor(row.get::<_, i64>(8)?))?,
+                        PurchaseQuantity::from_quantity(Quantity::from_raw(row.get::<_, i64>(9)?))?,
+                        TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(10)?))?,
+                        TaxCategory::new(row.get::<_, String>(11)?)?,
+                        row.get::<_, bool>(12)?,
+                        TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(13)?))?,
+                        row.get::<_, bool>(14)?,
+                    )
+                    .with_lot(
+                        row.get::<_, Option<String>>(15)?
+                            .map(LotNumber::new)
+                            .transpose()?,
+                        row.get::<_, Option<i64>>(16)?.map(ExpiryDate::new),
                     );
 
                     Ok(journal_record)
@@ -252,7 +285,7 @@ impl ForJournalPersistence for SqliteJournalRepository {
         Ok(journal)
     }
 
-    fn find(&self, query: FindJournalsQuery) -> Result<Vec<Journal>> {
+    fn find(&self, store: &StoreContext, query: FindJournalsQuery) -> Result<Vec<Journal>> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -270,11 +303,19 @@ impl ForJournalPersistence for SqliteJournalRepository {
                     journal_records.unit_name,
                     journal_records.unit_price,
                     journal_records.quantity,
-                    journal_records.total_price
+                    journal_records.total_price,
+                    journal_records.tax_category,
+                    journal_records.tax_included,
+                    journal_records.tax_price,
+                    journal_records.invoice_registered,
+                    journal_records.lot_number,
+                    journal_records.expiry_date
                 FROM journals
                 INNER JOIN journal_records
                     ON journal_records.journal_id = journals.id
                 WHERE
+                    (:store_id IS NULL OR journals.store_id = :store_id)
+                    AND
                     (:start IS NULL OR :start <= journals.recorded_at)
                     AND
                     (:end IS NULL OR journals.recorded_at <= :end)
@@ -294,6 +335,7 @@ impl ForJournalPersistence for SqliteJournalRepository {
         let mut jorunals = statement
             .query_map(
                 named_params! {
+                    ":store_id": store.scope().map(|id| id.as_str()),
                     ":start": query.period_start.as_ref().and_then(|start| Some(start.as_i64())),
                     ":end": query.period_end.as_ref().and_then(|end| Some(end.as_i64())),
                     ":supply_name": query.supply_name.as_ref().and_then(|name| Some(format!("%{}%", name.as_str()))),
@@ -303,7 +345,7 @@ impl ForJournalPersistence for SqliteJournalRepository {
                     let journal = Journal::restore(
                         JournalId::new(row.get::<_, i64>(0)?.to_string())?,
                         EntryDateTime::new(row.get::<_, i64>(1)?),
-                        TotalPrice::new(row.get::<_, i64>(2)? as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64)?,
+                        TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(2)?))?,
                         Vec::new(),
                     );
 
@@ -320,6 +362,7 @@ impl ForJournalPersistence for SqliteJournalRepository {
 
         let jorunal_records = statement
             .query_map(named_params! {
+                    ":store_id": store.scope().map(|id| id.as_str()),
                     ":start": query.period_start.and_then(|start| Some(start.as_i64())),
                     ":end": query.period_end.and_then(|end| Some(end.as_i64())),
                     ":supply_name": query.supply_name.and_then(|name| Some(format!("%{}%", name.as_str()))),
@@ -334,15 +377,19 @@ impl ForJournalPersistence for SqliteJournalRepository {
                     SupplierId::new(row.get::<_, i64>(5)?.to_string())?,
                     SupplierName::new(row.get::<_, String>(6)?)?,
                     UnitName::new(row.get::<_, String>(7)?)?,
-                    PurchaseUnitPrice::new(
-                        row.get::<_, i64>(8)? as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64
-                    )?,
-                    PurchaseQuantity::new(
-                        row.get::<_, i64>(9)? as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64
-                    )?,
-                    TotalPrice::new(
-                        row.get::<_, i64>(10)? as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64
-                    )?
+                    PurchaseUnitPrice::from_money(Money::from_minor(row.get::<_, i64>(8)?))?,
+                    PurchaseQuantity::from_quantity(Quantity::from_raw(row.get::<_, i64>(9)?))?,
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(10)?))?,
+                    TaxCategory::new(row.get::<_, String>(11)?)?,
+                    row.get::<_, bool>(12)?,
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(13)?))?,
+                    row.get::<_, bool>(14)?,
+                )
+                .with_lot(
+                    row.get::<_, Option<String>>(15)?
+                        .map(LotNumber::new)
+                        .transpose()?,
+                    row.get::<_, Option<i64>>(16)?.map(ExpiryDate::new),
                 );
 
                 Ok((journal_id, journal_record))
@@ -376,7 +423,14 @@ impl ForJournalPersistence for SqliteJournalRepository {
         Ok(jorunals)
     }
 
-    fn add(&self, journal: Journal) -> Result<()> {
+    fn add(
+        &self,
+        store: &StoreContext,
+        journal: Journal,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let store_id = store.store_id()?;
+
         let mut conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -391,11 +445,13 @@ impl ForJournalPersistence for SqliteJournalRepository {
                     INSERT INTO journals (
                         id,
                         recorded_at,
-                        total_price
+                        total_price,
+                        store_id
                     ) VALUES (
                         :id,
                         :recorded_at,
-                        :total_price
+                        :total_price,
+                        :store_id
                     );
                     ",
                 )
@@ -407,7 +463,8 @@ impl ForJournalPersistence for SqliteJournalRepository {
                 .execute(named_params! {
                     ":id": journal.id().as_str(),
                     ":recorded_at": journal.entry_datetime().as_i64(),
-                    ":total_price": (journal.total_price().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
+                    ":total_price": journal.total_price().as_money().as_minor(),
+                    ":store_id": store_id.as_str(),
                 })
                 .map_err(|e| {
                     Error::InfrastructureError(format!("failed to execute statement: {}", e))
@@ -425,6 +482,12 @@ impl ForJournalPersistence for SqliteJournalRepository {
                         unit_price,
                         quantity,
                         total_price,
+                        tax_category,
+                        tax_included,
+                        tax_price,
+                        invoice_registered,
+                        lot_number,
+                        expiry_date,
                         journal_id
                     ) VALUES (
                         :supply_id,
@@ -435,6 +498,12 @@ impl ForJournalPersistence for SqliteJournalRepository {
                         :unit_price,
                         :quantity,
                         :total_price,
+                        :tax_category,
+                        :tax_included,
+                        :tax_price,
+                        :invoice_registered,
+                        :lot_number,
+                        :expiry_date,
                         :journal_id
                     )
                     ",
@@ -451,9 +520,15 @@ impl ForJournalPersistence for SqliteJournalRepository {
                         ":supplier_id": record.supplier_id().as_str(),
                         ":supplier_name": record.supplier_name().as_str(),
                         ":unit_name": record.unit_name().as_str(),
-                        ":unit_price": (record.unit_price().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
-                        ":quantity": (record.quantity().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
-                        ":total_price": (record.total_price().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
+                        ":unit_price": record.unit_price().as_money().as_minor(),
+                        ":quantity": record.quantity().as_quantity().as_raw(),
+                        ":total_price": record.total_price().as_money().as_minor(),
+                        ":tax_category": record.tax_category().as_str(),
+                        ":tax_included": record.is_tax_included(),
+                        ":tax_price": record.tax_price().as_money().as_minor(),
+                        ":invoice_registered": record.is_invoice_registered(),
+                        ":lot_number": record.lot_number().map(|lot_number| lot_number.as_str()),
+                        ":expiry_date": record.expiry_date().map(|expiry_date| expiry_date.as_i64()),
                         ":journal_id": journal.id().as_str(),
                     })
                     .map_err(|e| {
@@ -464,6 +539,8 @@ impl ForJournalPersistence for SqliteJournalRepository {
             Ok(())
         })();
 
+        let result = result.and_then(|_| audit::insert_change(&tran, store, change.as_ref()));
+
         if let Err(e) = result {
             tran.rollback()
                 .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
@@ -477,7 +554,12 @@ impl ForJournalPersistence for SqliteJournalRepository {
         Ok(())
     }
 
-    fn save(&self, journal: Journal) -> Result<()> {
+    fn save(
+        &self,
+        store: &StoreContext,
+        journal: Journal,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
         let mut conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -495,22 +577,30 @@ impl ForJournalPersistence for SqliteJournalRepository {
                         total_price = :total_price
                     WHERE
                         id = :id
+                        AND
+                        (:store_id IS NULL OR store_id = :store_id)
                     ",
                 )
                 .map_err(|e| {
                     Error::InfrastructureError(format!("failed to prepare statement: {}", e))
                 })?;
 
-            statement
+            let updated = statement
                 .execute(named_params! {
                     ":id": journal.id().as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
                     ":recorded_at": journal.entry_datetime().as_i64(),
-                    ":total_price": (journal.total_price().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
+                    ":total_price": journal.total_price().as_money().as_minor(),
                 })
                 .map_err(|e| {
                     Error::InfrastructureError(format!("failed to execute statement: {}", e))
                 })?;
 
+            // a journal of another store is left as it is
+            if updated == 0 {
+                return Ok(());
+            }
+
             let mut statement = tran
                 .prepare(
                     r"
@@ -543,6 +633,12 @@ impl ForJournalPersistence for SqliteJournalRepository {
                         unit_price,
                         quantity,
                         total_price,
+                        tax_category,
+                        tax_included,
+                        tax_price,
+                        invoice_registered,
+                        lot_number,
+                        expiry_date,
                         journal_id
                     ) VALUES (
                         :supply_id,
@@ -553,6 +649,12 @@ impl ForJournalPersistence for SqliteJournalRepository {
                         :unit_price,
                         :quantity,
                         :total_price,
+                        :tax_category,
+                        :tax_included,
+                        :tax_price,
+                        :invoice_registered,
+                        :lot_number,
+                        :expiry_date,
                         :journal_id
                     )
                     ",
@@ -569,9 +671,15 @@ impl ForJournalPersistence for SqliteJournalRepository {
                         ":supplier_id": record.supplier_id().as_str(),
                         ":supplier_name": record.supplier_name().as_str(),
                         ":unit_name": record.unit_name().as_str(),
-                        ":unit_price": (record.unit_price().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
-                        ":quantity": (record.quantity().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
-                        ":total_price": (record.total_price().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
+                        ":unit_price": record.unit_price().as_money().as_minor(),
+                        ":quantity": record.quantity().as_quantity().as_raw(),
+                        ":total_price": record.total_price().as_money().as_minor(),
+                        ":tax_category": record.tax_category().as_str(),
+                        ":tax_included": record.is_tax_included(),
+                        ":tax_price": record.tax_price().as_money().as_minor(),
+                        ":invoice_registered": record.is_invoice_registered(),
+                        ":lot_number": record.lot_number().map(|lot_number| lot_number.as_str()),
+                        ":expiry_date": record.expiry_date().map(|expiry_date| expiry_date.as_i64()),
                         ":journal_id": journal.id().as_str(),
                     })
                     .map_err(|e| {
@@ -582,6 +690,8 @@ impl ForJournalPersistence for SqliteJournalRepository {
             Ok(())
         })();
 
+        let result = result.and_then(|_| audit::insert_change(&tran, store, change.as_ref()));
+
         if let Err(e) = result {
             tran.rollback()
                 .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
@@ -595,7 +705,12 @@ impl ForJournalPersistence for SqliteJournalRepository {
         Ok(())
     }
 
-    fn delete(&self, id: JournalId) -> Result<()> {
+    fn delete(
+        &self,
+        store: &StoreContext,
+        id: JournalId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
         let mut conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -612,6 +727,8 @@ impl ForJournalPersistence for SqliteJournalRepository {
                         deleted_at = :deleted_at
                     WHERE
                         id = :id
+                        AND
+                        (:store_id IS NULL OR store_id = :store_id)
                     ",
                 )
                 .map_err(|e| {
@@ -621,6 +738,7 @@ impl ForJournalPersistence for SqliteJournalRepository {
             statement
                 .execute(named_params! {
                     ":id": id.as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
                     ":deleted_at": Utc::now().timestamp_millis(),
                 })
                 .map_err(|e| {
@@ -630,6 +748,302 @@ impl ForJournalPersistence for SqliteJournalRepository {
             Ok(())
         })();
 
+        let result = result.and_then(|_| audit::insert_change(&tran, store, change.as_ref()));
+
+        if let Err(e) = result {
+            tran.rollback()
+                .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+            return Err(e);
+        }
+
+        tran.commit()
+            .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Journal>>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let store_id = store.scope().map(|id| id.as_str());
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    journals.id,
+                    journals.recorded_at,
+                    journals.total_price,
+                    journal_records.supply_id,
+                    journal_records.supply_name,
+                    journal_records.supplier_id,
+                    journal_records.supplier_name,
+                    journal_records.unit_name,
+                    journal_records.unit_price,
+                    journal_records.quantity,
+                    journal_records.total_price,
+                    journal_records.tax_category,
+                    journal_records.tax_included,
+                    journal_records.tax_price,
+                    journal_records.invoice_registered,
+                    journal_records.lot_number,
+                    journal_records.expiry_date
+                FROM journals
+                INNER JOIN journal_records
+                    ON journal_records.journal_id = journals.id
+                WHERE
+                    (:store_id IS NULL OR journals.store_id = :store_id)
+                    AND
+                    journals.deleted_at IS NOT NULL
+                ORDER BY journals.recorded_at ASC
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let mut jorunals = statement
+            .query_map(named_params! { ":store_id": store_id }, |row| {
+                let journal = Journal::restore(
+                    JournalId::new(row.get::<_, i64>(0)?.to_string())?,
+                    EntryDateTime::new(row.get::<_, i64>(1)?),
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(2)?))?,
+                    Vec::new(),
+                );
+
+                Ok(journal)
+            })
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|journal| {
+                journal.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<Journal>>>()?;
+
+        let jorunal_records = statement
+            .query_map(named_params! { ":store_id": store_id }, |row| {
+                let journal_id = JournalId::new(row.get::<_, i64>(0)?.to_string())
+                    .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+                let journal_record = JournalRecord::new(
+                    SupplyId::new(row.get::<_, i64>(3)?.to_string())?,
+                    SupplyName::new(row.get::<_, String>(4)?)?,
+                    SupplierId::new(row.get::<_, i64>(5)?.to_string())?,
+                    SupplierName::new(row.get::<_, String>(6)?)?,
+                    UnitName::new(row.get::<_, String>(7)?)?,
+                    PurchaseUnitPrice::from_money(Money::from_minor(row.get::<_, i64>(8)?))?,
+                    PurchaseQuantity::from_quantity(Quantity::from_raw(row.get::<_, i64>(9)?))?,
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(10)?))?,
+                    TaxCategory::new(row.get::<_, String>(11)?)?,
+                    row.get::<_, bool>(12)?,
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(13)?))?,
+                    row.get::<_, bool>(14)?,
+                )
+                .with_lot(
+                    row.get::<_, Option<String>>(15)?
+                        .map(LotNumber::new)
+                        .transpose()?,
+                    row.get::<_, Option<i64>>(16)?.map(ExpiryDate::new),
+                );
+
+                Ok((journal_id, journal_record))
+            })
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|journal| {
+                journal.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<(JournalId, JournalRecord)>>>()?;
+
+        for journal in jorunals.iter_mut() {
+            let records: Vec<JournalRecord> = jorunal_records
+                .iter()
+                .filter_map(|record| {
+                    let journal_id = &record.0;
+                    let journal_record = &record.1;
+                    if journal_id == journal.id() {
+                        Some(journal_record)
+                    } else {
+                        None
+                    }
+                })
+                .cloned()
+                .collect();
+
+            journal.swap_records(records);
+        }
+
+        let deleted_ats = trash::deleted_ats(&conn, "journals")?;
+
+        Ok(trash::trashed(jorunals, &deleted_ats, |journal| {
+            journal.id().as_str()
+        }))
+    }
+
+    fn restore(
+        &self,
+        store: &StoreContext,
+        id: JournalId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        trash::restore(
+            &self.db_path,
+            "journals",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            change.as_ref().map(|change| (store, change)),
+        )
+    }
+
+    fn purge(
+        &self,
+        store: &StoreContext,
+        id: JournalId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        trash::purge(
+            &self.db_path,
+            "journals",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            &[
+                ("journal_records", "journal_id"),
+                ("journal_revision_records", "journal_id"),
+                ("journal_revisions", "journal_id"),
+                ("purchase_order_journals", "journal_id"),
+            ],
+            change.as_ref().map(|change| (store, change)),
+        )
+    }
+
+    fn add_revision(&self, journal: Journal) -> Result<()> {
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let tran = conn.transaction().map_err(|e| {
+            Error::InfrastructureError(format!("failed to start transaction: {}", e))
+        })?;
+
+        let result: Result<()> = (|| {
+            let number = tran
+                .query_row(
+                    r"
+                    SELECT COALESCE(MAX(number), 0) + 1
+                    FROM journal_revisions
+                    WHERE
+                        journal_id = :journal_id
+                    ",
+                    named_params! {
+                        ":journal_id": journal.id().as_str(),
+                    },
+                    |row| row.get::<_, i64>(0),
+                )
+                .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
+
+            tran.execute(
+                r"
+                INSERT INTO journal_revisions (
+                    journal_id,
+                    number,
+                    revised_at,
+                    recorded_at,
+                    total_price
+                ) VALUES (
+                    :journal_id,
+                    :number,
+                    :revised_at,
+                    :recorded_at,
+                    :total_price
+                )
+                ",
+                named_params! {
+                    ":journal_id": journal.id().as_str(),
+                    ":number": number,
+                    ":revised_at": Utc::now().timestamp_millis(),
+                    ":recorded_at": journal.entry_datetime().as_i64(),
+                    ":total_price": journal.total_price().as_money().as_minor(),
+                },
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to execute statement: {}", e))
+            })?;
+
+            let mut statement = tran
+                .prepare(
+                    r"
+                    INSERT INTO journal_revision_records (
+                        journal_id,
+                        revision_number,
+                        supply_id,
+                        supply_name,
+                        supplier_id,
+                        supplier_name,
+                        unit_name,
+                        unit_price,
+                        quantity,
+                        total_price,
+                        tax_category,
+                        tax_included,
+                        tax_price,
+                        invoice_registered,
+                        lot_number,
+                        expiry_date
+                    ) VALUES (
+                        :journal_id,
+                        :revision_number,
+                        :supply_id,
+                        :supply_name,
+                        :supplier_id,
+                        :supplier_name,
+                        :unit_name,
+                        :unit_price,
+                        :quantity,
+                        :total_price,
+                        :tax_category,
+                        :tax_included,
+                        :tax_price,
+                        :invoice_registered,
+                        :lot_number,
+                        :expiry_date
+                    )
+                    ",
+                )
+                .map_err(|e| {
+                    Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+                })?;
+
+            for record in journal.records() {
+                statement
+                    .execute(named_params! {
+                        ":journal_id": journal.id().as_str(),
+                        ":revision_number": number,
+                        ":supply_id": record.supply_id().as_str(),
+                        ":supply_name": record.supply_name().as_str(),
+                        ":supplier_id": record.supplier_id().as_str(),
+                        ":supplier_name": record.supplier_name().as_str(),
+                        ":unit_name": record.unit_name().as_str(),
+                        ":unit_price": record.unit_price().as_money().as_minor(),
+                        ":quantity": record.quantity().as_quantity().as_raw(),
+                        ":total_price": record.total_price().as_money().as_minor(),
+                        ":tax_category": record.tax_category().as_str(),
+                        ":tax_included": record.is_tax_included(),
+                        ":tax_price": record.tax_price().as_money().as_minor(),
+                        ":invoice_registered": record.is_invoice_registered(),
+                        ":lot_number": record.lot_number().map(|lot_number| lot_number.as_str()),
+                        ":expiry_date": record.expiry_date().map(|expiry_date| expiry_date.as_i64()),
+                    })
+                    .map_err(|e| {
+                        Error::InfrastructureError(format!("failed to execute statement: {}", e))
+                    })?;
+            }
+
+            Ok(())
+        })();
+
         if let Err(e) = result {
             tran.rollback()
                 .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
@@ -642,4 +1056,148 @@ impl ForJournalPersistence for SqliteJournalRepository {
 
         Ok(())
     }
+
+    fn list_revisions(
+        &self,
+        store: &StoreContext,
+        id: JournalId,
+    ) -> Result<Vec<Revision<Journal>>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    number,
+                    revised_at,
+                    recorded_at,
+                    total_price
+                FROM journal_revisions
+                WHERE
+                    journal_id = :journal_id
+                    AND
+                    EXISTS (
+                        SELECT 1 FROM journals
+                        WHERE
+                            journals.id = journal_revisions.journal_id
+                            AND
+                            (:store_id IS NULL OR journals.store_id = :store_id)
+                    )
+                ORDER BY number ASC
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let revisions = statement
+            .query_map(
+                named_params! {
+                    ":journal_id": id.as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
+                },
+                |row| {
+                    let journal = Journal::restore(
+                        id.clone(),
+                        EntryDateTime::new(row.get::<_, i64>(2)?),
+                        TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(3)?))?,
+                        Vec::new(),
+                    );
+
+                    Ok(Revision::new(
+                        RevisionNumber::new(row.get::<_, u32>(0)?)?,
+                        RevisedDateTime::new(row.get::<_, i64>(1)?),
+                        journal,
+                    ))
+                },
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|revision| {
+                revision.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<Revision<Journal>>>>()?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    revision_number,
+                    supply_id,
+                    supply_name,
+                    supplier_id,
+                    supplier_name,
+                    unit_name,
+                    unit_price,
+                    quantity,
+                    total_price,
+                    tax_category,
+                    tax_included,
+                    tax_price,
+                    invoice_registered,
+                    lot_number,
+                    expiry_date
+                FROM journal_revision_records
+                WHERE
+                    journal_id = :journal_id
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let revision_records = statement
+            .query_map(named_params! { ":journal_id": id.as_str() }, |row| {
+                let journal_record = JournalRecord::new(
+                    SupplyId::new(row.get::<_, i64>(1)?.to_string())?,
+                    SupplyName::new(row.get::<_, String>(2)?)?,
+                    SupplierId::new(row.get::<_, i64>(3)?.to_string())?,
+                    SupplierName::new(row.get::<_, String>(4)?)?,
+                    UnitName::new(row.get::<_, String>(5)?)?,
+                    PurchaseUnitPrice::from_money(Money::from_minor(row.get::<_, i64>(6)?))?,
+                    PurchaseQuantity::from_quantity(Quantity::from_raw(row.get::<_, i64>(7)?))?,
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(8)?))?,
+                    TaxCategory::new(row.get::<_, String>(9)?)?,
+                    row.get::<_, bool>(10)?,
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(11)?))?,
+                    row.get::<_, bool>(12)?,
+                )
+                .with_lot(
+                    row.get::<_, Option<String>>(13)?
+                        .map(LotNumber::new)
+                        .transpose()?,
+                    row.get::<_, Option<i64>>(14)?.map(ExpiryDate::new),
+                );
+
+                Ok((row.get::<_, u32>(0)?, journal_record))
+            })
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|record| {
+                record.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<(u32, JournalRecord)>>>()?;
+
+        Ok(revisions
+            .into_iter()
+            .map(|revision| {
+                let number = revision.number();
+                let revised_at = revision.revised_at();
+                let mut journal = revision.into_item();
+
+                journal.swap_records(
+                    revision_records
+                        .iter()
+                        .filter(|(revision_number, _)| *revision_number == number.as_u32())
+                        .map(|(_, record)| record.clone())
+                        .collect(),
+                );
+
+                Revision::new(number, revised_at, journal)
+            })
+            .collect())
+    }
 }
@@ -5,10 +5,14 @@ use rusqlite::Connection;
 use scopeguard::defer;
 
 use crate::core::domain::entities::stock::Journal;
+use crate::core::domain::values::lot::{ExpiryDate, LotNumber};
+use crate::core::domain::values::revision::RevisionNumber;
 use crate::core::domain::values::stock::{
     EntryDateTime, JournalId, JournalRecord, PurchaseQuantity, PurchaseUnitPrice, SupplierId,
     SupplierName, SupplyId, SupplyName, TotalPrice, UnitName,
 };
+use crate::core::domain::values::store::StoreContext;
+use crate::core::domain::values::tax::TaxCategory;
 use crate::core::required_ports::{FindJournalsQuery, ForJournalPersistence};
 use crate::persistence::sqlite::{migrate, SqliteJournalRepository};
 
@@ -47,36 +51,48 @@ fn journal_repository_test() {
     assert_eq!(next_id, JournalId::new("1").unwrap());
 
     repository
-        .add(Journal::restore(
-            JournalId::new("1").unwrap(),
-            EntryDateTime::new(200000),
-            TotalPrice::new(1700.0).unwrap(),
-            vec![
-                JournalRecord::new(
-                    SupplyId::new("1").unwrap(),
-                    SupplyName::new("SupplyA").unwrap(),
-                    SupplierId::new("1").unwrap(),
-                    SupplierName::new("SupplierA").unwrap(),
-                    UnitName::new("g").unwrap(),
-                    PurchaseUnitPrice::new(100_u32).unwrap(),
-                    PurchaseQuantity::new(5_u32).unwrap(),
-                    TotalPrice::new(500.0).unwrap(),
-                ),
-                JournalRecord::new(
-                    SupplyId::new("2").unwrap(),
-                    SupplyName::new("SupplyB").unwrap(),
-                    SupplierId::new("1").unwrap(),
-                    SupplierName::new("SupplierA").unwrap(),
-                    UnitName::new("g").unwrap(),
-                    PurchaseUnitPrice::new(120_u32).unwrap(),
-                    PurchaseQuantity::new(10_u32).unwrap(),
-                    TotalPrice::new(1200.0).unwrap(),
-                ),
-            ],
-        ))
+        .add(
+            &StoreContext::default(),
+            Journal::restore(
+                JournalId::new("1").unwrap(),
+                EntryDateTime::new(200000),
+                TotalPrice::new(1700.0).unwrap(),
+                vec![
+                    JournalRecord::new(
+                        SupplyId::new("1").unwrap(),
+                        SupplyName::new("SupplyA").unwrap(),
+                        SupplierId::new("1").unwrap(),
+                        SupplierName::new("SupplierA").unwrap(),
+                        UnitName::new("g").unwrap(),
+                        PurchaseUnitPrice::new(100_u32).unwrap(),
+                        PurchaseQuantity::new(5_u32).unwrap(),
+                        TotalPrice::new(500.0).unwrap(),
+                        TaxCategory::Reduced,
+                        true,
+                        TotalPrice::new(37.0).unwrap(),
+                        true,
+                    ),
+                    JournalRecord::new(
+                        SupplyId::new("2").unwrap(),
+                        SupplyName::new("SupplyB").unwrap(),
+                        SupplierId::new("1").unwrap(),
+                        SupplierName::new("SupplierA").unwrap(),
+                        UnitName::new("g").unwrap(),
+                        PurchaseUnitPrice::new(120_u32).unwrap(),
+                        PurchaseQuantity::new(10_u32).unwrap(),
+                        TotalPrice::new(1200.0).unwrap(),
+                        TaxCategory::Standard,
+                        false,
+                        TotalPrice::new(120.0).unwrap(),
+                        false,
+                    ),
+                ],
+            ),
+            None,
+        )
         .unwrap();
 
-    let journals = repository.list().unwrap();
+    let journals = repository.list(&StoreContext::default()).unwrap();
 
     assert!(journals.first().is_some_and(|journal| {
         assert_eq!(journal.id(), &JournalId::new("1").unwrap());
@@ -94,6 +110,10 @@ fn journal_repository_test() {
                     PurchaseUnitPrice::new(100_u32).unwrap(),
                     PurchaseQuantity::new(5_u32).unwrap(),
                     TotalPrice::new(500.0).unwrap(),
+                    TaxCategory::Reduced,
+                    true,
+                    TotalPrice::new(37.0).unwrap(),
+                    true,
                 ),
                 JournalRecord::new(
                     SupplyId::new("2").unwrap(),
@@ -104,6 +124,10 @@ fn journal_repository_test() {
                     PurchaseUnitPrice::new(120_u32).unwrap(),
                     PurchaseQuantity::new(10_u32).unwrap(),
                     TotalPrice::new(1200.0).unwrap(),
+                    TaxCategory::Standard,
+                    false,
+                    TotalPrice::new(120.0).unwrap(),
+                    false,
                 ),
             ]
         );
@@ -111,24 +135,38 @@ fn journal_repository_test() {
     }));
 
     repository
-        .save(Journal::restore(
-            JournalId::new("1").unwrap(),
-            EntryDateTime::new(200000),
-            TotalPrice::new(1950.0).unwrap(),
-            vec![JournalRecord::new(
-                SupplyId::new("1").unwrap(),
-                SupplyName::new("SupplyC").unwrap(),
-                SupplierId::new("1").unwrap(),
-                SupplierName::new("SupplierA").unwrap(),
-                UnitName::new("kg").unwrap(),
-                PurchaseUnitPrice::new(130_u32).unwrap(),
-                PurchaseQuantity::new(15_u32).unwrap(),
+        .save(
+            &StoreContext::default(),
+            Journal::restore(
+                JournalId::new("1").unwrap(),
+                EntryDateTime::new(200000),
                 TotalPrice::new(1950.0).unwrap(),
-            )],
-        ))
+                vec![JournalRecord::new(
+                    SupplyId::new("1").unwrap(),
+                    SupplyName::new("SupplyC").unwrap(),
+                    SupplierId::new("1").unwrap(),
+                    SupplierName::new("SupplierA").unwrap(),
+                    UnitName::new("kg").unwrap(),
+                    PurchaseUnitPrice::new(130_u32).unwrap(),
+                    PurchaseQuantity::new(15_u32).unwrap(),
+                    TotalPrice::new(1950.0).unwrap(),
+                    TaxCategory::Standard,
+                    true,
+                    TotalPrice::new(177.0).unwrap(),
+                    true,
+                )
+                .with_lot(
+                    Some(LotNumber::new("L2401").unwrap()),
+                    Some(ExpiryDate::new(900000)),
+                )],
+            ),
+            None,
+        )
         .unwrap();
 
-    let journal = repository.get(JournalId::new("1").unwrap()).unwrap();
+    let journal = repository
+        .get(&StoreContext::default(), JournalId::new("1").unwrap())
+        .unwrap();
 
     assert!(journal.is_some_and(|journal| {
         assert_eq!(journal.id(), &JournalId::new("1").unwrap());
@@ -145,18 +183,29 @@ fn journal_repository_test() {
                 PurchaseUnitPrice::new(130_u32).unwrap(),
                 PurchaseQuantity::new(15_u32).unwrap(),
                 TotalPrice::new(1950.0).unwrap(),
+                TaxCategory::Standard,
+                true,
+                TotalPrice::new(177.0).unwrap(),
+                true,
+            )
+            .with_lot(
+                Some(LotNumber::new("L2401").unwrap()),
+                Some(ExpiryDate::new(900000)),
             ),]
         );
         true
     }));
 
     let journals = repository
-        .find(FindJournalsQuery {
-            period_start: Some(EntryDateTime::new(100000)),
-            period_end: Some(EntryDateTime::new(300000)),
-            supply_name: Some(SupplyName::new("C").unwrap()),
-            supplier_name: Some(SupplierName::new("A").unwrap()),
-        })
+        .find(
+            &StoreContext::default(),
+            FindJournalsQuery {
+                period_start: Some(EntryDateTime::new(100000)),
+                period_end: Some(EntryDateTime::new(300000)),
+                supply_name: Some(SupplyName::new("C").unwrap()),
+                supplier_name: Some(SupplierName::new("A").unwrap()),
+            },
+        )
         .unwrap();
 
     assert!(journals.first().as_ref().is_some_and(|journal| {
@@ -174,6 +223,14 @@ fn journal_repository_test() {
                 PurchaseUnitPrice::new(130_u32).unwrap(),
                 PurchaseQuantity::new(15_u32).unwrap(),
                 TotalPrice::new(1950.0).unwrap(),
+                TaxCategory::Standard,
+                true,
+                TotalPrice::new(177.0).unwrap(),
+                true,
+            )
+            .with_lot(
+                Some(LotNumber::new("L2401").unwrap()),
+                Some(ExpiryDate::new(900000)),
             ),]
         );
         true
@@ -181,9 +238,107 @@ fn journal_repository_test() {
 
     let journal = journals.first().unwrap();
 
-    repository.delete(journal.id().clone()).unwrap();
+    repository
+        .delete(&StoreContext::default(), journal.id().clone(), None)
+        .unwrap();
 
-    let journals = repository.list().unwrap();
+    let journals = repository.list(&StoreContext::default()).unwrap();
 
     assert_eq!(journals, vec![]);
 }
+
+#[test]
+fn journal_revision_test() {
+    let tmp_path = Path::new("tmp/journal_revision_test.db");
+
+    defer! {
+        fs::remove_file(tmp_path).unwrap();
+    }
+
+    if let Some(parent) = tmp_path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+
+    migrate(tmp_path.to_string_lossy()).unwrap();
+
+    let conn = Connection::open(tmp_path).unwrap();
+
+    conn.execute(
+        "INSERT INTO suppliers (id, name) VALUES (1, 'SupplierA');",
+        [],
+    )
+    .unwrap();
+
+    conn.execute(
+        "INSERT INTO supplies (id, name, unit_name, supplier_id) VALUES (1, 'SupplyA', 'g', 1);",
+        [],
+    )
+    .unwrap();
+
+    let repository = SqliteJournalRepository::new(tmp_path.to_string_lossy());
+
+    let journal = |quantity: u32| {
+        Journal::restore(
+            JournalId::new("1").unwrap(),
+            EntryDateTime::new(200000),
+            TotalPrice::new(100.0 * quantity as f64).unwrap(),
+            vec![JournalRecord::new(
+                SupplyId::new("1").unwrap(),
+                SupplyName::new("SupplyA").unwrap(),
+                SupplierId::new("1").unwrap(),
+                SupplierName::new("SupplierA").unwrap(),
+                UnitName::new("g").unwrap(),
+                PurchaseUnitPrice::new(100_u32).unwrap(),
+                PurchaseQuantity::new(quantity).unwrap(),
+                TotalPrice::new(100.0 * quantity as f64).unwrap(),
+                TaxCategory::Reduced,
+                true,
+                TotalPrice::new(7.0 * quantity as f64).unwrap(),
+                true,
+            )
+            .with_lot(
+                Some(LotNumber::new("LOT-1").unwrap()),
+                Some(ExpiryDate::new(300000)),
+            )],
+        )
+    };
+
+    repository
+        .add(&StoreContext::default(), journal(1), None)
+        .unwrap();
+
+    repository.add_revision(journal(1)).unwrap();
+    repository.add_revision(journal(2)).unwrap();
+
+    let revisions = repository
+        .list_revisions(&StoreContext::default(), JournalId::new("1").unwrap())
+        .unwrap();
+
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[0].number(), RevisionNumber::new(1).unwrap());
+    assert_eq!(revisions[1].number(), RevisionNumber::new(2).unwrap());
+    assert_eq!(revisions[0].item().records(), journal(1).records());
+    assert_eq!(revisions[1].item().records(), journal(2).records());
+    assert_eq!(
+        revisions[1].item().total_price(),
+        &TotalPrice::new(200.0).unwrap()
+    );
+
+    // revisions can not be changed
+    assert!(conn
+        .execute("UPDATE journal_revisions SET total_price = 0;", [])
+        .is_err());
+
+    // purging a journal deletes its revisions
+    repository
+        .delete(&StoreContext::default(), JournalId::new("1").unwrap(), None)
+        .unwrap();
+    repository
+        .purge(&StoreContext::default(), JournalId::new("1").unwrap(), None)
+        .unwrap();
+
+    assert!(repository
+        .list_revisions(&StoreContext::default(), JournalId::new("1").unwrap())
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,251 @@
+//! This module provides the SQLite-based implementation of the `ForStorageLocationPersistence` trait.
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::named_params;
+use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+use rusqlite::Row;
+
+use crate::core::domain::entities::stock::StorageLocation;
+use crate::core::domain::values::location::*;
+use crate::core::domain::values::trash::*;
+use crate::core::required_ports::*;
+use crate::core::Error;
+use crate::core::Result;
+
+use super::trash;
+
+/// SQLite implementation of `ForStorageLocationPersistence`
+pub struct SqliteStorageLocationRepository {
+    db_path: String,
+}
+
+impl SqliteStorageLocationRepository {
+    pub fn new(db_path: impl AsRef<str>) -> Self {
+        Self {
+            db_path: Path::new(db_path.as_ref()).to_string_lossy().to_string(),
+        }
+    }
+}
+
+/// Reads a storage location from a row of id, name and sort order.
+fn location_row(row: &Row) -> rusqlite::Result<StorageLocation> {
+    Ok(StorageLocation::restore(
+        StorageLocationId::new(row.get::<_, i64>(0)?.to_string())?,
+        StorageLocationName::new(row.get::<_, String>(1)?)?,
+        SortOrder::new(row.get::<_, i64>(2)?)?,
+    ))
+}
+
+/// Implementation of `ForStorageLocationPersistence` for `SqliteStorageLocationRepository`
+impl ForStorageLocationPersistence for SqliteStorageLocationRepository {
+    fn next_id(&self) -> Result<StorageLocationId> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let next_id = conn
+            .query_row(
+                r"
+                UPDATE storage_locations_id_sequence
+                SET value = value + 1
+                WHERE name = 'storage_location_id'
+                RETURNING value
+                ",
+                [],
+                |r| r.get::<_, i64>(0),
+            )
+            .map_err(|e| Error::InfrastructureError(format!("fail to query: {}", e)))?;
+
+        let next_id = StorageLocationId::new(next_id.to_string())?;
+
+        Ok(next_id)
+    }
+
+    fn list(&self) -> Result<Vec<StorageLocation>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    id,
+                    name,
+                    sort_order
+                FROM storage_locations
+                WHERE
+                    deleted_at IS NULL
+                ORDER BY sort_order, id
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let locations = statement
+            .query_map([], location_row)
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|location| {
+                location.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<StorageLocation>>>()?;
+
+        Ok(locations)
+    }
+
+    fn get(&self, id: StorageLocationId) -> Result<Option<StorageLocation>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let location = conn
+            .query_row(
+                r"
+                SELECT
+                    id,
+                    name,
+                    sort_order
+                FROM storage_locations
+                WHERE
+                    id = :id
+                    AND
+                    deleted_at IS NULL
+                ",
+                named_params! {
+                    ":id": id.as_str(),
+                },
+                location_row,
+            )
+            .optional()
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
+
+        Ok(location)
+    }
+
+    fn add(&self, location: StorageLocation) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.execute(
+            r"
+            INSERT INTO storage_locations (
+                id,
+                name,
+                sort_order
+            ) VALUES (
+                :id,
+                :name,
+                :sort_order
+            )
+            ",
+            named_params! {
+                ":id": location.id().as_str(),
+                ":name": location.name().as_str(),
+                ":sort_order": location.sort_order().as_u32(),
+            },
+        )
+        .map_err(|e| {
+            Error::InfrastructureError(format!("failed to insert new storage location: {}", e))
+        })?;
+
+        Ok(())
+    }
+
+    fn save(&self, location: StorageLocation) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.execute(
+            r"
+            UPDATE storage_locations
+            SET
+                name = :name,
+                sort_order = :sort_order
+            WHERE id = :id
+            ",
+            named_params! {
+                ":id": location.id().as_str(),
+                ":name": location.name().as_str(),
+                ":sort_order": location.sort_order().as_u32(),
+            },
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn delete(&self, id: StorageLocationId) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.execute(
+            r"
+            UPDATE storage_locations
+            SET deleted_at = :deleted_at
+            WHERE id = :id
+            ",
+            named_params! {
+                ":id": id.as_str(),
+                ":deleted_at": Utc::now().timestamp_millis(),
+            },
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<Trashed<StorageLocation>>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    id,
+                    name,
+                    sort_order
+                FROM storage_locations
+                WHERE
+                    deleted_at IS NOT NULL
+                ORDER BY sort_order, id
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let locations = statement
+            .query_map([], location_row)
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|location| {
+                location.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<StorageLocation>>>()?;
+
+        let deleted_ats = trash::deleted_ats(&conn, "storage_locations")?;
+
+        Ok(trash::trashed(locations, &deleted_ats, |location| {
+            location.id().as_str()
+        }))
+    }
+
+    fn restore(&self, id: StorageLocationId) -> Result<()> {
+        trash::restore(&self.db_path, "storage_locations", id.as_str(), None, None)
+    }
+
+    fn purge(&self, id: StorageLocationId) -> Result<()> {
+        trash::purge(
+            &self.db_path,
+            "storage_locations",
+            id.as_str(),
+            None,
+            &[],
+            None,
+        )
+    }
+}
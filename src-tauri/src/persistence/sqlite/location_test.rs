@@ -0,0 +1,71 @@
+use std::fs::{self};
+use std::path::Path;
+
+use scopeguard::defer;
+
+use crate::core::domain::entities::stock::StorageLocation;
+use crate::core::domain::values::location::*;
+use crate::core::required_ports::*;
+use crate::persistence::sqlite::{migrate, SqliteStorageLocationRepository};
+
+#[test]
+fn storage_location_repository_test() {
+    let tmp_path = Path::new("tmp/storage_location_repository_test.db");
+
+    defer! {
+        fs::remove_file(tmp_path).unwrap();
+    }
+
+    if let Some(parent) = tmp_path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+
+    migrate(tmp_path.to_string_lossy()).unwrap();
+
+    let repository = SqliteStorageLocationRepository::new(tmp_path.to_string_lossy());
+
+    let fridge = StorageLocation::restore(
+        repository.next_id().unwrap(),
+        StorageLocationName::new("冷蔵庫").unwrap(),
+        SortOrder::new(2).unwrap(),
+    );
+
+    let freezer = StorageLocation::restore(
+        repository.next_id().unwrap(),
+        StorageLocationName::new("冷凍庫").unwrap(),
+        SortOrder::new(1).unwrap(),
+    );
+
+    assert_eq!(freezer.id(), &StorageLocationId::new("2").unwrap());
+
+    repository.add(fridge.clone()).unwrap();
+    repository.add(freezer.clone()).unwrap();
+
+    // ordered by sort order
+    assert_eq!(
+        repository.list().unwrap(),
+        vec![freezer.clone(), fridge.clone()]
+    );
+
+    let mut fridge = fridge;
+
+    fridge.rename(StorageLocationName::new("冷蔵庫1").unwrap());
+    fridge.reorder(SortOrder::new(0).unwrap());
+
+    repository.save(fridge.clone()).unwrap();
+
+    let location = repository.get(fridge.id().clone()).unwrap();
+
+    assert!(location.is_some_and(|location| {
+        assert_eq!(
+            location.name(),
+            &StorageLocationName::new("冷蔵庫1").unwrap()
+        );
+        assert_eq!(location.sort_order(), SortOrder::new(0).unwrap());
+        true
+    }));
+
+    repository.delete(freezer.id().clone()).unwrap();
+
+    assert_eq!(repository.list().unwrap(), vec![fridge]);
+}
@@ -64,6 +64,96 @@ pub fn migrate(db_path: impl AsRef<str>) -> Result<()> {
             tran.execute_batch(include_str!("migrations/004_add_total_price_column.sql"))?;
         }
 
+        if version < 5 {
+            tran.execute_batch(include_str!("migrations/005_rescale_decimal_columns.sql"))?;
+        }
+
+        if version < 6 {
+            tran.execute_batch(include_str!("migrations/006_add_tax_columns.sql"))?;
+        }
+
+        if version < 7 {
+            tran.execute_batch(include_str!(
+                "migrations/007_add_invoice_registration_columns.sql"
+            ))?;
+        }
+
+        if version < 8 {
+            tran.execute_batch(include_str!("migrations/008_create_units.sql"))?;
+        }
+
+        if version < 9 {
+            tran.execute_batch(include_str!(
+                "migrations/009_add_valuation_method_column.sql"
+            ))?;
+        }
+
+        if version < 10 {
+            tran.execute_batch(include_str!("migrations/010_create_consumptions.sql"))?;
+        }
+
+        if version < 11 {
+            tran.execute_batch(include_str!("migrations/011_create_categories.sql"))?;
+        }
+
+        if version < 12 {
+            tran.execute_batch(include_str!("migrations/012_create_purchase_orders.sql"))?;
+        }
+
+        if version < 13 {
+            tran.execute_batch(include_str!("migrations/013_add_reorder_level_columns.sql"))?;
+        }
+
+        if version < 14 {
+            tran.execute_batch(include_str!("migrations/014_create_waste_records.sql"))?;
+        }
+
+        if version < 15 {
+            tran.execute_batch(include_str!("migrations/015_create_storage_locations.sql"))?;
+        }
+
+        if version < 16 {
+            tran.execute_batch(include_str!("migrations/016_create_stores.sql"))?;
+        }
+
+        if version < 17 {
+            tran.execute_batch(include_str!("migrations/017_create_transfers.sql"))?;
+        }
+
+        if version < 18 {
+            tran.execute_batch(include_str!("migrations/018_add_lot_columns.sql"))?;
+        }
+
+        if version < 19 {
+            tran.execute_batch(include_str!(
+                "migrations/019_add_supplier_details_columns.sql"
+            ))?;
+        }
+
+        if version < 20 {
+            tran.execute_batch(include_str!("migrations/020_create_supplier_invoices.sql"))?;
+        }
+
+        if version < 21 {
+            tran.execute_batch(include_str!("migrations/021_create_supply_suppliers.sql"))?;
+        }
+
+        if version < 22 {
+            tran.execute_batch(include_str!("migrations/022_create_accounting_periods.sql"))?;
+        }
+
+        if version < 23 {
+            tran.execute_batch(include_str!("migrations/023_create_audit_log.sql"))?;
+        }
+
+        if version < 24 {
+            tran.execute_batch(include_str!("migrations/024_create_revisions.sql"))?;
+        }
+
+        if version < 25 {
+            tran.execute_batch(include_str!("migrations/025_add_store_columns.sql"))?;
+        }
+
         Ok(())
     })()
     .map_err(|e| Error::InfrastructureError(format!("migration failed: {}", e)));
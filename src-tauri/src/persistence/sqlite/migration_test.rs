@@ -0,0 +1,114 @@
+use std::fs;
+use std::path::Path;
+
+use rusqlite::Connection;
+use scopeguard::defer;
+
+use crate::core::domain::values::stock::{
+    JournalId, PurchaseQuantity, PurchaseUnitPrice, TotalPrice,
+};
+use crate::core::domain::values::store::StoreContext;
+use crate::core::required_ports::ForJournalPersistence;
+use crate::persistence::sqlite::{migrate, SqliteJournalRepository};
+
+#[test]
+fn rescale_decimal_columns_test() {
+    let tmp_path = Path::new("tmp/rescale_decimal_columns_test.db");
+
+    defer! {
+        fs::remove_file(tmp_path).unwrap();
+    }
+
+    if let Some(parent) = tmp_path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+
+    // a database left at version 4 holds amounts and quantities multiplied by 20
+    let conn = Connection::open(tmp_path).unwrap();
+
+    conn.execute_batch(include_str!("migrations/001_create_tables.sql"))
+        .unwrap();
+    conn.execute_batch(include_str!("migrations/002_add_deleted_at_column.sql"))
+        .unwrap();
+    conn.execute_batch(include_str!("migrations/003_quantity_int_to_real.sql"))
+        .unwrap();
+    conn.execute_batch(include_str!("migrations/004_add_total_price_column.sql"))
+        .unwrap();
+
+    conn.execute_batch(
+        "INSERT INTO suppliers (id, name) VALUES (1, 'SupplierA');
+        INSERT INTO supplies (id, name, unit_name, supplier_id) VALUES (1, 'SupplyA', 'g', 1);
+        INSERT INTO journals (id, recorded_at, total_price) VALUES (1, 200000, 5025);
+        INSERT INTO journal_records (supply_id, supply_name, supplier_id, supplier_name, unit_name, unit_price, quantity, total_price, journal_id)
+            VALUES (1, 'SupplyA', 1, 'SupplierA', 'g', 2010, 50, 5025, 1);
+        INSERT INTO stocktakings (id, recorded_at, total_price) VALUES (1, 300000, 1800);
+        INSERT INTO stocktaking_records (unit_name, unit_price, quantity, total_price, supply_id, supply_name, stocktaking_id)
+            VALUES ('g', 2400, 15, 1800, 1, 'SupplyA', 1);",
+    )
+    .unwrap();
+
+    drop(conn);
+
+    migrate(tmp_path.to_string_lossy()).unwrap();
+
+    let conn = Connection::open(tmp_path).unwrap();
+
+    // amounts are rescaled to minor units and quantities to thousandths
+    let journal = conn
+        .query_row(
+            "SELECT journals.total_price, unit_price, quantity, journal_records.total_price
+            FROM journals JOIN journal_records ON journal_records.journal_id = journals.id",
+            [],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, i64>(3)?,
+                ))
+            },
+        )
+        .unwrap();
+
+    assert_eq!(journal, (25125, 10050, 2500, 25125));
+
+    let stocktaking = conn
+        .query_row(
+            "SELECT stocktakings.total_price, unit_price, quantity, stocktaking_records.total_price
+            FROM stocktakings JOIN stocktaking_records ON stocktaking_records.stocktaking_id = stocktakings.id",
+            [],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, i64>(3)?,
+                ))
+            },
+        )
+        .unwrap();
+
+    assert_eq!(stocktaking, (9000, 12000, 750, 9000));
+
+    // and are read back as the amounts and quantities recorded before
+    let journal = SqliteJournalRepository::new(tmp_path.to_string_lossy())
+        .get(&StoreContext::default(), JournalId::new("1").unwrap())
+        .unwrap();
+
+    assert!(journal.is_some_and(|journal| {
+        assert_eq!(journal.total_price(), &TotalPrice::new(251.25).unwrap());
+        assert_eq!(
+            journal.records()[0].unit_price(),
+            &PurchaseUnitPrice::new(100.5).unwrap()
+        );
+        assert_eq!(
+            journal.records()[0].quantity(),
+            &PurchaseQuantity::new(2.5).unwrap()
+        );
+        assert_eq!(
+            journal.records()[0].total_price(),
+            &TotalPrice::new(251.25).unwrap()
+        );
+        true
+    }));
+}
@@ -0,0 +1,23 @@
+-- rescale decimal columns
+-- amounts were stored multiplied by 20, they are now stored in minor units (x100)
+-- quantities were stored multiplied by 20, they are now stored in thousandths (x1000)
+
+UPDATE journals
+SET total_price = CAST(ROUND(total_price * 5) AS INTEGER);
+
+UPDATE stocktakings
+SET total_price = CAST(ROUND(total_price * 5) AS INTEGER);
+
+UPDATE journal_records
+SET
+    unit_price = CAST(ROUND(unit_price * 5) AS INTEGER),
+    quantity = CAST(ROUND(quantity * 50) AS INTEGER),
+    total_price = CAST(ROUND(total_price * 5) AS INTEGER);
+
+UPDATE stocktaking_records
+SET
+    unit_price = CAST(ROUND(unit_price * 5) AS INTEGER),
+    quantity = CAST(ROUND(quantity * 50) AS INTEGER),
+    total_price = CAST(ROUND(total_price * 5) AS INTEGER);
+
+PRAGMA user_version = 5;
@@ -0,0 +1,14 @@
+-- add consumption tax columns
+-- existing supplies and journal lines are treated as tax-inclusive at the standard rate (10%)
+
+ALTER TABLE supplies ADD COLUMN tax_category TEXT NOT NULL DEFAULT 'standard';
+
+ALTER TABLE journal_records ADD COLUMN tax_category TEXT NOT NULL DEFAULT 'standard';
+ALTER TABLE journal_records ADD COLUMN tax_included INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE journal_records ADD COLUMN tax_price INTEGER NOT NULL DEFAULT 0;
+
+-- tax included in the line total, rounded down to whole yen
+UPDATE journal_records
+SET tax_price = (total_price * 10 / 110) / 100 * 100;
+
+PRAGMA user_version = 6;
@@ -0,0 +1,10 @@
+-- add qualified invoice issuer registration columns
+-- suppliers are unregistered until their registration number is entered
+-- existing journal lines keep full deduction as they were recorded
+
+ALTER TABLE suppliers ADD COLUMN invoice_registration_number TEXT;
+ALTER TABLE suppliers ADD COLUMN invoice_registered INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE journal_records ADD COLUMN invoice_registered INTEGER NOT NULL DEFAULT 1;
+
+PRAGMA user_version = 7;
@@ -0,0 +1,57 @@
+-- create units of measure
+-- units are seeded from the unit names already in use
+-- existing supplies are purchased and stocked in their current unit
+
+-- table of sequence for unit id generation
+CREATE TABLE units_id_sequence (
+    name TEXT PRIMARY KEY,
+    value INTEGER DEFAULT 0
+);
+
+INSERT INTO units_id_sequence (
+    name,
+    value
+) VALUES (
+    'unit_id',
+    0
+);
+
+-- table of units
+CREATE TABLE units (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    deleted_at INTEGER
+);
+
+INSERT INTO units (name)
+SELECT unit_name FROM supplies
+UNION
+SELECT unit_name FROM journal_records
+UNION
+SELECT unit_name FROM stocktaking_records
+ORDER BY 1;
+
+UPDATE units_id_sequence
+SET value = (SELECT IFNULL(MAX(id), 0) FROM units)
+WHERE name = 'unit_id';
+
+ALTER TABLE supplies ADD COLUMN purchase_unit_id INTEGER REFERENCES units(id);
+ALTER TABLE supplies ADD COLUMN stock_unit_id INTEGER REFERENCES units(id);
+
+UPDATE supplies
+SET
+    purchase_unit_id = (SELECT id FROM units WHERE units.name = supplies.unit_name),
+    stock_unit_id = (SELECT id FROM units WHERE units.name = supplies.unit_name);
+
+-- table of conversions of units into the stock unit of supplies
+-- factor is the number of stock units in one unit, stored in thousandths (x1000)
+CREATE TABLE supply_unit_conversions (
+    supply_id INTEGER NOT NULL,
+    unit_id INTEGER NOT NULL,
+    factor INTEGER NOT NULL,
+    PRIMARY KEY(supply_id, unit_id),
+    FOREIGN KEY(supply_id) REFERENCES supplies(id),
+    FOREIGN KEY(unit_id) REFERENCES units(id)
+);
+
+PRAGMA user_version = 8;
@@ -0,0 +1,6 @@
+-- add valuation method column to stocktaking records
+-- the method the unit price was derived by, NULL if entered by hand
+
+ALTER TABLE stocktaking_records ADD COLUMN valuation_method TEXT;
+
+PRAGMA user_version = 9;
@@ -0,0 +1,36 @@
+-- create consumptions recorded between stocktakings
+
+-- table of sequence for consumption id generation
+CREATE TABLE consumptions_id_sequence (
+    name TEXT PRIMARY KEY,
+    value INTEGER DEFAULT 0
+);
+
+INSERT INTO consumptions_id_sequence (
+    name,
+    value
+) VALUES (
+    'consumption_id',
+    0
+);
+
+-- table of consumptions
+CREATE TABLE consumptions (
+    id INTEGER PRIMARY KEY,
+    recorded_at INTEGER NOT NULL, -- timestamp stock consumed at
+    deleted_at INTEGER
+);
+
+-- table of consumption records
+-- quantity is stored in thousandths (x1000)
+CREATE TABLE consumption_records (
+    supply_id INTEGER NOT NULL,
+    supply_name TEXT NOT NULL,
+    unit_name TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    consumption_id INTEGER NOT NULL,
+    FOREIGN KEY(supply_id) REFERENCES supplies(id),
+    FOREIGN KEY(consumption_id) REFERENCES consumptions(id)
+);
+
+PRAGMA user_version = 10;
@@ -0,0 +1,30 @@
+-- create supply categories
+-- categories form a hierarchy through parent_id, e.g. 食材 > 野菜
+-- existing supplies are not categorized
+
+-- table of sequence for category id generation
+CREATE TABLE categories_id_sequence (
+    name TEXT PRIMARY KEY,
+    value INTEGER DEFAULT 0
+);
+
+INSERT INTO categories_id_sequence (
+    name,
+    value
+) VALUES (
+    'category_id',
+    0
+);
+
+-- table of categories
+CREATE TABLE categories (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    parent_id INTEGER,
+    deleted_at INTEGER,
+    FOREIGN KEY(parent_id) REFERENCES categories(id)
+);
+
+ALTER TABLE supplies ADD COLUMN category_id INTEGER REFERENCES categories(id);
+
+PRAGMA user_version = 11;
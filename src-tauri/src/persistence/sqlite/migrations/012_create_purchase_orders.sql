@@ -0,0 +1,52 @@
+-- create purchase orders placed with suppliers
+-- deliveries of an order are recorded as journals, linked through purchase_order_journals
+
+-- table of sequence for purchase order id generation
+CREATE TABLE purchase_orders_id_sequence (
+    name TEXT PRIMARY KEY,
+    value INTEGER DEFAULT 0
+);
+
+INSERT INTO purchase_orders_id_sequence (
+    name,
+    value
+) VALUES (
+    'purchase_order_id',
+    0
+);
+
+-- table of purchase orders
+-- status is one of draft, sent, partially_received, received or cancelled
+CREATE TABLE purchase_orders (
+    id INTEGER PRIMARY KEY,
+    supplier_id INTEGER NOT NULL,
+    supplier_name TEXT NOT NULL,
+    ordered_at INTEGER NOT NULL, -- timestamp order placed at
+    status TEXT NOT NULL,
+    deleted_at INTEGER,
+    FOREIGN KEY(supplier_id) REFERENCES suppliers(id)
+);
+
+-- table of purchase order lines
+-- prices are stored in minor units (x100), quantities in thousandths (x1000)
+CREATE TABLE purchase_order_lines (
+    supply_id INTEGER NOT NULL,
+    supply_name TEXT NOT NULL,
+    unit_name TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    expected_unit_price INTEGER NOT NULL,
+    received_quantity INTEGER NOT NULL DEFAULT 0,
+    purchase_order_id INTEGER NOT NULL,
+    FOREIGN KEY(supply_id) REFERENCES supplies(id),
+    FOREIGN KEY(purchase_order_id) REFERENCES purchase_orders(id)
+);
+
+-- table of journals deliveries were recorded in
+CREATE TABLE purchase_order_journals (
+    purchase_order_id INTEGER NOT NULL,
+    journal_id INTEGER NOT NULL,
+    FOREIGN KEY(purchase_order_id) REFERENCES purchase_orders(id),
+    FOREIGN KEY(journal_id) REFERENCES journals(id)
+);
+
+PRAGMA user_version = 12;
@@ -0,0 +1,9 @@
+-- add par levels and reorder points of supplies
+-- quantities are stored in thousandths (x1000) of the stock unit
+-- supplies without them are not suggested for reorder
+
+ALTER TABLE supplies ADD COLUMN par_level INTEGER;
+
+ALTER TABLE supplies ADD COLUMN reorder_point INTEGER;
+
+PRAGMA user_version = 13;
@@ -0,0 +1,33 @@
+-- create waste records of stock thrown away
+
+-- table of sequence for waste id generation
+CREATE TABLE waste_records_id_sequence (
+    name TEXT PRIMARY KEY,
+    value INTEGER DEFAULT 0
+);
+
+INSERT INTO waste_records_id_sequence (
+    name,
+    value
+) VALUES (
+    'waste_id',
+    0
+);
+
+-- table of waste records
+-- quantity is stored in thousandths (x1000), prices in minor units (x100)
+CREATE TABLE waste_records (
+    id INTEGER PRIMARY KEY,
+    wasted_at INTEGER NOT NULL, -- timestamp stock thrown away at
+    supply_id INTEGER NOT NULL,
+    supply_name TEXT NOT NULL,
+    unit_name TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    total_price INTEGER NOT NULL,
+    reason TEXT NOT NULL, -- expired, damaged, over_prep or other
+    deleted_at INTEGER,
+    FOREIGN KEY(supply_id) REFERENCES supplies(id)
+);
+
+PRAGMA user_version = 14;
@@ -0,0 +1,34 @@
+-- create storage locations stock is counted in, e.g. 冷蔵庫・冷凍庫・倉庫
+-- existing supplies are not placed and existing stocktaking records have no location
+
+-- table of sequence for storage location id generation
+CREATE TABLE storage_locations_id_sequence (
+    name TEXT PRIMARY KEY,
+    value INTEGER DEFAULT 0
+);
+
+INSERT INTO storage_locations_id_sequence (
+    name,
+    value
+) VALUES (
+    'storage_location_id',
+    0
+);
+
+-- table of storage locations
+-- sort_order orders the locations on count sheets
+CREATE TABLE storage_locations (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    deleted_at INTEGER
+);
+
+-- default location of a supply and its position within the location
+ALTER TABLE supplies ADD COLUMN storage_location_id INTEGER REFERENCES storage_locations(id);
+ALTER TABLE supplies ADD COLUMN storage_sort_order INTEGER;
+
+-- location the quantity was counted in
+ALTER TABLE stocktaking_records ADD COLUMN storage_location_id INTEGER REFERENCES storage_locations(id);
+
+PRAGMA user_version = 15;
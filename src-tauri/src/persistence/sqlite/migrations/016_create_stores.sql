@@ -0,0 +1,41 @@
+-- create stores suppliers, supplies, journals and stocktakings belong to
+-- existing data is moved into the default store
+
+-- table of sequence for store id generation
+CREATE TABLE stores_id_sequence (
+    name TEXT PRIMARY KEY,
+    value INTEGER DEFAULT 0
+);
+
+-- the default store takes the first id
+INSERT INTO stores_id_sequence (
+    name,
+    value
+) VALUES (
+    'store_id',
+    1
+);
+
+-- table of stores
+CREATE TABLE stores (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    deleted_at INTEGER
+);
+
+INSERT INTO stores (
+    id,
+    name
+) VALUES (
+    1,
+    '本店'
+);
+
+-- store the data is recorded in
+-- SQLite cannot add a REFERENCES column with a non-NULL default to a table holding rows
+ALTER TABLE suppliers ADD COLUMN store_id INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE supplies ADD COLUMN store_id INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE journals ADD COLUMN store_id INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE stocktakings ADD COLUMN store_id INTEGER NOT NULL DEFAULT 1;
+
+PRAGMA user_version = 16;
@@ -0,0 +1,45 @@
+-- create transfers of stock between stores
+
+-- table of sequence for transfer id generation
+CREATE TABLE transfers_id_sequence (
+    name TEXT PRIMARY KEY,
+    value INTEGER DEFAULT 0
+);
+
+INSERT INTO transfers_id_sequence (
+    name,
+    value
+) VALUES (
+    'transfer_id',
+    0
+);
+
+-- table of transfers
+CREATE TABLE transfers (
+    id INTEGER PRIMARY KEY,
+    transferred_at INTEGER NOT NULL, -- timestamp stock moved at
+    source_store_id INTEGER NOT NULL,
+    destination_store_id INTEGER NOT NULL,
+    deleted_at INTEGER,
+    FOREIGN KEY(source_store_id) REFERENCES stores(id),
+    FOREIGN KEY(destination_store_id) REFERENCES stores(id)
+);
+
+-- table of transfer lines
+-- supply_id is the supply of the source store, destination_supply_id that of the destination
+-- prices are stored in minor units (x100), quantities in thousandths (x1000)
+CREATE TABLE transfer_lines (
+    supply_id INTEGER NOT NULL,
+    destination_supply_id INTEGER NOT NULL,
+    supply_name TEXT NOT NULL,
+    unit_name TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    unit_price INTEGER NOT NULL,
+    total_price INTEGER NOT NULL,
+    transfer_id INTEGER NOT NULL,
+    FOREIGN KEY(supply_id) REFERENCES supplies(id),
+    FOREIGN KEY(destination_supply_id) REFERENCES supplies(id),
+    FOREIGN KEY(transfer_id) REFERENCES transfers(id)
+);
+
+PRAGMA user_version = 17;
@@ -0,0 +1,9 @@
+-- add lot numbers and expiry dates of received goods
+
+ALTER TABLE journal_records ADD COLUMN lot_number TEXT;
+ALTER TABLE journal_records ADD COLUMN expiry_date INTEGER; -- timestamp goods expire at
+
+-- lot the counted quantity belongs to
+ALTER TABLE stocktaking_records ADD COLUMN lot_number TEXT;
+
+PRAGMA user_version = 18;
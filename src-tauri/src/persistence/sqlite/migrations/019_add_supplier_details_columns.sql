@@ -0,0 +1,18 @@
+-- add contact details and billing terms of suppliers
+
+ALTER TABLE suppliers ADD COLUMN contact_person TEXT;
+ALTER TABLE suppliers ADD COLUMN phone_number TEXT;
+ALTER TABLE suppliers ADD COLUMN fax_number TEXT;
+ALTER TABLE suppliers ADD COLUMN email TEXT;
+ALTER TABLE suppliers ADD COLUMN postal_code TEXT;
+ALTER TABLE suppliers ADD COLUMN address TEXT;
+ALTER TABLE suppliers ADD COLUMN notes TEXT;
+
+-- billing terms, all null if not agreed
+-- days are 1 to 28, or 31 for the end of the month
+ALTER TABLE suppliers ADD COLUMN closing_day INTEGER;
+ALTER TABLE suppliers ADD COLUMN payment_months_later INTEGER;
+ALTER TABLE suppliers ADD COLUMN payment_day INTEGER;
+ALTER TABLE suppliers ADD COLUMN payment_method TEXT; -- bank_transfer, direct_debit, cash or other
+
+PRAGMA user_version = 19;
@@ -0,0 +1,48 @@
+-- create supplier invoices and payments against them
+
+-- table of sequence for supplier invoice and payment id generation
+CREATE TABLE supplier_invoices_id_sequence (
+    name TEXT PRIMARY KEY,
+    value INTEGER DEFAULT 0
+);
+
+INSERT INTO supplier_invoices_id_sequence (
+    name,
+    value
+) VALUES (
+    'supplier_invoice_id',
+    0
+), (
+    'payment_id',
+    0
+);
+
+-- table of supplier invoices
+-- period_start and period_end are timestamps of the closing period billed, both included
+-- amounts are stored in minor units (x100)
+CREATE TABLE supplier_invoices (
+    id INTEGER PRIMARY KEY,
+    supplier_id INTEGER NOT NULL,
+    invoice_number TEXT,
+    period_start INTEGER NOT NULL,
+    period_end INTEGER NOT NULL,
+    due_date INTEGER NOT NULL,
+    amount INTEGER NOT NULL,
+    store_id INTEGER NOT NULL,
+    deleted_at INTEGER,
+    FOREIGN KEY(supplier_id) REFERENCES suppliers(id),
+    FOREIGN KEY(store_id) REFERENCES stores(id)
+);
+
+-- table of payments against supplier invoices
+-- method is one of bank_transfer, direct_debit, cash or other
+CREATE TABLE supplier_payments (
+    id INTEGER PRIMARY KEY,
+    paid_at INTEGER NOT NULL,
+    amount INTEGER NOT NULL,
+    method TEXT NOT NULL,
+    invoice_id INTEGER NOT NULL,
+    FOREIGN KEY(invoice_id) REFERENCES supplier_invoices(id)
+);
+
+PRAGMA user_version = 20;
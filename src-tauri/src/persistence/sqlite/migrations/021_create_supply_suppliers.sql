@@ -0,0 +1,34 @@
+-- let supplies be offered by multiple suppliers with per-supplier item codes and prices
+
+-- table of suppliers offering supplies
+-- unit_price is the current price per purchase unit, stored in minor units (x100)
+-- preferred is 1 for the supplier a supply is usually bought from, one per supply
+CREATE TABLE supply_suppliers (
+    supply_id INTEGER NOT NULL,
+    supplier_id INTEGER NOT NULL,
+    item_code TEXT,
+    unit_price INTEGER,
+    preferred INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY(supply_id, supplier_id),
+    FOREIGN KEY(supply_id) REFERENCES supplies(id),
+    FOREIGN KEY(supplier_id) REFERENCES suppliers(id)
+);
+
+-- the supplier of each existing supply becomes its preferred one
+INSERT INTO supply_suppliers (
+    supply_id,
+    supplier_id,
+    preferred
+)
+SELECT
+    id,
+    supplier_id,
+    1
+FROM supplies
+WHERE supplier_id IS NOT NULL;
+
+-- supplies.supplier_id is no longer used
+-- the column is kept as SQLite cannot drop a column referenced by a foreign key
+UPDATE supplies SET supplier_id = NULL;
+
+PRAGMA user_version = 21;
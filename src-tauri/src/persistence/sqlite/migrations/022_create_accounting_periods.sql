@@ -0,0 +1,41 @@
+-- create accounting periods closed to lock their records
+
+-- table of sequence for accounting period id generation
+CREATE TABLE accounting_periods_id_sequence (
+    name TEXT PRIMARY KEY,
+    value INTEGER DEFAULT 0
+);
+
+INSERT INTO accounting_periods_id_sequence (
+    name,
+    value
+) VALUES (
+    'accounting_period_id',
+    0
+);
+
+-- table of accounting periods
+-- period_start and period_end are timestamps, both included
+-- closed is 1 while records in the period are locked
+CREATE TABLE accounting_periods (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    period_start INTEGER NOT NULL,
+    period_end INTEGER NOT NULL,
+    closed INTEGER NOT NULL,
+    store_id INTEGER NOT NULL,
+    FOREIGN KEY(store_id) REFERENCES stores(id)
+);
+
+-- table of closing and reopening of accounting periods, in the order performed
+-- operation is close or reopen
+CREATE TABLE accounting_period_audit_entries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    operation TEXT NOT NULL,
+    performed_at INTEGER NOT NULL,
+    note TEXT,
+    accounting_period_id INTEGER NOT NULL,
+    FOREIGN KEY(accounting_period_id) REFERENCES accounting_periods(id)
+);
+
+PRAGMA user_version = 22;
@@ -0,0 +1,33 @@
+-- create the audit log of changes to suppliers, supplies, journals and stocktakings
+
+-- table of audit log entries, only appended
+-- entity is supplier, supply, journal or stocktaking
+-- operation is register, update, record, edit, delete, restore or purge
+-- snapshots are JSON of the entity before and after the change, null if it did not exist
+CREATE TABLE audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    entity TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    operator TEXT,
+    logged_at INTEGER NOT NULL,
+    before_snapshot TEXT,
+    after_snapshot TEXT,
+    store_id INTEGER NOT NULL,
+    FOREIGN KEY(store_id) REFERENCES stores(id)
+);
+
+-- entries can be neither changed nor deleted
+CREATE TRIGGER audit_log_no_update
+BEFORE UPDATE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit log is append-only');
+END;
+
+CREATE TRIGGER audit_log_no_delete
+BEFORE DELETE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit log is append-only');
+END;
+
+PRAGMA user_version = 23;
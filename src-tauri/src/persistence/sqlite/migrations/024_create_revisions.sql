@@ -0,0 +1,87 @@
+-- create revisions keeping the prior versions of edited journals and stocktakings
+
+-- table of journal revisions, numbered from 1 per journal
+CREATE TABLE journal_revisions (
+    journal_id INTEGER NOT NULL,
+    number INTEGER NOT NULL,
+    revised_at INTEGER NOT NULL, -- timestamp the version was replaced by an edit
+    recorded_at INTEGER NOT NULL,
+    total_price INTEGER NOT NULL,
+    PRIMARY KEY(journal_id, number),
+    FOREIGN KEY(journal_id) REFERENCES journals(id)
+);
+
+-- table of the records of journal revisions
+CREATE TABLE journal_revision_records (
+    journal_id INTEGER NOT NULL,
+    revision_number INTEGER NOT NULL,
+    supply_id INTEGER NOT NULL,
+    supply_name TEXT NOT NULL,
+    supplier_id INTEGER NOT NULL,
+    supplier_name TEXT NOT NULL,
+    unit_name TEXT NOT NULL,
+    unit_price INTEGER NOT NULL,
+    quantity INTEGER NOT NULL,
+    total_price INTEGER NOT NULL,
+    tax_category TEXT NOT NULL,
+    tax_included INTEGER NOT NULL,
+    tax_price INTEGER NOT NULL,
+    invoice_registered INTEGER NOT NULL,
+    lot_number TEXT,
+    expiry_date INTEGER,
+    FOREIGN KEY(journal_id, revision_number) REFERENCES journal_revisions(journal_id, number)
+);
+
+-- table of stocktaking revisions, numbered from 1 per stocktaking
+CREATE TABLE stocktaking_revisions (
+    stocktaking_id INTEGER NOT NULL,
+    number INTEGER NOT NULL,
+    revised_at INTEGER NOT NULL, -- timestamp the version was replaced by an edit
+    recorded_at INTEGER NOT NULL,
+    total_price INTEGER NOT NULL,
+    PRIMARY KEY(stocktaking_id, number),
+    FOREIGN KEY(stocktaking_id) REFERENCES stocktakings(id)
+);
+
+-- table of the records of stocktaking revisions
+CREATE TABLE stocktaking_revision_records (
+    stocktaking_id INTEGER NOT NULL,
+    revision_number INTEGER NOT NULL,
+    supply_id INTEGER NOT NULL,
+    supply_name TEXT NOT NULL,
+    unit_name TEXT NOT NULL,
+    unit_price INTEGER NOT NULL,
+    quantity INTEGER NOT NULL,
+    total_price INTEGER NOT NULL,
+    valuation_method TEXT,
+    storage_location_id INTEGER,
+    lot_number TEXT,
+    FOREIGN KEY(stocktaking_id, revision_number) REFERENCES stocktaking_revisions(stocktaking_id, number)
+);
+
+-- revisions are never changed, they are only deleted when their record is purged
+CREATE TRIGGER journal_revisions_no_update
+BEFORE UPDATE ON journal_revisions
+BEGIN
+    SELECT RAISE(ABORT, 'revisions are immutable');
+END;
+
+CREATE TRIGGER journal_revision_records_no_update
+BEFORE UPDATE ON journal_revision_records
+BEGIN
+    SELECT RAISE(ABORT, 'revisions are immutable');
+END;
+
+CREATE TRIGGER stocktaking_revisions_no_update
+BEFORE UPDATE ON stocktaking_revisions
+BEGIN
+    SELECT RAISE(ABORT, 'revisions are immutable');
+END;
+
+CREATE TRIGGER stocktaking_revision_records_no_update
+BEFORE UPDATE ON stocktaking_revision_records
+BEGIN
+    SELECT RAISE(ABORT, 'revisions are immutable');
+END;
+
+PRAGMA user_version = 24;
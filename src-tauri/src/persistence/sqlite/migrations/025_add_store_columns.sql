@@ -0,0 +1,10 @@
+-- move consumptions, waste records and purchase orders into stores
+-- existing data is moved into the default store
+
+-- store the data is recorded in
+-- SQLite cannot add a REFERENCES column with a non-NULL default to a table holding rows
+ALTER TABLE consumptions ADD COLUMN store_id INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE waste_records ADD COLUMN store_id INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE purchase_orders ADD COLUMN store_id INTEGER NOT NULL DEFAULT 1;
+
+PRAGMA user_version = 25;
@@ -1,8 +1,20 @@
+mod accounting;
+mod audit;
+mod category;
+mod consumption;
 mod jorunal;
+mod location;
 mod migration;
+mod payable;
+mod purchase_order;
 mod stocktaking;
+mod store;
 mod supplier;
 mod supply;
+mod transfer;
+mod trash;
+mod unit;
+mod waste;
 
 #[cfg(test)]
 mod supplier_test;
@@ -16,11 +28,58 @@ mod journal_test;
 #[cfg(test)]
 mod stocktaking_test;
 
+#[cfg(test)]
+mod unit_test;
+
+#[cfg(test)]
+mod consumption_test;
+
+#[cfg(test)]
+mod category_test;
+
+#[cfg(test)]
+mod location_test;
+
+#[cfg(test)]
+mod purchase_order_test;
+
+#[cfg(test)]
+mod waste_test;
+
+#[cfg(test)]
+mod store_test;
+
+#[cfg(test)]
+mod transfer_test;
+
+#[cfg(test)]
+mod payable_test;
+
+#[cfg(test)]
+mod accounting_test;
+
+#[cfg(test)]
+mod audit_test;
+
+#[cfg(test)]
+mod migration_test;
+
+pub use accounting::*;
+pub use audit::*;
+pub use category::*;
+pub use consumption::*;
 pub use jorunal::*;
+pub use location::*;
 pub use migration::*;
+pub use payable::*;
+pub use purchase_order::*;
 pub use stocktaking::*;
+pub use store::*;
 pub use supplier::*;
 pub use supply::*;
+pub use transfer::*;
+pub use unit::*;
+pub use waste::*;
 
 impl From<crate::core::Error> for rusqlite::Error {
     fn from(value: crate::core::Error) -> Self {
@@ -0,0 +1,472 @@
+//! This module provides the SQLite-based implementation of the `ForSupplierInvoicePersistence` trait.
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::named_params;
+use rusqlite::Connection;
+use rusqlite::Row;
+use rusqlite::Transaction;
+
+use crate::core::domain::entities::payable::SupplierInvoice;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::payable::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::trash::*;
+use crate::core::required_ports::*;
+use crate::core::Error;
+use crate::core::Result;
+
+use super::trash;
+
+/// SQLite implementation of `ForSupplierInvoicePersistence`
+pub struct SqliteSupplierInvoiceRepository {
+    db_path: String,
+}
+
+impl SqliteSupplierInvoiceRepository {
+    pub fn new(db_path: impl AsRef<str>) -> Self {
+        Self {
+            db_path: Path::new(db_path.as_ref()).to_string_lossy().to_string(),
+        }
+    }
+
+    /// Takes the next value of a sequence.
+    fn next_value(&self, name: &str) -> Result<i64> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.query_row(
+            r"
+            UPDATE supplier_invoices_id_sequence
+            SET value = value + 1
+            WHERE name = :name
+            RETURNING value
+            ",
+            named_params! { ":name": name },
+            |r| r.get::<_, i64>(0),
+        )
+        .map_err(|e| Error::InfrastructureError(format!("fail to query: {}", e)))
+    }
+
+    /// Reads invoices of the stores matching the query, only `id` if given, those in the trash if `trashed`.
+    fn select(
+        &self,
+        id: Option<&SupplierInvoiceId>,
+        trashed: bool,
+        store: &StoreContext,
+        query: FindSupplierInvoicesQuery,
+    ) -> Result<Vec<SupplierInvoice>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    supplier_invoices.id,
+                    supplier_invoices.supplier_id,
+                    supplier_invoices.invoice_number,
+                    supplier_invoices.period_start,
+                    supplier_invoices.period_end,
+                    supplier_invoices.due_date,
+                    supplier_invoices.amount,
+                    supplier_payments.id,
+                    supplier_payments.paid_at,
+                    supplier_payments.amount,
+                    supplier_payments.method
+                FROM supplier_invoices
+                LEFT JOIN supplier_payments
+                    ON supplier_payments.invoice_id = supplier_invoices.id
+                WHERE
+                    (:id IS NULL OR supplier_invoices.id = :id)
+                    AND
+                    (:store_id IS NULL OR supplier_invoices.store_id = :store_id)
+                    AND
+                    (:supplier_id IS NULL OR supplier_invoices.supplier_id = :supplier_id)
+                    AND
+                    (:start IS NULL OR :start <= supplier_invoices.period_end)
+                    AND
+                    (:end IS NULL OR supplier_invoices.period_start <= :end)
+                    AND
+                    (supplier_invoices.deleted_at IS NOT NULL) = :trashed
+                ORDER BY
+                    supplier_invoices.period_end DESC,
+                    supplier_invoices.id,
+                    supplier_payments.paid_at,
+                    supplier_payments.id
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let rows = statement
+            .query_map(
+                named_params! {
+                    ":id": id.map(|id| id.as_str()),
+                    ":trashed": trashed,
+                    ":store_id": store.scope().map(|id| id.as_str()),
+                    ":supplier_id": query.supplier_id.as_ref().map(|id| id.as_str()),
+                    ":start": query.period_start,
+                    ":end": query.period_end,
+                },
+                invoice_row,
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|row| {
+                row.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<(SupplierInvoice, Option<Payment>)>>>()?;
+
+        Ok(group_invoices(rows))
+    }
+}
+
+/// Reads an invoice and one of its payments, if any, from a joined row.
+fn invoice_row(row: &Row) -> rusqlite::Result<(SupplierInvoice, Option<Payment>)> {
+    let invoice = SupplierInvoice::restore(
+        SupplierInvoiceId::new(row.get::<_, i64>(0)?.to_string())?,
+        SupplierId::new(row.get::<_, i64>(1)?.to_string())?,
+        row.get::<_, Option<String>>(2)?
+            .map(InvoiceNumber::new)
+            .transpose()?,
+        BillingPeriod::new(row.get::<_, i64>(3)?, row.get::<_, i64>(4)?)?,
+        DueDate::new(row.get::<_, i64>(5)?),
+        InvoicedAmount::from_money(Money::from_minor(row.get::<_, i64>(6)?))?,
+        vec![],
+    );
+
+    let payment = match row.get::<_, Option<i64>>(7)? {
+        Some(payment_id) => Some(Payment::new(
+            PaymentId::new(payment_id.to_string())?,
+            PaidDateTime::new(row.get::<_, i64>(8)?),
+            PaymentAmount::from_money(Money::from_minor(row.get::<_, i64>(9)?))?,
+            PaymentMethod::new(row.get::<_, String>(10)?)?,
+        )),
+        None => None,
+    };
+
+    Ok((invoice, payment))
+}
+
+/// Groups joined rows into invoices, keeping the order of the rows.
+fn group_invoices(rows: Vec<(SupplierInvoice, Option<Payment>)>) -> Vec<SupplierInvoice> {
+    let mut invoices: Vec<(SupplierInvoice, Vec<Payment>)> = Vec::new();
+
+    for (invoice, payment) in rows {
+        let index = match invoices.iter().position(|i| i.0 == invoice) {
+            Some(index) => index,
+            None => {
+                invoices.push((invoice, vec![]));
+                invoices.len() - 1
+            }
+        };
+
+        if let Some(payment) = payment {
+            invoices[index].1.push(payment);
+        }
+    }
+
+    invoices
+        .into_iter()
+        .map(|(invoice, payments)| {
+            SupplierInvoice::restore(
+                invoice.id().clone(),
+                invoice.supplier_id().clone(),
+                invoice.invoice_number().cloned(),
+                *invoice.period(),
+                *invoice.due_date(),
+                *invoice.amount(),
+                payments,
+            )
+        })
+        .collect()
+}
+
+/// Inserts the payments of an invoice.
+fn insert_payments(tran: &Transaction, invoice: &SupplierInvoice) -> Result<()> {
+    let mut statement = tran
+        .prepare(
+            r"
+            INSERT INTO supplier_payments (
+                id,
+                paid_at,
+                amount,
+                method,
+                invoice_id
+            ) VALUES (
+                :id,
+                :paid_at,
+                :amount,
+                :method,
+                :invoice_id
+            )
+            ",
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to prepare statement: {}", e)))?;
+
+    for payment in invoice.payments() {
+        statement
+            .execute(named_params! {
+                ":id": payment.id().as_str(),
+                ":paid_at": payment.paid_at().as_i64(),
+                ":amount": payment.amount().as_money().as_minor(),
+                ":method": payment.method().as_str(),
+                ":invoice_id": invoice.id().as_str(),
+            })
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to execute statement: {}", e))
+            })?;
+    }
+
+    Ok(())
+}
+
+/// Implementation of `ForSupplierInvoicePersistence` for `SqliteSupplierInvoiceRepository`
+impl ForSupplierInvoicePersistence for SqliteSupplierInvoiceRepository {
+    fn next_id(&self) -> Result<SupplierInvoiceId> {
+        let next_id = self.next_value("supplier_invoice_id")?;
+
+        SupplierInvoiceId::new(next_id.to_string())
+    }
+
+    fn next_payment_id(&self) -> Result<PaymentId> {
+        let next_id = self.next_value("payment_id")?;
+
+        PaymentId::new(next_id.to_string())
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<SupplierInvoice>> {
+        self.find(
+            store,
+            FindSupplierInvoicesQuery {
+                supplier_id: None,
+                period_start: None,
+                period_end: None,
+            },
+        )
+    }
+
+    fn find(
+        &self,
+        store: &StoreContext,
+        query: FindSupplierInvoicesQuery,
+    ) -> Result<Vec<SupplierInvoice>> {
+        self.select(None, false, store, query)
+    }
+
+    fn get(&self, store: &StoreContext, id: SupplierInvoiceId) -> Result<Option<SupplierInvoice>> {
+        let invoices = self.select(
+            Some(&id),
+            false,
+            store,
+            FindSupplierInvoicesQuery {
+                supplier_id: None,
+                period_start: None,
+                period_end: None,
+            },
+        )?;
+
+        Ok(invoices.into_iter().next())
+    }
+
+    fn add(&self, store: &StoreContext, invoice: SupplierInvoice) -> Result<()> {
+        let store_id = store.store_id()?;
+
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let tran = conn.transaction().map_err(|e| {
+            Error::InfrastructureError(format!("failed to start transaction: {}", e))
+        })?;
+
+        let result: Result<()> = (|| {
+            tran.execute(
+                r"
+                INSERT INTO supplier_invoices (
+                    id,
+                    supplier_id,
+                    invoice_number,
+                    period_start,
+                    period_end,
+                    due_date,
+                    amount,
+                    store_id
+                ) VALUES (
+                    :id,
+                    :supplier_id,
+                    :invoice_number,
+                    :period_start,
+                    :period_end,
+                    :due_date,
+                    :amount,
+                    :store_id
+                )
+                ",
+                named_params! {
+                    ":id": invoice.id().as_str(),
+                    ":supplier_id": invoice.supplier_id().as_str(),
+                    ":invoice_number": invoice.invoice_number().map(|number| number.as_str()),
+                    ":period_start": invoice.period().start(),
+                    ":period_end": invoice.period().end(),
+                    ":due_date": invoice.due_date().as_i64(),
+                    ":amount": invoice.amount().as_money().as_minor(),
+                    ":store_id": store_id.as_str(),
+                },
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            insert_payments(&tran, &invoice)
+        })();
+
+        if result.is_err() {
+            tran.rollback()
+                .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+            return result;
+        }
+
+        tran.commit()
+            .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn save(&self, store: &StoreContext, invoice: SupplierInvoice) -> Result<()> {
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let tran = conn.transaction().map_err(|e| {
+            Error::InfrastructureError(format!("failed to start transaction: {}", e))
+        })?;
+
+        let result: Result<()> = (|| {
+            let updated = tran
+                .execute(
+                    r"
+                    UPDATE supplier_invoices
+                    SET
+                        invoice_number = :invoice_number,
+                        period_start = :period_start,
+                        period_end = :period_end,
+                        due_date = :due_date,
+                        amount = :amount
+                    WHERE
+                        id = :id
+                        AND
+                        (:store_id IS NULL OR store_id = :store_id)
+                    ",
+                    named_params! {
+                            ":id": invoice.id().as_str(),
+                            ":store_id": store.scope().map(|id| id.as_str()),
+                        ":invoice_number": invoice.invoice_number().map(|number| number.as_str()),
+                        ":period_start": invoice.period().start(),
+                        ":period_end": invoice.period().end(),
+                        ":due_date": invoice.due_date().as_i64(),
+                        ":amount": invoice.amount().as_money().as_minor(),
+                    },
+                )
+                .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            // an invoice of another store is left as it is
+            if updated == 0 {
+                return Ok(());
+            }
+
+            tran.execute(
+                r"
+                DELETE FROM supplier_payments
+                WHERE invoice_id = :id
+                ",
+                named_params! {
+                    ":id": invoice.id().as_str(),
+                },
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            insert_payments(&tran, &invoice)
+        })();
+
+        if result.is_err() {
+            tran.rollback()
+                .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+            return result;
+        }
+
+        tran.commit()
+            .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn delete(&self, store: &StoreContext, id: SupplierInvoiceId) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.execute(
+            r"
+            UPDATE supplier_invoices
+            SET deleted_at = :deleted_at
+            WHERE
+                id = :id
+                AND
+                (:store_id IS NULL OR store_id = :store_id)
+            ",
+            named_params! {
+                ":id": id.as_str(),
+                ":store_id": store.scope().map(|id| id.as_str()),
+                ":deleted_at": Utc::now().timestamp_millis(),
+            },
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<SupplierInvoice>>> {
+        let invoices = self.select(
+            None,
+            true,
+            store,
+            FindSupplierInvoicesQuery {
+                supplier_id: None,
+                period_start: None,
+                period_end: None,
+            },
+        )?;
+
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let deleted_ats = trash::deleted_ats(&conn, "supplier_invoices")?;
+
+        Ok(trash::trashed(invoices, &deleted_ats, |invoice| {
+            invoice.id().as_str()
+        }))
+    }
+
+    fn restore(&self, store: &StoreContext, id: SupplierInvoiceId) -> Result<()> {
+        trash::restore(
+            &self.db_path,
+            "supplier_invoices",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            None,
+        )
+    }
+
+    fn purge(&self, store: &StoreContext, id: SupplierInvoiceId) -> Result<()> {
+        trash::purge(
+            &self.db_path,
+            "supplier_invoices",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            &[("supplier_payments", "invoice_id")],
+            None,
+        )
+    }
+}
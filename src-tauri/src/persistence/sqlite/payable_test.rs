@@ -0,0 +1,193 @@
+use std::fs::{self};
+use std::path::Path;
+
+use rusqlite::Connection;
+use scopeguard::defer;
+
+use crate::core::domain::entities::payable::SupplierInvoice;
+use crate::core::domain::values::payable::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::required_ports::*;
+use crate::persistence::sqlite::{migrate, SqliteSupplierInvoiceRepository};
+
+#[test]
+fn supplier_invoice_repository_test() {
+    let tmp_path = Path::new("tmp/supplier_invoice_repository_test.db");
+
+    defer! {
+        fs::remove_file(tmp_path).unwrap();
+    }
+
+    if let Some(parent) = tmp_path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+
+    migrate(tmp_path.to_string_lossy()).unwrap();
+
+    let conn = Connection::open(tmp_path).unwrap();
+
+    conn.execute("INSERT INTO stores (id, name) VALUES (2, '駅前店');", [])
+        .unwrap();
+
+    conn.execute(
+        "INSERT INTO suppliers (id, name, store_id) VALUES (1, 'SupplierA', 1), (2, 'SupplierB', 1), (3, 'SupplierC', 2);",
+        [],
+    )
+    .unwrap();
+
+    let repository = SqliteSupplierInvoiceRepository::new(tmp_path.to_string_lossy());
+
+    let main = StoreContext::Store(StoreId::new("1").unwrap());
+    let branch = StoreContext::Store(StoreId::new("2").unwrap());
+
+    let next_id = repository.next_id().unwrap();
+
+    assert_eq!(next_id, SupplierInvoiceId::new("1").unwrap());
+
+    let mut invoice = SupplierInvoice::new(
+        next_id,
+        SupplierId::new("1").unwrap(),
+        Some(InvoiceNumber::new("INV-001").unwrap()),
+        BillingPeriod::new(1000, 1999).unwrap(),
+        DueDate::new(3000),
+        InvoicedAmount::new(11000.0).unwrap(),
+    )
+    .unwrap();
+
+    repository.add(&main, invoice.clone()).unwrap();
+
+    let other = SupplierInvoice::new(
+        repository.next_id().unwrap(),
+        SupplierId::new("2").unwrap(),
+        None,
+        BillingPeriod::new(2000, 2999).unwrap(),
+        DueDate::new(4000),
+        InvoicedAmount::new(500.0).unwrap(),
+    )
+    .unwrap();
+
+    repository.add(&main, other.clone()).unwrap();
+
+    let branch_invoice = SupplierInvoice::new(
+        repository.next_id().unwrap(),
+        SupplierId::new("3").unwrap(),
+        None,
+        BillingPeriod::new(1000, 1999).unwrap(),
+        DueDate::new(3000),
+        InvoicedAmount::new(800.0).unwrap(),
+    )
+    .unwrap();
+
+    repository.add(&branch, branch_invoice.clone()).unwrap();
+
+    // payments are saved with the invoice
+    invoice
+        .pay(Payment::new(
+            repository.next_payment_id().unwrap(),
+            PaidDateTime::new(2500),
+            PaymentAmount::new(6000.0).unwrap(),
+            PaymentMethod::BankTransfer,
+        ))
+        .unwrap();
+    invoice
+        .pay(Payment::new(
+            repository.next_payment_id().unwrap(),
+            PaidDateTime::new(2600),
+            PaymentAmount::new(1000.0).unwrap(),
+            PaymentMethod::Cash,
+        ))
+        .unwrap();
+
+    repository.save(&main, invoice.clone()).unwrap();
+
+    let saved = repository
+        .get(&main, SupplierInvoiceId::new("1").unwrap())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(saved.invoice_number(), invoice.invoice_number());
+    assert_eq!(saved.payments(), invoice.payments());
+    assert_eq!(saved.balance().unwrap().as_f64(), 4000.0);
+
+    invoice
+        .cancel_payment(&PaymentId::new("2").unwrap())
+        .unwrap();
+
+    repository.save(&main, invoice.clone()).unwrap();
+
+    let saved = repository
+        .get(&main, invoice.id().clone())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(saved.payments(), invoice.payments());
+
+    // an invoice of another store is neither read nor changed
+    assert_eq!(repository.get(&branch, invoice.id().clone()).unwrap(), None);
+
+    let mut changed = branch_invoice.clone();
+    changed
+        .pay(Payment::new(
+            repository.next_payment_id().unwrap(),
+            PaidDateTime::new(2700),
+            PaymentAmount::new(800.0).unwrap(),
+            PaymentMethod::Cash,
+        ))
+        .unwrap();
+
+    repository.save(&main, changed).unwrap();
+    repository
+        .delete(&main, branch_invoice.id().clone())
+        .unwrap();
+
+    assert_eq!(
+        repository.list(&main).unwrap(),
+        vec![other.clone(), invoice.clone()]
+    );
+    assert_eq!(
+        repository.list(&branch).unwrap(),
+        vec![branch_invoice.clone()]
+    );
+
+    // invoices whose period overlaps the query
+    let found = repository
+        .find(
+            &main,
+            FindSupplierInvoicesQuery {
+                supplier_id: None,
+                period_start: Some(1500),
+                period_end: Some(1800),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(found, vec![invoice.clone()]);
+
+    let found = repository
+        .find(
+            &StoreContext::Consolidated,
+            FindSupplierInvoicesQuery {
+                supplier_id: Some(SupplierId::new("2").unwrap()),
+                period_start: None,
+                period_end: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(found, vec![other.clone()]);
+
+    repository.delete(&main, other.id().clone()).unwrap();
+
+    assert_eq!(repository.list(&main).unwrap(), vec![invoice]);
+
+    // a deleted invoice is restored and purged only from its store
+    repository.restore(&branch, other.id().clone()).unwrap();
+    repository.purge(&branch, other.id().clone()).unwrap();
+
+    assert_eq!(repository.list_deleted(&main).unwrap().len(), 1);
+
+    repository.restore(&main, other.id().clone()).unwrap();
+
+    assert_eq!(repository.list_deleted(&main).unwrap().len(), 0);
+}
@@ -0,0 +1,536 @@
+//! This module provides the SQLite-based implementation of the `ForPurchaseOrderPersistence` trait.
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::named_params;
+use rusqlite::Connection;
+use rusqlite::Row;
+use rusqlite::Transaction;
+
+use crate::core::domain::entities::stock::PurchaseOrder;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::order::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::trash::*;
+use crate::core::required_ports::*;
+use crate::core::Error;
+use crate::core::Result;
+
+use super::trash;
+
+/// SQLite implementation of `ForPurchaseOrderPersistence`
+pub struct SqlitePurchaseOrderRepository {
+    db_path: String,
+}
+
+impl SqlitePurchaseOrderRepository {
+    pub fn new(db_path: impl AsRef<str>) -> Self {
+        Self {
+            db_path: Path::new(db_path.as_ref()).to_string_lossy().to_string(),
+        }
+    }
+
+    /// Reads the journal ids linked to each purchase order.
+    fn journal_ids(&self, conn: &Connection) -> Result<Vec<(PurchaseOrderId, JournalId)>> {
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    purchase_order_id,
+                    journal_id
+                FROM purchase_order_journals
+                ORDER BY rowid
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let journal_ids = statement
+            .query_map([], |row| {
+                Ok((
+                    PurchaseOrderId::new(row.get::<_, i64>(0)?.to_string())?,
+                    JournalId::new(row.get::<_, i64>(1)?.to_string())?,
+                ))
+            })
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|row| {
+                row.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<(PurchaseOrderId, JournalId)>>>()?;
+
+        Ok(journal_ids)
+    }
+
+    /// Reads purchase orders of the stores matching the query, only `id` if given, those in the trash if `trashed`.
+    fn select(
+        &self,
+        store: &StoreContext,
+        id: Option<&PurchaseOrderId>,
+        trashed: bool,
+        query: FindPurchaseOrdersQuery,
+    ) -> Result<Vec<PurchaseOrder>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    purchase_orders.id,
+                    purchase_orders.supplier_id,
+                    purchase_orders.supplier_name,
+                    purchase_orders.ordered_at,
+                    purchase_orders.status,
+                    purchase_order_lines.supply_id,
+                    purchase_order_lines.supply_name,
+                    purchase_order_lines.unit_name,
+                    purchase_order_lines.quantity,
+                    purchase_order_lines.expected_unit_price,
+                    purchase_order_lines.received_quantity
+                FROM purchase_orders
+                INNER JOIN purchase_order_lines
+                    ON purchase_order_lines.purchase_order_id = purchase_orders.id
+                WHERE
+                    (:id IS NULL OR purchase_orders.id = :id)
+                    AND
+                    (:start IS NULL OR :start <= purchase_orders.ordered_at)
+                    AND
+                    (:end IS NULL OR purchase_orders.ordered_at <= :end)
+                    AND
+                    (:supplier_id IS NULL OR purchase_orders.supplier_id = :supplier_id)
+                    AND
+                    (:status IS NULL OR purchase_orders.status = :status)
+                    AND
+                    (:store_id IS NULL OR purchase_orders.store_id = :store_id)
+                    AND
+                    (purchase_orders.deleted_at IS NOT NULL) = :trashed
+                ORDER BY purchase_orders.ordered_at DESC, purchase_orders.id, purchase_order_lines.rowid
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let rows = statement
+            .query_map(
+                named_params! {
+                    ":id": id.map(|id| id.as_str()),
+                    ":trashed": trashed,
+                    ":start": query.period_start.as_ref().map(|start| start.as_i64()),
+                    ":end": query.period_end.as_ref().map(|end| end.as_i64()),
+                    ":supplier_id": query.supplier_id.as_ref().map(|id| id.as_str()),
+                    ":status": query.status.as_ref().map(|status| status.as_str()),
+                    ":store_id": store.scope().map(|id| id.as_str()),
+                },
+                purchase_order_row,
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|row| {
+                row.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<(PurchaseOrder, PurchaseOrderLine)>>>()?;
+
+        let journal_ids = self.journal_ids(&conn)?;
+
+        Ok(group_purchase_orders(rows, &journal_ids))
+    }
+}
+
+/// Reads a purchase order and one of its lines from a joined row.
+fn purchase_order_row(row: &Row) -> rusqlite::Result<(PurchaseOrder, PurchaseOrderLine)> {
+    let order = PurchaseOrder::restore(
+        PurchaseOrderId::new(row.get::<_, i64>(0)?.to_string())?,
+        SupplierId::new(row.get::<_, i64>(1)?.to_string())?,
+        SupplierName::new(row.get::<_, String>(2)?)?,
+        OrderedDateTime::new(row.get::<_, i64>(3)?),
+        OrderStatus::new(row.get::<_, String>(4)?)?,
+        vec![],
+        vec![],
+    );
+
+    let line = PurchaseOrderLine::restore(
+        SupplyId::new(row.get::<_, i64>(5)?.to_string())?,
+        SupplyName::new(row.get::<_, String>(6)?)?,
+        UnitName::new(row.get::<_, String>(7)?)?,
+        PurchaseQuantity::from_quantity(Quantity::from_raw(row.get::<_, i64>(8)?))?,
+        PurchaseUnitPrice::from_money(Money::from_minor(row.get::<_, i64>(9)?))?,
+        Quantity::from_raw(row.get::<_, i64>(10)?),
+    );
+
+    Ok((order, line))
+}
+
+/// Groups joined rows into purchase orders, keeping the order of the rows.
+fn group_purchase_orders(
+    rows: Vec<(PurchaseOrder, PurchaseOrderLine)>,
+    journal_ids: &[(PurchaseOrderId, JournalId)],
+) -> Vec<PurchaseOrder> {
+    let mut orders: Vec<(PurchaseOrder, Vec<PurchaseOrderLine>)> = Vec::new();
+
+    for (order, line) in rows {
+        match orders.iter_mut().find(|o| o.0 == order) {
+            Some(o) => o.1.push(line),
+            None => orders.push((order, vec![line])),
+        }
+    }
+
+    orders
+        .into_iter()
+        .map(|(order, lines)| {
+            PurchaseOrder::restore(
+                order.id().clone(),
+                order.supplier_id().clone(),
+                order.supplier_name().clone(),
+                order.ordered_at().clone(),
+                order.status(),
+                lines,
+                journal_ids
+                    .iter()
+                    .filter(|(order_id, _)| order_id == order.id())
+                    .map(|(_, journal_id)| journal_id.clone())
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+/// Inserts the lines and linked journals of a purchase order.
+fn insert_details(tran: &Transaction, order: &PurchaseOrder) -> Result<()> {
+    let mut statement = tran
+        .prepare(
+            r"
+            INSERT INTO purchase_order_lines (
+                supply_id,
+                supply_name,
+                unit_name,
+                quantity,
+                expected_unit_price,
+                received_quantity,
+                purchase_order_id
+            ) VALUES (
+                :supply_id,
+                :supply_name,
+                :unit_name,
+                :quantity,
+                :expected_unit_price,
+                :received_quantity,
+                :purchase_order_id
+            )
+            ",
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to prepare statement: {}", e)))?;
+
+    for line in order.lines() {
+        statement
+            .execute(named_params! {
+                ":supply_id": line.supply_id().as_str(),
+                ":supply_name": line.supply_name().as_str(),
+                ":unit_name": line.unit_name().as_str(),
+                ":quantity": line.quantity().as_quantity().as_raw(),
+                ":expected_unit_price": line.expected_unit_price().as_money().as_minor(),
+                ":received_quantity": line.received_quantity().as_raw(),
+                ":purchase_order_id": order.id().as_str(),
+            })
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to execute statement: {}", e))
+            })?;
+    }
+
+    let mut statement = tran
+        .prepare(
+            r"
+            INSERT INTO purchase_order_journals (
+                purchase_order_id,
+                journal_id
+            ) VALUES (
+                :purchase_order_id,
+                :journal_id
+            )
+            ",
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to prepare statement: {}", e)))?;
+
+    for journal_id in order.journal_ids() {
+        statement
+            .execute(named_params! {
+                ":purchase_order_id": order.id().as_str(),
+                ":journal_id": journal_id.as_str(),
+            })
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to execute statement: {}", e))
+            })?;
+    }
+
+    Ok(())
+}
+
+/// Implementation of `ForPurchaseOrderPersistence` for `SqlitePurchaseOrderRepository`
+impl ForPurchaseOrderPersistence for SqlitePurchaseOrderRepository {
+    fn next_id(&self) -> Result<PurchaseOrderId> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let next_id = conn
+            .query_row(
+                r"
+                UPDATE purchase_orders_id_sequence
+                SET value = value + 1
+                WHERE name = 'purchase_order_id'
+                RETURNING value
+                ",
+                [],
+                |r| r.get::<_, i64>(0),
+            )
+            .map_err(|e| Error::InfrastructureError(format!("fail to query: {}", e)))?;
+
+        let next_id = PurchaseOrderId::new(next_id.to_string())?;
+
+        Ok(next_id)
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<PurchaseOrder>> {
+        self.find(
+            store,
+            FindPurchaseOrdersQuery {
+                period_start: None,
+                period_end: None,
+                supplier_id: None,
+                status: None,
+            },
+        )
+    }
+
+    fn find(
+        &self,
+        store: &StoreContext,
+        query: FindPurchaseOrdersQuery,
+    ) -> Result<Vec<PurchaseOrder>> {
+        self.select(store, None, false, query)
+    }
+
+    fn get(&self, store: &StoreContext, id: PurchaseOrderId) -> Result<Option<PurchaseOrder>> {
+        let orders = self.select(
+            store,
+            Some(&id),
+            false,
+            FindPurchaseOrdersQuery {
+                period_start: None,
+                period_end: None,
+                supplier_id: None,
+                status: None,
+            },
+        )?;
+
+        Ok(orders.into_iter().next())
+    }
+
+    fn add(&self, store: &StoreContext, order: PurchaseOrder) -> Result<()> {
+        let store_id = store.store_id()?;
+
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let tran = conn.transaction().map_err(|e| {
+            Error::InfrastructureError(format!("failed to start transaction: {}", e))
+        })?;
+
+        let result: Result<()> = (|| {
+            tran.execute(
+                r"
+                INSERT INTO purchase_orders (
+                    id,
+                    supplier_id,
+                    supplier_name,
+                    ordered_at,
+                    status,
+                    store_id
+                ) VALUES (
+                    :id,
+                    :supplier_id,
+                    :supplier_name,
+                    :ordered_at,
+                    :status,
+                    :store_id
+                )
+                ",
+                named_params! {
+                    ":id": order.id().as_str(),
+                    ":supplier_id": order.supplier_id().as_str(),
+                    ":supplier_name": order.supplier_name().as_str(),
+                    ":ordered_at": order.ordered_at().as_i64(),
+                    ":status": order.status().as_str(),
+                    ":store_id": store_id.as_str(),
+                },
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            insert_details(&tran, &order)
+        })();
+
+        if result.is_err() {
+            tran.rollback()
+                .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+            return result;
+        }
+
+        tran.commit()
+            .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn save(&self, store: &StoreContext, order: PurchaseOrder) -> Result<()> {
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let tran = conn.transaction().map_err(|e| {
+            Error::InfrastructureError(format!("failed to start transaction: {}", e))
+        })?;
+
+        let result: Result<()> = (|| {
+            let updated = tran
+                .execute(
+                    r"
+                    UPDATE purchase_orders
+                    SET
+                        supplier_id = :supplier_id,
+                        supplier_name = :supplier_name,
+                        ordered_at = :ordered_at,
+                        status = :status
+                    WHERE
+                        id = :id
+                        AND
+                        (:store_id IS NULL OR store_id = :store_id)
+                    ",
+                    named_params! {
+                            ":id": order.id().as_str(),
+                            ":store_id": store.scope().map(|id| id.as_str()),
+                        ":supplier_id": order.supplier_id().as_str(),
+                        ":supplier_name": order.supplier_name().as_str(),
+                        ":ordered_at": order.ordered_at().as_i64(),
+                        ":status": order.status().as_str(),
+                    },
+                )
+                .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            // a purchase order of another store is left as it is
+            if updated == 0 {
+                return Ok(());
+            }
+
+            tran.execute(
+                r"
+                DELETE FROM purchase_order_lines
+                WHERE purchase_order_id = :id
+                ",
+                named_params! {
+                    ":id": order.id().as_str(),
+                },
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            tran.execute(
+                r"
+                DELETE FROM purchase_order_journals
+                WHERE purchase_order_id = :id
+                ",
+                named_params! {
+                    ":id": order.id().as_str(),
+                },
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            insert_details(&tran, &order)
+        })();
+
+        if result.is_err() {
+            tran.rollback()
+                .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+            return result;
+        }
+
+        tran.commit()
+            .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn delete(&self, store: &StoreContext, id: PurchaseOrderId) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.execute(
+            r"
+            UPDATE purchase_orders
+            SET deleted_at = :deleted_at
+            WHERE
+                id = :id
+                AND
+                (:store_id IS NULL OR store_id = :store_id)
+            ",
+            named_params! {
+                ":id": id.as_str(),
+                ":deleted_at": Utc::now().timestamp_millis(),
+                ":store_id": store.scope().map(|id| id.as_str()),
+            },
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<PurchaseOrder>>> {
+        let orders = self.select(
+            store,
+            None,
+            true,
+            FindPurchaseOrdersQuery {
+                period_start: None,
+                period_end: None,
+                supplier_id: None,
+                status: None,
+            },
+        )?;
+
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let deleted_ats = trash::deleted_ats(&conn, "purchase_orders")?;
+
+        Ok(trash::trashed(orders, &deleted_ats, |order| {
+            order.id().as_str()
+        }))
+    }
+
+    fn restore(&self, store: &StoreContext, id: PurchaseOrderId) -> Result<()> {
+        trash::restore(
+            &self.db_path,
+            "purchase_orders",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            None,
+        )
+    }
+
+    fn purge(&self, store: &StoreContext, id: PurchaseOrderId) -> Result<()> {
+        trash::purge(
+            &self.db_path,
+            "purchase_orders",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            &[
+                ("purchase_order_lines", "purchase_order_id"),
+                ("purchase_order_journals", "purchase_order_id"),
+            ],
+            None,
+        )
+    }
+}
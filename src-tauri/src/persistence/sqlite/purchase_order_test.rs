@@ -0,0 +1,180 @@
+use std::fs::{self};
+use std::path::Path;
+
+use rusqlite::Connection;
+use scopeguard::defer;
+
+use crate::core::domain::entities::stock::PurchaseOrder;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::order::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::{StoreContext, StoreId};
+use crate::core::required_ports::*;
+use crate::persistence::sqlite::{migrate, SqlitePurchaseOrderRepository};
+
+#[test]
+fn purchase_order_repository_test() {
+    let tmp_path = Path::new("tmp/purchase_order_repository_test.db");
+
+    defer! {
+        fs::remove_file(tmp_path).unwrap();
+    }
+
+    if let Some(parent) = tmp_path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+
+    migrate(tmp_path.to_string_lossy()).unwrap();
+
+    let conn = Connection::open(tmp_path).unwrap();
+
+    conn.execute(
+        "INSERT INTO suppliers (id, name) VALUES (1, 'SupplierA'), (2, 'SupplierB');",
+        [],
+    )
+    .unwrap();
+
+    conn.execute(
+        "INSERT INTO supplies (id, name, unit_name, supplier_id) VALUES (1, 'SupplyA', 'kg', 1), (2, 'SupplyB', 'pc', 1);",
+        [],
+    )
+    .unwrap();
+
+    conn.execute(
+        "INSERT INTO journals (id, recorded_at, total_price) VALUES (7, 3000, 100000);",
+        [],
+    )
+    .unwrap();
+
+    let repository = SqlitePurchaseOrderRepository::new(tmp_path.to_string_lossy());
+
+    let next_id = repository.next_id().unwrap();
+
+    assert_eq!(next_id, PurchaseOrderId::new("1").unwrap());
+
+    let mut order = PurchaseOrder::draft(
+        next_id,
+        SupplierId::new("1").unwrap(),
+        SupplierName::new("SupplierA").unwrap(),
+        OrderedDateTime::new(1000),
+        vec![
+            PurchaseOrderLine::new(
+                SupplyId::new("1").unwrap(),
+                SupplyName::new("SupplyA").unwrap(),
+                UnitName::new("kg").unwrap(),
+                PurchaseQuantity::new(2.5).unwrap(),
+                PurchaseUnitPrice::new(400).unwrap(),
+            ),
+            PurchaseOrderLine::new(
+                SupplyId::new("2").unwrap(),
+                SupplyName::new("SupplyB").unwrap(),
+                UnitName::new("pc").unwrap(),
+                PurchaseQuantity::new(10).unwrap(),
+                PurchaseUnitPrice::new(50).unwrap(),
+            ),
+        ],
+    )
+    .unwrap();
+
+    repository
+        .add(&StoreContext::default(), order.clone())
+        .unwrap();
+
+    let other = PurchaseOrder::draft(
+        repository.next_id().unwrap(),
+        SupplierId::new("2").unwrap(),
+        SupplierName::new("SupplierB").unwrap(),
+        OrderedDateTime::new(2000),
+        vec![PurchaseOrderLine::new(
+            SupplyId::new("1").unwrap(),
+            SupplyName::new("SupplyA").unwrap(),
+            UnitName::new("kg").unwrap(),
+            PurchaseQuantity::new(1).unwrap(),
+            PurchaseUnitPrice::new(420).unwrap(),
+        )],
+    )
+    .unwrap();
+
+    repository
+        .add(&StoreContext::default(), other.clone())
+        .unwrap();
+
+    order.send().unwrap();
+    order
+        .receive(&[(
+            SupplyId::new("1").unwrap(),
+            Quantity::from_f64(2.5, RoundingMode::HalfUp).unwrap(),
+        )])
+        .unwrap();
+    order.link_journal(JournalId::new("7").unwrap());
+
+    repository
+        .save(&StoreContext::default(), order.clone())
+        .unwrap();
+
+    let saved = repository
+        .get(&StoreContext::default(), PurchaseOrderId::new("1").unwrap())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(saved.status(), OrderStatus::PartiallyReceived);
+    assert_eq!(saved.lines(), order.lines());
+    assert_eq!(saved.journal_ids(), &[JournalId::new("7").unwrap()]);
+
+    // purchase orders of another store are not read
+    let branch = StoreContext::Store(StoreId::new("2").unwrap());
+
+    assert_eq!(
+        repository
+            .get(&branch, PurchaseOrderId::new("1").unwrap())
+            .unwrap(),
+        None
+    );
+
+    let found = repository
+        .find(
+            &StoreContext::default(),
+            FindPurchaseOrdersQuery {
+                period_start: None,
+                period_end: None,
+                supplier_id: None,
+                status: Some(OrderStatus::Draft),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(found, vec![other.clone()]);
+
+    repository
+        .delete(&StoreContext::default(), other.id().clone())
+        .unwrap();
+
+    let orders = repository.list(&StoreContext::default()).unwrap();
+
+    assert_eq!(orders, vec![order.clone()]);
+
+    let trashed = repository.list_deleted(&StoreContext::default()).unwrap();
+
+    assert_eq!(trashed.len(), 1);
+    assert_eq!(trashed[0].item(), &other);
+
+    repository
+        .purge(&StoreContext::default(), other.id().clone())
+        .unwrap();
+
+    assert!(repository
+        .list_deleted(&StoreContext::default())
+        .unwrap()
+        .is_empty());
+
+    // the lines go with the purged order
+    let lines = conn
+        .query_row(
+            "SELECT COUNT(*) FROM purchase_order_lines WHERE purchase_order_id = :id",
+            rusqlite::named_params! { ":id": other.id().as_str() },
+            |row| row.get::<_, i64>(0),
+        )
+        .unwrap();
+
+    assert_eq!(lines, 0);
+}
@@ -8,11 +8,22 @@ use rusqlite::Connection;
 use rusqlite::OptionalExtension;
 
 use crate::core::domain::entities::stock::Stocktaking;
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::location::StorageLocationId;
+use crate::core::domain::values::lot::LotNumber;
+use crate::core::domain::values::revision::*;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::trash::*;
+use crate::core::domain::values::valuation::ValuationMethod;
 use crate::core::required_ports::*;
 use crate::core::Error;
 use crate::core::Result;
 
+use super::audit;
+use super::trash;
+
 /// SQLite implementation of `ForStocktakingPersistence`
 pub struct SqliteStocktakingRepository {
     db_path: String,
@@ -50,10 +61,12 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
         Ok(next_id)
     }
 
-    fn list(&self) -> Result<Vec<Stocktaking>> {
+    fn list(&self, store: &StoreContext) -> Result<Vec<Stocktaking>> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
+        let store_id = store.scope().map(|id| id.as_str());
+
         let mut statement = conn
             .prepare(
                 r"
@@ -66,11 +79,16 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
                     stocktaking_records.unit_name,
                     stocktaking_records.unit_price,
                     stocktaking_records.quantity,
-                    stocktaking_records.total_price
+                    stocktaking_records.total_price,
+                    stocktaking_records.valuation_method,
+                    stocktaking_records.storage_location_id,
+                    stocktaking_records.lot_number
                 FROM stocktakings
                 INNER JOIN stocktaking_records
                     ON stocktaking_records.stocktaking_id = stocktakings.id
                 WHERE
+                    (:store_id IS NULL OR stocktakings.store_id = :store_id)
+                    AND
                     stocktakings.deleted_at IS NULL
                 ORDER BY stocktakings.recorded_at DESC
                 ",
@@ -80,13 +98,11 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
             })?;
 
         let mut stocktakings = statement
-            .query_map([], |row| {
+            .query_map(named_params! { ":store_id": store_id }, |row| {
                 let stocktaking = Stocktaking::restore(
                     StocktakingId::new(row.get::<_, i64>(0)?.to_string())?,
                     StocktakenDateTime::new(row.get::<_, i64>(1)?),
-                    TotalPrice::new(
-                        row.get::<_, i64>(2)? as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                    )?,
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(2)?))?,
                     vec![],
                 );
 
@@ -103,22 +119,29 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
             .collect::<Vec<Stocktaking>>();
 
         let stocktaking_records = statement
-            .query_map([], |row| {
+            .query_map(named_params! { ":store_id": store_id }, |row| {
                 let id = StocktakingId::new(row.get::<_, i64>(0)?.to_string())?;
 
                 let stocktaking_record = StocktakingRecord::new(
                     SupplyId::new(row.get::<_, i64>(3)?.to_string())?,
                     SupplyName::new(row.get::<_, String>(4)?)?,
                     UnitName::new(row.get::<_, String>(5)?)?,
-                    StocktakingUnitPrice::new(
-                        row.get::<_, i64>(6)? as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                    )?,
-                    StocktakingQuantity::new(
-                        row.get::<_, i64>(7)? as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                    )?,
-                    TotalPrice::new(
-                        row.get::<_, i64>(8)? as f64 / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                    )?,
+                    StocktakingUnitPrice::from_money(Money::from_minor(row.get::<_, i64>(6)?))?,
+                    StocktakingQuantity::from_quantity(Quantity::from_raw(row.get::<_, i64>(7)?))?,
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(8)?))?,
+                    row.get::<_, Option<String>>(9)?
+                        .map(ValuationMethod::new)
+                        .transpose()?,
+                )
+                .with_location(
+                    row.get::<_, Option<i64>>(10)?
+                        .map(|location_id| StorageLocationId::new(location_id.to_string()))
+                        .transpose()?,
+                )
+                .with_lot(
+                    row.get::<_, Option<String>>(11)?
+                        .map(LotNumber::new)
+                        .transpose()?,
                 );
 
                 Ok((id, stocktaking_record))
@@ -153,7 +176,7 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
         Ok(stocktakings)
     }
 
-    fn find(&self, query: FindStocktakingsQuery) -> Result<Vec<Stocktaking>> {
+    fn find(&self, store: &StoreContext, query: FindStocktakingsQuery) -> Result<Vec<Stocktaking>> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -169,11 +192,16 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
                     stocktaking_records.unit_name,
                     stocktaking_records.unit_price,
                     stocktaking_records.quantity,
-                    stocktaking_records.total_price
+                    stocktaking_records.total_price,
+                    stocktaking_records.valuation_method,
+                    stocktaking_records.storage_location_id,
+                    stocktaking_records.lot_number
                 FROM stocktakings
                 INNER JOIN stocktaking_records
                     ON stocktaking_records.stocktaking_id = stocktakings.id
                 WHERE
+                    (:store_id IS NULL OR stocktakings.store_id = :store_id)
+                    AND
                     (:start IS NULL OR :start <= stocktakings.recorded_at)
                     AND
                     (:end IS NULL OR stocktakings.recorded_at <= :end)
@@ -188,6 +216,7 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
         let mut stocktakings = statement
             .query_map(
                 named_params! {
+                    ":store_id": store.scope().map(|id| id.as_str()),
                     ":start": query.period_start.as_ref().and_then(|start| Some(start.as_i64())),
                     ":end": query.period_end.as_ref().and_then(|end| Some(end.as_i64())),
                 },
@@ -195,10 +224,7 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
                     let stocktaking = Stocktaking::restore(
                         StocktakingId::new(row.get::<_, i64>(0)?.to_string())?,
                         StocktakenDateTime::new(row.get::<_, i64>(1)?),
-                        TotalPrice::new(
-                            row.get::<_, i64>(2)? as f64
-                                / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                        )?,
+                        TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(2)?))?,
                         vec![],
                     );
 
@@ -218,6 +244,7 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
         let stocktaking_records = statement
             .query_map(
                 named_params! {
+                    ":store_id": store.scope().map(|id| id.as_str()),
                     ":start": query.period_start.as_ref().and_then(|start| Some(start.as_i64())),
                     ":end": query.period_end.as_ref().and_then(|end| Some(end.as_i64())),
                 },
@@ -228,18 +255,24 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
                         SupplyId::new(row.get::<_, i64>(3)?.to_string())?,
                         SupplyName::new(row.get::<_, String>(4)?)?,
                         UnitName::new(row.get::<_, String>(5)?)?,
-                        StocktakingUnitPrice::new(
-                            row.get::<_, i64>(6)? as f64
-                                / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                        )?,
-                        StocktakingQuantity::new(
-                            row.get::<_, i64>(7)? as f64
-                                / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                        )?,
-                        TotalPrice::new(
-                            row.get::<_, i64>(8)? as f64
-                                / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                        )?,
+                        StocktakingUnitPrice::from_money(Money::from_minor(row.get::<_, i64>(6)?))?,
+                        StocktakingQuantity::from_quantity(Quantity::from_raw(
+                            row.get::<_, i64>(7)?,
+                        ))?,
+                        TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(8)?))?,
+                        row.get::<_, Option<String>>(9)?
+                            .map(ValuationMethod::new)
+                            .transpose()?,
+                    )
+                    .with_location(
+                        row.get::<_, Option<i64>>(10)?
+                            .map(|location_id| StorageLocationId::new(location_id.to_string()))
+                            .transpose()?,
+                    )
+                    .with_lot(
+                        row.get::<_, Option<String>>(11)?
+                            .map(LotNumber::new)
+                            .transpose()?,
                     );
 
                     Ok((id, stocktaking_record))
@@ -275,7 +308,7 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
         Ok(stocktakings)
     }
 
-    fn get(&self, id: StocktakingId) -> Result<Option<Stocktaking>> {
+    fn get(&self, store: &StoreContext, id: StocktakingId) -> Result<Option<Stocktaking>> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -291,13 +324,18 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
                     stocktaking_records.unit_name,
                     stocktaking_records.unit_price,
                     stocktaking_records.quantity,
-                    stocktaking_records.total_price
+                    stocktaking_records.total_price,
+                    stocktaking_records.valuation_method,
+                    stocktaking_records.storage_location_id,
+                    stocktaking_records.lot_number
                 FROM stocktakings
                 INNER JOIN stocktaking_records
                     ON stocktaking_records.stocktaking_id = stocktakings.id
                 WHERE
                     stocktakings.id = :id
                     AND
+                    (:store_id IS NULL OR stocktakings.store_id = :store_id)
+                    AND
                     stocktakings.deleted_at IS NULL
                 ",
             )
@@ -309,15 +347,13 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
             .query_row(
                 named_params! {
                     ":id": id.as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
                 },
                 |row| {
                     let stocktaking = Stocktaking::restore(
                         StocktakingId::new(row.get::<_, i64>(0)?.to_string())?,
                         StocktakenDateTime::new(row.get::<_, i64>(1)?),
-                        TotalPrice::new(
-                            row.get::<_, i64>(2)? as f64
-                                / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                        )?,
+                        TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(2)?))?,
                         vec![],
                     );
 
@@ -331,24 +367,31 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
             .query_map(
                 named_params! {
                     ":id": id.as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
                 },
                 |row| {
                     let stocktaking_record = StocktakingRecord::new(
                         SupplyId::new(row.get::<_, i64>(3)?.to_string())?,
                         SupplyName::new(row.get::<_, String>(4)?)?,
                         UnitName::new(row.get::<_, String>(5)?)?,
-                        StocktakingUnitPrice::new(
-                            row.get::<_, i64>(6)? as f64
-                                / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                        )?,
-                        StocktakingQuantity::new(
-                            row.get::<_, i64>(7)? as f64
-                                / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                        )?,
-                        TotalPrice::new(
-                            row.get::<_, i64>(8)? as f64
-                                / (GUARANTEED_DECIMAL_PRECISION * 10) as f64,
-                        )?,
+                        StocktakingUnitPrice::from_money(Money::from_minor(row.get::<_, i64>(6)?))?,
+                        StocktakingQuantity::from_quantity(Quantity::from_raw(
+                            row.get::<_, i64>(7)?,
+                        ))?,
+                        TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(8)?))?,
+                        row.get::<_, Option<String>>(9)?
+                            .map(ValuationMethod::new)
+                            .transpose()?,
+                    )
+                    .with_location(
+                        row.get::<_, Option<i64>>(10)?
+                            .map(|location_id| StorageLocationId::new(location_id.to_string()))
+                            .transpose()?,
+                    )
+                    .with_lot(
+                        row.get::<_, Option<String>>(11)?
+                            .map(LotNumber::new)
+                            .transpose()?,
                     );
 
                     Ok(stocktaking_record)
@@ -369,7 +412,14 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
         Ok(stocktaking)
     }
 
-    fn add(&self, stocktaking: Stocktaking) -> Result<()> {
+    fn add(
+        &self,
+        store: &StoreContext,
+        stocktaking: Stocktaking,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let store_id = store.store_id()?;
+
         let mut conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -383,17 +433,20 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
                 INSERT INTO stocktakings (
                     id,
                     recorded_at,
-                    total_price
+                    total_price,
+                    store_id
                 ) VALUES (
                     :id,
                     :recorded_at,
-                    :total_price
+                    :total_price,
+                    :store_id
                 )
                 ",
                 named_params! {
                     ":id": stocktaking.id().as_str(),
                     ":recorded_at": stocktaking.stocktaken_at().as_i64(),
-                    ":total_price": (stocktaking.total_price().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64
+                    ":total_price": stocktaking.total_price().as_money().as_minor(),
+                    ":store_id": store_id.as_str(),
                 },
             )
             .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
@@ -408,6 +461,9 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
                     unit_price,
                     quantity,
                     total_price,
+                    valuation_method,
+                    storage_location_id,
+                    lot_number,
                     stocktaking_id
                 ) VALUES (
                     :supply_id,
@@ -416,6 +472,9 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
                     :unit_price,
                     :quantity,
                     :total_price,
+                    :valuation_method,
+                    :storage_location_id,
+                    :lot_number,
                     :stocktaking_id
                 )
                 ",
@@ -430,9 +489,16 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
                         ":supply_id": record.supply_id().as_str(),
                         ":supply_name": record.supply_name().as_str(),
                         ":unit_name": record.unit_name().as_str(),
-                        ":unit_price": (record.unit_price().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
-                        ":quantity": (record.quantity().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
-                        ":total_price": (record.total_price().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
+                        ":unit_price": record.unit_price().as_money().as_minor(),
+                        ":quantity": record.quantity().as_quantity().as_raw(),
+                        ":total_price": record.total_price().as_money().as_minor(),
+                        ":valuation_method": record
+                            .valuation_method()
+                            .map(|method| method.as_str().to_string()),
+                        ":storage_location_id": record
+                            .location_id()
+                            .map(|location_id| location_id.as_str()),
+                        ":lot_number": record.lot_number().map(|lot_number| lot_number.as_str()),
                         ":stocktaking_id": stocktaking.id().as_str(),
                     })
                     .map_err(|e| {
@@ -443,6 +509,8 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
             Ok(())
         })();
 
+        let result = result.and_then(|_| audit::insert_change(&tran, store, change.as_ref()));
+
         if result.is_err() {
             tran.rollback()
                 .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
@@ -456,7 +524,12 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
         Ok(())
     }
 
-    fn save(&self, stocktaking: Stocktaking) -> Result<()> {
+    fn save(
+        &self,
+        store: &StoreContext,
+        stocktaking: Stocktaking,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
         let mut conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -465,21 +538,31 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
         })?;
 
         let result: Result<()> = (|| {
-            tran.execute(
-                r"
+            let updated = tran
+                .execute(
+                    r"
                 UPDATE stocktakings
                 SET
                     recorded_at = :recorded_at,
                     total_price = :total_price
-                WHERE id = :id
+                WHERE
+                    id = :id
+                    AND
+                    (:store_id IS NULL OR store_id = :store_id)
                 ",
-                named_params! {
-                    ":id": stocktaking.id().as_str(),
-                    ":recorded_at": stocktaking.stocktaken_at().as_i64(),
-                    ":total_price": (stocktaking.total_price().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
-                },
-            )
-            .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+                    named_params! {
+                        ":id": stocktaking.id().as_str(),
+                        ":store_id": store.scope().map(|id| id.as_str()),
+                        ":recorded_at": stocktaking.stocktaken_at().as_i64(),
+                        ":total_price": stocktaking.total_price().as_money().as_minor(),
+                    },
+                )
+                .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            // a stocktaking of another store is left as it is
+            if updated == 0 {
+                return Ok(());
+            }
 
             tran.execute(
                 r"
@@ -502,6 +585,9 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
                     unit_price,
                     quantity,
                     total_price,
+                    valuation_method,
+                    storage_location_id,
+                    lot_number,
                     stocktaking_id
                 ) VALUES (
                     :supply_id,
@@ -510,6 +596,9 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
                     :unit_price,
                     :quantity,
                     :total_price,
+                    :valuation_method,
+                    :storage_location_id,
+                    :lot_number,
                     :stocktaking_id
                 )
                 ",
@@ -524,9 +613,16 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
                         ":supply_id": record.supply_id().as_str(),
                         ":supply_name": record.supply_name().as_str(),
                         ":unit_name": record.unit_name().as_str(),
-                        ":unit_price": (record.unit_price().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
-                        ":quantity": (record.quantity().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
-                        ":total_price": (record.total_price().as_f64() * (GUARANTEED_DECIMAL_PRECISION * 10) as f64) as i64,
+                        ":unit_price": record.unit_price().as_money().as_minor(),
+                        ":quantity": record.quantity().as_quantity().as_raw(),
+                        ":total_price": record.total_price().as_money().as_minor(),
+                        ":valuation_method": record
+                            .valuation_method()
+                            .map(|method| method.as_str().to_string()),
+                        ":storage_location_id": record
+                            .location_id()
+                            .map(|location_id| location_id.as_str()),
+                        ":lot_number": record.lot_number().map(|lot_number| lot_number.as_str()),
                         ":stocktaking_id": stocktaking.id().as_str(),
                     })
                     .map_err(|e| {
@@ -537,6 +633,8 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
             Ok(())
         })();
 
+        let result = result.and_then(|_| audit::insert_change(&tran, store, change.as_ref()));
+
         if result.is_err() {
             tran.rollback()
                 .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
@@ -550,7 +648,12 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
         Ok(())
     }
 
-    fn delete(&self, id: StocktakingId) -> Result<()> {
+    fn delete(
+        &self,
+        store: &StoreContext,
+        id: StocktakingId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
         let mut conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -563,10 +666,14 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
                 r"
                 UPDATE stocktakings
                 SET deleted_at = :deleted_at
-                WHERE id = :id
+                WHERE
+                    id = :id
+                    AND
+                    (:store_id IS NULL OR store_id = :store_id)
                 ",
                 named_params! {
                     ":id": id.as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
                     ":deleted_at": Utc::now().timestamp_millis(),
                 },
             )
@@ -575,6 +682,8 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
             Ok(())
         })();
 
+        let result = result.and_then(|_| audit::insert_change(&tran, store, change.as_ref()));
+
         if result.is_err() {
             tran.rollback()
                 .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
@@ -587,4 +696,423 @@ impl ForStocktakingPersistence for SqliteStocktakingRepository {
 
         Ok(())
     }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Stocktaking>>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let store_id = store.scope().map(|id| id.as_str());
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    stocktakings.id,
+                    stocktakings.recorded_at,
+                    stocktakings.total_price,
+                    stocktaking_records.supply_id,
+                    stocktaking_records.supply_name,
+                    stocktaking_records.unit_name,
+                    stocktaking_records.unit_price,
+                    stocktaking_records.quantity,
+                    stocktaking_records.total_price,
+                    stocktaking_records.valuation_method,
+                    stocktaking_records.storage_location_id,
+                    stocktaking_records.lot_number
+                FROM stocktakings
+                INNER JOIN stocktaking_records
+                    ON stocktaking_records.stocktaking_id = stocktakings.id
+                WHERE
+                    (:store_id IS NULL OR stocktakings.store_id = :store_id)
+                    AND
+                    stocktakings.deleted_at IS NOT NULL
+                ORDER BY stocktakings.recorded_at DESC
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let mut stocktakings = statement
+            .query_map(named_params! { ":store_id": store_id }, |row| {
+                let stocktaking = Stocktaking::restore(
+                    StocktakingId::new(row.get::<_, i64>(0)?.to_string())?,
+                    StocktakenDateTime::new(row.get::<_, i64>(1)?),
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(2)?))?,
+                    vec![],
+                );
+
+                Ok(stocktaking)
+            })
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|stocktaking| {
+                stocktaking.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<HashSet<Stocktaking>>>()?
+            .into_iter()
+            .collect::<Vec<Stocktaking>>();
+
+        let stocktaking_records = statement
+            .query_map(named_params! { ":store_id": store_id }, |row| {
+                let id = StocktakingId::new(row.get::<_, i64>(0)?.to_string())?;
+
+                let stocktaking_record = StocktakingRecord::new(
+                    SupplyId::new(row.get::<_, i64>(3)?.to_string())?,
+                    SupplyName::new(row.get::<_, String>(4)?)?,
+                    UnitName::new(row.get::<_, String>(5)?)?,
+                    StocktakingUnitPrice::from_money(Money::from_minor(row.get::<_, i64>(6)?))?,
+                    StocktakingQuantity::from_quantity(Quantity::from_raw(row.get::<_, i64>(7)?))?,
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(8)?))?,
+                    row.get::<_, Option<String>>(9)?
+                        .map(ValuationMethod::new)
+                        .transpose()?,
+                )
+                .with_location(
+                    row.get::<_, Option<i64>>(10)?
+                        .map(|location_id| StorageLocationId::new(location_id.to_string()))
+                        .transpose()?,
+                )
+                .with_lot(
+                    row.get::<_, Option<String>>(11)?
+                        .map(LotNumber::new)
+                        .transpose()?,
+                );
+
+                Ok((id, stocktaking_record))
+            })
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|record| {
+                record.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<(StocktakingId, StocktakingRecord)>>>()?;
+
+        for stocktaking in stocktakings.iter_mut() {
+            let records: Vec<StocktakingRecord> = stocktaking_records
+                .iter()
+                .filter_map(|record| {
+                    let stocktaking_id = &record.0;
+                    let stocktaking_record = &record.1;
+
+                    if stocktaking_id == stocktaking.id() {
+                        Some(stocktaking_record)
+                    } else {
+                        None
+                    }
+                })
+                .cloned()
+                .collect();
+
+            stocktaking.swap_records(records);
+        }
+
+        let deleted_ats = trash::deleted_ats(&conn, "stocktakings")?;
+
+        Ok(trash::trashed(stocktakings, &deleted_ats, |stocktaking| {
+            stocktaking.id().as_str()
+        }))
+    }
+
+    fn restore(
+        &self,
+        store: &StoreContext,
+        id: StocktakingId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        trash::restore(
+            &self.db_path,
+            "stocktakings",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            change.as_ref().map(|change| (store, change)),
+        )
+    }
+
+    fn purge(
+        &self,
+        store: &StoreContext,
+        id: StocktakingId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        trash::purge(
+            &self.db_path,
+            "stocktakings",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            &[
+                ("stocktaking_records", "stocktaking_id"),
+                ("stocktaking_revision_records", "stocktaking_id"),
+                ("stocktaking_revisions", "stocktaking_id"),
+            ],
+            change.as_ref().map(|change| (store, change)),
+        )
+    }
+
+    fn add_revision(&self, stocktaking: Stocktaking) -> Result<()> {
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let tran = conn.transaction().map_err(|e| {
+            Error::InfrastructureError(format!("failed to start transaction: {}", e))
+        })?;
+
+        let result: Result<()> = (|| {
+            let number = tran
+                .query_row(
+                    r"
+                    SELECT COALESCE(MAX(number), 0) + 1
+                    FROM stocktaking_revisions
+                    WHERE
+                        stocktaking_id = :stocktaking_id
+                    ",
+                    named_params! {
+                        ":stocktaking_id": stocktaking.id().as_str(),
+                    },
+                    |row| row.get::<_, i64>(0),
+                )
+                .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
+
+            tran.execute(
+                r"
+                INSERT INTO stocktaking_revisions (
+                    stocktaking_id,
+                    number,
+                    revised_at,
+                    recorded_at,
+                    total_price
+                ) VALUES (
+                    :stocktaking_id,
+                    :number,
+                    :revised_at,
+                    :recorded_at,
+                    :total_price
+                )
+                ",
+                named_params! {
+                    ":stocktaking_id": stocktaking.id().as_str(),
+                    ":number": number,
+                    ":revised_at": Utc::now().timestamp_millis(),
+                    ":recorded_at": stocktaking.stocktaken_at().as_i64(),
+                    ":total_price": stocktaking.total_price().as_money().as_minor(),
+                },
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to execute statement: {}", e))
+            })?;
+
+            let mut statement = tran
+                .prepare(
+                    r"
+                    INSERT INTO stocktaking_revision_records (
+                        stocktaking_id,
+                        revision_number,
+                        supply_id,
+                        supply_name,
+                        unit_name,
+                        unit_price,
+                        quantity,
+                        total_price,
+                        valuation_method,
+                        storage_location_id,
+                        lot_number
+                    ) VALUES (
+                        :stocktaking_id,
+                        :revision_number,
+                        :supply_id,
+                        :supply_name,
+                        :unit_name,
+                        :unit_price,
+                        :quantity,
+                        :total_price,
+                        :valuation_method,
+                        :storage_location_id,
+                        :lot_number
+                    )
+                    ",
+                )
+                .map_err(|e| {
+                    Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+                })?;
+
+            for record in stocktaking.records() {
+                statement
+                    .execute(named_params! {
+                        ":stocktaking_id": stocktaking.id().as_str(),
+                        ":revision_number": number,
+                        ":supply_id": record.supply_id().as_str(),
+                        ":supply_name": record.supply_name().as_str(),
+                        ":unit_name": record.unit_name().as_str(),
+                        ":unit_price": record.unit_price().as_money().as_minor(),
+                        ":quantity": record.quantity().as_quantity().as_raw(),
+                        ":total_price": record.total_price().as_money().as_minor(),
+                        ":valuation_method": record
+                            .valuation_method()
+                            .map(|method| method.as_str().to_string()),
+                        ":storage_location_id": record
+                            .location_id()
+                            .map(|location_id| location_id.as_str()),
+                        ":lot_number": record.lot_number().map(|lot_number| lot_number.as_str()),
+                    })
+                    .map_err(|e| {
+                        Error::InfrastructureError(format!("failed to execute statement: {}", e))
+                    })?;
+            }
+
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            tran.rollback()
+                .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+            return Err(e);
+        }
+
+        tran.commit()
+            .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn list_revisions(
+        &self,
+        store: &StoreContext,
+        id: StocktakingId,
+    ) -> Result<Vec<Revision<Stocktaking>>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    number,
+                    revised_at,
+                    recorded_at,
+                    total_price
+                FROM stocktaking_revisions
+                WHERE
+                    stocktaking_id = :stocktaking_id
+                    AND
+                    EXISTS (
+                        SELECT 1 FROM stocktakings
+                        WHERE
+                            stocktakings.id = stocktaking_revisions.stocktaking_id
+                            AND
+                            (:store_id IS NULL OR stocktakings.store_id = :store_id)
+                    )
+                ORDER BY number ASC
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let revisions = statement
+            .query_map(
+                named_params! {
+                    ":stocktaking_id": id.as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
+                },
+                |row| {
+                    let stocktaking = Stocktaking::restore(
+                        id.clone(),
+                        StocktakenDateTime::new(row.get::<_, i64>(2)?),
+                        TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(3)?))?,
+                        vec![],
+                    );
+
+                    Ok(Revision::new(
+                        RevisionNumber::new(row.get::<_, u32>(0)?)?,
+                        RevisedDateTime::new(row.get::<_, i64>(1)?),
+                        stocktaking,
+                    ))
+                },
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|revision| {
+                revision.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<Revision<Stocktaking>>>>()?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    revision_number,
+                    supply_id,
+                    supply_name,
+                    unit_name,
+                    unit_price,
+                    quantity,
+                    total_price,
+                    valuation_method,
+                    storage_location_id,
+                    lot_number
+                FROM stocktaking_revision_records
+                WHERE
+                    stocktaking_id = :stocktaking_id
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let revision_records = statement
+            .query_map(named_params! { ":stocktaking_id": id.as_str() }, |row| {
+                let stocktaking_record = StocktakingRecord::new(
+                    SupplyId::new(row.get::<_, i64>(1)?.to_string())?,
+                    SupplyName::new(row.get::<_, String>(2)?)?,
+                    UnitName::new(row.get::<_, String>(3)?)?,
+                    StocktakingUnitPrice::from_money(Money::from_minor(row.get::<_, i64>(4)?))?,
+                    StocktakingQuantity::from_quantity(Quantity::from_raw(row.get::<_, i64>(5)?))?,
+                    TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(6)?))?,
+                    row.get::<_, Option<String>>(7)?
+                        .map(ValuationMethod::new)
+                        .transpose()?,
+                )
+                .with_location(
+                    row.get::<_, Option<i64>>(8)?
+                        .map(|location_id| StorageLocationId::new(location_id.to_string()))
+                        .transpose()?,
+                )
+                .with_lot(
+                    row.get::<_, Option<String>>(9)?
+                        .map(LotNumber::new)
+                        .transpose()?,
+                );
+
+                Ok((row.get::<_, u32>(0)?, stocktaking_record))
+            })
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|record| {
+                record.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<(u32, StocktakingRecord)>>>()?;
+
+        Ok(revisions
+            .into_iter()
+            .map(|revision| {
+                let number = revision.number();
+                let revised_at = revision.revised_at();
+                let mut stocktaking = revision.into_item();
+
+                stocktaking.swap_records(
+                    revision_records
+                        .iter()
+                        .filter(|(revision_number, _)| *revision_number == number.as_u32())
+                        .map(|(_, record)| record.clone()),
+                );
+
+                Revision::new(number, revised_at, stocktaking)
+            })
+            .collect())
+    }
 }
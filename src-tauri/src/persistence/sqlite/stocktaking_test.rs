@@ -6,6 +6,8 @@ use scopeguard::defer;
 
 use crate::core::domain::entities::stock::Stocktaking;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::valuation::ValuationMethod;
 use crate::core::required_ports::*;
 use crate::persistence::sqlite::*;
 
@@ -44,32 +46,38 @@ fn stocktaking_repository_test() {
     assert_eq!(next_id, StocktakingId::new("1").unwrap());
 
     repository
-        .add(Stocktaking::restore(
-            StocktakingId::new("1").unwrap(),
-            StocktakenDateTime::new(200000),
-            TotalPrice::new(1650_u32).unwrap(),
-            vec![
-                StocktakingRecord::new(
-                    SupplyId::new("1").unwrap(),
-                    SupplyName::new("SupplyA").unwrap(),
-                    UnitName::new("g").unwrap(),
-                    StocktakingUnitPrice::new(100_u32).unwrap(),
-                    StocktakingQuantity::new(10_u32).unwrap(),
-                    TotalPrice::new(1000_u32).unwrap(),
-                ),
-                StocktakingRecord::new(
-                    SupplyId::new("2").unwrap(),
-                    SupplyName::new("SupplyB").unwrap(),
-                    UnitName::new("g").unwrap(),
-                    StocktakingUnitPrice::new(130_u32).unwrap(),
-                    StocktakingQuantity::new(5_u32).unwrap(),
-                    TotalPrice::new(650_u32).unwrap(),
-                ),
-            ],
-        ))
+        .add(
+            &StoreContext::default(),
+            Stocktaking::restore(
+                StocktakingId::new("1").unwrap(),
+                StocktakenDateTime::new(200000),
+                TotalPrice::new(1650_u32).unwrap(),
+                vec![
+                    StocktakingRecord::new(
+                        SupplyId::new("1").unwrap(),
+                        SupplyName::new("SupplyA").unwrap(),
+                        UnitName::new("g").unwrap(),
+                        StocktakingUnitPrice::new(100_u32).unwrap(),
+                        StocktakingQuantity::new(10_u32).unwrap(),
+                        TotalPrice::new(1000_u32).unwrap(),
+                        Some(ValuationMethod::Fifo),
+                    ),
+                    StocktakingRecord::new(
+                        SupplyId::new("2").unwrap(),
+                        SupplyName::new("SupplyB").unwrap(),
+                        UnitName::new("g").unwrap(),
+                        StocktakingUnitPrice::new(130_u32).unwrap(),
+                        StocktakingQuantity::new(5_u32).unwrap(),
+                        TotalPrice::new(650_u32).unwrap(),
+                        None,
+                    ),
+                ],
+            ),
+            None,
+        )
         .unwrap();
 
-    let stocktakings = repository.list().unwrap();
+    let stocktakings = repository.list(&StoreContext::default()).unwrap();
 
     assert!(stocktakings.first().is_some_and(|stocktaking| {
         assert_eq!(stocktaking.id(), &StocktakingId::new("1").unwrap());
@@ -91,6 +99,7 @@ fn stocktaking_repository_test() {
                     StocktakingUnitPrice::new(100_u32).unwrap(),
                     StocktakingQuantity::new(10_u32).unwrap(),
                     TotalPrice::new(1000_u32).unwrap(),
+                    Some(ValuationMethod::Fifo),
                 ),
                 StocktakingRecord::new(
                     SupplyId::new("2").unwrap(),
@@ -99,6 +108,7 @@ fn stocktaking_repository_test() {
                     StocktakingUnitPrice::new(130_u32).unwrap(),
                     StocktakingQuantity::new(5_u32).unwrap(),
                     TotalPrice::new(650_u32).unwrap(),
+                    None,
                 ),
             ]
         );
@@ -106,22 +116,29 @@ fn stocktaking_repository_test() {
     }));
 
     repository
-        .save(Stocktaking::restore(
-            StocktakingId::new("1").unwrap(),
-            StocktakenDateTime::new(240000),
-            TotalPrice::new(2100_u32).unwrap(),
-            vec![StocktakingRecord::new(
-                SupplyId::new("1").unwrap(),
-                SupplyName::new("SupplyC").unwrap(),
-                UnitName::new("kg").unwrap(),
-                StocktakingUnitPrice::new(140_u32).unwrap(),
-                StocktakingQuantity::new(15_u32).unwrap(),
+        .save(
+            &StoreContext::default(),
+            Stocktaking::restore(
+                StocktakingId::new("1").unwrap(),
+                StocktakenDateTime::new(240000),
                 TotalPrice::new(2100_u32).unwrap(),
-            )],
-        ))
+                vec![StocktakingRecord::new(
+                    SupplyId::new("1").unwrap(),
+                    SupplyName::new("SupplyC").unwrap(),
+                    UnitName::new("kg").unwrap(),
+                    StocktakingUnitPrice::new(140_u32).unwrap(),
+                    StocktakingQuantity::new(15_u32).unwrap(),
+                    TotalPrice::new(2100_u32).unwrap(),
+                    None,
+                )],
+            ),
+            None,
+        )
         .unwrap();
 
-    let stocktaking = repository.get(StocktakingId::new("1").unwrap()).unwrap();
+    let stocktaking = repository
+        .get(&StoreContext::default(), StocktakingId::new("1").unwrap())
+        .unwrap();
 
     assert!(stocktaking.is_some_and(|stocktaking| {
         assert_eq!(stocktaking.id(), &StocktakingId::new("1").unwrap());
@@ -142,16 +159,20 @@ fn stocktaking_repository_test() {
                 StocktakingUnitPrice::new(140_u32).unwrap(),
                 StocktakingQuantity::new(15_u32).unwrap(),
                 TotalPrice::new(2100_u32).unwrap(),
+                None,
             ),]
         );
         true
     }));
 
     let stocktakings = repository
-        .find(FindStocktakingsQuery {
-            period_start: Some(StocktakenDateTime::new(100000)),
-            period_end: Some(StocktakenDateTime::new(300000)),
-        })
+        .find(
+            &StoreContext::default(),
+            FindStocktakingsQuery {
+                period_start: Some(StocktakenDateTime::new(100000)),
+                period_end: Some(StocktakenDateTime::new(300000)),
+            },
+        )
         .unwrap();
 
     assert!(stocktakings.first().as_ref().is_some_and(|stocktaking| {
@@ -173,6 +194,7 @@ fn stocktaking_repository_test() {
                 StocktakingUnitPrice::new(140_u32).unwrap(),
                 StocktakingQuantity::new(15_u32).unwrap(),
                 TotalPrice::new(2100_u32).unwrap(),
+                None,
             ),]
         );
         true
@@ -180,9 +202,11 @@ fn stocktaking_repository_test() {
 
     let stocktaking = stocktakings.first().unwrap();
 
-    repository.delete(stocktaking.id().clone()).unwrap();
+    repository
+        .delete(&StoreContext::default(), stocktaking.id().clone(), None)
+        .unwrap();
 
-    let stocktakings = repository.list().unwrap();
+    let stocktakings = repository.list(&StoreContext::default()).unwrap();
 
     assert_eq!(stocktakings, vec![]);
 }
@@ -0,0 +1,233 @@
+//! This module provides the SQLite-based implementation of the `ForStorePersistence` trait.
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::named_params;
+use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+use rusqlite::Row;
+
+use crate::core::domain::entities::stock::Store;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::trash::*;
+use crate::core::required_ports::*;
+use crate::core::Error;
+use crate::core::Result;
+
+use super::trash;
+
+/// SQLite implementation of `ForStorePersistence`
+pub struct SqliteStoreRepository {
+    db_path: String,
+}
+
+impl SqliteStoreRepository {
+    pub fn new(db_path: impl AsRef<str>) -> Self {
+        Self {
+            db_path: Path::new(db_path.as_ref()).to_string_lossy().to_string(),
+        }
+    }
+}
+
+/// Reads a store from a row of id and name.
+fn store_row(row: &Row) -> rusqlite::Result<Store> {
+    Ok(Store::restore(
+        StoreId::new(row.get::<_, i64>(0)?.to_string())?,
+        StoreName::new(row.get::<_, String>(1)?)?,
+    ))
+}
+
+/// Implementation of `ForStorePersistence` for `SqliteStoreRepository`
+impl ForStorePersistence for SqliteStoreRepository {
+    fn next_id(&self) -> Result<StoreId> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let next_id = conn
+            .query_row(
+                r"
+                UPDATE stores_id_sequence
+                SET value = value + 1
+                WHERE name = 'store_id'
+                RETURNING value
+                ",
+                [],
+                |r| r.get::<_, i64>(0),
+            )
+            .map_err(|e| Error::InfrastructureError(format!("fail to query: {}", e)))?;
+
+        let next_id = StoreId::new(next_id.to_string())?;
+
+        Ok(next_id)
+    }
+
+    fn list(&self) -> Result<Vec<Store>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    id,
+                    name
+                FROM stores
+                WHERE
+                    deleted_at IS NULL
+                ORDER BY id
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let stores = statement
+            .query_map([], store_row)
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|store| {
+                store.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<Store>>>()?;
+
+        Ok(stores)
+    }
+
+    fn get(&self, id: StoreId) -> Result<Option<Store>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let store = conn
+            .query_row(
+                r"
+                SELECT
+                    id,
+                    name
+                FROM stores
+                WHERE
+                    id = :id
+                    AND
+                    deleted_at IS NULL
+                ",
+                named_params! {
+                    ":id": id.as_str(),
+                },
+                store_row,
+            )
+            .optional()
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
+
+        Ok(store)
+    }
+
+    fn add(&self, store: Store) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.execute(
+            r"
+            INSERT INTO stores (
+                id,
+                name
+            ) VALUES (
+                :id,
+                :name
+            )
+            ",
+            named_params! {
+                ":id": store.id().as_str(),
+                ":name": store.name().as_str(),
+            },
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to insert new store: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn save(&self, store: Store) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.execute(
+            r"
+            UPDATE stores
+            SET
+                name = :name
+            WHERE id = :id
+            ",
+            named_params! {
+                ":id": store.id().as_str(),
+                ":name": store.name().as_str(),
+            },
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn delete(&self, id: StoreId) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.execute(
+            r"
+            UPDATE stores
+            SET deleted_at = :deleted_at
+            WHERE id = :id
+            ",
+            named_params! {
+                ":id": id.as_str(),
+                ":deleted_at": Utc::now().timestamp_millis(),
+            },
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<Trashed<Store>>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    id,
+                    name
+                FROM stores
+                WHERE
+                    deleted_at IS NOT NULL
+                ORDER BY id
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let stores = statement
+            .query_map([], store_row)
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|store| {
+                store.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<Store>>>()?;
+
+        let deleted_ats = trash::deleted_ats(&conn, "stores")?;
+
+        Ok(trash::trashed(stores, &deleted_ats, |store| {
+            store.id().as_str()
+        }))
+    }
+
+    fn restore(&self, id: StoreId) -> Result<()> {
+        trash::restore(&self.db_path, "stores", id.as_str(), None, None)
+    }
+
+    fn purge(&self, id: StoreId) -> Result<()> {
+        trash::purge(&self.db_path, "stores", id.as_str(), None, &[], None)
+    }
+}
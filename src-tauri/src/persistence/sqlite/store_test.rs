@@ -0,0 +1,97 @@
+use std::fs::{self};
+use std::path::Path;
+
+use scopeguard::defer;
+
+use crate::core::domain::entities::stock::{Store, Supplier};
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::required_ports::*;
+use crate::persistence::sqlite::{migrate, SqliteStoreRepository, SqliteSupplierRepository};
+
+#[test]
+fn store_repository_test() {
+    let tmp_path = Path::new("tmp/store_repository_test.db");
+
+    defer! {
+        fs::remove_file(tmp_path).unwrap();
+    }
+
+    if let Some(parent) = tmp_path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+
+    migrate(tmp_path.to_string_lossy()).unwrap();
+
+    let repository = SqliteStoreRepository::new(tmp_path.to_string_lossy());
+
+    // existing data is migrated into the default store
+    let main = repository.get(StoreId::default()).unwrap().unwrap();
+
+    assert_eq!(main.name(), &StoreName::new("本店").unwrap());
+
+    let branch = Store::restore(
+        repository.next_id().unwrap(),
+        StoreName::new("駅前店").unwrap(),
+    );
+
+    assert_eq!(branch.id(), &StoreId::new("2").unwrap());
+
+    repository.add(branch.clone()).unwrap();
+
+    assert_eq!(
+        repository.list().unwrap(),
+        vec![main.clone(), branch.clone()]
+    );
+
+    let mut branch = branch;
+
+    branch.rename(StoreName::new("駅前店2").unwrap());
+
+    repository.save(branch.clone()).unwrap();
+
+    let store = repository.get(branch.id().clone()).unwrap();
+
+    assert!(store.is_some_and(|store| {
+        assert_eq!(store.name(), &StoreName::new("駅前店2").unwrap());
+        true
+    }));
+
+    // data is read from its own store
+    let supplier_repository = SqliteSupplierRepository::new(tmp_path.to_string_lossy());
+
+    let branch_store = StoreContext::Store(branch.id().clone());
+
+    let supplier = Supplier::restore(
+        supplier_repository.next_id().unwrap(),
+        SupplierName::new("SupplierA").unwrap(),
+        None,
+        false,
+    );
+
+    supplier_repository
+        .add(&branch_store, supplier.clone(), None)
+        .unwrap();
+
+    assert!(supplier_repository
+        .list(&StoreContext::default())
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        supplier_repository.list(&branch_store).unwrap(),
+        vec![supplier.clone()]
+    );
+    assert_eq!(
+        supplier_repository
+            .list(&StoreContext::Consolidated)
+            .unwrap(),
+        vec![supplier.clone()]
+    );
+    assert!(!supplier_repository
+        .has(&StoreContext::default(), &[supplier.id().clone()])
+        .unwrap());
+
+    repository.delete(branch.id().clone()).unwrap();
+
+    assert_eq!(repository.list().unwrap(), vec![main]);
+}
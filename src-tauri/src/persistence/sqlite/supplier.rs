@@ -5,13 +5,21 @@ use rusqlite::named_params;
 use rusqlite::params_from_iter;
 use rusqlite::Connection;
 use rusqlite::OptionalExtension;
+use rusqlite::Row;
 
 use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::audit::*;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::tax::*;
+use crate::core::domain::values::trash::*;
 use crate::core::required_ports::*;
 use crate::core::Error;
 use crate::core::Result;
 
+use super::audit;
+use super::trash;
+
 // supplier repository implementation of SQLite
 pub struct SqliteSupplierRepository {
     db_path: String,
@@ -25,6 +33,77 @@ impl SqliteSupplierRepository {
     }
 }
 
+/// Reads a supplier from a row of the columns of `suppliers` in table order.
+fn supplier_row(row: &Row) -> rusqlite::Result<Supplier> {
+    let mut supplier = Supplier::restore(
+        SupplierId::new(row.get::<_, i64>(0)?.to_string())?,
+        SupplierName::new(row.get::<_, String>(1)?)?,
+        row.get::<_, Option<String>>(2)?
+            .map(InvoiceRegistrationNumber::new)
+            .transpose()?,
+        row.get::<_, bool>(3)?,
+    );
+
+    supplier.change_contact(
+        SupplierContact::default()
+            .with_person(
+                row.get::<_, Option<String>>(4)?
+                    .map(ContactPerson::new)
+                    .transpose()?,
+            )
+            .with_phone_number(
+                row.get::<_, Option<String>>(5)?
+                    .map(PhoneNumber::new)
+                    .transpose()?,
+            )
+            .with_fax_number(
+                row.get::<_, Option<String>>(6)?
+                    .map(PhoneNumber::new)
+                    .transpose()?,
+            )
+            .with_email(
+                row.get::<_, Option<String>>(7)?
+                    .map(EmailAddress::new)
+                    .transpose()?,
+            )
+            .with_address(
+                row.get::<_, Option<String>>(8)?
+                    .map(PostalCode::new)
+                    .transpose()?,
+                row.get::<_, Option<String>>(9)?
+                    .map(Address::new)
+                    .transpose()?,
+            ),
+    );
+
+    supplier.change_notes(
+        row.get::<_, Option<String>>(10)?
+            .map(SupplierNotes::new)
+            .transpose()?,
+    );
+
+    let billing_terms = match (
+        row.get::<_, Option<u32>>(11)?,
+        row.get::<_, Option<u32>>(12)?,
+        row.get::<_, Option<u32>>(13)?,
+        row.get::<_, Option<String>>(14)?,
+    ) {
+        (Some(closing_day), Some(payment_months_later), Some(payment_day), Some(method)) => {
+            Some(BillingTerms::new(
+                BillingDay::new(closing_day)?,
+                payment_months_later,
+                BillingDay::new(payment_day)?,
+                PaymentMethod::new(method)?,
+            )?)
+        }
+        _ => None,
+    };
+
+    supplier.change_billing_terms(billing_terms);
+
+    Ok(supplier)
+}
+
 /// Implementation of `ForSupplierPersistence` for `SqliteSupplierRepository`
 impl ForSupplierPersistence for SqliteSupplierRepository {
     fn next_id(&self) -> Result<SupplierId> {
@@ -49,15 +128,16 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
         Ok(next_id)
     }
 
-    fn has(&self, supplier_ids: &[SupplierId]) -> Result<bool> {
+    fn has(&self, store: &StoreContext, supplier_ids: &[SupplierId]) -> Result<bool> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
 
         let params = vec!["?"; supplier_ids.len()].join(", ");
+        let store_id = store.scope().map(|id| id.as_str());
 
         let mut statement = conn
             .prepare(&format!(
-                "SELECT COUNT(id) FROM suppliers WHERE id IN ({}) AND deleted_at IS NULL",
+                "SELECT COUNT(id) FROM suppliers WHERE id IN ({}) AND (? IS NULL OR store_id = ?) AND deleted_at IS NULL",
                 params
             ))
             .map_err(|e| {
@@ -66,7 +146,12 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
 
         let count = statement
             .query_row(
-                params_from_iter(supplier_ids.iter().map(|id| id.as_str())),
+                params_from_iter(
+                    supplier_ids
+                        .iter()
+                        .map(|id| Some(id.as_str()))
+                        .chain([store_id, store_id]),
+                ),
                 |r| r.get::<_, i64>(0),
             )
             .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
@@ -74,7 +159,7 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
         Ok(count == supplier_ids.len() as i64)
     }
 
-    fn list(&self) -> Result<Vec<Supplier>> {
+    fn list(&self, store: &StoreContext) -> Result<Vec<Supplier>> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
 
@@ -83,9 +168,24 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
                 r"
                 SELECT
                     id,
-                    name
+                    name,
+                    invoice_registration_number,
+                    invoice_registered,
+                    contact_person,
+                    phone_number,
+                    fax_number,
+                    email,
+                    postal_code,
+                    address,
+                    notes,
+                    closing_day,
+                    payment_months_later,
+                    payment_day,
+                    payment_method
                 FROM suppliers
                 WHERE
+                    (:store_id IS NULL OR store_id = :store_id)
+                    AND
                     deleted_at IS NULL
                 ",
             )
@@ -94,14 +194,10 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
             })?;
 
         let supplier_results = statement
-            .query_map([], |row| {
-                let supplier = Supplier::restore(
-                    SupplierId::new(row.get::<_, i64>(0)?.to_string())?,
-                    SupplierName::new(row.get::<_, String>(1)?)?,
-                );
-
-                Ok(supplier)
-            })
+            .query_map(
+                named_params! { ":store_id": store.scope().map(|id| id.as_str()) },
+                supplier_row,
+            )
             .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
 
         let suppliers = supplier_results
@@ -115,7 +211,7 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
         Ok(suppliers)
     }
 
-    fn get(&self, id: SupplierId) -> Result<Option<Supplier>> {
+    fn get(&self, store: &StoreContext, id: SupplierId) -> Result<Option<Supplier>> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
 
@@ -124,11 +220,26 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
                 r"
                 SELECT
                     id,
-                    name
+                    name,
+                    invoice_registration_number,
+                    invoice_registered,
+                    contact_person,
+                    phone_number,
+                    fax_number,
+                    email,
+                    postal_code,
+                    address,
+                    notes,
+                    closing_day,
+                    payment_months_later,
+                    payment_day,
+                    payment_method
                 FROM suppliers
                 WHERE
                     id = :id
                     AND
+                    (:store_id IS NULL OR store_id = :store_id)
+                    AND
                     deleted_at IS NULL
                 ",
             )
@@ -140,15 +251,9 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
             .query_row(
                 named_params! {
                     ":id": id.as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
                 },
-                |row| {
-                    let supplier = Supplier::restore(
-                        SupplierId::new(row.get::<_, i64>(0)?.to_string())?,
-                        SupplierName::new(row.get::<_, String>(1)?)?,
-                    );
-
-                    Ok(supplier)
-                },
+                supplier_row,
             )
             .optional();
 
@@ -158,7 +263,7 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
         Ok(supplier)
     }
 
-    fn find(&self, query: FindSupplierQuery) -> Result<Vec<Supplier>> {
+    fn find(&self, store: &StoreContext, query: FindSupplierQuery) -> Result<Vec<Supplier>> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
 
@@ -167,20 +272,46 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
                 r"
                 WITH supplier_ids AS (
                     SELECT
-                        supplier_id
-                    FROM supplies
+                        supply_suppliers.supplier_id
+                    FROM supply_suppliers
+                    INNER JOIN supplies ON supplies.id = supply_suppliers.supply_id
                     WHERE
-                        (:supply_name IS NULL OR name LIKE :supply_name)
+                        (:supply_name IS NULL OR supplies.name LIKE :supply_name)
                         AND
-                        deleted_at IS NULL
+                        supplies.deleted_at IS NULL
                 )
                 SELECT
                     id,
-                    name
+                    name,
+                    invoice_registration_number,
+                    invoice_registered,
+                    contact_person,
+                    phone_number,
+                    fax_number,
+                    email,
+                    postal_code,
+                    address,
+                    notes,
+                    closing_day,
+                    payment_months_later,
+                    payment_day,
+                    payment_method
                 FROM suppliers
                 WHERE
+                    (:store_id IS NULL OR store_id = :store_id)
+                    AND
                     (:supplier_name IS NULL OR name LIKE :supplier_name)
                     AND
+                    (:contact_person IS NULL OR contact_person LIKE :contact_person)
+                    AND
+                    (
+                        :phone_digits IS NULL
+                        OR
+                        REPLACE(REPLACE(REPLACE(REPLACE(REPLACE(phone_number, '-', ''), ' ', ''), '(', ''), ')', ''), '+', '') LIKE :phone_digits
+                        OR
+                        REPLACE(REPLACE(REPLACE(REPLACE(REPLACE(fax_number, '-', ''), ' ', ''), '(', ''), ')', ''), '+', '') LIKE :phone_digits
+                    )
+                    AND
                     (
                         :supply_name IS NULL -- includes no supply suppliers if supply name not provided
                         OR
@@ -197,17 +328,13 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
         let supplier_results = statement
             .query_map(
                 named_params! {
+                    ":store_id": store.scope().map(|id| id.as_str()),
                     ":supplier_name": query.supplier_name.and_then(|name| Some(format!("%{}%", name.to_string()))),
                     ":supply_name": query.supply_name.and_then(|name| Some(format!("%{}%", name.to_string()))),
+                    ":contact_person": query.contact_person.map(|person| format!("%{}%", person.as_str())),
+                    ":phone_digits": query.phone_digits.map(|digits| format!("%{}%", digits)),
                 },
-                |row| {
-                    let supplier = Supplier::restore(
-                        SupplierId::new(row.get::<_, i64>(0)?.to_string())?,
-                        SupplierName::new(row.get::<_, String>(1)?)?,
-                    );
-
-                    Ok(supplier)
-                },
+                supplier_row,
             )
             .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
 
@@ -222,7 +349,14 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
         Ok(suppliers)
     }
 
-    fn add(&self, supplier: Supplier) -> Result<()> {
+    fn add(
+        &self,
+        store: &StoreContext,
+        supplier: Supplier,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let store_id = store.store_id()?;
+
         let mut conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
 
@@ -230,26 +364,75 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
             Error::InfrastructureError(format!("failed to start transacrion: {}", e))
         })?;
 
+        let contact = supplier.contact();
+        let terms = supplier.billing_terms();
+
         let result = tran
             .execute(
                 r"
                 INSERT INTO suppliers (
                     id,
-                    name
+                    name,
+                    invoice_registration_number,
+                    invoice_registered,
+                    contact_person,
+                    phone_number,
+                    fax_number,
+                    email,
+                    postal_code,
+                    address,
+                    notes,
+                    closing_day,
+                    payment_months_later,
+                    payment_day,
+                    payment_method,
+                    store_id
                 ) VALUES (
                     :id,
-                    :name
+                    :name,
+                    :invoice_registration_number,
+                    :invoice_registered,
+                    :contact_person,
+                    :phone_number,
+                    :fax_number,
+                    :email,
+                    :postal_code,
+                    :address,
+                    :notes,
+                    :closing_day,
+                    :payment_months_later,
+                    :payment_day,
+                    :payment_method,
+                    :store_id
                 )
                 ",
                 named_params! {
                     ":id": supplier.id().as_str(),
                     ":name": supplier.name().as_str(),
+                    ":invoice_registration_number": supplier
+                        .invoice_registration_number()
+                        .map(|number| number.as_str()),
+                    ":invoice_registered": supplier.is_invoice_registered(),
+                    ":contact_person": contact.person().map(|person| person.as_str()),
+                    ":phone_number": contact.phone_number().map(|number| number.as_str()),
+                    ":fax_number": contact.fax_number().map(|number| number.as_str()),
+                    ":email": contact.email().map(|email| email.as_str()),
+                    ":postal_code": contact.postal_code().map(|code| code.as_str()),
+                    ":address": contact.address().map(|address| address.as_str()),
+                    ":notes": supplier.notes().map(|notes| notes.as_str()),
+                    ":closing_day": terms.map(|terms| terms.closing_day().as_u32()),
+                    ":payment_months_later": terms.map(|terms| terms.payment_months_later()),
+                    ":payment_day": terms.map(|terms| terms.payment_day().as_u32()),
+                    ":payment_method": terms.map(|terms| terms.payment_method().to_string()),
+                    ":store_id": store_id.as_str(),
                 },
             )
             .map_err(|e| {
                 Error::InfrastructureError(format!("failed to insert new supplier: {}", e))
             });
 
+        let result = result.and_then(|_| audit::insert_change(&tran, store, change.as_ref()));
+
         if let Err(e) = result {
             tran.rollback()
                 .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
@@ -263,7 +446,12 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
         Ok(())
     }
 
-    fn save(&self, supplier: Supplier) -> Result<()> {
+    fn save(
+        &self,
+        store: &StoreContext,
+        supplier: Supplier,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
         let mut conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
 
@@ -271,22 +459,60 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
             Error::InfrastructureError(format!("failed to start transacrion: {}", e))
         })?;
 
+        let contact = supplier.contact();
+        let terms = supplier.billing_terms();
+
         let result = tran
             .execute(
                 r"
                 UPDATE suppliers
-                SET name = :name
-                WHERE id = :id
+                SET
+                    name = :name,
+                    invoice_registration_number = :invoice_registration_number,
+                    invoice_registered = :invoice_registered,
+                    contact_person = :contact_person,
+                    phone_number = :phone_number,
+                    fax_number = :fax_number,
+                    email = :email,
+                    postal_code = :postal_code,
+                    address = :address,
+                    notes = :notes,
+                    closing_day = :closing_day,
+                    payment_months_later = :payment_months_later,
+                    payment_day = :payment_day,
+                    payment_method = :payment_method
+                WHERE
+                    id = :id
+                    AND
+                    (:store_id IS NULL OR store_id = :store_id)
                 ",
                 named_params! {
                     ":id": supplier.id().as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
                     ":name": supplier.name().as_str(),
+                    ":invoice_registration_number": supplier
+                        .invoice_registration_number()
+                        .map(|number| number.as_str()),
+                    ":invoice_registered": supplier.is_invoice_registered(),
+                    ":contact_person": contact.person().map(|person| person.as_str()),
+                    ":phone_number": contact.phone_number().map(|number| number.as_str()),
+                    ":fax_number": contact.fax_number().map(|number| number.as_str()),
+                    ":email": contact.email().map(|email| email.as_str()),
+                    ":postal_code": contact.postal_code().map(|code| code.as_str()),
+                    ":address": contact.address().map(|address| address.as_str()),
+                    ":notes": supplier.notes().map(|notes| notes.as_str()),
+                    ":closing_day": terms.map(|terms| terms.closing_day().as_u32()),
+                    ":payment_months_later": terms.map(|terms| terms.payment_months_later()),
+                    ":payment_day": terms.map(|terms| terms.payment_day().as_u32()),
+                    ":payment_method": terms.map(|terms| terms.payment_method().to_string()),
                 },
             )
             .map_err(|e| {
                 Error::InfrastructureError(format!("failed to execute transaction: {}", e))
             });
 
+        let result = result.and_then(|_| audit::insert_change(&tran, store, change.as_ref()));
+
         if result.is_err() {
             tran.rollback()
                 .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
@@ -300,7 +526,12 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
         Ok(())
     }
 
-    fn delete(&self, id: SupplierId) -> Result<()> {
+    fn delete(
+        &self,
+        store: &StoreContext,
+        id: SupplierId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
         let mut conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
 
@@ -313,10 +544,14 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
                 r"
                 UPDATE suppliers
                 SET deleted_at = :deleted_at
-                WHERE id = :id
+                WHERE
+                    id = :id
+                    AND
+                    (:store_id IS NULL OR store_id = :store_id)
                 ",
                 named_params! {
                     ":id": id.as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
                     ":deleted_at": Utc::now().timestamp_millis(),
                 },
             )
@@ -324,6 +559,8 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
                 Error::InfrastructureError(format!("failed to execute transaction: {}", e))
             });
 
+        let result = result.and_then(|_| audit::insert_change(&tran, store, change.as_ref()));
+
         if result.is_err() {
             tran.rollback()
                 .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
@@ -336,4 +573,91 @@ impl ForSupplierPersistence for SqliteSupplierRepository {
 
         Ok(())
     }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Supplier>>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    id,
+                    name,
+                    invoice_registration_number,
+                    invoice_registered,
+                    contact_person,
+                    phone_number,
+                    fax_number,
+                    email,
+                    postal_code,
+                    address,
+                    notes,
+                    closing_day,
+                    payment_months_later,
+                    payment_day,
+                    payment_method
+                FROM suppliers
+                WHERE
+                    (:store_id IS NULL OR store_id = :store_id)
+                    AND
+                    deleted_at IS NOT NULL
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let supplier_results = statement
+            .query_map(
+                named_params! { ":store_id": store.scope().map(|id| id.as_str()) },
+                supplier_row,
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
+
+        let suppliers = supplier_results
+            .map(|supplier| {
+                supplier.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<Supplier>>>()?;
+
+        let deleted_ats = trash::deleted_ats(&conn, "suppliers")?;
+
+        Ok(trash::trashed(suppliers, &deleted_ats, |supplier| {
+            supplier.id().as_str()
+        }))
+    }
+
+    fn restore(
+        &self,
+        store: &StoreContext,
+        id: SupplierId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        trash::restore(
+            &self.db_path,
+            "suppliers",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            change.as_ref().map(|change| (store, change)),
+        )
+    }
+
+    fn purge(
+        &self,
+        store: &StoreContext,
+        id: SupplierId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        trash::purge(
+            &self.db_path,
+            "suppliers",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            &[],
+            change.as_ref().map(|change| (store, change)),
+        )
+    }
 }
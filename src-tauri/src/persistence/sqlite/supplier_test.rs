@@ -5,6 +5,8 @@ use scopeguard::defer;
 
 use crate::core::domain::entities::stock::Supplier;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::tax::InvoiceRegistrationNumber;
 use crate::core::required_ports::*;
 use crate::persistence::sqlite::{migrate, SqliteSupplierRepository};
 
@@ -28,15 +30,26 @@ fn supplier_repository_test() {
 
     assert_eq!(next_id, SupplierId::new("1").unwrap());
 
-    let supplier = Supplier::restore(next_id.clone(), SupplierName::new("SupplierA").unwrap());
+    let supplier = Supplier::restore(
+        next_id.clone(),
+        SupplierName::new("SupplierA").unwrap(),
+        None,
+        false,
+    );
 
-    repository.add(supplier).unwrap();
+    repository
+        .add(&StoreContext::default(), supplier, None)
+        .unwrap();
 
-    let has_supplies = repository.has(&[next_id]).unwrap();
+    let has_supplies = repository
+        .has(&StoreContext::default(), &[next_id])
+        .unwrap();
 
     assert!(has_supplies);
 
-    let supplier = repository.get(SupplierId::new("1").unwrap()).unwrap();
+    let supplier = repository
+        .get(&StoreContext::default(), SupplierId::new("1").unwrap())
+        .unwrap();
 
     assert!(supplier.is_some_and(|supplier| {
         assert_eq!(supplier.id(), &SupplierId::new("1").unwrap());
@@ -44,7 +57,7 @@ fn supplier_repository_test() {
         true
     }));
 
-    let suppliers = repository.list().unwrap();
+    let suppliers = repository.list(&StoreContext::default()).unwrap();
 
     assert!(suppliers.first().is_some_and(|supplier| {
         assert_eq!(supplier.id(), &SupplierId::new("1").unwrap());
@@ -53,10 +66,15 @@ fn supplier_repository_test() {
     }));
 
     let suppliers = repository
-        .find(FindSupplierQuery {
-            supplier_name: Some(SupplierName::new("A").unwrap()),
-            supply_name: None,
-        })
+        .find(
+            &StoreContext::default(),
+            FindSupplierQuery {
+                supplier_name: Some(SupplierName::new("A").unwrap()),
+                supply_name: None,
+                contact_person: None,
+                phone_digits: None,
+            },
+        )
         .unwrap();
 
     assert!(suppliers.first().is_some_and(|supplier| {
@@ -65,24 +83,89 @@ fn supplier_repository_test() {
         true
     }));
 
+    let mut supplier = Supplier::restore(
+        SupplierId::new("1").unwrap(),
+        SupplierName::new("SupplierB").unwrap(),
+        Some(InvoiceRegistrationNumber::new("T7000012050002").unwrap()),
+        true,
+    );
+
+    let contact = SupplierContact::default()
+        .with_person(Some(ContactPerson::new("山田").unwrap()))
+        .with_phone_number(Some(PhoneNumber::new("03-1234-5678").unwrap()))
+        .with_fax_number(Some(PhoneNumber::new("03-1234-5679").unwrap()))
+        .with_email(Some(EmailAddress::new("order@example.com").unwrap()))
+        .with_address(
+            Some(PostalCode::new("100-0005").unwrap()),
+            Some(Address::new("東京都千代田区丸の内1-1-1").unwrap()),
+        );
+
+    let billing_terms = BillingTerms::new(
+        BillingDay::new(20).unwrap(),
+        1,
+        BillingDay::EndOfMonth,
+        PaymentMethod::BankTransfer,
+    )
+    .unwrap();
+
+    supplier.change_contact(contact.clone());
+    supplier.change_notes(Some(SupplierNotes::new("火・金配送").unwrap()));
+    supplier.change_billing_terms(Some(billing_terms));
+
     repository
-        .save(Supplier::restore(
-            SupplierId::new("1").unwrap(),
-            SupplierName::new("SupplierB").unwrap(),
-        ))
+        .save(&StoreContext::default(), supplier, None)
         .unwrap();
 
-    let supplier = repository.get(SupplierId::new("1").unwrap()).unwrap();
+    let supplier = repository
+        .get(&StoreContext::default(), SupplierId::new("1").unwrap())
+        .unwrap();
 
     assert!(supplier.as_ref().is_some_and(|supplier| {
         assert_eq!(supplier.id(), &SupplierId::new("1").unwrap());
         assert_eq!(supplier.name(), &SupplierName::new("SupplierB").unwrap());
+        assert_eq!(
+            supplier.invoice_registration_number(),
+            Some(&InvoiceRegistrationNumber::new("T7000012050002").unwrap())
+        );
+        assert!(supplier.is_invoice_registered());
+        assert_eq!(supplier.contact(), &contact);
+        assert_eq!(
+            supplier.notes(),
+            Some(&SupplierNotes::new("火・金配送").unwrap())
+        );
+        assert_eq!(supplier.billing_terms(), Some(&billing_terms));
         true
     }));
 
-    repository.delete(supplier.unwrap().id().clone()).unwrap();
+    let find = |contact_person: Option<&str>, phone_digits: Option<&str>| {
+        repository
+            .find(
+                &StoreContext::default(),
+                FindSupplierQuery {
+                    supplier_name: None,
+                    supply_name: None,
+                    contact_person: contact_person
+                        .map(|person| ContactPerson::new(person).unwrap()),
+                    phone_digits: phone_digits.map(|digits| digits.to_string()),
+                },
+            )
+            .unwrap()
+            .len()
+    };
+
+    assert_eq!(find(Some("山田"), None), 1);
+    assert_eq!(find(None, Some("12345679")), 1);
+    assert_eq!(find(None, Some("0612345678")), 0);
+
+    repository
+        .delete(
+            &StoreContext::default(),
+            supplier.unwrap().id().clone(),
+            None,
+        )
+        .unwrap();
 
-    let suppliers = repository.list().unwrap();
+    let suppliers = repository.list(&StoreContext::default()).unwrap();
 
     assert_eq!(suppliers, vec![]);
 }
@@ -6,13 +6,26 @@ use rusqlite::named_params;
 use rusqlite::params_from_iter;
 use rusqlite::Connection;
 use rusqlite::OptionalExtension;
+use rusqlite::Row;
 
 use crate::core::domain::entities::stock::Supply;
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::category::*;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::location::*;
+use crate::core::domain::values::reorder::*;
 use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::tax::*;
+use crate::core::domain::values::trash::*;
+use crate::core::domain::values::unit::*;
 use crate::core::required_ports::*;
 use crate::core::Error;
 use crate::core::Result;
 
+use super::audit;
+use super::trash;
+
 /// SQLite implementation of `ForSupplyPersistence`
 pub struct SqliteSupplyRepository {
     db_path: String,
@@ -50,11 +63,12 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
         Ok(next_id)
     }
 
-    fn has(&self, supply_ids: &[SupplyId]) -> Result<bool> {
+    fn has(&self, store: &StoreContext, supply_ids: &[SupplyId]) -> Result<bool> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
         let params = vec!["?"; supply_ids.len()].join(", ");
+        let store_id = store.scope().map(|id| id.as_str());
 
         let mut statement = conn
             .prepare(&format!(
@@ -64,6 +78,8 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
                 WHERE 
                     id IN ({})
                     AND
+                    (? IS NULL OR store_id = ?)
+                    AND
                     deleted_at IS NULL
                 ",
                 params
@@ -74,7 +90,12 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
 
         let count = statement
             .query_one(
-                params_from_iter(supply_ids.iter().map(|id| id.as_str())),
+                params_from_iter(
+                    supply_ids
+                        .iter()
+                        .map(|id| Some(id.as_str()))
+                        .chain([store_id, store_id]),
+                ),
                 |r| r.get::<_, i64>(0),
             )
             .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
@@ -82,7 +103,10 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
         Ok(count == supply_ids.len() as i64)
     }
 
-    fn list(&self) -> Result<Vec<crate::core::domain::entities::stock::Supply>> {
+    fn list(
+        &self,
+        store: &StoreContext,
+    ) -> Result<Vec<crate::core::domain::entities::stock::Supply>> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -93,9 +117,18 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
                     id,
                     name,
                     unit_name,
-                    supplier_id
+                    tax_category,
+                    purchase_unit_id,
+                    stock_unit_id,
+                    category_id,
+                    par_level,
+                    reorder_point,
+                    storage_location_id,
+                    storage_sort_order
                 FROM supplies
                 WHERE
+                    (:store_id IS NULL OR store_id = :store_id)
+                    AND
                     deleted_at IS NULL
                 ",
             )
@@ -104,16 +137,10 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
             })?;
 
         let supply_results = statement
-            .query_map([], |row| {
-                let supply = Supply::new(
-                    SupplyId::new(row.get::<_, i64>(0)?.to_string())?,
-                    SupplyName::new(row.get::<_, String>(1)?)?,
-                    UnitName::new(row.get::<_, String>(2)?)?,
-                    SupplierId::new(row.get::<_, i64>(3)?.to_string())?,
-                );
-
-                Ok(supply)
-            })
+            .query_map(
+                named_params! { ":store_id": store.scope().map(|id| id.as_str()) },
+                |row| supply_of(&conn, row),
+            )
             .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
 
         let supplies = supply_results
@@ -127,7 +154,11 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
         Ok(supplies)
     }
 
-    fn get(&self, id: SupplyId) -> Result<Option<crate::core::domain::entities::stock::Supply>> {
+    fn get(
+        &self,
+        store: &StoreContext,
+        id: SupplyId,
+    ) -> Result<Option<crate::core::domain::entities::stock::Supply>> {
         let conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -138,11 +169,20 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
                     id,
                     name,
                     unit_name,
-                    supplier_id
+                    tax_category,
+                    purchase_unit_id,
+                    stock_unit_id,
+                    category_id,
+                    par_level,
+                    reorder_point,
+                    storage_location_id,
+                    storage_sort_order
                 FROM supplies
                 WHERE
                     id = :id
                     AND
+                    (:store_id IS NULL OR store_id = :store_id)
+                    AND
                     deleted_at IS NULL
                 ",
             )
@@ -153,18 +193,10 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
         let supply = statement
             .query_row(
                 named_params! {
-                    ":id": id.as_str()
-                },
-                |row| {
-                    let supply = Supply::new(
-                        SupplyId::new(row.get::<_, i64>(0)?.to_string())?,
-                        SupplyName::new(row.get::<_, String>(1)?)?,
-                        UnitName::new(row.get::<_, String>(2)?)?,
-                        SupplierId::new(row.get::<_, i64>(3)?.to_string())?,
-                    );
-
-                    Ok(supply)
+                    ":id": id.as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
                 },
+                |row| supply_of(&conn, row),
             )
             .optional()
             .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
@@ -183,10 +215,21 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
                     id,
                     name,
                     unit_name,
-                    supplier_id
+                    tax_category,
+                    purchase_unit_id,
+                    stock_unit_id,
+                    category_id,
+                    par_level,
+                    reorder_point,
+                    storage_location_id,
+                    storage_sort_order
                 FROM supplies
                 WHERE
-                    supplier_id = :supplier_id
+                    id IN (
+                        SELECT supply_id
+                        FROM supply_suppliers
+                        WHERE supplier_id = :supplier_id
+                    )
                     AND
                     deleted_at IS NULL
                 ",
@@ -200,16 +243,7 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
                 named_params! {
                     ":supplier_id": supplier_id.as_str(),
                 },
-                |row| {
-                    let supply = Supply::new(
-                        SupplyId::new(row.get::<_, i64>(0)?.to_string())?,
-                        SupplyName::new(row.get::<_, String>(1)?)?,
-                        UnitName::new(row.get::<_, String>(2)?)?,
-                        SupplierId::new(row.get::<_, i64>(3)?.to_string())?,
-                    );
-
-                    Ok(supply)
-                },
+                |row| supply_of(&conn, row),
             )
             .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
             .map(|supply| {
@@ -222,7 +256,14 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
         Ok(supplies)
     }
 
-    fn add(&self, supply: crate::core::domain::entities::stock::Supply) -> Result<()> {
+    fn add(
+        &self,
+        store: &StoreContext,
+        supply: crate::core::domain::entities::stock::Supply,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        let store_id = store.store_id()?;
+
         let mut conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -237,23 +278,51 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
                     id,
                     name,
                     unit_name,
-                    supplier_id
+                    tax_category,
+                    purchase_unit_id,
+                    stock_unit_id,
+                    category_id,
+                    par_level,
+                    reorder_point,
+                    storage_location_id,
+                    storage_sort_order,
+                    store_id
                 ) VALUES (
                     :id,
                     :name,
                     :unit_name,
-                    :supplier_id
+                    :tax_category,
+                    :purchase_unit_id,
+                    :stock_unit_id,
+                    :category_id,
+                    :par_level,
+                    :reorder_point,
+                    :storage_location_id,
+                    :storage_sort_order,
+                    :store_id
                 );
                 ",
                 named_params! {
                     ":id": supply.id().as_str(),
                     ":name": supply.name().as_str(),
                     ":unit_name": supply.unit_name().as_str(),
-                    ":supplier_id": supply.supplier_id().as_str(),
+                    ":tax_category": supply.tax_category().as_str(),
+                    ":purchase_unit_id": supply.units().purchase_unit_id().as_str(),
+                    ":stock_unit_id": supply.units().stock_unit_id().as_str(),
+                    ":category_id": supply.category_id().map(|category_id| category_id.as_str()),
+                    ":par_level": supply.reorder_levels().map(|levels| levels.par_level().as_raw()),
+                    ":reorder_point": supply.reorder_levels().map(|levels| levels.reorder_point().as_raw()),
+                    ":storage_location_id": supply.placement().map(|placement| placement.location_id().as_str()),
+                    ":storage_sort_order": supply.placement().map(|placement| placement.sort_order().as_u32()),
+                    ":store_id": store_id.as_str(),
                 },
             )
+            .and_then(|_| save_conversions(&tran, &supply))
+            .and_then(|_| save_offers(&tran, &supply))
             .map_err(|e| Error::InfrastructureError(format!("failed to insert new supply: {}", e)));
 
+        let result = result.and_then(|_| audit::insert_change(&tran, store, change.as_ref()));
+
         if let Err(e) = result {
             tran.rollback()
                 .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
@@ -267,7 +336,12 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
         Ok(())
     }
 
-    fn save(&self, supply: Supply) -> Result<()> {
+    fn save(
+        &self,
+        store: &StoreContext,
+        supply: Supply,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
         let mut conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -282,19 +356,43 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
                 SET
                     name = :name,
                     unit_name = :unit_name,
-                    supplier_id = :supplier_id
+                    tax_category = :tax_category,
+                    purchase_unit_id = :purchase_unit_id,
+                    stock_unit_id = :stock_unit_id,
+                    category_id = :category_id,
+                    par_level = :par_level,
+                    reorder_point = :reorder_point,
+                    storage_location_id = :storage_location_id,
+                    storage_sort_order = :storage_sort_order
                 WHERE
                     id = :id
+                    AND
+                    (:store_id IS NULL OR store_id = :store_id)
                 ",
                 named_params! {
                     ":id": supply.id().as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
                     ":name": supply.name().as_str(),
                     ":unit_name": supply.unit_name().as_str(),
-                    ":supplier_id": supply.supplier_id().as_str(),
+                    ":tax_category": supply.tax_category().as_str(),
+                    ":purchase_unit_id": supply.units().purchase_unit_id().as_str(),
+                    ":stock_unit_id": supply.units().stock_unit_id().as_str(),
+                    ":category_id": supply.category_id().map(|category_id| category_id.as_str()),
+                    ":par_level": supply.reorder_levels().map(|levels| levels.par_level().as_raw()),
+                    ":reorder_point": supply.reorder_levels().map(|levels| levels.reorder_point().as_raw()),
+                    ":storage_location_id": supply.placement().map(|placement| placement.location_id().as_str()),
+                    ":storage_sort_order": supply.placement().map(|placement| placement.sort_order().as_u32()),
                 },
             )
+            // a supply of another store is left as it is
+            .and_then(|updated| match updated {
+                0 => Ok(0),
+                _ => save_conversions(&tran, &supply).and_then(|_| save_offers(&tran, &supply)),
+            })
             .map_err(|e| Error::InfrastructureError(format!("failed to update supply: {}", e)));
 
+        let result = result.and_then(|_| audit::insert_change(&tran, store, change.as_ref()));
+
         if let Err(e) = result {
             tran.rollback()
                 .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
@@ -308,7 +406,12 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
         Ok(())
     }
 
-    fn delete(&self, id: SupplyId) -> Result<()> {
+    fn delete(
+        &self,
+        store: &StoreContext,
+        id: SupplyId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
         let mut conn = Connection::open(&self.db_path)
             .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
 
@@ -323,14 +426,19 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
                 SET deleted_at = :deleted_at
                 WHERE
                     id = :id
+                    AND
+                    (:store_id IS NULL OR store_id = :store_id)
                 ",
                 named_params! {
                     ":id": id.as_str(),
+                    ":store_id": store.scope().map(|id| id.as_str()),
                     ":deleted_at": Utc::now().timestamp_millis(),
                 },
             )
             .map_err(|e| Error::InfrastructureError(format!("failed to update supply: {}", e)));
 
+        let result = result.and_then(|_| audit::insert_change(&tran, store, change.as_ref()));
+
         if let Err(e) = result {
             tran.rollback()
                 .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
@@ -343,4 +451,286 @@ impl ForSupplyPersistence for SqliteSupplyRepository {
 
         Ok(())
     }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Supply>>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    id,
+                    name,
+                    unit_name,
+                    tax_category,
+                    purchase_unit_id,
+                    stock_unit_id,
+                    category_id,
+                    par_level,
+                    reorder_point,
+                    storage_location_id,
+                    storage_sort_order
+                FROM supplies
+                WHERE
+                    (:store_id IS NULL OR store_id = :store_id)
+                    AND
+                    deleted_at IS NOT NULL
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let supply_results = statement
+            .query_map(
+                named_params! { ":store_id": store.scope().map(|id| id.as_str()) },
+                |row| supply_of(&conn, row),
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
+
+        let supplies = supply_results
+            .map(|supply| {
+                supply.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<Supply>>>()?;
+
+        let deleted_ats = trash::deleted_ats(&conn, "supplies")?;
+
+        Ok(trash::trashed(supplies, &deleted_ats, |supply| {
+            supply.id().as_str()
+        }))
+    }
+
+    fn restore(
+        &self,
+        store: &StoreContext,
+        id: SupplyId,
+        change: Option<AuditChange>,
+    ) -> Result<()> {
+        trash::restore(
+            &self.db_path,
+            "supplies",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            change.as_ref().map(|change| (store, change)),
+        )
+    }
+
+    fn purge(&self, store: &StoreContext, id: SupplyId, change: Option<AuditChange>) -> Result<()> {
+        trash::purge(
+            &self.db_path,
+            "supplies",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            &[
+                ("supply_unit_conversions", "supply_id"),
+                ("supply_suppliers", "supply_id"),
+            ],
+            change.as_ref().map(|change| (store, change)),
+        )
+    }
+}
+
+/// Restores a supply from a row of `supplies` with its unit conversions and offers.
+fn supply_of(conn: &Connection, row: &Row) -> rusqlite::Result<Supply> {
+    let supply_id = SupplyId::new(row.get::<_, i64>(0)?.to_string())?;
+
+    let mut statement = conn.prepare(
+        r"
+        SELECT
+            unit_id,
+            factor
+        FROM supply_unit_conversions
+        WHERE
+            supply_id = :supply_id
+        ORDER BY unit_id
+        ",
+    )?;
+
+    let conversions = statement
+        .query_map(
+            named_params! {
+                ":supply_id": supply_id.as_str(),
+            },
+            |row| {
+                Ok(UnitConversion::new(
+                    UnitId::new(row.get::<_, i64>(0)?.to_string())?,
+                    ConversionFactor::from_quantity(Quantity::from_raw(row.get::<_, i64>(1)?))?,
+                ))
+            },
+        )?
+        .collect::<rusqlite::Result<Vec<UnitConversion>>>()?;
+
+    let mut statement = conn.prepare(
+        r"
+        SELECT
+            supplier_id,
+            item_code,
+            unit_price,
+            preferred
+        FROM supply_suppliers
+        WHERE
+            supply_id = :supply_id
+        ORDER BY rowid
+        ",
+    )?;
+
+    let offers = statement
+        .query_map(
+            named_params! {
+                ":supply_id": supply_id.as_str(),
+            },
+            |row| {
+                Ok(
+                    SupplyOffer::new(SupplierId::new(row.get::<_, i64>(0)?.to_string())?)
+                        .with_item_code(
+                            row.get::<_, Option<String>>(1)?
+                                .map(ItemCode::new)
+                                .transpose()?,
+                        )
+                        .with_unit_price(
+                            row.get::<_, Option<i64>>(2)?
+                                .map(|price| {
+                                    PurchaseUnitPrice::from_money(Money::from_minor(price))
+                                })
+                                .transpose()?,
+                        )
+                        .with_preferred(row.get::<_, bool>(3)?),
+                )
+            },
+        )?
+        .collect::<rusqlite::Result<Vec<SupplyOffer>>>()?;
+
+    let supplier_id = offers
+        .first()
+        .map(|offer| offer.supplier_id().clone())
+        .ok_or(Error::DomainError(format!(
+            "supply is not offered by any supplier."
+        )))?;
+
+    let units = SupplyUnits::new(
+        UnitId::new(row.get::<_, i64>(4)?.to_string())?,
+        UnitId::new(row.get::<_, i64>(5)?.to_string())?,
+        conversions,
+    )?;
+
+    let mut supply = Supply::new(
+        supply_id,
+        SupplyName::new(row.get::<_, String>(1)?)?,
+        UnitName::new(row.get::<_, String>(2)?)?,
+        supplier_id,
+        TaxCategory::new(row.get::<_, String>(3)?)?,
+        units,
+        row.get::<_, Option<i64>>(6)?
+            .map(|category_id| CategoryId::new(category_id.to_string()))
+            .transpose()?,
+    );
+
+    supply.change_offers(offers)?;
+
+    let reorder_levels = match (row.get::<_, Option<i64>>(7)?, row.get::<_, Option<i64>>(8)?) {
+        (Some(par_level), Some(reorder_point)) => Some(ReorderLevels::from_quantities(
+            Quantity::from_raw(par_level),
+            Quantity::from_raw(reorder_point),
+        )?),
+        _ => None,
+    };
+
+    supply.change_reorder_levels(reorder_levels);
+
+    let placement = match (
+        row.get::<_, Option<i64>>(9)?,
+        row.get::<_, Option<i64>>(10)?,
+    ) {
+        (Some(location_id), sort_order) => Some(StoragePlacement::new(
+            StorageLocationId::new(location_id.to_string())?,
+            SortOrder::new(sort_order.unwrap_or_default())?,
+        )),
+        _ => None,
+    };
+
+    supply.change_placement(placement);
+
+    Ok(supply)
+}
+
+/// Replaces the unit conversions of a supply.
+fn save_conversions(tran: &Connection, supply: &Supply) -> rusqlite::Result<usize> {
+    tran.execute(
+        r"
+        DELETE FROM supply_unit_conversions
+        WHERE supply_id = :supply_id
+        ",
+        named_params! {
+            ":supply_id": supply.id().as_str(),
+        },
+    )?;
+
+    for conversion in supply.units().conversions() {
+        tran.execute(
+            r"
+            INSERT INTO supply_unit_conversions (
+                supply_id,
+                unit_id,
+                factor
+            ) VALUES (
+                :supply_id,
+                :unit_id,
+                :factor
+            )
+            ",
+            named_params! {
+                ":supply_id": supply.id().as_str(),
+                ":unit_id": conversion.unit_id().as_str(),
+                ":factor": conversion.factor().as_quantity().as_raw(),
+            },
+        )?;
+    }
+
+    Ok(supply.units().conversions().len())
+}
+
+/// Replaces the suppliers offering a supply.
+fn save_offers(tran: &Connection, supply: &Supply) -> rusqlite::Result<usize> {
+    tran.execute(
+        r"
+        DELETE FROM supply_suppliers
+        WHERE supply_id = :supply_id
+        ",
+        named_params! {
+            ":supply_id": supply.id().as_str(),
+        },
+    )?;
+
+    for offer in supply.offers() {
+        tran.execute(
+            r"
+            INSERT INTO supply_suppliers (
+                supply_id,
+                supplier_id,
+                item_code,
+                unit_price,
+                preferred
+            ) VALUES (
+                :supply_id,
+                :supplier_id,
+                :item_code,
+                :unit_price,
+                :preferred
+            )
+            ",
+            named_params! {
+                ":supply_id": supply.id().as_str(),
+                ":supplier_id": offer.supplier_id().as_str(),
+                ":item_code": offer.item_code().map(|code| code.as_str()),
+                ":unit_price": offer.unit_price().map(|price| price.as_money().as_minor()),
+                ":preferred": offer.is_preferred(),
+            },
+        )?;
+    }
+
+    Ok(supply.offers().len())
 }
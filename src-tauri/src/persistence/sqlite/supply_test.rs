@@ -5,7 +5,14 @@ use rusqlite::Connection;
 use scopeguard::defer;
 
 use crate::core::domain::entities::stock::Supply;
-use crate::core::domain::values::stock::{SupplierId, SupplyId, SupplyName, UnitName};
+use crate::core::domain::values::location::{SortOrder, StorageLocationId, StoragePlacement};
+use crate::core::domain::values::reorder::ReorderLevels;
+use crate::core::domain::values::stock::{
+    ItemCode, PurchaseUnitPrice, SupplierId, SupplyId, SupplyName, SupplyOffer, UnitName,
+};
+use crate::core::domain::values::store::StoreContext;
+use crate::core::domain::values::tax::TaxCategory;
+use crate::core::domain::values::unit::{ConversionFactor, SupplyUnits, UnitConversion, UnitId};
 use crate::core::required_ports::ForSupplyPersistence;
 use crate::persistence::sqlite::{migrate, SqliteSupplyRepository};
 
@@ -31,6 +38,12 @@ fn supply_repository_test() {
     )
     .unwrap();
 
+    conn.execute(
+        "INSERT INTO units (id, name) VALUES (1, 'g'), (2, 'kg')",
+        [],
+    )
+    .unwrap();
+
     let repository = SqliteSupplyRepository::new(tmp_path.to_string_lossy());
 
     let next_id = repository.next_id().unwrap();
@@ -38,46 +51,147 @@ fn supply_repository_test() {
     assert_eq!(next_id, SupplyId::new("1").unwrap());
 
     repository
-        .add(Supply::new(
-            SupplyId::new("1").unwrap(),
-            SupplyName::new("SupplyA").unwrap(),
-            UnitName::new("g").unwrap(),
-            SupplierId::new("1").unwrap(),
-        ))
+        .add(
+            &StoreContext::default(),
+            Supply::new(
+                SupplyId::new("1").unwrap(),
+                SupplyName::new("SupplyA").unwrap(),
+                UnitName::new("g").unwrap(),
+                SupplierId::new("1").unwrap(),
+                TaxCategory::Reduced,
+                SupplyUnits::single(UnitId::new("1").unwrap()),
+                None,
+            ),
+            None,
+        )
         .unwrap();
 
-    let supplies = repository.list().unwrap();
+    let supplies = repository.list(&StoreContext::default()).unwrap();
 
     assert!(supplies.first().is_some_and(|supply| {
         assert_eq!(supply.id(), &SupplyId::new("1").unwrap());
         assert_eq!(supply.name(), &SupplyName::new("SupplyA").unwrap());
         assert_eq!(supply.unit_name(), &UnitName::new("g").unwrap());
         assert_eq!(supply.supplier_id(), &SupplierId::new("1").unwrap());
+        assert_eq!(supply.tax_category(), TaxCategory::Reduced);
         true
     }));
 
     repository
-        .save(Supply::new(
-            SupplyId::new("1").unwrap(),
-            SupplyName::new("SupplyB").unwrap(),
-            UnitName::new("kg").unwrap(),
-            SupplierId::new("2").unwrap(),
-        ))
+        .save(
+            &StoreContext::default(),
+            Supply::new(
+                SupplyId::new("1").unwrap(),
+                SupplyName::new("SupplyB").unwrap(),
+                UnitName::new("kg").unwrap(),
+                SupplierId::new("2").unwrap(),
+                TaxCategory::Standard,
+                SupplyUnits::new(
+                    UnitId::new("2").unwrap(),
+                    UnitId::new("1").unwrap(),
+                    vec![UnitConversion::new(
+                        UnitId::new("2").unwrap(),
+                        ConversionFactor::new(1000).unwrap(),
+                    )],
+                )
+                .unwrap(),
+                None,
+            ),
+            None,
+        )
         .unwrap();
 
-    let supply = repository.get(SupplyId::new("1").unwrap()).unwrap();
+    let supply = repository
+        .get(&StoreContext::default(), SupplyId::new("1").unwrap())
+        .unwrap();
 
     assert!(supply.as_ref().is_some_and(|supply| {
         assert_eq!(supply.id(), &SupplyId::new("1").unwrap());
         assert_eq!(supply.name(), &SupplyName::new("SupplyB").unwrap());
         assert_eq!(supply.unit_name(), &UnitName::new("kg").unwrap());
         assert_eq!(supply.supplier_id(), &SupplierId::new("2").unwrap());
+        assert_eq!(supply.tax_category(), TaxCategory::Standard);
+        assert_eq!(
+            supply.units().purchase_unit_id(),
+            &UnitId::new("2").unwrap()
+        );
+        assert_eq!(supply.units().stock_unit_id(), &UnitId::new("1").unwrap());
+        assert_eq!(
+            supply.units().factor_of(&UnitId::new("2").unwrap()),
+            Some(ConversionFactor::new(1000).unwrap())
+        );
         true
     }));
 
-    repository.delete(supply.unwrap().id().clone()).unwrap();
+    let mut supply = supply.unwrap();
+
+    assert_eq!(supply.reorder_levels(), None);
+
+    supply.change_reorder_levels(Some(ReorderLevels::new(5000, 1500).unwrap()));
+
+    conn.execute(
+        "INSERT INTO storage_locations (id, name, sort_order) VALUES (1, '冷蔵庫', 0)",
+        [],
+    )
+    .unwrap();
+
+    let placement = StoragePlacement::new(
+        StorageLocationId::new("1").unwrap(),
+        SortOrder::new(3).unwrap(),
+    );
+
+    supply.change_placement(Some(placement.clone()));
+
+    repository
+        .save(&StoreContext::default(), supply.clone(), None)
+        .unwrap();
+
+    let saved = repository
+        .get(&StoreContext::default(), supply.id().clone())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        saved.reorder_levels(),
+        Some(&ReorderLevels::new(5000, 1500).unwrap())
+    );
+    assert_eq!(saved.placement(), Some(&placement));
+
+    let mut supply = saved;
+
+    supply
+        .change_offers(vec![
+            SupplyOffer::new(SupplierId::new("2").unwrap()).with_preferred(true),
+            SupplyOffer::new(SupplierId::new("1").unwrap())
+                .with_item_code(Some(ItemCode::new("ON-01").unwrap()))
+                .with_unit_price(Some(PurchaseUnitPrice::new(120.5).unwrap())),
+        ])
+        .unwrap();
+
+    repository
+        .save(&StoreContext::default(), supply.clone(), None)
+        .unwrap();
+
+    let saved = repository
+        .get(&StoreContext::default(), supply.id().clone())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(saved.offers(), supply.offers());
+    assert_eq!(saved.supplier_id(), &SupplierId::new("2").unwrap());
+
+    // supplies are listed for every supplier offering them
+    let supplies = repository
+        .list_of_supplier(SupplierId::new("1").unwrap())
+        .unwrap();
+
+    assert_eq!(supplies, vec![supply.clone()]);
+
+    repository
+        .delete(&StoreContext::default(), supply.id().clone(), None)
+        .unwrap();
 
-    let supplies = repository.list().unwrap();
+    let supplies = repository.list(&StoreContext::default()).unwrap();
 
     assert_eq!(supplies, vec![]);
 }
@@ -0,0 +1,459 @@
+//! This module provides the SQLite-based implementation of the `ForTransferPersistence` trait.
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::named_params;
+use rusqlite::Connection;
+use rusqlite::Row;
+use rusqlite::Transaction;
+
+use crate::core::domain::entities::stock::Transfer;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::transfer::*;
+use crate::core::domain::values::trash::*;
+use crate::core::required_ports::*;
+use crate::core::Error;
+use crate::core::Result;
+
+use super::trash;
+
+/// SQLite implementation of `ForTransferPersistence`
+pub struct SqliteTransferRepository {
+    db_path: String,
+}
+
+impl SqliteTransferRepository {
+    pub fn new(db_path: impl AsRef<str>) -> Self {
+        Self {
+            db_path: Path::new(db_path.as_ref()).to_string_lossy().to_string(),
+        }
+    }
+
+    /// Reads transfers from or to the stores matching the query, only `id` if given, those in the trash if `trashed`.
+    fn select(
+        &self,
+        id: Option<&TransferId>,
+        trashed: bool,
+        store: &StoreContext,
+        query: FindTransfersQuery,
+    ) -> Result<Vec<Transfer>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    transfers.id,
+                    transfers.transferred_at,
+                    transfers.source_store_id,
+                    transfers.destination_store_id,
+                    transfer_lines.supply_id,
+                    transfer_lines.destination_supply_id,
+                    transfer_lines.supply_name,
+                    transfer_lines.unit_name,
+                    transfer_lines.quantity,
+                    transfer_lines.unit_price,
+                    transfer_lines.total_price
+                FROM transfers
+                INNER JOIN transfer_lines
+                    ON transfer_lines.transfer_id = transfers.id
+                WHERE
+                    (:id IS NULL OR transfers.id = :id)
+                    AND
+                    (
+                        :store_id IS NULL
+                        OR transfers.source_store_id = :store_id
+                        OR transfers.destination_store_id = :store_id
+                    )
+                    AND
+                    (:start IS NULL OR :start <= transfers.transferred_at)
+                    AND
+                    (:end IS NULL OR transfers.transferred_at <= :end)
+                    AND
+                    (transfers.deleted_at IS NOT NULL) = :trashed
+                ORDER BY transfers.transferred_at DESC, transfers.id, transfer_lines.rowid
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let rows = statement
+            .query_map(
+                named_params! {
+                    ":id": id.map(|id| id.as_str()),
+                    ":trashed": trashed,
+                    ":store_id": store.scope().map(|id| id.as_str()),
+                    ":start": query.period_start.as_ref().map(|start| start.as_i64()),
+                    ":end": query.period_end.as_ref().map(|end| end.as_i64()),
+                },
+                transfer_row,
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|row| {
+                row.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<(Transfer, TransferLine)>>>()?;
+
+        Ok(group_transfers(rows))
+    }
+
+    /// Whether the transfer `id` from or to the stores is in the trash.
+    fn has_trashed(&self, store: &StoreContext, id: &TransferId) -> Result<bool> {
+        let transfers = self.select(
+            Some(id),
+            true,
+            store,
+            FindTransfersQuery {
+                period_start: None,
+                period_end: None,
+            },
+        )?;
+
+        Ok(!transfers.is_empty())
+    }
+}
+
+/// Reads a transfer and one of its lines from a joined row.
+fn transfer_row(row: &Row) -> rusqlite::Result<(Transfer, TransferLine)> {
+    let transfer = Transfer::restore(
+        TransferId::new(row.get::<_, i64>(0)?.to_string())?,
+        TransferredDateTime::new(row.get::<_, i64>(1)?),
+        StoreId::new(row.get::<_, i64>(2)?.to_string())?,
+        StoreId::new(row.get::<_, i64>(3)?.to_string())?,
+        vec![],
+    );
+
+    let line = TransferLine::new(
+        SupplyId::new(row.get::<_, i64>(4)?.to_string())?,
+        SupplyId::new(row.get::<_, i64>(5)?.to_string())?,
+        SupplyName::new(row.get::<_, String>(6)?)?,
+        UnitName::new(row.get::<_, String>(7)?)?,
+        TransferQuantity::from_quantity(Quantity::from_raw(row.get::<_, i64>(8)?))?,
+        TransferUnitPrice::from_money(Money::from_minor(row.get::<_, i64>(9)?))?,
+        TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(10)?))?,
+    );
+
+    Ok((transfer, line))
+}
+
+/// Groups joined rows into transfers, keeping the order of the rows.
+fn group_transfers(rows: Vec<(Transfer, TransferLine)>) -> Vec<Transfer> {
+    let mut transfers: Vec<(Transfer, Vec<TransferLine>)> = Vec::new();
+
+    for (transfer, line) in rows {
+        match transfers.iter_mut().find(|t| t.0 == transfer) {
+            Some(t) => t.1.push(line),
+            None => transfers.push((transfer, vec![line])),
+        }
+    }
+
+    transfers
+        .into_iter()
+        .map(|(transfer, lines)| {
+            Transfer::restore(
+                transfer.id().clone(),
+                transfer.transferred_at().clone(),
+                transfer.source_store_id().clone(),
+                transfer.destination_store_id().clone(),
+                lines,
+            )
+        })
+        .collect()
+}
+
+/// Inserts the lines of a transfer.
+fn insert_lines(tran: &Transaction, transfer: &Transfer) -> Result<()> {
+    let mut statement = tran
+        .prepare(
+            r"
+            INSERT INTO transfer_lines (
+                supply_id,
+                destination_supply_id,
+                supply_name,
+                unit_name,
+                quantity,
+                unit_price,
+                total_price,
+                transfer_id
+            ) VALUES (
+                :supply_id,
+                :destination_supply_id,
+                :supply_name,
+                :unit_name,
+                :quantity,
+                :unit_price,
+                :total_price,
+                :transfer_id
+            )
+            ",
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to prepare statement: {}", e)))?;
+
+    for line in transfer.lines() {
+        statement
+            .execute(named_params! {
+                ":supply_id": line.supply_id().as_str(),
+                ":destination_supply_id": line.destination_supply_id().as_str(),
+                ":supply_name": line.supply_name().as_str(),
+                ":unit_name": line.unit_name().as_str(),
+                ":quantity": line.quantity().as_quantity().as_raw(),
+                ":unit_price": line.unit_price().as_money().as_minor(),
+                ":total_price": line.total_price().as_money().as_minor(),
+                ":transfer_id": transfer.id().as_str(),
+            })
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to execute statement: {}", e))
+            })?;
+    }
+
+    Ok(())
+}
+
+/// Implementation of `ForTransferPersistence` for `SqliteTransferRepository`
+impl ForTransferPersistence for SqliteTransferRepository {
+    fn next_id(&self) -> Result<TransferId> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let next_id = conn
+            .query_row(
+                r"
+                UPDATE transfers_id_sequence
+                SET value = value + 1
+                WHERE name = 'transfer_id'
+                RETURNING value
+                ",
+                [],
+                |r| r.get::<_, i64>(0),
+            )
+            .map_err(|e| Error::InfrastructureError(format!("fail to query: {}", e)))?;
+
+        let next_id = TransferId::new(next_id.to_string())?;
+
+        Ok(next_id)
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<Transfer>> {
+        self.find(
+            store,
+            FindTransfersQuery {
+                period_start: None,
+                period_end: None,
+            },
+        )
+    }
+
+    fn find(&self, store: &StoreContext, query: FindTransfersQuery) -> Result<Vec<Transfer>> {
+        self.select(None, false, store, query)
+    }
+
+    fn get(&self, store: &StoreContext, id: TransferId) -> Result<Option<Transfer>> {
+        let transfers = self.select(
+            Some(&id),
+            false,
+            store,
+            FindTransfersQuery {
+                period_start: None,
+                period_end: None,
+            },
+        )?;
+
+        Ok(transfers.into_iter().next())
+    }
+
+    fn add(&self, transfer: Transfer) -> Result<()> {
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let tran = conn.transaction().map_err(|e| {
+            Error::InfrastructureError(format!("failed to start transaction: {}", e))
+        })?;
+
+        let result: Result<()> = (|| {
+            tran.execute(
+                r"
+                INSERT INTO transfers (
+                    id,
+                    transferred_at,
+                    source_store_id,
+                    destination_store_id
+                ) VALUES (
+                    :id,
+                    :transferred_at,
+                    :source_store_id,
+                    :destination_store_id
+                )
+                ",
+                named_params! {
+                    ":id": transfer.id().as_str(),
+                    ":transferred_at": transfer.transferred_at().as_i64(),
+                    ":source_store_id": transfer.source_store_id().as_str(),
+                    ":destination_store_id": transfer.destination_store_id().as_str(),
+                },
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            insert_lines(&tran, &transfer)
+        })();
+
+        if result.is_err() {
+            tran.rollback()
+                .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+            return result;
+        }
+
+        tran.commit()
+            .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn save(&self, store: &StoreContext, transfer: Transfer) -> Result<()> {
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let tran = conn.transaction().map_err(|e| {
+            Error::InfrastructureError(format!("failed to start transaction: {}", e))
+        })?;
+
+        let result: Result<()> = (|| {
+            let updated = tran
+                .execute(
+                    r"
+                    UPDATE transfers
+                    SET
+                        transferred_at = :transferred_at,
+                        source_store_id = :source_store_id,
+                        destination_store_id = :destination_store_id
+                    WHERE
+                        id = :id
+                        AND
+                        (
+                            :store_id IS NULL
+                            OR source_store_id = :store_id
+                            OR destination_store_id = :store_id
+                        )
+                    ",
+                    named_params! {
+                        ":id": transfer.id().as_str(),
+                        ":store_id": store.scope().map(|id| id.as_str()),
+                        ":transferred_at": transfer.transferred_at().as_i64(),
+                        ":source_store_id": transfer.source_store_id().as_str(),
+                        ":destination_store_id": transfer.destination_store_id().as_str(),
+                    },
+                )
+                .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            // a transfer between other stores is left as it is
+            if updated == 0 {
+                return Ok(());
+            }
+
+            tran.execute(
+                r"
+                DELETE FROM transfer_lines
+                WHERE transfer_id = :id
+                ",
+                named_params! {
+                    ":id": transfer.id().as_str(),
+                },
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+            insert_lines(&tran, &transfer)
+        })();
+
+        if result.is_err() {
+            tran.rollback()
+                .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+            return result;
+        }
+
+        tran.commit()
+            .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn delete(&self, store: &StoreContext, id: TransferId) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.execute(
+            r"
+            UPDATE transfers
+            SET deleted_at = :deleted_at
+            WHERE
+                id = :id
+                AND
+                (
+                    :store_id IS NULL
+                    OR source_store_id = :store_id
+                    OR destination_store_id = :store_id
+                )
+            ",
+            named_params! {
+                ":id": id.as_str(),
+                ":store_id": store.scope().map(|id| id.as_str()),
+                ":deleted_at": Utc::now().timestamp_millis(),
+            },
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<Transfer>>> {
+        let transfers = self.select(
+            None,
+            true,
+            store,
+            FindTransfersQuery {
+                period_start: None,
+                period_end: None,
+            },
+        )?;
+
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let deleted_ats = trash::deleted_ats(&conn, "transfers")?;
+
+        Ok(trash::trashed(transfers, &deleted_ats, |transfer| {
+            transfer.id().as_str()
+        }))
+    }
+
+    fn restore(&self, store: &StoreContext, id: TransferId) -> Result<()> {
+        // a transfer between other stores is left as it is
+        if !self.has_trashed(store, &id)? {
+            return Ok(());
+        }
+
+        trash::restore(&self.db_path, "transfers", id.as_str(), None, None)
+    }
+
+    fn purge(&self, store: &StoreContext, id: TransferId) -> Result<()> {
+        // a transfer between other stores is left as it is
+        if !self.has_trashed(store, &id)? {
+            return Ok(());
+        }
+
+        trash::purge(
+            &self.db_path,
+            "transfers",
+            id.as_str(),
+            None,
+            &[("transfer_lines", "transfer_id")],
+            None,
+        )
+    }
+}
@@ -0,0 +1,176 @@
+use std::fs::{self};
+use std::path::Path;
+
+use rusqlite::Connection;
+use scopeguard::defer;
+
+use crate::core::domain::entities::stock::Transfer;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::transfer::*;
+use crate::core::required_ports::*;
+use crate::persistence::sqlite::{migrate, SqliteTransferRepository};
+
+/// Builds a transfer line of `quantity` at `unit_price`.
+fn line(
+    supply_id: &str,
+    destination_supply_id: &str,
+    supply_name: &str,
+    quantity: f64,
+    unit_price: f64,
+) -> TransferLine {
+    TransferLine::new(
+        SupplyId::new(supply_id).unwrap(),
+        SupplyId::new(destination_supply_id).unwrap(),
+        SupplyName::new(supply_name).unwrap(),
+        UnitName::new("kg").unwrap(),
+        TransferQuantity::new(quantity).unwrap(),
+        TransferUnitPrice::new(unit_price).unwrap(),
+        TotalPrice::new(quantity * unit_price).unwrap(),
+    )
+}
+
+#[test]
+fn transfer_repository_test() {
+    let tmp_path = Path::new("tmp/transfer_repository_test.db");
+
+    defer! {
+        fs::remove_file(tmp_path).unwrap();
+    }
+
+    if let Some(parent) = tmp_path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+
+    migrate(tmp_path.to_string_lossy()).unwrap();
+
+    let conn = Connection::open(tmp_path).unwrap();
+
+    conn.execute(
+        "INSERT INTO stores (id, name) VALUES (2, '駅前店'), (3, '郊外店');",
+        [],
+    )
+    .unwrap();
+
+    conn.execute(
+        "INSERT INTO suppliers (id, name) VALUES (1, 'SupplierA');",
+        [],
+    )
+    .unwrap();
+
+    conn.execute(
+        "INSERT INTO supplies (id, name, unit_name, supplier_id, store_id) VALUES (1, 'SupplyA', 'kg', 1, 1), (2, 'SupplyB', 'kg', 1, 1), (11, 'SupplyA', 'kg', 1, 2), (12, 'SupplyB', 'kg', 1, 2), (21, 'SupplyA', 'kg', 1, 3);",
+        [],
+    )
+    .unwrap();
+
+    let repository = SqliteTransferRepository::new(tmp_path.to_string_lossy());
+
+    let next_id = repository.next_id().unwrap();
+
+    assert_eq!(next_id, TransferId::new("1").unwrap());
+
+    let mut transfer = Transfer::new(
+        next_id,
+        TransferredDateTime::new(1000),
+        StoreId::new("1").unwrap(),
+        StoreId::new("2").unwrap(),
+        vec![
+            line("1", "11", "SupplyA", 2.5, 400.0),
+            line("2", "12", "SupplyB", 1.0, 50.0),
+        ],
+    )
+    .unwrap();
+
+    repository.add(transfer.clone()).unwrap();
+
+    let other = Transfer::new(
+        repository.next_id().unwrap(),
+        TransferredDateTime::new(2000),
+        StoreId::new("3").unwrap(),
+        StoreId::new("1").unwrap(),
+        vec![line("21", "1", "SupplyA", 1.0, 420.0)],
+    )
+    .unwrap();
+
+    repository.add(other.clone()).unwrap();
+
+    transfer
+        .revise(
+            TransferredDateTime::new(1500),
+            StoreId::new("1").unwrap(),
+            StoreId::new("2").unwrap(),
+            vec![line("1", "11", "SupplyA", 3.0, 400.0)],
+        )
+        .unwrap();
+
+    // transfers are read and changed by the stores on both ends
+    let main = StoreContext::Store(StoreId::new("1").unwrap());
+    let branch = StoreContext::Store(StoreId::new("2").unwrap());
+    let outlet = StoreContext::Store(StoreId::new("3").unwrap());
+
+    repository.save(&outlet, transfer.clone()).unwrap();
+
+    let saved = repository
+        .get(&main, TransferId::new("1").unwrap())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(saved.transferred_at(), &TransferredDateTime::new(1000));
+
+    repository.save(&branch, transfer.clone()).unwrap();
+
+    let saved = repository
+        .get(&main, TransferId::new("1").unwrap())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(saved.transferred_at(), &TransferredDateTime::new(1500));
+    assert_eq!(saved.lines(), transfer.lines());
+
+    assert_eq!(
+        repository
+            .get(&outlet, TransferId::new("1").unwrap())
+            .unwrap(),
+        None
+    );
+
+    assert_eq!(
+        repository.list(&main).unwrap(),
+        vec![other.clone(), transfer.clone()]
+    );
+    assert_eq!(repository.list(&branch).unwrap(), vec![transfer.clone()]);
+
+    let found = repository
+        .find(
+            &StoreContext::Consolidated,
+            FindTransfersQuery {
+                period_start: Some(TransferredDateTime::new(1800)),
+                period_end: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(found, vec![other.clone()]);
+
+    repository.delete(&branch, other.id().clone()).unwrap();
+
+    assert_eq!(repository.list(&main).unwrap().len(), 2);
+
+    repository.delete(&outlet, other.id().clone()).unwrap();
+
+    assert_eq!(
+        repository.list(&StoreContext::Consolidated).unwrap(),
+        vec![transfer]
+    );
+
+    // a deleted transfer is restored and purged only by the stores on both ends
+    repository.restore(&branch, other.id().clone()).unwrap();
+    repository.purge(&branch, other.id().clone()).unwrap();
+
+    assert_eq!(repository.list_deleted(&main).unwrap().len(), 1);
+
+    repository.restore(&main, other.id().clone()).unwrap();
+
+    assert_eq!(repository.list_deleted(&main).unwrap().len(), 0);
+}
@@ -0,0 +1,217 @@
+//! This module provides helpers shared by the SQLite repositories to manage records in the trash.
+use rusqlite::types::ToSql;
+use rusqlite::Connection;
+use rusqlite::ErrorCode;
+
+use crate::core::domain::values::audit::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::trash::*;
+use crate::core::Error;
+use crate::core::Result;
+
+use super::audit;
+
+/// Reads the ids of the records of `table` in the trash with when they were deleted.
+pub(super) fn deleted_ats(
+    conn: &Connection,
+    table: &str,
+) -> Result<Vec<(String, DeletedDateTime)>> {
+    let mut statement = conn
+        .prepare(&format!(
+            r"
+            SELECT
+                id,
+                deleted_at
+            FROM {}
+            WHERE
+                deleted_at IS NOT NULL
+            ",
+            table
+        ))
+        .map_err(|e| Error::InfrastructureError(format!("failed to prepare statement: {}", e)))?;
+
+    let deleted_ats = statement
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?.to_string(),
+                DeletedDateTime::new(row.get::<_, i64>(1)?),
+            ))
+        })
+        .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+        .map(|row| {
+            row.map_err(|e| Error::InfrastructureError(format!("failed to convert rows: {}", e)))
+        })
+        .collect::<Result<Vec<(String, DeletedDateTime)>>>()?;
+
+    Ok(deleted_ats)
+}
+
+/// Pairs records read from the trash with when they were deleted, keeping their order.
+pub(super) fn trashed<T>(
+    items: Vec<T>,
+    deleted_ats: &[(String, DeletedDateTime)],
+    id: impl Fn(&T) -> &str,
+) -> Vec<Trashed<T>> {
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let deleted_at = deleted_ats
+                .iter()
+                .find(|(deleted_id, _)| deleted_id == id(&item))
+                .map(|(_, deleted_at)| *deleted_at)?;
+
+            Some(Trashed::new(item, deleted_at))
+        })
+        .collect()
+}
+
+/// Condition limiting records to the store `store_id`, none for all stores.
+fn store_condition(store_id: Option<&str>) -> &'static str {
+    match store_id {
+        Some(_) => "AND store_id = :store_id",
+        None => "",
+    }
+}
+
+/// Parameters of a statement on the record `id`, of the store `store_id` if any.
+fn id_params<'a>(
+    id: &'a &'a str,
+    store_id: &'a Option<&'a str>,
+) -> Vec<(&'static str, &'a dyn ToSql)> {
+    match store_id {
+        Some(store_id) => vec![(":id", id as &dyn ToSql), (":store_id", store_id)],
+        None => vec![(":id", id as &dyn ToSql)],
+    }
+}
+
+/// Moves a record of `table` back from the trash.
+///
+/// The record is only restored if it belongs to the store `store_id`, or any store if none.
+/// The change, if given, is logged to the audit log of its store in the same transaction.
+pub(super) fn restore(
+    db_path: &str,
+    table: &str,
+    id: &str,
+    store_id: Option<&str>,
+    change: Option<(&StoreContext, &AuditChange)>,
+) -> Result<()> {
+    let mut conn = Connection::open(db_path)
+        .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+    let tran = conn
+        .transaction()
+        .map_err(|e| Error::InfrastructureError(format!("failed to start transaction: {}", e)))?;
+
+    let result = tran
+        .execute(
+            &format!(
+                r"
+                UPDATE {}
+                SET deleted_at = NULL
+                WHERE id = :id {}
+                ",
+                table,
+                store_condition(store_id)
+            ),
+            id_params(&id, &store_id).as_slice(),
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))
+        .and_then(|_| log_change(&tran, change));
+
+    if let Err(e) = result {
+        tran.rollback()
+            .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+        return Err(e);
+    }
+
+    tran.commit()
+        .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+    Ok(())
+}
+
+/// Deletes a record of `table` in the trash permanently,
+/// with its rows in the `(table, column)` pairs of `children`.
+///
+/// The record is only purged if it belongs to the store `store_id`, or any store if none.
+/// The change, if given, is logged to the audit log of its store in the same transaction.
+pub(super) fn purge(
+    db_path: &str,
+    table: &str,
+    id: &str,
+    store_id: Option<&str>,
+    children: &[(&str, &str)],
+    change: Option<(&StoreContext, &AuditChange)>,
+) -> Result<()> {
+    let mut conn = Connection::open(db_path)
+        .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+    let tran = conn
+        .transaction()
+        .map_err(|e| Error::InfrastructureError(format!("failed to start transaction: {}", e)))?;
+
+    // only a record actually in the trash takes its rows with it
+    let result = children
+        .iter()
+        .map(|(child, column)| {
+            format!(
+                r"
+                DELETE FROM {}
+                WHERE
+                    {} = :id
+                    AND
+                    EXISTS (
+                        SELECT 1 FROM {} WHERE id = :id AND deleted_at IS NOT NULL {}
+                    )
+                ",
+                child,
+                column,
+                table,
+                store_condition(store_id)
+            )
+        })
+        .chain([format!(
+            r"
+            DELETE FROM {}
+            WHERE
+                id = :id
+                AND
+                deleted_at IS NOT NULL
+                {}
+            ",
+            table,
+            store_condition(store_id)
+        )])
+        .try_for_each(|sql| {
+            tran.execute(&sql, id_params(&id, &store_id).as_slice())
+                .map(|_| ())
+                .map_err(|e| match e.sqlite_error_code() {
+                    Some(ErrorCode::ConstraintViolation) => {
+                        Error::DomainError(format!("record is still referred to by other records."))
+                    }
+                    _ => Error::InfrastructureError(format!("failed to execute: {}", e)),
+                })
+        })
+        .and_then(|_| log_change(&tran, change));
+
+    if let Err(e) = result {
+        tran.rollback()
+            .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+        return Err(e);
+    }
+
+    tran.commit()
+        .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+    Ok(())
+}
+
+/// Logs the change, if given, to the audit log of its store.
+fn log_change(conn: &Connection, change: Option<(&StoreContext, &AuditChange)>) -> Result<()> {
+    match change {
+        Some((store, change)) => audit::insert_change(conn, store, Some(change)),
+        None => Ok(()),
+    }
+}
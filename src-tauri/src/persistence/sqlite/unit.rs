@@ -0,0 +1,363 @@
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::named_params;
+use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+
+use crate::core::domain::entities::stock::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::trash::*;
+use crate::core::domain::values::unit::*;
+use crate::core::required_ports::*;
+use crate::core::Error;
+use crate::core::Result;
+
+use super::trash;
+
+// unit repository implementation of SQLite
+pub struct SqliteUnitRepository {
+    db_path: String,
+}
+
+impl SqliteUnitRepository {
+    pub fn new(db_path: impl AsRef<str>) -> Self {
+        Self {
+            db_path: Path::new(db_path.as_ref()).to_string_lossy().to_string(),
+        }
+    }
+}
+
+/// Implementation of `ForUnitPersistence` for `SqliteUnitRepository`
+impl ForUnitPersistence for SqliteUnitRepository {
+    fn next_id(&self) -> Result<UnitId> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
+
+        let next_id = conn
+            .query_row(
+                r"
+                UPDATE units_id_sequence
+                SET value = value + 1
+                WHERE name = 'unit_id'
+                RETURNING value
+                ",
+                [],
+                |r| r.get::<_, i64>(0),
+            )
+            .map_err(|e| Error::InfrastructureError(format!("fail to query: {}", e)))?;
+
+        let next_id = UnitId::new(next_id.to_string())?;
+
+        Ok(next_id)
+    }
+
+    fn list(&self) -> Result<Vec<Unit>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    id,
+                    name
+                FROM units
+                WHERE
+                    deleted_at IS NULL
+                ORDER BY id
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let unit_results = statement
+            .query_map([], |row| {
+                let unit = Unit::restore(
+                    UnitId::new(row.get::<_, i64>(0)?.to_string())?,
+                    UnitName::new(row.get::<_, String>(1)?)?,
+                );
+
+                Ok(unit)
+            })
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
+
+        let units = unit_results
+            .map(|unit| {
+                unit.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<Unit>>>()?;
+
+        Ok(units)
+    }
+
+    fn get(&self, id: UnitId) -> Result<Option<Unit>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    id,
+                    name
+                FROM units
+                WHERE
+                    id = :id
+                    AND
+                    deleted_at IS NULL
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let unit = statement
+            .query_row(
+                named_params! {
+                    ":id": id.as_str(),
+                },
+                |row| {
+                    let unit = Unit::restore(
+                        UnitId::new(row.get::<_, i64>(0)?.to_string())?,
+                        UnitName::new(row.get::<_, String>(1)?)?,
+                    );
+
+                    Ok(unit)
+                },
+            )
+            .optional();
+
+        let unit =
+            unit.map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
+
+        Ok(unit)
+    }
+
+    fn get_by_name(&self, name: UnitName) -> Result<Option<Unit>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    id,
+                    name
+                FROM units
+                WHERE
+                    name = :name
+                    AND
+                    deleted_at IS NULL
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let unit = statement
+            .query_row(
+                named_params! {
+                    ":name": name.as_str(),
+                },
+                |row| {
+                    let unit = Unit::restore(
+                        UnitId::new(row.get::<_, i64>(0)?.to_string())?,
+                        UnitName::new(row.get::<_, String>(1)?)?,
+                    );
+
+                    Ok(unit)
+                },
+            )
+            .optional();
+
+        let unit =
+            unit.map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
+
+        Ok(unit)
+    }
+
+    fn add(&self, unit: Unit) -> Result<()> {
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
+
+        let tran = conn.transaction().map_err(|e| {
+            Error::InfrastructureError(format!("failed to start transacrion: {}", e))
+        })?;
+
+        let result = tran
+            .execute(
+                r"
+                INSERT INTO units (
+                    id,
+                    name
+                ) VALUES (
+                    :id,
+                    :name
+                )
+                ",
+                named_params! {
+                    ":id": unit.id().as_str(),
+                    ":name": unit.name().as_str(),
+                },
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to insert new unit: {}", e)));
+
+        if let Err(e) = result {
+            tran.rollback()
+                .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+            return Err(e);
+        }
+
+        tran.commit()
+            .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn save(&self, unit: Unit) -> Result<()> {
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
+
+        let tran = conn.transaction().map_err(|e| {
+            Error::InfrastructureError(format!("failed to start transacrion: {}", e))
+        })?;
+
+        // unit names are denormalized into supplies
+        let result = tran
+            .execute(
+                r"
+                UPDATE units
+                SET name = :name
+                WHERE id = :id
+                ",
+                named_params! {
+                    ":id": unit.id().as_str(),
+                    ":name": unit.name().as_str(),
+                },
+            )
+            .and_then(|_| {
+                tran.execute(
+                    r"
+                    UPDATE supplies
+                    SET unit_name = :name
+                    WHERE purchase_unit_id = :id
+                    ",
+                    named_params! {
+                        ":id": unit.id().as_str(),
+                        ":name": unit.name().as_str(),
+                    },
+                )
+            })
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to execute transaction: {}", e))
+            });
+
+        if result.is_err() {
+            tran.rollback()
+                .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+            return Err(result.err().unwrap());
+        }
+
+        tran.commit()
+            .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn delete(&self, id: UnitId) -> Result<()> {
+        let mut conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
+
+        let tran = conn.transaction().map_err(|e| {
+            Error::InfrastructureError(format!("failed to start transacrion: {}", e))
+        })?;
+
+        let result = tran
+            .execute(
+                r"
+                UPDATE units
+                SET deleted_at = :deleted_at
+                WHERE id = :id
+                ",
+                named_params! {
+                    ":id": id.as_str(),
+                    ":deleted_at": Utc::now().timestamp_millis(),
+                },
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to execute transaction: {}", e))
+            });
+
+        if result.is_err() {
+            tran.rollback()
+                .map_err(|e| Error::InfrastructureError(format!("failed to rollback: {}", e)))?;
+
+            return Err(result.err().unwrap());
+        }
+
+        tran.commit()
+            .map_err(|e| Error::InfrastructureError(format!("failed to commit: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self) -> Result<Vec<Trashed<Unit>>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("fail to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    id,
+                    name
+                FROM units
+                WHERE
+                    deleted_at IS NOT NULL
+                ORDER BY id
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let unit_results = statement
+            .query_map([], |row| {
+                let unit = Unit::restore(
+                    UnitId::new(row.get::<_, i64>(0)?.to_string())?,
+                    UnitName::new(row.get::<_, String>(1)?)?,
+                );
+
+                Ok(unit)
+            })
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?;
+
+        let units = unit_results
+            .map(|unit| {
+                unit.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<Unit>>>()?;
+
+        let deleted_ats = trash::deleted_ats(&conn, "units")?;
+
+        Ok(trash::trashed(units, &deleted_ats, |unit| {
+            unit.id().as_str()
+        }))
+    }
+
+    fn restore(&self, id: UnitId) -> Result<()> {
+        trash::restore(&self.db_path, "units", id.as_str(), None, None)
+    }
+
+    fn purge(&self, id: UnitId) -> Result<()> {
+        trash::purge(&self.db_path, "units", id.as_str(), None, &[], None)
+    }
+}
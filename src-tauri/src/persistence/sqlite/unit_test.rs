@@ -0,0 +1,68 @@
+use std::fs::{self};
+use std::path::Path;
+
+use scopeguard::defer;
+
+use crate::core::domain::entities::stock::Unit;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::unit::*;
+use crate::core::required_ports::*;
+use crate::persistence::sqlite::{migrate, SqliteUnitRepository};
+
+#[test]
+fn unit_repository_test() {
+    let tmp_path = Path::new("tmp/unit_repository_test.db");
+
+    defer! {
+        fs::remove_file(tmp_path).unwrap();
+    }
+
+    if let Some(parent) = tmp_path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+
+    migrate(tmp_path.to_string_lossy()).unwrap();
+
+    let repository = SqliteUnitRepository::new(tmp_path.to_string_lossy());
+
+    let next_id = repository.next_id().unwrap();
+
+    assert_eq!(next_id, UnitId::new("1").unwrap());
+
+    repository
+        .add(Unit::restore(
+            UnitId::new("1").unwrap(),
+            UnitName::new("bottle").unwrap(),
+        ))
+        .unwrap();
+
+    let unit = repository
+        .get_by_name(UnitName::new("bottle").unwrap())
+        .unwrap();
+
+    assert!(unit.is_some_and(|unit| {
+        assert_eq!(unit.id(), &UnitId::new("1").unwrap());
+        assert_eq!(unit.name(), &UnitName::new("bottle").unwrap());
+        true
+    }));
+
+    repository
+        .save(Unit::restore(
+            UnitId::new("1").unwrap(),
+            UnitName::new("case").unwrap(),
+        ))
+        .unwrap();
+
+    let unit = repository.get(UnitId::new("1").unwrap()).unwrap();
+
+    assert!(unit.is_some_and(|unit| {
+        assert_eq!(unit.name(), &UnitName::new("case").unwrap());
+        true
+    }));
+
+    repository.delete(UnitId::new("1").unwrap()).unwrap();
+
+    let units = repository.list().unwrap();
+
+    assert_eq!(units, vec![]);
+}
@@ -0,0 +1,272 @@
+//! This module provides the SQLite-based implementation of the `ForWastePersistence` trait.
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::named_params;
+use rusqlite::Connection;
+use rusqlite::Row;
+
+use crate::core::domain::entities::stock::WasteRecord;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::*;
+use crate::core::domain::values::trash::*;
+use crate::core::domain::values::waste::*;
+use crate::core::required_ports::*;
+use crate::core::Error;
+use crate::core::Result;
+
+use super::trash;
+
+/// SQLite implementation of `ForWastePersistence`
+pub struct SqliteWasteRepository {
+    db_path: String,
+}
+
+impl SqliteWasteRepository {
+    pub fn new(db_path: impl AsRef<str>) -> Self {
+        Self {
+            db_path: Path::new(db_path.as_ref()).to_string_lossy().to_string(),
+        }
+    }
+
+    /// Reads waste records of the stores matching the query, those in the trash if `trashed`.
+    fn select(
+        &self,
+        store: &StoreContext,
+        trashed: bool,
+        query: FindWasteQuery,
+    ) -> Result<Vec<WasteRecord>> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let mut statement = conn
+            .prepare(
+                r"
+                SELECT
+                    id,
+                    wasted_at,
+                    supply_id,
+                    supply_name,
+                    unit_name,
+                    quantity,
+                    unit_price,
+                    total_price,
+                    reason
+                FROM waste_records
+                WHERE
+                    (:start IS NULL OR :start <= wasted_at)
+                    AND
+                    (:end IS NULL OR wasted_at <= :end)
+                    AND
+                    (:supply_id IS NULL OR supply_id = :supply_id)
+                    AND
+                    (:store_id IS NULL OR store_id = :store_id)
+                    AND
+                    (deleted_at IS NOT NULL) = :trashed
+                ORDER BY wasted_at DESC, id
+                ",
+            )
+            .map_err(|e| {
+                Error::InfrastructureError(format!("failed to prepare statement: {}", e))
+            })?;
+
+        let wastes = statement
+            .query_map(
+                named_params! {
+                    ":trashed": trashed,
+                    ":start": query.period_start.as_ref().map(|start| start.as_i64()),
+                    ":end": query.period_end.as_ref().map(|end| end.as_i64()),
+                    ":supply_id": query.supply_id.as_ref().map(|id| id.as_str()),
+                    ":store_id": store.scope().map(|id| id.as_str()),
+                },
+                waste_row,
+            )
+            .map_err(|e| Error::InfrastructureError(format!("failed to query: {}", e)))?
+            .map(|row| {
+                row.map_err(|e| {
+                    Error::InfrastructureError(format!("failed to convert rows: {}", e))
+                })
+            })
+            .collect::<Result<Vec<WasteRecord>>>()?;
+
+        Ok(wastes)
+    }
+}
+
+/// Reads a waste record from a row.
+fn waste_row(row: &Row) -> rusqlite::Result<WasteRecord> {
+    let wasted = WastedSupply::restore(
+        SupplyId::new(row.get::<_, i64>(2)?.to_string())?,
+        SupplyName::new(row.get::<_, String>(3)?)?,
+        UnitName::new(row.get::<_, String>(4)?)?,
+        WasteQuantity::from_quantity(Quantity::from_raw(row.get::<_, i64>(5)?))?,
+        WasteUnitPrice::from_money(Money::from_minor(row.get::<_, i64>(6)?))?,
+        TotalPrice::from_money(Money::from_minor(row.get::<_, i64>(7)?))?,
+    );
+
+    Ok(WasteRecord::restore(
+        WasteId::new(row.get::<_, i64>(0)?.to_string())?,
+        WastedDateTime::new(row.get::<_, i64>(1)?),
+        wasted,
+        WasteReason::new(row.get::<_, String>(8)?)?,
+    ))
+}
+
+/// Implementation of `ForWastePersistence` for `SqliteWasteRepository`
+impl ForWastePersistence for SqliteWasteRepository {
+    fn next_id(&self) -> Result<WasteId> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let next_id = conn
+            .query_row(
+                r"
+                UPDATE waste_records_id_sequence
+                SET value = value + 1
+                WHERE name = 'waste_id'
+                RETURNING value
+                ",
+                [],
+                |r| r.get::<_, i64>(0),
+            )
+            .map_err(|e| Error::InfrastructureError(format!("fail to query: {}", e)))?;
+
+        let next_id = WasteId::new(next_id.to_string())?;
+
+        Ok(next_id)
+    }
+
+    fn list(&self, store: &StoreContext) -> Result<Vec<WasteRecord>> {
+        self.find(
+            store,
+            FindWasteQuery {
+                period_start: None,
+                period_end: None,
+                supply_id: None,
+            },
+        )
+    }
+
+    fn find(&self, store: &StoreContext, query: FindWasteQuery) -> Result<Vec<WasteRecord>> {
+        self.select(store, false, query)
+    }
+
+    fn add(&self, store: &StoreContext, waste: WasteRecord) -> Result<()> {
+        let store_id = store.store_id()?;
+
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let wasted = waste.wasted();
+
+        conn.execute(
+            r"
+            INSERT INTO waste_records (
+                id,
+                wasted_at,
+                supply_id,
+                supply_name,
+                unit_name,
+                quantity,
+                unit_price,
+                total_price,
+                reason,
+                store_id
+            ) VALUES (
+                :id,
+                :wasted_at,
+                :supply_id,
+                :supply_name,
+                :unit_name,
+                :quantity,
+                :unit_price,
+                :total_price,
+                :reason,
+                :store_id
+            )
+            ",
+            named_params! {
+                ":id": waste.id().as_str(),
+                ":wasted_at": waste.wasted_at().as_i64(),
+                ":supply_id": wasted.supply_id().as_str(),
+                ":supply_name": wasted.supply_name().as_str(),
+                ":unit_name": wasted.unit_name().as_str(),
+                ":quantity": wasted.quantity().as_quantity().as_raw(),
+                ":unit_price": wasted.unit_price().as_money().as_minor(),
+                ":total_price": wasted.total_price().as_money().as_minor(),
+                ":reason": waste.reason().as_str(),
+                ":store_id": store_id.as_str(),
+            },
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn delete(&self, store: &StoreContext, id: WasteId) -> Result<()> {
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        conn.execute(
+            r"
+            UPDATE waste_records
+            SET deleted_at = :deleted_at
+            WHERE
+                id = :id
+                AND
+                (:store_id IS NULL OR store_id = :store_id)
+            ",
+            named_params! {
+                ":id": id.as_str(),
+                ":deleted_at": Utc::now().timestamp_millis(),
+                ":store_id": store.scope().map(|id| id.as_str()),
+            },
+        )
+        .map_err(|e| Error::InfrastructureError(format!("failed to execute: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn list_deleted(&self, store: &StoreContext) -> Result<Vec<Trashed<WasteRecord>>> {
+        let wastes = self.select(
+            store,
+            true,
+            FindWasteQuery {
+                period_start: None,
+                period_end: None,
+                supply_id: None,
+            },
+        )?;
+
+        let conn = Connection::open(&self.db_path)
+            .map_err(|e| Error::InfrastructureError(format!("failed to open connection: {}", e)))?;
+
+        let deleted_ats = trash::deleted_ats(&conn, "waste_records")?;
+
+        Ok(trash::trashed(wastes, &deleted_ats, |waste| {
+            waste.id().as_str()
+        }))
+    }
+
+    fn restore(&self, store: &StoreContext, id: WasteId) -> Result<()> {
+        trash::restore(
+            &self.db_path,
+            "waste_records",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            None,
+        )
+    }
+
+    fn purge(&self, store: &StoreContext, id: WasteId) -> Result<()> {
+        trash::purge(
+            &self.db_path,
+            "waste_records",
+            id.as_str(),
+            store.scope().map(|id| id.as_str()),
+            &[],
+            None,
+        )
+    }
+}
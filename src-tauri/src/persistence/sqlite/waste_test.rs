@@ -0,0 +1,136 @@
+use std::fs::{self};
+use std::path::Path;
+
+use rusqlite::Connection;
+use scopeguard::defer;
+
+use crate::core::domain::entities::stock::WasteRecord;
+use crate::core::domain::values::decimal::*;
+use crate::core::domain::values::stock::*;
+use crate::core::domain::values::store::{StoreContext, StoreId};
+use crate::core::domain::values::waste::*;
+use crate::core::required_ports::*;
+use crate::persistence::sqlite::{migrate, SqliteWasteRepository};
+
+#[test]
+fn waste_repository_test() {
+    let tmp_path = Path::new("tmp/waste_repository_test.db");
+
+    defer! {
+        fs::remove_file(tmp_path).unwrap();
+    }
+
+    if let Some(parent) = tmp_path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+
+    migrate(tmp_path.to_string_lossy()).unwrap();
+
+    let conn = Connection::open(tmp_path).unwrap();
+
+    conn.execute(
+        "INSERT INTO suppliers (id, name) VALUES (1, 'SupplierA');",
+        [],
+    )
+    .unwrap();
+
+    conn.execute(
+        "INSERT INTO supplies (id, name, unit_name, supplier_id) VALUES (1, 'SupplyA', 'kg', 1), (2, 'SupplyB', 'pc', 1);",
+        [],
+    )
+    .unwrap();
+
+    let repository = SqliteWasteRepository::new(tmp_path.to_string_lossy());
+
+    let next_id = repository.next_id().unwrap();
+
+    assert_eq!(next_id, WasteId::new("1").unwrap());
+
+    let policy = RoundingPolicy::default();
+
+    let expired = WasteRecord::restore(
+        WasteId::new("1").unwrap(),
+        WastedDateTime::new(100000),
+        WastedSupply::new(
+            SupplyId::new("1").unwrap(),
+            SupplyName::new("SupplyA").unwrap(),
+            UnitName::new("kg").unwrap(),
+            WasteQuantity::new(1.5).unwrap(),
+            WasteUnitPrice::new(120.5).unwrap(),
+            &policy,
+        )
+        .unwrap(),
+        WasteReason::Expired,
+    );
+
+    let damaged = WasteRecord::restore(
+        WasteId::new("2").unwrap(),
+        WastedDateTime::new(200000),
+        WastedSupply::new(
+            SupplyId::new("2").unwrap(),
+            SupplyName::new("SupplyB").unwrap(),
+            UnitName::new("pc").unwrap(),
+            WasteQuantity::new(3_u32).unwrap(),
+            WasteUnitPrice::new(80_u32).unwrap(),
+            &policy,
+        )
+        .unwrap(),
+        WasteReason::Damaged,
+    );
+
+    repository
+        .add(&StoreContext::default(), expired.clone())
+        .unwrap();
+    repository
+        .add(&StoreContext::default(), damaged.clone())
+        .unwrap();
+
+    let wastes = repository
+        .find(
+            &StoreContext::default(),
+            FindWasteQuery {
+                period_start: Some(WastedDateTime::new(50000)),
+                period_end: Some(WastedDateTime::new(150000)),
+                supply_id: None,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(wastes.len(), 1);
+    assert_eq!(wastes[0].wasted_at(), &WastedDateTime::new(100000));
+    assert_eq!(wastes[0].wasted(), expired.wasted());
+    assert_eq!(wastes[0].reason(), WasteReason::Expired);
+
+    let wastes = repository
+        .find(
+            &StoreContext::default(),
+            FindWasteQuery {
+                period_start: None,
+                period_end: None,
+                supply_id: Some(SupplyId::new("2").unwrap()),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(wastes, vec![damaged.clone()]);
+    assert_eq!(wastes[0].wasted().total_price().as_f64(), 240.0);
+
+    // waste records of another store are neither read nor deleted
+    let branch = StoreContext::Store(StoreId::new("2").unwrap());
+
+    assert!(repository.list(&branch).unwrap().is_empty());
+
+    repository
+        .delete(&branch, WasteId::new("1").unwrap())
+        .unwrap();
+
+    assert_eq!(repository.list(&StoreContext::default()).unwrap().len(), 2);
+
+    repository
+        .delete(&StoreContext::default(), WasteId::new("1").unwrap())
+        .unwrap();
+
+    let wastes = repository.list(&StoreContext::default()).unwrap();
+
+    assert_eq!(wastes, vec![damaged]);
+}